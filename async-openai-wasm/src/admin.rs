@@ -0,0 +1,406 @@
+//! [Administration API](https://platform.openai.com/docs/api-reference/administration) managing the users, invites
+//! and projects of an organization programmatically. Its endpoints can only be called with an admin API key.
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), async_openai_wasm::error::OpenAIError> {
+//! use async_openai_wasm::{
+//!     config::OpenAIConfig,
+//!     types::{ProjectCreateRequestArgs, ProjectServiceAccountCreateRequestArgs},
+//!     Client,
+//! };
+//!
+//! let client = Client::with_config(OpenAIConfig::new().with_api_key("sk-admin-..."));
+//! let project = client
+//!     .admin()
+//!     .projects()
+//!     .create(ProjectCreateRequestArgs::default().name("Staging").build()?)
+//!     .await?;
+//! let account = client
+//!     .admin()
+//!     .project_service_accounts(&project.id)
+//!     .create(ProjectServiceAccountCreateRequestArgs::default().name("deploy").build()?)
+//!     .await?;
+//! println!("{}", account.api_key.value);
+//! # Ok(())
+//! # }
+//! ```
+use serde::Serialize;
+
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    pagination::Paginator,
+    types::{
+        Invite, InviteDeleteResponse, InviteListResponse, InviteRequest, Project, ProjectApiKey,
+        ProjectApiKeyDeleteResponse, ProjectApiKeyListResponse, ProjectCreateRequest,
+        ProjectListResponse, ProjectServiceAccount, ProjectServiceAccountCreateRequest,
+        ProjectServiceAccountCreateResponse, ProjectServiceAccountDeleteResponse,
+        ProjectServiceAccountListResponse, ProjectUpdateRequest, User, UserDeleteResponse,
+        UserListResponse, UserRoleUpdateRequest,
+    },
+    Client,
+};
+
+/// Manage the organization: its users, invites, projects and their API keys and service accounts.
+pub struct Admin<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Admin<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    /// [Users] of the organization
+    pub fn users(&self) -> Users<'c, C> {
+        Users::new(self.client)
+    }
+
+    /// [Invites] to the organization
+    pub fn invites(&self) -> Invites<'c, C> {
+        Invites::new(self.client)
+    }
+
+    /// [Projects] of the organization
+    pub fn projects(&self) -> Projects<'c, C> {
+        Projects::new(self.client)
+    }
+
+    /// [ProjectApiKeys] of the project `project_id`
+    pub fn project_api_keys(&self, project_id: &str) -> ProjectApiKeys<'c, C> {
+        ProjectApiKeys::new(self.client, project_id)
+    }
+
+    /// [ProjectServiceAccounts] of the project `project_id`
+    pub fn project_service_accounts(&self, project_id: &str) -> ProjectServiceAccounts<'c, C> {
+        ProjectServiceAccounts::new(self.client, project_id)
+    }
+}
+
+/// Manage users and their role in an organization. Users are added to it by [Invites].
+pub struct Users<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Users<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    /// Lists all of the users in the organization.
+    pub async fn list<Q>(&self, query: &Q) -> Result<UserListResponse, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.client
+            .get_with_query("/organization/users", query)
+            .await
+    }
+
+    /// Returns all the users in the organization, fetching them page by page as the stream is consumed.
+    pub fn list_auto_paginated<Q>(&self, query: &Q) -> Paginator<'c, User>
+    where
+        C: Sync,
+        Q: Serialize + ?Sized,
+    {
+        Paginator::new::<_, UserListResponse, _>(self.client, "/organization/users".into(), query)
+    }
+
+    /// Retrieves a user by their identifier.
+    pub async fn retrieve(&self, user_id: &str) -> Result<User, OpenAIError> {
+        self.client
+            .get(&format!("/organization/users/{user_id}"))
+            .await
+    }
+
+    /// Modifies a user's role in the organization.
+    pub async fn modify(
+        &self,
+        user_id: &str,
+        request: UserRoleUpdateRequest,
+    ) -> Result<User, OpenAIError> {
+        self.client
+            .post(&format!("/organization/users/{user_id}"), request)
+            .await
+    }
+
+    /// Deletes a user from the organization.
+    pub async fn delete(&self, user_id: &str) -> Result<UserDeleteResponse, OpenAIError> {
+        self.client
+            .delete(&format!("/organization/users/{user_id}"))
+            .await
+    }
+}
+
+/// Invite and manage invitations for an organization. Invited users are automatically added to the Default project.
+pub struct Invites<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Invites<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    /// Returns a list of invites in the organization.
+    pub async fn list<Q>(&self, query: &Q) -> Result<InviteListResponse, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.client
+            .get_with_query("/organization/invites", query)
+            .await
+    }
+
+    /// Returns all the invites in the organization, fetching them page by page as the stream is consumed.
+    pub fn list_auto_paginated<Q>(&self, query: &Q) -> Paginator<'c, Invite>
+    where
+        C: Sync,
+        Q: Serialize + ?Sized,
+    {
+        Paginator::new::<_, InviteListResponse, _>(
+            self.client,
+            "/organization/invites".into(),
+            query,
+        )
+    }
+
+    /// Create an invite for a user to the organization. The invite must be accepted by the user before they have access to the organization.
+    pub async fn create(&self, request: InviteRequest) -> Result<Invite, OpenAIError> {
+        self.client.post("/organization/invites", request).await
+    }
+
+    /// Retrieves an invite.
+    pub async fn retrieve(&self, invite_id: &str) -> Result<Invite, OpenAIError> {
+        self.client
+            .get(&format!("/organization/invites/{invite_id}"))
+            .await
+    }
+
+    /// Delete an invite. If the invite has already been accepted, it cannot be deleted.
+    pub async fn delete(&self, invite_id: &str) -> Result<InviteDeleteResponse, OpenAIError> {
+        self.client
+            .delete(&format!("/organization/invites/{invite_id}"))
+            .await
+    }
+}
+
+/// Manage the projects within an organization, including creation, updating, and archiving of projects.
+/// The Default project cannot be archived.
+pub struct Projects<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Projects<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    /// Returns a list of projects. Archived projects are only listed with `include_archived=true`.
+    pub async fn list<Q>(&self, query: &Q) -> Result<ProjectListResponse, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.client
+            .get_with_query("/organization/projects", query)
+            .await
+    }
+
+    /// Returns all the projects, fetching them page by page as the stream is consumed.
+    pub fn list_auto_paginated<Q>(&self, query: &Q) -> Paginator<'c, Project>
+    where
+        C: Sync,
+        Q: Serialize + ?Sized,
+    {
+        Paginator::new::<_, ProjectListResponse, _>(
+            self.client,
+            "/organization/projects".into(),
+            query,
+        )
+    }
+
+    /// Create a new project in the organization. Projects can be created and archived, but cannot be deleted.
+    pub async fn create(&self, request: ProjectCreateRequest) -> Result<Project, OpenAIError> {
+        self.client.post("/organization/projects", request).await
+    }
+
+    /// Retrieves a project.
+    pub async fn retrieve(&self, project_id: &str) -> Result<Project, OpenAIError> {
+        self.client
+            .get(&format!("/organization/projects/{project_id}"))
+            .await
+    }
+
+    /// Modifies a project in the organization.
+    pub async fn modify(
+        &self,
+        project_id: &str,
+        request: ProjectUpdateRequest,
+    ) -> Result<Project, OpenAIError> {
+        self.client
+            .post(&format!("/organization/projects/{project_id}"), request)
+            .await
+    }
+
+    /// Archives a project in the organization. Archived projects cannot be used or updated.
+    pub async fn archive(&self, project_id: &str) -> Result<Project, OpenAIError> {
+        self.client
+            .post(
+                &format!("/organization/projects/{project_id}/archive"),
+                serde_json::json!({}),
+            )
+            .await
+    }
+}
+
+/// Manage API keys for a given project. Keys are created in the dashboard, or for [ProjectServiceAccounts] when creating them.
+pub struct ProjectApiKeys<'c, C: Config> {
+    client: &'c Client<C>,
+    pub project_id: String,
+}
+
+impl<'c, C: Config> ProjectApiKeys<'c, C> {
+    pub fn new(client: &'c Client<C>, project_id: &str) -> Self {
+        Self {
+            client,
+            project_id: project_id.into(),
+        }
+    }
+
+    /// Returns a list of API keys in the project.
+    pub async fn list<Q>(&self, query: &Q) -> Result<ProjectApiKeyListResponse, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.client
+            .get_with_query(
+                &format!("/organization/projects/{}/api_keys", self.project_id),
+                query,
+            )
+            .await
+    }
+
+    /// Returns all the API keys in the project, fetching them page by page as the stream is consumed.
+    pub fn list_auto_paginated<Q>(&self, query: &Q) -> Paginator<'c, ProjectApiKey>
+    where
+        C: Sync,
+        Q: Serialize + ?Sized,
+    {
+        Paginator::new::<_, ProjectApiKeyListResponse, _>(
+            self.client,
+            format!("/organization/projects/{}/api_keys", self.project_id),
+            query,
+        )
+    }
+
+    /// Retrieves an API key in the project.
+    pub async fn retrieve(&self, key_id: &str) -> Result<ProjectApiKey, OpenAIError> {
+        self.client
+            .get(&format!(
+                "/organization/projects/{}/api_keys/{key_id}",
+                self.project_id
+            ))
+            .await
+    }
+
+    /// Deletes an API key from the project.
+    pub async fn delete(&self, key_id: &str) -> Result<ProjectApiKeyDeleteResponse, OpenAIError> {
+        self.client
+            .delete(&format!(
+                "/organization/projects/{}/api_keys/{key_id}",
+                self.project_id
+            ))
+            .await
+    }
+}
+
+/// Manage service accounts within a project. A service account is a bot user that is not associated with a user.
+/// If a user leaves an organization, their keys and membership in projects will no longer work.
+/// Service accounts do not have this limitation.
+pub struct ProjectServiceAccounts<'c, C: Config> {
+    client: &'c Client<C>,
+    pub project_id: String,
+}
+
+impl<'c, C: Config> ProjectServiceAccounts<'c, C> {
+    pub fn new(client: &'c Client<C>, project_id: &str) -> Self {
+        Self {
+            client,
+            project_id: project_id.into(),
+        }
+    }
+
+    /// Returns a list of service accounts in the project.
+    pub async fn list<Q>(&self, query: &Q) -> Result<ProjectServiceAccountListResponse, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.client
+            .get_with_query(
+                &format!(
+                    "/organization/projects/{}/service_accounts",
+                    self.project_id
+                ),
+                query,
+            )
+            .await
+    }
+
+    /// Returns all the service accounts in the project, fetching them page by page as the stream is consumed.
+    pub fn list_auto_paginated<Q>(&self, query: &Q) -> Paginator<'c, ProjectServiceAccount>
+    where
+        C: Sync,
+        Q: Serialize + ?Sized,
+    {
+        Paginator::new::<_, ProjectServiceAccountListResponse, _>(
+            self.client,
+            format!(
+                "/organization/projects/{}/service_accounts",
+                self.project_id
+            ),
+            query,
+        )
+    }
+
+    /// Creates a new service account in the project. This also returns an unredacted API key for the service account.
+    pub async fn create(
+        &self,
+        request: ProjectServiceAccountCreateRequest,
+    ) -> Result<ProjectServiceAccountCreateResponse, OpenAIError> {
+        self.client
+            .post(
+                &format!(
+                    "/organization/projects/{}/service_accounts",
+                    self.project_id
+                ),
+                request,
+            )
+            .await
+    }
+
+    /// Retrieves a service account in the project.
+    pub async fn retrieve(
+        &self,
+        service_account_id: &str,
+    ) -> Result<ProjectServiceAccount, OpenAIError> {
+        self.client
+            .get(&format!(
+                "/organization/projects/{}/service_accounts/{service_account_id}",
+                self.project_id
+            ))
+            .await
+    }
+
+    /// Deletes a service account from the project.
+    pub async fn delete(
+        &self,
+        service_account_id: &str,
+    ) -> Result<ProjectServiceAccountDeleteResponse, OpenAIError> {
+        self.client
+            .delete(&format!(
+                "/organization/projects/{}/service_accounts/{service_account_id}",
+                self.project_id
+            ))
+            .await
+    }
+}
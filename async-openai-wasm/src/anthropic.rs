@@ -0,0 +1,776 @@
+//! Compatibility layer calling the [Anthropic Messages API](https://docs.anthropic.com/en/api/messages)
+//! with chat completion requests, so that the same code path can target both OpenAI and Anthropic.
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), async_openai_wasm::error::OpenAIError> {
+//! use async_openai_wasm::{
+//!     config::AnthropicConfig,
+//!     types::{ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs},
+//!     Client,
+//! };
+//!
+//! let client = Client::with_config(AnthropicConfig::new());
+//! let request = CreateChatCompletionRequestArgs::default()
+//!     .model("claude-3-5-sonnet-latest")
+//!     .max_tokens(512_u16)
+//!     .messages([ChatCompletionRequestUserMessageArgs::default()
+//!         .content("Hello!")
+//!         .build()?
+//!         .into()])
+//!     .build()?;
+//!
+//! let response = client.anthropic().create(request).await?;
+//! println!("{}", response.text());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The translation functions and types are public too, to be used with other transports.
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::Stream;
+use pin_project::pin_project;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::Instrument;
+
+use crate::{
+    client::OpenAIEventStream,
+    config::Config,
+    error::{ApiError, OpenAIError},
+    telemetry,
+    time::unix_timestamp,
+    types::{
+        ChatChoice, ChatChoiceStream, ChatCompletionMessageToolCall,
+        ChatCompletionMessageToolCallChunk, ChatCompletionRequestMessage,
+        ChatCompletionRequestMessageContentPart, ChatCompletionRequestUserMessageContent,
+        ChatCompletionResponseMessage, ChatCompletionStreamResponseDelta,
+        ChatCompletionToolChoiceOption, ChatCompletionToolType, CompletionUsage,
+        CreateChatCompletionRequest, CreateChatCompletionResponse,
+        CreateChatCompletionStreamResponse, FinishReason, FunctionCall, FunctionCallStream,
+        PromptTokensDetails, Role, Stop,
+    },
+    Client,
+};
+
+/// `max_tokens` of translated requests which set neither `max_tokens` nor `max_completion_tokens`, as Anthropic requires it
+pub const DEFAULT_MAX_TOKENS: u32 = 4096;
+
+/// Request of the Messages API
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MessagesRequest {
+    pub model: String,
+    pub messages: Vec<Message>,
+    pub max_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_k: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_sequences: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<Tool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<ToolChoice>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageRole {
+    User,
+    Assistant,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Message {
+    pub role: MessageRole,
+    pub content: Vec<ContentBlock>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentBlock {
+    Text {
+        text: String,
+    },
+    Image {
+        source: ImageSource,
+    },
+    /// PDF document, with the same sources as images
+    Document {
+        source: ImageSource,
+    },
+    ToolUse {
+        id: String,
+        name: String,
+        input: Value,
+    },
+    ToolResult {
+        tool_use_id: String,
+        content: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        is_error: Option<bool>,
+    },
+    Thinking {
+        thinking: String,
+        #[serde(default)]
+        signature: String,
+    },
+    /// Block of a type unknown to this crate
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ImageSource {
+    Base64 { media_type: String, data: String },
+    Url { url: String },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Tool {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub input_schema: Value,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ToolChoice {
+    Auto {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        disable_parallel_tool_use: Option<bool>,
+    },
+    Any {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        disable_parallel_tool_use: Option<bool>,
+    },
+    Tool {
+        name: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        disable_parallel_tool_use: Option<bool>,
+    },
+    None,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Metadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+}
+
+/// Response of the Messages API
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MessagesResponse {
+    pub id: String,
+    pub role: MessageRole,
+    pub content: Vec<ContentBlock>,
+    pub model: String,
+    pub stop_reason: Option<StopReason>,
+    pub stop_sequence: Option<String>,
+    #[serde(default)]
+    pub usage: Usage,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum StopReason {
+    EndTurn,
+    MaxTokens,
+    StopSequence,
+    ToolUse,
+    PauseTurn,
+    Refusal,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+pub struct Usage {
+    #[serde(default)]
+    pub input_tokens: u32,
+    #[serde(default)]
+    pub output_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_creation_input_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_read_input_tokens: Option<u32>,
+}
+
+/// Server-sent event of a streamed Messages API response
+#[derive(Debug, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StreamEvent {
+    MessageStart {
+        message: MessagesResponse,
+    },
+    ContentBlockStart {
+        index: u32,
+        content_block: ContentBlock,
+    },
+    ContentBlockDelta {
+        index: u32,
+        delta: ContentBlockDelta,
+    },
+    ContentBlockStop {
+        index: u32,
+    },
+    MessageDelta {
+        delta: MessageDelta,
+        #[serde(default)]
+        usage: Option<Usage>,
+    },
+    MessageStop,
+    Ping,
+    Error {
+        error: ApiError,
+    },
+    /// Event of a type unknown to this crate
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentBlockDelta {
+    TextDelta {
+        text: String,
+    },
+    InputJsonDelta {
+        partial_json: String,
+    },
+    ThinkingDelta {
+        thinking: String,
+    },
+    SignatureDelta {
+        signature: String,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct MessageDelta {
+    pub stop_reason: Option<StopReason>,
+    pub stop_sequence: Option<String>,
+}
+
+impl TryFrom<CreateChatCompletionRequest> for MessagesRequest {
+    type Error = OpenAIError;
+
+    /// System messages become the `system` prompt, tool messages `tool_result` blocks of user messages
+    /// and tool calls `tool_use` blocks. Legacy functions are migrated to tools first.
+    ///
+    /// Fails on parameters without an Anthropic equivalent changing the response shape, like `n` > 1.
+    fn try_from(mut request: CreateChatCompletionRequest) -> Result<Self, Self::Error> {
+        if matches!(request.n, Some(n) if n > 1) {
+            return Err(OpenAIError::InvalidArgument(
+                "Anthropic does not support n > 1".into(),
+            ));
+        }
+        if request.uses_legacy_functions() {
+            request.migrate_functions_to_tools();
+        }
+
+        let mut system: Vec<String> = vec![];
+        let mut messages: Vec<Message> = vec![];
+        for message in request.messages {
+            let (role, content) = match message {
+                ChatCompletionRequestMessage::Developer(message) => {
+                    system.push(message.content);
+                    continue;
+                }
+                ChatCompletionRequestMessage::System(message) => {
+                    system.push(message.content);
+                    continue;
+                }
+                ChatCompletionRequestMessage::User(message) => {
+                    (MessageRole::User, user_content(message.content)?)
+                }
+                ChatCompletionRequestMessage::Assistant(message) => {
+                    let mut content: Vec<ContentBlock> = message
+                        .content
+                        .filter(|text| !text.is_empty())
+                        .map(|text| ContentBlock::Text { text })
+                        .into_iter()
+                        .collect();
+                    for tool_call in message.tool_calls.into_iter().flatten() {
+                        let input =
+                            serde_json::from_str(&tool_call.function.arguments).map_err(|e| {
+                                OpenAIError::InvalidArgument(format!(
+                                    "arguments of tool call {} are not valid JSON: {e}",
+                                    tool_call.id
+                                ))
+                            })?;
+                        content.push(ContentBlock::ToolUse {
+                            id: tool_call.id,
+                            name: tool_call.function.name,
+                            input,
+                        });
+                    }
+                    (MessageRole::Assistant, content)
+                }
+                ChatCompletionRequestMessage::Tool(message) => (
+                    MessageRole::User,
+                    vec![ContentBlock::ToolResult {
+                        tool_use_id: message.tool_call_id,
+                        content: message.content,
+                        is_error: None,
+                    }],
+                ),
+                ChatCompletionRequestMessage::Function(_) => unreachable!("migrated to tools"),
+            };
+            // roles must alternate, e.g. results of parallel tool calls go in a single message
+            match messages.last_mut() {
+                Some(last) if last.role == role => last.content.extend(content),
+                _ => messages.push(Message { role, content }),
+            }
+        }
+
+        let disable_parallel_tool_use = request.parallel_tool_calls.map(|parallel| !parallel);
+        let tool_choice = match request.tool_choice {
+            Some(ChatCompletionToolChoiceOption::None) => Some(ToolChoice::None),
+            Some(ChatCompletionToolChoiceOption::Auto) => Some(ToolChoice::Auto {
+                disable_parallel_tool_use,
+            }),
+            Some(ChatCompletionToolChoiceOption::Required) => Some(ToolChoice::Any {
+                disable_parallel_tool_use,
+            }),
+            Some(ChatCompletionToolChoiceOption::Named(named)) => Some(ToolChoice::Tool {
+                name: named.function.name,
+                disable_parallel_tool_use,
+            }),
+            None => disable_parallel_tool_use.map(|_| ToolChoice::Auto {
+                disable_parallel_tool_use,
+            }),
+        };
+
+        Ok(Self {
+            model: request.model,
+            messages,
+            max_tokens: request
+                .max_tokens
+                .map(u32::from)
+                .or(request.max_completion_tokens)
+                .unwrap_or(DEFAULT_MAX_TOKENS),
+            system: (!system.is_empty()).then(|| system.join("\n\n")),
+            temperature: request.temperature,
+            top_p: request.top_p,
+            top_k: None,
+            stop_sequences: request.stop.map(|stop| match stop {
+                Stop::String(stop) => vec![stop],
+                Stop::StringArray(stops) => stops,
+            }),
+            stream: request.stream,
+            tools: request.tools.map(|tools| {
+                tools
+                    .into_iter()
+                    .map(|tool| Tool {
+                        name: tool.function.name,
+                        description: tool.function.description,
+                        input_schema: tool
+                            .function
+                            .parameters
+                            .unwrap_or_else(|| serde_json::json!({"type": "object"})),
+                    })
+                    .collect()
+            }),
+            tool_choice,
+            metadata: request.user.map(|user| Metadata {
+                user_id: Some(user),
+            }),
+        })
+    }
+}
+
+fn user_content(
+    content: ChatCompletionRequestUserMessageContent,
+) -> Result<Vec<ContentBlock>, OpenAIError> {
+    match content {
+        ChatCompletionRequestUserMessageContent::Text(text) => Ok(vec![ContentBlock::Text { text }]),
+        ChatCompletionRequestUserMessageContent::Array(parts) => parts
+            .into_iter()
+            .map(|part| match part {
+                ChatCompletionRequestMessageContentPart::Text(part) => {
+                    Ok(ContentBlock::Text { text: part.text })
+                }
+                ChatCompletionRequestMessageContentPart::ImageUrl(part) => Ok(ContentBlock::Image {
+                    source: image_source(part.image_url.url),
+                }),
+                ChatCompletionRequestMessageContentPart::InputAudio(_) => Err(
+                    OpenAIError::InvalidArgument("Anthropic does not support audio inputs".into()),
+                ),
+                ChatCompletionRequestMessageContentPart::File(part) => match part.file.file_data {
+                    Some(file_data) => Ok(ContentBlock::Document {
+                        source: image_source(file_data),
+                    }),
+                    None => Err(OpenAIError::InvalidArgument(
+                        "Anthropic does not support OpenAI file IDs, pass the file data instead"
+                            .into(),
+                    )),
+                },
+            })
+            .collect(),
+    }
+}
+
+/// Base64 source of a `data:` URL, URL source otherwise
+fn image_source(url: String) -> ImageSource {
+    let data = url
+        .strip_prefix("data:")
+        .and_then(|data| data.split_once(";base64,"));
+    match data {
+        Some((media_type, data)) => ImageSource::Base64 {
+            media_type: media_type.to_string(),
+            data: data.to_string(),
+        },
+        None => ImageSource::Url { url },
+    }
+}
+
+impl From<StopReason> for FinishReason {
+    fn from(value: StopReason) -> Self {
+        match value {
+            StopReason::MaxTokens => FinishReason::Length,
+            StopReason::ToolUse => FinishReason::ToolCalls,
+            StopReason::Refusal => FinishReason::ContentFilter,
+            StopReason::EndTurn
+            | StopReason::StopSequence
+            | StopReason::PauseTurn
+            | StopReason::Unknown => FinishReason::Stop,
+        }
+    }
+}
+
+impl From<Usage> for CompletionUsage {
+    /// Cached input tokens are counted as prompt tokens
+    fn from(value: Usage) -> Self {
+        let prompt_tokens = value.input_tokens
+            + value.cache_creation_input_tokens.unwrap_or_default()
+            + value.cache_read_input_tokens.unwrap_or_default();
+        CompletionUsage {
+            prompt_tokens,
+            completion_tokens: value.output_tokens,
+            total_tokens: prompt_tokens + value.output_tokens,
+            prompt_tokens_details: value
+                .cache_read_input_tokens
+                .map(|cached_tokens| PromptTokensDetails {
+                    cached_tokens: Some(cached_tokens),
+                    ..Default::default()
+                }),
+            completion_tokens_details: None,
+        }
+    }
+}
+
+impl From<MessagesResponse> for CreateChatCompletionResponse {
+    /// Text blocks are concatenated into the content of the only choice and `tool_use` blocks become tool calls,
+    /// other blocks such as thinking are dropped.
+    fn from(response: MessagesResponse) -> Self {
+        let mut text: Option<String> = None;
+        let mut tool_calls = vec![];
+        for block in response.content {
+            match block {
+                ContentBlock::Text { text: block } => {
+                    text.get_or_insert_with(String::new).push_str(&block)
+                }
+                ContentBlock::ToolUse { id, name, input } => {
+                    tool_calls.push(ChatCompletionMessageToolCall {
+                        id,
+                        r#type: ChatCompletionToolType::Function,
+                        function: FunctionCall {
+                            name,
+                            arguments: input.to_string(),
+                        },
+                    })
+                }
+                _ => {}
+            }
+        }
+
+        #[allow(deprecated)]
+        let message = ChatCompletionResponseMessage {
+            content: text,
+            tool_calls: (!tool_calls.is_empty()).then_some(tool_calls),
+            role: Role::Assistant,
+            function_call: None,
+            audio: None,
+            annotations: None,
+        };
+        CreateChatCompletionResponse {
+            id: response.id,
+            choices: vec![ChatChoice {
+                index: 0,
+                message,
+                finish_reason: response.stop_reason.map(Into::into),
+                logprobs: None,
+            }],
+            created: unix_timestamp(),
+            model: response.model,
+            system_fingerprint: None,
+            service_tier: None,
+            object: "chat.completion".into(),
+            usage: Some(response.usage.into()),
+            metadata: None,
+        }
+    }
+}
+
+/// Translates the events of a streamed Messages API response into chat completion chunks.
+#[derive(Debug, Clone, Default)]
+pub struct ChatStreamTranslator {
+    id: String,
+    model: String,
+    created: u32,
+    usage: Usage,
+    /// Index of the tool call of each `tool_use` block, by block index
+    tool_calls: HashMap<u32, i32>,
+}
+
+impl ChatStreamTranslator {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The chunk `event` translates to, if any. Error events are returned as [OpenAIError::ApiError].
+    pub fn translate(
+        &mut self,
+        event: StreamEvent,
+    ) -> Result<Option<CreateChatCompletionStreamResponse>, OpenAIError> {
+        let chunk = match event {
+            StreamEvent::MessageStart { message } => {
+                self.id = message.id;
+                self.model = message.model;
+                self.created = unix_timestamp();
+                self.usage = message.usage;
+                Some(self.chunk(
+                    ChatCompletionStreamResponseDelta {
+                        role: Some(Role::Assistant),
+                        ..delta()
+                    },
+                    None,
+                ))
+            }
+            StreamEvent::ContentBlockStart {
+                index,
+                content_block,
+            } => match content_block {
+                ContentBlock::Text { text } if !text.is_empty() => Some(self.chunk(
+                    ChatCompletionStreamResponseDelta {
+                        content: Some(text),
+                        ..delta()
+                    },
+                    None,
+                )),
+                ContentBlock::ToolUse { id, name, .. } => {
+                    let tool_index = self.tool_calls.len() as i32;
+                    self.tool_calls.insert(index, tool_index);
+                    Some(self.chunk(
+                        ChatCompletionStreamResponseDelta {
+                            tool_calls: Some(vec![ChatCompletionMessageToolCallChunk {
+                                index: tool_index,
+                                id: Some(id),
+                                r#type: Some(ChatCompletionToolType::Function),
+                                function: Some(FunctionCallStream {
+                                    name: Some(name),
+                                    arguments: Some(String::new()),
+                                }),
+                            }]),
+                            ..delta()
+                        },
+                        None,
+                    ))
+                }
+                _ => None,
+            },
+            StreamEvent::ContentBlockDelta {
+                index,
+                delta: block,
+            } => match block {
+                ContentBlockDelta::TextDelta { text } => Some(self.chunk(
+                    ChatCompletionStreamResponseDelta {
+                        content: Some(text),
+                        ..delta()
+                    },
+                    None,
+                )),
+                ContentBlockDelta::InputJsonDelta { partial_json } => {
+                    let tool_index = self.tool_calls.get(&index).copied().unwrap_or_default();
+                    Some(self.chunk(
+                        ChatCompletionStreamResponseDelta {
+                            tool_calls: Some(vec![ChatCompletionMessageToolCallChunk {
+                                index: tool_index,
+                                id: None,
+                                r#type: None,
+                                function: Some(FunctionCallStream {
+                                    name: None,
+                                    arguments: Some(partial_json),
+                                }),
+                            }]),
+                            ..delta()
+                        },
+                        None,
+                    ))
+                }
+                _ => None,
+            },
+            StreamEvent::MessageDelta {
+                delta: message_delta,
+                usage,
+            } => {
+                if let Some(usage) = usage {
+                    self.usage.output_tokens = usage.output_tokens;
+                }
+                let mut chunk = self.chunk(delta(), message_delta.stop_reason.map(Into::into));
+                chunk.usage = Some(self.usage.into());
+                Some(chunk)
+            }
+            StreamEvent::Error { error } => return Err(OpenAIError::ApiError(error)),
+            StreamEvent::ContentBlockStop { .. }
+            | StreamEvent::MessageStop
+            | StreamEvent::Ping
+            | StreamEvent::Unknown => None,
+        };
+        Ok(chunk)
+    }
+
+    fn chunk(
+        &self,
+        delta: ChatCompletionStreamResponseDelta,
+        finish_reason: Option<FinishReason>,
+    ) -> CreateChatCompletionStreamResponse {
+        CreateChatCompletionStreamResponse {
+            id: self.id.clone(),
+            choices: vec![ChatChoiceStream {
+                index: 0,
+                delta,
+                finish_reason,
+                logprobs: None,
+            }],
+            created: self.created,
+            model: self.model.clone(),
+            system_fingerprint: None,
+            service_tier: None,
+            object: "chat.completion.chunk".into(),
+            usage: None,
+        }
+    }
+}
+
+#[allow(deprecated)]
+fn delta() -> ChatCompletionStreamResponseDelta {
+    ChatCompletionStreamResponseDelta {
+        content: None,
+        function_call: None,
+        tool_calls: None,
+        role: None,
+    }
+}
+
+/// Streamed Messages API response, as chat completion chunks
+#[pin_project]
+pub struct ChatCompletionStream {
+    #[pin]
+    events: OpenAIEventStream<StreamEvent>,
+    translator: ChatStreamTranslator,
+}
+
+impl Stream for ChatCompletionStream {
+    type Item = Result<CreateChatCompletionStreamResponse, OpenAIError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            let event = match this.events.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(event))) => event,
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            };
+            match this.translator.translate(event) {
+                Ok(Some(chunk)) => return Poll::Ready(Some(Ok(chunk))),
+                Ok(None) => continue,
+                Err(e) => return Poll::Ready(Some(Err(e))),
+            }
+        }
+    }
+}
+
+/// Chat completions through the Anthropic Messages API, to be used with [AnthropicConfig](crate::config::AnthropicConfig).
+///
+/// Related guide: [OpenAI SDK compatibility](https://docs.anthropic.com/en/api/openai-sdk)
+pub struct Anthropic<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Anthropic<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    /// Creates a model response for the given chat conversation, as translated by [MessagesRequest::try_from].
+    pub async fn create(
+        &self,
+        mut request: CreateChatCompletionRequest,
+    ) -> Result<CreateChatCompletionResponse, OpenAIError> {
+        if request.stream == Some(true) {
+            return Err(OpenAIError::InvalidArgument(
+                "When stream is true, use Anthropic::create_stream".into(),
+            ));
+        }
+        self.client.inject_default_user(&mut request.user);
+        let span = telemetry::span(self.client.config(), "chat", &request);
+        let request = MessagesRequest::try_from(request)?;
+        telemetry::instrument(span, async {
+            Ok(self.create_message(request).await?.into())
+        })
+        .await
+    }
+
+    /// Creates a streamed model response for the given chat conversation, as chat completion chunks.
+    ///
+    /// The chunk with the finish reason also has the usage of the request.
+    pub async fn create_stream(
+        &self,
+        mut request: CreateChatCompletionRequest,
+    ) -> Result<ChatCompletionStream, OpenAIError> {
+        if request.stream == Some(false) {
+            return Err(OpenAIError::InvalidArgument(
+                "When stream is false, use Anthropic::create".into(),
+            ));
+        }
+        request.stream = Some(true);
+        self.client.inject_default_user(&mut request.user);
+        let span = telemetry::span(self.client.config(), "chat", &request);
+        let request = MessagesRequest::try_from(request)?;
+
+        Ok(ChatCompletionStream {
+            events: self
+                .client
+                .post_stream("/messages", request)
+                .instrument(span)
+                .await?,
+            translator: ChatStreamTranslator::new(),
+        })
+    }
+
+    /// Send a Messages API request as is.
+    pub async fn create_message(
+        &self,
+        request: MessagesRequest,
+    ) -> Result<MessagesResponse, OpenAIError> {
+        self.client.post("/messages", request).await
+    }
+}
@@ -11,11 +11,16 @@ use crate::{
 };
 
 /// Files attached to an assistant.
+///
+/// Deprecated with the v1 Assistants API, which the `OpenAI-Beta: assistants=v2` header sent by [OpenAIConfig](crate::config::OpenAIConfig)
+/// no longer targets: attach files with the `tool_resources` of the assistant instead.
+#[deprecated]
 pub struct AssistantFiles<'c, C: Config> {
     client: &'c Client<C>,
     pub assistant_id: String,
 }
 
+#[allow(deprecated)]
 impl<'c, C: Config> AssistantFiles<'c, C> {
     pub fn new(client: &'c Client<C>, assistant_id: &str) -> Self {
         Self {
@@ -1,12 +1,17 @@
+use std::time::Duration;
+
 use serde::Serialize;
 
+#[allow(deprecated)]
+use crate::AssistantFiles;
 use crate::{
-    AssistantFiles,
     Client,
     config::Config,
-    error::OpenAIError, types::{
-        AssistantObject, CreateAssistantRequest, DeleteAssistantResponse, ListAssistantsResponse,
-        ModifyAssistantRequest,
+    error::OpenAIError,
+    pagination::Paginator, types::{
+        AssistantObject, AssistantTools, CreateAssistantRequest, CreateAssistantToolResources,
+        CreateFileRequest, CreateVectorStoreRequest, DeleteAssistantResponse, FileInput,
+        FilePurpose, ListAssistantsResponse, ModifyAssistantRequest,
     },
 };
 
@@ -23,6 +28,10 @@ impl<'c, C: Config> Assistants<'c, C> {
     }
 
     /// Assistant [AssistantFiles] API group
+    ///
+    /// Deprecated in favor of the `tool_resources` of [CreateAssistantRequest] and [ModifyAssistantRequest].
+    #[deprecated]
+    #[allow(deprecated)]
     pub fn files(&self, assistant_id: &str) -> AssistantFiles<C> {
         AssistantFiles::new(self.client, assistant_id)
     }
@@ -67,4 +76,71 @@ impl<'c, C: Config> Assistants<'c, C> {
     {
         self.client.get_with_query("/assistants", query).await
     }
+
+    /// Returns all the assistants, fetching them page by page as the stream is consumed.
+    pub fn list_auto_paginated<Q>(&self, query: &Q) -> Paginator<'c, AssistantObject>
+    where
+        C: Sync,
+        Q: Serialize + ?Sized,
+    {
+        Paginator::new::<_, ListAssistantsResponse, _>(self.client, "/assistants".into(), query)
+    }
+
+    /// Create an assistant searching `files`: they are uploaded, added to a new vector store named after the assistant,
+    /// and once the vector store has processed them it is set as the `file_search` resource of the assistant,
+    /// whose tools get `file_search` if they do not have it yet.
+    ///
+    /// The vector store is polled like [VectorStores::create_and_poll](crate::VectorStores::create_and_poll),
+    /// and the assistant is created even if some files fail to be processed, with a warning.
+    pub async fn create_with_file_search(
+        &self,
+        mut request: CreateAssistantRequest,
+        files: Vec<FileInput>,
+        poll_interval: Duration,
+        timeout: Option<Duration>,
+    ) -> Result<AssistantObject, OpenAIError> {
+        let files_api = self.client.files();
+        let uploads = files.into_iter().map(|file| {
+            files_api.create(CreateFileRequest {
+                file,
+                purpose: FilePurpose::Assistants,
+            })
+        });
+        let file_ids = futures::future::try_join_all(uploads)
+            .await?
+            .into_iter()
+            .map(|file| file.id)
+            .collect();
+        let vector_store = self
+            .client
+            .vector_stores()
+            .create_and_poll(
+                CreateVectorStoreRequest {
+                    file_ids: Some(file_ids),
+                    name: request.name.clone(),
+                    ..Default::default()
+                },
+                poll_interval,
+                timeout,
+            )
+            .await?;
+        let counts = &vector_store.file_counts;
+        if counts.failed > 0 || counts.cancelled > 0 || counts.in_progress > 0 {
+            tracing::warn!(
+                "Vector store {} has {} of {} files processed, {} failed, {} cancelled and {} in progress",
+                vector_store.id, counts.completed, counts.total, counts.failed, counts.cancelled, counts.in_progress
+            );
+        }
+
+        let tool_resources = match request.tool_resources.take() {
+            Some(tool_resources) => tool_resources.with_file_search([&vector_store.id]),
+            None => CreateAssistantToolResources::file_search([&vector_store.id]),
+        };
+        request.tool_resources = Some(tool_resources);
+        let tools = request.tools.get_or_insert_with(Vec::new);
+        if !tools.iter().any(|tool| matches!(tool, AssistantTools::FileSearch(_))) {
+            tools.push(AssistantTools::FileSearch(Default::default()));
+        }
+        self.create(request).await
+    }
 }
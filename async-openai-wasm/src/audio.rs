@@ -5,7 +5,7 @@ use crate::{
     config::Config,
     error::OpenAIError,
     types::{
-        CreateSpeechRequest, CreateSpeechResponse, CreateTranscriptionRequest,
+        ByteStream, CreateSpeechRequest, CreateSpeechResponse, CreateTranscriptionRequest,
         CreateTranscriptionResponseJson, CreateTranscriptionResponseVerboseJson,
         CreateTranslationRequest, CreateTranslationResponseJson,
         CreateTranslationResponseVerboseJson,
@@ -88,4 +88,15 @@ impl<'c, C: Config> Audio<'c, C> {
 
         Ok(CreateSpeechResponse { bytes })
     }
+
+    /// Generates audio from the input text, streaming it as it is generated
+    /// so that playback can start before the whole audio is received.
+    ///
+    /// Use a `response_format` decodable in chunks, such as `mp3`, `aac`, `opus` or `pcm`.
+    pub async fn create_speech_byte_stream(
+        &self,
+        request: CreateSpeechRequest,
+    ) -> Result<ByteStream, OpenAIError> {
+        self.client.post_byte_stream("/audio/speech", request).await
+    }
 }
@@ -4,6 +4,7 @@ use crate::{
     Client,
     config::Config,
     error::OpenAIError,
+    pagination::Paginator,
     types::{Batch, BatchRequest, ListBatchesResponse},
 };
 
@@ -32,6 +33,15 @@ impl<'c, C: Config> Batches<'c, C> {
         self.client.get_with_query("/batches", query).await
     }
 
+    /// Returns all your organization's batches, fetching them page by page as the stream is consumed.
+    pub fn list_auto_paginated<Q>(&self, query: &Q) -> Paginator<'c, Batch>
+    where
+        C: Sync,
+        Q: Serialize + ?Sized,
+    {
+        Paginator::new::<_, ListBatchesResponse, _>(self.client, "/batches".into(), query)
+    }
+
     /// Retrieves a batch.
     pub async fn retrieve(&self, batch_id: &str) -> Result<Batch, OpenAIError> {
         self.client.get(&format!("/batches/{batch_id}")).await
@@ -0,0 +1,239 @@
+//! Cancellation of API calls and of the helpers chaining them, such as tool loops and chunked uploads,
+//! with a [CancellationToken] set on the client with [Client::with_cancellation](crate::Client::with_cancellation).
+//!
+//! Once the token is cancelled, requests in flight are aborted, retries, rate limiting and polling stop waiting,
+//! streams end, and calls fail with [OpenAIError::Cancelled]. Helpers leaving state on the server clean it up:
+//! [Runs::create_with_tools](crate::Runs::create_with_tools) cancels its run and
+//! [Uploads::upload_file_chunked](crate::Uploads::upload_file_chunked) its upload.
+//!
+//! ```
+//! # tokio_test::block_on(async {
+//! use async_openai_wasm::{cancel::CancellationToken, error::OpenAIError, Client};
+//!
+//! let token = CancellationToken::new();
+//! let client = Client::new().with_cancellation(token.clone());
+//!
+//! // e.g. when the user closes the page the answer was for
+//! token.cancel();
+//! let result = client.models().list().await;
+//! assert!(matches!(result, Err(OpenAIError::Cancelled)));
+//! # });
+//! ```
+//!
+//! Single calls can be given a token of their own too, cancelled along with the one of the client, with
+//! [RequestOptions::with_cancellation](crate::RequestOptions::with_cancellation) or the `create*_with_cancel`
+//! methods, e.g. [Chat::create_stream_with_cancel](crate::Chat::create_stream_with_cancel) for the stop button of a UI.
+//!
+//! Dropping the future of a call cancels it too, but leaves the state of helpers on the server.
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, Weak};
+use std::task::{Context, Poll, Waker};
+
+use futures::{Stream, StreamExt};
+
+use crate::error::OpenAIError;
+
+/// Flag shared by clones of a token, waking the tasks waiting on it once raised.
+///
+/// Tokens are cheap to clone, and [CancellationToken::child_token] derives tokens cancelled along with their parent,
+/// e.g. one per helper call of a job to cancel them one by one or all at once.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    cancelled: bool,
+    /// Wakers of the pending [WaitForCancellation] futures, by their key, removed when they are dropped
+    wakers: BTreeMap<usize, Waker>,
+    next_waiter: usize,
+    children: Vec<Weak<Inner>>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Token cancelled when this one is, which can be cancelled on its own too
+    pub fn child_token(&self) -> Self {
+        let child = Self::new();
+        self.adopt(&child);
+        child
+    }
+
+    /// Cancel `child` when this token is, `child` possibly having other parents
+    pub(crate) fn adopt(&self, child: &CancellationToken) {
+        let mut state = self.inner.state.lock().unwrap();
+        if state.cancelled {
+            child.cancel();
+        } else {
+            state.children.retain(|child| child.strong_count() > 0);
+            state.children.push(Arc::downgrade(&child.inner));
+        }
+    }
+
+    /// Cancel this token, its clones and its children. Cancelling a cancelled token does nothing.
+    pub fn cancel(&self) {
+        Inner::cancel(&self.inner);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.state.lock().unwrap().cancelled
+    }
+
+    /// Future completing once the token is cancelled
+    pub fn cancelled(&self) -> WaitForCancellation {
+        WaitForCancellation {
+            token: self.clone(),
+            waiter: None,
+        }
+    }
+
+    /// Run `future` until the token is cancelled, failing with [OpenAIError::Cancelled] then.
+    pub async fn run<F: Future>(&self, future: F) -> Result<F::Output, OpenAIError> {
+        if self.is_cancelled() {
+            return Err(OpenAIError::Cancelled);
+        }
+        futures::pin_mut!(future);
+        match futures::future::select(future, self.cancelled()).await {
+            futures::future::Either::Left((output, _)) => Ok(output),
+            futures::future::Either::Right(_) => Err(OpenAIError::Cancelled),
+        }
+    }
+}
+
+impl Inner {
+    fn cancel(inner: &Arc<Inner>) {
+        let (wakers, children) = {
+            let mut state = inner.state.lock().unwrap();
+            if state.cancelled {
+                return;
+            }
+            state.cancelled = true;
+            (
+                std::mem::take(&mut state.wakers),
+                std::mem::take(&mut state.children),
+            )
+        };
+        wakers.into_values().for_each(Waker::wake);
+        for child in children.iter().filter_map(Weak::upgrade) {
+            Inner::cancel(&child);
+        }
+    }
+}
+
+/// Future returned by [CancellationToken::cancelled]
+#[derive(Debug)]
+pub struct WaitForCancellation {
+    token: CancellationToken,
+    /// Key of the waker of this future in the state of the token, once polled
+    waiter: Option<usize>,
+}
+
+impl Future for WaitForCancellation {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let mut state = this.token.inner.state.lock().unwrap();
+        if state.cancelled {
+            return Poll::Ready(());
+        }
+        let waiter = *this.waiter.get_or_insert_with(|| {
+            state.next_waiter += 1;
+            state.next_waiter
+        });
+        match state.wakers.get_mut(&waiter) {
+            Some(waker) if waker.will_wake(cx.waker()) => {}
+            Some(waker) => *waker = cx.waker().clone(),
+            None => {
+                state.wakers.insert(waiter, cx.waker().clone());
+            }
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for WaitForCancellation {
+    fn drop(&mut self) {
+        if let Some(waiter) = self.waiter {
+            if let Ok(mut state) = self.token.inner.state.lock() {
+                state.wakers.remove(&waiter);
+            }
+        }
+    }
+}
+
+/// `stream` until `token` is cancelled, ending with [OpenAIError::Cancelled] then
+pub(crate) fn until_cancelled<T, S>(
+    stream: S,
+    token: CancellationToken,
+) -> impl Stream<Item = Result<T, OpenAIError>>
+where
+    S: Stream<Item = Result<T, OpenAIError>> + Unpin,
+{
+    futures::stream::unfold(Some((stream, token)), |state| async move {
+        let (mut stream, token) = state?;
+        match token.run(stream.next()).await {
+            Ok(Some(item)) => Some((item, Some((stream, token)))),
+            Ok(None) => None,
+            Err(e) => Some((Err(e), None)),
+        }
+    })
+}
+
+/// Whether `cancelled`, if any, is done, registering the waker of `cx` otherwise
+pub(crate) fn poll_cancelled(
+    cancelled: &mut Option<WaitForCancellation>,
+    cx: &mut Context<'_>,
+) -> bool {
+    match cancelled {
+        Some(cancelled) => Pin::new(cancelled).poll(cx).is_ready(),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn waiters(token: &CancellationToken) -> usize {
+        token.inner.state.lock().unwrap().wakers.len()
+    }
+
+    #[test]
+    fn dropped_waits_unregister_their_waker() {
+        let token = CancellationToken::new();
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+        let mut waits: Vec<_> = (0..1000).map(|_| token.cancelled()).collect();
+        for wait in &mut waits {
+            assert!(Pin::new(&mut *wait).poll(&mut cx).is_pending());
+            assert!(Pin::new(&mut *wait).poll(&mut cx).is_pending());
+        }
+        assert_eq!(waiters(&token), 1000);
+
+        drop(waits);
+        assert_eq!(waiters(&token), 0);
+    }
+
+    #[test]
+    fn cancelling_wakes_pending_waits() {
+        let token = CancellationToken::new();
+        let mut wait = token.cancelled();
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+        assert!(Pin::new(&mut wait).poll(&mut cx).is_pending());
+
+        token.cancel();
+        assert_eq!(waiters(&token), 0);
+        assert!(Pin::new(&mut wait).poll(&mut cx).is_ready());
+    }
+}
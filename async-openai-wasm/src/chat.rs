@@ -1,12 +1,34 @@
+use futures::{stream, StreamExt, TryStreamExt};
+use serde::{de::DeserializeOwned, Serialize};
+use tracing::Instrument;
+
 use crate::{
-    Client,
+    Client, RequestOptions,
+    cancel::CancellationToken,
     config::Config,
-    error::OpenAIError,
+    content_filter::{self, ContentFilterOutcome, FilterReason, FilteredAttempt, Sanitizer},
+    error::{ApiErrorKind, OpenAIError},
+    json_mode,
+    pagination::Paginator,
+    retry::StreamRetryPolicy,
+    sampling::{self, Aggregator, Consensus},
+    session::count_tokens,
+    telemetry,
+    time::sleep,
+    tools::ToolRegistry,
     types::{
-        ChatCompletionResponseStream, CreateChatCompletionRequest, CreateChatCompletionResponse
+        ChatCompletionDeleted, ChatCompletionList, ChatCompletionMessageList,
+        ChatCompletionRequestAssistantMessageArgs, ChatCompletionRequestUserMessage,
+        ChatCompletionResponseStream, ChatCompletionStoreMessage, ChatCompletionStreamExt,
+        ChatCompletionStreamOptions, ChatCompletionUsageStream,
+        CreateChatCompletionRequest, CreateChatCompletionResponse, CreateChatCompletionStreamResponse,
+        ResumableChatCompletionStream, UpdateChatCompletionRequest,
     },
 };
 
+/// Budget of prompt tokens of the reduce requests of [Chat::map_reduce]
+const DEFAULT_REDUCE_TOKEN_BUDGET: usize = 16_000;
+
 /// Given a list of messages comprising a conversation, the model will return a response.
 ///
 /// Related guide: [Chat completions](https://platform.openai.com//docs/guides/text-generation)
@@ -22,14 +44,24 @@ impl<'c, C: Config> Chat<'c, C> {
     /// Creates a model response for the given chat conversation.
     pub async fn create(
         &self,
-        request: CreateChatCompletionRequest,
+        mut request: CreateChatCompletionRequest,
     ) -> Result<CreateChatCompletionResponse, OpenAIError> {
         if request.stream.is_some() && request.stream.unwrap() {
             return Err(OpenAIError::InvalidArgument(
                 "When stream is true, use Chat::create_stream".into(),
             ));
         }
-        self.client.post("/chat/completions", request).await
+        self.client.inject_default_user(&mut request.user);
+        migrate_max_tokens(&mut request);
+        let migrated = self.client.migrate_legacy_functions(&mut request);
+        self.client.validate_chat_request(&request)?;
+        let span = telemetry::span(self.client.config(), "chat", &request);
+        let mut response: CreateChatCompletionResponse =
+            telemetry::instrument(span, self.client.post("/chat/completions", request)).await?;
+        if migrated {
+            response.restore_function_calls();
+        }
+        Ok(response)
     }
 
 
@@ -49,7 +81,593 @@ impl<'c, C: Config> Chat<'c, C> {
         }
 
         request.stream = Some(true);
+        self.client.inject_default_user(&mut request.user);
+        migrate_max_tokens(&mut request);
+        self.client.migrate_legacy_functions(&mut request);
+        self.client.validate_chat_request(&request)?;
+
+        let span = telemetry::span(self.client.config(), "chat", &request);
+        self.client.post_stream("/chat/completions", request).instrument(span).await
+    }
+
+    /// Creates a model response like [Chat::create], with the timeout, headers and cancellation of `options`
+    pub async fn create_with_options(
+        &self,
+        request: CreateChatCompletionRequest,
+        options: &RequestOptions,
+    ) -> Result<CreateChatCompletionResponse, OpenAIError> {
+        let client = self.client.with_request_options(options);
+        Chat::new(&client).create(request).await
+    }
+
+    /// Creates a completion stream like [Chat::create_stream], with the timeout, headers and cancellation of `options`,
+    /// the timeout being the one between events
+    pub async fn create_stream_with_options(
+        &self,
+        request: CreateChatCompletionRequest,
+        options: &RequestOptions,
+    ) -> Result<ChatCompletionResponseStream, OpenAIError> {
+        let client = self.client.with_request_options(options);
+        Chat::new(&client).create_stream(request).await
+    }
+
+    /// Creates a model response like [Chat::create], aborted once `token` is cancelled, e.g. by the stop button of a UI
+    pub async fn create_with_cancel(
+        &self,
+        request: CreateChatCompletionRequest,
+        token: &CancellationToken,
+    ) -> Result<CreateChatCompletionResponse, OpenAIError> {
+        let options = RequestOptions::new().with_cancellation(token.clone());
+        self.create_with_options(request, &options).await
+    }
+
+    /// Creates a completion stream like [Chat::create_stream], ending with [OpenAIError::Cancelled]
+    /// once `token` is cancelled, which aborts the request and stops reading its body
+    pub async fn create_stream_with_cancel(
+        &self,
+        request: CreateChatCompletionRequest,
+        token: &CancellationToken,
+    ) -> Result<ChatCompletionResponseStream, OpenAIError> {
+        let options = RequestOptions::new().with_cancellation(token.clone());
+        self.create_stream_with_options(request, &options).await
+    }
+
+    /// Creates a completion for the chat message like [Chat::create_stream], with `stream_options.include_usage` set
+    /// so that the usage of the request is streamed last and kept by the returned stream once it is done.
+    pub async fn create_stream_with_usage(
+        &self,
+        mut request: CreateChatCompletionRequest,
+    ) -> Result<ChatCompletionUsageStream<ChatCompletionResponseStream>, OpenAIError> {
+        request.stream_options = Some(ChatCompletionStreamOptions {
+            include_usage: true,
+        });
+        Ok(self.create_stream(request).await?.track_usage())
+    }
+
+    /// Creates a completion stream like [Chat::create_stream], resumed as `policy` says when it fails or ends
+    /// before a `finish_reason`, e.g. on a network blip, by requesting the rest of the answer after an assistant
+    /// message holding the content received so far.
+    ///
+    /// Only single answers of text are resumed: a stream with `n` greater than 1, tool calls or function calls
+    /// fails like [Chat::create_stream] does. A resumed stream is a new request, billed as such.
+    pub fn create_stream_resumable(
+        &self,
+        request: CreateChatCompletionRequest,
+        policy: StreamRetryPolicy,
+    ) -> ResumableChatCompletionStream<'c>
+    where
+        C: Sync,
+    {
+        let client = self.client;
+        let state = ResumeState {
+            resumable: request.n.map_or(true, |n| n <= 1),
+            request,
+            policy,
+            stream: None,
+            content: String::new(),
+            resumes: 0,
+            finished: false,
+        };
+        let chunks = stream::unfold(Some(state), move |state| async move {
+            let mut state = state?;
+            loop {
+                // failures to connect are resumed like failures of the stream, the network may still be down
+                let error = match state.stream.take() {
+                    None => match Chat::new(client).create_stream(state.continuation()).await {
+                        Ok(stream) => {
+                            state.stream = Some(stream);
+                            continue;
+                        }
+                        Err(e) => e,
+                    },
+                    Some(mut stream) => match stream.next().await {
+                        Some(Ok(chunk)) => {
+                            state.track(&chunk);
+                            state.stream = Some(stream);
+                            return Some((Ok(chunk), Some(state)));
+                        }
+                        None if state.finished => return None,
+                        None => OpenAIError::StreamError {
+                            message: "stream ended before the answer was complete".into(),
+                            source: None,
+                        },
+                        Some(Err(e)) => e,
+                    },
+                };
+                let recoverable = matches!(
+                    error,
+                    OpenAIError::StreamError { .. }
+                        | OpenAIError::Reqwest(_)
+                        | OpenAIError::Timeout(_)
+                        | OpenAIError::StreamStalled(_)
+                ) || matches!(
+                    &error,
+                    OpenAIError::ApiError(e) if matches!(e.kind(), ApiErrorKind::Server | ApiErrorKind::RateLimit)
+                );
+                if !(recoverable && state.resumable && state.resumes < state.policy.max_resumes) {
+                    return Some((Err(error), None));
+                }
+                state.resumes += 1;
+                let backoff = state.policy.backoff(state.resumes);
+                tracing::warn!(
+                    "resuming chat completion stream in {backoff:?} ({}/{}): {error}",
+                    state.resumes,
+                    state.policy.max_resumes
+                );
+                let backoff = client.cancellable(async {
+                    sleep(backoff).await;
+                    Ok(())
+                });
+                if let Err(e) = backoff.await {
+                    return Some((Err(e), None));
+                }
+            }
+        });
+        ResumableChatCompletionStream::new(Box::pin(chunks))
+    }
+
+    /// List stored chat completions, the ones created with `store` set to `true`.
+    pub async fn list<Q>(&self, query: &Q) -> Result<ChatCompletionList, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.client.get_with_query("/chat/completions", query).await
+    }
+
+    /// Returns all the stored chat completions, fetching them page by page as the stream is consumed.
+    pub fn list_auto_paginated<Q>(&self, query: &Q) -> Paginator<'c, CreateChatCompletionResponse>
+    where
+        C: Sync,
+        Q: Serialize + ?Sized,
+    {
+        Paginator::new::<_, ChatCompletionList, _>(self.client, "/chat/completions".into(), query)
+    }
+
+    /// Get a stored chat completion.
+    pub async fn retrieve(
+        &self,
+        completion_id: &str,
+    ) -> Result<CreateChatCompletionResponse, OpenAIError> {
+        self.client
+            .get(&format!("/chat/completions/{completion_id}"))
+            .await
+    }
+
+    /// Get the messages of a stored chat completion.
+    pub async fn messages<Q>(
+        &self,
+        completion_id: &str,
+        query: &Q,
+    ) -> Result<ChatCompletionMessageList, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.client
+            .get_with_query(&format!("/chat/completions/{completion_id}/messages"), query)
+            .await
+    }
+
+    /// Returns all the messages of a stored chat completion, fetching them page by page as the stream is consumed.
+    pub fn messages_auto_paginated<Q>(
+        &self,
+        completion_id: &str,
+        query: &Q,
+    ) -> Paginator<'c, ChatCompletionStoreMessage>
+    where
+        C: Sync,
+        Q: Serialize + ?Sized,
+    {
+        Paginator::new::<_, ChatCompletionMessageList, _>(
+            self.client,
+            format!("/chat/completions/{completion_id}/messages"),
+            query,
+        )
+    }
+
+    /// Modify the metadata of a stored chat completion.
+    pub async fn update(
+        &self,
+        completion_id: &str,
+        request: UpdateChatCompletionRequest,
+    ) -> Result<CreateChatCompletionResponse, OpenAIError> {
+        self.client
+            .post(&format!("/chat/completions/{completion_id}"), request)
+            .await
+    }
+
+    /// Delete a stored chat completion.
+    pub async fn delete(&self, completion_id: &str) -> Result<ChatCompletionDeleted, OpenAIError> {
+        self.client
+            .delete(&format!("/chat/completions/{completion_id}"))
+            .await
+    }
+
+    /// Creates a model response in JSON mode, parsed into `T`.
+    ///
+    /// `response_format` is set to `json_object`, and [JSON_INSTRUCTION](json_mode::JSON_INSTRUCTION)
+    /// is injected unless a message mentions JSON, as the API requires.
+    /// Fails with [OpenAIError::InvalidJsonResponse] if the content does not parse, e.g. when cut off by `max_tokens`.
+    pub async fn create_json<T: DeserializeOwned>(
+        &self,
+        mut request: CreateChatCompletionRequest,
+    ) -> Result<T, OpenAIError> {
+        json_mode::enforce_json_mode(&mut request);
+        let response = self.create(request).await?;
+        json_mode::parse_json(&response)
+    }
+
+    /// Creates a model response, calling the functions of `registry` requested by the model
+    /// and sending their outputs back, until the model answers without calling any.
+    ///
+    /// The functions of `registry` are added to the `tools` of the request.
+    /// Fails if the model still calls functions after [ToolRegistry::max_rounds] round trips.
+    pub async fn create_with_tools(
+        &self,
+        mut request: CreateChatCompletionRequest,
+        registry: &ToolRegistry,
+    ) -> Result<CreateChatCompletionResponse, OpenAIError> {
+        let tools = request.tools.get_or_insert_with(Vec::new);
+        tools.retain(|tool| !registry.contains(&tool.function.name));
+        tools.extend(registry.tools());
+
+        for round in 0..=registry.max_rounds() {
+            let response = self.create(request.clone()).await?;
+            let tool_calls = match response.choices.first() {
+                Some(choice) => choice.message.tool_calls.clone().unwrap_or_default(),
+                None => vec![],
+            };
+            if tool_calls.is_empty() {
+                return Ok(response);
+            }
+            // the outputs of the tools would not be sent, so they are not called for their side effects
+            if round == registry.max_rounds() {
+                break;
+            }
+
+            let mut assistant_message = ChatCompletionRequestAssistantMessageArgs::default();
+            assistant_message.tool_calls(tool_calls.clone());
+            if let Some(content) = response.first_text() {
+                assistant_message.content(content);
+            }
+            request.messages.push(assistant_message.build()?.into());
+            for tool_call in &tool_calls {
+                let message = self
+                    .client
+                    .cancellable(async { Ok(registry.call(tool_call).await) })
+                    .await?;
+                request.messages.push(message.into());
+            }
+        }
+
+        Err(OpenAIError::InvalidArgument(format!(
+            "the model kept calling tools after {} rounds; raise ToolRegistry::with_max_rounds or check the tool outputs",
+            registry.max_rounds()
+        )))
+    }
+
+    /// Creates a model response, sending the request again sanitized by `sanitizer`, up to `max_retries` times,
+    /// while a content filter rejects its prompt or stops its completion.
+    ///
+    /// The outcome lists the filtered attempts. Its response is the last one received,
+    /// whose completion is still filtered if all retries were; a rejected prompt fails once retries are exhausted.
+    pub async fn create_with_content_filter_fallback(
+        &self,
+        mut request: CreateChatCompletionRequest,
+        sanitizer: &Sanitizer,
+        max_retries: usize,
+    ) -> Result<ContentFilterOutcome, OpenAIError> {
+        let mut filtered = vec![];
+        loop {
+            let (reason, result) = match self.create(request.clone()).await {
+                Ok(response) if content_filter::completion_filtered(&response) => {
+                    (FilterReason::Completion, Ok(response))
+                }
+                Ok(response) => {
+                    return Ok(ContentFilterOutcome {
+                        response,
+                        request,
+                        filtered,
+                    })
+                }
+                Err(e) => match content_filter::prompt_filtered(&e) {
+                    Some(message) => (FilterReason::Prompt(message), Err(e)),
+                    None => return Err(e),
+                },
+            };
+            if filtered.len() == max_retries {
+                return result.map(|response| ContentFilterOutcome {
+                    response,
+                    request,
+                    filtered,
+                });
+            }
+            tracing::warn!("Content filter stopped the request ({reason:?}), sanitizing it with {sanitizer:?}");
+            filtered.push(FilteredAttempt {
+                request: request.clone(),
+                reason,
+            });
+            request = match sanitizer {
+                Sanitizer::Transform(transform) => transform(request, filtered.len()),
+                Sanitizer::Rephrase { model } => {
+                    let Some(text) = content_filter::last_user_text(&request) else {
+                        return Err(OpenAIError::InvalidArgument(
+                            "Sanitizer::Rephrase needs a user message to rephrase".into(),
+                        ));
+                    };
+                    let rephrased = self
+                        .create(CreateChatCompletionRequest {
+                            model: model.clone(),
+                            messages: vec![ChatCompletionRequestUserMessage {
+                                content: content_filter::rephrase_prompt(&text).into(),
+                                ..Default::default()
+                            }
+                            .into()],
+                            ..Default::default()
+                        })
+                        .await?;
+                    if content_filter::completion_filtered(&rephrased) {
+                        return Err(OpenAIError::InvalidArgument(
+                            "a content filter stopped the rephrasing of the filtered request too".into(),
+                        ));
+                    }
+                    content_filter::replace_last_user_text(&mut request, rephrased.text().into_owned());
+                    request
+                }
+            };
+        }
+    }
+
+    /// Map each of `items` to an answer of the model, at most `concurrency` at a time,
+    /// then reduce the answers to one, e.g. to summarize a document split in chunks.
+    ///
+    /// `map_prompt` makes the request of an item, and `reduce_prompt` the request combining answers.
+    /// Answers are reduced in batches whose requests fit in 16 000 tokens,
+    /// and the answers of the batches again, until one request combines them all; see [Chat::map_reduce_with_budget].
+    pub async fn map_reduce<T, I, M, R>(
+        &self,
+        items: I,
+        map_prompt: M,
+        reduce_prompt: R,
+        concurrency: usize,
+    ) -> Result<CreateChatCompletionResponse, OpenAIError>
+    where
+        I: IntoIterator<Item = T>,
+        M: Fn(T) -> CreateChatCompletionRequest,
+        R: Fn(&[String]) -> CreateChatCompletionRequest,
+    {
+        self.map_reduce_with_budget(
+            items,
+            map_prompt,
+            reduce_prompt,
+            concurrency,
+            DEFAULT_REDUCE_TOKEN_BUDGET,
+        )
+        .await
+    }
+
+    /// [Chat::map_reduce] with reduce requests of at most `max_reduce_tokens` prompt tokens,
+    /// counted like [ChatSession](crate::session::ChatSession) does.
+    ///
+    /// Fails if answers do not fit in the budget two at a time, as reducing them would not make progress.
+    pub async fn map_reduce_with_budget<T, I, M, R>(
+        &self,
+        items: I,
+        map_prompt: M,
+        reduce_prompt: R,
+        concurrency: usize,
+        max_reduce_tokens: usize,
+    ) -> Result<CreateChatCompletionResponse, OpenAIError>
+    where
+        I: IntoIterator<Item = T>,
+        M: Fn(T) -> CreateChatCompletionRequest,
+        R: Fn(&[String]) -> CreateChatCompletionRequest,
+    {
+        let concurrency = concurrency.max(1);
+        let mut answers: Vec<String> = stream::iter(items)
+            .map(|item| self.create(map_prompt(item)))
+            .buffered(concurrency)
+            .map_ok(|response| response.text().into_owned())
+            .try_collect()
+            .await?;
+        if answers.is_empty() {
+            return Err(OpenAIError::InvalidArgument(
+                "map_reduce needs at least one item".into(),
+            ));
+        }
+
+        loop {
+            let batches = reduce_batches(&answers, &reduce_prompt, max_reduce_tokens);
+            if let [batch] = batches.as_slice() {
+                return self.create(reduce_prompt(batch)).await;
+            }
+            if batches.len() == answers.len() {
+                return Err(OpenAIError::InvalidArgument(format!(
+                    "map_reduce answers do not fit two at a time in reduce requests of {max_reduce_tokens} tokens; raise the budget or shorten the answers"
+                )));
+            }
+            answers = stream::iter(batches)
+                .map(|batch| self.create(reduce_prompt(&batch)))
+                .buffered(concurrency)
+                .map_ok(|response| response.text().into_owned())
+                .try_collect()
+                .await?;
+        }
+    }
 
-        Ok(self.client.post_stream("/chat/completions", request).await)
+    /// Sample `n` answers to `request` and pick one with `aggregator`, for self-consistency.
+    ///
+    /// Answers are sampled with the `n` parameter of the request, at most 128 at a time. When the provider
+    /// returns fewer choices than asked for, the remaining answers are sampled with parallel requests
+    /// of as many choices as it returned. Choices without content, such as tool calls, are not counted.
+    pub async fn sample_n(
+        &self,
+        mut request: CreateChatCompletionRequest,
+        n: usize,
+        aggregator: &Aggregator,
+    ) -> Result<Consensus, OpenAIError> {
+        if n == 0 {
+            return Err(OpenAIError::InvalidArgument(
+                "sample_n needs at least one sample".into(),
+            ));
+        }
+        request.n = Some(n.min(sampling::MAX_N) as u8);
+        let response = self.create(request.clone()).await?;
+        let per_request = response.choices.len().clamp(1, sampling::MAX_N);
+        let mut samples: Vec<String> = answers(response).collect();
+
+        let remaining = n.saturating_sub(per_request);
+        if remaining > 0 {
+            request.n = Some(per_request as u8);
+            let requests = (remaining + per_request - 1) / per_request;
+            let responses = futures::future::try_join_all(
+                (0..requests).map(|_| self.create(request.clone())),
+            )
+            .await?;
+            samples.extend(responses.into_iter().flat_map(answers));
+        }
+        samples.truncate(n);
+        if samples.is_empty() {
+            return Err(OpenAIError::InvalidArgument(
+                "no sample has content to vote on".into(),
+            ));
+        }
+
+        let tally = sampling::tally(&samples);
+        let winner = match aggregator {
+            Aggregator::MajorityVote => 0,
+            Aggregator::Judge { .. } if tally.len() == 1 => 0,
+            Aggregator::Judge { model } => {
+                let mut messages = request.messages;
+                messages.push(
+                    ChatCompletionRequestUserMessage {
+                        content: sampling::judge_prompt(&tally).into(),
+                        ..Default::default()
+                    }
+                    .into(),
+                );
+                let verdict = self
+                    .create(CreateChatCompletionRequest {
+                        model: model.clone(),
+                        messages,
+                        ..Default::default()
+                    })
+                    .await?;
+                let verdict = verdict.text();
+                sampling::parse_verdict(&verdict, tally.len()).unwrap_or_else(|| {
+                    tracing::warn!("Judge verdict {verdict:?} names no answer, falling back to the majority vote");
+                    0
+                })
+            }
+        };
+        Ok(Consensus::new(samples, tally, winner))
+    }
+}
+
+/// Progress of a stream of [Chat::create_stream_resumable]
+struct ResumeState {
+    request: CreateChatCompletionRequest,
+    policy: StreamRetryPolicy,
+    stream: Option<ChatCompletionResponseStream>,
+    /// Content of the answer received so far
+    content: String,
+    resumes: u32,
+    /// Whether the answer can still be resumed, not being made of several choices or of tool calls
+    resumable: bool,
+    /// Whether a `finish_reason` was received
+    finished: bool,
+}
+
+impl ResumeState {
+    /// The request asking for the rest of the answer, the original one before any content is received
+    fn continuation(&self) -> CreateChatCompletionRequest {
+        let mut request = self.request.clone();
+        if !self.content.is_empty() {
+            if let Ok(message) = ChatCompletionRequestAssistantMessageArgs::default()
+                .content(self.content.clone())
+                .build()
+            {
+                request.messages.push(message.into());
+            }
+        }
+        request
+    }
+
+    #[allow(deprecated)]
+    fn track(&mut self, chunk: &CreateChatCompletionStreamResponse) {
+        for choice in &chunk.choices {
+            if choice.index != 0
+                || choice.delta.tool_calls.is_some()
+                || choice.delta.function_call.is_some()
+            {
+                self.resumable = false;
+            }
+            if let Some(content) = &choice.delta.content {
+                self.content.push_str(content);
+            }
+            if choice.finish_reason.is_some() {
+                self.finished = true;
+            }
+        }
+    }
+}
+
+/// Answers of the choices of `response`, skipping the ones without content
+fn answers(response: CreateChatCompletionResponse) -> impl Iterator<Item = String> {
+    response
+        .choices
+        .into_iter()
+        .filter_map(|choice| choice.message.content)
+}
+
+/// Move `max_tokens` to `max_completion_tokens` for reasoning models, which reject it, with a warning
+fn migrate_max_tokens(request: &mut CreateChatCompletionRequest) {
+    if request.migrate_max_tokens() {
+        tracing::warn!(
+            "{} rejects `max_tokens`, sending it as `max_completion_tokens`; set `max_completion_tokens` instead",
+            request.model
+        );
+    }
+}
+
+/// Split `answers` in consecutive batches whose reduce requests fit in `max_tokens`,
+/// an answer too long on its own making a batch of one
+fn reduce_batches<R>(answers: &[String], reduce_prompt: &R, max_tokens: usize) -> Vec<Vec<String>>
+where
+    R: Fn(&[String]) -> CreateChatCompletionRequest,
+{
+    let tokens = |batch: &[String]| {
+        let request = reduce_prompt(batch);
+        count_tokens(&request.model, &request.messages)
+    };
+    let mut batches: Vec<Vec<String>> = vec![];
+    let mut batch: Vec<String> = vec![];
+    for answer in answers {
+        batch.push(answer.clone());
+        if batch.len() > 1 && tokens(&batch) > max_tokens {
+            let answer = batch.pop().unwrap();
+            batches.push(std::mem::replace(&mut batch, vec![answer]));
+        }
     }
+    batches.push(batch);
+    batches
 }
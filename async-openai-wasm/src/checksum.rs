@@ -0,0 +1,192 @@
+//! Checksums of file contents, to detect uploads and downloads corrupted on the way.
+//!
+//! Downloaded file contents are verified against the digests sent by the server, if any,
+//! in the `Content-MD5`, `Digest`, `Repr-Digest` or `Content-Digest` headers.
+//!
+//! ```
+//! use async_openai_wasm::checksum::{md5_hex, sha256_hex};
+//!
+//! assert_eq!(md5_hex(b"abc"), "900150983cd24fb0d6963f7d28e17f72");
+//! assert_eq!(
+//!     sha256_hex(b"abc"),
+//!     "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+//! );
+//! ```
+use std::fmt::{Display, Formatter};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use reqwest::header::HeaderMap;
+use sha2::Digest;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    Md5,
+    Sha256,
+}
+
+impl Display for Algorithm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Algorithm::Md5 => write!(f, "MD5"),
+            Algorithm::Sha256 => write!(f, "SHA-256"),
+        }
+    }
+}
+
+/// Content whose checksum differs from the expected one
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{algorithm} checksum mismatch: expected {expected}, got {actual}")]
+pub struct ChecksumMismatch {
+    pub algorithm: Algorithm,
+    /// Expected digest, hex encoded
+    pub expected: String,
+    /// Digest of the content, hex encoded
+    pub actual: String,
+}
+
+/// Hex encoded MD5 digest of `data`
+pub fn md5_hex(data: &[u8]) -> String {
+    format!("{:x}", md5::compute(data))
+}
+
+/// Hex encoded SHA-256 digest of `data`
+pub fn sha256_hex(data: &[u8]) -> String {
+    hex(&sha2::Sha256::digest(data))
+}
+
+/// MD5 digest computed incrementally, e.g. over the parts of an upload
+#[derive(Clone)]
+pub struct Md5(md5::Context);
+
+impl Md5 {
+    pub fn new() -> Self {
+        Self(md5::Context::new())
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.0.consume(data);
+    }
+
+    pub fn finalize(self) -> [u8; 16] {
+        self.0.compute().0
+    }
+
+    /// The digest, hex encoded
+    pub fn finalize_hex(self) -> String {
+        hex(&self.finalize())
+    }
+}
+
+impl Default for Md5 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for Md5 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Md5")
+    }
+}
+
+/// SHA-256 digest computed incrementally
+#[derive(Clone, Default)]
+pub struct Sha256(sha2::Sha256);
+
+impl Sha256 {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    pub fn finalize(self) -> [u8; 32] {
+        self.0.finalize().into()
+    }
+}
+
+impl std::fmt::Debug for Sha256 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Sha256")
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Digests of the content of a response, from its headers
+pub(crate) fn expected_digests(headers: &HeaderMap) -> Vec<(Algorithm, Vec<u8>)> {
+    let mut digests = vec![];
+    let decode = |value: &str| STANDARD.decode(value.trim().trim_matches(':')).ok();
+
+    if let Some(md5) = headers
+        .get("content-md5")
+        .and_then(|value| decode(value.to_str().ok()?))
+    {
+        digests.push((Algorithm::Md5, md5));
+    }
+    // `Digest: sha-256=<base64>` and `Repr-Digest: sha-256=:<base64>:`
+    for name in ["digest", "repr-digest", "content-digest"] {
+        for value in headers.get_all(name).iter().filter_map(|v| v.to_str().ok()) {
+            for digest in value.split(',') {
+                let Some((algorithm, value)) = digest.split_once('=') else {
+                    continue;
+                };
+                let algorithm = match algorithm.trim().to_ascii_lowercase().as_str() {
+                    "md5" => Algorithm::Md5,
+                    "sha-256" => Algorithm::Sha256,
+                    _ => continue,
+                };
+                if let Some(value) = decode(value) {
+                    digests.push((algorithm, value));
+                }
+            }
+        }
+    }
+    digests
+}
+
+/// Check `content` against the digests sent along it
+pub(crate) fn verify(
+    digests: &[(Algorithm, Vec<u8>)],
+    content: &[u8],
+) -> Result<(), ChecksumMismatch> {
+    for (algorithm, expected) in digests {
+        let actual = match algorithm {
+            Algorithm::Md5 => md5::compute(content).0.to_vec(),
+            Algorithm::Sha256 => sha2::Sha256::digest(content).to_vec(),
+        };
+        if &actual != expected {
+            return Err(ChecksumMismatch {
+                algorithm: *algorithm,
+                expected: hex(expected),
+                actual: hex(&actual),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha256_of_multiple_blocks() {
+        assert_eq!(
+            sha256_hex(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        let mut hasher = Sha256::new();
+        for _ in 0..1000 {
+            hasher.update(b"a");
+        }
+        assert_eq!(
+            hex(&hasher.finalize()),
+            "41edece42d63e8d9bf515a9ba6932e1c20cbc9f5a5d134645adb5db1b9737ea3"
+        );
+    }
+}
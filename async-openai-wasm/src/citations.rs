@@ -0,0 +1,214 @@
+//! Rendering of the citations of answers, from the `url_citation` and `file_citation` annotations
+//! of chat completions, responses and assistant messages.
+//!
+//! Annotations locate citations in the text by character indices, which are not the byte indices
+//! of Rust strings as soon as the text is not ASCII. [splice] replaces the cited ranges with markers
+//! made by a callback, and [footnotes] with Markdown footnotes listing the sources at the end of the text.
+//!
+//! ```no_run
+//! use async_openai_wasm::{citations, Client};
+//!
+//! # async fn example(request: async_openai_wasm::types::CreateChatCompletionRequest) -> Result<(), async_openai_wasm::error::OpenAIError> {
+//! let client = Client::new();
+//! let response = client.chat().create(request).await?;
+//! let message = &response.choices[0].message;
+//! let text = message.content.as_deref().unwrap_or_default();
+//! println!("{}", citations::footnotes(text, &message.citations()));
+//! // or with markers of your own
+//! let linked = citations::splice(text, &message.citations(), |number, citation| match &citation.source {
+//!     citations::CitationSource::Url { url, .. } => format!("<sup><a href=\"{url}\">{number}</a></sup>"),
+//!     _ => format!("<sup>{number}</sup>"),
+//! });
+//! # Ok(())
+//! # }
+//! ```
+use crate::types::{
+    ChatCompletionResponseMessage, ChatCompletionResponseMessageAnnotation,
+    MessageContentTextAnnotations, ResponseAnnotation, ResponseOutputContent, TextData,
+};
+
+/// A citation of an annotated text, spanning the characters `start..end`, empty when the annotation
+/// only gives the position of the citation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Citation<'a> {
+    /// Index of the first character of the citation
+    pub start: usize,
+    /// Index past the last character of the citation
+    pub end: usize,
+    pub source: CitationSource<'a>,
+}
+
+/// What a [Citation] refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CitationSource<'a> {
+    /// A web resource, cited by web search
+    Url { url: &'a str, title: &'a str },
+    /// A file, cited by file search, with the quote cited if known
+    File {
+        file_id: &'a str,
+        quote: Option<&'a str>,
+    },
+    /// A file generated by the code interpreter
+    FilePath { file_id: &'a str },
+}
+
+/// `text` with the ranges of `citations` replaced by `marker(number, citation)`.
+///
+/// Sources are numbered from 1 in their order of appearance in the text, a source cited twice keeping its number.
+/// Citations overlapping an earlier one are dropped, and indices past the end of the text point to its end.
+pub fn splice<F>(text: &str, citations: &[Citation], mut marker: F) -> String
+where
+    F: FnMut(usize, &Citation) -> String,
+{
+    let boundaries: Vec<usize> = text
+        .char_indices()
+        .map(|(index, _)| index)
+        .chain([text.len()])
+        .collect();
+    let byte_index = |index: usize| boundaries.get(index).copied().unwrap_or(text.len());
+
+    let mut spliced = String::with_capacity(text.len());
+    let mut sources: Vec<CitationSource> = vec![];
+    let mut cursor = 0;
+    for citation in ordered(citations) {
+        let start = byte_index(citation.start);
+        let end = byte_index(citation.end.max(citation.start));
+        if start < cursor {
+            continue;
+        }
+        let number = match sources.iter().position(|source| *source == citation.source) {
+            Some(position) => position + 1,
+            None => {
+                sources.push(citation.source);
+                sources.len()
+            }
+        };
+        spliced.push_str(&text[cursor..start]);
+        spliced.push_str(&marker(number, citation));
+        cursor = end;
+    }
+    spliced.push_str(&text[cursor..]);
+    spliced
+}
+
+/// `text` with the ranges of `citations` replaced by Markdown footnote references `[^1]`,
+/// followed by the definitions of the footnotes, e.g. `[^1]: [title](url)`
+pub fn footnotes(text: &str, citations: &[Citation]) -> String {
+    let mut definitions: Vec<String> = vec![];
+    let mut spliced = splice(text, citations, |number, citation| {
+        if number > definitions.len() {
+            definitions.push(format!("[^{number}]: {}", describe(&citation.source)));
+        }
+        format!("[^{number}]")
+    });
+    if !definitions.is_empty() {
+        spliced.push_str("\n\n");
+        spliced.push_str(&definitions.join("\n"));
+    }
+    spliced
+}
+
+/// `citations` by position in the text, keeping the order of the ones at the same position
+fn ordered<'c, 'a>(citations: &'c [Citation<'a>]) -> Vec<&'c Citation<'a>> {
+    let mut ordered: Vec<_> = citations.iter().collect();
+    ordered.sort_by_key(|citation| citation.start);
+    ordered
+}
+
+/// Markdown description of `source`
+fn describe(source: &CitationSource) -> String {
+    match source {
+        CitationSource::Url { url, title: "" } => format!("<{url}>"),
+        CitationSource::Url { url, title } => format!("[{title}]({url})"),
+        CitationSource::File {
+            file_id,
+            quote: Some(quote),
+        } => format!("{file_id}: \"{quote}\""),
+        CitationSource::File { file_id, .. } | CitationSource::FilePath { file_id } => {
+            file_id.to_string()
+        }
+    }
+}
+
+impl ChatCompletionResponseMessage {
+    /// Citations of the annotations of the content
+    pub fn citations(&self) -> Vec<Citation<'_>> {
+        self.annotations
+            .iter()
+            .flatten()
+            .map(|annotation| match annotation {
+                ChatCompletionResponseMessageAnnotation::UrlCitation { url_citation } => Citation {
+                    start: url_citation.start_index as usize,
+                    end: url_citation.end_index as usize,
+                    source: CitationSource::Url {
+                        url: &url_citation.url,
+                        title: &url_citation.title,
+                    },
+                },
+            })
+            .collect()
+    }
+}
+
+impl ResponseOutputContent {
+    /// Citations of the annotations of the text, none for refusals
+    pub fn citations(&self) -> Vec<Citation<'_>> {
+        let ResponseOutputContent::OutputText { annotations, .. } = self else {
+            return vec![];
+        };
+        annotations
+            .iter()
+            .map(|annotation| match annotation {
+                ResponseAnnotation::UrlCitation {
+                    url,
+                    title,
+                    start_index,
+                    end_index,
+                } => Citation {
+                    start: *start_index as usize,
+                    end: *end_index as usize,
+                    source: CitationSource::Url { url, title },
+                },
+                ResponseAnnotation::FileCitation { file_id, index } => Citation {
+                    start: *index as usize,
+                    end: *index as usize,
+                    source: CitationSource::File {
+                        file_id,
+                        quote: None,
+                    },
+                },
+                ResponseAnnotation::FilePath { file_id, index } => Citation {
+                    start: *index as usize,
+                    end: *index as usize,
+                    source: CitationSource::FilePath { file_id },
+                },
+            })
+            .collect()
+    }
+}
+
+impl TextData {
+    /// Citations of the annotations of the text, whose ranges hold placeholders such as `【4:0†source】`
+    pub fn citations(&self) -> Vec<Citation<'_>> {
+        self.annotations
+            .iter()
+            .map(|annotation| match annotation {
+                MessageContentTextAnnotations::FileCitation(citation) => Citation {
+                    start: citation.start_index as usize,
+                    end: citation.end_index as usize,
+                    source: CitationSource::File {
+                        file_id: &citation.file_citation.file_id,
+                        quote: citation.file_citation.quote.as_deref(),
+                    },
+                },
+                MessageContentTextAnnotations::FilePath(path) => Citation {
+                    start: path.start_index as usize,
+                    end: path.end_index as usize,
+                    source: CitationSource::FilePath {
+                        file_id: &path.file_path.file_id,
+                    },
+                },
+            })
+            .collect()
+    }
+}
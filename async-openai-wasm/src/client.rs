@@ -1,31 +1,77 @@
 use std::future;
 use std::marker::PhantomData;
 use std::pin::Pin;
+use std::sync::{Arc, Mutex, RwLock};
 use std::task::{Context, Poll};
+use std::time::Duration;
 
-use bytes::Bytes;
-use futures::{Stream, stream::StreamExt};
-use futures::stream::Filter;
+use bytes::{Bytes, BytesMut};
+use futures::{Stream, stream::{StreamExt, TryStreamExt}};
 use pin_project::pin_project;
-use reqwest_eventsource::{Event, EventSource, RequestBuilderExt};
+use reqwest::header::{HeaderMap, HeaderValue, IntoHeaderName, ACCEPT_ENCODING, CONTENT_TYPE, RANGE};
+use eventsource_stream::{EventStreamError, Eventsource};
+use secrecy::ExposeSecret;
 use serde::{de::DeserializeOwned, Serialize};
+use tracing::Instrument;
 
 use crate::{
-    Assistants, Audio, Batches, Chat, Completions,
-    config::{Config, OpenAIConfig}, Embeddings,
-    error::{map_deserialization_error, OpenAIError, WrappedError},
+    Admin, Anthropic, Assistants, Audio, Batches, cancel::{self, CancellationToken, WaitForCancellation}, Chat, checksum, Completions,
+    compression::{ContentDecoder, ContentDecoders},
+    config::{ApiKeyKind, Config, OpenAIConfig}, Embeddings,
+    context::request_span,
+    echo::EchoedRequest,
+    error::{map_deserialization_error, response_error, InvalidStreamEvent, OpenAIError},
     file::Files, FineTuning,
+    health::VerifyReport,
     image::Images, Models,
-    moderation::Moderations, Threads,
+    middleware::{Interceptors, RequestInterceptor},
+    progress::{DownloadProgress, UploadProgress, UploadProgressHook},
+    rate_limit::{RateLimit, RateLimiter},
+    recorder::{StreamRecorder, StreamRecording},
+    retry::{self, RetryBudget, RetryPolicy},
+    stats::{Active, ClientStats, Counters},
+    telemetry,
+    time::{sleep, IdleTimeout, Instant, StallWatchdog},
+    timing::ResponseTiming,
+    moderation::Moderations, Raw, Responses, Threads, Uploads,
+    types::{ByteStream, CreateChatCompletionRequest},
+    validation::RequestLimits,
     VectorStores,
 };
 
+/// Client shared by [Client::global], once set or first used
+static GLOBAL: RwLock<Option<Client<OpenAIConfig>>> = RwLock::new(None);
+
+/// Maximum number of times [Client::get_raw_with_progress] resumes an interrupted download
+const MAX_DOWNLOAD_RESUMES: u32 = 3;
+
 #[derive(Debug, Clone)]
 /// Client is a container for config, backoff and http_client
 /// used to make API calls.
 pub struct Client<C: Config> {
     http_client: reqwest::Client,
     config: C,
+    default_user: Option<String>,
+    request_limits: Option<RequestLimits>,
+    interceptors: Interceptors,
+    migrate_legacy_functions: bool,
+    retry_policy: RetryPolicy,
+    retry_budget: Option<RetryBudget>,
+    upload_progress: UploadProgressHook,
+    rate_limit: Arc<Mutex<Option<RateLimit>>>,
+    response_timing: Arc<Mutex<Option<ResponseTiming>>>,
+    rate_limiter: Option<RateLimiter>,
+    stream_recorder: Option<StreamRecorder>,
+    lenient_streaming: bool,
+    stream_decoders: ContentDecoders,
+    identity_stream_encoding: bool,
+    request_echo: bool,
+    last_request: Arc<Mutex<Option<EchoedRequest>>>,
+    cancellation: Option<CancellationToken>,
+    timeout: Option<Duration>,
+    stream_watchdog: Option<Duration>,
+    headers: HeaderMap,
+    stats: Arc<Counters>,
     // #[cfg(feature = "backoff")]
     // backoff: backoff::ExponentialBackoff,
 }
@@ -33,12 +79,44 @@ pub struct Client<C: Config> {
 impl Client<OpenAIConfig> {
     /// Client with default [OpenAIConfig]
     pub fn new() -> Self {
-        Self {
-            http_client: reqwest::Client::new(),
-            config: OpenAIConfig::default(),
-            // #[cfg(feature = "backoff")]
-            // backoff: Default::default(),
+        Self::with_config(OpenAIConfig::default())
+    }
+
+    /// Build a client in one chain, set up with [ClientBuilder::config] for other configs than [OpenAIConfig]
+    pub fn builder() -> ClientBuilder<OpenAIConfig> {
+        ClientBuilder::default()
+    }
+
+    /// The client shared across the program, as set with [Client::set_global],
+    /// or a [Client::new] set as the global client on first use.
+    ///
+    /// This lets utility code use one configured client without passing it down every function.
+    /// Functions wanting a per-call override can take an `Option<&Client>` and fall back on this one:
+    ///
+    /// ```
+    /// use async_openai_wasm::{config::OpenAIConfig, Client};
+    ///
+    /// fn client_or_global(client: Option<&Client<OpenAIConfig>>) -> Client<OpenAIConfig> {
+    ///     client.cloned().unwrap_or_else(Client::global)
+    /// }
+    /// ```
+    pub fn global() -> Self {
+        if let Some(client) = GLOBAL.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+            return client.clone();
         }
+        GLOBAL
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .get_or_insert_with(Self::new)
+            .clone()
+    }
+
+    /// Set the client returned by [Client::global] from now on, returning the previous one if any
+    pub fn set_global(client: Self) -> Option<Self> {
+        GLOBAL
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .replace(client)
     }
 }
 
@@ -53,6 +131,27 @@ impl<C: Config> Client<C> {
         Self {
             http_client,
             config,
+            default_user: None,
+            request_limits: None,
+            interceptors: Default::default(),
+            migrate_legacy_functions: false,
+            retry_policy: RetryPolicy::none(),
+            retry_budget: None,
+            upload_progress: Default::default(),
+            rate_limit: Default::default(),
+            response_timing: Default::default(),
+            rate_limiter: None,
+            stream_recorder: None,
+            lenient_streaming: false,
+            stream_decoders: Default::default(),
+            identity_stream_encoding: false,
+            request_echo: false,
+            last_request: Default::default(),
+            cancellation: None,
+            timeout: None,
+            stream_watchdog: None,
+            headers: HeaderMap::new(),
+            stats: Default::default(),
             // #[cfg(feature = "backoff")]
             // backoff,
         }
@@ -60,12 +159,7 @@ impl<C: Config> Client<C> {
 
     /// Create client with [OpenAIConfig] or [crate::config::AzureConfig]
     pub fn with_config(config: C) -> Self {
-        Self {
-            http_client: reqwest::Client::new(),
-            config,
-            // #[cfg(feature = "backoff")]
-            // backoff: Default::default(),
-        }
+        Self::build(reqwest::Client::new(), config)
     }
 
     /// Provide your own [client] to make HTTP requests with.
@@ -76,6 +170,167 @@ impl<C: Config> Client<C> {
         self
     }
 
+    /// Set a default end-user identifier which is sent as the `user` field of
+    /// chat, completions, images and embeddings requests that don't set one themselves.
+    ///
+    /// See [end-user ids](https://platform.openai.com/docs/guides/safety-best-practices/end-user-ids).
+    pub fn with_default_user<S: Into<String>>(mut self, user: S) -> Self {
+        self.default_user = Some(user.into());
+        self
+    }
+
+    /// Validate requests against [RequestLimits] before sending them,
+    /// e.g. [RequestLimits::azure] to get actionable errors instead of the vague ones of Azure.
+    pub fn with_request_limits(mut self, request_limits: RequestLimits) -> Self {
+        self.request_limits = Some(request_limits);
+        self
+    }
+
+    /// Do not validate requests against limits, not even the ones of the config, e.g. the image profile of
+    /// [AzureConfig](crate::config::AzureConfig) once Azure raised them
+    pub fn without_request_limits(mut self) -> Self {
+        self.request_limits = Some(RequestLimits::new());
+        self
+    }
+
+    /// Add an interceptor called around every HTTP request, after the ones added before.
+    pub fn with_interceptor<I: RequestInterceptor + 'static>(mut self, interceptor: I) -> Self {
+        self.interceptors.push(Arc::new(interceptor));
+        self
+    }
+
+    /// Migrate chat completion requests using the deprecated `functions` and `function_call` to `tools` and `tool_choice`
+    /// before sending them, and mirror tool calls of responses back into `function_call`.
+    ///
+    /// Streamed responses are not mapped back.
+    pub fn with_legacy_functions_migration(mut self) -> Self {
+        self.migrate_legacy_functions = true;
+        self
+    }
+
+    /// Retry failed requests as `retry_policy` says; requests are not retried by default.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Cap the retries of this client and its clones to `retry_budget`, see [RetryBudget]
+    pub fn with_retry_budget(mut self, retry_budget: RetryBudget) -> Self {
+        self.retry_budget = Some(retry_budget);
+        self
+    }
+
+    /// Call `callback` as the bodies of multipart requests, such as file uploads, are sent.
+    ///
+    /// Progress starts over when a request is retried, and is not reported on wasm32.
+    pub fn with_upload_progress<F>(mut self, callback: F) -> Self
+        where
+            F: Fn(UploadProgress) + Send + Sync + 'static,
+    {
+        self.upload_progress = UploadProgressHook::new(callback);
+        self
+    }
+
+    /// Delay requests to stay under `requests_per_minute` and `tokens_per_minute`, `0` disabling a limit.
+    ///
+    /// The limits are shared by all API groups and clones of the client, see [RateLimiter].
+    pub fn with_rate_limit(self, requests_per_minute: u32, tokens_per_minute: u32) -> Self {
+        self.with_rate_limiter(RateLimiter::new(requests_per_minute, tokens_per_minute))
+    }
+
+    /// Delay requests as `rate_limiter` says, e.g. to share one between clients of different configs
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Record the events of all SSE streams with `recorder`, e.g. to export them as a replayable log
+    pub fn with_stream_recorder(mut self, recorder: StreamRecorder) -> Self {
+        self.stream_recorder = Some(recorder);
+        self
+    }
+
+    /// Skip the malformed and unknown events of SSE streams, such as the ones Azure OpenAI Service
+    /// and proxies inject, instead of ending the streams with [OpenAIError::InvalidStreamEvent].
+    ///
+    /// Skipped events are logged as warnings.
+    pub fn with_lenient_streaming(mut self, lenient_streaming: bool) -> Self {
+        self.lenient_streaming = lenient_streaming;
+        self
+    }
+
+    /// Keep a copy of the last request sent by this client or its clones, as sent, for [Client::last_request],
+    /// see [echo](crate::echo)
+    pub fn with_request_echo(mut self, request_echo: bool) -> Self {
+        self.request_echo = request_echo;
+        self
+    }
+
+    /// Decode SSE streams compressed with `encoding`, e.g. `zstd` by a gateway, with a decoder made by `decoder`
+    /// for each stream, see [compression](crate::compression). Decoders are not used on wasm32, where browsers decode streams.
+    pub fn with_stream_decoder<F>(mut self, encoding: &str, decoder: F) -> Self
+    where
+        F: Fn() -> Box<dyn ContentDecoder> + Send + Sync + 'static,
+    {
+        self.stream_decoders.insert(encoding, Arc::new(decoder));
+        self
+    }
+
+    /// Ask for uncompressed SSE streams with `Accept-Encoding: identity`, so that gateways forward events
+    /// as soon as they come instead of buffering them to compress them. Browsers ignore it on wasm32.
+    pub fn with_identity_stream_encoding(mut self, identity_stream_encoding: bool) -> Self {
+        self.identity_stream_encoding = identity_stream_encoding;
+        self
+    }
+
+    /// Abort the calls of this client, and of the helpers using it, once `token` is cancelled, see [cancel](crate::cancel).
+    ///
+    /// Clones of the client share the token, give each task its own clone with a [CancellationToken::child_token]
+    /// to cancel them one by one.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Fail calls with [OpenAIError::Timeout] once `timeout` elapses before their response is received, retries included,
+    /// and streams and downloads once no data arrives for `timeout`.
+    ///
+    /// Unlike the timeouts of the HTTP client, this one works on wasm32 too, where `fetch` has none.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Fail streams with [OpenAIError::StreamStalled] once no event arrives for `after` after their first one,
+    /// aborting their request, so that a lost connection is told apart from a stream ending normally
+    /// instead of hanging forever.
+    ///
+    /// Unlike [Client::with_timeout], the wait for the first event, e.g. while a reasoning model thinks, is not bounded.
+    pub fn with_stream_watchdog(mut self, after: Duration) -> Self {
+        self.stream_watchdog = Some(after);
+        self
+    }
+
+    /// This client with the overrides of `options`, to make one call with them,
+    /// e.g. `client.with_request_options(&options).models().list()`.
+    pub fn with_request_options(&self, options: &RequestOptions) -> Self {
+        let mut client = self.clone();
+        client.timeout = options.timeout.or(self.timeout);
+        client.headers.extend(options.headers.clone());
+        if let Some(token) = &options.cancellation {
+            // cancelled by either token
+            client.cancellation = Some(match &self.cancellation {
+                Some(client_token) => {
+                    let call_token = client_token.child_token();
+                    token.adopt(&call_token);
+                    call_token
+                }
+                None => token.clone(),
+            });
+        }
+        client
+    }
+
     // #[cfg(feature = "backoff")]
     // /// Exponential backoff for retrying [rate limited](https://platform.openai.com/docs/guides/rate-limits) requests.
     // pub fn with_backoff(mut self, backoff: backoff::ExponentialBackoff) -> Self {
@@ -150,10 +405,204 @@ impl<C: Config> Client<C> {
         Batches::new(self)
     }
 
+    /// To call [Uploads] group related APIs using this client.
+    pub fn uploads(&self) -> Uploads<C> {
+        Uploads::new(self)
+    }
+
+    /// To call [Responses] group related APIs using this client.
+    pub fn responses(&self) -> Responses<C> {
+        Responses::new(self)
+    }
+
+    /// To make [Raw] untyped requests to any endpoint using this client.
+    pub fn raw(&self) -> Raw<C> {
+        Raw::new(self)
+    }
+
+    /// To call [Admin] group related APIs using this client, which requires an admin API key.
+    pub fn admin(&self) -> Admin<C> {
+        Admin::new(self)
+    }
+
+    /// To call [Anthropic] group related APIs using this client.
+    pub fn anthropic(&self) -> Anthropic<C> {
+        Anthropic::new(self)
+    }
+
     pub fn config(&self) -> &C {
         &self.config
     }
 
+    /// The default end-user identifier set by [Client::with_default_user]
+    pub fn default_user(&self) -> Option<&str> {
+        self.default_user.as_deref()
+    }
+
+    /// The token set by [Client::with_cancellation]
+    pub fn cancellation(&self) -> Option<&CancellationToken> {
+        self.cancellation.as_ref()
+    }
+
+    /// Rate limits of the last response with `x-ratelimit-*` headers received by this client or its clones
+    pub fn last_rate_limit(&self) -> Option<RateLimit> {
+        *self.rate_limit.lock().unwrap()
+    }
+
+    /// Timings of the last response received by this client or its clones, see [timing](crate::timing)
+    pub fn last_response_timing(&self) -> Option<ResponseTiming> {
+        *self.response_timing.lock().unwrap()
+    }
+
+    /// The last request sent by this client or its clones, with the status of its response,
+    /// if enabled with [Client::with_request_echo]
+    pub fn last_request(&self) -> Option<EchoedRequest> {
+        self.last_request.lock().unwrap().clone()
+    }
+
+    /// What this client and its clones are doing, see [stats](crate::stats)
+    pub fn stats(&self) -> ClientStats {
+        self.stats.snapshot()
+    }
+
+    /// Check the credentials of the config with a cheap authenticated call, listing models,
+    /// or projects with an admin API key, see [health](crate::health).
+    ///
+    /// The call is made once, without retries. Answers of the API, refusals of the credentials included,
+    /// are reported in the [VerifyReport], other errors such as unreachable hosts are returned.
+    pub async fn verify(&self) -> Result<VerifyReport, OpenAIError> {
+        let request = match self.config.api_key_kind() {
+            Some(ApiKeyKind::Admin) => self
+                .request(reqwest::Method::GET, "/organization/projects")
+                .query(&[("limit", "1")]),
+            _ => self.request(reqwest::Method::GET, "/models"),
+        };
+        let span = request_span();
+        let verify = async {
+            let mut request = request.build()?;
+            self.check_api_key(&request)?;
+            self.interceptors.before_request(&mut request).await?;
+            telemetry::record_request(&span, &request, false);
+            let started = Instant::now();
+            let _in_flight = self.stats.in_flight_request();
+            self.stats.request_sent();
+            let response = self.http_client.execute(request).await?;
+            let timing = ResponseTiming::from_headers(response.headers(), started.elapsed());
+            self.interceptors.after_response(&response).await?;
+            telemetry::record_response(&span, &response, 1, started);
+            telemetry::record_timing(&span, &timing);
+            *self.response_timing.lock().unwrap() = Some(timing);
+
+            let header = |name: &str| {
+                response
+                    .headers()
+                    .get(name)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string)
+            };
+            let organization = header("openai-organization");
+            let project = header("openai-project");
+            let request_id = header("x-request-id");
+            let status = response.status();
+            let error = match status.is_success() {
+                true => None,
+                false => {
+                    let headers = response.headers().clone();
+                    let bytes = response.bytes().await?;
+                    Some(response_error(status, &headers, &bytes))
+                }
+            };
+            Ok(VerifyReport {
+                status: status.as_u16(),
+                error,
+                organization,
+                project,
+                request_id,
+                latency: timing.latency,
+                processing: timing.processing,
+            })
+        };
+        self.cancellable(verify.instrument(span.clone())).await
+    }
+
+    /// Fill in `user` with the default end-user identifier, if it is not already set
+    pub(crate) fn inject_default_user(&self, user: &mut Option<String>) {
+        if user.is_none() {
+            user.clone_from(&self.default_user);
+        }
+    }
+
+    /// Migrate `request` if it uses legacy functions and [Client::with_legacy_functions_migration] is set,
+    /// returning whether it was.
+    pub(crate) fn migrate_legacy_functions(&self, request: &mut CreateChatCompletionRequest) -> bool {
+        if self.migrate_legacy_functions && request.uses_legacy_functions() {
+            request.migrate_functions_to_tools();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Check a chat completion request against the limits set by [Client::with_request_limits],
+    /// or else the ones of the config
+    pub(crate) fn validate_chat_request(
+        &self,
+        request: &CreateChatCompletionRequest,
+    ) -> Result<(), OpenAIError> {
+        match (&self.request_limits, self.config.request_limits()) {
+            (Some(request_limits), _) => request_limits.validate_chat(request),
+            (None, Some(request_limits)) => request_limits.validate_chat(request),
+            (None, None) => Ok(()),
+        }
+    }
+
+    /// Start a request to {path} with the URL, query params and headers of the config
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        self.http_client
+            .request(method, self.config.url(path))
+            .query(&self.config.query())
+            .query(&self.config.extra_query(path))
+            .headers(self.config.headers())
+            .headers(self.config.extra_headers(path))
+            .headers(self.headers.clone())
+    }
+
+    /// Check that the API key of the config can call the endpoint of `request`, see [crate::config::ApiKeyKind::check_path]
+    ///
+    /// The endpoint is the path of the URL after the one of the API base, when on its origin, compared regardless
+    /// of case, empty segments and trailing slashes, e.g. `/organization/users` for `https://API.openai.com/V1//organization/users/`.
+    fn check_api_key(&self, request: &reqwest::Request) -> Result<(), OpenAIError> {
+        let Some(kind) = self.config.api_key_kind() else {
+            return Ok(());
+        };
+        let url = request.url();
+        let mut path = url.path();
+        if let Ok(base) = reqwest::Url::parse(self.config.api_base()) {
+            let base_path = base.path().trim_end_matches('/');
+            let is_under_base = path
+                .get(..base_path.len())
+                .map_or(false, |prefix| prefix.eq_ignore_ascii_case(base_path))
+                && path[base_path.len()..].chars().next().map_or(true, |next| next == '/');
+            if base.origin() == url.origin() && is_under_base {
+                path = &path[base_path.len()..];
+            }
+        }
+        let segments: Vec<_> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+        kind.check_path(&format!("/{}", segments.join("/").to_ascii_lowercase()))
+    }
+
+    /// JSON body of a request to {path}, with the [Config::extra_body] fields it does not set itself
+    fn json_body<I: Serialize>(&self, path: &str, request: I) -> Result<serde_json::Value, OpenAIError> {
+        let mut body = serde_json::to_value(request)
+            .map_err(|e| OpenAIError::InvalidArgument(format!("cannot serialize request: {e}")))?;
+        if let serde_json::Value::Object(fields) = &mut body {
+            for (field, value) in self.config.extra_body(path) {
+                fields.entry(field).or_insert(value);
+            }
+        }
+        Ok(body)
+    }
+
     /// Make a GET request to {path} and deserialize the response body
     pub(crate) async fn get<O>(&self, path: &str) -> Result<O, OpenAIError>
         where
@@ -161,10 +610,7 @@ impl<C: Config> Client<C> {
     {
         let request_maker = || async {
             Ok(self
-                .http_client
-                .get(self.config.url(path))
-                .query(&self.config.query())
-                .headers(self.config.headers())
+                .request(reqwest::Method::GET, path)
                 .build()?)
         };
 
@@ -179,11 +625,8 @@ impl<C: Config> Client<C> {
     {
         let request_maker = || async {
             Ok(self
-                .http_client
-                .get(self.config.url(path))
-                .query(&self.config.query())
+                .request(reqwest::Method::GET, path)
                 .query(query)
-                .headers(self.config.headers())
                 .build()?)
         };
 
@@ -197,46 +640,102 @@ impl<C: Config> Client<C> {
     {
         let request_maker = || async {
             Ok(self
-                .http_client
-                .delete(self.config.url(path))
-                .query(&self.config.query())
-                .headers(self.config.headers())
+                .request(reqwest::Method::DELETE, path)
                 .build()?)
         };
 
         self.execute(request_maker).await
     }
 
-    /// Make a GET request to {path} and return the response body
-    pub(crate) async fn get_raw(&self, path: &str) -> Result<Bytes, OpenAIError> {
+    /// Make a GET request to {path} and return the response body, reporting the progress of its download.
+    ///
+    /// Interrupted downloads are resumed up to [MAX_DOWNLOAD_RESUMES] times with HTTP range requests,
+    /// or started over when the server does not support them.
+    /// The content is verified against the digests of the full response, if the server sent any.
+    pub(crate) async fn get_raw_with_progress<F>(&self, path: &str, mut on_progress: F) -> Result<Bytes, OpenAIError>
+        where
+            F: FnMut(DownloadProgress),
+    {
+        let mut content = BytesMut::new();
+        let mut total = None;
+        let mut digests = vec![];
+        let mut resumes = 0;
+        loop {
+            let range_start = content.len();
+            let request_maker = || async {
+                let mut request = self.request(reqwest::Method::GET, path);
+                if range_start > 0 {
+                    request = request.header(RANGE, format!("bytes={range_start}-"));
+                }
+                Ok(request.build()?)
+            };
+            let response = self.execute_response(request_maker).await?;
+
+            if response.status() == reqwest::StatusCode::PARTIAL_CONTENT {
+                total = content_range_total(response.headers()).or(total);
+            } else {
+                content.clear();
+                total = response.content_length();
+                digests = checksum::expected_digests(response.headers());
+            }
+
+            let mut stream = response.bytes_stream();
+            let error = loop {
+                match self.timed(self.cancellable(async { Ok(stream.next().await) })).await? {
+                    Some(Ok(chunk)) => {
+                        content.extend_from_slice(&chunk);
+                        on_progress(DownloadProgress { received: content.len() as u64, total });
+                    }
+                    Some(Err(e)) => break e,
+                    None => {
+                        checksum::verify(&digests, &content)?;
+                        return Ok(content.freeze());
+                    }
+                }
+            };
+            if resumes == MAX_DOWNLOAD_RESUMES {
+                return Err(OpenAIError::Reqwest(error));
+            }
+            resumes += 1;
+            tracing::warn!("Resuming download at byte {} after error: {error}", content.len());
+        }
+    }
+
+    /// Make a POST request to {path} and return the response body
+    pub(crate) async fn post_raw<I>(&self, path: &str, request: I) -> Result<Bytes, OpenAIError>
+        where
+            I: Serialize,
+    {
+        let body = self.json_body(path, request)?;
         let request_maker = || async {
             Ok(self
-                .http_client
-                .get(self.config.url(path))
-                .query(&self.config.query())
-                .headers(self.config.headers())
+                .request(reqwest::Method::POST, path)
+                .json(&body)
                 .build()?)
         };
 
         self.execute_raw(request_maker).await
     }
 
-    /// Make a POST request to {path} and return the response body
-    pub(crate) async fn post_raw<I>(&self, path: &str, request: I) -> Result<Bytes, OpenAIError>
+    /// Make a POST request to {path} and stream the response body as it is received
+    pub(crate) async fn post_byte_stream<I>(&self, path: &str, request: I) -> Result<ByteStream, OpenAIError>
         where
             I: Serialize,
     {
+        let body = self.json_body(path, request)?;
         let request_maker = || async {
             Ok(self
-                .http_client
-                .post(self.config.url(path))
-                .query(&self.config.query())
-                .headers(self.config.headers())
-                .json(&request)
+                .request(reqwest::Method::POST, path)
+                .json(&body)
                 .build()?)
         };
 
-        self.execute_raw(request_maker).await
+        let response = self.execute_response(request_maker).await?;
+        let chunks = response.bytes_stream().map(|chunk| chunk.map_err(OpenAIError::Reqwest));
+        match self.cancellation.clone() {
+            Some(token) => Ok(Box::pin(cancel::until_cancelled(chunks, token))),
+            None => Ok(Box::pin(chunks)),
+        }
     }
 
     /// Make a POST request to {path} and deserialize the response body
@@ -245,13 +744,14 @@ impl<C: Config> Client<C> {
             I: Serialize,
             O: DeserializeOwned,
     {
+        #[cfg(feature = "request-lint")]
+        crate::lint::warn_request(path, &request);
+
+        let body = self.json_body(path, request)?;
         let request_maker = || async {
             Ok(self
-                .http_client
-                .post(self.config.url(path))
-                .query(&self.config.query())
-                .headers(self.config.headers())
-                .json(&request)
+                .request(reqwest::Method::POST, path)
+                .json(&body)
                 .build()?)
         };
 
@@ -265,13 +765,12 @@ impl<C: Config> Client<C> {
             F: Clone,
     {
         let request_maker = || async {
-            Ok(self
-                .http_client
-                .post(self.config.url(path))
-                .query(&self.config.query())
-                .headers(self.config.headers())
+            let mut request = self
+                .request(reqwest::Method::POST, path)
                 .multipart(async_convert::TryFrom::try_from(form.clone()).await?)
-                .build()?)
+                .build()?;
+            self.upload_progress.wrap(&mut request);
+            Ok(request)
         };
 
         self.execute_raw(request_maker).await
@@ -285,13 +784,12 @@ impl<C: Config> Client<C> {
             F: Clone,
     {
         let request_maker = || async {
-            Ok(self
-                .http_client
-                .post(self.config.url(path))
-                .query(&self.config.query())
-                .headers(self.config.headers())
+            let mut request = self
+                .request(reqwest::Method::POST, path)
                 .multipart(async_convert::TryFrom::try_from(form.clone()).await?)
-                .build()?)
+                .build()?;
+            self.upload_progress.wrap(&mut request);
+            Ok(request)
         };
 
         self.execute(request_maker).await
@@ -355,7 +853,7 @@ impl<C: Config> Client<C> {
     // }
 
     #[cfg(not(feature = "backoff"))]
-    /// Execute a HTTP request and retry on rate limit
+    /// Execute a HTTP request and retry as the [RetryPolicy] says
     ///
     /// request_maker serves one purpose: to be able to create request again
     /// to retry API call after getting rate limited. request_maker is async because
@@ -365,39 +863,179 @@ impl<C: Config> Client<C> {
             M: Fn() -> Fut,
             Fut: future::Future<Output=Result<reqwest::Request, OpenAIError>>,
     {
-        let client = self.http_client.clone();
+        let span = request_span();
+        let _in_flight = self.stats.in_flight_request();
+        let execute = async {
+            let response = self.send(request_maker, &span).await?;
+            let bytes = response.bytes().await.map_err(OpenAIError::Reqwest)?;
+            telemetry::record_usage(&span, &bytes);
+            Ok(bytes)
+        };
+        self.timed(self.cancellable(execute.instrument(span.clone()))).await
+    }
 
-        let request = request_maker().await?;
-        let response = client
-            .execute(request)
+    /// Execute a HTTP request like [Client::execute_raw], returning the successful response before reading its body
+    async fn execute_response<M, Fut>(&self, request_maker: M) -> Result<reqwest::Response, OpenAIError>
+        where
+            M: Fn() -> Fut,
+            Fut: future::Future<Output=Result<reqwest::Request, OpenAIError>>,
+    {
+        let span = request_span();
+        let _in_flight = self.stats.in_flight_request();
+        self.timed(self.cancellable(self.send(request_maker, &span).instrument(span.clone())))
             .await
-            .map_err(OpenAIError::Reqwest)?;
+    }
 
-        let status = response.status();
-        let bytes = response
-            .bytes()
-            .await
-            .map_err(OpenAIError::Reqwest)?;
-
-        // Deserialize response body from either error object or actual response object
-        if !status.is_success() {
-            let wrapped_error: WrappedError = serde_json::from_slice(bytes.as_ref())
-                .map_err(|e| map_deserialization_error(e, bytes.as_ref()))?;
-
-            if status.as_u16() == 429
-                // API returns 429 also when:
-                // "You exceeded your current quota, please check your plan and billing details."
-                && wrapped_error.error.r#type != Some("insufficient_quota".to_string())
-            {
-                // Rate limited retry...
-                tracing::warn!("Rate limited: {}", wrapped_error.error.message);
-                return Err(OpenAIError::ApiError(wrapped_error.error));
-            } else {
-                return Err(OpenAIError::ApiError(wrapped_error.error));
-            }
+    /// Spend a retry of the [RetryBudget], if any, recording what is left of it on `span`
+    fn spend_retry(&self, span: &tracing::Span) -> bool {
+        let Some(retry_budget) = &self.retry_budget else {
+            return true;
+        };
+        let spent = retry_budget.try_acquire();
+        if !spent {
+            tracing::warn!("Not retrying, the retry budget is exhausted");
+        }
+        telemetry::record_retry_budget(span, retry_budget.available());
+        spent
+    }
+
+    /// Run `future` until the token set by [Client::with_cancellation] is cancelled, if any
+    pub(crate) async fn cancellable<T, F>(&self, future: F) -> Result<T, OpenAIError>
+        where
+            F: future::Future<Output=Result<T, OpenAIError>>,
+    {
+        match &self.cancellation {
+            Some(token) => token.run(future).await?,
+            None => future.await,
         }
+    }
+
+    /// Run `future` until the timeout set by [Client::with_timeout] elapses, if any
+    async fn timed<T, F>(&self, future: F) -> Result<T, OpenAIError>
+        where
+            F: future::Future<Output=Result<T, OpenAIError>>,
+    {
+        let Some(timeout) = self.timeout else {
+            return future.await;
+        };
+        futures::pin_mut!(future);
+        match futures::future::select(future, Box::pin(sleep(timeout))).await {
+            futures::future::Either::Left((output, _)) => output,
+            futures::future::Either::Right(_) => Err(OpenAIError::Timeout(timeout)),
+        }
+    }
+
+    /// Keep the echo of `request`, about to be sent, as the last request if enabled with [Client::with_request_echo]
+    fn echo(&self, request: &reqwest::Request) -> Option<EchoedRequest> {
+        if !self.request_echo {
+            return None;
+        }
+        let echo = EchoedRequest::new(request);
+        *self.last_request.lock().unwrap() = Some(echo.clone());
+        Some(echo)
+    }
+
+    /// Count a helper polling the status of a resource in [Client::stats] until dropped
+    pub(crate) fn active_poller(&self) -> Active {
+        self.stats.active_poller()
+    }
+
+    /// This client without its cancellation token, to clean up the server side state of a cancelled helper
+    pub(crate) fn uncancellable(&self) -> Self {
+        Self {
+            cancellation: None,
+            ..self.clone()
+        }
+    }
+
+    /// Send a HTTP request and retry as the [RetryPolicy] says, recording it on `span`, a [request_span]
+    async fn send<M, Fut>(&self, request_maker: M, span: &tracing::Span) -> Result<reqwest::Response, OpenAIError>
+        where
+            M: Fn() -> Fut,
+            Fut: future::Future<Output=Result<reqwest::Request, OpenAIError>>,
+    {
+        let client = self.http_client.clone();
+
+        let started = Instant::now();
+        let mut attempt = 0;
+        let mut flex_refusals = 0;
+        loop {
+            attempt += 1;
+            let mut request = request_maker().await?;
+            if self.retry_policy.flex_fallback_after.map_or(false, |after| flex_refusals >= after) {
+                retry::fall_back_to_default_tier(&mut request);
+            }
+            let flex = retry::is_flex(&request);
+            self.check_api_key(&request)?;
+            self.interceptors.before_request(&mut request).await?;
+            if attempt == 1 {
+                telemetry::record_request(span, &request, false);
+            }
+            if let Some(rate_limiter) = &self.rate_limiter {
+                let _rate_limited = self.stats.rate_limited();
+                rate_limiter.acquire_for(&request).await;
+            }
+            self.stats.request_sent();
+            let echo = self.echo(&request);
+            let sent = Instant::now();
+            let response = match client.execute(request).await {
+                Ok(response) => response,
+                Err(e) => {
+                    let retryable = self.retry_policy.retry_transport_errors
+                        && (e.is_timeout() || e.is_request());
+                    match self.retry_policy.next_delay(attempt, started.elapsed(), None) {
+                        Some(delay) if retryable && self.spend_retry(span) => {
+                            tracing::warn!("Retrying in {delay:?} after request error: {e}");
+                            self.stats.retried();
+                            sleep(delay).await;
+                            continue;
+                        }
+                        _ => return Err(OpenAIError::Reqwest(e)),
+                    }
+                }
+            };
+            if let Some(echo) = echo {
+                *self.last_request.lock().unwrap() = Some(echo.answered(&response));
+            }
+            let timing = ResponseTiming::from_headers(response.headers(), sent.elapsed());
+            self.interceptors.after_response(&response).await?;
+            telemetry::record_response(span, &response, attempt, started);
+            telemetry::record_timing(span, &timing);
+            *self.response_timing.lock().unwrap() = Some(timing);
+            if let Some(rate_limit) = RateLimit::from_headers(response.headers()) {
+                *self.rate_limit.lock().unwrap() = Some(rate_limit);
+            }
+
+            let status = response.status();
+            if status.is_success() {
+                return Ok(response);
+            }
 
-        Ok(bytes)
+            // Deserialize the error object of the response body
+            let headers = response.headers().clone();
+            let bytes = response
+                .bytes()
+                .await
+                .map_err(OpenAIError::Reqwest)?;
+            let error = response_error(status, &headers, &bytes);
+
+            if flex && RetryPolicy::is_resource_unavailable(status, &error) {
+                flex_refusals += 1;
+            }
+            if self.retry_policy.is_retryable(status, &error) {
+                let delay = self.retry_policy.next_delay(attempt, started.elapsed(), Some(&headers));
+                if let Some(delay) = delay.filter(|_| self.spend_retry(span)) {
+                    tracing::warn!("Retrying in {delay:?} after {status}: {}", error.message);
+                    if flex && self.retry_policy.flex_fallback_after == Some(flex_refusals) {
+                        tracing::warn!("Falling back to the default tier after {flex_refusals} flex attempts refused as resource unavailable");
+                    }
+                    self.stats.retried();
+                    sleep(delay).await;
+                    continue;
+                }
+            }
+            return Err(OpenAIError::ApiError(error));
+        }
     }
 
     /// Execute a HTTP request and retry on rate limit
@@ -424,21 +1062,19 @@ impl<C: Config> Client<C> {
         &self,
         path: &str,
         request: I,
-    ) -> OpenAIEventStream<O>
+    ) -> Result<OpenAIEventStream<O>, OpenAIError>
         where
             I: Serialize,
             O: DeserializeOwned + Send + 'static,
     {
-        let event_source = self
-            .http_client
-            .post(self.config.url(path))
-            .query(&self.config.query())
-            .headers(self.config.headers())
-            .json(&request)
-            .eventsource()
-            .unwrap();
+        #[cfg(feature = "request-lint")]
+        crate::lint::warn_request(path, &request);
+
+        let request_builder = self
+            .request(reqwest::Method::POST, path)
+            .json(&self.json_body(path, request)?);
 
-        OpenAIEventStream::new(event_source)
+        Ok(OpenAIEventStream::new(self.event_source(request_builder).await?))
     }
 
     pub(crate) async fn post_stream_mapped_raw_events<I, O>(
@@ -446,21 +1082,19 @@ impl<C: Config> Client<C> {
         path: &str,
         request: I,
         event_mapper: impl Fn(eventsource_stream::Event) -> Result<O, OpenAIError> + Send + 'static,
-    ) -> OpenAIEventMappedStream<O>
+    ) -> Result<OpenAIEventMappedStream<O>, OpenAIError>
         where
             I: Serialize,
             O: DeserializeOwned + Send + 'static
     {
-        let event_source = self
-            .http_client
-            .post(self.config.url(path))
-            .query(&self.config.query())
-            .headers(self.config.headers())
-            .json(&request)
-            .eventsource()
-            .unwrap();
+        #[cfg(feature = "request-lint")]
+        crate::lint::warn_request(path, &request);
 
-        OpenAIEventMappedStream::new(event_source, event_mapper)
+        let request_builder = self
+            .request(reqwest::Method::POST, path)
+            .json(&self.json_body(path, request)?);
+
+        Ok(OpenAIEventMappedStream::new(self.event_source(request_builder).await?, event_mapper))
     }
 
     /// Make HTTP GET request to receive SSE
@@ -468,43 +1102,576 @@ impl<C: Config> Client<C> {
         &self,
         path: &str,
         query: &Q,
-    ) -> OpenAIEventStream<O>
+    ) -> Result<OpenAIEventStream<O>, OpenAIError>
         where
             Q: Serialize + ?Sized,
             O: DeserializeOwned + Send + 'static,
     {
-        let event_source = self
-            .http_client
-            .get(self.config.url(path))
-            .query(query)
-            .query(&self.config.query())
-            .headers(self.config.headers())
-            .eventsource()
-            .unwrap();
+        let request_builder = self
+            .request(reqwest::Method::GET, path)
+            .query(query);
+
+        Ok(OpenAIEventStream::new(self.event_source(request_builder).await?))
+    }
+
+    /// Open an SSE stream of the request, once intercepted, along with the [request_span] it is recorded on
+    async fn event_source(&self, request_builder: reqwest::RequestBuilder) -> Result<TracedEventSource, OpenAIError> {
+        let span = request_span();
+        let mut request = request_builder.build()?;
+        self.check_api_key(&request)?;
+        let accept_encoding = match self.identity_stream_encoding {
+            true => Some("identity".to_string()),
+            false => self.stream_decoders.accept_encoding(),
+        };
+        if let Some(accept_encoding) = accept_encoding.and_then(|value| HeaderValue::from_str(&value).ok()) {
+            request.headers_mut().insert(ACCEPT_ENCODING, accept_encoding);
+        }
+        self.interceptors.before_request(&mut request).await?;
+        telemetry::record_request(&span, &request, true);
+        if let Some(rate_limiter) = &self.rate_limiter {
+            let _rate_limited = self.stats.rate_limited();
+            self.cancellable(async {
+                rate_limiter.acquire_for(&request).await;
+                Ok(())
+            })
+            .await?;
+        }
+        let recording = self
+            .stream_recorder
+            .as_ref()
+            .map(|recorder| recorder.start(request.url().path()));
+        self.stats.request_sent();
+        self.echo(&request);
+        Ok(TracedEventSource {
+            events: sse_events(
+                self.http_client.clone(),
+                request,
+                self.stream_decoders.clone(),
+                self.interceptors.clone(),
+                self.response_timing.clone(),
+                span.clone(),
+            ),
+            span,
+            started: Instant::now(),
+            recording,
+            lenient: self.lenient_streaming,
+            cancelled: self.cancellation.as_ref().map(CancellationToken::cancelled),
+            idle_timeout: self.timeout.map(IdleTimeout::new),
+            watchdog: self.stream_watchdog.map(StallWatchdog::new),
+            active: Some(self.stats.active_stream()),
+        })
+    }
+}
+
+/// Overrides of the options of a client for one call, see [Client::with_request_options]
+///
+/// ```
+/// use std::time::Duration;
+/// use async_openai_wasm::RequestOptions;
+/// use reqwest::header::HeaderValue;
+///
+/// let options = RequestOptions::new()
+///     .with_timeout(Duration::from_secs(10))
+///     .with_header("x-request-source", HeaderValue::from_static("batch"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RequestOptions {
+    /// Timeout of the call instead of the one of the client, see [Client::with_timeout]
+    pub timeout: Option<Duration>,
+    /// Headers of the request, replacing the ones of the config and client with the same names
+    pub headers: HeaderMap,
+    /// Token aborting the call once cancelled, along with the one of the client, see [Client::with_cancellation]
+    pub cancellation: Option<CancellationToken>,
+}
+
+impl RequestOptions {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// See [RequestOptions::timeout]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// See [RequestOptions::cancellation]
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Set the header `name` of the request to `value`
+    pub fn with_header<K: IntoHeaderName>(mut self, name: K, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+}
+
+
+/// Builder of a [Client], combining its config, HTTP client options, retries, interceptors and defaults
+/// in one chain validated by [ClientBuilder::build].
+///
+/// ```
+/// use std::time::Duration;
+/// use async_openai_wasm::{retry::RetryPolicy, Client};
+///
+/// let client = Client::builder()
+///     .api_key("sk-...")
+///     .timeout(Duration::from_secs(30))
+///     .retry_policy(RetryPolicy::default())
+///     .default_user("user-1234")
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug)]
+pub struct ClientBuilder<C: Config> {
+    config: C,
+    http_client: Option<reqwest::Client>,
+    user_agent: Option<String>,
+    timeout: Option<Duration>,
+    stream_watchdog: Option<Duration>,
+    #[cfg(not(target_arch = "wasm32"))]
+    connect_timeout: Option<std::time::Duration>,
+    default_user: Option<String>,
+    request_limits: Option<RequestLimits>,
+    interceptors: Interceptors,
+    migrate_legacy_functions: bool,
+    retry_policy: RetryPolicy,
+    retry_budget: Option<RetryBudget>,
+    upload_progress: UploadProgressHook,
+    rate_limiter: Option<RateLimiter>,
+    stream_recorder: Option<StreamRecorder>,
+    lenient_streaming: bool,
+    stream_decoders: ContentDecoders,
+    identity_stream_encoding: bool,
+    request_echo: bool,
+    cancellation: Option<CancellationToken>,
+}
+
+impl Default for ClientBuilder<OpenAIConfig> {
+    fn default() -> Self {
+        Self::new(OpenAIConfig::default())
+    }
+}
+
+impl ClientBuilder<OpenAIConfig> {
+    pub fn api_key<S: Into<String>>(mut self, api_key: S) -> Self {
+        self.config = self.config.with_api_key(api_key);
+        self
+    }
+
+    pub fn api_base<S: Into<String>>(mut self, api_base: S) -> Self {
+        self.config = self.config.with_api_base(api_base);
+        self
+    }
+
+    pub fn org_id<S: Into<String>>(mut self, org_id: S) -> Self {
+        self.config = self.config.with_org_id(org_id);
+        self
+    }
+
+    pub fn project_id<S: Into<String>>(mut self, project_id: S) -> Self {
+        self.config = self.config.with_project_id(project_id);
+        self
+    }
+}
+
+impl<C: Config> ClientBuilder<C> {
+    pub fn new(config: C) -> Self {
+        Self {
+            config,
+            http_client: None,
+            user_agent: None,
+            timeout: None,
+            stream_watchdog: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            connect_timeout: None,
+            default_user: None,
+            request_limits: None,
+            interceptors: Default::default(),
+            migrate_legacy_functions: false,
+            retry_policy: RetryPolicy::none(),
+            retry_budget: None,
+            upload_progress: Default::default(),
+            rate_limiter: None,
+            stream_recorder: None,
+            lenient_streaming: false,
+            stream_decoders: Default::default(),
+            identity_stream_encoding: false,
+            request_echo: false,
+            cancellation: None,
+        }
+    }
+
+    /// Use `config`, e.g. an [AzureConfig](crate::config::AzureConfig), keeping the other settings
+    pub fn config<D: Config>(self, config: D) -> ClientBuilder<D> {
+        ClientBuilder {
+            config,
+            http_client: self.http_client,
+            user_agent: self.user_agent,
+            timeout: self.timeout,
+            stream_watchdog: self.stream_watchdog,
+            #[cfg(not(target_arch = "wasm32"))]
+            connect_timeout: self.connect_timeout,
+            default_user: self.default_user,
+            request_limits: self.request_limits,
+            interceptors: self.interceptors,
+            migrate_legacy_functions: self.migrate_legacy_functions,
+            retry_policy: self.retry_policy,
+            retry_budget: self.retry_budget,
+            upload_progress: self.upload_progress,
+            rate_limiter: self.rate_limiter,
+            stream_recorder: self.stream_recorder,
+            lenient_streaming: self.lenient_streaming,
+            stream_decoders: self.stream_decoders,
+            identity_stream_encoding: self.identity_stream_encoding,
+            request_echo: self.request_echo,
+            cancellation: self.cancellation,
+        }
+    }
+
+    /// HTTP client to make requests with, instead of one built from the options of this builder
+    pub fn http_client(mut self, http_client: reqwest::Client) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    pub fn user_agent<S: Into<String>>(mut self, user_agent: S) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// See [Client::with_timeout]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// See [Client::with_stream_watchdog]
+    pub fn stream_watchdog(mut self, after: Duration) -> Self {
+        self.stream_watchdog = Some(after);
+        self
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn connect_timeout(mut self, connect_timeout: std::time::Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// See [Client::with_retry_policy]
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// See [Client::with_retry_budget]
+    pub fn retry_budget(mut self, retry_budget: RetryBudget) -> Self {
+        self.retry_budget = Some(retry_budget);
+        self
+    }
+
+    /// See [Client::with_rate_limit]
+    pub fn rate_limit(self, requests_per_minute: u32, tokens_per_minute: u32) -> Self {
+        self.rate_limiter(RateLimiter::new(requests_per_minute, tokens_per_minute))
+    }
+
+    /// See [Client::with_rate_limiter]
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// See [Client::with_stream_recorder]
+    pub fn stream_recorder(mut self, recorder: StreamRecorder) -> Self {
+        self.stream_recorder = Some(recorder);
+        self
+    }
+
+    /// See [Client::with_lenient_streaming]
+    pub fn lenient_streaming(mut self, lenient_streaming: bool) -> Self {
+        self.lenient_streaming = lenient_streaming;
+        self
+    }
 
-        OpenAIEventStream::new(event_source)
+    /// See [Client::with_request_echo]
+    pub fn request_echo(mut self, request_echo: bool) -> Self {
+        self.request_echo = request_echo;
+        self
+    }
+
+    /// See [Client::with_stream_decoder]
+    pub fn stream_decoder<F>(mut self, encoding: &str, decoder: F) -> Self
+    where
+        F: Fn() -> Box<dyn ContentDecoder> + Send + Sync + 'static,
+    {
+        self.stream_decoders.insert(encoding, Arc::new(decoder));
+        self
+    }
+
+    /// See [Client::with_identity_stream_encoding]
+    pub fn identity_stream_encoding(mut self, identity_stream_encoding: bool) -> Self {
+        self.identity_stream_encoding = identity_stream_encoding;
+        self
+    }
+
+    /// See [Client::with_cancellation]
+    pub fn cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// See [Client::with_interceptor]
+    pub fn interceptor<I: RequestInterceptor + 'static>(mut self, interceptor: I) -> Self {
+        self.interceptors.push(Arc::new(interceptor));
+        self
+    }
+
+    /// See [Client::with_default_user]
+    pub fn default_user<S: Into<String>>(mut self, user: S) -> Self {
+        self.default_user = Some(user.into());
+        self
+    }
+
+    /// See [Client::with_request_limits]
+    pub fn request_limits(mut self, request_limits: RequestLimits) -> Self {
+        self.request_limits = Some(request_limits);
+        self
+    }
+
+    /// See [Client::without_request_limits]
+    pub fn without_request_limits(mut self) -> Self {
+        self.request_limits = Some(RequestLimits::new());
+        self
+    }
+
+    /// See [Client::with_legacy_functions_migration]
+    pub fn legacy_functions_migration(mut self, migrate_legacy_functions: bool) -> Self {
+        self.migrate_legacy_functions = migrate_legacy_functions;
+        self
+    }
+
+    /// See [Client::with_upload_progress]
+    pub fn upload_progress<F>(mut self, callback: F) -> Self
+        where
+            F: Fn(UploadProgress) + Send + Sync + 'static,
+    {
+        self.upload_progress = UploadProgressHook::new(callback);
+        self
+    }
+
+    /// Check the settings and build the client.
+    ///
+    /// Fails with [OpenAIError::InvalidArgument] when the API base is not a URL, the API key is malformed,
+    /// the retry policy cannot make any attempt, or HTTP client options are set along with an
+    /// [http_client](ClientBuilder::http_client).
+    pub fn build(self) -> Result<Client<C>, OpenAIError> {
+        let invalid = |message: &str| Err(OpenAIError::InvalidArgument(message.into()));
+
+        let api_key = self.config.api_key().expose_secret();
+        if api_key.contains(|c: char| c.is_whitespace() || c.is_control()) {
+            return invalid("api key must not contain whitespace");
+        }
+        if let Some(kind) = self.config.api_key_kind() {
+            if api_key.len() == kind.prefix().len() {
+                return Err(OpenAIError::InvalidArgument(format!(
+                    "api key {}... is missing its secret after the prefix",
+                    kind.prefix()
+                )));
+            }
+        }
+
+        if let Err(e) = reqwest::Url::parse(self.config.api_base()) {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "invalid api base {:?}: {e}",
+                self.config.api_base()
+            )));
+        }
+        let policy = &self.retry_policy;
+        if policy.max_attempts == 0 {
+            return invalid("retry policy max_attempts must be at least 1");
+        }
+        if !policy.multiplier.is_finite() || policy.multiplier < 1.0 {
+            return invalid("retry policy multiplier must be a finite number of at least 1");
+        }
+        if policy.initial_backoff > policy.max_backoff {
+            return invalid("retry policy initial_backoff must not exceed max_backoff");
+        }
+        if policy.flex_fallback_after == Some(0) {
+            return invalid("retry policy flex_fallback_after must be at least 1");
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let has_http_options = self.user_agent.is_some() || self.connect_timeout.is_some();
+        #[cfg(target_arch = "wasm32")]
+        let has_http_options = self.user_agent.is_some();
+
+        let http_client = match self.http_client {
+            Some(_) if has_http_options => {
+                return invalid("user_agent and connect_timeout cannot be set along with a custom http_client; set them on the http client instead");
+            }
+            Some(http_client) => http_client,
+            None => {
+                let mut builder = reqwest::Client::builder();
+                if let Some(user_agent) = self.user_agent {
+                    builder = builder.user_agent(user_agent);
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    if let Some(connect_timeout) = self.connect_timeout {
+                        builder = builder.connect_timeout(connect_timeout);
+                    }
+                }
+                builder.build()?
+            }
+        };
+
+        Ok(Client {
+            http_client,
+            config: self.config,
+            default_user: self.default_user,
+            request_limits: self.request_limits,
+            interceptors: self.interceptors,
+            migrate_legacy_functions: self.migrate_legacy_functions,
+            retry_policy: self.retry_policy,
+            retry_budget: self.retry_budget,
+            upload_progress: self.upload_progress,
+            rate_limit: Default::default(),
+            response_timing: Default::default(),
+            rate_limiter: self.rate_limiter,
+            stream_recorder: self.stream_recorder,
+            lenient_streaming: self.lenient_streaming,
+            stream_decoders: self.stream_decoders,
+            identity_stream_encoding: self.identity_stream_encoding,
+            request_echo: self.request_echo,
+            last_request: Default::default(),
+            cancellation: self.cancellation,
+            timeout: self.timeout,
+            stream_watchdog: self.stream_watchdog,
+            headers: HeaderMap::new(),
+            stats: Default::default(),
+        })
     }
 }
 
+/// Length of the whole content from the `Content-Range` header of a partial response, e.g. `bytes 200-999/1000`
+fn content_range_total(headers: &HeaderMap) -> Option<u64> {
+    let content_range = headers.get(reqwest::header::CONTENT_RANGE)?.to_str().ok()?;
+    content_range.rsplit_once('/')?.1.trim().parse().ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+type SseEvents = futures::stream::BoxStream<'static, Result<eventsource_stream::Event, OpenAIError>>;
+#[cfg(target_arch = "wasm32")]
+type SseEvents = futures::stream::LocalBoxStream<'static, Result<eventsource_stream::Event, OpenAIError>>;
+
+/// Events of the response to `request`, sent once the stream is first polled, with its body decoded by `decoders`.
+///
+/// The response is handed to `interceptors` once its status and headers are received. Its status, request id
+/// and timings are recorded on `span`, and its timings stored in `timing`.
+fn sse_events(
+    http_client: reqwest::Client,
+    request: reqwest::Request,
+    decoders: ContentDecoders,
+    interceptors: Interceptors,
+    timing: Arc<Mutex<Option<ResponseTiming>>>,
+    span: tracing::Span,
+) -> SseEvents {
+    let events = futures::stream::once(async move {
+        let sent = Instant::now();
+        let response = http_client
+            .execute(request)
+            .await
+            .map_err(|e| OpenAIError::stream_error("failed to send the request", e))?;
+        let response_timing = ResponseTiming::from_headers(response.headers(), sent.elapsed());
+        interceptors.after_response(&response).await?;
+        telemetry::record_response(&span, &response, 1, sent);
+        telemetry::record_timing(&span, &response_timing);
+        *timing.lock().unwrap() = Some(response_timing);
+        let status = response.status();
+        if status != reqwest::StatusCode::OK {
+            let headers = response.headers().clone();
+            let body = response
+                .bytes()
+                .await
+                .map_err(|e| OpenAIError::stream_error("failed to read the error response", e))?;
+            return Err(OpenAIError::ApiError(response_error(status, &headers, &body)));
+        }
+        let content_type = response.headers().get(CONTENT_TYPE).cloned().unwrap_or(HeaderValue::from_static(""));
+        let is_event_stream = content_type
+            .to_str()
+            .map(|value| value.trim_start().to_ascii_lowercase().starts_with("text/event-stream"))
+            .unwrap_or(false);
+        if !is_event_stream {
+            return Err(OpenAIError::StreamError {
+                message: format!("Invalid header value: {content_type:?}"),
+                source: None,
+            });
+        }
+        let headers = response.headers().clone();
+        let body = Box::pin(response.bytes_stream().map(move |chunk| {
+            chunk.map_err(|e| OpenAIError::stream_error("failed to read the stream", e))
+        }));
+        let events = decoders.decode(&headers, body)?.eventsource().map_err(|e| match e {
+            EventStreamError::Transport(e) => e,
+            e => OpenAIError::stream_error("invalid stream data", e),
+        });
+        Ok(events)
+    })
+    .try_flatten();
+    Box::pin(events)
+}
+
+/// SSE stream of a request and the span its events are recorded on
+pub(crate) struct TracedEventSource {
+    events: SseEvents,
+    span: tracing::Span,
+    started: Instant,
+    recording: Option<StreamRecording>,
+    /// Whether invalid events are skipped, see [Client::with_lenient_streaming]
+    lenient: bool,
+    /// Cancellation ending the stream, see [Client::with_cancellation]
+    cancelled: Option<WaitForCancellation>,
+    /// Timeout between events ending the stream, see [Client::with_timeout]
+    idle_timeout: Option<IdleTimeout>,
+    /// Timeout between events once the stream started, see [Client::with_stream_watchdog]
+    watchdog: Option<StallWatchdog>,
+    /// Count of the stream in [Client::stats] until it ends
+    active: Option<Active>,
+}
 
 /// Request which responds with SSE.
 /// [server-sent events](https://developer.mozilla.org/en-US/docs/Web/API/Server-sent_events/Using_server-sent_events#event_stream_format)
 #[pin_project]
 pub struct OpenAIEventStream<O: DeserializeOwned + Send + 'static> {
     #[pin]
-    stream: Filter<EventSource, future::Ready<bool>, fn(&Result<Event, reqwest_eventsource::Error>) -> future::Ready<bool>>,
+    stream: SseEvents,
     done: bool,
+    span: tracing::Span,
+    started: Instant,
+    recording: Option<StreamRecording>,
+    lenient: bool,
+    cancelled: Option<WaitForCancellation>,
+    idle_timeout: Option<IdleTimeout>,
+    watchdog: Option<StallWatchdog>,
+    active: Option<Active>,
     _phantom_data: PhantomData<O>,
 }
 
 impl<O: DeserializeOwned + Send + 'static> OpenAIEventStream<O> {
-    pub(crate) fn new(event_source: EventSource) -> Self {
+    pub(crate) fn new(event_source: TracedEventSource) -> Self {
         Self {
-            stream: event_source.filter(|result|
-                // filter out the first event which is always Event::Open
-                future::ready(!(result.is_ok() && result.as_ref().unwrap().eq(&Event::Open)))
-            ),
+            stream: event_source.events,
             done: false,
+            span: event_source.span,
+            started: event_source.started,
+            recording: event_source.recording,
+            lenient: event_source.lenient,
+            cancelled: event_source.cancelled,
+            idle_timeout: event_source.idle_timeout,
+            watchdog: event_source.watchdog,
+            active: event_source.active,
             _phantom_data: PhantomData,
         }
     }
@@ -514,43 +1681,77 @@ impl<O: DeserializeOwned + Send + 'static> Stream for OpenAIEventStream<O> {
     type Item = Result<O, OpenAIError>;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        let this = self.project();
+        let mut this = self.project();
         if *this.done {
+            this.active.take();
             return Poll::Ready(None);
         }
-        let stream: Pin<&mut _> = this.stream;
-        match stream.poll_next(cx) {
-            Poll::Ready(response) => {
-                match response {
-                    None => Poll::Ready(None), // end of the stream
-                    Some(result) => match result {
-                        Ok(event) => match event {
-                            Event::Open => unreachable!(), // it has been filtered out
-                            Event::Message(message) => {
+        let _entered = this.span.enter();
+        if cancel::poll_cancelled(this.cancelled, cx) {
+            *this.done = true;
+            this.active.take();
+            telemetry::record_duration(this.span, *this.started);
+            return Poll::Ready(Some(Err(OpenAIError::Cancelled)));
+        }
+        let poll = loop {
+            let poll = match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(response) => {
+                    match response {
+                        None => {
+                            // streams without a [DONE] message, like Responses, end when the server closes them
+                            *this.done = true;
+                            Poll::Ready(None)
+                        }
+                        Some(result) => match result {
+                            Ok(message) => {
+                                telemetry::record_usage(this.span, message.data.as_bytes());
+                                if let Some(recording) = this.recording {
+                                    recording.record(&message);
+                                }
                                 if message.data == "[DONE]" {
                                     *this.done = true;
                                     Poll::Ready(None)  // end of the stream, defined by OpenAI
                                 } else {
                                     // deserialize the data
                                     match serde_json::from_str::<O>(&message.data) {
-                                        Err(e) => {
-                                            *this.done = true;
-                                            Poll::Ready(Some(Err(map_deserialization_error(e, &message.data.as_bytes()))))
-                                        }
+                                        Err(e) => Poll::Ready(Some(Err(invalid_event(&message, e.to_string())))),
                                         Ok(output) => Poll::Ready(Some(Ok(output))),
                                     }
                                 }
                             }
-                        }
-                        Err(e) => {
-                            *this.done = true;
-                            Poll::Ready(Some(Err(OpenAIError::StreamError(e.to_string()))))
+                            Err(e) => Poll::Ready(Some(Err(e))),
                         }
                     }
                 }
+                Poll::Pending => Poll::Pending
+            };
+            if let Some(poll) = settle(poll, *this.lenient, this.done) {
+                break poll;
+            }
+        };
+        if let Some(idle_timeout) = this.idle_timeout {
+            if poll.is_ready() {
+                idle_timeout.restart();
+            } else if idle_timeout.poll_elapsed(cx) {
+                *this.done = true;
+                this.active.take();
+                telemetry::record_duration(this.span, *this.started);
+                return Poll::Ready(Some(Err(OpenAIError::Timeout(idle_timeout.timeout()))));
             }
-            Poll::Pending => Poll::Pending
         }
+        if let Some(watchdog) = this.watchdog {
+            if watchdog.poll_stalled(poll.is_ready(), cx) {
+                *this.done = true;
+                this.active.take();
+                telemetry::record_duration(this.span, *this.started);
+                return Poll::Ready(Some(Err(OpenAIError::StreamStalled(watchdog.after()))));
+            }
+        }
+        if *this.done || matches!(poll, Poll::Ready(None)) {
+            this.active.take();
+            telemetry::record_duration(this.span, *this.started);
+        }
+        poll
     }
 }
 
@@ -559,24 +1760,37 @@ pub struct OpenAIEventMappedStream<O>
     where O: Send + 'static
 {
     #[pin]
-    stream: Filter<EventSource, future::Ready<bool>, fn(&Result<Event, reqwest_eventsource::Error>) -> future::Ready<bool>>,
+    stream: SseEvents,
     event_mapper: Box<dyn Fn(eventsource_stream::Event) -> Result<O, OpenAIError> + Send + 'static>,
     done: bool,
+    span: tracing::Span,
+    started: Instant,
+    recording: Option<StreamRecording>,
+    lenient: bool,
+    cancelled: Option<WaitForCancellation>,
+    idle_timeout: Option<IdleTimeout>,
+    watchdog: Option<StallWatchdog>,
+    active: Option<Active>,
     _phantom_data: PhantomData<O>,
 }
 
 impl<O> OpenAIEventMappedStream<O>
     where O: Send + 'static
 {
-    pub(crate) fn new<M>(event_source: EventSource, event_mapper: M) -> Self
+    pub(crate) fn new<M>(event_source: TracedEventSource, event_mapper: M) -> Self
         where M: Fn(eventsource_stream::Event) -> Result<O, OpenAIError> + Send + 'static {
         Self {
-            stream: event_source.filter(|result|
-                // filter out the first event which is always Event::Open
-                future::ready(!(result.is_ok() && result.as_ref().unwrap().eq(&Event::Open)))
-            ),
+            stream: event_source.events,
             done: false,
             event_mapper: Box::new(event_mapper),
+            span: event_source.span,
+            started: event_source.started,
+            recording: event_source.recording,
+            lenient: event_source.lenient,
+            cancelled: event_source.cancelled,
+            idle_timeout: event_source.idle_timeout,
+            watchdog: event_source.watchdog,
+            active: event_source.active,
             _phantom_data: PhantomData,
         }
     }
@@ -589,38 +1803,108 @@ impl<O> Stream for OpenAIEventMappedStream<O>
     type Item = Result<O, OpenAIError>;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        let this = self.project();
+        let mut this = self.project();
         if *this.done {
+            this.active.take();
             return Poll::Ready(None);
         }
-        let stream: Pin<&mut _> = this.stream;
-        match stream.poll_next(cx) {
-            Poll::Ready(response) => {
-                match response {
-                    None => Poll::Ready(None), // end of the stream
-                    Some(result) => match result {
-                        Ok(event) => match event {
-                            Event::Open => unreachable!(), // it has been filtered out
-                            Event::Message(message) => {
+        let _entered = this.span.enter();
+        if cancel::poll_cancelled(this.cancelled, cx) {
+            *this.done = true;
+            this.active.take();
+            telemetry::record_duration(this.span, *this.started);
+            return Poll::Ready(Some(Err(OpenAIError::Cancelled)));
+        }
+        let poll = loop {
+            let poll = match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(response) => {
+                    match response {
+                        None => {
+                            *this.done = true;
+                            Poll::Ready(None)
+                        }
+                        Some(result) => match result {
+                            Ok(message) => {
+                                telemetry::record_usage(this.span, message.data.as_bytes());
+                                if let Some(recording) = this.recording {
+                                    recording.record(&message);
+                                }
                                 if message.data == "[DONE]" {
                                     *this.done = true;
                                 }
-                                let response = (this.event_mapper)(message);
-                                match response {
+                                match (this.event_mapper)(message.clone()) {
                                     Ok(output) => Poll::Ready(Some(Ok(output))),
-                                    Err(_) => Poll::Ready(None)
+                                    Err(OpenAIError::JSONDeserialize(e)) => {
+                                        Poll::Ready(Some(Err(invalid_event(&message, e.to_string()))))
+                                    }
+                                    Err(e) => Poll::Ready(Some(Err(e))),
                                 }
                             }
-                        }
-                        Err(e) => {
-                            *this.done = true;
-                            Poll::Ready(Some(Err(OpenAIError::StreamError(e.to_string()))))
+                            Err(e) => Poll::Ready(Some(Err(e))),
                         }
                     }
                 }
+                Poll::Pending => Poll::Pending
+            };
+            if let Some(poll) = settle(poll, *this.lenient, this.done) {
+                break poll;
+            }
+        };
+        if let Some(idle_timeout) = this.idle_timeout {
+            if poll.is_ready() {
+                idle_timeout.restart();
+            } else if idle_timeout.poll_elapsed(cx) {
+                *this.done = true;
+                this.active.take();
+                telemetry::record_duration(this.span, *this.started);
+                return Poll::Ready(Some(Err(OpenAIError::Timeout(idle_timeout.timeout()))));
+            }
+        }
+        if let Some(watchdog) = this.watchdog {
+            if watchdog.poll_stalled(poll.is_ready(), cx) {
+                *this.done = true;
+                this.active.take();
+                telemetry::record_duration(this.span, *this.started);
+                return Poll::Ready(Some(Err(OpenAIError::StreamStalled(watchdog.after()))));
+            }
+        }
+        if *this.done || matches!(poll, Poll::Ready(None)) {
+            this.active.take();
+            telemetry::record_duration(this.span, *this.started);
+        }
+        poll
+    }
+}
+
+/// [OpenAIError::InvalidStreamEvent] of `message`
+fn invalid_event(message: &eventsource_stream::Event, reason: String) -> OpenAIError {
+    InvalidStreamEvent {
+        event: message.event.clone(),
+        data: message.data.clone(),
+        reason,
+    }
+    .into()
+}
+
+/// `poll` of a stream, which ends on errors, or `None` to poll again when a `lenient` stream skips an invalid event
+fn settle<O>(
+    poll: Poll<Option<Result<O, OpenAIError>>>,
+    lenient: bool,
+    done: &mut bool,
+) -> Option<Poll<Option<Result<O, OpenAIError>>>> {
+    match poll {
+        Poll::Ready(Some(Err(OpenAIError::InvalidStreamEvent(event)))) if lenient && !*done => {
+            tracing::warn!("skipping {event}");
+            None
+        }
+        Poll::Ready(Some(Err(error))) => {
+            if let OpenAIError::InvalidStreamEvent(event) = &error {
+                tracing::error!("{event}");
             }
-            Poll::Pending => Poll::Pending
+            *done = true;
+            Some(Poll::Ready(Some(Err(error))))
         }
+        poll => Some(poll),
     }
 }
 
@@ -672,3 +1956,26 @@ impl<O> Stream for OpenAIEventMappedStream<O>
 //
 //     Box::pin(tokio_stream::wrappers::UnboundedReceiverStream::new(rx))
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_user_fills_in_unset_users_only() {
+        let client = Client::new().with_default_user("user-1");
+        assert_eq!(client.default_user(), Some("user-1"));
+
+        let mut user = None;
+        client.inject_default_user(&mut user);
+        assert_eq!(user.as_deref(), Some("user-1"));
+
+        let mut user = Some("user-2".to_string());
+        client.inject_default_user(&mut user);
+        assert_eq!(user.as_deref(), Some("user-2"));
+
+        let mut user = None;
+        Client::new().inject_default_user(&mut user);
+        assert_eq!(user, None);
+    }
+}
@@ -0,0 +1,205 @@
+//! Compatibility of chat completion requests with providers, to catch in CI the requests of a prompt library
+//! that a provider would reject or silently ignore, e.g. penalties sent to reasoning models.
+//!
+//! A [Provider] lists what it supports of requests: the fields it accepts, the ones its reasoning models
+//! and its vision requests do not, and the [RequestLimits] of its requests. The registry has
+//! [Provider::openai], [Provider::azure] and [Provider::ollama], and is data to adjust to other providers.
+//!
+//! ```
+//! use async_openai_wasm::{
+//!     compat::{check_compat, Provider},
+//!     types::{ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs},
+//! };
+//!
+//! let request = CreateChatCompletionRequestArgs::default()
+//!     .model("o3-mini")
+//!     .messages([ChatCompletionRequestUserMessageArgs::default()
+//!         .content("Hello!")
+//!         .build()
+//!         .unwrap()
+//!         .into()])
+//!     .presence_penalty(0.5)
+//!     .build()
+//!     .unwrap();
+//!
+//! let incompatibilities = check_compat(&request, &Provider::openai());
+//! assert_eq!(incompatibilities[0].field, "presence_penalty");
+//! ```
+use std::fmt::Display;
+
+use serde_json::Value;
+
+use crate::{types::CreateChatCompletionRequest, validation::RequestLimits};
+
+/// Sampling fields which reasoning models reject
+const REASONING_MODEL_UNSUPPORTED_FIELDS: [&str; 7] = [
+    "temperature",
+    "top_p",
+    "presence_penalty",
+    "frequency_penalty",
+    "logit_bias",
+    "logprobs",
+    "top_logprobs",
+];
+
+/// What a provider supports of chat completion requests
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Provider {
+    /// Name of the provider in the messages of incompatibilities
+    pub name: String,
+    /// Top level fields of requests the provider accepts, all of them when `None`
+    pub supported_fields: Option<Vec<String>>,
+    /// Fields the reasoning models of the provider reject, see [CreateChatCompletionRequest::is_reasoning_model]
+    pub reasoning_model_unsupported_fields: Vec<String>,
+    /// Fields the provider rejects in requests with images
+    pub vision_unsupported_fields: Vec<String>,
+    /// Limits of the requests of the provider
+    pub limits: Option<RequestLimits>,
+}
+
+impl Provider {
+    /// Provider supporting all requests
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// The OpenAI API
+    pub fn openai() -> Self {
+        Self::new("OpenAI")
+            .with_reasoning_model_unsupported_fields(REASONING_MODEL_UNSUPPORTED_FIELDS)
+    }
+
+    /// [Azure OpenAI Service](https://learn.microsoft.com/en-us/azure/ai-services/openai/reference),
+    /// with the limits of [RequestLimits::azure]
+    pub fn azure() -> Self {
+        Self::new("Azure OpenAI Service")
+            .with_reasoning_model_unsupported_fields(REASONING_MODEL_UNSUPPORTED_FIELDS)
+            .with_vision_unsupported_fields(["logit_bias"])
+            .with_limits(RequestLimits::azure())
+    }
+
+    /// The [OpenAI compatible API](https://github.com/ollama/ollama/blob/main/docs/openai.md) of Ollama
+    pub fn ollama() -> Self {
+        Self::new("Ollama").with_supported_fields([
+            "model",
+            "messages",
+            "frequency_penalty",
+            "presence_penalty",
+            "response_format",
+            "seed",
+            "stop",
+            "stream",
+            "stream_options",
+            "temperature",
+            "top_p",
+            "max_tokens",
+            "tools",
+        ])
+    }
+
+    pub fn with_supported_fields<I, S>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.supported_fields = Some(fields.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn with_reasoning_model_unsupported_fields<I, S>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.reasoning_model_unsupported_fields = fields.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn with_vision_unsupported_fields<I, S>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.vision_unsupported_fields = fields.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn with_limits(mut self, limits: RequestLimits) -> Self {
+        self.limits = Some(limits);
+        self
+    }
+}
+
+/// Part of a request a provider does not support
+#[derive(Debug, Clone, PartialEq)]
+pub struct Incompatibility {
+    /// The top level field of the request at fault, empty for the request as a whole
+    pub field: String,
+    pub message: String,
+}
+
+impl Display for Incompatibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.field.as_str() {
+            "" => write!(f, "{}", self.message),
+            field => write!(f, "`{field}`: {}", self.message),
+        }
+    }
+}
+
+/// The parts of `request` which `provider` does not support, by field
+pub fn check_compat(
+    request: &CreateChatCompletionRequest,
+    provider: &Provider,
+) -> Vec<Incompatibility> {
+    let mut incompatibilities = vec![];
+    let Ok(Value::Object(fields)) = serde_json::to_value(request) else {
+        return incompatibilities;
+    };
+    let reasoning_model = request.is_reasoning_model();
+    let has_images = request
+        .messages
+        .iter()
+        .any(|message| !crate::validation::images(message).is_empty());
+    let listed = |fields: &[String], field: &str| fields.iter().any(|listed| listed == field);
+    let supported = |field: &str| {
+        provider
+            .supported_fields
+            .as_ref()
+            .map_or(true, |supported| listed(supported, field))
+    };
+
+    for (field, _) in fields.iter().filter(|(_, value)| !value.is_null()) {
+        let message = if !supported(field) {
+            format!("is not supported by {}", provider.name)
+        } else if reasoning_model && listed(&provider.reasoning_model_unsupported_fields, field) {
+            format!(
+                "is not supported by {}, a reasoning model, on {}",
+                request.model, provider.name
+            )
+        } else if has_images && listed(&provider.vision_unsupported_fields, field) {
+            format!("is not supported along with images by {}", provider.name)
+        } else {
+            continue;
+        };
+        incompatibilities.push(Incompatibility {
+            field: field.clone(),
+            message,
+        });
+    }
+
+    if let Some(Err(e)) = provider
+        .limits
+        .as_ref()
+        .map(|limits| limits.validate_chat(request))
+    {
+        incompatibilities.push(Incompatibility {
+            field: String::new(),
+            message: format!("exceeds the limits of {}: {e}", provider.name),
+        });
+    }
+    incompatibilities
+}
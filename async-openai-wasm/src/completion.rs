@@ -2,6 +2,7 @@ use crate::{
     client::Client,
     config::Config,
     error::OpenAIError,
+    telemetry,
     types::{CompletionResponseStream, CreateCompletionRequest, CreateCompletionResponse},
 };
 
@@ -23,14 +24,16 @@ impl<'c, C: Config> Completions<'c, C> {
     /// Creates a completion for the provided prompt and parameters
     pub async fn create(
         &self,
-        request: CreateCompletionRequest,
+        mut request: CreateCompletionRequest,
     ) -> Result<CreateCompletionResponse, OpenAIError> {
         if request.stream.is_some() && request.stream.unwrap() {
             return Err(OpenAIError::InvalidArgument(
                 "When stream is true, use Completion::create_stream".into(),
             ));
         }
-        self.client.post("/completions", request).await
+        self.client.inject_default_user(&mut request.user);
+        let span = telemetry::span(self.client.config(), "text_completion", &request);
+        telemetry::instrument(span, self.client.post("/completions", request)).await
     }
 
 
@@ -52,7 +55,8 @@ impl<'c, C: Config> Completions<'c, C> {
         }
 
         request.stream = Some(true);
+        self.client.inject_default_user(&mut request.user);
 
-        Ok(self.client.post_stream("/completions", request).await)
+        self.client.post_stream("/completions", request).await
     }
 }
@@ -0,0 +1,130 @@
+//! Decoding of SSE streams compressed by gateways.
+//!
+//! On native targets, the `gzip`, `brotli` and `deflate` features, passed through to `reqwest`, ask for
+//! and decode responses in these encodings, streams included:
+//!
+//! ```toml
+//! async-openai-wasm = { version = "0.23", features = ["gzip", "brotli"] }
+//! ```
+//!
+//! Other encodings, e.g. `zstd`, are decoded by [ContentDecoder]s plugged into a client with
+//! [Client::with_stream_decoder](crate::Client::with_stream_decoder). Their encodings are asked for with
+//! `Accept-Encoding`, instead of the ones of the features. A stream in an encoding without decoder fails with
+//! [OpenAIError::StreamError]. Latency-sensitive code can ask for uncompressed streams instead, with
+//! [Client::with_identity_stream_encoding](crate::Client::with_identity_stream_encoding).
+//!
+//! Browsers decode the responses of `fetch` themselves and choose the encodings they accept,
+//! so on wasm32 streams are never decoded by the client and the features have no effect.
+use std::sync::Arc;
+
+use bytes::Bytes;
+use futures::StreamExt;
+use reqwest::header::{HeaderMap, CONTENT_ENCODING};
+
+use crate::{error::OpenAIError, types::ByteStream};
+
+/// Incremental decoder of a body in a content encoding, such as `gzip` or `br`
+pub trait ContentDecoder: Send {
+    /// Decode the next `chunk` of the body, returning the bytes decoded so far
+    fn decode(&mut self, chunk: &[u8]) -> std::io::Result<Vec<u8>>;
+
+    /// Decode what remains once the whole body is received
+    fn finish(&mut self) -> std::io::Result<Vec<u8>>;
+}
+
+type DecoderFactory = Arc<dyn Fn() -> Box<dyn ContentDecoder> + Send + Sync>;
+
+/// Decoders of a client by content encoding, one made per stream
+#[derive(Clone, Default)]
+pub(crate) struct ContentDecoders(Vec<(String, DecoderFactory)>);
+
+impl ContentDecoders {
+    /// Decode `encoding` with the decoders of `factory`, replacing its previous decoder if any
+    pub(crate) fn insert(&mut self, encoding: &str, factory: DecoderFactory) {
+        let encoding = encoding.trim().to_ascii_lowercase();
+        self.0.retain(|(known, _)| *known != encoding);
+        self.0.push((encoding, factory));
+    }
+
+    /// Value of `Accept-Encoding` asking for the encodings with a decoder, if any
+    pub(crate) fn accept_encoding(&self) -> Option<String> {
+        if self.0.is_empty() {
+            return None;
+        }
+        let encodings: Vec<_> = self
+            .0
+            .iter()
+            .map(|(encoding, _)| encoding.as_str())
+            .collect();
+        Some(encodings.join(", "))
+    }
+
+    /// `body` decoded as the `Content-Encoding` of `headers` says
+    pub(crate) fn decode(
+        &self,
+        headers: &HeaderMap,
+        body: ByteStream,
+    ) -> Result<ByteStream, OpenAIError> {
+        // fetch responses are decoded by the browser, keeping their Content-Encoding header
+        if cfg!(target_arch = "wasm32") {
+            return Ok(body);
+        }
+        // encodings are listed in the order they were applied
+        let mut body = body;
+        for encoding in content_encodings(headers).into_iter().rev() {
+            let Some((_, factory)) = self.0.iter().find(|(known, _)| *known == encoding) else {
+                return Err(OpenAIError::StreamError {
+                    message: format!(
+                        "the stream is encoded with {encoding}, which the client has no decoder for; \
+                         enable the feature of the encoding if any, add a decoder with Client::with_stream_decoder \
+                         or ask for uncompressed streams with Client::with_identity_stream_encoding"
+                    ),
+                    source: None,
+                });
+            };
+            body = decoded(body, factory(), encoding);
+        }
+        Ok(body)
+    }
+}
+
+impl std::fmt::Debug for ContentDecoders {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.0.iter().map(|(encoding, _)| encoding))
+            .finish()
+    }
+}
+
+/// Encodings of `Content-Encoding`, lowercase and without `identity`
+fn content_encodings(headers: &HeaderMap) -> Vec<String> {
+    headers
+        .get_all(CONTENT_ENCODING)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|encoding| encoding.trim().to_ascii_lowercase())
+        .filter(|encoding| !encoding.is_empty() && encoding != "identity")
+        .collect()
+}
+
+/// `body` decoded by `decoder`, ending after the first error
+fn decoded(body: ByteStream, decoder: Box<dyn ContentDecoder>, encoding: String) -> ByteStream {
+    let failed = move |e: std::io::Error| {
+        OpenAIError::stream_error(format!("failed to decode the {encoding} stream"), e)
+    };
+    Box::pin(futures::stream::unfold(
+        Some((body, decoder, failed)),
+        |state| async move {
+            let (mut body, mut decoder, failed) = state?;
+            match body.next().await {
+                Some(Ok(chunk)) => match decoder.decode(&chunk) {
+                    Ok(decoded) => Some((Ok(Bytes::from(decoded)), Some((body, decoder, failed)))),
+                    Err(e) => Some((Err(failed(e)), None)),
+                },
+                Some(Err(e)) => Some((Err(e), None)),
+                None => Some((decoder.finish().map(Bytes::from).map_err(failed), None)),
+            }
+        },
+    ))
+}
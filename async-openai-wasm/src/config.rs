@@ -1,7 +1,11 @@
-//! Client configurations: [OpenAIConfig] for OpenAI, [AzureConfig] for Azure OpenAI Service.
-use reqwest::header::{AUTHORIZATION, HeaderMap};
+//! Client configurations: [OpenAIConfig] for OpenAI, [AzureConfig] for Azure OpenAI Service,
+//! [OpenRouterConfig] for OpenRouter, [AnthropicConfig] for Anthropic.
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderName, HeaderValue};
 use secrecy::{ExposeSecret, Secret};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{error::OpenAIError, validation::RequestLimits};
 
 /// Default v1 API base url
 pub const OPENAI_API_BASE: &str = "https://api.openai.com/v1";
@@ -12,9 +16,80 @@ pub const OPENAI_PROJECT_HEADER: &str = "OpenAI-Project";
 
 /// Calls to the Assistants API require that you pass a Beta header
 pub const OPENAI_BETA_HEADER: &str = "OpenAI-Beta";
+/// Path prefix of the Administration API endpoints, the only ones admin API keys can call
+pub const OPENAI_ADMIN_PATH_PREFIX: &str = "/organization";
+
+/// Kinds of OpenAI API keys, told apart by their prefix.
+///
+/// See <https://platform.openai.com/docs/api-reference/authentication>
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiKeyKind {
+    /// Legacy user key, `sk-...`
+    User,
+    /// Key scoped to a project, `sk-proj-...`
+    Project,
+    /// Key of a service account of a project, `sk-svcacct-...`
+    ServiceAccount,
+    /// Admin key of an organization, `sk-admin-...`, only valid for the Administration API
+    Admin,
+}
+
+impl ApiKeyKind {
+    /// Kind of `api_key`, `None` if it is not an OpenAI API key
+    pub fn of(api_key: &str) -> Option<Self> {
+        [Self::Project, Self::ServiceAccount, Self::Admin, Self::User]
+            .into_iter()
+            .find(|kind| api_key.starts_with(kind.prefix()))
+    }
+
+    /// Prefix of the keys of this kind
+    pub fn prefix(self) -> &'static str {
+        match self {
+            Self::User => "sk-",
+            Self::Project => "sk-proj-",
+            Self::ServiceAccount => "sk-svcacct-",
+            Self::Admin => "sk-admin-",
+        }
+    }
+
+    /// Check that keys of this kind can call the endpoint at `path`:
+    /// admin keys only the Administration API under [OPENAI_ADMIN_PATH_PREFIX], other keys all but it.
+    pub fn check_path(self, path: &str) -> Result<(), OpenAIError> {
+        let is_admin_path = path == OPENAI_ADMIN_PATH_PREFIX
+            || path.starts_with(&format!("{OPENAI_ADMIN_PATH_PREFIX}/"));
+        match (self, is_admin_path) {
+            (Self::Admin, false) => Err(OpenAIError::InvalidArgument(format!(
+                "admin API keys (sk-admin-...) can only call {OPENAI_ADMIN_PATH_PREFIX} endpoints, not {path}; use a project or service account key instead"
+            ))),
+            (Self::User | Self::Project | Self::ServiceAccount, true) => Err(OpenAIError::InvalidArgument(format!(
+                "{path} is an Administration API endpoint, which requires an admin API key (sk-admin-...) instead of a {} key",
+                self.prefix()
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Default OpenRouter API base url
+pub const OPENROUTER_API_BASE: &str = "https://openrouter.ai/api/v1";
+/// Header of the URL of the app making requests, to attribute them on OpenRouter rankings
+pub const OPENROUTER_REFERER_HEADER: &str = "HTTP-Referer";
+/// Header of the name of the app making requests, shown on OpenRouter rankings
+pub const OPENROUTER_TITLE_HEADER: &str = "X-Title";
+
+/// Default Anthropic API base url
+pub const ANTHROPIC_API_BASE: &str = "https://api.anthropic.com/v1";
+/// Default version of the Anthropic API
+pub const ANTHROPIC_VERSION: &str = "2023-06-01";
+/// Anthropic API version header
+pub const ANTHROPIC_VERSION_HEADER: &str = "anthropic-version";
+/// Header to opt in Anthropic beta features
+pub const ANTHROPIC_BETA_HEADER: &str = "anthropic-beta";
+/// Header allowing to call the Anthropic API from browsers, exposing the API key to their users
+pub const ANTHROPIC_BROWSER_ACCESS_HEADER: &str = "anthropic-dangerous-direct-browser-access";
 
 /// [crate::Client] relies on this for every API call on OpenAI
-/// or Azure OpenAI service
+/// or Azure OpenAI service or other OpenAI compatible providers
 pub trait Config: Clone {
     fn headers(&self) -> HeaderMap;
     fn url(&self, path: &str) -> String;
@@ -23,6 +98,38 @@ pub trait Config: Clone {
     fn api_base(&self) -> &str;
 
     fn api_key(&self) -> &Secret<String>;
+
+    /// Kind of the API key, checked against the endpoint of every request.
+    /// `None`, the default, for providers whose keys are not OpenAI ones.
+    fn api_key_kind(&self) -> Option<ApiKeyKind> {
+        None
+    }
+
+    /// Limits requests are validated against before being sent, unless overridden by [crate::Client::with_request_limits]
+    fn request_limits(&self) -> Option<RequestLimits> {
+        None
+    }
+
+    /// Provider reported as the `gen_ai.system` attribute of the spans of API calls
+    fn gen_ai_system(&self) -> &'static str {
+        "openai"
+    }
+
+    /// Headers added to the request to `path`, on top of [Config::headers]
+    fn extra_headers(&self, _path: &str) -> HeaderMap {
+        HeaderMap::new()
+    }
+
+    /// Query params added to the request to `path`, on top of [Config::query]
+    fn extra_query(&self, _path: &str) -> Vec<(String, String)> {
+        vec![]
+    }
+
+    /// Fields added to the JSON body of a POST request to `path`, unless the request sets them itself.
+    /// Useful for provider specific fields the request types lack.
+    fn extra_body(&self, _path: &str) -> Map<String, Value> {
+        Map::new()
+    }
 }
 
 /// Configuration for OpenAI API
@@ -33,6 +140,9 @@ pub struct OpenAIConfig {
     api_key: Secret<String>,
     org_id: String,
     project_id: String,
+    #[serde(skip)]
+    default_headers: HeaderMap,
+    default_query: Vec<(String, String)>,
 }
 
 impl Default for OpenAIConfig {
@@ -44,6 +154,8 @@ impl Default for OpenAIConfig {
                 .into(),
             org_id: Default::default(),
             project_id: Default::default(),
+            default_headers: Default::default(),
+            default_query: Default::default(),
         }
     }
 }
@@ -78,9 +190,40 @@ impl OpenAIConfig {
         self
     }
 
+    /// Send a header with every request, e.g. the ones of an observability gateway in front of the API.
+    ///
+    /// Default headers are sent along with, and take precedence over, the ones set by this config.
+    /// Headers with an invalid name or value are ignored with a warning.
+    pub fn with_header<K: AsRef<str>, V: AsRef<str>>(mut self, name: K, value: V) -> Self {
+        let (name, value) = (name.as_ref(), value.as_ref());
+        match (name.parse::<HeaderName>(), value.parse::<HeaderValue>()) {
+            (Ok(name), Ok(value)) => {
+                self.default_headers.insert(name, value);
+            }
+            _ => tracing::warn!("ignoring invalid default header: {name}"),
+        }
+        self
+    }
+
+    /// Send all of `headers` with every request, like [OpenAIConfig::with_header]
+    pub fn with_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers.extend(headers);
+        self
+    }
+
+    /// Add a query param to every request
+    pub fn with_query_param<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.default_query.push((key.into(), value.into()));
+        self
+    }
+
     pub fn org_id(&self) -> &str {
         &self.org_id
     }
+
+    pub fn default_headers(&self) -> &HeaderMap {
+        &self.default_headers
+    }
 }
 
 impl Config for OpenAIConfig {
@@ -93,7 +236,10 @@ impl Config for OpenAIConfig {
             );
         }
 
-        if !self.project_id.is_empty() {
+        let is_admin_key = self.api_key_kind() == Some(ApiKeyKind::Admin);
+
+        // admin keys belong to the organization, not to a project
+        if !self.project_id.is_empty() && !is_admin_key {
             headers.insert(
                 OPENAI_PROJECT_HEADER,
                 self.project_id.as_str().parse().unwrap(),
@@ -110,7 +256,11 @@ impl Config for OpenAIConfig {
 
         // hack for Assistants APIs
         // Calls to the Assistants API require that you pass a Beta header
-        headers.insert(OPENAI_BETA_HEADER, "assistants=v2".parse().unwrap());
+        if !is_admin_key {
+            headers.insert(OPENAI_BETA_HEADER, "assistants=v2".parse().unwrap());
+        }
+
+        headers.extend(self.default_headers.clone());
 
         headers
     }
@@ -127,8 +277,15 @@ impl Config for OpenAIConfig {
         &self.api_key
     }
 
+    fn api_key_kind(&self) -> Option<ApiKeyKind> {
+        ApiKeyKind::of(self.api_key.expose_secret())
+    }
+
     fn query(&self) -> Vec<(&str, &str)> {
-        vec![]
+        self.default_query
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect()
     }
 }
 
@@ -213,4 +370,306 @@ impl Config for AzureConfig {
     fn query(&self) -> Vec<(&str, &str)> {
         vec![("api-version", &self.api_version)]
     }
+
+    fn request_limits(&self) -> Option<RequestLimits> {
+        Some(RequestLimits::azure_vision(&self.api_version))
+    }
+
+    fn gen_ai_system(&self) -> &'static str {
+        "az.ai.openai"
+    }
+}
+
+/// How OpenRouter picks the providers serving a model, see [provider routing](https://openrouter.ai/docs/features/provider-routing)
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct ProviderPreferences {
+    /// Providers to try in order, e.g. `["anthropic", "openai"]`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<Vec<String>>,
+    /// Whether to fall back to other providers than the ones of `order` when they are unavailable
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_fallbacks: Option<bool>,
+    /// Only use providers supporting all the parameters of the request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub require_parameters: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_collection: Option<DataCollection>,
+    /// Providers allowed to serve requests
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub only: Option<Vec<String>>,
+    /// Providers never to serve requests
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignore: Option<Vec<String>>,
+    /// Quantization levels of the models to use, e.g. `["fp8"]`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantizations: Option<Vec<String>>,
+    /// Sort providers by this instead of load balancing them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<ProviderSort>,
+}
+
+/// Whether providers storing or training on data can be used
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DataCollection {
+    Allow,
+    Deny,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderSort {
+    Price,
+    Throughput,
+    Latency,
+}
+
+/// Configuration for [OpenRouter](https://openrouter.ai/docs/quickstart)
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct OpenRouterConfig {
+    api_base: String,
+    api_key: Secret<String>,
+    site_url: String,
+    app_name: String,
+    provider: Option<ProviderPreferences>,
+    fallback_models: Vec<String>,
+}
+
+impl Default for OpenRouterConfig {
+    fn default() -> Self {
+        Self {
+            api_base: OPENROUTER_API_BASE.to_string(),
+            api_key: std::env::var("OPENROUTER_API_KEY")
+                .unwrap_or_else(|_| "".to_string())
+                .into(),
+            site_url: Default::default(),
+            app_name: Default::default(),
+            provider: None,
+            fallback_models: vec![],
+        }
+    }
+}
+
+impl OpenRouterConfig {
+    /// Create config with default [OPENROUTER_API_BASE] url and default API key from OPENROUTER_API_KEY env var
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// To use a different API key different from default OPENROUTER_API_KEY env var
+    pub fn with_api_key<S: Into<String>>(mut self, api_key: S) -> Self {
+        self.api_key = Secret::from(api_key.into());
+        self
+    }
+
+    /// To use a API base url different from default [OPENROUTER_API_BASE]
+    pub fn with_api_base<S: Into<String>>(mut self, api_base: S) -> Self {
+        self.api_base = api_base.into();
+        self
+    }
+
+    /// URL of your app, sent as the [OPENROUTER_REFERER_HEADER] header
+    pub fn with_site_url<S: Into<String>>(mut self, site_url: S) -> Self {
+        self.site_url = site_url.into();
+        self
+    }
+
+    /// Name of your app, sent as the [OPENROUTER_TITLE_HEADER] header
+    pub fn with_app_name<S: Into<String>>(mut self, app_name: S) -> Self {
+        self.app_name = app_name.into();
+        self
+    }
+
+    /// Provider routing preferences of chat and completions requests
+    pub fn with_provider(mut self, provider: ProviderPreferences) -> Self {
+        self.provider = Some(provider);
+        self
+    }
+
+    /// Models to try in order when the model of a chat or completions request is unavailable
+    pub fn with_fallback_models<I, S>(mut self, models: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.fallback_models = models.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn provider(&self) -> Option<&ProviderPreferences> {
+        self.provider.as_ref()
+    }
+}
+
+impl Config for OpenRouterConfig {
+    fn headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+
+        headers.insert(
+            AUTHORIZATION,
+            format!("Bearer {}", self.api_key.expose_secret())
+                .as_str()
+                .parse()
+                .unwrap(),
+        );
+
+        if !self.site_url.is_empty() {
+            if let Ok(site_url) = self.site_url.parse() {
+                headers.insert(OPENROUTER_REFERER_HEADER, site_url);
+            }
+        }
+
+        if !self.app_name.is_empty() {
+            if let Ok(app_name) = self.app_name.parse() {
+                headers.insert(OPENROUTER_TITLE_HEADER, app_name);
+            }
+        }
+
+        headers
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.api_base, path)
+    }
+
+    fn api_base(&self) -> &str {
+        &self.api_base
+    }
+
+    fn api_key(&self) -> &Secret<String> {
+        &self.api_key
+    }
+
+    fn query(&self) -> Vec<(&str, &str)> {
+        vec![]
+    }
+
+    fn gen_ai_system(&self) -> &'static str {
+        "openrouter"
+    }
+
+    fn extra_body(&self, path: &str) -> Map<String, Value> {
+        let mut body = Map::new();
+        if !matches!(path, "/chat/completions" | "/completions") {
+            return body;
+        }
+        if let Some(provider) = &self.provider {
+            body.insert(
+                "provider".into(),
+                serde_json::to_value(provider).unwrap_or_default(),
+            );
+        }
+        if !self.fallback_models.is_empty() {
+            body.insert("models".into(), self.fallback_models.clone().into());
+        }
+        body
+    }
+}
+
+/// Configuration for the [Anthropic](https://docs.anthropic.com/en/api/messages) API,
+/// to be called with [Anthropic](crate::Anthropic) using chat completion requests.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct AnthropicConfig {
+    api_base: String,
+    api_key: Secret<String>,
+    version: String,
+    beta: Vec<String>,
+    browser_access: bool,
+}
+
+impl Default for AnthropicConfig {
+    fn default() -> Self {
+        Self {
+            api_base: ANTHROPIC_API_BASE.to_string(),
+            api_key: std::env::var("ANTHROPIC_API_KEY")
+                .unwrap_or_else(|_| "".to_string())
+                .into(),
+            version: ANTHROPIC_VERSION.to_string(),
+            beta: vec![],
+            browser_access: false,
+        }
+    }
+}
+
+impl AnthropicConfig {
+    /// Create config with default [ANTHROPIC_API_BASE] url and default API key from ANTHROPIC_API_KEY env var
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// To use a different API key different from default ANTHROPIC_API_KEY env var
+    pub fn with_api_key<S: Into<String>>(mut self, api_key: S) -> Self {
+        self.api_key = Secret::from(api_key.into());
+        self
+    }
+
+    /// To use a API base url different from default [ANTHROPIC_API_BASE]
+    pub fn with_api_base<S: Into<String>>(mut self, api_base: S) -> Self {
+        self.api_base = api_base.into();
+        self
+    }
+
+    /// To use an API version different from default [ANTHROPIC_VERSION]
+    pub fn with_version<S: Into<String>>(mut self, version: S) -> Self {
+        self.version = version.into();
+        self
+    }
+
+    /// Opt in a beta feature, e.g. `prompt-caching-2024-07-31`
+    pub fn with_beta<S: Into<String>>(mut self, beta: S) -> Self {
+        self.beta.push(beta.into());
+        self
+    }
+
+    /// Allow calling the API from browsers, where the API key is visible to users.
+    pub fn with_browser_access(mut self) -> Self {
+        self.browser_access = true;
+        self
+    }
+}
+
+impl Config for AnthropicConfig {
+    fn headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+
+        headers.insert(
+            "x-api-key",
+            self.api_key.expose_secret().as_str().parse().unwrap(),
+        );
+        if let Ok(version) = self.version.parse() {
+            headers.insert(ANTHROPIC_VERSION_HEADER, version);
+        }
+        if !self.beta.is_empty() {
+            if let Ok(beta) = self.beta.join(",").parse() {
+                headers.insert(ANTHROPIC_BETA_HEADER, beta);
+            }
+        }
+        if self.browser_access {
+            headers.insert(ANTHROPIC_BROWSER_ACCESS_HEADER, "true".parse().unwrap());
+        }
+
+        headers
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.api_base, path)
+    }
+
+    fn api_base(&self) -> &str {
+        &self.api_base
+    }
+
+    fn api_key(&self) -> &Secret<String> {
+        &self.api_key
+    }
+
+    fn query(&self) -> Vec<(&str, &str)> {
+        vec![]
+    }
+
+    fn gen_ai_system(&self) -> &'static str {
+        "anthropic"
+    }
 }
@@ -0,0 +1,209 @@
+//! Conformance vectors of OpenAI compatible servers, enabled with the `conformance` feature: requests the crate sends
+//! and responses it accepts, shared as JSON in the `conformance` directory of the crate, for authors of OpenAI
+//! compatible servers to check their implementations against the crate.
+//!
+//! [run] sends the requests of vectors to a server and reports the responses the crate cannot use.
+//!
+//! ```no_run
+//! use async_openai_wasm::{config::OpenAIConfig, conformance, Client};
+//!
+//! # async fn example() {
+//! let client = Client::with_config(OpenAIConfig::new().with_api_base("http://localhost:8080/v1"));
+//! let vectors: Vec<_> = conformance::vectors()
+//!     .into_iter()
+//!     .map(|vector| vector.with_model("my-model"))
+//!     .collect();
+//! let report = conformance::run(&client, &vectors).await;
+//! println!("{report}");
+//! assert!(report.passed());
+//! # }
+//! ```
+use std::fmt::{Display, Formatter};
+
+use futures::StreamExt;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    config::Config,
+    types::{CreateEmbeddingRequest, EmbeddingInput},
+    Client,
+};
+
+/// The vectors of the `conformance/vectors.json` file of the crate
+const VECTORS: &str = include_str!("../conformance/vectors.json");
+
+/// A request of the crate to an endpoint, and a response it accepts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Vector {
+    /// What the vector exercises, e.g. `chat.completions.stream`
+    pub name: String,
+    /// What a server is expected to do
+    pub description: String,
+    pub method: String,
+    /// Path of the endpoint, relative to the API base
+    pub path: String,
+    /// JSON body of the request, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request: Option<Value>,
+    /// JSON body of a response the crate accepts, `null` for streamed responses
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub response: Value,
+    /// Data of the SSE events of a streamed response, ended by `data: [DONE]`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<Value>,
+}
+
+impl Vector {
+    /// This vector requesting `model`, e.g. one the server under test has
+    pub fn with_model<S: Into<String>>(mut self, model: S) -> Self {
+        if let Some(Value::Object(request)) = &mut self.request {
+            request.insert("model".into(), Value::String(model.into()));
+        }
+        self
+    }
+}
+
+/// The vectors shipped with the crate
+pub fn vectors() -> Vec<Vector> {
+    serde_json::from_str(VECTORS).expect("conformance/vectors.json must hold valid vectors")
+}
+
+/// Outcome of a vector run by [run]
+#[derive(Debug, Clone, PartialEq)]
+pub struct VectorOutcome {
+    pub name: String,
+    /// Why the crate could not use the response of the server, if it could not
+    pub failure: Option<String>,
+}
+
+/// Outcomes of the vectors run by [run], in their order
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConformanceReport {
+    pub outcomes: Vec<VectorOutcome>,
+}
+
+impl ConformanceReport {
+    /// Whether the crate could use all the responses of the server
+    pub fn passed(&self) -> bool {
+        self.outcomes
+            .iter()
+            .all(|outcome| outcome.failure.is_none())
+    }
+
+    /// The outcomes of the vectors which failed
+    pub fn failures(&self) -> impl Iterator<Item = &VectorOutcome> {
+        self.outcomes
+            .iter()
+            .filter(|outcome| outcome.failure.is_some())
+    }
+}
+
+impl Display for ConformanceReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for outcome in &self.outcomes {
+            match &outcome.failure {
+                None => writeln!(f, "ok     {}", outcome.name)?,
+                Some(failure) => writeln!(f, "FAILED {}: {failure}", outcome.name)?,
+            }
+        }
+        let failed = self.failures().count();
+        write!(
+            f,
+            "{} passed, {failed} failed",
+            self.outcomes.len() - failed
+        )
+    }
+}
+
+/// Send the requests of `vectors` to the API of `client`, one after the other, checking that the crate can use
+/// the responses: that they deserialize and that they answer the request, e.g. with one embedding per input.
+///
+/// Vectors unknown to this version of the crate fail.
+pub async fn run<C: Config>(client: &Client<C>, vectors: &[Vector]) -> ConformanceReport {
+    let mut report = ConformanceReport::default();
+    for vector in vectors {
+        report.outcomes.push(VectorOutcome {
+            name: vector.name.clone(),
+            failure: check(client, vector).await.err(),
+        });
+    }
+    report
+}
+
+/// Why the response of the server to `vector` cannot be used, if it cannot
+async fn check<C: Config>(client: &Client<C>, vector: &Vector) -> Result<(), String> {
+    match vector.name.as_str() {
+        "models.list" => {
+            let models = client.models().list().await.map_err(|e| e.to_string())?;
+            expect(models.object == "list", "`object` is not `list`")
+        }
+        "chat.completions" => {
+            let response = client
+                .chat()
+                .create(request(vector)?)
+                .await
+                .map_err(|e| e.to_string())?;
+            expect(!response.choices.is_empty(), "no choices")?;
+            expect(
+                response.choices[0].finish_reason.is_some(),
+                "no `finish_reason` in the first choice",
+            )
+        }
+        "chat.completions.stream" => {
+            let mut stream = client
+                .chat()
+                .create_stream(request(vector)?)
+                .await
+                .map_err(|e| e.to_string())?;
+            let mut finished = false;
+            while let Some(chunk) = stream.next().await {
+                let chunk = chunk.map_err(|e| e.to_string())?;
+                finished |= chunk
+                    .choices
+                    .iter()
+                    .any(|choice| choice.finish_reason.is_some());
+            }
+            expect(finished, "no chunk with a `finish_reason`")
+        }
+        "embeddings" => {
+            let request: CreateEmbeddingRequest = request(vector)?;
+            let inputs = match &request.input {
+                EmbeddingInput::String(_) | EmbeddingInput::IntegerArray(_) => 1,
+                EmbeddingInput::StringArray(inputs) => inputs.len(),
+                EmbeddingInput::ArrayOfIntegerArray(inputs) => inputs.len(),
+            };
+            let response = client
+                .embeddings()
+                .create(request)
+                .await
+                .map_err(|e| e.to_string())?;
+            expect(
+                response.data.len() == inputs,
+                &format!("{} embeddings for {inputs} inputs", response.data.len()),
+            )?;
+            expect(
+                response
+                    .data
+                    .iter()
+                    .enumerate()
+                    .all(|(index, embedding)| embedding.index as usize == index),
+                "embeddings not in the order of the inputs",
+            )
+        }
+        name => Err(format!("unknown vector `{name}`")),
+    }
+}
+
+/// The request of `vector` as `T`
+fn request<T: DeserializeOwned>(vector: &Vector) -> Result<T, String> {
+    let request = vector.request.clone().ok_or("no request in the vector")?;
+    serde_json::from_value(request).map_err(|e| format!("invalid request in the vector: {e}"))
+}
+
+fn expect(condition: bool, failure: &str) -> Result<(), String> {
+    match condition {
+        true => Ok(()),
+        false => Err(failure.to_string()),
+    }
+}
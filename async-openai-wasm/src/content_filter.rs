@@ -0,0 +1,169 @@
+//! Fallback for chat completions stopped by content filters, which Azure OpenAI Service deployments
+//! trigger on innocuous prompts: [Chat::create_with_content_filter_fallback](crate::Chat::create_with_content_filter_fallback)
+//! sanitizes the request with a [Sanitizer] and sends it again.
+//!
+//! ```no_run
+//! use async_openai_wasm::{content_filter::Sanitizer, types::CreateChatCompletionRequest, Client};
+//!
+//! # async fn example(request: CreateChatCompletionRequest) -> Result<(), async_openai_wasm::error::OpenAIError> {
+//! let client = Client::new();
+//! let sanitizer = Sanitizer::Rephrase { model: "gpt-4o-mini".into() };
+//! let outcome = client
+//!     .chat()
+//!     .create_with_content_filter_fallback(request, &sanitizer, 2)
+//!     .await?;
+//! if !outcome.filtered.is_empty() {
+//!     println!("answered after {} filtered attempts", outcome.filtered.len());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use std::sync::Arc;
+
+use crate::{
+    error::{ApiErrorKind, OpenAIError},
+    types::{
+        ChatCompletionRequestMessage, ChatCompletionRequestMessageContentPart,
+        ChatCompletionRequestMessageContentPartText, ChatCompletionRequestUserMessageContent,
+        CreateChatCompletionRequest, CreateChatCompletionResponse, FinishReason,
+    },
+};
+
+/// Transform of a chat completion request [Chat::create_with_content_filter_fallback](crate::Chat::create_with_content_filter_fallback)
+/// sends again after a content filter stopped it.
+#[derive(Clone)]
+pub enum Sanitizer {
+    /// Rephrase the text of the last user message with `model`, asked to keep its meaning
+    /// in wording complying with content policies.
+    Rephrase {
+        /// Model rephrasing the message
+        model: String,
+    },
+    /// Custom transform of the filtered request, given the number of the retry, starting at 1
+    Transform(Arc<dyn Fn(CreateChatCompletionRequest, usize) -> CreateChatCompletionRequest + Send + Sync>),
+}
+
+impl std::fmt::Debug for Sanitizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rephrase { model } => f.debug_struct("Rephrase").field("model", model).finish(),
+            Self::Transform(_) => f.write_str("Transform"),
+        }
+    }
+}
+
+impl Sanitizer {
+    /// [Sanitizer::Transform] of `transform`
+    pub fn transform<F>(transform: F) -> Self
+    where
+        F: Fn(CreateChatCompletionRequest, usize) -> CreateChatCompletionRequest + Send + Sync + 'static,
+    {
+        Self::Transform(Arc::new(transform))
+    }
+}
+
+/// Why a content filter stopped a request
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterReason {
+    /// The prompt was rejected with a `content_filter` error, as the message of the error says
+    Prompt(String),
+    /// The completion ended with a `content_filter` finish reason
+    Completion,
+}
+
+/// Request stopped by a content filter
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilteredAttempt {
+    pub request: CreateChatCompletionRequest,
+    pub reason: FilterReason,
+}
+
+/// Result of [Chat::create_with_content_filter_fallback](crate::Chat::create_with_content_filter_fallback)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContentFilterOutcome {
+    /// The response to the last request sent, whose completion may still be filtered
+    /// if all retries were, see [ContentFilterOutcome::is_filtered]
+    pub response: CreateChatCompletionResponse,
+    /// The request the response answers, sanitized if any attempt was filtered
+    pub request: CreateChatCompletionRequest,
+    /// The attempts stopped by a content filter, in order
+    pub filtered: Vec<FilteredAttempt>,
+}
+
+impl ContentFilterOutcome {
+    /// Whether the completion of the response ended with a content filter, all retries being exhausted
+    pub fn is_filtered(&self) -> bool {
+        completion_filtered(&self.response)
+    }
+}
+
+/// Whether a choice of `response` was stopped by a content filter
+pub(crate) fn completion_filtered(response: &CreateChatCompletionResponse) -> bool {
+    response
+        .choices
+        .iter()
+        .any(|choice| choice.finish_reason == Some(FinishReason::ContentFilter))
+}
+
+/// Message of `error` if it rejects a prompt for its content, as Azure OpenAI Service does with a `content_filter` code
+pub(crate) fn prompt_filtered(error: &OpenAIError) -> Option<String> {
+    match error {
+        OpenAIError::ApiError(error) if error.kind() == ApiErrorKind::ContentFilter => {
+            Some(error.message.clone())
+        }
+        _ => None,
+    }
+}
+
+/// Text of the last user message of `request`, its text parts joined by new lines
+pub(crate) fn last_user_text(request: &CreateChatCompletionRequest) -> Option<String> {
+    request.messages.iter().rev().find_map(|message| match message {
+        ChatCompletionRequestMessage::User(user) => Some(match &user.content {
+            ChatCompletionRequestUserMessageContent::Text(text) => text.clone(),
+            ChatCompletionRequestUserMessageContent::Array(parts) => parts
+                .iter()
+                .filter_map(|part| match part {
+                    ChatCompletionRequestMessageContentPart::Text(text) => Some(text.text.as_str()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }),
+        _ => None,
+    })
+}
+
+/// Replace the text of the last user message of `request` with `text`, keeping its images
+pub(crate) fn replace_last_user_text(request: &mut CreateChatCompletionRequest, text: String) {
+    let user = request.messages.iter_mut().rev().find_map(|message| match message {
+        ChatCompletionRequestMessage::User(user) => Some(user),
+        _ => None,
+    });
+    let Some(user) = user else { return };
+    match &mut user.content {
+        ChatCompletionRequestUserMessageContent::Text(content) => *content = text,
+        ChatCompletionRequestUserMessageContent::Array(parts) => {
+            let mut text = Some(text);
+            parts.retain_mut(|part| match part {
+                ChatCompletionRequestMessageContentPart::Text(part) => match text.take() {
+                    // the first text part holds the whole text
+                    Some(text) => {
+                        *part = ChatCompletionRequestMessageContentPartText { text };
+                        true
+                    }
+                    None => false,
+                },
+                _ => true,
+            });
+        }
+    }
+}
+
+/// Prompt asking to rephrase `text` in wording complying with content policies
+pub(crate) fn rephrase_prompt(text: &str) -> String {
+    format!(
+        "A content filter rejected the message below, although it is likely legitimate. \
+         Rephrase it so that it complies with content policies while keeping its meaning and intent. \
+         Reply with the rephrased message only.\n\n{text}"
+    )
+}
@@ -0,0 +1,179 @@
+//! Request-scoped metadata, such as trace ids and tenant ids, which is set once per task
+//! and attached to every API call made within it.
+//!
+//! ```
+//! # tokio_test::block_on(async {
+//! use async_openai_wasm::context::RequestContext;
+//!
+//! let context = RequestContext::new()
+//!     .with_trace_id("trace-1234")
+//!     .with_tenant_id("the-continental");
+//!
+//! context
+//!     .scope(async {
+//!         // every call made by any client in here carries the context
+//!         let current = RequestContext::current().unwrap();
+//!         assert_eq!(current.trace_id(), Some("trace-1234"));
+//!     })
+//!     .await;
+//!
+//! assert!(RequestContext::current().is_none());
+//! # });
+//! ```
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use pin_project::pin_project;
+
+thread_local! {
+    static CURRENT: RefCell<Option<Arc<RequestContext>>> = const { RefCell::new(None) };
+}
+
+/// Application metadata propagated to all nested API calls of a task.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RequestContext {
+    trace_id: Option<String>,
+    tenant_id: Option<String>,
+    attributes: HashMap<String, String>,
+}
+
+impl RequestContext {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Application trace id, e.g. the id of an incoming HTTP request
+    pub fn with_trace_id<S: Into<String>>(mut self, trace_id: S) -> Self {
+        self.trace_id = Some(trace_id.into());
+        self
+    }
+
+    /// Tenant the calls are made on behalf of
+    pub fn with_tenant_id<S: Into<String>>(mut self, tenant_id: S) -> Self {
+        self.tenant_id = Some(tenant_id.into());
+        self
+    }
+
+    /// Any other key-value pair to be propagated
+    pub fn with_attribute<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.attributes.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn trace_id(&self) -> Option<&str> {
+        self.trace_id.as_deref()
+    }
+
+    pub fn tenant_id(&self) -> Option<&str> {
+        self.tenant_id.as_deref()
+    }
+
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes.get(key).map(String::as_str)
+    }
+
+    pub fn attributes(&self) -> &HashMap<String, String> {
+        &self.attributes
+    }
+
+    /// The context of the innermost enclosing [RequestContext::scope], if any.
+    pub fn current() -> Option<RequestContext> {
+        CURRENT.with(|current| current.borrow().as_deref().cloned())
+    }
+
+    /// Run `future` with this context as the [RequestContext::current] context.
+    ///
+    /// Unlike a plain thread local, the context follows the future across threads,
+    /// and scopes can be nested, in which case the innermost one wins.
+    pub fn scope<F: Future>(self, future: F) -> Scoped<F> {
+        Scoped {
+            future,
+            context: Some(Arc::new(self)),
+        }
+    }
+
+    /// Synchronous version of [RequestContext::scope]
+    pub fn sync_scope<R>(self, f: impl FnOnce() -> R) -> R {
+        let _guard = Guard::enter(Some(Arc::new(self)));
+        f()
+    }
+}
+
+/// Future returned by [RequestContext::scope]
+#[pin_project]
+pub struct Scoped<F> {
+    #[pin]
+    future: F,
+    context: Option<Arc<RequestContext>>,
+}
+
+impl<F: Future> Future for Scoped<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let _guard = Guard::enter(this.context.clone());
+        this.future.poll(cx)
+    }
+}
+
+/// Restores the previous context when dropped, even on panic.
+struct Guard {
+    previous: Option<Arc<RequestContext>>,
+}
+
+impl Guard {
+    fn enter(context: Option<Arc<RequestContext>>) -> Self {
+        let previous = CURRENT.with(|current| current.replace(context));
+        Self { previous }
+    }
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// Span carrying the current context, entered around every API call so that
+/// logs emitted while making the call are attributed to it.
+///
+/// With the `tracing` feature, the call itself is recorded on it too, see [crate::telemetry::record_request].
+pub(crate) fn request_span() -> tracing::Span {
+    use tracing::field::Empty;
+
+    let span = tracing::debug_span!(
+        "openai_request",
+        trace_id = Empty,
+        tenant_id = Empty,
+        http.request.method = Empty,
+        url.path = Empty,
+        gen_ai.request.model = Empty,
+        openai.stream = Empty,
+        openai.attempts = Empty,
+        openai.retry_budget = Empty,
+        http.response.status_code = Empty,
+        openai.request_id = Empty,
+        openai.processing_ms = Empty,
+        openai.overhead_ms = Empty,
+        duration_ms = Empty,
+        gen_ai.usage.input_tokens = Empty,
+        gen_ai.usage.output_tokens = Empty,
+    );
+    CURRENT.with(|current| {
+        if let Some(context) = current.borrow().as_deref() {
+            if let Some(trace_id) = context.trace_id() {
+                span.record("trace_id", trace_id);
+            }
+            if let Some(tenant_id) = context.tenant_id() {
+                span.record("tenant_id", tenant_id);
+            }
+        }
+    });
+    span
+}
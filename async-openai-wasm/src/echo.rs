@@ -0,0 +1,96 @@
+//! Echo of the requests as sent, after the defaults, migrations and sanitization of the client and the headers
+//! of its config and interceptors, to debug why a model behaved differently without reconstructing what was sent.
+//!
+//! Opt-in with [Client::with_request_echo](crate::Client::with_request_echo), as requests are copied to be kept.
+//!
+//! ```no_run
+//! use async_openai_wasm::Client;
+//!
+//! # async fn example(request: async_openai_wasm::types::CreateChatCompletionRequest) -> Result<(), async_openai_wasm::error::OpenAIError> {
+//! let client = Client::new().with_request_echo(true);
+//! let response = client.chat().create(request).await?;
+//! if let Some(echo) = client.last_request() {
+//!     println!("{} {} answered {:?}", echo.method, echo.url, echo.status);
+//!     println!("{:#}", echo.body.unwrap_or_default());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use reqwest::header::HeaderMap;
+use serde_json::Value;
+
+/// Value of the headers of credentials in echoes
+const REDACTED: &str = "[redacted]";
+
+/// Headers holding credentials, redacted in echoes along with the ones marked sensitive
+const CREDENTIAL_HEADERS: [&str; 5] = [
+    "authorization",
+    "api-key",
+    "x-api-key",
+    "x-portkey-api-key",
+    "cookie",
+];
+
+/// A request as sent by the client, for the last attempt if it was retried
+#[derive(Debug, Clone, PartialEq)]
+pub struct EchoedRequest {
+    pub method: String,
+    /// URL of the request, query included
+    pub url: String,
+    /// Headers of the request, the ones holding credentials redacted
+    pub headers: Vec<(String, String)>,
+    /// JSON body of the request, `None` for requests without one or with a multipart or streamed body
+    pub body: Option<Value>,
+    /// HTTP status of the response, `None` for streams and for requests which failed before a response
+    pub status: Option<u16>,
+    /// `x-request-id` of the response, to find the request in the logs of the provider
+    pub request_id: Option<String>,
+}
+
+impl EchoedRequest {
+    pub(crate) fn new(request: &reqwest::Request) -> Self {
+        Self {
+            method: request.method().to_string(),
+            url: request.url().to_string(),
+            headers: redacted(request.headers()),
+            body: request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .and_then(|body| serde_json::from_slice(body).ok()),
+            status: None,
+            request_id: None,
+        }
+    }
+
+    /// Echo of a request answered with `response`
+    pub(crate) fn answered(mut self, response: &reqwest::Response) -> Self {
+        self.status = Some(response.status().as_u16());
+        self.request_id = response
+            .headers()
+            .get("x-request-id")
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        self
+    }
+
+    /// Value of the header `name`, if any
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+fn redacted(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = match value.is_sensitive() || CREDENTIAL_HEADERS.contains(&name.as_str()) {
+                true => REDACTED.to_string(),
+                false => String::from_utf8_lossy(value.as_bytes()).into_owned(),
+            };
+            (name.to_string(), value)
+        })
+        .collect()
+}
@@ -1,10 +1,16 @@
+use std::future::Future;
+use std::ops::Range;
+
+use futures::{stream, StreamExt, TryStreamExt};
+
 use crate::{
     Client,
     config::Config,
     error::OpenAIError,
+    telemetry,
     types::{
         CreateBase64EmbeddingResponse, CreateEmbeddingRequest, CreateEmbeddingResponse,
-        EncodingFormat,
+        EmbeddingChunk, EmbeddingInput, EmbeddingUsage, EncodingFormat,
     },
 };
 
@@ -24,30 +30,187 @@ impl<'c, C: Config> Embeddings<'c, C> {
     /// Creates an embedding vector representing the input text.
     pub async fn create(
         &self,
-        request: CreateEmbeddingRequest,
+        mut request: CreateEmbeddingRequest,
     ) -> Result<CreateEmbeddingResponse, OpenAIError> {
         if matches!(request.encoding_format, Some(EncodingFormat::Base64)) {
             return Err(OpenAIError::InvalidArgument(
                 "When encoding_format is base64, use Embeddings::create_base64".into(),
             ));
         }
-        self.client.post("/embeddings", request).await
+        self.client.inject_default_user(&mut request.user);
+        let span = telemetry::span(self.client.config(), "embeddings", &request);
+        telemetry::instrument(span, self.client.post("/embeddings", request)).await
+    }
+
+    /// Creates the embedding vectors of many inputs, in requests of at most `chunk_size` inputs
+    /// of which up to `concurrency` are sent at once.
+    ///
+    /// The embeddings of the response are in the order of the inputs, indexed like a single request
+    /// would be, and its usage is the sum of the usage of all requests. Fails on the first failed request.
+    pub async fn create_chunked(
+        &self,
+        request: CreateEmbeddingRequest,
+        chunk_size: usize,
+        concurrency: usize,
+    ) -> Result<CreateEmbeddingResponse, OpenAIError> {
+        if chunk_size == 0 {
+            return Err(OpenAIError::InvalidArgument(
+                "chunk_size of Embeddings::create_chunked must be at least 1".into(),
+            ));
+        }
+        let inputs = chunk_inputs(&request.input, 0, chunk_size);
+
+        let responses: Vec<CreateEmbeddingResponse> = stream::iter(inputs)
+            .map(|(_, input)| {
+                self.create(CreateEmbeddingRequest {
+                    input,
+                    ..request.clone()
+                })
+            })
+            .buffered(concurrency.max(1))
+            .try_collect()
+            .await?;
+
+        let mut data = vec![];
+        let mut usage = EmbeddingUsage {
+            prompt_tokens: 0,
+            total_tokens: 0,
+        };
+        let (mut object, mut model) = (String::from("list"), request.model);
+        for response in responses {
+            let offset = data.len() as u32;
+            let mut embeddings = response.data;
+            embeddings.sort_by_key(|embedding| embedding.index);
+            data.extend(embeddings.into_iter().map(|mut embedding| {
+                embedding.index += offset;
+                embedding
+            }));
+            usage.prompt_tokens += response.usage.prompt_tokens;
+            usage.total_tokens += response.usage.total_tokens;
+            (object, model) = (response.object, response.model);
+        }
+        Ok(CreateEmbeddingResponse {
+            object,
+            model,
+            data,
+            usage,
+        })
+    }
+
+    /// Creates the embedding vectors of many inputs like [Embeddings::create_chunked], handing the embeddings
+    /// of each request to `sink` as they come, e.g. to persist them, instead of collecting them all.
+    ///
+    /// `sink` is called once at a time, in the order of the inputs, so that once it has returned for a chunk
+    /// all the inputs before the end of its range are embedded. A run which is interrupted, cancelled or fails
+    /// can be resumed from there by passing the end of the last range as `resume_from`, the index of the first
+    /// input to embed. The embeddings are indexed like a single request of all the inputs would be.
+    /// Fails on the first failed request or sink call, and returns the usage of the requests otherwise.
+    pub async fn create_chunked_with_sink<S, Fut>(
+        &self,
+        request: CreateEmbeddingRequest,
+        chunk_size: usize,
+        concurrency: usize,
+        resume_from: usize,
+        mut sink: S,
+    ) -> Result<EmbeddingUsage, OpenAIError>
+    where
+        S: FnMut(EmbeddingChunk) -> Fut,
+        Fut: Future<Output = Result<(), OpenAIError>>,
+    {
+        if chunk_size == 0 {
+            return Err(OpenAIError::InvalidArgument(
+                "chunk_size of Embeddings::create_chunked_with_sink must be at least 1".into(),
+            ));
+        }
+        let inputs = chunk_inputs(&request.input, resume_from, chunk_size);
+
+        let mut responses = stream::iter(inputs)
+            .map(|(range, input)| {
+                let response = self.create(CreateEmbeddingRequest {
+                    input,
+                    ..request.clone()
+                });
+                async move { Ok::<_, OpenAIError>((range, response.await?)) }
+            })
+            .buffered(concurrency.max(1));
+
+        let mut usage = EmbeddingUsage {
+            prompt_tokens: 0,
+            total_tokens: 0,
+        };
+        while let Some((range, response)) = responses.try_next().await? {
+            usage.prompt_tokens += response.usage.prompt_tokens;
+            usage.total_tokens += response.usage.total_tokens;
+            let mut embeddings = response.data;
+            embeddings.sort_by_key(|embedding| embedding.index);
+            for embedding in &mut embeddings {
+                embedding.index += range.start as u32;
+            }
+            sink(EmbeddingChunk {
+                range,
+                embeddings,
+                usage: response.usage,
+            })
+            .await?;
+        }
+        Ok(usage)
     }
 
     /// Creates an embedding vector representing the input text.
     ///
-    /// The response will contain the embedding in base64 format.
+    /// The response will contain the embedding in base64 format, about a third smaller than floats in JSON.
+    /// Decode the vectors with [CreateBase64EmbeddingResponse::vectors] or [CreateBase64EmbeddingResponse::decode].
     pub async fn create_base64(
         &self,
-        request: CreateEmbeddingRequest,
+        mut request: CreateEmbeddingRequest,
     ) -> Result<CreateBase64EmbeddingResponse, OpenAIError> {
         if !matches!(request.encoding_format, Some(EncodingFormat::Base64)) {
             return Err(OpenAIError::InvalidArgument(
                 "When encoding_format is not base64, use Embeddings::create".into(),
             ));
         }
+        self.client.inject_default_user(&mut request.user);
+        let span = telemetry::span(self.client.config(), "embeddings", &request);
+        telemetry::instrument(span, self.client.post("/embeddings", request)).await
+    }
+}
 
-        self.client.post("/embeddings", request).await
+/// The inputs of `input` from the `start`-th one, in chunks of at most `chunk_size` inputs, with their range
+fn chunk_inputs(
+    input: &EmbeddingInput,
+    start: usize,
+    chunk_size: usize,
+) -> Vec<(Range<usize>, EmbeddingInput)> {
+    fn chunks<T: Clone>(
+        items: &[T],
+        start: usize,
+        chunk_size: usize,
+        input: fn(Vec<T>) -> EmbeddingInput,
+    ) -> Vec<(Range<usize>, EmbeddingInput)> {
+        let items = items.get(start..).unwrap_or_default();
+        items
+            .chunks(chunk_size)
+            .enumerate()
+            .map(|(i, chunk)| {
+                let first = start + i * chunk_size;
+                (first..first + chunk.len(), input(chunk.to_vec()))
+            })
+            .collect()
+    }
+
+    match input {
+        EmbeddingInput::StringArray(strings) => {
+            chunks(strings, start, chunk_size, EmbeddingInput::StringArray)
+        }
+        EmbeddingInput::ArrayOfIntegerArray(arrays) => chunks(
+            arrays,
+            start,
+            chunk_size,
+            EmbeddingInput::ArrayOfIntegerArray,
+        ),
+        // a single input
+        input if start == 0 => vec![(0..1, input.clone())],
+        _ => vec![],
     }
 }
 
@@ -1,6 +1,9 @@
 //! Errors originating from API calls, parsing responses, and reading-or-writing to the file system.
+use reqwest::{header::HeaderMap, StatusCode};
 use serde::Deserialize;
 
+use crate::{checksum::ChecksumMismatch, types::FinishReason};
+
 #[derive(Debug, thiserror::Error)]
 pub enum OpenAIError {
     /// Underlying error from reqwest library after an API call was made
@@ -8,32 +11,316 @@ pub enum OpenAIError {
     Reqwest(#[from] reqwest::Error),
     /// OpenAI returns error object with details of API call failure
     #[error("{:?}: {}", .0.r#type, .0.message)]
-    ApiError(ApiError),
+    ApiError(#[source] ApiError),
     /// Error when a response cannot be deserialized into a Rust type
     #[error("failed to deserialize api response: {0}")]
-    JSONDeserialize(serde_json::Error),
+    JSONDeserialize(#[source] serde_json::Error),
+    /// Content of a JSON mode response which is not the JSON expected, see [json_mode](crate::json_mode)
+    #[error(transparent)]
+    InvalidJsonResponse(#[from] InvalidJsonResponse),
     /// Error on the client side when saving file to file system
-    #[error("failed to save file: {0}")]
-    FileSaveError(String),
+    #[error("failed to save file: {message}")]
+    FileSaveError {
+        message: String,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
     /// Error on the client side when reading file from file system
-    #[error("failed to read file: {0}")]
-    FileReadError(String),
-    /// Error on SSE streaming
-    #[error("stream failed: {0}")]
-    StreamError(String),
+    #[error("failed to read file: {message}")]
+    FileReadError {
+        message: String,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+    /// Error on SSE streaming, with the error of the connection or of the stream data which caused it if any
+    #[error("stream failed: {message}")]
+    StreamError {
+        message: String,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+    /// Event of an SSE stream which is malformed or of an unknown type, skipped instead with
+    /// [Client::with_lenient_streaming](crate::Client::with_lenient_streaming)
+    #[error(transparent)]
+    InvalidStreamEvent(#[from] InvalidStreamEvent),
     /// Error from client side validation
     /// or when builder fails to build request before making API call
     #[error("invalid args: {0}")]
     InvalidArgument(String),
+    /// Field of a request, or of its builder, left unset or set to an invalid value,
+    /// caught by client side validation before making API call
+    #[error(transparent)]
+    InvalidField(#[from] FieldError),
+    /// Content of a file upload or download which does not match its checksum
+    #[error(transparent)]
+    ChecksumMismatch(#[from] ChecksumMismatch),
+    /// Call aborted by the [CancellationToken](crate::cancel::CancellationToken) of the client
+    #[error("cancelled")]
+    Cancelled,
+    /// Call aborted once the timeout of the client, or of its [RequestOptions](crate::RequestOptions), elapsed
+    #[error("timed out after {0:?}")]
+    Timeout(std::time::Duration),
+    /// Stream which received no event for the time set by [Client::with_stream_watchdog](crate::Client::with_stream_watchdog)
+    /// after it started, e.g. on a lost connection, unlike a stream ending normally
+    #[error("stream stalled: no event for {0:?}")]
+    StreamStalled(std::time::Duration),
+}
+
+/// Event of an SSE stream which cannot be turned into a Rust type, as received
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid `{event}` stream event: {reason}; data: {data}")]
+pub struct InvalidStreamEvent {
+    /// Name of the event, `message` for events without one
+    pub event: String,
+    /// Raw data of the event
+    pub data: String,
+    /// Why the event is invalid, e.g. the deserialization error of its data
+    pub reason: String,
+}
+
+/// Content of a JSON mode response which does not parse into the type expected, as received
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("invalid JSON response: {reason}; content: {content}")]
+pub struct InvalidJsonResponse {
+    /// Raw content of the response
+    pub content: String,
+    /// Finish reason of the response, `length` when the content was cut off by `max_tokens`
+    pub finish_reason: Option<FinishReason>,
+    /// Why the content is invalid, i.e. the deserialization error
+    pub reason: String,
+}
+
+/// Field with an invalid value, named as serialized so that apps can point at it
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid args: `{field}` {message}{}", .suggestion.as_ref().map(|suggestion| format!("; {suggestion}")).unwrap_or_default())]
+pub struct FieldError {
+    /// Name of the field, e.g. `temperature`
+    pub field: String,
+    /// What is wrong with the value, e.g. `must be within [0, 2], got 2.5`
+    pub message: String,
+    /// How to fix the value, if there is an obvious fix
+    pub suggestion: Option<String>,
+}
+
+impl FieldError {
+    pub fn new<F: Into<String>, M: Into<String>>(field: F, message: M) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+            suggestion: None,
+        }
+    }
+
+    pub fn with_suggestion<S: Into<String>>(mut self, suggestion: S) -> Self {
+        self.suggestion = Some(suggestion.into());
+        self
+    }
 }
 
 /// OpenAI API returns error object on failure
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, Default, thiserror::Error)]
+#[error("{message}")]
 pub struct ApiError {
     pub message: String,
     pub r#type: Option<String>,
     pub param: Option<String>,
+    /// Code of the error, e.g. `invalid_api_key`, or `429` from Azure OpenAI Service, which may send it as a number
+    #[serde(default, deserialize_with = "string_or_number::deserialize")]
     pub code: Option<String>,
+    /// HTTP status of the response with the error, `None` for errors of stream events
+    #[serde(skip)]
+    pub status: Option<StatusCode>,
+    /// Details of the error from Azure OpenAI Service, e.g. the content filter results of a rejected prompt
+    #[serde(default)]
+    pub innererror: Option<Box<InnerError>>,
+    /// Response the error was received in, `None` for errors of stream events
+    #[serde(skip)]
+    pub response: Option<Box<ErrorResponse>>,
+}
+
+/// Response an [ApiError] was received in
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ErrorResponse {
+    /// `x-request-id` of the response, to give to OpenAI support
+    pub request_id: Option<String>,
+    /// Raw body of the response, e.g. to log the fields of providers [ApiError] does not have
+    pub body: String,
+}
+
+impl ApiError {
+    /// `x-request-id` of the response with the error, if received in a response
+    pub fn request_id(&self) -> Option<&str> {
+        self.response.as_ref()?.request_id.as_deref()
+    }
+
+    /// Raw body of the response with the error, if received in a response
+    pub fn body(&self) -> Option<&str> {
+        self.response
+            .as_ref()
+            .map(|response| response.body.as_str())
+    }
+
+    /// What the error is about, to match on instead of comparing codes and types of providers
+    pub fn kind(&self) -> ApiErrorKind {
+        let is = |names: &[&str]| {
+            [self.r#type.as_deref(), self.code.as_deref()]
+                .iter()
+                .flatten()
+                .any(|value| names.contains(value))
+        };
+        let filtered = self.innererror.as_ref().map_or(false, |inner| {
+            inner.content_filter_result.is_some()
+                || inner.code.as_deref() == Some("ResponsibleAIPolicyViolation")
+        });
+        if filtered || is(&["content_filter", "content_policy_violation"]) {
+            ApiErrorKind::ContentFilter
+        } else if is(&["insufficient_quota"]) {
+            ApiErrorKind::QuotaExceeded
+        } else if self.status == Some(StatusCode::TOO_MANY_REQUESTS)
+            || is(&["rate_limit_exceeded", "429", "requests", "tokens"])
+        {
+            ApiErrorKind::RateLimit
+        } else if matches!(
+            self.status,
+            Some(StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN)
+        ) || is(&[
+            "invalid_api_key",
+            "invalid_organization",
+            "authentication_error",
+            "permission_error",
+            "401",
+            "403",
+        ]) {
+            ApiErrorKind::Authentication
+        } else if self.status.map_or(false, |status| status.is_server_error())
+            || is(&[
+                "server_error",
+                "api_error",
+                "overloaded_error",
+                "500",
+                "503",
+            ])
+        {
+            ApiErrorKind::Server
+        } else if self.status.map_or(false, |status| status.is_client_error())
+            || is(&["invalid_request_error", "not_found_error", "400", "404"])
+        {
+            ApiErrorKind::InvalidRequest
+        } else {
+            ApiErrorKind::Other
+        }
+    }
+}
+
+/// Kinds of [ApiError]s, from their HTTP status, type and code, the ones of OpenAI, Azure OpenAI Service
+/// and Anthropic included
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiErrorKind {
+    /// Too many requests or tokens per minute, worth retrying later
+    RateLimit,
+    /// Credits or quota spent, which retrying does not help with
+    QuotaExceeded,
+    /// Request the API cannot process, e.g. with an invalid field or an unknown model
+    InvalidRequest,
+    /// Prompt or completion rejected by a content filter, see [ApiError::innererror] for Azure's results
+    ContentFilter,
+    /// Missing or invalid credentials, or credentials without access to the resource
+    Authentication,
+    /// Failure or overload of the API
+    Server,
+    Other,
+}
+
+/// `innererror` of the errors of Azure OpenAI Service
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct InnerError {
+    /// e.g. `ResponsibleAIPolicyViolation` for prompts rejected by the content filter
+    pub code: Option<String>,
+    /// Results of the content filter on the prompt
+    pub content_filter_result: Option<ContentFilterResults>,
+}
+
+/// Results of the content filter of Azure OpenAI Service by category, for the categories it reports
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct ContentFilterResults {
+    pub hate: Option<ContentFilterResult>,
+    pub self_harm: Option<ContentFilterResult>,
+    pub sexual: Option<ContentFilterResult>,
+    pub violence: Option<ContentFilterResult>,
+    pub jailbreak: Option<ContentFilterResult>,
+    pub profanity: Option<ContentFilterResult>,
+}
+
+impl ContentFilterResults {
+    /// Names of the categories which were filtered, e.g. `violence`
+    pub fn filtered(&self) -> Vec<&'static str> {
+        [
+            ("hate", &self.hate),
+            ("self_harm", &self.self_harm),
+            ("sexual", &self.sexual),
+            ("violence", &self.violence),
+            ("jailbreak", &self.jailbreak),
+            ("profanity", &self.profanity),
+        ]
+        .into_iter()
+        .filter(|(_, result)| result.as_ref().map_or(false, |result| result.filtered))
+        .map(|(category, _)| category)
+        .collect()
+    }
+}
+
+/// Result of the content filter of Azure OpenAI Service for a category
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct ContentFilterResult {
+    pub filtered: bool,
+    /// Severity of the content, `safe`, `low`, `medium` or `high`, for harm categories
+    pub severity: Option<String>,
+    /// Whether the content was detected, for detection categories such as `jailbreak`
+    pub detected: Option<bool>,
+}
+
+mod string_or_number {
+    use serde::{Deserialize, Deserializer};
+    use serde_json::Value;
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<String>, D::Error> {
+        Ok(match Option::<Value>::deserialize(deserializer)? {
+            Some(Value::String(value)) => Some(value),
+            Some(Value::Number(value)) => Some(value.to_string()),
+            _ => None,
+        })
+    }
+}
+
+impl OpenAIError {
+    /// [OpenAIError::StreamError] caused by `source`
+    pub(crate) fn stream_error(
+        message: impl Into<String>,
+        source: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+    ) -> Self {
+        OpenAIError::StreamError {
+            message: message.into(),
+            source: Some(source.into()),
+        }
+    }
+
+    /// HTTP status of the response with the error, for API errors received as responses
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            OpenAIError::ApiError(error) => error.status,
+            _ => None,
+        }
+    }
+
+    /// `x-request-id` of the response with the error, for API errors received as responses
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            OpenAIError::ApiError(error) => error.request_id(),
+            _ => None,
+        }
+    }
 }
 
 /// Wrapper to deserialize the error object nested in "error" JSON key
@@ -42,6 +329,37 @@ pub(crate) struct WrappedError {
     pub(crate) error: ApiError,
 }
 
+/// The [ApiError] of a failed response with `status`, `headers` and `body`.
+///
+/// Bodies which are not errors of the API, e.g. the HTML or plain text pages of proxies and gateways, give an error
+/// with the status as message, their raw body kept in [ApiError::response].
+pub(crate) fn response_error(status: StatusCode, headers: &HeaderMap, body: &[u8]) -> ApiError {
+    let error = match serde_json::from_slice::<WrappedError>(body) {
+        Ok(wrapped_error) => wrapped_error.error,
+        Err(e) => {
+            tracing::warn!(
+                "response with status {status} is not an API error ({e}): {}",
+                String::from_utf8_lossy(body)
+            );
+            ApiError {
+                message: status.to_string(),
+                ..Default::default()
+            }
+        }
+    };
+    ApiError {
+        status: Some(status),
+        response: Some(Box::new(ErrorResponse {
+            request_id: headers
+                .get("x-request-id")
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string),
+            body: String::from_utf8_lossy(body).into_owned(),
+        })),
+        ..error
+    }
+}
+
 pub(crate) fn map_deserialization_error(e: serde_json::Error, bytes: &[u8]) -> OpenAIError {
     tracing::error!(
         "failed deserialization of: {}",
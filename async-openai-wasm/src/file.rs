@@ -5,6 +5,8 @@ use crate::{
     Client,
     config::Config,
     error::OpenAIError,
+    pagination::Paginator,
+    progress::DownloadProgress,
     types::{CreateFileRequest, DeleteFileResponse, ListFilesResponse, OpenAIFile},
 };
 
@@ -39,6 +41,15 @@ impl<'c, C: Config> Files<'c, C> {
         self.client.get_with_query("/files", query).await
     }
 
+    /// Returns all the files that belong to the user's organization, fetching them page by page as the stream is consumed.
+    pub fn list_auto_paginated<Q>(&self, query: &Q) -> Paginator<'c, OpenAIFile>
+        where
+            C: Sync,
+            Q: Serialize + ?Sized,
+    {
+        Paginator::new::<_, ListFilesResponse, _>(self.client, "/files".into(), query)
+    }
+
     /// Returns information about a specific file.
     pub async fn retrieve(&self, file_id: &str) -> Result<OpenAIFile, OpenAIError> {
         self.client.get(format!("/files/{file_id}").as_str()).await
@@ -52,9 +63,27 @@ impl<'c, C: Config> Files<'c, C> {
     }
 
     /// Returns the contents of the specified file
+    ///
+    /// Fails with [OpenAIError::ChecksumMismatch] when the contents do not match the digests sent by the server, if any.
     pub async fn content(&self, file_id: &str) -> Result<Bytes, OpenAIError> {
+        self.retrieve_content_with_progress(file_id, |_| {}).await
+    }
+
+    /// Returns the contents of the specified file like [Files::content], calling `on_progress` as they are received.
+    ///
+    /// Interrupted downloads are resumed where they stopped with HTTP range requests.
+    /// The contents are verified like those of [Files::content].
+    /// When the server does not honor them, the download starts over and the progress is reported from 0 again.
+    pub async fn retrieve_content_with_progress<F>(
+        &self,
+        file_id: &str,
+        on_progress: F,
+    ) -> Result<Bytes, OpenAIError>
+        where
+            F: FnMut(DownloadProgress),
+    {
         self.client
-            .get_raw(format!("/files/{file_id}/content").as_str())
+            .get_raw_with_progress(format!("/files/{file_id}/content").as_str(), on_progress)
             .await
     }
 }
@@ -4,6 +4,7 @@ use crate::{
     Client,
     config::Config,
     error::OpenAIError,
+    pagination::Paginator,
     types::{
         CreateFineTuningJobRequest, FineTuningJob, ListFineTuningJobCheckpointsResponse,
         ListFineTuningJobEventsResponse, ListPaginatedFineTuningJobsResponse,
@@ -45,6 +46,15 @@ impl<'c, C: Config> FineTuning<'c, C> {
         self.client.get_with_query("/fine_tuning/jobs", query).await
     }
 
+    /// Lists all your organization's fine-tuning jobs, fetching them page by page as the stream is consumed.
+    pub fn list_auto_paginated<Q>(&self, query: &Q) -> Paginator<'c, FineTuningJob>
+    where
+        C: Sync,
+        Q: Serialize + ?Sized,
+    {
+        Paginator::new::<_, ListPaginatedFineTuningJobsResponse, _>(self.client, "/fine_tuning/jobs".into(), query)
+    }
+
     /// Gets info about the fine-tune job.
     ///
     /// [Learn more about Fine-tuning](https://platform.openai.com/docs/guides/fine-tuning)
@@ -64,6 +74,26 @@ impl<'c, C: Config> FineTuning<'c, C> {
             .await
     }
 
+    /// Pause a running fine-tune job. The job keeps its progress and can be continued with [FineTuning::resume].
+    pub async fn pause(&self, fine_tuning_job_id: &str) -> Result<FineTuningJob, OpenAIError> {
+        self.client
+            .post(
+                format!("/fine_tuning/jobs/{fine_tuning_job_id}/pause").as_str(),
+                (),
+            )
+            .await
+    }
+
+    /// Resume a paused fine-tune job.
+    pub async fn resume(&self, fine_tuning_job_id: &str) -> Result<FineTuningJob, OpenAIError> {
+        self.client
+            .post(
+                format!("/fine_tuning/jobs/{fine_tuning_job_id}/resume").as_str(),
+                (),
+            )
+            .await
+    }
+
     /// Get fine-grained status updates for a fine-tune job.
     pub async fn list_events<Q>(
         &self,
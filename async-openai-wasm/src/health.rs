@@ -0,0 +1,93 @@
+//! Startup checks of the credentials of a client with [Client::verify](crate::Client::verify),
+//! to fail fast with clear diagnostics instead of on the first user request.
+//!
+//! ```no_run
+//! use async_openai_wasm::Client;
+//!
+//! # async fn example() -> Result<(), async_openai_wasm::error::OpenAIError> {
+//! let client = Client::new();
+//! let report = client.verify().await?;
+//! println!("{report}");
+//! // refuse to start with credentials the API refused
+//! report.into_result()?;
+//! # Ok(())
+//! # }
+//! ```
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+use crate::error::{ApiError, OpenAIError};
+
+/// Outcome of [Client::verify](crate::Client::verify)
+#[derive(Debug, Clone)]
+pub struct VerifyReport {
+    /// HTTP status of the response
+    pub status: u16,
+    /// Error the API answered with, e.g. `invalid_api_key` or `invalid_organization`, if it did
+    pub error: Option<ApiError>,
+    /// Organization the call was made for, from the `openai-organization` header of the response
+    pub organization: Option<String>,
+    /// Project the call was made for, from the `openai-project` header of the response
+    pub project: Option<String>,
+    /// `x-request-id` of the response, to give to OpenAI support
+    pub request_id: Option<String>,
+    /// Time between sending the request and receiving the headers of the response
+    pub latency: Duration,
+    /// Part of the latency the API spent processing the call, see [ResponseTiming](crate::timing::ResponseTiming)
+    pub processing: Option<Duration>,
+}
+
+impl VerifyReport {
+    /// Whether the API accepted the credentials, even if it failed the call for another reason such as a rate limit
+    pub fn is_authenticated(&self) -> bool {
+        !matches!(self.status, 401 | 403)
+    }
+
+    /// Whether the call succeeded
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// This report, or the error of the API if the call failed
+    pub fn into_result(self) -> Result<Self, OpenAIError> {
+        match self.error {
+            Some(error) => Err(OpenAIError::ApiError(error)),
+            None => Ok(self),
+        }
+    }
+}
+
+impl Display for VerifyReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.error {
+            None => write!(f, "authenticated")?,
+            Some(error) => {
+                match self.is_authenticated() {
+                    true => write!(f, "authenticated, but failed with {}: {error}", self.status)?,
+                    false => write!(
+                        f,
+                        "not authenticated, refused with {}: {error}",
+                        self.status
+                    )?,
+                }
+                if let Some(code) = &error.code {
+                    write!(f, " ({code})")?;
+                }
+            }
+        }
+        if let Some(organization) = &self.organization {
+            write!(f, ", organization {organization}")?;
+        }
+        if let Some(project) = &self.project {
+            write!(f, ", project {project}")?;
+        }
+        write!(f, ", in {}ms", self.latency.as_millis())?;
+        if let Some(processing) = self.processing {
+            write!(f, " of which {}ms processing", processing.as_millis())?;
+        }
+        if let Some(request_id) = &self.request_id {
+            write!(f, " (request {request_id})")?;
+        }
+        Ok(())
+    }
+}
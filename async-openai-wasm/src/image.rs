@@ -20,23 +20,29 @@ impl<'c, C: Config> Images<'c, C> {
     }
 
     /// Creates an image given a prompt.
-    pub async fn create(&self, request: CreateImageRequest) -> Result<ImagesResponse, OpenAIError> {
+    pub async fn create(
+        &self,
+        mut request: CreateImageRequest,
+    ) -> Result<ImagesResponse, OpenAIError> {
+        self.client.inject_default_user(&mut request.user);
         self.client.post("/images/generations", request).await
     }
 
     /// Creates an edited or extended image given an original image and a prompt.
     pub async fn create_edit(
         &self,
-        request: CreateImageEditRequest,
+        mut request: CreateImageEditRequest,
     ) -> Result<ImagesResponse, OpenAIError> {
+        self.client.inject_default_user(&mut request.user);
         self.client.post_form("/images/edits", request).await
     }
 
     /// Creates a variation of a given image.
     pub async fn create_variation(
         &self,
-        request: CreateImageVariationRequest,
+        mut request: CreateImageVariationRequest,
     ) -> Result<ImagesResponse, OpenAIError> {
+        self.client.inject_default_user(&mut request.user);
         self.client.post_form("/images/variations", request).await
     }
 }
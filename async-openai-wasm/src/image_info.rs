@@ -0,0 +1,186 @@
+//! Format, dimensions and EXIF orientation of PNG, JPEG, GIF and WebP images, read from their
+//! headers without decoding them.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+}
+
+impl ImageFormat {
+    /// The format of `image`, detected from its first bytes
+    pub fn detect(image: &[u8]) -> Option<Self> {
+        if image.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(Self::Png)
+        } else if image.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            Some(Self::Jpeg)
+        } else if image.starts_with(b"GIF87a") || image.starts_with(b"GIF89a") {
+            Some(Self::Gif)
+        } else if image.starts_with(b"RIFF") && image.get(8..12) == Some(b"WEBP") {
+            Some(Self::Webp)
+        } else {
+            None
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            Self::Png => "image/png",
+            Self::Jpeg => "image/jpeg",
+            Self::Gif => "image/gif",
+            Self::Webp => "image/webp",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Jpeg => "jpg",
+            Self::Gif => "gif",
+            Self::Webp => "webp",
+        }
+    }
+}
+
+/// Format, dimensions and orientation of an image, read from its header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageInfo {
+    pub format: ImageFormat,
+    /// Width in pixels, as stored, before the orientation is applied
+    pub width: u32,
+    /// Height in pixels, as stored, before the orientation is applied
+    pub height: u32,
+    /// EXIF orientation, from 1 for upright to 8, of JPEG images
+    pub orientation: u8,
+}
+
+impl ImageInfo {
+    /// The info of `image`, `None` if its format is not supported or its header is truncated
+    pub fn read(image: &[u8]) -> Option<Self> {
+        let format = ImageFormat::detect(image)?;
+        let mut orientation = 1;
+        let (width, height) = match format {
+            ImageFormat::Png => (be_u32(image, 16)?, be_u32(image, 20)?),
+            ImageFormat::Gif => (le_u16(image, 6)?, le_u16(image, 8)?),
+            ImageFormat::Webp => webp_dimensions(image)?,
+            ImageFormat::Jpeg => {
+                // walk the segments up to a start of frame, which holds the dimensions
+                let mut at = 2;
+                loop {
+                    if *image.get(at)? != 0xFF {
+                        return None;
+                    }
+                    let marker = *image.get(at + 1)?;
+                    let length = be_u16(image, at + 2)? as usize;
+                    // the length counts its own two bytes
+                    let end = at.checked_add(2)?.checked_add(length)?;
+                    if marker == 0xE1 && length >= 2 {
+                        if let Some(exif) = image.get(at + 4..end) {
+                            orientation = exif_orientation(exif).unwrap_or(1);
+                        }
+                    }
+                    let is_start_of_frame =
+                        matches!(marker, 0xC0..=0xCF) && !matches!(marker, 0xC4 | 0xC8 | 0xCC);
+                    if is_start_of_frame {
+                        break (be_u16(image, at + 7)?, be_u16(image, at + 5)?);
+                    }
+                    at = end;
+                }
+            }
+        };
+        Some(Self {
+            format,
+            width,
+            height,
+            orientation,
+        })
+    }
+
+    /// Width and height once the orientation is applied, swapped for orientations rotating a quarter turn
+    pub fn oriented_dimensions(&self) -> (u32, u32) {
+        if (5..=8).contains(&self.orientation) {
+            (self.height, self.width)
+        } else {
+            (self.width, self.height)
+        }
+    }
+}
+
+fn be_u16(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u16::from_be_bytes(bytes.get(at..at.checked_add(2)?)?.try_into().ok()?) as u32)
+}
+
+fn be_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(bytes.get(at..at.checked_add(4)?)?.try_into().ok()?))
+}
+
+fn le_u16(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u16::from_le_bytes(bytes.get(at..at.checked_add(2)?)?.try_into().ok()?) as u32)
+}
+
+fn le_u24(bytes: &[u8], at: usize) -> Option<u32> {
+    let bytes = bytes.get(at..at.checked_add(3)?)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]))
+}
+
+/// Dimensions of a WebP image, from its first chunk
+fn webp_dimensions(image: &[u8]) -> Option<(u32, u32)> {
+    match image.get(12..16)? {
+        // extended format, dimensions minus one on 24 bits
+        b"VP8X" => Some((le_u24(image, 24)? + 1, le_u24(image, 27)? + 1)),
+        // lossy, dimensions on 14 bits after the frame tag and start code
+        b"VP8 " => Some((le_u16(image, 26)? & 0x3FFF, le_u16(image, 28)? & 0x3FFF)),
+        // lossless, dimensions minus one on 14 bits after the signature byte
+        b"VP8L" => {
+            let bits = u32::from_le_bytes(image.get(21..25)?.try_into().ok()?);
+            Some(((bits & 0x3FFF) + 1, ((bits >> 14) & 0x3FFF) + 1))
+        }
+        _ => None,
+    }
+}
+
+/// Orientation tag of the first image file directory of an EXIF segment, `None` if it is missing or
+/// the offsets of the segment point outside of it
+fn exif_orientation(exif: &[u8]) -> Option<u8> {
+    let tiff = exif.strip_prefix(b"Exif\0\0")?;
+    let big_endian = match tiff.get(0..2)? {
+        b"MM" => true,
+        b"II" => false,
+        _ => return None,
+    };
+    let u16_at = |at: usize| {
+        let bytes: [u8; 2] = tiff.get(at..at.checked_add(2)?)?.try_into().ok()?;
+        Some(if big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        })
+    };
+    let u32_at = |at: usize| {
+        let bytes: [u8; 4] = tiff.get(at..at.checked_add(4)?)?.try_into().ok()?;
+        Some(if big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    };
+
+    // offsets are read from the segment, so may overflow on 32 bit targets
+    let directory = usize::try_from(u32_at(4)?).ok()?;
+    let entries = u16_at(directory)? as usize;
+    let first_entry = directory.checked_add(2)?;
+    for entry in 0..entries {
+        let at = entry
+            .checked_mul(12)
+            .and_then(|offset| first_entry.checked_add(offset))?;
+        if u16_at(at)? == 0x0112 {
+            let orientation = u16_at(at.checked_add(8)?)?;
+            return u8::try_from(orientation)
+                .ok()
+                .filter(|orientation| (1..=8).contains(orientation));
+        }
+    }
+    None
+}
@@ -0,0 +1,123 @@
+//! JSON mode, where the model answers a JSON object: the API rejects requests with `response_format: json_object`
+//! whose messages do not mention JSON, and answers cut off by `max_tokens` are not valid JSON.
+//! [Chat::create_json](crate::Chat::create_json) takes care of both,
+//! injecting [JSON_INSTRUCTION] when needed and parsing the answer into a Rust type.
+//!
+//! ```no_run
+//! use async_openai_wasm::{
+//!     types::{ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs},
+//!     Client,
+//! };
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Capital {
+//!     country: String,
+//!     capital: String,
+//! }
+//!
+//! # async fn example() -> Result<(), async_openai_wasm::error::OpenAIError> {
+//! let client = Client::new();
+//! let request = CreateChatCompletionRequestArgs::default()
+//!     .model("gpt-4o-mini")
+//!     .messages([ChatCompletionRequestUserMessageArgs::default()
+//!         .content("What is the capital of France? Answer with `country` and `capital`.")
+//!         .build()?
+//!         .into()])
+//!     .build()?;
+//! let answer: Capital = client.chat().create_json(request).await?;
+//! println!("{} of {}", answer.capital, answer.country);
+//! # Ok(())
+//! # }
+//! ```
+use serde::de::DeserializeOwned;
+
+use crate::{
+    error::{InvalidJsonResponse, OpenAIError},
+    types::{
+        ChatCompletionRequestMessage, ChatCompletionRequestMessageContentPart,
+        ChatCompletionRequestSystemMessage, ChatCompletionRequestUserMessageContent,
+        ChatCompletionResponseFormat, ChatCompletionResponseFormatType,
+        CreateChatCompletionRequest, CreateChatCompletionResponse,
+    },
+};
+
+/// Instruction injected in the system message of JSON mode requests which do not mention JSON
+pub const JSON_INSTRUCTION: &str = "Respond with a valid JSON object.";
+
+/// Set `response_format` of `request` to `json_object` and, unless a message mentions JSON,
+/// append [JSON_INSTRUCTION] to its first system or developer message, or insert a system message with it.
+///
+/// Returns whether the instruction was injected.
+pub fn enforce_json_mode(request: &mut CreateChatCompletionRequest) -> bool {
+    request.response_format = Some(ChatCompletionResponseFormat {
+        r#type: ChatCompletionResponseFormatType::JsonObject,
+    });
+    if request.messages.iter().any(mentions_json) {
+        return false;
+    }
+    let instructions = match request.messages.first_mut() {
+        Some(ChatCompletionRequestMessage::System(system)) => Some(&mut system.content),
+        Some(ChatCompletionRequestMessage::Developer(developer)) => Some(&mut developer.content),
+        _ => None,
+    };
+    match instructions {
+        Some(instructions) => {
+            if !instructions.is_empty() {
+                instructions.push_str("\n\n");
+            }
+            instructions.push_str(JSON_INSTRUCTION);
+        }
+        _ => request.messages.insert(
+            0,
+            ChatCompletionRequestSystemMessage {
+                content: JSON_INSTRUCTION.into(),
+                name: None,
+            }
+            .into(),
+        ),
+    }
+    true
+}
+
+/// Parse the content of the first choice of `response` as JSON,
+/// failing with [OpenAIError::InvalidJsonResponse] holding the raw content otherwise.
+pub fn parse_json<T: DeserializeOwned>(
+    response: &CreateChatCompletionResponse,
+) -> Result<T, OpenAIError> {
+    let choice = response.choices.first();
+    let content = response.first_text().unwrap_or_default();
+    serde_json::from_str(content).map_err(|e| {
+        InvalidJsonResponse {
+            content: content.into(),
+            finish_reason: choice.and_then(|choice| choice.finish_reason),
+            reason: e.to_string(),
+        }
+        .into()
+    })
+}
+
+/// Whether the text of `message` mentions JSON, in any case as the API checks
+fn mentions_json(message: &ChatCompletionRequestMessage) -> bool {
+    let mentions = |text: &str| text.to_ascii_lowercase().contains("json");
+    match message {
+        ChatCompletionRequestMessage::Developer(message) => mentions(&message.content),
+        ChatCompletionRequestMessage::System(message) => mentions(&message.content),
+        ChatCompletionRequestMessage::User(message) => match &message.content {
+            ChatCompletionRequestUserMessageContent::Text(text) => mentions(text),
+            ChatCompletionRequestUserMessageContent::Array(parts) => {
+                parts.iter().any(|part| match part {
+                    ChatCompletionRequestMessageContentPart::Text(part) => mentions(&part.text),
+                    _ => false,
+                })
+            }
+        },
+        ChatCompletionRequestMessage::Assistant(message) => {
+            message.content.as_deref().map_or(false, mentions)
+        }
+        ChatCompletionRequestMessage::Tool(message) => mentions(&message.content),
+        ChatCompletionRequestMessage::Function(message) => {
+            message.content.as_deref().map_or(false, mentions)
+        }
+    }
+}
@@ -25,6 +25,12 @@
 //! let client = Client::new().with_http_client(http_client);
 //! ```
 //!
+//! ## TLS
+//!
+//! On native targets, the TLS stack is chosen with the `rustls` (default), `rustls-webpki-roots`,
+//! `native-tls` and `native-tls-vendored` features, passed through to `reqwest`.
+//! Disable the default features to use another one than rustls with the roots of the operating system.
+//!
 //! ## Microsoft Azure Endpoints
 //!
 //! ```
@@ -73,6 +79,8 @@
 //! # });
 //!```
 //!
+//! The types most programs use can be imported at once with `use async_openai_wasm::prelude::*;`, see [prelude].
+//!
 //! ## Examples
 //! For full working examples of the original `async-openai` for all supported features see [examples](https://github.com/64bit/async-openai/tree/main/examples) directory in the repository.
 //! Also see [wasm examples](https://github.com/ifsheldon/async-openai-wasm/tree/main/examples)
@@ -80,51 +88,104 @@
 //!
 
 
+pub use admin::Admin;
+pub use anthropic::Anthropic;
+#[allow(deprecated)]
 pub use assistant_files::AssistantFiles;
 pub use assistants::Assistants;
 pub use audio::Audio;
 pub use batches::Batches;
 pub use chat::Chat;
-pub use client::Client;
+pub use client::{Client, ClientBuilder, RequestOptions};
 pub use completion::Completions;
 pub use embedding::Embeddings;
 pub use file::Files;
 pub use fine_tuning::FineTuning;
 pub use image::Images;
+#[allow(deprecated)]
 pub use message_files::MessageFiles;
 pub use messages::Messages;
 pub use model::Models;
 pub use moderation::Moderations;
+pub use raw::Raw;
+pub use responses::Responses;
 pub use runs::Runs;
 pub use steps::Steps;
 pub use threads::Threads;
+pub use uploads::Uploads;
 pub use vector_store_file_batches::VectorStoreFileBatches;
 pub use vector_store_files::VectorStoreFiles;
 pub use vector_stores::VectorStores;
 
+pub mod admin;
+pub mod anthropic;
 mod assistant_files;
 mod assistants;
 mod audio;
 mod batches;
+pub mod cancel;
 mod chat;
+pub mod checksum;
+pub mod citations;
+pub mod compat;
+pub mod compression;
 mod client;
 mod completion;
+#[cfg(feature = "conformance")]
+pub mod conformance;
 pub mod config;
+pub mod content_filter;
+pub mod context;
+pub mod echo;
 mod embedding;
 pub mod error;
 mod file;
 mod fine_tuning;
+pub mod health;
 mod image;
+#[cfg_attr(not(feature = "vision"), allow(dead_code))]
+mod image_info;
+pub mod json_mode;
+#[cfg(feature = "request-lint")]
+pub mod lint;
 mod message_files;
 mod messages;
+pub mod middleware;
 mod model;
 mod moderation;
+pub mod observability;
+pub mod pagination;
+pub mod prelude;
+pub mod progress;
+pub mod prompt_guard;
+pub mod rate_limit;
+mod raw;
+pub mod recorder;
+mod responses;
+pub mod retry;
 mod runs;
+pub mod sampling;
+pub mod session;
+pub mod stats;
 mod steps;
+pub mod structured_output;
+mod telemetry;
+#[cfg(all(feature = "testing", not(target_arch = "wasm32")))]
+pub mod testing;
 mod threads;
+mod time;
+pub mod timing;
+pub mod transcode;
+#[cfg(feature = "tokens")]
+pub mod tokens;
+pub mod tools;
 pub mod types;
+mod uploads;
 mod util;
+pub mod validation;
 mod vector_store_file_batches;
 mod vector_store_files;
 mod vector_stores;
+#[cfg(feature = "vision")]
+pub mod vision;
 
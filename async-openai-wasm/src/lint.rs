@@ -0,0 +1,173 @@
+//! Development time checks of requests for likely misconfigurations, which the API silently ignores
+//! or rejects with vague errors. Enabled with the `request-lint` feature, every JSON request sent by
+//! [Client](crate::Client) is linted and findings are logged as `tracing` warnings.
+//!
+//! ```
+//! use async_openai_wasm::{lint, types::CreateChatCompletionRequestArgs};
+//!
+//! let request = CreateChatCompletionRequestArgs::default()
+//!     .model("gpt-4o")
+//!     .messages([])
+//!     .tool_choice("get_weather")
+//!     .build()
+//!     .unwrap();
+//!
+//! let lints = lint::lint_request(&request);
+//! assert!(lints.iter().any(|lint| lint.field == "tool_choice"));
+//! ```
+use std::fmt::Display;
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+
+use crate::structured_output::validate_strict_schema;
+
+/// A likely misconfiguration of a request
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestLint {
+    /// The top level field of the request at fault
+    pub field: String,
+    pub message: String,
+}
+
+impl Display for RequestLint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}`: {}", self.field, self.message)
+    }
+}
+
+/// Lint the JSON serialization of `request`.
+pub fn lint_request<I: Serialize>(request: &I) -> Vec<RequestLint> {
+    let mut lints = vec![];
+    let value = match serde_json::to_value(request) {
+        Ok(value) => value,
+        Err(e) => {
+            lints.push(lint("", format!("cannot be serialized: {e}")));
+            return lints;
+        }
+    };
+    let Value::Object(fields) = value else {
+        return lints;
+    };
+
+    for (field, value) in &fields {
+        match value {
+            Value::Null => lints.push(lint(
+                field,
+                "is serialized as null instead of being left out, which the API may reject or treat differently from unset",
+            )),
+            Value::Array(values) if values.is_empty() && field != "messages" => {
+                lints.push(lint(field, "is empty; leave it unset instead"))
+            }
+            _ => {}
+        }
+    }
+
+    let set = |field: &str| !matches!(fields.get(field), None | Some(Value::Null));
+
+    if set("tools") && (set("functions") || set("function_call")) {
+        lints.push(lint(
+            "functions",
+            "the deprecated `functions` and `function_call` are set along with `tools`; migrate them to `tools` and `tool_choice`",
+        ));
+    }
+    for field in ["tool_choice", "parallel_tool_calls"] {
+        if set(field) && !set("tools") {
+            lints.push(lint(field, "has no effect without `tools`"));
+        }
+    }
+    let strict = |tool: &Value| tool["function"]["strict"] == Value::Bool(true);
+    if matches!(fields.get("tools"), Some(Value::Array(tools)) if tools.iter().any(strict))
+        && fields.get("parallel_tool_calls") != Some(&Value::Bool(false))
+    {
+        lints.push(lint(
+            "parallel_tool_calls",
+            "parallel tool calls may not match strict function schemas; set it to false",
+        ));
+    }
+    let tools = fields.get("tools").and_then(Value::as_array);
+    for tool in tools.into_iter().flatten().filter(|tool| strict(tool)) {
+        let name = tool["function"]["name"].as_str().unwrap_or_default();
+        let parameters = &tool["function"]["parameters"];
+        if parameters.is_null() {
+            continue;
+        }
+        for issue in validate_strict_schema(parameters) {
+            lints.push(lint(
+                "tools",
+                format!("schema of strict function `{name}`: {issue}"),
+            ));
+        }
+    }
+    let format = fields.get("text").map(|text| &text["format"]);
+    if let Some(format) = format.filter(|format| format["strict"] == Value::Bool(true)) {
+        for issue in validate_strict_schema(&format["schema"]) {
+            lints.push(lint("text", format!("strict schema: {issue}")));
+        }
+    }
+    if set("function_call") && !set("functions") {
+        lints.push(lint("function_call", "has no effect without `functions`"));
+    }
+    if set("stream_options") && fields.get("stream") != Some(&Value::Bool(true)) {
+        lints.push(lint(
+            "stream_options",
+            "has no effect unless `stream` is true; use `create_stream`",
+        ));
+    }
+    if set("top_logprobs") && fields.get("logprobs") != Some(&Value::Bool(true)) {
+        lints.push(lint("top_logprobs", "requires `logprobs` to be true"));
+    }
+
+    lints.extend(lint_messages(&fields));
+    lints
+}
+
+fn lint_messages(fields: &Map<String, Value>) -> Vec<RequestLint> {
+    let mut lints = vec![];
+    let Some(Value::Array(messages)) = fields.get("messages") else {
+        return lints;
+    };
+    for (index, message) in messages.iter().enumerate() {
+        if message.get("role").and_then(Value::as_str) == Some("tool")
+            && !matches!(message.get("tool_call_id"), Some(Value::String(id)) if !id.is_empty())
+        {
+            lints.push(lint(
+                "messages",
+                format!("tool message #{index} has no `tool_call_id`"),
+            ));
+        }
+    }
+    lints
+}
+
+/// Check that `request` deserializes back from its serialization unchanged,
+/// which fails when e.g. an untagged enum variant is ambiguous.
+pub fn check_round_trip<T>(request: &T) -> Result<(), RequestLint>
+where
+    T: Serialize + DeserializeOwned + PartialEq,
+{
+    let json = serde_json::to_string(request).map_err(|e| lint("", e.to_string()))?;
+    let round_tripped: T = serde_json::from_str(&json).map_err(|e| lint("", e.to_string()))?;
+    if &round_tripped == request {
+        Ok(())
+    } else {
+        Err(lint(
+            "",
+            format!("changes when serialized and deserialized back: {json}"),
+        ))
+    }
+}
+
+/// Log the lints of a request to `path` as warnings
+pub(crate) fn warn_request<I: Serialize>(path: &str, request: &I) {
+    for lint in lint_request(request) {
+        tracing::warn!(path, "request lint: {lint}");
+    }
+}
+
+fn lint(field: &str, message: impl Into<String>) -> RequestLint {
+    RequestLint {
+        field: field.into(),
+        message: message.into(),
+    }
+}
@@ -8,12 +8,17 @@ use crate::{
 };
 
 /// Files attached to a message.
+///
+/// Deprecated with the v1 Assistants API, which the `OpenAI-Beta: assistants=v2` header sent by [OpenAIConfig](crate::config::OpenAIConfig)
+/// no longer targets: attach files with the `attachments` of the message instead.
+#[deprecated]
 pub struct MessageFiles<'c, C: Config> {
     client: &'c Client<C>,
     pub thread_id: String,
     pub message_id: String,
 }
 
+#[allow(deprecated)]
 impl<'c, C: Config> MessageFiles<'c, C> {
     pub fn new(client: &'c Client<C>, thread_id: &str, message_id: &str) -> Self {
         Self {
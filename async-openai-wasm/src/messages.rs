@@ -1,10 +1,13 @@
 use serde::Serialize;
 
+#[allow(deprecated)]
+use crate::MessageFiles;
 use crate::{
     Client,
     config::Config,
     error::OpenAIError,
-    MessageFiles, types::{
+    pagination::Paginator,
+    types::{
         CreateMessageRequest, DeleteMessageResponse, ListMessagesResponse, MessageObject,
         ModifyMessageRequest,
     },
@@ -26,6 +29,10 @@ impl<'c, C: Config> Messages<'c, C> {
     }
 
     /// Call [MessageFiles] API group
+    ///
+    /// Deprecated in favor of the `attachments` of [CreateMessageRequest].
+    #[deprecated]
+    #[allow(deprecated)]
     pub fn files(&self, message_id: &str) -> MessageFiles<C> {
         MessageFiles::new(self.client, &self.thread_id, message_id)
     }
@@ -74,6 +81,15 @@ impl<'c, C: Config> Messages<'c, C> {
             .await
     }
 
+    /// Returns all the messages of the thread, fetching them page by page as the stream is consumed.
+    pub fn list_auto_paginated<Q>(&self, query: &Q) -> Paginator<'c, MessageObject>
+    where
+        C: Sync,
+        Q: Serialize + ?Sized,
+    {
+        Paginator::new::<_, ListMessagesResponse, _>(self.client, format!("/threads/{}/messages", self.thread_id), query)
+    }
+
     pub async fn delete(&self, message_id: &str) -> Result<DeleteMessageResponse, OpenAIError> {
         self.client
             .delete(&format!(
@@ -0,0 +1,83 @@
+//! Hooks into every HTTP request made by [Client](crate::Client), to add logging,
+//! refresh auth tokens or otherwise mutate requests without forking the client.
+//!
+//! ```
+//! use async_openai_wasm::{
+//!     error::OpenAIError,
+//!     middleware::{async_trait, RequestInterceptor},
+//!     Client,
+//! };
+//!
+//! struct Logger;
+//!
+//! #[async_trait]
+//! impl RequestInterceptor for Logger {
+//!     async fn before_request(&self, request: &mut reqwest::Request) -> Result<(), OpenAIError> {
+//!         println!("{} {}", request.method(), request.url());
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let client = Client::new().with_interceptor(Logger);
+//! ```
+use std::sync::Arc;
+
+/// To implement [RequestInterceptor] with, as `#[async_trait(?Send)]` on wasm32
+pub use async_convert::async_trait;
+
+use crate::error::OpenAIError;
+
+/// Hooks called around every HTTP request of a [Client](crate::Client).
+///
+/// Both hooks do nothing by default. Returning an error from either aborts the API call with that error.
+#[cfg_attr(target_arch = "wasm32", async_convert::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_convert::async_trait)]
+pub trait RequestInterceptor: Send + Sync {
+    /// Called right before `request` is sent, including for requests receiving SSE streams.
+    async fn before_request(&self, _request: &mut reqwest::Request) -> Result<(), OpenAIError> {
+        Ok(())
+    }
+
+    /// Called when the status and headers of a response are received, before its body is read,
+    /// including for responses of SSE streams, before their first event.
+    async fn after_response(&self, _response: &reqwest::Response) -> Result<(), OpenAIError> {
+        Ok(())
+    }
+}
+
+/// Chain of interceptors: `before_request` hooks run in the order interceptors were added,
+/// `after_response` hooks in the reverse order, so the first one added wraps all the others.
+#[derive(Clone, Default)]
+pub(crate) struct Interceptors(Vec<Arc<dyn RequestInterceptor>>);
+
+impl Interceptors {
+    pub(crate) fn push(&mut self, interceptor: Arc<dyn RequestInterceptor>) {
+        self.0.push(interceptor);
+    }
+
+    pub(crate) async fn before_request(
+        &self,
+        request: &mut reqwest::Request,
+    ) -> Result<(), OpenAIError> {
+        for interceptor in &self.0 {
+            interceptor.before_request(request).await?;
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn after_response(
+        &self,
+        response: &reqwest::Response,
+    ) -> Result<(), OpenAIError> {
+        for interceptor in self.0.iter().rev() {
+            interceptor.after_response(response).await?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for Interceptors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Interceptors({})", self.0.len())
+    }
+}
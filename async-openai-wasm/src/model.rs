@@ -23,6 +23,26 @@ impl<'c, C: Config> Models<'c, C> {
         self.client.get("/models").await
     }
 
+    /// Lists the models owned by `owner`, e.g. your organization id.
+    pub async fn list_owned_by(&self, owner: &str) -> Result<Vec<Model>, OpenAIError> {
+        let response = self.list().await?;
+        Ok(response
+            .data
+            .into_iter()
+            .filter(|model| model.owned_by == owner)
+            .collect())
+    }
+
+    /// Lists the fine-tuned models available to you.
+    pub async fn list_fine_tuned(&self) -> Result<Vec<Model>, OpenAIError> {
+        let response = self.list().await?;
+        Ok(response
+            .data
+            .into_iter()
+            .filter(Model::is_fine_tuned)
+            .collect())
+    }
+
     /// Retrieves a model instance, providing basic information about the model
     /// such as the owner and permissioning.
     pub async fn retrieve(&self, id: &str) -> Result<Model, OpenAIError> {
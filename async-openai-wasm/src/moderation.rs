@@ -17,7 +17,7 @@ impl<'c, C: Config> Moderations<'c, C> {
         Self { client }
     }
 
-    /// Classifies if text is potentially harmful.
+    /// Classifies if text, or text and images with the `omni-moderation` models, is potentially harmful.
     pub async fn create(
         &self,
         request: CreateModerationRequest,
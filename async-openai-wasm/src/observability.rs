@@ -0,0 +1,247 @@
+//! Presets for LLM observability gateways, attaching their headers to every request.
+//!
+//! Each preset is a [RequestInterceptor] which also propagates the [RequestContext] of the call:
+//! its trace id, tenant id and attributes become the gateway's per request metadata.
+//! Point the config at the gateway with e.g. [HELICONE_API_BASE] or [PORTKEY_API_BASE].
+//!
+//! ```
+//! use async_openai_wasm::{
+//!     config::OpenAIConfig,
+//!     observability::{Helicone, HELICONE_API_BASE},
+//!     Client,
+//! };
+//!
+//! let client = Client::with_config(OpenAIConfig::new().with_api_base(HELICONE_API_BASE))
+//!     .with_interceptor(
+//!         Helicone::new("sk-helicone-...")
+//!             .with_session_id("session-42")
+//!             .with_property("Environment", "staging"),
+//!     );
+//! ```
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+
+use crate::{
+    context::RequestContext,
+    error::OpenAIError,
+    middleware::{async_trait, RequestInterceptor},
+};
+
+/// Helicone gateway to the OpenAI API
+pub const HELICONE_API_BASE: &str = "https://oai.helicone.ai/v1";
+/// Portkey gateway
+pub const PORTKEY_API_BASE: &str = "https://api.portkey.ai/v1";
+
+/// [Helicone](https://docs.helicone.ai/helicone-headers/header-directory) headers.
+///
+/// The trace id and tenant id of the [RequestContext] are sent as the `Trace-Id` and `Tenant-Id`
+/// custom properties, and its attributes as custom properties too.
+#[derive(Debug, Clone, Default)]
+pub struct Helicone {
+    api_key: String,
+    user_id: Option<String>,
+    session_id: Option<String>,
+    session_name: Option<String>,
+    properties: Vec<(String, String)>,
+    cache: bool,
+}
+
+impl Helicone {
+    pub fn new<S: Into<String>>(api_key: S) -> Self {
+        Self {
+            api_key: api_key.into(),
+            ..Default::default()
+        }
+    }
+
+    /// End-user requests are attributed to
+    pub fn with_user_id<S: Into<String>>(mut self, user_id: S) -> Self {
+        self.user_id = Some(user_id.into());
+        self
+    }
+
+    /// Session grouping the requests
+    pub fn with_session_id<S: Into<String>>(mut self, session_id: S) -> Self {
+        self.session_id = Some(session_id.into());
+        self
+    }
+
+    pub fn with_session_name<S: Into<String>>(mut self, session_name: S) -> Self {
+        self.session_name = Some(session_name.into());
+        self
+    }
+
+    /// Custom property sent as the `Helicone-Property-{name}` header
+    pub fn with_property<K: Into<String>, V: Into<String>>(mut self, name: K, value: V) -> Self {
+        self.properties.push((name.into(), value.into()));
+        self
+    }
+
+    /// Serve identical requests from the Helicone cache
+    pub fn with_cache(mut self, cache: bool) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// The headers of a request made within `context`
+    pub fn headers(&self, context: Option<&RequestContext>) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        insert(
+            &mut headers,
+            "Helicone-Auth",
+            &format!("Bearer {}", self.api_key),
+        );
+        if let Some(user_id) = &self.user_id {
+            insert(&mut headers, "Helicone-User-Id", user_id);
+        }
+        if let Some(session_id) = &self.session_id {
+            insert(&mut headers, "Helicone-Session-Id", session_id);
+        }
+        if let Some(session_name) = &self.session_name {
+            insert(&mut headers, "Helicone-Session-Name", session_name);
+        }
+        if self.cache {
+            insert(&mut headers, "Helicone-Cache-Enabled", "true");
+        }
+
+        let mut properties = self.properties.clone();
+        if let Some(context) = context {
+            properties.extend(
+                context
+                    .trace_id()
+                    .map(|trace_id| ("Trace-Id".to_string(), trace_id.to_string())),
+            );
+            properties.extend(
+                context
+                    .tenant_id()
+                    .map(|tenant_id| ("Tenant-Id".to_string(), tenant_id.to_string())),
+            );
+            properties.extend(
+                context
+                    .attributes()
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.clone())),
+            );
+        }
+        for (name, value) in properties {
+            insert(&mut headers, &format!("Helicone-Property-{name}"), &value);
+        }
+        headers
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl RequestInterceptor for Helicone {
+    async fn before_request(&self, request: &mut reqwest::Request) -> Result<(), OpenAIError> {
+        let headers = self.headers(RequestContext::current().as_ref());
+        request.headers_mut().extend(headers);
+        Ok(())
+    }
+}
+
+/// [Portkey](https://portkey.ai/docs/api-reference/inference-api/headers) headers.
+///
+/// The trace id of the [RequestContext] is sent as `x-portkey-trace-id`, its tenant id and attributes
+/// as `x-portkey-metadata` along with the metadata set here.
+#[derive(Debug, Clone, Default)]
+pub struct Portkey {
+    api_key: String,
+    virtual_key: Option<String>,
+    provider: Option<String>,
+    config: Option<String>,
+    metadata: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Portkey {
+    pub fn new<S: Into<String>>(api_key: S) -> Self {
+        Self {
+            api_key: api_key.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Virtual key holding the provider credentials
+    pub fn with_virtual_key<S: Into<String>>(mut self, virtual_key: S) -> Self {
+        self.virtual_key = Some(virtual_key.into());
+        self
+    }
+
+    /// Provider to route requests to, e.g. `openai`, when not using a virtual key
+    pub fn with_provider<S: Into<String>>(mut self, provider: S) -> Self {
+        self.provider = Some(provider.into());
+        self
+    }
+
+    /// ID of a saved Portkey config, for fallbacks, load balancing and the like
+    pub fn with_config<S: Into<String>>(mut self, config: S) -> Self {
+        self.config = Some(config.into());
+        self
+    }
+
+    /// End-user requests are attributed to, sent as the `_user` metadata
+    pub fn with_user<S: Into<String>>(self, user: S) -> Self {
+        self.with_metadata("_user", user)
+    }
+
+    pub fn with_metadata<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.metadata
+            .insert(key.into(), serde_json::Value::String(value.into()));
+        self
+    }
+
+    /// The headers of a request made within `context`
+    pub fn headers(&self, context: Option<&RequestContext>) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        insert(&mut headers, "x-portkey-api-key", &self.api_key);
+        if let Some(virtual_key) = &self.virtual_key {
+            insert(&mut headers, "x-portkey-virtual-key", virtual_key);
+        }
+        if let Some(provider) = &self.provider {
+            insert(&mut headers, "x-portkey-provider", provider);
+        }
+        if let Some(config) = &self.config {
+            insert(&mut headers, "x-portkey-config", config);
+        }
+
+        let mut metadata = self.metadata.clone();
+        if let Some(context) = context {
+            if let Some(trace_id) = context.trace_id() {
+                insert(&mut headers, "x-portkey-trace-id", trace_id);
+            }
+            if let Some(tenant_id) = context.tenant_id() {
+                metadata.insert("tenant_id".into(), tenant_id.into());
+            }
+            for (key, value) in context.attributes() {
+                metadata.insert(key.clone(), value.clone().into());
+            }
+        }
+        if !metadata.is_empty() {
+            insert(
+                &mut headers,
+                "x-portkey-metadata",
+                &serde_json::Value::Object(metadata).to_string(),
+            );
+        }
+        headers
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl RequestInterceptor for Portkey {
+    async fn before_request(&self, request: &mut reqwest::Request) -> Result<(), OpenAIError> {
+        let headers = self.headers(RequestContext::current().as_ref());
+        request.headers_mut().extend(headers);
+        Ok(())
+    }
+}
+
+/// Insert a header, ignoring it with a warning if it is not a valid one
+fn insert(headers: &mut HeaderMap, name: &str, value: &str) {
+    match (name.parse::<HeaderName>(), value.parse::<HeaderValue>()) {
+        (Ok(name), Ok(value)) => {
+            headers.insert(name, value);
+        }
+        _ => tracing::warn!("ignoring invalid observability header: {name}"),
+    }
+}
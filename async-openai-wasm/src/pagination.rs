@@ -0,0 +1,192 @@
+//! Streams of the items of list endpoints, fetching the next page as the previous one is consumed.
+//!
+//! ```no_run
+//! use async_openai_wasm::Client;
+//! use futures::StreamExt;
+//!
+//! # async fn example() -> Result<(), async_openai_wasm::error::OpenAIError> {
+//! let client = Client::new();
+//! let mut assistants = client.assistants().list_auto_paginated(&[("limit", "100")]);
+//! while let Some(assistant) = assistants.next().await {
+//!     println!("{}", assistant?.id);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::{Stream, StreamExt, TryStreamExt};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{config::Config, error::OpenAIError, types::*, Client};
+
+/// A page of a list endpoint.
+pub trait Page: DeserializeOwned {
+    type Item;
+
+    /// Whether there are more items after the ones of this page
+    fn has_more(&self) -> bool;
+
+    /// Cursor to the next page, the ID of the last item of this page
+    fn last_id(&self) -> Option<String>;
+
+    fn into_items(self) -> Vec<Self::Item>;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+type ItemStream<'c, T> = futures::stream::BoxStream<'c, Result<T, OpenAIError>>;
+#[cfg(target_arch = "wasm32")]
+type ItemStream<'c, T> = futures::stream::LocalBoxStream<'c, Result<T, OpenAIError>>;
+
+/// Stream of the items of all the pages of a list endpoint, following their `has_more` and `last_id` cursors.
+///
+/// Pages are only fetched when the items of the previous one are consumed. The stream ends after the first error.
+pub struct Paginator<'c, T> {
+    stream: ItemStream<'c, T>,
+}
+
+impl<'c, T: Send + 'c> Paginator<'c, T> {
+    /// Items of the pages at `path`, starting with the one selected by `query`
+    pub(crate) fn new<C, P, Q>(client: &'c Client<C>, path: String, query: &Q) -> Self
+    where
+        C: Config + Sync,
+        P: Page<Item = T> + Send + 'c,
+        Q: Serialize + ?Sized,
+    {
+        let query = match query_pairs(query) {
+            Ok(query) => query,
+            Err(e) => {
+                return Self {
+                    stream: Box::pin(futures::stream::once(async { Err(e) })),
+                }
+            }
+        };
+
+        let pages = futures::stream::try_unfold(Some(query), move |query| {
+            let path = path.clone();
+            async move {
+                let Some(query) = query else {
+                    return Ok::<_, OpenAIError>(None);
+                };
+                let page: P = client.get_with_query(&path, &query).await?;
+                let next = match page.last_id() {
+                    Some(last_id) if page.has_more() => {
+                        let mut query = query;
+                        query.retain(|(key, _)| key != "after");
+                        query.push(("after".to_string(), last_id));
+                        Some(query)
+                    }
+                    _ => None,
+                };
+                Ok(Some((page.into_items(), next)))
+            }
+        });
+        let items = pages
+            .map_ok(|items| futures::stream::iter(items.into_iter().map(Ok)))
+            .try_flatten();
+        Self {
+            stream: Box::pin(items),
+        }
+    }
+}
+
+impl<T> Stream for Paginator<'_, T> {
+    type Item = Result<T, OpenAIError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.stream.poll_next_unpin(cx)
+    }
+}
+
+/// Key-value pairs of a query given as a struct, a map or a list of pairs
+fn query_pairs<Q: Serialize + ?Sized>(query: &Q) -> Result<Vec<(String, String)>, OpenAIError> {
+    let invalid = || {
+        OpenAIError::InvalidArgument(
+            "query must be a struct, a map or a list of key-value pairs".into(),
+        )
+    };
+    let value = |value: serde_json::Value| match value {
+        serde_json::Value::String(value) => Some(value),
+        serde_json::Value::Null => None,
+        value => Some(value.to_string()),
+    };
+
+    let pairs = match serde_json::to_value(query).map_err(|_| invalid())? {
+        serde_json::Value::Object(map) => map
+            .into_iter()
+            .filter_map(|(key, v)| Some((key, value(v)?)))
+            .collect(),
+        serde_json::Value::Array(pairs) => pairs
+            .into_iter()
+            .map(|pair| match pair {
+                serde_json::Value::Array(pair) if pair.len() == 2 => {
+                    let mut pair = pair.into_iter();
+                    match (pair.next(), pair.next().and_then(value)) {
+                        (Some(serde_json::Value::String(key)), Some(v)) => Ok(Some((key, v))),
+                        (Some(serde_json::Value::String(_)), None) => Ok(None),
+                        _ => Err(invalid()),
+                    }
+                }
+                _ => Err(invalid()),
+            })
+            .filter_map(Result::transpose)
+            .collect::<Result<_, _>>()?,
+        serde_json::Value::Null => vec![],
+        _ => return Err(invalid()),
+    };
+    Ok(pairs)
+}
+
+macro_rules! impl_page_with_cursor {
+    ($page:ty, $item:ty) => {
+        impl Page for $page {
+            type Item = $item;
+
+            fn has_more(&self) -> bool {
+                self.has_more
+            }
+
+            fn last_id(&self) -> Option<String> {
+                self.last_id.clone()
+            }
+
+            fn into_items(self) -> Vec<Self::Item> {
+                self.data
+            }
+        }
+    };
+}
+
+impl_page_with_cursor!(ListAssistantsResponse, AssistantObject);
+impl_page_with_cursor!(ListBatchesResponse, Batch);
+impl_page_with_cursor!(ChatCompletionList, CreateChatCompletionResponse);
+impl_page_with_cursor!(ChatCompletionMessageList, ChatCompletionStoreMessage);
+impl_page_with_cursor!(ListFilesResponse, OpenAIFile);
+impl_page_with_cursor!(ListMessagesResponse, MessageObject);
+impl_page_with_cursor!(ListRunsResponse, RunObject);
+impl_page_with_cursor!(ListRunStepsResponse, RunStepObject);
+impl_page_with_cursor!(ListVectorStoresResponse, VectorStoreObject);
+impl_page_with_cursor!(ListVectorStoreFilesResponse, VectorStoreFileObject);
+impl_page_with_cursor!(UserListResponse, User);
+impl_page_with_cursor!(InviteListResponse, Invite);
+impl_page_with_cursor!(ProjectListResponse, Project);
+impl_page_with_cursor!(ProjectApiKeyListResponse, ProjectApiKey);
+impl_page_with_cursor!(ProjectServiceAccountListResponse, ProjectServiceAccount);
+
+/// Jobs are listed without `last_id`, the ID of the last job is the cursor.
+impl Page for ListPaginatedFineTuningJobsResponse {
+    type Item = FineTuningJob;
+
+    fn has_more(&self) -> bool {
+        self.has_more
+    }
+
+    fn last_id(&self) -> Option<String> {
+        self.data.last().map(|job| job.id.clone())
+    }
+
+    fn into_items(self) -> Vec<Self::Item> {
+        self.data
+    }
+}
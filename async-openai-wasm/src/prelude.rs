@@ -0,0 +1,40 @@
+//! The types most code using the client needs, to import at once.
+//!
+//! ```
+//! use async_openai_wasm::prelude::*;
+//!
+//! let client = Client::new();
+//! let request = CreateChatCompletionRequestArgs::default()
+//!     .model("gpt-4o-mini")
+//!     .messages([
+//!         ChatCompletionRequestSystemMessageArgs::default()
+//!             .content("You are a helpful assistant.")
+//!             .build()?
+//!             .into(),
+//!         ChatCompletionRequestUserMessageArgs::default()
+//!             .content("Who won the world series in 2020?")
+//!             .build()?
+//!             .into(),
+//!     ])
+//!     .build()?;
+//! # Ok::<(), OpenAIError>(())
+//! ```
+//!
+//! The prelude is curated: it only grows with types used across most programs, and items are not
+//! removed from it outside of breaking releases. Import the other types from [types](crate::types).
+
+pub use crate::config::{AzureConfig, Config, OpenAIConfig};
+pub use crate::error::OpenAIError;
+pub use crate::types::{
+    AssistantEventStreamExt, ChatCompletionMessageToolCall,
+    ChatCompletionRequestAssistantMessageArgs, ChatCompletionRequestDeveloperMessageArgs,
+    ChatCompletionRequestMessage, ChatCompletionRequestMessageContentPartImageArgs,
+    ChatCompletionRequestMessageContentPartTextArgs, ChatCompletionRequestSystemMessageArgs,
+    ChatCompletionRequestToolMessageArgs, ChatCompletionRequestUserMessageArgs,
+    ChatCompletionResponseMessage, ChatCompletionStreamAccumulator, ChatCompletionStreamExt,
+    ChatCompletionToolArgs, ChatCompletionToolType, CreateChatCompletionRequest,
+    CreateChatCompletionRequestArgs, CreateChatCompletionResponse,
+    CreateChatCompletionStreamResponse, CreateEmbeddingRequestArgs, CreateEmbeddingResponse,
+    FinishReason, FunctionObjectArgs, ImageDetail, ImageUrlArgs, Role,
+};
+pub use crate::{Client, ClientBuilder};
@@ -0,0 +1,96 @@
+//! Progress of request bodies being sent, for file uploads and other multipart requests,
+//! and of file contents being downloaded.
+//!
+//! ```
+//! use async_openai_wasm::{progress::UploadProgress, Client};
+//!
+//! let client = Client::new().with_upload_progress(|progress: UploadProgress| {
+//!     if let Some(total) = progress.total {
+//!         println!("{}/{} bytes sent", progress.sent, total);
+//!     }
+//! });
+//! ```
+use std::sync::Arc;
+
+/// Bytes of a request body sent so far
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UploadProgress {
+    pub sent: u64,
+    /// Length of the whole body, when known
+    pub total: Option<u64>,
+}
+
+/// Bytes of a response body received so far
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DownloadProgress {
+    pub received: u64,
+    /// Length of the whole body, when known
+    pub total: Option<u64>,
+}
+
+type Callback = Arc<dyn Fn(UploadProgress) + Send + Sync>;
+
+/// Callback reporting the upload progress of request bodies, if any
+#[derive(Clone, Default)]
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+pub(crate) struct UploadProgressHook(Option<Callback>);
+
+impl UploadProgressHook {
+    pub(crate) fn new<F: Fn(UploadProgress) + Send + Sync + 'static>(callback: F) -> Self {
+        Self(Some(Arc::new(callback)))
+    }
+
+    /// Report the progress of the body of `request` as it is sent.
+    ///
+    /// Bodies are sent at once by `fetch` on wasm32, which does not report progress.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn wrap(&self, request: &mut reqwest::Request) {
+        use std::pin::Pin;
+        use std::task::Poll;
+
+        use futures::{ready, StreamExt};
+        use http_body::Body as _;
+        use reqwest::header::CONTENT_LENGTH;
+
+        let Some(callback) = self.0.clone() else {
+            return;
+        };
+        let Some(mut body) = request.body_mut().take() else {
+            return;
+        };
+        let total = request
+            .headers()
+            .get(CONTENT_LENGTH)
+            .and_then(|length| length.to_str().ok()?.parse().ok());
+
+        let data = futures::stream::poll_fn(move |cx| loop {
+            match ready!(Pin::new(&mut body).poll_frame(cx)) {
+                Some(Ok(frame)) => {
+                    // trailers are not sent by reqwest
+                    if let Ok(data) = frame.into_data() {
+                        return Poll::Ready(Some(Ok::<_, reqwest::Error>(data)));
+                    }
+                }
+                Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                None => return Poll::Ready(None),
+            }
+        });
+        let mut sent = 0;
+        let data = data.inspect(move |data| {
+            if let Ok(data) = data {
+                sent += data.len() as u64;
+                callback(UploadProgress { sent, total });
+            }
+        });
+        *request.body_mut() = Some(reqwest::Body::wrap_stream(data));
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn wrap(&self, _request: &mut reqwest::Request) {}
+}
+
+impl std::fmt::Debug for UploadProgressHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "UploadProgressHook({})", self.0.is_some())
+    }
+}
@@ -0,0 +1,243 @@
+//! Rate limits of the API key from the `x-ratelimit-*` headers of responses, for client side throttling.
+//!
+//! ```no_run
+//! use async_openai_wasm::Client;
+//!
+//! # async fn example() -> Result<(), async_openai_wasm::error::OpenAIError> {
+//! let client = Client::new();
+//! client.models().list().await?;
+//! if let Some(rate_limit) = client.last_rate_limit() {
+//!     if rate_limit.remaining_requests == Some(0) {
+//!         println!("requests allowed again in {:?}", rate_limit.reset_requests);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Clients can also throttle themselves with [Client::with_rate_limit](crate::Client::with_rate_limit),
+//! delaying requests to stay under requests and tokens per minute limits:
+//!
+//! ```
+//! use async_openai_wasm::Client;
+//!
+//! // shared by all API groups and clones of the client
+//! let client = Client::new().with_rate_limit(500, 200_000);
+//! ```
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use reqwest::header::HeaderMap;
+
+use crate::time::{sleep, Instant};
+
+/// Rate limits of a response, each `None` when their header is missing or invalid.
+///
+/// See <https://platform.openai.com/docs/guides/rate-limits#rate-limits-in-headers>
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Maximum number of requests allowed before exhausting the limit
+    pub limit_requests: Option<u64>,
+    /// Maximum number of tokens allowed before exhausting the limit
+    pub limit_tokens: Option<u64>,
+    /// Number of requests left before exhausting the limit
+    pub remaining_requests: Option<u64>,
+    /// Number of tokens left before exhausting the limit
+    pub remaining_tokens: Option<u64>,
+    /// Time until the request limit is reset to its initial state, from when the response was received
+    pub reset_requests: Option<Duration>,
+    /// Time until the token limit is reset to its initial state, from when the response was received
+    pub reset_tokens: Option<Duration>,
+}
+
+impl RateLimit {
+    /// Rate limits from the headers of a response, `None` if it has no `x-ratelimit-*` header
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        if !headers
+            .keys()
+            .any(|name| name.as_str().starts_with("x-ratelimit-"))
+        {
+            return None;
+        }
+        let header = |name: &str| headers.get(name)?.to_str().ok().map(str::trim);
+        let count = |name: &str| header(name)?.parse().ok();
+        let reset = |name: &str| parse_duration(header(name)?);
+
+        Some(Self {
+            limit_requests: count("x-ratelimit-limit-requests"),
+            limit_tokens: count("x-ratelimit-limit-tokens"),
+            remaining_requests: count("x-ratelimit-remaining-requests"),
+            remaining_tokens: count("x-ratelimit-remaining-tokens"),
+            reset_requests: reset("x-ratelimit-reset-requests"),
+            reset_tokens: reset("x-ratelimit-reset-tokens"),
+        })
+    }
+}
+
+/// Token buckets of requests and tokens per minute, delaying requests which would exceed them.
+///
+/// Buckets start full, allowing bursts of up to a minute of requests, and refill continuously.
+/// Requests wait in the order they are made, on wasm32 too. The tokens of a request are estimated
+/// as one per 4 bytes of its body, plus the `max_tokens`, `max_completion_tokens` or `max_output_tokens` it asks for.
+///
+/// Clones share their buckets.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    requests: Option<Arc<Mutex<TokenBucket>>>,
+    tokens: Option<Arc<Mutex<TokenBucket>>>,
+}
+
+impl RateLimiter {
+    /// Limit requests to `requests_per_minute` and their tokens to `tokens_per_minute`, `0` disabling a limit
+    pub fn new(requests_per_minute: u32, tokens_per_minute: u32) -> Self {
+        let bucket = |per_minute: u32| {
+            (per_minute > 0).then(|| Arc::new(Mutex::new(TokenBucket::new(per_minute))))
+        };
+        Self {
+            requests: bucket(requests_per_minute),
+            tokens: bucket(tokens_per_minute),
+        }
+    }
+
+    /// Wait until a request of `tokens` tokens can be made without exceeding the limits
+    pub async fn acquire(&self, tokens: u32) {
+        let reserve = |bucket: &Option<Arc<Mutex<TokenBucket>>>, cost: u32| {
+            bucket
+                .as_ref()
+                .map(|bucket| bucket.lock().unwrap().reserve(cost))
+                .unwrap_or_default()
+        };
+        let delay = reserve(&self.requests, 1).max(reserve(&self.tokens, tokens));
+        if !delay.is_zero() {
+            tracing::debug!("Delaying request by {delay:?} to stay under the rate limit");
+            sleep(delay).await;
+        }
+    }
+
+    /// Wait until `request` can be made, see [RateLimiter::acquire]
+    pub(crate) async fn acquire_for(&self, request: &reqwest::Request) {
+        let tokens = match &self.tokens {
+            Some(_) => estimate_tokens(request.body().and_then(|body| body.as_bytes())),
+            None => 0,
+        };
+        self.acquire(tokens).await
+    }
+}
+
+/// Bucket refilling `capacity` tokens per minute, whose balance goes negative as requests wait for their turn
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: f64,
+    available: f64,
+    refilled: Instant,
+}
+
+impl TokenBucket {
+    fn new(per_minute: u32) -> Self {
+        Self {
+            capacity: per_minute as f64,
+            available: per_minute as f64,
+            refilled: Instant::now(),
+        }
+    }
+
+    /// Take `cost` tokens, returning how long to wait until they are refilled.
+    /// A cost over the capacity takes the whole capacity, instead of never being allowed.
+    fn reserve(&mut self, cost: u32) -> Duration {
+        let per_second = self.capacity / 60.0;
+        let elapsed = self.refilled.elapsed().as_secs_f64();
+        self.refilled = Instant::now();
+        self.available = (self.available + elapsed * per_second).min(self.capacity);
+        self.available -= (cost as f64).min(self.capacity);
+        if self.available >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.available / per_second)
+        }
+    }
+}
+
+/// Tokens of a request with a JSON `body`: one per 4 bytes, plus the completion tokens it asks for at most
+fn estimate_tokens(body: Option<&[u8]>) -> u32 {
+    let Some(body) = body else {
+        return 0;
+    };
+    let max_tokens = serde_json::from_slice::<serde_json::Value>(body)
+        .ok()
+        .and_then(|body| {
+            ["max_tokens", "max_completion_tokens", "max_output_tokens"]
+                .iter()
+                .find_map(|field| body.get(field)?.as_u64())
+        })
+        .unwrap_or_default();
+    ((body.len() as u64 + 3) / 4 + max_tokens).min(u32::MAX as u64) as u32
+}
+
+/// Upper bound of reset durations, beyond the daily windows of the longest limits
+const MAX_RESET: Duration = Duration::from_secs(7 * 24 * 3600);
+
+/// Durations formatted like `1s`, `6m0s`, `20ms` or `1h2m3.5s`, up to [MAX_RESET]
+fn parse_duration(value: &str) -> Option<Duration> {
+    let mut rest = value;
+    let mut secs = 0.0;
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(rest.len());
+        let number: f64 = rest[..number_len].parse().ok()?;
+        rest = &rest[number_len..];
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(rest.len());
+        let unit = match &rest[..unit_len] {
+            "h" => 3600.0,
+            "m" => 60.0,
+            "s" => 1.0,
+            "ms" => 1e-3,
+            "us" | "µs" => 1e-6,
+            "ns" => 1e-9,
+            _ => return None,
+        };
+        rest = &rest[unit_len..];
+        secs += number * unit;
+    }
+    (!value.is_empty() && secs.is_finite())
+        .then(|| Duration::from_secs_f64(secs.min(MAX_RESET.as_secs_f64())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations_of_reset_headers() {
+        assert_eq!(parse_duration("1s"), Some(Duration::from_secs(1)));
+        assert_eq!(parse_duration("6m0s"), Some(Duration::from_secs(360)));
+        assert_eq!(parse_duration("20ms"), Some(Duration::from_millis(20)));
+        assert_eq!(parse_duration("1h2m3.5s"), Some(Duration::from_millis(3_723_500)));
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("soon"), None);
+        assert_eq!(parse_duration("100000000000000000000s"), Some(MAX_RESET));
+        assert_eq!(parse_duration("99999999999999999999h1s"), Some(MAX_RESET));
+    }
+
+    #[test]
+    fn buckets_delay_requests_over_capacity() {
+        let mut bucket = TokenBucket::new(60);
+        assert_eq!(bucket.reserve(60), Duration::ZERO);
+        let delay = bucket.reserve(2);
+        assert!(delay > Duration::from_millis(1900) && delay <= Duration::from_secs(2));
+        // waiting requests queue up behind each other
+        assert!(bucket.reserve(1) > delay);
+
+        let mut bucket = TokenBucket::new(60);
+        assert_eq!(bucket.reserve(1_000), Duration::ZERO);
+    }
+
+    #[test]
+    fn tokens_of_request_bodies() {
+        assert_eq!(estimate_tokens(None), 0);
+        assert_eq!(estimate_tokens(Some(b"{}")), 1);
+        assert_eq!(estimate_tokens(Some(br#"{"max_tokens": 100}"#)), 105);
+    }
+}
@@ -0,0 +1,85 @@
+use bytes::Bytes;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{config::Config, error::OpenAIError, types::JsonEventStream, Client};
+
+/// Untyped requests to any endpoint, made with the config, retries, interceptors and streaming of the client,
+/// to call endpoints which have no typed support yet.
+///
+/// Paths are relative to the API base, e.g. `/chat/completions`.
+pub struct Raw<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Raw<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    /// GET {path} and return the response body as JSON
+    pub async fn get(&self, path: &str) -> Result<Value, OpenAIError> {
+        self.client.get(check_path(path)?).await
+    }
+
+    /// GET {path} with `query` and return the response body as JSON
+    pub async fn get_with_query<Q>(&self, path: &str, query: &Q) -> Result<Value, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.client.get_with_query(check_path(path)?, query).await
+    }
+
+    /// GET {path} and return the response body as is, e.g. the content of a file
+    pub async fn get_bytes(&self, path: &str) -> Result<Bytes, OpenAIError> {
+        self.client
+            .get_raw_with_progress(check_path(path)?, |_| {})
+            .await
+    }
+
+    /// POST `body` as JSON to {path} and return the response body as JSON
+    pub async fn post<I: Serialize>(&self, path: &str, body: I) -> Result<Value, OpenAIError> {
+        self.client.post(check_path(path)?, body).await
+    }
+
+    /// POST `body` as JSON to {path} and return the response body as is, e.g. audio
+    pub async fn post_bytes<I: Serialize>(
+        &self,
+        path: &str,
+        body: I,
+    ) -> Result<Bytes, OpenAIError> {
+        self.client.post_raw(check_path(path)?, body).await
+    }
+
+    /// POST `body` as JSON to {path} and stream the data of the server-sent events of the response as JSON.
+    ///
+    /// `"stream": true` is added to `body` when it is an object without a `stream` field.
+    pub async fn post_stream<I: Serialize>(
+        &self,
+        path: &str,
+        body: I,
+    ) -> Result<JsonEventStream, OpenAIError> {
+        let path = check_path(path)?;
+        let mut body = serde_json::to_value(body)
+            .map_err(|e| OpenAIError::InvalidArgument(format!("cannot serialize request: {e}")))?;
+        if let Value::Object(fields) = &mut body {
+            fields.entry("stream").or_insert(Value::Bool(true));
+        }
+        self.client.post_stream(path, body).await
+    }
+
+    /// DELETE {path} and return the response body as JSON
+    pub async fn delete(&self, path: &str) -> Result<Value, OpenAIError> {
+        self.client.delete(check_path(path)?).await
+    }
+}
+
+fn check_path(path: &str) -> Result<&str, OpenAIError> {
+    if path.starts_with('/') {
+        Ok(path)
+    } else {
+        Err(OpenAIError::InvalidArgument(format!(
+            "raw path {path:?} must start with /, e.g. /chat/completions"
+        )))
+    }
+}
@@ -0,0 +1,142 @@
+//! Recording of the events of SSE streams with their timestamps, into a log to debug streams
+//! of production, or to replay them with [MockResponse::replay](crate::testing::MockResponse::replay) in tests.
+//!
+//! ```
+//! use async_openai_wasm::{recorder::StreamRecorder, Client};
+//!
+//! let recorder = StreamRecorder::new();
+//! let client = Client::new().with_stream_recorder(recorder.clone());
+//! // ... stream chat completions with the client, then
+//! let log = recorder.to_jsonl();
+//! ```
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::OpenAIError;
+use crate::time::{unix_timestamp_millis, Instant};
+
+/// Event received on an SSE stream
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    /// Number of the stream among the ones of the recorder, in order of request
+    pub stream: u64,
+    /// Path of the URL of the request, e.g. `/v1/chat/completions`
+    pub path: String,
+    /// Milliseconds since the Unix epoch when the event was received
+    pub timestamp_ms: u64,
+    /// Milliseconds since the request was sent when the event was received
+    pub elapsed_ms: u64,
+    /// Name of the event, `message` for events without one, like the ones of chat completion streams
+    pub event: String,
+    pub data: String,
+}
+
+#[derive(Debug, Default)]
+struct Log {
+    streams: u64,
+    events: Vec<RecordedEvent>,
+}
+
+/// Log of the events of the SSE streams of the clients it is set on with
+/// [Client::with_stream_recorder](crate::Client::with_stream_recorder), shared by its clones.
+///
+/// Events are kept in memory until [cleared](StreamRecorder::clear).
+#[derive(Debug, Clone, Default)]
+pub struct StreamRecorder {
+    log: Arc<Mutex<Log>>,
+}
+
+impl StreamRecorder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Recorder holding `events`, e.g. loaded from a log
+    pub fn with_events(events: Vec<RecordedEvent>) -> Self {
+        let streams = events.iter().map(|event| event.stream + 1).max().unwrap_or(0);
+        Self {
+            log: Arc::new(Mutex::new(Log { streams, events })),
+        }
+    }
+
+    /// Recorder holding the events of `jsonl`, one JSON [RecordedEvent] per line
+    pub fn from_jsonl(jsonl: &str) -> Result<Self, OpenAIError> {
+        let events = jsonl
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()
+            .map_err(OpenAIError::JSONDeserialize)?;
+        Ok(Self::with_events(events))
+    }
+
+    /// Events recorded so far, in order of reception
+    pub fn events(&self) -> Vec<RecordedEvent> {
+        self.log.lock().unwrap().events.clone()
+    }
+
+    /// Events of the stream number `stream`
+    pub fn stream_events(&self, stream: u64) -> Vec<RecordedEvent> {
+        self.log
+            .lock()
+            .unwrap()
+            .events
+            .iter()
+            .filter(|event| event.stream == stream)
+            .cloned()
+            .collect()
+    }
+
+    /// Events recorded so far as JSON lines
+    pub fn to_jsonl(&self) -> String {
+        self.log
+            .lock()
+            .unwrap()
+            .events
+            .iter()
+            .map(|event| serde_json::to_string(event).expect("events serialize to JSON") + "\n")
+            .collect()
+    }
+
+    /// Forget the events recorded so far
+    pub fn clear(&self) {
+        self.log.lock().unwrap().events.clear();
+    }
+
+    /// Recording of a new stream of a request to `path`
+    pub(crate) fn start(&self, path: &str) -> StreamRecording {
+        let mut log = self.log.lock().unwrap();
+        let stream = log.streams;
+        log.streams += 1;
+        StreamRecording {
+            recorder: self.clone(),
+            stream,
+            path: path.into(),
+            started: Instant::now(),
+        }
+    }
+}
+
+/// Recording of the events of one stream
+#[derive(Debug)]
+pub(crate) struct StreamRecording {
+    recorder: StreamRecorder,
+    stream: u64,
+    path: String,
+    started: Instant,
+}
+
+impl StreamRecording {
+    pub(crate) fn record(&self, event: &eventsource_stream::Event) {
+        let event = RecordedEvent {
+            stream: self.stream,
+            path: self.path.clone(),
+            timestamp_ms: unix_timestamp_millis(),
+            elapsed_ms: self.started.elapsed().as_millis() as u64,
+            event: event.event.clone(),
+            data: event.data.clone(),
+        };
+        self.recorder.log.lock().unwrap().events.push(event);
+    }
+}
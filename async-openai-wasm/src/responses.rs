@@ -0,0 +1,127 @@
+use serde::Serialize;
+
+use crate::{
+    Client, RequestOptions,
+    cancel::CancellationToken,
+    config::Config,
+    error::OpenAIError,
+    telemetry,
+    types::{
+        CreateResponseRequest, DeleteResponseResponse, ListResponseInputItemsResponse, Response,
+        ResponseStream,
+    },
+};
+
+/// Generate model responses from text, image and file inputs, with built-in tools
+/// such as web search and file search, and multi-turn conversations via `previous_response_id`.
+///
+/// Related guide: [Responses](https://platform.openai.com/docs/api-reference/responses)
+pub struct Responses<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Responses<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    /// Creates a model response.
+    pub async fn create(
+        &self,
+        mut request: CreateResponseRequest,
+    ) -> Result<Response, OpenAIError> {
+        if request.stream.is_some() && request.stream.unwrap() {
+            return Err(OpenAIError::InvalidArgument(
+                "When stream is true, use Responses::create_stream".into(),
+            ));
+        }
+        self.client.inject_default_user(&mut request.user);
+        let span = telemetry::span(self.client.config(), "chat", &request);
+        telemetry::instrument(span, self.client.post("/responses", request)).await
+    }
+
+    /// Creates a model response, streamed as [server-sent events](https://developer.mozilla.org/en-US/docs/Web/API/Server-sent_events/Using_server-sent_events#Event_stream_format) while it is generated.
+    ///
+    /// [ResponseStream] is a parsed SSE stream which ends once the response is completed, failed or incomplete.
+    pub async fn create_stream(
+        &self,
+        mut request: CreateResponseRequest,
+    ) -> Result<ResponseStream, OpenAIError> {
+        if request.stream.is_some() && !request.stream.unwrap() {
+            return Err(OpenAIError::InvalidArgument(
+                "When stream is false, use Responses::create".into(),
+            ));
+        }
+
+        request.stream = Some(true);
+        self.client.inject_default_user(&mut request.user);
+
+        self.client.post_stream("/responses", request).await
+    }
+
+    /// Creates a model response like [Responses::create], with the timeout, headers and cancellation of `options`
+    pub async fn create_with_options(
+        &self,
+        request: CreateResponseRequest,
+        options: &RequestOptions,
+    ) -> Result<Response, OpenAIError> {
+        let client = self.client.with_request_options(options);
+        Responses::new(&client).create(request).await
+    }
+
+    /// Creates a model response stream like [Responses::create_stream], with the timeout, headers and cancellation
+    /// of `options`, the timeout being the one between events
+    pub async fn create_stream_with_options(
+        &self,
+        request: CreateResponseRequest,
+        options: &RequestOptions,
+    ) -> Result<ResponseStream, OpenAIError> {
+        let client = self.client.with_request_options(options);
+        Responses::new(&client).create_stream(request).await
+    }
+
+    /// Creates a model response like [Responses::create], aborted once `token` is cancelled
+    pub async fn create_with_cancel(
+        &self,
+        request: CreateResponseRequest,
+        token: &CancellationToken,
+    ) -> Result<Response, OpenAIError> {
+        let options = RequestOptions::new().with_cancellation(token.clone());
+        self.create_with_options(request, &options).await
+    }
+
+    /// Creates a model response stream like [Responses::create_stream], ending with [OpenAIError::Cancelled]
+    /// once `token` is cancelled, which aborts the request and stops reading its body
+    pub async fn create_stream_with_cancel(
+        &self,
+        request: CreateResponseRequest,
+        token: &CancellationToken,
+    ) -> Result<ResponseStream, OpenAIError> {
+        let options = RequestOptions::new().with_cancellation(token.clone());
+        self.create_stream_with_options(request, &options).await
+    }
+
+    /// Retrieves a model response with the given ID.
+    pub async fn retrieve(&self, response_id: &str) -> Result<Response, OpenAIError> {
+        self.client.get(&format!("/responses/{response_id}")).await
+    }
+
+    /// Deletes a model response with the given ID.
+    pub async fn delete(&self, response_id: &str) -> Result<DeleteResponseResponse, OpenAIError> {
+        self.client.delete(&format!("/responses/{response_id}")).await
+    }
+
+    /// Returns a list of input items for a given response.
+    pub async fn list_input_items<Q>(
+        &self,
+        response_id: &str,
+        query: &Q,
+    ) -> Result<ListResponseInputItemsResponse, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.client
+            .get_with_query(&format!("/responses/{response_id}/input_items"), query)
+            .await
+    }
+}
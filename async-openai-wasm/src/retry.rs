@@ -0,0 +1,485 @@
+//! Retrying failed requests with exponential backoff, natively and on wasm32.
+//!
+//! ```
+//! use std::time::Duration;
+//! use async_openai_wasm::{retry::RetryPolicy, Client};
+//!
+//! let client = Client::new().with_retry_policy(
+//!     RetryPolicy::default()
+//!         .with_max_attempts(5)
+//!         .with_max_elapsed_time(Duration::from_secs(120)),
+//! );
+//! ```
+//!
+//! Requests receiving SSE streams are not retried, but chat completion streams failing midway can be resumed
+//! as a [StreamRetryPolicy] says with [Chat::create_stream_resumable](crate::Chat::create_stream_resumable).
+//!
+//! During provider incidents, every failing request retrying multiplies the traffic sent to the provider.
+//! A [RetryBudget] caps the retries of a client and its clones, whatever the requests or helpers they come from:
+//!
+//! ```
+//! use async_openai_wasm::{retry::{RetryBudget, RetryPolicy}, Client};
+//!
+//! let client = Client::new()
+//!     .with_retry_policy(RetryPolicy::default())
+//!     .with_retry_budget(RetryBudget::new(60));
+//! ```
+//!
+//! Requests with `service_tier: flex` are refused with resource unavailable errors when capacity runs out.
+//! They are retried at the flex tier like other rate limits, and can fall back to the `default` tier
+//! after a number of refused attempts, trading the flex discount for latency:
+//!
+//! ```
+//! use async_openai_wasm::{retry::RetryPolicy, Client};
+//!
+//! let client = Client::new().with_retry_policy(
+//!     RetryPolicy::default()
+//!         .with_max_attempts(6)
+//!         .with_flex_fallback_after(3),
+//! );
+//! ```
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rand::Rng;
+use reqwest::{header::HeaderMap, StatusCode};
+
+use crate::{error::ApiError, time::Instant};
+
+/// When and how often to retry a failed request.
+///
+/// [RetryPolicy::default] retries rate limits, overloads and server errors up to 3 times within a minute;
+/// clients do not retry unless given a policy with [Client::with_retry_policy](crate::Client::with_retry_policy).
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one. `1` disables retries.
+    pub max_attempts: u32,
+    /// HTTP statuses of responses to retry
+    pub retry_statuses: Vec<u16>,
+    /// Whether to retry when no response is received, e.g. on connection errors or timeouts
+    pub retry_transport_errors: bool,
+    /// Delay before the first retry
+    pub initial_backoff: Duration,
+    /// Upper bound of the delay between attempts
+    pub max_backoff: Duration,
+    /// Factor the delay grows by after each attempt
+    pub multiplier: f64,
+    /// Randomization of the delays: a delay `d` becomes a random one between `d * (1 - jitter)` and `d * (1 + jitter)`.
+    pub jitter: f64,
+    /// No retry is made once this much time has elapsed since the first attempt, or would have before it is made.
+    pub max_elapsed_time: Option<Duration>,
+    /// Whether to wait for as long as the `Retry-After`/`retry-after-ms` headers of a response ask, up to `max_backoff`
+    pub honor_retry_after: bool,
+    /// Number of attempts of a `service_tier: flex` request refused as resource unavailable after which
+    /// it is retried with the `default` tier; `None` keeps retrying at the flex tier
+    pub flex_fallback_after: Option<u32>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            retry_statuses: vec![408, 409, 429, 500, 502, 503, 504],
+            retry_transport_errors: true,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: 0.5,
+            max_elapsed_time: Some(Duration::from_secs(60)),
+            honor_retry_after: true,
+            flex_fallback_after: None,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Make a single attempt
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    pub fn with_retry_statuses<I: IntoIterator<Item = u16>>(mut self, retry_statuses: I) -> Self {
+        self.retry_statuses = retry_statuses.into_iter().collect();
+        self
+    }
+
+    pub fn with_retry_transport_errors(mut self, retry_transport_errors: bool) -> Self {
+        self.retry_transport_errors = retry_transport_errors;
+        self
+    }
+
+    pub fn with_initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_max_elapsed_time(mut self, max_elapsed_time: Duration) -> Self {
+        self.max_elapsed_time = Some(max_elapsed_time);
+        self
+    }
+
+    pub fn without_max_elapsed_time(mut self) -> Self {
+        self.max_elapsed_time = None;
+        self
+    }
+
+    pub fn with_honor_retry_after(mut self, honor_retry_after: bool) -> Self {
+        self.honor_retry_after = honor_retry_after;
+        self
+    }
+
+    /// Fall back to the `default` tier after `attempts` attempts of a flex request refused as resource unavailable
+    pub fn with_flex_fallback_after(mut self, attempts: u32) -> Self {
+        self.flex_fallback_after = Some(attempts);
+        self
+    }
+
+    pub fn without_flex_fallback(mut self) -> Self {
+        self.flex_fallback_after = None;
+        self
+    }
+
+    /// Whether a response with `status` and `error` warrants a retry, regardless of attempts made so far
+    pub fn is_retryable(&self, status: StatusCode, error: &ApiError) -> bool {
+        // API returns 429 also when:
+        // "You exceeded your current quota, please check your plan and billing details."
+        // which retrying does not help with
+        if error.r#type.as_deref() == Some("insufficient_quota") {
+            return false;
+        }
+        self.is_retryable_status(status)
+    }
+
+    /// Whether a response with `status` warrants a retry, for responses without an error of the API,
+    /// e.g. the HTML pages of gateways
+    pub fn is_retryable_status(&self, status: StatusCode) -> bool {
+        self.retry_statuses.contains(&status.as_u16())
+    }
+
+    /// Whether a response with `status` and `error` refuses a request for lack of capacity at its service tier,
+    /// as flex processing does
+    pub fn is_resource_unavailable(status: StatusCode, error: &ApiError) -> bool {
+        status == StatusCode::TOO_MANY_REQUESTS
+            && [error.code.as_deref(), error.r#type.as_deref()].contains(&Some("resource_unavailable"))
+    }
+
+    /// Delay before the retry following `attempt` (starting at 1), without jitter
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        exponential_backoff(self.initial_backoff, self.multiplier, self.max_backoff, attempt)
+    }
+
+    /// Delay before the retry following `attempt`, or `None` if no more retries should be made
+    pub(crate) fn next_delay(
+        &self,
+        attempt: u32,
+        elapsed: Duration,
+        headers: Option<&HeaderMap>,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+
+        let retry_after = headers
+            .filter(|_| self.honor_retry_after)
+            .and_then(|headers| retry_after(headers, self.max_backoff));
+        let delay = match retry_after {
+            Some(retry_after) => retry_after,
+            None => {
+                let backoff = self.backoff(attempt).as_secs_f64();
+                let jitter = self.jitter.clamp(0.0, 1.0);
+                let factor = if jitter > 0.0 {
+                    rand::thread_rng().gen_range(1.0 - jitter..=1.0 + jitter)
+                } else {
+                    1.0
+                };
+                Duration::from_secs_f64(backoff * factor)
+            }
+        };
+
+        match self.max_elapsed_time {
+            Some(max_elapsed_time) if elapsed + delay > max_elapsed_time => None,
+            _ => Some(delay),
+        }
+    }
+}
+
+/// When and how often to resume a chat completion stream which fails or ends before its answer is complete,
+/// e.g. on a network blip, see [Chat::create_stream_resumable](crate::Chat::create_stream_resumable).
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamRetryPolicy {
+    /// Maximum number of times a stream is resumed. `0` disables resuming.
+    pub max_resumes: u32,
+    /// Delay before the first resume
+    pub initial_backoff: Duration,
+    /// Upper bound of the delay between resumes
+    pub max_backoff: Duration,
+    /// Factor the delay grows by after each resume
+    pub multiplier: f64,
+}
+
+impl Default for StreamRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_resumes: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(8),
+            multiplier: 2.0,
+        }
+    }
+}
+
+impl StreamRetryPolicy {
+    /// Policy never resuming streams
+    pub fn none() -> Self {
+        Self {
+            max_resumes: 0,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_max_resumes(mut self, max_resumes: u32) -> Self {
+        self.max_resumes = max_resumes;
+        self
+    }
+
+    pub fn with_initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Delay before the `resume`-th resume, counted from 1
+    pub fn backoff(&self, resume: u32) -> Duration {
+        exponential_backoff(self.initial_backoff, self.multiplier, self.max_backoff, resume)
+    }
+}
+
+/// `initial` grown by `multiplier` for each attempt after the first, up to `max`
+fn exponential_backoff(initial: Duration, multiplier: f64, max: Duration, attempt: u32) -> Duration {
+    let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+    let backoff = initial.as_secs_f64() * multiplier.powi(exponent);
+    if backoff.is_finite() {
+        Duration::from_secs_f64(backoff.max(0.0)).min(max)
+    } else {
+        max
+    }
+}
+
+/// Retries allowed per minute across all the requests of the clients sharing the budget.
+///
+/// The budget starts full and refills continuously. Once it is spent, failed requests fail at once instead of
+/// being retried as their [RetryPolicy] says, with a warning. Clones share their budget.
+#[derive(Debug, Clone)]
+pub struct RetryBudget {
+    bucket: Arc<Mutex<RetryBucket>>,
+}
+
+#[derive(Debug)]
+struct RetryBucket {
+    per_minute: f64,
+    available: f64,
+    refilled: Instant,
+}
+
+impl RetryBudget {
+    /// Allow at most `retries_per_minute` retries, in bursts of up to as many
+    pub fn new(retries_per_minute: u32) -> Self {
+        Self {
+            bucket: Arc::new(Mutex::new(RetryBucket {
+                per_minute: retries_per_minute as f64,
+                available: retries_per_minute as f64,
+                refilled: Instant::now(),
+            })),
+        }
+    }
+
+    /// Retries which can be made right now, e.g. to export as a gauge
+    pub fn available(&self) -> u32 {
+        self.bucket.lock().unwrap().refill() as u32
+    }
+
+    /// Spend one retry, `false` if none is left
+    pub fn try_acquire(&self) -> bool {
+        let mut bucket = self.bucket.lock().unwrap();
+        if bucket.refill() < 1.0 {
+            return false;
+        }
+        bucket.available -= 1.0;
+        true
+    }
+}
+
+impl RetryBucket {
+    /// Retries available once refilled for the time elapsed since the last refill
+    fn refill(&mut self) -> f64 {
+        let elapsed = self.refilled.elapsed().as_secs_f64();
+        self.refilled = Instant::now();
+        self.available = (self.available + elapsed * self.per_minute / 60.0).min(self.per_minute);
+        self.available
+    }
+}
+
+/// Whether the JSON body of `request` asks for the flex service tier
+pub(crate) fn is_flex(request: &reqwest::Request) -> bool {
+    json_body(request).map_or(false, |body| body["service_tier"] == "flex")
+}
+
+/// Ask for the `default` service tier in the JSON body of `request` instead of the flex one
+pub(crate) fn fall_back_to_default_tier(request: &mut reqwest::Request) {
+    let Some(mut body) = json_body(request) else {
+        return;
+    };
+    body["service_tier"] = "default".into();
+    if let Ok(body) = serde_json::to_vec(&body) {
+        *request.body_mut() = Some(body.into());
+    }
+}
+
+fn json_body(request: &reqwest::Request) -> Option<serde_json::Value> {
+    let bytes = request.body()?.as_bytes()?;
+    serde_json::from_slice::<serde_json::Value>(bytes)
+        .ok()
+        .filter(serde_json::Value::is_object)
+}
+
+/// Delay asked by the `retry-after-ms` or `retry-after` header, up to `max`
+fn retry_after(headers: &HeaderMap, max: Duration) -> Option<Duration> {
+    let header = |name: &str| headers.get(name)?.to_str().ok()?.trim().parse::<f64>().ok();
+    header("retry-after-ms")
+        .map(|millis| millis / 1000.0)
+        .or_else(|| header("retry-after"))
+        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+        .map(|secs| Duration::from_secs_f64(secs.min(max.as_secs_f64())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_grows_up_to_max() {
+        let policy = RetryPolicy::default()
+            .with_jitter(0.0)
+            .with_max_backoff(Duration::from_secs(3));
+        assert_eq!(policy.backoff(1), Duration::from_millis(500));
+        assert_eq!(policy.backoff(2), Duration::from_secs(1));
+        assert_eq!(policy.backoff(4), Duration::from_secs(3));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn budgets_are_spent_and_shared() {
+        let budget = RetryBudget::new(2);
+        let clone = budget.clone();
+        assert!(budget.try_acquire());
+        assert!(clone.try_acquire());
+        assert!(!budget.try_acquire());
+        assert_eq!(clone.available(), 0);
+
+        assert!(!RetryBudget::new(0).try_acquire());
+    }
+
+    #[test]
+    fn flex_requests_fall_back_to_the_default_tier() {
+        let mut request = reqwest::Client::new()
+            .post("https://api.openai.com/v1/chat/completions")
+            .json(&serde_json::json!({"model": "o3", "service_tier": "flex"}))
+            .build()
+            .unwrap();
+        assert!(is_flex(&request));
+
+        fall_back_to_default_tier(&mut request);
+        assert!(!is_flex(&request));
+        let body: serde_json::Value =
+            serde_json::from_slice(request.body().unwrap().as_bytes().unwrap()).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({"model": "o3", "service_tier": "default"})
+        );
+    }
+
+    #[test]
+    fn next_delay_honors_limits_and_retry_after() {
+        let policy = RetryPolicy::default()
+            .with_jitter(0.0)
+            .with_max_attempts(3)
+            .with_max_elapsed_time(Duration::from_secs(10));
+
+        assert_eq!(
+            policy.next_delay(1, Duration::ZERO, None),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(policy.next_delay(3, Duration::ZERO, None), None);
+        assert_eq!(policy.next_delay(1, Duration::from_secs(10), None), None);
+
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after", "2".parse().unwrap());
+        assert_eq!(
+            policy.next_delay(1, Duration::ZERO, Some(&headers)),
+            Some(Duration::from_secs(2))
+        );
+        headers.insert("retry-after-ms", "250".parse().unwrap());
+        assert_eq!(
+            policy.next_delay(1, Duration::ZERO, Some(&headers)),
+            Some(Duration::from_millis(250))
+        );
+        assert_eq!(
+            policy.clone().with_honor_retry_after(false).next_delay(
+                1,
+                Duration::ZERO,
+                Some(&headers)
+            ),
+            Some(Duration::from_millis(500))
+        );
+    }
+
+    #[test]
+    fn huge_retry_after_is_capped_at_max_backoff() {
+        let policy = RetryPolicy::default()
+            .with_max_backoff(Duration::from_secs(3))
+            .without_max_elapsed_time();
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after", "99999999999999999999".parse().unwrap());
+        assert_eq!(
+            policy.next_delay(1, Duration::ZERO, Some(&headers)),
+            Some(Duration::from_secs(3))
+        );
+        headers.insert("retry-after-ms", "1e300".parse().unwrap());
+        assert_eq!(
+            policy.next_delay(1, Duration::ZERO, Some(&headers)),
+            Some(Duration::from_secs(3))
+        );
+    }
+}
@@ -1,16 +1,27 @@
+use std::time::Duration;
+
+use futures::TryStreamExt;
 use serde::Serialize;
 
 use crate::{
     Client,
     config::Config,
     error::OpenAIError,
+    pagination::Paginator,
+    session::SessionStore,
     steps::Steps,
+    time::{sleep, Instant},
+    tools::ToolRegistry,
     types::{
         AssistantEventStream, AssistantStreamEvent, CreateRunRequest, ListRunsResponse,
-        ModifyRunRequest, RunObject, SubmitToolOutputsRunRequest,
+        ModifyRunRequest, PendingToolCalls, RunCompletionUsage, RunObject, RunStatus,
+        SubmitToolOutputsRunRequest, ToolsOutputs,
     },
 };
 
+/// Delay between two retrievals of a run waiting for the model, in [Runs::create_with_tools]
+const RUN_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
 /// Represents an execution run on a thread.
 ///
 /// Related guide: [Assistants](https://platform.openai.com/docs/assistants/overview)
@@ -34,12 +45,17 @@ impl<'c, C: Config> Runs<'c, C> {
 
     /// Create a run.
     pub async fn create(&self, request: CreateRunRequest) -> Result<RunObject, OpenAIError> {
+        if request.stream.is_some() && request.stream.unwrap() {
+            return Err(OpenAIError::InvalidArgument(
+                "When stream is true, use Runs::create_stream".into(),
+            ));
+        }
         self.client
             .post(&format!("/threads/{}/runs", self.thread_id), request)
             .await
     }
 
-    /// Create a run.
+    /// Create a run and receive its [AssistantStreamEvent]s as they happen, instead of polling its status.
     pub async fn create_stream(
         &self,
         mut request: CreateRunRequest,
@@ -52,14 +68,14 @@ impl<'c, C: Config> Runs<'c, C> {
 
         request.stream = Some(true);
 
-        Ok(self
+        self
             .client
             .post_stream_mapped_raw_events(
                 &format!("/threads/{}/runs", self.thread_id),
                 request,
                 AssistantStreamEvent::try_from,
             )
-            .await)
+            .await
     }
 
     /// Retrieves a run.
@@ -93,12 +109,43 @@ impl<'c, C: Config> Runs<'c, C> {
             .await
     }
 
+    /// Returns all the runs belonging to the thread, fetching them page by page as the stream is consumed.
+    pub fn list_auto_paginated<Q>(&self, query: &Q) -> Paginator<'c, RunObject>
+    where
+        C: Sync,
+        Q: Serialize + ?Sized,
+    {
+        Paginator::new::<_, ListRunsResponse, _>(self.client, format!("/threads/{}/runs", self.thread_id), query)
+    }
+
+    /// Token usage of all the runs of the thread, for per-conversation accounting.
+    ///
+    /// Runs which are not in a terminal state yet have no usage, so are not accounted for.
+    pub async fn usage(&self) -> Result<RunCompletionUsage, OpenAIError>
+    where
+        C: Sync,
+    {
+        self.list_auto_paginated(&[("limit", "100")])
+            .try_fold(RunCompletionUsage::default(), |mut total, run| async move {
+                if let Some(usage) = &run.usage {
+                    total += usage;
+                }
+                Ok(total)
+            })
+            .await
+    }
+
     /// When a run has the status: "requires_action" and required_action.type is submit_tool_outputs, this endpoint can be used to submit the outputs from the tool calls once they're all completed. All outputs must be submitted in a single request.
     pub async fn submit_tool_outputs(
         &self,
         run_id: &str,
         request: SubmitToolOutputsRunRequest,
     ) -> Result<RunObject, OpenAIError> {
+        if request.stream.is_some() && request.stream.unwrap() {
+            return Err(OpenAIError::InvalidArgument(
+                "When stream is true, use Runs::submit_tool_outputs_stream".into(),
+            ));
+        }
         self.client
             .post(
                 &format!(
@@ -110,6 +157,7 @@ impl<'c, C: Config> Runs<'c, C> {
             .await
     }
 
+    /// Same as [Runs::submit_tool_outputs], but continues the run with a stream of [AssistantStreamEvent]s.
     pub async fn submit_tool_outputs_stream(
         &self,
         run_id: &str,
@@ -123,7 +171,7 @@ impl<'c, C: Config> Runs<'c, C> {
 
         request.stream = Some(true);
 
-        Ok(self
+        self
             .client
             .post_stream_mapped_raw_events(
                 &format!(
@@ -133,7 +181,7 @@ impl<'c, C: Config> Runs<'c, C> {
                 request,
                 AssistantStreamEvent::try_from,
             )
-            .await)
+            .await
     }
 
     /// Cancels a run that is `in_progress`
@@ -145,4 +193,238 @@ impl<'c, C: Config> Runs<'c, C> {
             )
             .await
     }
+
+    /// Create a run and poll it until it ends or requires action, when its tool calls are in `required_action`.
+    ///
+    /// The delay between two polls starts at `poll_interval` and doubles after each poll, up to 8 times `poll_interval`.
+    /// Once `timeout` elapses, if any, the run is returned as last retrieved, still queued or in progress,
+    /// to be cancelled or waited for with [Runs::retrieve].
+    pub async fn create_and_poll(
+        &self,
+        request: CreateRunRequest,
+        poll_interval: Duration,
+        timeout: Option<Duration>,
+    ) -> Result<RunObject, OpenAIError> {
+        let _polling = self.client.active_poller();
+        let started = Instant::now();
+        let mut run = self.create(request).await?;
+        let mut delay = poll_interval;
+        while matches!(
+            run.status,
+            RunStatus::Queued | RunStatus::InProgress | RunStatus::Cancelling
+        ) {
+            let delay_until_timeout = match timeout {
+                Some(timeout) => match timeout.checked_sub(started.elapsed()) {
+                    Some(remaining) if !remaining.is_zero() => delay.min(remaining),
+                    _ => break,
+                },
+                None => delay,
+            };
+            self.client
+                .cancellable(async {
+                    sleep(delay_until_timeout).await;
+                    Ok(())
+                })
+                .await?;
+            run = self.retrieve(&run.id).await?;
+            delay = (delay * 2).min(poll_interval * 8);
+        }
+        Ok(run)
+    }
+
+    /// Create a run and poll it until it ends, calling the functions of `registry` whenever it requires action.
+    ///
+    /// Tool side effects are committed in two phases through `store`: the tool calls are saved before the
+    /// functions are called, and their outputs before they are submitted. A worker crashing along the way
+    /// leaves them in the store, for [Runs::resume_with_tools] to submit instead of orphaning the run.
+    /// Functions whose outputs were not saved yet are called again on resumption, so they should be idempotent.
+    ///
+    /// Fails if the run still requires action after [ToolRegistry::max_rounds] rounds of tool calls.
+    /// The run is cancelled when the [CancellationToken](crate::cancel::CancellationToken) of the client is.
+    pub async fn create_with_tools<S>(
+        &self,
+        request: CreateRunRequest,
+        registry: &ToolRegistry,
+        store: &S,
+    ) -> Result<RunObject, OpenAIError>
+    where
+        S: SessionStore + ?Sized,
+    {
+        let run = self.create(request).await?;
+        self.poll_with_tools(run, registry, store).await
+    }
+
+    /// Resume the run whose tool calls were left pending in `store` by [Runs::create_with_tools],
+    /// submitting their saved outputs, or calling the tools again when they were not saved,
+    /// then polling the run until it ends. `None` if no tool calls of the thread are pending.
+    pub async fn resume_with_tools<S>(
+        &self,
+        registry: &ToolRegistry,
+        store: &S,
+    ) -> Result<Option<RunObject>, OpenAIError>
+    where
+        S: SessionStore + ?Sized,
+    {
+        let key = self.pending_tool_calls_key();
+        let Some(pending) = store.load(&key).await? else {
+            return Ok(None);
+        };
+        let pending: PendingToolCalls = serde_json::from_str(&pending).map_err(|e| {
+            OpenAIError::InvalidArgument(format!("invalid pending tool calls at {key}: {e}"))
+        })?;
+
+        let run = self.retrieve(&pending.run_id).await?;
+        let still_pending = run.status == RunStatus::RequiresAction
+            && run
+                .required_action
+                .as_ref()
+                .map(|action| &action.submit_tool_outputs.tool_calls)
+                == Some(&pending.tool_calls);
+        let run = if still_pending {
+            self.submit_pending_tool_calls(pending, registry, store)
+                .await?
+        } else {
+            // the outputs were submitted before the crash, or the run expired
+            store.remove(&key).await?;
+            run
+        };
+        self.poll_with_tools(run, registry, store).await.map(Some)
+    }
+
+    /// Poll `run` until it ends, making the tool calls it requires,
+    /// or cancel it if the [CancellationToken](crate::cancel::CancellationToken) of the client is cancelled
+    async fn poll_with_tools<S>(
+        &self,
+        run: RunObject,
+        registry: &ToolRegistry,
+        store: &S,
+    ) -> Result<RunObject, OpenAIError>
+    where
+        S: SessionStore + ?Sized,
+    {
+        let run_id = run.id.clone();
+        let result = self.poll_until_done(run, registry, store).await;
+        if let Err(OpenAIError::Cancelled) = result {
+            let client = self.client.uncancellable();
+            let runs = Runs::new(&client, &self.thread_id);
+            if let Err(e) = runs.cancel(&run_id).await {
+                tracing::warn!("failed to cancel run {run_id}: {e}");
+            }
+            // the tool calls of a cancelled run are not to be resumed
+            if let Err(e) = store.remove(&self.pending_tool_calls_key()).await {
+                tracing::warn!("failed to forget the pending tool calls of run {run_id}: {e}");
+            }
+        }
+        result
+    }
+
+    async fn poll_until_done<S>(
+        &self,
+        mut run: RunObject,
+        registry: &ToolRegistry,
+        store: &S,
+    ) -> Result<RunObject, OpenAIError>
+    where
+        S: SessionStore + ?Sized,
+    {
+        let mut rounds = 0;
+        loop {
+            match run.status {
+                RunStatus::Queued | RunStatus::InProgress | RunStatus::Cancelling => {
+                    self.client
+                        .cancellable(async {
+                            sleep(RUN_POLL_INTERVAL).await;
+                            Ok(())
+                        })
+                        .await?;
+                    run = self.retrieve(&run.id).await?;
+                }
+                RunStatus::RequiresAction => {
+                    if rounds == registry.max_rounds() {
+                        return Err(OpenAIError::InvalidArgument(format!(
+                            "run {} kept requiring tool calls after {rounds} rounds; raise ToolRegistry::with_max_rounds or check the tool outputs",
+                            run.id
+                        )));
+                    }
+                    rounds += 1;
+                    let pending = PendingToolCalls {
+                        run_id: run.id.clone(),
+                        tool_calls: run
+                            .required_action
+                            .map(|action| action.submit_tool_outputs.tool_calls)
+                            .unwrap_or_default(),
+                        tool_outputs: None,
+                    };
+                    run = self
+                        .submit_pending_tool_calls(pending, registry, store)
+                        .await?;
+                }
+                _ => return Ok(run),
+            }
+        }
+    }
+
+    /// Save `pending`, make its tool calls unless their outputs were saved, save the outputs,
+    /// submit them and forget them.
+    async fn submit_pending_tool_calls<S>(
+        &self,
+        mut pending: PendingToolCalls,
+        registry: &ToolRegistry,
+        store: &S,
+    ) -> Result<RunObject, OpenAIError>
+    where
+        S: SessionStore + ?Sized,
+    {
+        let key = self.pending_tool_calls_key();
+        let tool_outputs = match pending.tool_outputs.take() {
+            Some(tool_outputs) => tool_outputs,
+            None => {
+                store.save(&key, to_json(&pending)?).await?;
+                let mut tool_outputs = vec![];
+                for tool_call in &pending.tool_calls {
+                    let output = self
+                        .client
+                        .cancellable(async {
+                            Ok(registry
+                                .call_function(
+                                    &tool_call.function.name,
+                                    &tool_call.function.arguments,
+                                )
+                                .await)
+                        })
+                        .await?;
+                    tool_outputs.push(ToolsOutputs {
+                        tool_call_id: Some(tool_call.id.clone()),
+                        output: Some(output),
+                    });
+                }
+                pending.tool_outputs = Some(tool_outputs.clone());
+                store.save(&key, to_json(&pending)?).await?;
+                tool_outputs
+            }
+        };
+
+        let run = self
+            .submit_tool_outputs(
+                &pending.run_id,
+                SubmitToolOutputsRunRequest {
+                    tool_outputs,
+                    stream: None,
+                },
+            )
+            .await?;
+        store.remove(&key).await?;
+        Ok(run)
+    }
+
+    /// Key of the pending tool calls of the thread, which has at most one active run at a time
+    fn pending_tool_calls_key(&self) -> String {
+        format!("pending_tool_calls/{}", self.thread_id)
+    }
+}
+
+fn to_json(pending: &PendingToolCalls) -> Result<String, OpenAIError> {
+    serde_json::to_string(pending).map_err(|e| {
+        OpenAIError::InvalidArgument(format!("cannot serialize pending tool calls: {e}"))
+    })
 }
@@ -0,0 +1,139 @@
+//! Self-consistency sampling: ask for several answers to the same request with
+//! [Chat::sample_n](crate::Chat::sample_n) and keep the one an [Aggregator] picks.
+//!
+//! ```no_run
+//! use async_openai_wasm::{sampling::Aggregator, types::CreateChatCompletionRequest, Client};
+//!
+//! # async fn example(request: CreateChatCompletionRequest) -> Result<(), async_openai_wasm::error::OpenAIError> {
+//! let client = Client::new();
+//! let consensus = client
+//!     .chat()
+//!     .sample_n(request, 5, &Aggregator::MajorityVote)
+//!     .await?;
+//! println!("{} ({} of {} votes)", consensus.answer, consensus.votes, consensus.samples.len());
+//! # Ok(())
+//! # }
+//! ```
+
+/// Maximum `n` of a chat completion request
+pub(crate) const MAX_N: usize = 128;
+
+/// How [Chat::sample_n](crate::Chat::sample_n) picks the winning answer among the samples.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Aggregator {
+    /// The most frequent answer, compared ignoring case and surrounding whitespace.
+    /// Ties go to the answer sampled first.
+    MajorityVote,
+    /// The answer a judge model deems best when shown the conversation and all distinct answers.
+    /// Falls back to [Aggregator::MajorityVote] when the judge does not answer with the number of one.
+    Judge {
+        /// Model of the judge
+        model: String,
+    },
+}
+
+/// Winning answer of [Chat::sample_n](crate::Chat::sample_n), with vote statistics.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Consensus {
+    /// The winning answer, as first sampled
+    pub answer: String,
+    /// Number of samples matching the winning answer
+    pub votes: usize,
+    /// All sampled answers, in order
+    pub samples: Vec<String>,
+    /// Distinct answers, as first sampled, and their number of samples, most frequent first
+    pub tally: Vec<(String, usize)>,
+}
+
+impl Consensus {
+    /// Share of the samples matching the winning answer, between 0 and 1
+    pub fn agreement(&self) -> f64 {
+        if self.samples.is_empty() {
+            0.0
+        } else {
+            self.votes as f64 / self.samples.len() as f64
+        }
+    }
+
+    /// Consensus on the answer at `index` of the tally of `samples`
+    pub(crate) fn new(samples: Vec<String>, tally: Vec<(String, usize)>, index: usize) -> Self {
+        let (answer, votes) = tally[index].clone();
+        Self {
+            answer,
+            votes,
+            samples,
+            tally,
+        }
+    }
+}
+
+/// Distinct answers of `samples` and their counts, most frequent first, then in order of first sample
+pub(crate) fn tally(samples: &[String]) -> Vec<(String, usize)> {
+    let mut tally: Vec<(String, usize)> = vec![];
+    for sample in samples {
+        match tally
+            .iter_mut()
+            .find(|(answer, _)| normalize(answer) == normalize(sample))
+        {
+            Some((_, count)) => *count += 1,
+            None => tally.push((sample.clone(), 1)),
+        }
+    }
+    // stable, so ties keep the order of first samples
+    tally.sort_by(|(_, a), (_, b)| b.cmp(a));
+    tally
+}
+
+fn normalize(answer: &str) -> String {
+    answer.trim().to_lowercase()
+}
+
+/// Prompt asking a judge to pick one of the numbered `answers`
+pub(crate) fn judge_prompt(answers: &[(String, usize)]) -> String {
+    let mut prompt = String::from(
+        "Here are candidate answers to the last message of the conversation above.\n\n",
+    );
+    for (index, (answer, _)) in answers.iter().enumerate() {
+        prompt.push_str(&format!("[{}]\n{}\n\n", index + 1, answer.trim()));
+    }
+    prompt.push_str("Which answer is the best one? Reply with its number only.");
+    prompt
+}
+
+/// Index of the answer picked by the `verdict` of a judge among `count` answers, if it names one
+pub(crate) fn parse_verdict(verdict: &str, count: usize) -> Option<usize> {
+    let digits: String = verdict
+        .chars()
+        .skip_while(|c| !c.is_ascii_digit())
+        .take_while(char::is_ascii_digit)
+        .collect();
+    let number: usize = digits.parse().ok()?;
+    (1..=count).contains(&number).then(|| number - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tally_counts_answers_ignoring_case_and_whitespace() {
+        let samples = ["Paris", "Lyon", " paris\n", "Lyon", "Nice"].map(String::from);
+        assert_eq!(
+            tally(&samples),
+            vec![
+                ("Paris".to_string(), 2),
+                ("Lyon".to_string(), 2),
+                ("Nice".to_string(), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn verdicts_name_an_answer() {
+        assert_eq!(parse_verdict("2", 3), Some(1));
+        assert_eq!(parse_verdict("[3]", 3), Some(2));
+        assert_eq!(parse_verdict("Answer 1 is best.", 3), Some(0));
+        assert_eq!(parse_verdict("4", 3), None);
+        assert_eq!(parse_verdict("none", 3), None);
+    }
+}
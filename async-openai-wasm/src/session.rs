@@ -0,0 +1,262 @@
+//! A chat conversation keeping its own history, for chat UIs and other multi-turn uses.
+//!
+//! ```no_run
+//! use async_openai_wasm::{session::ChatSession, Client};
+//!
+//! # async fn example() -> Result<(), async_openai_wasm::error::OpenAIError> {
+//! let mut session = ChatSession::new(Client::new(), "gpt-4o-mini")
+//!     .with_system_prompt("You are a helpful assistant.")
+//!     .with_max_history_tokens(8_000);
+//!
+//! let answer = session.send("What is the capital of France?").await?;
+//! println!("{}", answer.content.unwrap_or_default());
+//! let answer = session.send("And of Italy?").await?;
+//! # Ok(())
+//! # }
+//! ```
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    types::{
+        ChatCompletionRequestAssistantMessage, ChatCompletionRequestMessage,
+        ChatCompletionRequestSystemMessage, ChatCompletionRequestUserMessage,
+        ChatCompletionRequestUserMessageContent, ChatCompletionResponseMessage,
+        CreateChatCompletionRequest,
+    },
+    Client,
+};
+
+/// The system prompt, history and settings of a conversation with a chat model.
+///
+/// When given a token budget with [ChatSession::with_max_history_tokens], the oldest messages are dropped
+/// until the system prompt and the history fit in it. Tokens are counted with `types::token_count`
+/// with the `tokens` feature, and estimated from the length of the messages otherwise.
+#[derive(Debug, Clone)]
+pub struct ChatSession<C: Config> {
+    client: Client<C>,
+    settings: CreateChatCompletionRequest,
+    system_prompt: Option<String>,
+    history: Vec<ChatCompletionRequestMessage>,
+    max_history_tokens: Option<usize>,
+}
+
+impl<C: Config> ChatSession<C> {
+    pub fn new<S: Into<String>>(client: Client<C>, model: S) -> Self {
+        Self {
+            client,
+            settings: CreateChatCompletionRequest {
+                model: model.into(),
+                ..Default::default()
+            },
+            system_prompt: None,
+            history: vec![],
+            max_history_tokens: None,
+        }
+    }
+
+    pub fn with_system_prompt<S: Into<String>>(mut self, system_prompt: S) -> Self {
+        self.system_prompt = Some(system_prompt.into());
+        self
+    }
+
+    /// Model and parameters of the requests, such as `temperature` or `tools`. Their `messages` are ignored.
+    pub fn with_settings(mut self, settings: CreateChatCompletionRequest) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Budget of prompt tokens, including the system prompt, the history is truncated to
+    pub fn with_max_history_tokens(mut self, max_history_tokens: usize) -> Self {
+        self.max_history_tokens = Some(max_history_tokens);
+        self
+    }
+
+    pub fn system_prompt(&self) -> Option<&str> {
+        self.system_prompt.as_deref()
+    }
+
+    pub fn set_system_prompt<S: Into<String>>(&mut self, system_prompt: Option<S>) {
+        self.system_prompt = system_prompt.map(Into::into);
+    }
+
+    pub fn settings(&self) -> &CreateChatCompletionRequest {
+        &self.settings
+    }
+
+    pub fn settings_mut(&mut self) -> &mut CreateChatCompletionRequest {
+        &mut self.settings
+    }
+
+    /// Messages of the conversation so far, without the system prompt
+    pub fn history(&self) -> &[ChatCompletionRequestMessage] {
+        &self.history
+    }
+
+    /// Append a message to the history, e.g. tool outputs to send with [ChatSession::complete]
+    pub fn push<M: Into<ChatCompletionRequestMessage>>(&mut self, message: M) {
+        self.history.push(message.into());
+    }
+
+    /// Forget the history, keeping the system prompt and settings
+    pub fn clear(&mut self) {
+        self.history.clear();
+    }
+
+    /// The messages the next request would be made of: the system prompt and the history fitting in the budget
+    pub fn messages(&self) -> Vec<ChatCompletionRequestMessage> {
+        self.messages_from(self.window_start())
+    }
+
+    /// Send a user message, appending it and the answer of the assistant to the history.
+    ///
+    /// The history is left untouched when the request fails, so that it can be retried.
+    pub async fn send<S: Into<ChatCompletionRequestUserMessageContent>>(
+        &mut self,
+        content: S,
+    ) -> Result<ChatCompletionResponseMessage, OpenAIError> {
+        self.push(ChatCompletionRequestUserMessage {
+            content: content.into(),
+            ..Default::default()
+        });
+        let result = self.complete().await;
+        if result.is_err() {
+            self.history.pop();
+        }
+        result
+    }
+
+    /// Get the answer of the assistant to the history as is, appending it to the history.
+    ///
+    /// Messages not fitting in the token budget are dropped from the history once the answer is received.
+    pub async fn complete(&mut self) -> Result<ChatCompletionResponseMessage, OpenAIError> {
+        let start = self.window_start();
+        let request = CreateChatCompletionRequest {
+            messages: self.messages_from(start),
+            ..self.settings.clone()
+        };
+        let response = self.client.chat().create(request).await?;
+        let message = response
+            .choices
+            .into_iter()
+            .next()
+            .map(|choice| choice.message)
+            .ok_or_else(|| OpenAIError::InvalidArgument("response has no choices".into()))?;
+
+        self.history.drain(..start);
+        self.history
+            .push(ChatCompletionRequestAssistantMessage::from(message.clone()).into());
+        Ok(message)
+    }
+
+    /// Index of the oldest message of the history fitting in the budget along with the newer ones.
+    ///
+    /// The latest message is always kept, and tool outputs are never kept without the tool calls they answer:
+    /// when the latest messages are tool outputs, the assistant message calling them is kept too, over budget.
+    fn window_start(&self) -> usize {
+        let Some(max_tokens) = self.max_history_tokens else {
+            return 0;
+        };
+        let is_tool = |index: usize| {
+            matches!(
+                self.history.get(index),
+                Some(ChatCompletionRequestMessage::Tool(_))
+            )
+        };
+        let mut messages = self.messages_from(0);
+        let mut start = 0;
+        while start + 1 < self.history.len()
+            && count_tokens(&self.settings.model, &messages) > max_tokens
+        {
+            start += 1;
+            while start + 1 < self.history.len() && is_tool(start) {
+                start += 1;
+            }
+            messages = self.messages_from(start);
+        }
+        while start > 0 && is_tool(start) {
+            start -= 1;
+        }
+        start
+    }
+
+    fn messages_from(&self, start: usize) -> Vec<ChatCompletionRequestMessage> {
+        let system_prompt = self.system_prompt.as_ref().map(|content| {
+            ChatCompletionRequestSystemMessage {
+                content: content.clone(),
+                ..Default::default()
+            }
+            .into()
+        });
+        system_prompt
+            .into_iter()
+            .chain(self.history[start..].iter().cloned())
+            .collect()
+    }
+}
+
+/// Persistent storage of the state of sessions, e.g. a database table or a key-value store,
+/// for work interrupted by a crash to be resumed by another worker.
+///
+/// Used by [Runs::create_with_tools](crate::Runs::create_with_tools) to keep track of pending tool calls.
+/// Values are JSON; keys are namespaced by their users, e.g. `pending_tool_calls/{thread_id}`.
+#[cfg_attr(target_arch = "wasm32", async_convert::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_convert::async_trait)]
+pub trait SessionStore: Send + Sync {
+    /// The value saved at `key`, if any
+    async fn load(&self, key: &str) -> Result<Option<String>, OpenAIError>;
+
+    /// Save `value` at `key`, replacing any previous one. The value must be durable once this returns.
+    async fn save(&self, key: &str, value: String) -> Result<(), OpenAIError>;
+
+    /// Remove the value at `key`, if any
+    async fn remove(&self, key: &str) -> Result<(), OpenAIError>;
+}
+
+/// [SessionStore] in memory, shared by its clones, for tests and single process deployments
+#[derive(Debug, Clone, Default)]
+pub struct MemorySessionStore(Arc<Mutex<HashMap<String, String>>>);
+
+impl MemorySessionStore {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_convert::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_convert::async_trait)]
+impl SessionStore for MemorySessionStore {
+    async fn load(&self, key: &str) -> Result<Option<String>, OpenAIError> {
+        Ok(self.0.lock().unwrap().get(key).cloned())
+    }
+
+    async fn save(&self, key: &str, value: String) -> Result<(), OpenAIError> {
+        self.0.lock().unwrap().insert(key.into(), value);
+        Ok(())
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), OpenAIError> {
+        self.0.lock().unwrap().remove(key);
+        Ok(())
+    }
+}
+
+/// Exact or estimated: trimming to a budget only needs the number of tokens
+#[cfg(feature = "tokens")]
+pub(crate) fn count_tokens(model: &str, messages: &[ChatCompletionRequestMessage]) -> usize {
+    crate::types::token_count(model, messages).get()
+}
+
+/// Roughly a token per 4 bytes of JSON, plus the tokens added by the chat format
+#[cfg(not(feature = "tokens"))]
+pub(crate) fn count_tokens(_model: &str, messages: &[ChatCompletionRequestMessage]) -> usize {
+    3 + messages
+        .iter()
+        .map(|message| {
+            let json = serde_json::to_string(message).unwrap_or_default();
+            3 + (json.len() + 3) / 4
+        })
+        .sum::<usize>()
+}
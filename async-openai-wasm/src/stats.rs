@@ -0,0 +1,106 @@
+//! Counters of what a client and its clones are doing, snapshotted by [Client::stats](crate::Client::stats)
+//! for operators to see, e.g. in a metrics endpoint, how a client behaves under load.
+//!
+//! The client spawns no tasks of its own: requests, streams and pollers run in the tasks of their callers,
+//! which are counted here while they are active.
+//!
+//! ```
+//! use async_openai_wasm::Client;
+//!
+//! let client = Client::new();
+//! let stats = client.stats();
+//! println!(
+//!     "{} requests in flight, {} streams open, {} retries so far",
+//!     stats.in_flight_requests, stats.active_streams, stats.retries
+//! );
+//! ```
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Snapshot of the activity of a client and its clones
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClientStats {
+    /// Calls waiting for their response, retries and backoffs included
+    pub in_flight_requests: usize,
+    /// SSE streams opened and not yet ended or dropped
+    pub active_streams: usize,
+    /// Calls delayed by the [RateLimiter](crate::rate_limit::RateLimiter) of the client
+    pub rate_limited: usize,
+    /// Helpers polling the status of runs or vector stores, e.g. [Runs::create_and_poll](crate::Runs::create_and_poll)
+    pub active_pollers: usize,
+    /// HTTP requests sent since the client was made, retries and streams included
+    pub requests_sent: u64,
+    /// Requests retried since the client was made
+    pub retries: u64,
+}
+
+/// Counters shared by a client and its clones
+#[derive(Debug, Default)]
+pub(crate) struct Counters {
+    in_flight_requests: AtomicUsize,
+    active_streams: AtomicUsize,
+    rate_limited: AtomicUsize,
+    active_pollers: AtomicUsize,
+    requests_sent: AtomicU64,
+    retries: AtomicU64,
+}
+
+impl Counters {
+    pub(crate) fn snapshot(&self) -> ClientStats {
+        ClientStats {
+            in_flight_requests: self.in_flight_requests.load(Ordering::Relaxed),
+            active_streams: self.active_streams.load(Ordering::Relaxed),
+            rate_limited: self.rate_limited.load(Ordering::Relaxed),
+            active_pollers: self.active_pollers.load(Ordering::Relaxed),
+            requests_sent: self.requests_sent.load(Ordering::Relaxed),
+            retries: self.retries.load(Ordering::Relaxed),
+        }
+    }
+
+    pub(crate) fn request_sent(&self) {
+        self.requests_sent.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn retried(&self) {
+        self.retries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn in_flight_request(self: &Arc<Self>) -> Active {
+        Active::new(self, |counters| &counters.in_flight_requests)
+    }
+
+    pub(crate) fn active_stream(self: &Arc<Self>) -> Active {
+        Active::new(self, |counters| &counters.active_streams)
+    }
+
+    pub(crate) fn rate_limited(self: &Arc<Self>) -> Active {
+        Active::new(self, |counters| &counters.rate_limited)
+    }
+
+    pub(crate) fn active_poller(self: &Arc<Self>) -> Active {
+        Active::new(self, |counters| &counters.active_pollers)
+    }
+}
+
+/// Activity counted until dropped
+#[derive(Debug)]
+pub(crate) struct Active {
+    counters: Arc<Counters>,
+    counter: fn(&Counters) -> &AtomicUsize,
+}
+
+impl Active {
+    fn new(counters: &Arc<Counters>, counter: fn(&Counters) -> &AtomicUsize) -> Self {
+        counter(counters).fetch_add(1, Ordering::Relaxed);
+        Self {
+            counters: counters.clone(),
+            counter,
+        }
+    }
+}
+
+impl Drop for Active {
+    fn drop(&mut self) {
+        (self.counter)(&self.counters).fetch_sub(1, Ordering::Relaxed);
+    }
+}
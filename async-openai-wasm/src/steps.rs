@@ -4,6 +4,7 @@ use crate::{
     Client,
     config::Config,
     error::OpenAIError,
+    pagination::Paginator,
     types::{ListRunStepsResponse, RunStepObject},
 };
 
@@ -45,4 +46,13 @@ impl<'c, C: Config> Steps<'c, C> {
             )
             .await
     }
+
+    /// Returns all the run steps belonging to the run, fetching them page by page as the stream is consumed.
+    pub fn list_auto_paginated<Q>(&self, query: &Q) -> Paginator<'c, RunStepObject>
+    where
+        C: Sync,
+        Q: Serialize + ?Sized,
+    {
+        Paginator::new::<_, ListRunStepsResponse, _>(self.client, format!("/threads/{}/runs/{}/steps", self.thread_id, self.run_id), query)
+    }
 }
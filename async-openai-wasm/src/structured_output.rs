@@ -0,0 +1,472 @@
+//! Structured outputs with `strict: true`, where the model follows a JSON schema exactly, but only if the schema sticks to
+//! [the subset of JSON Schema](https://platform.openai.com/docs/guides/structured-outputs#supported-schemas) the API supports:
+//! every property required, no additional properties, and no validation keywords such as `minimum` or `pattern`.
+//!
+//! [strict_schema] brings a schema, written by hand or generated, into that subset or fails with a [FieldError] naming
+//! the JSON pointer of the offending construct. [tagged_union] builds the schema of a Rust enum serialized with
+//! `#[serde(tag = "...")]`, which strict schemas can only nest in an object property.
+//! [validate_strict_schema] lists the issues of a schema without changing it, e.g. to check hand written schemas in tests.
+//!
+//! ```
+//! use async_openai_wasm::{structured_output::{strict_schema, tagged_union}, types::FunctionObject};
+//! use serde::Deserialize;
+//! use serde_json::json;
+//!
+//! #[derive(Deserialize)]
+//! #[serde(tag = "kind", rename_all = "snake_case")]
+//! enum Shape {
+//!     Circle { radius: f64 },
+//!     Rectangle { width: f64, height: f64 },
+//! }
+//!
+//! #[derive(Deserialize)]
+//! struct Drawing {
+//!     shape: Shape,
+//!     label: Option<String>,
+//! }
+//!
+//! # fn main() -> Result<(), async_openai_wasm::error::OpenAIError> {
+//! let number = json!({"type": "number"});
+//! let shape = tagged_union(
+//!     "kind",
+//!     [
+//!         ("circle", json!({"type": "object", "properties": {"radius": number}})),
+//!         (
+//!             "rectangle",
+//!             json!({"type": "object", "properties": {"width": number, "height": number}}),
+//!         ),
+//!     ],
+//! )?;
+//! // `label` is made required and nullable, as `Option` deserializes `null`
+//! let parameters = strict_schema(json!({
+//!     "type": "object",
+//!     "properties": {"shape": shape, "label": {"type": "string"}},
+//!     "required": ["shape"],
+//! }))?;
+//! let function = FunctionObject {
+//!     name: "draw".into(),
+//!     description: None,
+//!     parameters: Some(parameters),
+//!     strict: Some(true),
+//! };
+//! # Ok(())
+//! # }
+//! ```
+use std::fmt::Display;
+
+use serde_json::{json, Map, Value};
+
+use crate::error::{FieldError, OpenAIError};
+
+/// Keywords strict structured outputs reject, with how to do without them
+const UNSUPPORTED_KEYWORDS: &[(&str, &str)] = &[
+    ("minLength", VALIDATE_AFTER_PARSING),
+    ("maxLength", VALIDATE_AFTER_PARSING),
+    ("pattern", VALIDATE_AFTER_PARSING),
+    ("minimum", VALIDATE_AFTER_PARSING),
+    ("maximum", VALIDATE_AFTER_PARSING),
+    ("exclusiveMinimum", VALIDATE_AFTER_PARSING),
+    ("exclusiveMaximum", VALIDATE_AFTER_PARSING),
+    ("multipleOf", VALIDATE_AFTER_PARSING),
+    ("minItems", VALIDATE_AFTER_PARSING),
+    ("maxItems", VALIDATE_AFTER_PARSING),
+    ("uniqueItems", VALIDATE_AFTER_PARSING),
+    ("contains", VALIDATE_AFTER_PARSING),
+    ("minContains", VALIDATE_AFTER_PARSING),
+    ("maxContains", VALIDATE_AFTER_PARSING),
+    ("unevaluatedItems", VALIDATE_AFTER_PARSING),
+    ("minProperties", VALIDATE_AFTER_PARSING),
+    ("maxProperties", VALIDATE_AFTER_PARSING),
+    ("propertyNames", VALIDATE_AFTER_PARSING),
+    ("unevaluatedProperties", VALIDATE_AFTER_PARSING),
+    (
+        "patternProperties",
+        "use an array of objects with a key property instead of a map",
+    ),
+    ("allOf", "merge the subschemas into a single object schema"),
+    ("not", VALIDATE_AFTER_PARSING),
+    ("if", VALIDATE_AFTER_PARSING),
+    ("then", VALIDATE_AFTER_PARSING),
+    ("else", VALIDATE_AFTER_PARSING),
+    ("dependentRequired", VALIDATE_AFTER_PARSING),
+    ("dependentSchemas", VALIDATE_AFTER_PARSING),
+];
+
+const VALIDATE_AFTER_PARSING: &str =
+    "state the constraint in `description` and check it after parsing the output";
+
+/// Values of `format` strict structured outputs support
+const SUPPORTED_FORMATS: &[&str] = &[
+    "date-time",
+    "time",
+    "date",
+    "duration",
+    "email",
+    "hostname",
+    "ipv4",
+    "ipv6",
+    "uuid",
+];
+
+/// A construct of a schema which strict structured outputs reject, found by [validate_strict_schema]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaIssue {
+    /// JSON pointer of the construct in the schema, e.g. `#/properties/age/minimum`
+    pub pointer: String,
+    /// What is wrong with it, e.g. `is not supported by strict structured outputs`
+    pub message: String,
+    /// How to fix it, if there is an obvious fix
+    pub suggestion: Option<String>,
+}
+
+impl SchemaIssue {
+    fn new<P: Into<String>, M: Into<String>>(pointer: P, message: M) -> Self {
+        Self {
+            pointer: pointer.into(),
+            message: message.into(),
+            suggestion: None,
+        }
+    }
+
+    fn with_suggestion<S: Into<String>>(mut self, suggestion: S) -> Self {
+        self.suggestion = Some(suggestion.into());
+        self
+    }
+}
+
+impl Display for SchemaIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}` {}", self.pointer, self.message)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, "; {suggestion}")?;
+        }
+        Ok(())
+    }
+}
+
+impl From<SchemaIssue> for FieldError {
+    fn from(value: SchemaIssue) -> Self {
+        FieldError {
+            field: value.pointer,
+            message: value.message,
+            suggestion: value.suggestion,
+        }
+    }
+}
+
+impl From<SchemaIssue> for OpenAIError {
+    fn from(value: SchemaIssue) -> Self {
+        FieldError::from(value).into()
+    }
+}
+
+/// The constructs of `schema` which strict structured outputs reject, all of them, without changing the schema.
+///
+/// The API rejects such schemas with errors which seldom say where the construct is, while issues point at it.
+/// Unlike [strict_schema], optional properties and objects allowing additional properties are reported rather than fixed.
+pub fn validate_strict_schema(schema: &Value) -> Vec<SchemaIssue> {
+    let mut issues = vec![];
+    if let Some(issue) = root_issue(schema) {
+        issues.push(issue);
+    }
+    validate(schema, "#", &mut issues);
+    issues
+}
+
+/// `schema` made compatible with `strict: true`, failing on constructs strict schemas cannot express.
+///
+/// - objects get `additionalProperties: false`, and properties missing from `required` are added to it
+///   and made nullable, so that they deserialize into `Option` fields
+/// - `oneOf` becomes `anyOf`, equivalent for the disjoint variants of Rust enums
+///
+/// `format` is only allowed with the values the API supports, such as `date-time` or `uuid`.
+/// The root, which must be an object, and the subschemas in `properties`, `items`, `anyOf`, `$defs`
+/// and `definitions` are checked.
+pub fn strict_schema(mut schema: Value) -> Result<Value, OpenAIError> {
+    if let Some(issue) = root_issue(&schema) {
+        return Err(issue.into());
+    }
+    make_strict(&mut schema, "#")?;
+    Ok(schema)
+}
+
+/// Schema of an enum internally tagged with `#[serde(tag = "<tag>")]`, from the name and the object schema of each variant.
+///
+/// The tag property is added to the variants and required, with the name of the variant as only value.
+/// Unit variants are objects without properties, e.g. `{"type": "object", "properties": {}}`.
+pub fn tagged_union<N, I>(tag: &str, variants: I) -> Result<Value, OpenAIError>
+where
+    N: Into<String>,
+    I: IntoIterator<Item = (N, Value)>,
+{
+    let mut any_of = vec![];
+    for (index, (name, mut schema)) in variants.into_iter().enumerate() {
+        let path = format!("#/anyOf/{index}");
+        let Some(object) = schema
+            .as_object_mut()
+            .filter(|object| object.get("type") == Some(&json!("object")))
+        else {
+            return Err(FieldError::new(path, "must be an object schema")
+                .with_suggestion(
+                    "internally tagged variants must be structs or units, as serde requires",
+                )
+                .into());
+        };
+        let properties = object
+            .entry("properties")
+            .or_insert_with(|| json!({}))
+            .as_object_mut()
+            .ok_or_else(|| FieldError::new(format!("{path}/properties"), "must be an object"))?;
+        if properties.contains_key(tag) {
+            return Err(FieldError::new(
+                format!("{path}/properties/{}", escape(tag)),
+                "conflicts with the tag of the union",
+            )
+            .with_suggestion("rename the field or the tag")
+            .into());
+        }
+        let mut tagged = Map::new();
+        tagged.insert(tag.into(), json!({"type": "string", "enum": [name.into()]}));
+        tagged.append(properties);
+        *properties = tagged;
+
+        let required = object.entry("required").or_insert_with(|| json!([]));
+        match required.as_array_mut() {
+            Some(required) => required.insert(0, tag.into()),
+            None => {
+                return Err(FieldError::new(format!("{path}/required"), "must be an array").into())
+            }
+        }
+        any_of.push(schema);
+    }
+    Ok(json!({ "anyOf": any_of }))
+}
+
+fn make_strict(schema: &mut Value, path: &str) -> Result<(), OpenAIError> {
+    let Some(object) = schema.as_object_mut() else {
+        // `true` and `false` schemas
+        return Err(FieldError::new(path, "must be a schema object").into());
+    };
+
+    if let Some(issue) = keyword_issues(object, path).into_iter().next() {
+        return Err(issue.into());
+    }
+
+    if let Some(one_of) = object.remove("oneOf") {
+        if object.contains_key("anyOf") {
+            return Err(FieldError::new(
+                format!("{path}/oneOf"),
+                "cannot be turned into `anyOf`, which the schema already has",
+            )
+            .into());
+        }
+        object.insert("anyOf".into(), one_of);
+    }
+
+    if object.get("type") == Some(&json!("object")) || object.contains_key("properties") {
+        match object.get("additionalProperties") {
+            None | Some(Value::Bool(false)) => {}
+            Some(_) => return Err(additional_properties_issue(path).into()),
+        }
+        object.insert("additionalProperties".into(), false.into());
+
+        let required: Vec<String> = match object.get("required") {
+            None => vec![],
+            Some(Value::Array(required)) => required
+                .iter()
+                .filter_map(Value::as_str)
+                .map(String::from)
+                .collect(),
+            Some(_) => {
+                return Err(FieldError::new(format!("{path}/required"), "must be an array").into())
+            }
+        };
+        let properties = object
+            .entry("properties")
+            .or_insert_with(|| json!({}))
+            .as_object_mut()
+            .ok_or_else(|| FieldError::new(format!("{path}/properties"), "must be an object"))?;
+        for (name, property) in properties.iter_mut() {
+            let property_path = format!("{path}/properties/{}", escape(name));
+            make_strict(property, &property_path)?;
+            if !required.contains(name) {
+                make_nullable(property);
+            }
+        }
+        let names: Vec<Value> = properties.keys().cloned().map(Value::from).collect();
+        object.insert("required".into(), names.into());
+    }
+
+    if let Some(items) = object.get_mut("items") {
+        make_strict(items, &format!("{path}/items"))?;
+    }
+    if let Some(any_of) = object.get_mut("anyOf") {
+        let Some(any_of) = any_of.as_array_mut() else {
+            return Err(FieldError::new(format!("{path}/anyOf"), "must be an array").into());
+        };
+        for (index, variant) in any_of.iter_mut().enumerate() {
+            make_strict(variant, &format!("{path}/anyOf/{index}"))?;
+        }
+    }
+    for keyword in ["$defs", "definitions"] {
+        if let Some(definitions) = object.get_mut(keyword).and_then(Value::as_object_mut) {
+            for (name, definition) in definitions.iter_mut() {
+                make_strict(definition, &format!("{path}/{keyword}/{}", escape(name)))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn validate(schema: &Value, path: &str, issues: &mut Vec<SchemaIssue>) {
+    let Some(object) = schema.as_object() else {
+        issues.push(SchemaIssue::new(path, "must be a schema object"));
+        return;
+    };
+    issues.extend(keyword_issues(object, path));
+    if object.contains_key("oneOf") {
+        issues.push(
+            SchemaIssue::new(
+                format!("{path}/oneOf"),
+                "is not supported by strict structured outputs",
+            )
+            .with_suggestion("use `anyOf`"),
+        );
+    }
+
+    if object.get("type") == Some(&json!("object")) || object.contains_key("properties") {
+        match object.get("additionalProperties") {
+            Some(Value::Bool(false)) => {}
+            None => issues.push(
+                SchemaIssue::new(path, "allows additional properties")
+                    .with_suggestion("set `additionalProperties` to false"),
+            ),
+            Some(_) => issues.push(additional_properties_issue(path)),
+        }
+        let required = object.get("required").and_then(Value::as_array);
+        let is_required =
+            |name: &str| required.map_or(false, |required| required.contains(&json!(name)));
+        if let Some(properties) = object.get("properties").and_then(Value::as_object) {
+            for (name, property) in properties {
+                let property_path = format!("{path}/properties/{}", escape(name));
+                if !is_required(name) {
+                    issues.push(
+                        SchemaIssue::new(
+                            &property_path,
+                            "is optional, while strict structured outputs require every property",
+                        )
+                        .with_suggestion(
+                            "add it to `required` and let it be null, e.g. with `\"type\": [\"string\", \"null\"]`",
+                        ),
+                    );
+                }
+                validate(property, &property_path, issues);
+            }
+        }
+    }
+
+    if let Some(items) = object.get("items") {
+        validate(items, &format!("{path}/items"), issues);
+    }
+    for keyword in ["anyOf", "oneOf"] {
+        if let Some(variants) = object.get(keyword).and_then(Value::as_array) {
+            for (index, variant) in variants.iter().enumerate() {
+                validate(variant, &format!("{path}/{keyword}/{index}"), issues);
+            }
+        }
+    }
+    for keyword in ["$defs", "definitions"] {
+        if let Some(definitions) = object.get(keyword).and_then(Value::as_object) {
+            for (name, definition) in definitions {
+                validate(
+                    definition,
+                    &format!("{path}/{keyword}/{}", escape(name)),
+                    issues,
+                );
+            }
+        }
+    }
+}
+
+fn root_issue(schema: &Value) -> Option<SchemaIssue> {
+    (schema.get("type") != Some(&json!("object"))).then(|| {
+        SchemaIssue::new("#", "must be an object schema")
+            .with_suggestion("wrap the schema in a property of an object schema")
+    })
+}
+
+/// Issues of the keywords of `object` strict structured outputs reject
+fn keyword_issues(object: &Map<String, Value>, path: &str) -> Vec<SchemaIssue> {
+    let mut issues = vec![];
+    if let Some(format) = object.get("format") {
+        if !matches!(format.as_str(), Some(format) if SUPPORTED_FORMATS.contains(&format)) {
+            issues.push(
+                SchemaIssue::new(
+                    format!("{path}/format"),
+                    "is not one of the formats strict structured outputs support",
+                )
+                .with_suggestion(format!(
+                    "use one of {}, or {VALIDATE_AFTER_PARSING}",
+                    SUPPORTED_FORMATS.join(", ")
+                )),
+            );
+        }
+    }
+    for (keyword, suggestion) in UNSUPPORTED_KEYWORDS {
+        if object.contains_key(*keyword) {
+            issues.push(
+                SchemaIssue::new(
+                    format!("{path}/{keyword}"),
+                    "is not supported by strict structured outputs",
+                )
+                .with_suggestion(*suggestion),
+            );
+        }
+    }
+    issues
+}
+
+fn additional_properties_issue(path: &str) -> SchemaIssue {
+    SchemaIssue::new(
+        format!("{path}/additionalProperties"),
+        "must be false in strict structured outputs",
+    )
+    .with_suggestion("use an array of objects with a key property instead of a map")
+}
+
+/// Let `schema` match `null` too
+fn make_nullable(schema: &mut Value) {
+    let null_schema = json!({"type": "null"});
+    let Some(object) = schema.as_object_mut() else {
+        return;
+    };
+    if object.contains_key("const") || object.contains_key("$ref") {
+        *schema = json!({"anyOf": [schema.take(), null_schema]});
+        return;
+    }
+    if let Some(Value::Array(values)) = object.get_mut("enum") {
+        if !values.contains(&Value::Null) {
+            values.push(Value::Null);
+        }
+    }
+    match object.get_mut("type") {
+        Some(Value::String(r#type)) if r#type != "null" => {
+            let r#type = json!([r#type.as_str(), "null"]);
+            object.insert("type".into(), r#type);
+        }
+        Some(Value::Array(types)) if !types.contains(&json!("null")) => types.push("null".into()),
+        Some(_) => {}
+        None => match object.get_mut("anyOf") {
+            Some(Value::Array(any_of)) if !any_of.contains(&null_schema) => {
+                any_of.push(null_schema)
+            }
+            Some(_) => {}
+            None => *schema = json!({"anyOf": [schema.take(), null_schema]}),
+        },
+    }
+}
+
+/// `name` escaped as a JSON pointer segment
+fn escape(name: &str) -> String {
+    name.replace('~', "~0").replace('/', "~1")
+}
@@ -0,0 +1,317 @@
+//! Spans following the [OpenTelemetry GenAI semantic conventions](https://opentelemetry.io/docs/specs/semconv/gen-ai/gen-ai-spans/),
+//! so that traces exported with e.g. `tracing-opentelemetry` are understood by LLM observability backends.
+//!
+//! With the `tracing` feature, the `openai_request` span of every HTTP call, streams and uploads included,
+//! also records its endpoint, model, status, latency, token usage and `x-request-id`, the processing time of the API
+//! (see [timing](crate::timing)), and the retries left in the [RetryBudget](crate::retry::RetryBudget) of the client once it retried.
+use std::future::Future;
+
+use tracing::{field::Empty, Instrument, Span};
+
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    time::Instant,
+    timing::ResponseTiming,
+    types::{
+        CreateBase64EmbeddingResponse, CreateChatCompletionRequest, CreateChatCompletionResponse,
+        CreateCompletionRequest, CreateCompletionResponse, CreateEmbeddingRequest,
+        CreateEmbeddingResponse, CreateResponseRequest, Response,
+    },
+};
+
+/// Record the method, endpoint and model of `request` on a [request_span](crate::context::request_span),
+/// with the `tracing` feature.
+pub(crate) fn record_request(span: &Span, request: &reqwest::Request, stream: bool) {
+    #[cfg(feature = "tracing")]
+    {
+        span.record("http.request.method", request.method().as_str());
+        span.record("url.path", request.url().path());
+        span.record("openai.stream", stream);
+        let model = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .and_then(|body| serde_json::from_slice::<serde_json::Value>(body).ok())
+            .and_then(|body| Some(body.get("model")?.as_str()?.to_string()));
+        if let Some(model) = model {
+            span.record("gen_ai.request.model", model.as_str());
+        }
+    }
+    #[cfg(not(feature = "tracing"))]
+    let _ = (span, request, stream);
+}
+
+/// Record the status, request id and latency of `response`, received after `attempts` attempts, with the `tracing` feature
+pub(crate) fn record_response(
+    span: &Span,
+    response: &reqwest::Response,
+    attempts: u32,
+    started: Instant,
+) {
+    #[cfg(feature = "tracing")]
+    {
+        span.record("http.response.status_code", response.status().as_u16());
+        span.record("openai.attempts", attempts);
+        if let Some(request_id) = response
+            .headers()
+            .get("x-request-id")
+            .and_then(|id| id.to_str().ok())
+        {
+            span.record("openai.request_id", request_id);
+        }
+        record_duration(span, started);
+    }
+    #[cfg(not(feature = "tracing"))]
+    let _ = (span, response, attempts, started);
+}
+
+/// Record the processing time of a response and the overhead on top of it, see [ResponseTiming], with the `tracing` feature
+pub(crate) fn record_timing(span: &Span, timing: &ResponseTiming) {
+    #[cfg(feature = "tracing")]
+    {
+        if let Some(processing) = timing.processing {
+            span.record("openai.processing_ms", processing.as_millis() as u64);
+        }
+        if let Some(overhead) = timing.overhead() {
+            span.record("openai.overhead_ms", overhead.as_millis() as u64);
+        }
+    }
+    #[cfg(not(feature = "tracing"))]
+    let _ = (span, timing);
+}
+
+/// Record the retries left in the [RetryBudget](crate::retry::RetryBudget) of the client, with the `tracing` feature
+pub(crate) fn record_retry_budget(span: &Span, available: u32) {
+    #[cfg(feature = "tracing")]
+    span.record("openai.retry_budget", available);
+    #[cfg(not(feature = "tracing"))]
+    let _ = (span, available);
+}
+
+/// Record the time elapsed since `started` in milliseconds, with the `tracing` feature
+pub(crate) fn record_duration(span: &Span, started: Instant) {
+    #[cfg(feature = "tracing")]
+    span.record("duration_ms", started.elapsed().as_millis() as u64);
+    #[cfg(not(feature = "tracing"))]
+    let _ = (span, started);
+}
+
+/// Record the token usage of a JSON response body or stream chunk, if it has any, with the `tracing` feature.
+///
+/// Chat and legacy completions count `prompt_tokens` and `completion_tokens`, responses `input_tokens` and `output_tokens`.
+pub(crate) fn record_usage(span: &Span, body: &[u8]) {
+    #[cfg(feature = "tracing")]
+    {
+        // skip parsing bodies which cannot have any usage, e.g. most stream chunks
+        if !body.windows(7).any(|window| window == b"\"usage\"") {
+            return;
+        }
+        let Ok(body) = serde_json::from_slice::<serde_json::Value>(body) else {
+            return;
+        };
+        let Some(usage) = body.get("usage").filter(|usage| usage.is_object()) else {
+            return;
+        };
+        let tokens = |names: [&str; 2]| names.iter().find_map(|name| usage.get(name)?.as_u64());
+        if let Some(input_tokens) = tokens(["prompt_tokens", "input_tokens"]) {
+            span.record("gen_ai.usage.input_tokens", input_tokens);
+        }
+        if let Some(output_tokens) = tokens(["completion_tokens", "output_tokens"]) {
+            span.record("gen_ai.usage.output_tokens", output_tokens);
+        }
+    }
+    #[cfg(not(feature = "tracing"))]
+    let _ = (span, body);
+}
+
+/// Request attributes of a GenAI span
+pub(crate) trait GenAiRequest {
+    fn model(&self) -> &str;
+
+    fn record(&self, _span: &Span) {}
+}
+
+/// Response attributes of a GenAI span
+pub(crate) trait GenAiResponse {
+    fn record(&self, span: &Span);
+}
+
+/// Span of an `operation` (e.g. `chat`) on the model of `request`, named `{operation} {model}`
+pub(crate) fn span<C: Config, R: GenAiRequest>(
+    config: &C,
+    operation: &'static str,
+    request: &R,
+) -> Span {
+    let model = request.model();
+    let span = tracing::info_span!(
+        "gen_ai",
+        otel.name = %format!("{operation} {model}"),
+        otel.kind = "client",
+        gen_ai.system = config.gen_ai_system(),
+        gen_ai.operation.name = operation,
+        gen_ai.request.model = model,
+        gen_ai.request.max_tokens = Empty,
+        gen_ai.request.temperature = Empty,
+        gen_ai.request.top_p = Empty,
+        gen_ai.response.id = Empty,
+        gen_ai.response.model = Empty,
+        gen_ai.response.finish_reasons = Empty,
+        gen_ai.usage.input_tokens = Empty,
+        gen_ai.usage.output_tokens = Empty,
+        error.type = Empty,
+    );
+    request.record(&span);
+    span
+}
+
+/// Run `future` in `span`, recording the attributes of its response or the type of its error.
+pub(crate) async fn instrument<O, F>(span: Span, future: F) -> Result<O, OpenAIError>
+where
+    O: GenAiResponse,
+    F: Future<Output = Result<O, OpenAIError>>,
+{
+    let result = future.instrument(span.clone()).await;
+    match &result {
+        Ok(response) => response.record(&span),
+        Err(e) => {
+            span.record("error.type", error_type(e));
+        }
+    }
+    result
+}
+
+fn error_type(error: &OpenAIError) -> &str {
+    match error {
+        OpenAIError::ApiError(error) => error
+            .r#type
+            .as_deref()
+            .or(error.code.as_deref())
+            .unwrap_or("api_error"),
+        OpenAIError::Reqwest(_) => "http_error",
+        OpenAIError::JSONDeserialize(_) | OpenAIError::InvalidJsonResponse(_) => {
+            "deserialization_error"
+        }
+        OpenAIError::InvalidArgument(_) | OpenAIError::InvalidField(_) => "invalid_argument",
+        OpenAIError::Cancelled => "cancelled",
+        OpenAIError::Timeout(_) => "timeout",
+        OpenAIError::StreamStalled(_) => "stream_stalled",
+        _ => "_OTHER",
+    }
+}
+
+/// Finish reasons as a JSON array, since span fields cannot be arrays
+fn finish_reasons<T: serde::Serialize>(reasons: impl Iterator<Item = T>) -> String {
+    serde_json::to_string(&reasons.collect::<Vec<_>>()).unwrap_or_default()
+}
+
+impl GenAiRequest for CreateChatCompletionRequest {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn record(&self, span: &Span) {
+        span.record(
+            "gen_ai.request.max_tokens",
+            self.max_tokens
+                .map(u32::from)
+                .or(self.max_completion_tokens),
+        );
+        span.record("gen_ai.request.temperature", self.temperature);
+        span.record("gen_ai.request.top_p", self.top_p);
+    }
+}
+
+impl GenAiResponse for CreateChatCompletionResponse {
+    fn record(&self, span: &Span) {
+        span.record("gen_ai.response.id", self.id.as_str());
+        span.record("gen_ai.response.model", self.model.as_str());
+        span.record(
+            "gen_ai.response.finish_reasons",
+            finish_reasons(
+                self.choices
+                    .iter()
+                    .filter_map(|choice| choice.finish_reason),
+            )
+            .as_str(),
+        );
+        if let Some(usage) = &self.usage {
+            span.record("gen_ai.usage.input_tokens", usage.prompt_tokens);
+            span.record("gen_ai.usage.output_tokens", usage.completion_tokens);
+        }
+    }
+}
+
+impl GenAiRequest for CreateCompletionRequest {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn record(&self, span: &Span) {
+        span.record("gen_ai.request.max_tokens", self.max_tokens);
+        span.record("gen_ai.request.temperature", self.temperature);
+        span.record("gen_ai.request.top_p", self.top_p);
+    }
+}
+
+impl GenAiResponse for CreateCompletionResponse {
+    fn record(&self, span: &Span) {
+        span.record("gen_ai.response.id", self.id.as_str());
+        span.record("gen_ai.response.model", self.model.as_str());
+        span.record(
+            "gen_ai.response.finish_reasons",
+            finish_reasons(
+                self.choices
+                    .iter()
+                    .filter_map(|choice| choice.finish_reason),
+            )
+            .as_str(),
+        );
+        if let Some(usage) = &self.usage {
+            span.record("gen_ai.usage.input_tokens", usage.prompt_tokens);
+            span.record("gen_ai.usage.output_tokens", usage.completion_tokens);
+        }
+    }
+}
+
+impl GenAiRequest for CreateEmbeddingRequest {
+    fn model(&self) -> &str {
+        &self.model
+    }
+}
+
+impl GenAiResponse for CreateEmbeddingResponse {
+    fn record(&self, span: &Span) {
+        span.record("gen_ai.response.model", self.model.as_str());
+        span.record("gen_ai.usage.input_tokens", self.usage.prompt_tokens);
+    }
+}
+
+impl GenAiResponse for CreateBase64EmbeddingResponse {
+    fn record(&self, span: &Span) {
+        span.record("gen_ai.response.model", self.model.as_str());
+        span.record("gen_ai.usage.input_tokens", self.usage.prompt_tokens);
+    }
+}
+
+impl GenAiRequest for CreateResponseRequest {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn record(&self, span: &Span) {
+        span.record("gen_ai.request.max_tokens", self.max_output_tokens);
+        span.record("gen_ai.request.temperature", self.temperature);
+        span.record("gen_ai.request.top_p", self.top_p);
+    }
+}
+
+impl GenAiResponse for Response {
+    fn record(&self, span: &Span) {
+        span.record("gen_ai.response.id", self.id.as_str());
+        span.record("gen_ai.response.model", self.model.as_str());
+        if let Some(usage) = &self.usage {
+            span.record("gen_ai.usage.input_tokens", usage.input_tokens);
+            span.record("gen_ai.usage.output_tokens", usage.output_tokens);
+        }
+    }
+}
@@ -0,0 +1,440 @@
+//! Mock of the API for unit tests of code using [Client], enabled with the `testing` feature on native targets.
+//!
+//! [MockServer] answers requests on a local port with responses programmed per endpoint,
+//! JSON or SSE streams, and captures them to assert on. Clients reach it as any other API base,
+//! so retries, streaming and uploads work as they do against the real API.
+//!
+//! ```
+//! # tokio_test::block_on(async {
+//! use async_openai_wasm::testing::{MockResponse, MockServer};
+//! use serde_json::json;
+//!
+//! let server = MockServer::start();
+//! server.mock("GET", "/models", MockResponse::json(json!({"object": "list", "data": []})));
+//!
+//! let models = server.client().models().list().await.unwrap();
+//! assert!(models.data.is_empty());
+//! assert_eq!(server.requests()[0].path, "/models");
+//! # });
+//! ```
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{config::OpenAIConfig, recorder::RecordedEvent, Client};
+
+/// Prefix of the paths the server answers, like the `/v1` of [OPENAI_API_BASE](crate::config::OPENAI_API_BASE)
+const API_PREFIX: &str = "/v1";
+
+/// Response programmed with [MockServer::mock]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    /// Time the server waits before answering, e.g. to test timeouts
+    pub delay: Duration,
+    /// Time the server waits before sending the last event of an SSE body, e.g. to test stalled streams
+    pub stall: Duration,
+    /// Bytes of the body sent before the connection is closed, its whole length being announced,
+    /// e.g. to test interrupted downloads
+    pub truncate: Option<usize>,
+}
+
+impl MockResponse {
+    /// `200 OK` with `body` as JSON
+    pub fn json<T: Serialize>(body: T) -> Self {
+        Self {
+            status: 200,
+            headers: vec![("content-type".into(), "application/json".into())],
+            body: serde_json::to_vec(&body).expect("response body must serialize to JSON"),
+            delay: Duration::ZERO,
+            stall: Duration::ZERO,
+            truncate: None,
+        }
+    }
+
+    /// `200 OK` with an SSE stream of `events` as JSON, ended by `data: [DONE]` like the API does
+    pub fn sse<T, I>(events: I) -> Self
+    where
+        T: Serialize,
+        I: IntoIterator<Item = T>,
+    {
+        let mut body = String::new();
+        for event in events {
+            let data = serde_json::to_string(&event).expect("event must serialize to JSON");
+            body.push_str(&format!("data: {data}\n\n"));
+        }
+        body.push_str("data: [DONE]\n\n");
+        Self::raw_sse(body)
+    }
+
+    /// `200 OK` with an SSE stream of `body`, as is, e.g. to send named events
+    pub fn raw_sse<S: Into<String>>(body: S) -> Self {
+        Self {
+            status: 200,
+            headers: vec![("content-type".into(), "text/event-stream".into())],
+            body: body.into().into_bytes(),
+            delay: Duration::ZERO,
+            stall: Duration::ZERO,
+            truncate: None,
+        }
+    }
+
+    /// `200 OK` with an SSE stream of the `events` of a [StreamRecorder](crate::recorder::StreamRecorder),
+    /// e.g. [stream_events](crate::recorder::StreamRecorder::stream_events) loaded from a log of production
+    pub fn replay<'a, I>(events: I) -> Self
+    where
+        I: IntoIterator<Item = &'a RecordedEvent>,
+    {
+        let mut body = String::new();
+        for event in events {
+            if event.event != "message" {
+                body.push_str(&format!("event: {}\n", event.event));
+            }
+            for line in event.data.split('\n') {
+                body.push_str(&format!("data: {line}\n"));
+            }
+            body.push('\n');
+        }
+        Self::raw_sse(body)
+    }
+
+    /// `200 OK` with `body` as is, e.g. the audio of a speech
+    pub fn bytes<B: Into<Vec<u8>>>(body: B) -> Self {
+        Self {
+            status: 200,
+            headers: vec![("content-type".into(), "application/octet-stream".into())],
+            body: body.into(),
+            delay: Duration::ZERO,
+            stall: Duration::ZERO,
+            truncate: None,
+        }
+    }
+
+    /// An error of the API with `status`, e.g. `429` and `rate_limit_exceeded`
+    pub fn error(status: u16, r#type: &str, message: &str) -> Self {
+        Self {
+            status,
+            ..Self::json(serde_json::json!({
+                "error": {"message": message, "type": r#type, "param": null, "code": null}
+            }))
+        }
+    }
+
+    pub fn with_status(mut self, status: u16) -> Self {
+        self.status = status;
+        self
+    }
+
+    pub fn with_header<K: Into<String>, V: Into<String>>(mut self, name: K, value: V) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    pub fn with_stall(mut self, stall: Duration) -> Self {
+        self.stall = stall;
+        self
+    }
+
+    /// Close the connection after the first `length` bytes of the body, see [MockResponse::truncate]
+    pub fn truncated(mut self, length: usize) -> Self {
+        self.truncate = Some(length);
+        self
+    }
+}
+
+/// Request received by a [MockServer]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapturedRequest {
+    pub method: String,
+    /// Path of the endpoint, without the `/v1` of the API base, e.g. `/chat/completions`
+    pub path: String,
+    /// Query string, without `?`, empty if there is none
+    pub query: String,
+    /// Headers, with lowercase names
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl CapturedRequest {
+    /// Value of the header `name`, if sent
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The body as JSON, `Value::Null` if it is not
+    pub fn json(&self) -> Value {
+        serde_json::from_slice(&self.body).unwrap_or_default()
+    }
+}
+
+/// Endpoint and its programmed responses
+#[derive(Debug)]
+struct Mock {
+    method: String,
+    path: String,
+    responses: Vec<MockResponse>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    mocks: Vec<Mock>,
+    requests: Vec<CapturedRequest>,
+}
+
+impl State {
+    /// Next programmed response of the endpoint, the last one being repeated
+    fn respond(&mut self, method: &str, path: &str) -> MockResponse {
+        let mock = self
+            .mocks
+            .iter_mut()
+            .find(|mock| mock.method.eq_ignore_ascii_case(method) && mock.path == path);
+        match mock {
+            Some(mock) if mock.responses.len() > 1 => mock.responses.remove(0),
+            Some(mock) => mock.responses[0].clone(),
+            None => MockResponse::error(
+                404,
+                "invalid_request_error",
+                &format!("no mock for {method} {path}"),
+            ),
+        }
+    }
+}
+
+/// API answering requests with the responses programmed per endpoint, on a local port, until dropped.
+///
+/// Requests to endpoints without responses get a `404` error of the API.
+#[derive(Debug)]
+pub struct MockServer {
+    api_base: String,
+    state: Arc<Mutex<State>>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl MockServer {
+    /// Listen on a free local port
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("cannot bind a local port");
+        let address = listener.local_addr().unwrap();
+        let state: Arc<Mutex<State>> = Default::default();
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        let (server_state, server_stopped) = (state.clone(), stopped.clone());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                if server_stopped.load(Ordering::SeqCst) {
+                    break;
+                }
+                let Ok(stream) = stream else { continue };
+                let state = server_state.clone();
+                std::thread::spawn(move || serve(stream, state));
+            }
+        });
+
+        Self {
+            api_base: format!("http://{address}{API_PREFIX}"),
+            state,
+            stopped,
+        }
+    }
+
+    /// Answer `method` requests to `path`, e.g. `POST` and `/chat/completions`, with `response`.
+    ///
+    /// Responses programmed for the same endpoint are sent in turn, the last one to all further requests.
+    pub fn mock(&self, method: &str, path: &str, response: MockResponse) -> &Self {
+        let mut state = self.state.lock().unwrap();
+        match state
+            .mocks
+            .iter_mut()
+            .find(|mock| mock.method.eq_ignore_ascii_case(method) && mock.path == path)
+        {
+            Some(mock) => mock.responses.push(response),
+            None => state.mocks.push(Mock {
+                method: method.into(),
+                path: path.into(),
+                responses: vec![response],
+            }),
+        }
+        self
+    }
+
+    /// Forget the programmed responses and the captured requests
+    pub fn reset(&self) {
+        *self.state.lock().unwrap() = Default::default();
+    }
+
+    /// API base of the server, to configure clients with
+    pub fn api_base(&self) -> &str {
+        &self.api_base
+    }
+
+    /// Config of the server, with a dummy API key
+    pub fn config(&self) -> OpenAIConfig {
+        OpenAIConfig::new()
+            .with_api_base(self.api_base.as_str())
+            .with_api_key("sk-test")
+    }
+
+    /// Client of the server
+    pub fn client(&self) -> Client<OpenAIConfig> {
+        Client::with_config(self.config())
+    }
+
+    /// Requests received so far, in order
+    pub fn requests(&self) -> Vec<CapturedRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    /// Requests to `path` received so far, in order
+    pub fn requests_to(&self, path: &str) -> Vec<CapturedRequest> {
+        self.requests()
+            .into_iter()
+            .filter(|request| request.path == path)
+            .collect()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        // wake up the listener so that it sees the server is stopped
+        let address = self.api_base.trim_start_matches("http://");
+        let _ = TcpStream::connect(address.trim_end_matches(API_PREFIX));
+    }
+}
+
+/// Answer the requests of a connection until it is closed
+fn serve(stream: TcpStream, state: Arc<Mutex<State>>) {
+    let Ok(reader) = stream.try_clone() else {
+        return;
+    };
+    let mut reader = BufReader::new(reader);
+    while let Some(request) = read_request(&mut reader) {
+        let response = {
+            let mut state = state.lock().unwrap();
+            let response = state.respond(&request.method, &request.path);
+            state.requests.push(request);
+            response
+        };
+        if write_response(&stream, &response).is_err() || response.truncate.is_some() {
+            let _ = stream.shutdown(std::net::Shutdown::Both);
+            return;
+        }
+    }
+}
+
+/// Next request of a connection, `None` once it is closed or unreadable
+fn read_request(reader: &mut BufReader<TcpStream>) -> Option<CapturedRequest> {
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).ok()? == 0 {
+        return None;
+    }
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next()?.to_string();
+    let target = parts.next()?;
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let path = path.strip_prefix(API_PREFIX).unwrap_or(path).to_string();
+
+    let mut headers = vec![];
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).ok()? == 0 {
+            return None;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+        }
+    }
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(header, _)| header == name)
+            .map(|(_, value)| value.as_str())
+    };
+
+    let chunked = header("transfer-encoding").map_or(false, |encoding| encoding.contains("chunked"));
+    let body = if chunked {
+        read_chunked(reader)?
+    } else {
+        let content_length = header("content-length").and_then(|length| length.parse().ok());
+        let mut body = vec![0; content_length.unwrap_or(0)];
+        reader.read_exact(&mut body).ok()?;
+        body
+    };
+
+    Some(CapturedRequest {
+        method,
+        path,
+        query: query.to_string(),
+        headers,
+        body,
+    })
+}
+
+/// Body sent with `Transfer-Encoding: chunked`, as streamed uploads are
+fn read_chunked(reader: &mut BufReader<TcpStream>) -> Option<Vec<u8>> {
+    let mut body = vec![];
+    loop {
+        let mut size = String::new();
+        reader.read_line(&mut size).ok()?;
+        let size = size.trim().split(';').next()?;
+        let size = usize::from_str_radix(size, 16).ok()?;
+        let mut chunk = vec![0; size + 2];
+        reader.read_exact(&mut chunk).ok()?;
+        if size == 0 {
+            return Some(body);
+        }
+        body.extend_from_slice(&chunk[..size]);
+    }
+}
+
+fn write_response(mut stream: &TcpStream, response: &MockResponse) -> std::io::Result<()> {
+    std::thread::sleep(response.delay);
+    let mut head = format!("HTTP/1.1 {} Mock\r\n", response.status);
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{name}: {value}\r\n"));
+    }
+    head.push_str(&format!("content-length: {}\r\n\r\n", response.body.len()));
+    stream.write_all(head.as_bytes())?;
+    if let Some(length) = response.truncate {
+        stream.write_all(&response.body[..length.min(response.body.len())])?;
+    } else if !response.stall.is_zero() {
+        let (events, last) = response.body.split_at(last_event_start(&response.body));
+        stream.write_all(events)?;
+        stream.flush()?;
+        std::thread::sleep(response.stall);
+        stream.write_all(last)?;
+    } else {
+        stream.write_all(&response.body)?;
+    }
+    stream.flush()
+}
+
+/// Index of the last event of an SSE body, past the blank line ending the event before it
+fn last_event_start(body: &[u8]) -> usize {
+    let events = body.strip_suffix(b"\n\n").unwrap_or(body);
+    events
+        .windows(2)
+        .rposition(|window| window == b"\n\n")
+        .map_or(0, |index| index + 2)
+}
@@ -35,6 +35,11 @@ impl<'c, C: Config> Threads<'c, C> {
         &self,
         request: CreateThreadAndRunRequest,
     ) -> Result<RunObject, OpenAIError> {
+        if request.stream.is_some() && request.stream.unwrap() {
+            return Err(OpenAIError::InvalidArgument(
+                "When stream is true, use Threads::create_and_run_stream".into(),
+            ));
+        }
         self.client.post("/threads/runs", request).await
     }
 
@@ -51,10 +56,10 @@ impl<'c, C: Config> Threads<'c, C> {
 
         request.stream = Some(true);
 
-        Ok(self
+        self
             .client
             .post_stream_mapped_raw_events("/threads/runs", request, AssistantStreamEvent::try_from)
-            .await)
+            .await
     }
 
     /// Create a thread.
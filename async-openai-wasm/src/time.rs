@@ -0,0 +1,121 @@
+//! Timers and clocks working both natively and on wasm32, where `std::time::Instant` panics.
+use std::future::Future;
+use std::time::Duration;
+
+/// Wait for `duration` without blocking the thread, using `setTimeout` on wasm32.
+pub(crate) async fn sleep(duration: Duration) {
+    futures_timer::Delay::new(duration).await
+}
+
+/// Deadline of the waits between the items of a stream, restarted by each item
+#[derive(Debug)]
+pub(crate) struct IdleTimeout {
+    timeout: Duration,
+    delay: futures_timer::Delay,
+}
+
+impl IdleTimeout {
+    pub(crate) fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            delay: futures_timer::Delay::new(timeout),
+        }
+    }
+
+    pub(crate) fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Wait `timeout` again from now
+    pub(crate) fn restart(&mut self) {
+        self.delay.reset(self.timeout);
+    }
+
+    /// Whether `timeout` elapsed since the last restart, waking the task of `cx` once it does otherwise
+    pub(crate) fn poll_elapsed(&mut self, cx: &mut std::task::Context<'_>) -> bool {
+        std::pin::Pin::new(&mut self.delay).poll(cx).is_ready()
+    }
+}
+
+/// Deadline of the waits between the items of a stream once it started, armed by its first item
+#[derive(Debug)]
+pub(crate) struct StallWatchdog {
+    after: Duration,
+    timer: Option<IdleTimeout>,
+}
+
+impl StallWatchdog {
+    pub(crate) fn new(after: Duration) -> Self {
+        Self { after, timer: None }
+    }
+
+    pub(crate) fn after(&self) -> Duration {
+        self.after
+    }
+
+    /// Whether `after` elapsed since the last item, `ready` telling whether an item was just received,
+    /// waking the task of `cx` once it does otherwise
+    pub(crate) fn poll_stalled(&mut self, ready: bool, cx: &mut std::task::Context<'_>) -> bool {
+        match (&mut self.timer, ready) {
+            (Some(timer), true) => {
+                timer.restart();
+                false
+            }
+            (None, true) => {
+                self.timer = Some(IdleTimeout::new(self.after));
+                false
+            }
+            (Some(timer), false) => timer.poll_elapsed(cx),
+            (None, false) => false,
+        }
+    }
+}
+
+/// Monotonic (natively) point in time to measure elapsed time from
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Instant {
+    #[cfg(not(target_arch = "wasm32"))]
+    instant: std::time::Instant,
+    #[cfg(target_arch = "wasm32")]
+    millis: f64,
+}
+
+impl Instant {
+    pub(crate) fn now() -> Self {
+        Self {
+            #[cfg(not(target_arch = "wasm32"))]
+            instant: std::time::Instant::now(),
+            #[cfg(target_arch = "wasm32")]
+            millis: js_sys::Date::now(),
+        }
+    }
+
+    pub(crate) fn elapsed(&self) -> Duration {
+        #[cfg(not(target_arch = "wasm32"))]
+        return self.instant.elapsed();
+        #[cfg(target_arch = "wasm32")]
+        return Duration::from_secs_f64((js_sys::Date::now() - self.millis).max(0.0) / 1000.0);
+    }
+}
+
+/// Seconds since the Unix epoch, using `Date.now()` on wasm32
+pub(crate) fn unix_timestamp() -> u32 {
+    #[cfg(not(target_arch = "wasm32"))]
+    return std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() as u32)
+        .unwrap_or_default();
+    #[cfg(target_arch = "wasm32")]
+    return (js_sys::Date::now() / 1000.0) as u32;
+}
+
+/// Milliseconds since the Unix epoch, using `Date.now()` on wasm32
+pub(crate) fn unix_timestamp_millis() -> u64 {
+    #[cfg(not(target_arch = "wasm32"))]
+    return std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default();
+    #[cfg(target_arch = "wasm32")]
+    return js_sys::Date::now() as u64;
+}
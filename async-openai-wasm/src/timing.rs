@@ -0,0 +1,52 @@
+//! Breakdown of the latency of responses into the time the API spent processing requests, from the
+//! `openai-processing-ms` header, and the time spent in networks, queues and proxies, to debug slow responses.
+//!
+//! ```no_run
+//! use async_openai_wasm::Client;
+//!
+//! # async fn example() -> Result<(), async_openai_wasm::error::OpenAIError> {
+//! let client = Client::new();
+//! client.models().list().await?;
+//! if let Some(timing) = client.last_response_timing() {
+//!     println!("processed in {:?}, plus {:?} of overhead", timing.processing, timing.overhead());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! With the `tracing` feature, the `openai_request` spans record them too, as `openai.processing_ms` and `openai.overhead_ms`.
+use std::time::Duration;
+
+use reqwest::header::HeaderMap;
+
+/// Timings of a response, the ones from headers being `None` when their header is missing or invalid
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResponseTiming {
+    /// Time from sending the request to receiving the headers of the response, earlier attempts excluded
+    pub latency: Duration,
+    /// Time the API spent processing the request, from the `openai-processing-ms` header
+    pub processing: Option<Duration>,
+    /// Time the edge proxy of the API waited for the service behind it, from the `x-envoy-upstream-service-time` header
+    pub upstream: Option<Duration>,
+}
+
+impl ResponseTiming {
+    /// Timings of a response received `latency` after its request was sent
+    pub fn from_headers(headers: &HeaderMap, latency: Duration) -> Self {
+        let milliseconds = |name: &str| {
+            let milliseconds: f64 = headers.get(name)?.to_str().ok()?.trim().parse().ok()?;
+            (milliseconds.is_finite() && milliseconds >= 0.0)
+                .then(|| Duration::from_secs_f64(milliseconds / 1000.0))
+        };
+        Self {
+            latency,
+            processing: milliseconds("openai-processing-ms"),
+            upstream: milliseconds("x-envoy-upstream-service-time"),
+        }
+    }
+
+    /// Part of the latency not spent processing the request: network, queues and proxies
+    pub fn overhead(&self) -> Option<Duration> {
+        Some(self.latency.saturating_sub(self.processing?))
+    }
+}
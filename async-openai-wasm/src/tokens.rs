@@ -0,0 +1,643 @@
+//! Client side token counting, enabled with the `tokens` feature, to truncate conversations before hitting
+//! context limits.
+//!
+//! Text is counted with a byte pair encoding implemented in the crate, which reads the `.tiktoken` files of
+//! [tiktoken](https://github.com/openai/tiktoken) and needs no other dependency. Its pre-tokenization approximates
+//! the regexes of tiktoken with the Unicode categories of [char], so counts of uncommon scripts may be off by a few tokens.
+//!
+//! The encodings are not bundled in the crate, as they weigh megabytes: load the `.tiktoken` file of an encoding once,
+//! e.g. fetched from <https://openaipublic.blob.core.windows.net/encodings/o200k_base.tiktoken>, and register it.
+//! Counts of models whose encoding is not registered are [TokenCount::Estimate]s of a token per 4 bytes of text.
+//!
+//! ```
+//! use async_openai_wasm::tokens::{self, Encoding, EncodingName, TokenCount};
+//!
+//! assert_eq!(tokens::text_token_count("gpt-4o", "Hello world"), TokenCount::Estimate(3));
+//!
+//! # let o200k_base = "SGVsbG8= 0\nIHdvcmxk 1";
+//! // let o200k_base = std::fs::read_to_string("o200k_base.tiktoken").unwrap();
+//! let encoding = Encoding::from_tiktoken(EncodingName::O200kBase, &o200k_base).unwrap();
+//! tokens::register_encoding(encoding);
+//!
+//! assert_eq!(tokens::text_token_count("gpt-4o", "Hello world"), TokenCount::Exact(2));
+//! ```
+use std::collections::HashMap;
+use std::iter::Sum;
+use std::ops::Add;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use crate::{
+    error::OpenAIError,
+    image_info::ImageInfo,
+    types::{
+        ChatCompletionRequestMessage, ChatCompletionRequestMessageContentPart,
+        ChatCompletionRequestUserMessageContent, ImageDetail, ImageUrl, InputAudio,
+        InputAudioFormat, Prompt,
+    },
+};
+
+/// Tokens added by the chat format to each message, and once to prime the reply
+const TOKENS_PER_MESSAGE: usize = 3;
+/// Tokens added by the chat format when a message has a name
+const TOKENS_PER_NAME: usize = 1;
+/// Tokens of audio input per second, one per 100ms
+const AUDIO_TOKENS_PER_SECOND: f64 = 10.0;
+/// Bytes per second of compressed audio, estimated at 128 kbps
+const COMPRESSED_AUDIO_BYTES_PER_SECOND: f64 = 16_000.0;
+
+static ENCODINGS: RwLock<Vec<Arc<Encoding>>> = RwLock::new(Vec::new());
+
+/// A number of tokens, counted with the encoding of the model or estimated without it.
+///
+/// Adding an estimate to a count gives an estimate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenCount {
+    /// Counted with the registered encoding of the model
+    Exact(usize),
+    /// Estimated from the length of the text, as the encoding of the model is not registered
+    Estimate(usize),
+}
+
+impl TokenCount {
+    /// The number of tokens, whether counted or estimated
+    pub fn get(self) -> usize {
+        match self {
+            Self::Exact(tokens) | Self::Estimate(tokens) => tokens,
+        }
+    }
+
+    pub fn is_estimate(self) -> bool {
+        matches!(self, Self::Estimate(_))
+    }
+}
+
+impl Default for TokenCount {
+    fn default() -> Self {
+        Self::Exact(0)
+    }
+}
+
+impl Add for TokenCount {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Exact(a), Self::Exact(b)) => Self::Exact(a + b),
+            (a, b) => Self::Estimate(a.get() + b.get()),
+        }
+    }
+}
+
+impl Add<usize> for TokenCount {
+    type Output = Self;
+
+    fn add(self, tokens: usize) -> Self {
+        match self {
+            Self::Exact(count) => Self::Exact(count + tokens),
+            Self::Estimate(count) => Self::Estimate(count + tokens),
+        }
+    }
+}
+
+impl std::ops::AddAssign for TokenCount {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl std::ops::AddAssign<usize> for TokenCount {
+    fn add_assign(&mut self, tokens: usize) {
+        *self = *self + tokens;
+    }
+}
+
+impl Sum for TokenCount {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EncodingName {
+    /// Encoding of GPT-4, GPT-3.5 and the `text-embedding-3` models
+    Cl100kBase,
+    /// Encoding of GPT-4o, GPT-4.1 and the o-series models
+    O200kBase,
+}
+
+impl EncodingName {
+    /// The encoding used by `model`, if known
+    pub fn for_model(model: &str) -> Option<Self> {
+        // fine-tuned models are named `ft:{base model}:...`
+        let model = model.strip_prefix("ft:").unwrap_or(model);
+        const O200K_BASE: [&str; 8] = [
+            "gpt-4o",
+            "gpt-4.1",
+            "gpt-4.5",
+            "gpt-5",
+            "chatgpt-4o",
+            "o1",
+            "o3",
+            "o4",
+        ];
+        const CL100K_BASE: [&str; 4] = ["gpt-4", "gpt-3.5", "gpt-35", "text-embedding-"];
+        if O200K_BASE.iter().any(|prefix| model.starts_with(prefix)) {
+            Some(Self::O200kBase)
+        } else if CL100K_BASE.iter().any(|prefix| model.starts_with(prefix)) {
+            Some(Self::Cl100kBase)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Cl100kBase => "cl100k_base",
+            Self::O200kBase => "o200k_base",
+        }
+    }
+}
+
+/// A byte pair encoding, turning text into tokens like tiktoken does, special tokens aside.
+#[derive(Debug, Clone)]
+pub struct Encoding {
+    name: EncodingName,
+    ranks: HashMap<Vec<u8>, u32>,
+}
+
+impl Encoding {
+    /// Parse the content of a `.tiktoken` file, made of a base64 encoded token and its rank per line.
+    pub fn from_tiktoken(name: EncodingName, data: &str) -> Result<Self, OpenAIError> {
+        let mut ranks = HashMap::new();
+        for (index, line) in data.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let invalid =
+                || OpenAIError::InvalidArgument(format!("invalid tiktoken line {}", index + 1));
+            let (token, rank) = line.split_once(' ').ok_or_else(invalid)?;
+            let token = STANDARD.decode(token).map_err(|_| invalid())?;
+            let rank = rank.trim().parse().map_err(|_| invalid())?;
+            ranks.insert(token, rank);
+        }
+        Ok(Self { name, ranks })
+    }
+
+    pub fn name(&self) -> EncodingName {
+        self.name
+    }
+
+    /// The tokens of `text`
+    pub fn encode(&self, text: &str) -> Vec<u32> {
+        let mut tokens = vec![];
+        for piece in split(self.name, text) {
+            let piece = piece.as_bytes();
+            if let Some(rank) = self.ranks.get(piece) {
+                tokens.push(*rank);
+                continue;
+            }
+            let parts = self.merge(piece);
+            tokens.extend(
+                parts
+                    .windows(2)
+                    .filter_map(|part| self.ranks.get(&piece[part[0]..part[1]]).copied()),
+            );
+        }
+        tokens
+    }
+
+    /// The number of tokens of `text`
+    pub fn count(&self, text: &str) -> usize {
+        split(self.name, text)
+            .map(|piece| {
+                if self.ranks.contains_key(piece.as_bytes()) {
+                    1
+                } else {
+                    self.merge(piece.as_bytes()).len() - 1
+                }
+            })
+            .sum()
+    }
+
+    /// Start offsets of the tokens of `piece`, and its end, merging the pair of lowest rank first
+    fn merge(&self, piece: &[u8]) -> Vec<usize> {
+        let rank = |parts: &[(usize, u32)], i: usize| {
+            if i + 2 < parts.len() {
+                self.ranks
+                    .get(&piece[parts[i].0..parts[i + 2].0])
+                    .copied()
+                    .unwrap_or(u32::MAX)
+            } else {
+                u32::MAX
+            }
+        };
+
+        // (start, rank of the merge with the next part)
+        let mut parts: Vec<(usize, u32)> = (0..=piece.len()).map(|i| (i, u32::MAX)).collect();
+        for i in 0..parts.len() {
+            parts[i].1 = rank(&parts, i);
+        }
+        while let Some((i, _)) = parts
+            .iter()
+            .enumerate()
+            .filter(|(_, part)| part.1 != u32::MAX)
+            .min_by_key(|(_, part)| part.1)
+        {
+            parts.remove(i + 1);
+            parts[i].1 = rank(&parts, i);
+            if i > 0 {
+                parts[i - 1].1 = rank(&parts, i - 1);
+            }
+        }
+        parts.into_iter().map(|(start, _)| start).collect()
+    }
+}
+
+/// Make `encoding` the one used to count the tokens of its models, replacing any of the same name.
+pub fn register_encoding(encoding: Encoding) -> Arc<Encoding> {
+    let encoding = Arc::new(encoding);
+    let mut encodings = ENCODINGS.write().unwrap_or_else(|e| e.into_inner());
+    encodings.retain(|registered| registered.name != encoding.name);
+    encodings.push(encoding.clone());
+    encoding
+}
+
+/// The registered encoding of `model`, if any
+pub fn encoding_for_model(model: &str) -> Option<Arc<Encoding>> {
+    let name = EncodingName::for_model(model)?;
+    ENCODINGS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .find(|encoding| encoding.name == name)
+        .cloned()
+}
+
+/// The number of tokens of `text` for `model`, estimated as one per 4 bytes
+/// when the encoding of the model is not registered.
+pub fn text_token_count(model: &str, text: &str) -> TokenCount {
+    count(encoding_for_model(model).as_deref(), text)
+}
+
+fn count(encoding: Option<&Encoding>, text: &str) -> TokenCount {
+    match encoding {
+        Some(encoding) => TokenCount::Exact(encoding.count(text)),
+        None => TokenCount::Estimate((text.len() + 3) / 4),
+    }
+}
+
+/// The number of prompt tokens of `messages` for `model`, including the tokens added by the chat format.
+///
+/// Like [text_token_count], this is an estimate when the encoding of the model is not registered.
+/// Images are counted with [image_url_token_count], files are not counted, and tool definitions are not part of `messages`.
+pub fn token_count(model: &str, messages: &[ChatCompletionRequestMessage]) -> TokenCount {
+    let encoding = encoding_for_model(model);
+    let count = |text: &str| count(encoding.as_deref(), text);
+
+    let mut tokens = TokenCount::Exact(TOKENS_PER_MESSAGE);
+    for message in messages {
+        tokens += TOKENS_PER_MESSAGE;
+        #[allow(deprecated)]
+        let (content, name) = match message {
+            ChatCompletionRequestMessage::Developer(message) => {
+                (count(&message.content), message.name.as_deref())
+            }
+            ChatCompletionRequestMessage::System(message) => {
+                (count(&message.content), message.name.as_deref())
+            }
+            ChatCompletionRequestMessage::User(message) => {
+                let content = match &message.content {
+                    ChatCompletionRequestUserMessageContent::Text(text) => count(text),
+                    ChatCompletionRequestUserMessageContent::Array(parts) => parts
+                        .iter()
+                        .map(|part| match part {
+                            ChatCompletionRequestMessageContentPart::Text(part) => {
+                                count(&part.text)
+                            }
+                            ChatCompletionRequestMessageContentPart::ImageUrl(part) => {
+                                TokenCount::Exact(image_url_token_count(model, &part.image_url))
+                            }
+                            ChatCompletionRequestMessageContentPart::InputAudio(part) => {
+                                TokenCount::Exact(input_audio_token_count(&part.input_audio))
+                            }
+                            // files are converted server side, their pages are not counted
+                            ChatCompletionRequestMessageContentPart::File(_) => {
+                                TokenCount::default()
+                            }
+                        })
+                        .sum(),
+                };
+                (content, message.name.as_deref())
+            }
+            ChatCompletionRequestMessage::Assistant(message) => {
+                let mut content = message.content.as_deref().map(count).unwrap_or_default();
+                let function_calls = message
+                    .tool_calls
+                    .iter()
+                    .flatten()
+                    .map(|tool_call| &tool_call.function)
+                    .chain(&message.function_call);
+                for function in function_calls {
+                    content += count(&function.name) + count(&function.arguments);
+                }
+                (content, message.name.as_deref())
+            }
+            ChatCompletionRequestMessage::Tool(message) => (count(&message.content), None),
+            ChatCompletionRequestMessage::Function(message) => (
+                message.content.as_deref().map(count).unwrap_or_default(),
+                Some(message.name.as_str()),
+            ),
+        };
+        tokens += content;
+        if let Some(name) = name {
+            tokens += count(name) + TOKENS_PER_NAME;
+        }
+    }
+    tokens
+}
+
+/// The number of tokens of an image of `width` by `height` pixels for `model` in vision inputs.
+///
+/// Low detail images cost a flat base of tokens. High detail images are scaled to fit in a 2048 pixels square,
+/// then down to 768 pixels on their shortest side, and cost the base plus a number of tokens per 512 pixels tile.
+/// [ImageDetail::Auto] is counted as high detail, the costliest the model may pick.
+pub fn image_token_count(model: &str, width: u32, height: u32, detail: &ImageDetail) -> usize {
+    let (base, per_tile) = image_token_costs(model);
+    if *detail == ImageDetail::Low {
+        return base;
+    }
+    let (mut width, mut height) = (width.max(1) as f64, height.max(1) as f64);
+    let fit = (2048.0 / width.max(height)).min(1.0);
+    (width, height) = (width * fit, height * fit);
+    let shortest = (768.0 / width.min(height)).min(1.0);
+    (width, height) = (width * shortest, height * shortest);
+    let tiles = (width / 512.0).ceil() as usize * (height / 512.0).ceil() as usize;
+    base + tiles * per_tile
+}
+
+/// The number of tokens of `image` for `model`, see [image_token_count].
+///
+/// The dimensions of base64 encoded PNG, JPEG, GIF and WebP images are read from their data URL. Images of
+/// other URLs, whose dimensions are unknown until downloaded, are counted at the highest cost of their detail.
+pub fn image_url_token_count(model: &str, image: &ImageUrl) -> usize {
+    let detail = image.detail.clone().unwrap_or_default();
+    // the largest tiling: 768 by 2048 pixels once scaled, in 2 by 4 tiles
+    let (width, height) = data_url_image_dimensions(&image.url).unwrap_or((768, 2048));
+    image_token_count(model, width, height, &detail)
+}
+
+/// The number of tokens of `duration` of audio input, such as `input_audio` content of audio models.
+pub fn audio_token_count(duration: Duration) -> usize {
+    (duration.as_secs_f64() * AUDIO_TOKENS_PER_SECOND).ceil() as usize
+}
+
+/// The number of tokens of `audio`, see [audio_token_count].
+///
+/// The duration of WAV audio is read from its header, and the one of MP3 audio estimated from its size at 128 kbps.
+pub fn input_audio_token_count(audio: &InputAudio) -> usize {
+    let wav_duration = match audio.format {
+        InputAudioFormat::Wav => STANDARD
+            .decode(audio.data.trim())
+            .ok()
+            .and_then(|bytes| wav_duration(&bytes)),
+        InputAudioFormat::Mp3 => None,
+    };
+    let duration = wav_duration.unwrap_or_else(|| {
+        let size = audio.data.trim().trim_end_matches('=').len() * 3 / 4;
+        Duration::from_secs_f64(size as f64 / COMPRESSED_AUDIO_BYTES_PER_SECOND)
+    });
+    audio_token_count(duration)
+}
+
+/// Duration of WAV audio, from the byte rate of its `fmt ` chunk and the size of its `data` chunk
+fn wav_duration(bytes: &[u8]) -> Option<Duration> {
+    if bytes.get(0..4)? != b"RIFF" || bytes.get(8..12)? != b"WAVE" {
+        return None;
+    }
+    let le_u32 = |offset: usize| -> Option<u32> {
+        Some(u32::from_le_bytes(
+            bytes.get(offset..offset + 4)?.try_into().ok()?,
+        ))
+    };
+    let mut byte_rate = None;
+    let mut offset = 12;
+    while let (Some(id), Some(size)) = (bytes.get(offset..offset + 4), le_u32(offset + 4)) {
+        let body = offset + 8;
+        match id {
+            b"fmt " => byte_rate = le_u32(body + 8).filter(|rate| *rate > 0),
+            b"data" => {
+                // streamed audio leaves the size of its data unset
+                let size = (size as usize).min(bytes.len() - body);
+                return Some(Duration::from_secs_f64(size as f64 / byte_rate? as f64));
+            }
+            _ => {}
+        }
+        // chunks are padded to an even size
+        offset = body.saturating_add(size as usize + size as usize % 2);
+    }
+    None
+}
+
+/// Base and per tile tokens of images for `model`
+fn image_token_costs(model: &str) -> (usize, usize) {
+    let model = model.strip_prefix("ft:").unwrap_or(model);
+    // gpt-4o-mini counts images in more tokens, at its lower price per token, to cost as much as with gpt-4o
+    if model.starts_with("gpt-4o-mini") {
+        (2833, 5667)
+    } else {
+        (85, 170)
+    }
+}
+
+/// Width and height of the image of a base64 data URL, see [ImageInfo::read]
+fn data_url_image_dimensions(url: &str) -> Option<(u32, u32)> {
+    let (header, data) = url.strip_prefix("data:")?.split_once(',')?;
+    if !header.ends_with(";base64") {
+        return None;
+    }
+    let bytes = STANDARD.decode(data.trim()).ok()?;
+    Some(ImageInfo::read(&bytes)?.oriented_dimensions())
+}
+
+impl Prompt {
+    /// The number of tokens of the prompt for `model`, see [text_token_count].
+    pub fn token_len(&self, model: &str) -> TokenCount {
+        match self {
+            Prompt::String(text) => text_token_count(model, text),
+            Prompt::StringArray(texts) => {
+                texts.iter().map(|text| text_token_count(model, text)).sum()
+            }
+            Prompt::IntegerArray(tokens) => TokenCount::Exact(tokens.len()),
+            Prompt::ArrayOfIntegerArray(prompts) => {
+                TokenCount::Exact(prompts.iter().map(Vec::len).sum())
+            }
+        }
+    }
+}
+
+/// Split `text` into the pieces tokens never span, like the regex of the encoding.
+///
+/// Unicode categories are approximated with the ones of [char], such as [char::is_alphabetic] for letters.
+fn split(name: EncodingName, text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        let first = rest.chars().next()?;
+        let len = match name {
+            EncodingName::Cl100kBase => cl100k_piece(rest),
+            EncodingName::O200kBase => o200k_piece(rest),
+        }
+        .max(first.len_utf8());
+        let (piece, remaining) = rest.split_at(len);
+        rest = remaining;
+        Some(piece)
+    })
+}
+
+/// `(?i:'s|'t|'re|'ve|'m|'ll|'d)|[^\r\n\p{L}\p{N}]?\p{L}+|\p{N}{1,3}| ?[^\s\p{L}\p{N}]+[\r\n]*|\s*[\r\n]+|\s+(?!\S)|\s+`
+fn cl100k_piece(text: &str) -> usize {
+    if let Some(len) = contraction(text) {
+        return len;
+    }
+    let prefix = word_prefix(text);
+    let letters = run(&text[prefix..], char::is_alphabetic);
+    if letters > 0 {
+        return prefix + letters;
+    }
+    numbers(text)
+        .or_else(|| symbols(text, |c| c == '\r' || c == '\n'))
+        .unwrap_or_else(|| whitespace(text))
+}
+
+/// `[^\r\n\p{L}\p{N}]?[\p{Lu}\p{Lt}\p{Lm}\p{Lo}\p{M}]*[\p{Ll}\p{Lm}\p{Lo}\p{M}]+(?i:'s|'t|'re|'ve|'m|'ll|'d)?`
+/// `|[^\r\n\p{L}\p{N}]?[\p{Lu}\p{Lt}\p{Lm}\p{Lo}\p{M}]+[\p{Ll}\p{Lm}\p{Lo}\p{M}]*(?i:'s|'t|'re|'ve|'m|'ll|'d)?`
+/// `|\p{N}{1,3}| ?[^\s\p{L}\p{N}]+[\r\n/]*|\s*[\r\n]+|\s+(?!\S)|\s+`
+fn o200k_piece(text: &str) -> usize {
+    let upper = |c: char| c.is_alphabetic() && !c.is_lowercase();
+    let lower = |c: char| c.is_alphabetic() && !c.is_uppercase();
+
+    let prefix = word_prefix(text);
+    let word = &text[prefix..];
+    let uppers = run(word, upper);
+    // upper* lower+, giving back uppers which are lower too, e.g. uncased letters
+    let mut start = uppers;
+    let mut cased = loop {
+        let lowers = run(&word[start..], lower);
+        if lowers > 0 {
+            break Some(start + lowers);
+        }
+        match word[..start].char_indices().next_back() {
+            Some((previous, _)) => start = previous,
+            None => break None,
+        }
+    };
+    // upper+ lower*
+    if cased.is_none() && uppers > 0 {
+        cased = Some(uppers + run(&word[uppers..], lower));
+    }
+    if let Some(len) = cased {
+        return prefix + len + contraction(&word[len..]).unwrap_or_default();
+    }
+
+    numbers(text)
+        .or_else(|| symbols(text, |c| c == '\r' || c == '\n' || c == '/'))
+        .unwrap_or_else(|| whitespace(text))
+}
+
+/// Byte length of the longest prefix of `text` whose chars match `predicate`
+fn run(text: &str, predicate: impl Fn(char) -> bool) -> usize {
+    text.char_indices()
+        .find(|(_, c)| !predicate(*c))
+        .map(|(index, _)| index)
+        .unwrap_or(text.len())
+}
+
+/// `(?i:'s|'t|'re|'ve|'m|'ll|'d)`
+fn contraction(text: &str) -> Option<usize> {
+    let rest = text.strip_prefix('\'')?;
+    ["s", "t", "re", "ve", "m", "ll", "d"]
+        .iter()
+        .find(|suffix| {
+            rest.get(..suffix.len())
+                .map_or(false, |start| start.eq_ignore_ascii_case(suffix))
+        })
+        .map(|suffix| 1 + suffix.len())
+}
+
+/// `[^\r\n\p{L}\p{N}]?`, if followed by a letter
+fn word_prefix(text: &str) -> usize {
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(first), Some(second))
+            if !matches!(first, '\r' | '\n')
+                && !first.is_alphabetic()
+                && !first.is_numeric()
+                && second.is_alphabetic() =>
+        {
+            first.len_utf8()
+        }
+        _ => 0,
+    }
+}
+
+/// `\p{N}{1,3}`
+fn numbers(text: &str) -> Option<usize> {
+    let len = text
+        .char_indices()
+        .take(3)
+        .take_while(|(_, c)| c.is_numeric())
+        .last()
+        .map(|(index, c)| index + c.len_utf8())?;
+    Some(len)
+}
+
+/// ` ?[^\s\p{L}\p{N}]+` followed by any chars matching `trailing`
+fn symbols(text: &str, trailing: impl Fn(char) -> bool) -> Option<usize> {
+    let symbol = |c: char| !c.is_whitespace() && !c.is_alphabetic() && !c.is_numeric();
+    let start = match text.strip_prefix(' ') {
+        Some(rest) if rest.chars().next().map_or(false, symbol) => 1,
+        _ => 0,
+    };
+    let symbols = run(&text[start..], symbol);
+    if symbols == 0 {
+        return None;
+    }
+    let end = start + symbols;
+    Some(end + run(&text[end..], trailing))
+}
+
+/// `\s*[\r\n]+|\s+(?!\S)|\s+`
+fn whitespace(text: &str) -> usize {
+    let spaces = run(text, char::is_whitespace);
+    let spaces_text = &text[..spaces];
+    if let Some(newline) = spaces_text.rfind(['\r', '\n']) {
+        return newline + 1;
+    }
+    if spaces == text.len() {
+        return spaces;
+    }
+    match spaces_text.char_indices().next_back() {
+        Some((last, _)) if last > 0 => last,
+        _ => spaces,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_like_tiktoken() {
+        let text = "Hello world's  123456\n\n  x";
+        assert_eq!(
+            split(EncodingName::Cl100kBase, text).collect::<Vec<_>>(),
+            ["Hello", " world", "'s", " ", " ", "123", "456", "\n\n", " ", " x"]
+        );
+        assert_eq!(
+            split(EncodingName::O200kBase, "HTTPServer isn't//ok").collect::<Vec<_>>(),
+            ["HTTPServer", " isn't", "//", "ok"]
+        );
+    }
+}
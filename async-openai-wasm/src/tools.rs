@@ -0,0 +1,186 @@
+//! Automatic function calling: register Rust functions as tools once, and let
+//! [Chat::create_with_tools](crate::Chat::create_with_tools) call them until the model answers.
+//!
+//! ```
+//! use async_openai_wasm::tools::ToolRegistry;
+//! use serde::Deserialize;
+//! use serde_json::json;
+//!
+//! #[derive(Deserialize)]
+//! struct Weather {
+//!     city: String,
+//! }
+//!
+//! let mut registry = ToolRegistry::new();
+//! registry.register(
+//!     "get_weather",
+//!     "Get the current weather in a city",
+//!     json!({
+//!         "type": "object",
+//!         "properties": {"city": {"type": "string"}},
+//!         "required": ["city"],
+//!     }),
+//!     |weather: Weather| async move {
+//!         Ok::<_, std::convert::Infallible>(json!({"city": weather.city, "forecast": "sunny"}))
+//!     },
+//! );
+//! assert_eq!(registry.tools().len(), 1);
+//! ```
+use std::fmt::Display;
+use std::future::Future;
+
+use futures::future::LocalBoxFuture;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::types::{
+    ChatCompletionMessageToolCall, ChatCompletionRequestToolMessage, ChatCompletionTool,
+    ChatCompletionToolType, FunctionObject,
+};
+
+/// Default for [ToolRegistry::with_max_rounds]
+pub const DEFAULT_MAX_TOOL_ROUNDS: usize = 10;
+
+type Handler = Box<dyn Fn(&str) -> LocalBoxFuture<'static, String>>;
+
+struct RegisteredTool {
+    function: FunctionObject,
+    handler: Handler,
+}
+
+/// A set of functions the model can call, along with their JSON schemas.
+///
+/// Failures, such as arguments not matching the handler's argument type or an error returned by it,
+/// are reported back to the model as the tool output so that it can correct itself.
+pub struct ToolRegistry {
+    tools: Vec<RegisteredTool>,
+    max_rounds: usize,
+}
+
+impl Default for ToolRegistry {
+    fn default() -> Self {
+        Self {
+            tools: vec![],
+            max_rounds: DEFAULT_MAX_TOOL_ROUNDS,
+        }
+    }
+}
+
+impl std::fmt::Debug for ToolRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ToolRegistry")
+            .field(
+                "tools",
+                &self
+                    .tools
+                    .iter()
+                    .map(|tool| tool.function.name.as_str())
+                    .collect::<Vec<_>>(),
+            )
+            .field("max_rounds", &self.max_rounds)
+            .finish()
+    }
+}
+
+impl ToolRegistry {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Maximum number of tool calling round trips before giving up, to not loop forever on a confused model.
+    pub fn with_max_rounds(mut self, max_rounds: usize) -> Self {
+        self.max_rounds = max_rounds;
+        self
+    }
+
+    pub fn max_rounds(&self) -> usize {
+        self.max_rounds
+    }
+
+    /// Register `handler` as the function `name`, replacing any function of the same name.
+    ///
+    /// `parameters` is the JSON schema of `A`, which the arguments generated by the model are deserialized into.
+    pub fn register<A, R, E, F, Fut>(
+        &mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        parameters: serde_json::Value,
+        handler: F,
+    ) -> &mut Self
+    where
+        A: DeserializeOwned,
+        R: Serialize,
+        E: Display,
+        F: Fn(A) -> Fut + 'static,
+        Fut: Future<Output = Result<R, E>> + 'static,
+    {
+        let name = name.into();
+        let function = FunctionObject {
+            name: name.clone(),
+            description: Some(description.into()),
+            parameters: Some(parameters),
+            strict: None,
+        };
+        let handler: Handler = Box::new(move |arguments: &str| {
+            let arguments = match serde_json::from_str::<A>(arguments) {
+                Ok(arguments) => arguments,
+                Err(e) => {
+                    let output = error_output(format!("invalid arguments: {e}"));
+                    return Box::pin(async move { output });
+                }
+            };
+            let output = handler(arguments);
+            Box::pin(async move {
+                match output.await {
+                    Ok(output) => serde_json::to_string(&output)
+                        .unwrap_or_else(|e| error_output(format!("invalid output: {e}"))),
+                    Err(e) => error_output(e.to_string()),
+                }
+            })
+        });
+
+        self.tools.retain(|tool| tool.function.name != name);
+        self.tools.push(RegisteredTool { function, handler });
+        self
+    }
+
+    /// Whether a function named `name` is registered
+    pub fn contains(&self, name: &str) -> bool {
+        self.tools.iter().any(|tool| tool.function.name == name)
+    }
+
+    /// The registered functions, to be passed as `tools` of a request.
+    pub fn tools(&self) -> Vec<ChatCompletionTool> {
+        self.tools
+            .iter()
+            .map(|tool| ChatCompletionTool {
+                r#type: ChatCompletionToolType::Function,
+                function: tool.function.clone(),
+            })
+            .collect()
+    }
+
+    /// Call the function `name` with `arguments` in JSON, returning its output in JSON.
+    pub async fn call_function(&self, name: &str, arguments: &str) -> String {
+        match self.tools.iter().find(|tool| tool.function.name == name) {
+            Some(tool) => (tool.handler)(arguments).await,
+            None => error_output(format!("unknown function: {name}")),
+        }
+    }
+
+    /// Call the function requested by `tool_call`, returning the message to send back to the model.
+    pub async fn call(
+        &self,
+        tool_call: &ChatCompletionMessageToolCall,
+    ) -> ChatCompletionRequestToolMessage {
+        ChatCompletionRequestToolMessage {
+            content: self
+                .call_function(&tool_call.function.name, &tool_call.function.arguments)
+                .await,
+            tool_call_id: tool_call.id.clone(),
+        }
+    }
+}
+
+fn error_output(error: String) -> String {
+    serde_json::json!({ "error": error }).to_string()
+}
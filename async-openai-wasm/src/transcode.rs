@@ -0,0 +1,591 @@
+//! Transcoding between streamed chat completions and streamed [Responses](crate::Responses),
+//! to migrate one endpoint at a time while keeping the code consuming the other stream.
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), async_openai_wasm::error::OpenAIError> {
+//! use async_openai_wasm::{
+//!     transcode,
+//!     types::{ChatCompletionStreamExt, CreateResponseRequestArgs},
+//!     Client,
+//! };
+//!
+//! let client = Client::new();
+//! let request = CreateResponseRequestArgs::default()
+//!     .model("gpt-4o-mini")
+//!     .input("Hello!")
+//!     .build()?;
+//!
+//! // the stream of the Responses API, as the chunks of a chat completion
+//! let events = client.responses().create_stream(request).await?;
+//! let response = transcode::chat_stream(events).accumulate().await?.into_response();
+//! println!("{}", response.first_text().unwrap_or_default());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Only the first choice of chat completions is transcoded, and the output items of responses which have no
+//! chat completion counterpart, such as web search calls or reasoning summaries, are skipped.
+use std::collections::{HashMap, VecDeque};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::Stream;
+use pin_project::pin_project;
+
+use crate::{
+    error::{ApiError, OpenAIError},
+    types::{
+        ChatChoiceStream, ChatCompletionMessageToolCallChunk, ChatCompletionStreamResponseDelta,
+        ChatCompletionToolType, CompletionTokensDetails, CompletionUsage,
+        CreateChatCompletionStreamResponse, FinishReason, FunctionCallStream, PromptTokensDetails,
+        Response, ResponseError, ResponseFunctionToolCall, ResponseIncompleteDetails,
+        ResponseInputTokensDetails, ResponseItemStatus, ResponseOutputContent, ResponseOutputItem,
+        ResponseOutputMessage, ResponseOutputTokensDetails, ResponseRole, ResponseStatus,
+        ResponseStreamEvent, ResponseUsage, Role,
+    },
+};
+
+impl From<ResponseUsage> for CompletionUsage {
+    fn from(value: ResponseUsage) -> Self {
+        CompletionUsage {
+            prompt_tokens: value.input_tokens,
+            completion_tokens: value.output_tokens,
+            total_tokens: value.total_tokens,
+            prompt_tokens_details: value
+                .input_tokens_details
+                .map(|details| PromptTokensDetails {
+                    cached_tokens: Some(details.cached_tokens),
+                    ..Default::default()
+                }),
+            completion_tokens_details: value.output_tokens_details.map(|details| {
+                CompletionTokensDetails {
+                    reasoning_tokens: Some(details.reasoning_tokens),
+                    ..Default::default()
+                }
+            }),
+        }
+    }
+}
+
+impl From<CompletionUsage> for ResponseUsage {
+    fn from(value: CompletionUsage) -> Self {
+        ResponseUsage {
+            input_tokens: value.prompt_tokens,
+            output_tokens: value.completion_tokens,
+            total_tokens: value.total_tokens,
+            input_tokens_details: value
+                .prompt_tokens_details
+                .and_then(|details| details.cached_tokens)
+                .map(|cached_tokens| ResponseInputTokensDetails { cached_tokens }),
+            output_tokens_details: value
+                .completion_tokens_details
+                .and_then(|details| details.reasoning_tokens)
+                .map(|reasoning_tokens| ResponseOutputTokensDetails { reasoning_tokens }),
+        }
+    }
+}
+
+/// Translates the events of a streamed response into chat completion chunks.
+#[derive(Debug, Clone, Default)]
+pub struct ResponsesToChat {
+    id: String,
+    model: String,
+    created: u32,
+    /// Index of the tool call of each function call item, by output index
+    tool_calls: HashMap<u32, i32>,
+}
+
+impl ResponsesToChat {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The chunk `event` translates to, if any.
+    /// Failed responses and error events are returned as [OpenAIError::ApiError].
+    pub fn translate(
+        &mut self,
+        event: ResponseStreamEvent,
+    ) -> Result<Option<CreateChatCompletionStreamResponse>, OpenAIError> {
+        let chunk = match event {
+            ResponseStreamEvent::Created { response } => {
+                self.id = response.id;
+                self.model = response.model;
+                self.created = response.created_at as u32;
+                Some(self.chunk(
+                    ChatCompletionStreamResponseDelta {
+                        role: Some(Role::Assistant),
+                        ..delta()
+                    },
+                    None,
+                ))
+            }
+            ResponseStreamEvent::OutputItemAdded {
+                output_index,
+                item: ResponseOutputItem::FunctionCall(call),
+            } => {
+                let tool_index = self.tool_calls.len() as i32;
+                self.tool_calls.insert(output_index, tool_index);
+                Some(self.tool_call_chunk(ChatCompletionMessageToolCallChunk {
+                    index: tool_index,
+                    id: Some(call.call_id),
+                    r#type: Some(ChatCompletionToolType::Function),
+                    function: Some(FunctionCallStream {
+                        name: Some(call.name),
+                        arguments: Some(call.arguments),
+                    }),
+                }))
+            }
+            ResponseStreamEvent::FunctionCallArgumentsDelta {
+                output_index,
+                delta: arguments,
+                ..
+            } => {
+                let tool_index = self
+                    .tool_calls
+                    .get(&output_index)
+                    .copied()
+                    .unwrap_or_default();
+                Some(self.tool_call_chunk(ChatCompletionMessageToolCallChunk {
+                    index: tool_index,
+                    id: None,
+                    r#type: None,
+                    function: Some(FunctionCallStream {
+                        name: None,
+                        arguments: Some(arguments),
+                    }),
+                }))
+            }
+            ResponseStreamEvent::OutputTextDelta { delta: text, .. } => Some(self.chunk(
+                ChatCompletionStreamResponseDelta {
+                    content: Some(text),
+                    ..delta()
+                },
+                None,
+            )),
+            ResponseStreamEvent::Completed { response }
+            | ResponseStreamEvent::Incomplete { response } => {
+                let finish_reason = match &response.incomplete_details {
+                    Some(details) if details.reason == "content_filter" => {
+                        FinishReason::ContentFilter
+                    }
+                    Some(_) => FinishReason::Length,
+                    None if !self.tool_calls.is_empty() => FinishReason::ToolCalls,
+                    None => FinishReason::Stop,
+                };
+                let mut chunk = self.chunk(delta(), Some(finish_reason));
+                chunk.usage = response.usage.map(Into::into);
+                Some(chunk)
+            }
+            ResponseStreamEvent::Failed { response } => {
+                let error = response.error.unwrap_or_else(|| ResponseError {
+                    code: "server_error".into(),
+                    message: "the response failed".into(),
+                });
+                return Err(OpenAIError::ApiError(ApiError {
+                    message: error.message,
+                    r#type: None,
+                    param: None,
+                    code: Some(error.code),
+                    ..Default::default()
+                }));
+            }
+            ResponseStreamEvent::Error {
+                code,
+                message,
+                param,
+            } => {
+                return Err(OpenAIError::ApiError(ApiError {
+                    message,
+                    r#type: None,
+                    param,
+                    code,
+                    ..Default::default()
+                }))
+            }
+            _ => None,
+        };
+        Ok(chunk)
+    }
+
+    fn tool_call_chunk(
+        &self,
+        tool_call: ChatCompletionMessageToolCallChunk,
+    ) -> CreateChatCompletionStreamResponse {
+        self.chunk(
+            ChatCompletionStreamResponseDelta {
+                tool_calls: Some(vec![tool_call]),
+                ..delta()
+            },
+            None,
+        )
+    }
+
+    fn chunk(
+        &self,
+        delta: ChatCompletionStreamResponseDelta,
+        finish_reason: Option<FinishReason>,
+    ) -> CreateChatCompletionStreamResponse {
+        CreateChatCompletionStreamResponse {
+            id: self.id.clone(),
+            choices: vec![ChatChoiceStream {
+                index: 0,
+                delta,
+                finish_reason,
+                logprobs: None,
+            }],
+            created: self.created,
+            model: self.model.clone(),
+            system_fingerprint: None,
+            service_tier: None,
+            object: "chat.completion.chunk".into(),
+            usage: None,
+        }
+    }
+}
+
+#[allow(deprecated)]
+fn delta() -> ChatCompletionStreamResponseDelta {
+    ChatCompletionStreamResponseDelta {
+        content: None,
+        function_call: None,
+        tool_calls: None,
+        role: None,
+    }
+}
+
+/// Translates chat completion chunks into the events of a streamed response.
+///
+/// As chat completions only tell they are done by the end of their stream, where a chunk with the usage may follow
+/// the one with the finish reason, [ChatToResponses::finish] emits the closing events once all chunks are translated.
+#[derive(Debug, Clone, Default)]
+pub struct ChatToResponses {
+    id: String,
+    model: String,
+    created_at: u64,
+    output: Vec<ResponseOutputItem>,
+    /// Output index of the message item, once text is streamed
+    message: Option<usize>,
+    /// Output index of the function call item of each tool call, by tool call index
+    tool_calls: HashMap<i32, usize>,
+    finish_reason: Option<FinishReason>,
+    usage: Option<CompletionUsage>,
+}
+
+impl ChatToResponses {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The events `chunk` translates to, starting with [ResponseStreamEvent::Created] for the first chunk.
+    pub fn translate(
+        &mut self,
+        chunk: CreateChatCompletionStreamResponse,
+    ) -> Vec<ResponseStreamEvent> {
+        let mut events = vec![];
+        if self.id.is_empty() {
+            self.id = chunk.id;
+            self.model = chunk.model;
+            self.created_at = chunk.created.into();
+            events.push(ResponseStreamEvent::Created {
+                response: self.response(ResponseStatus::InProgress),
+            });
+        }
+        if chunk.usage.is_some() {
+            self.usage = chunk.usage;
+        }
+        let Some(choice) = chunk.choices.into_iter().find(|choice| choice.index == 0) else {
+            return events;
+        };
+
+        if let Some(text) = choice.delta.content.filter(|text| !text.is_empty()) {
+            let output_index = self.message_index(&mut events);
+            if let ResponseOutputItem::Message(message) = &mut self.output[output_index] {
+                if let Some(ResponseOutputContent::OutputText { text: content, .. }) =
+                    message.content.first_mut()
+                {
+                    content.push_str(&text);
+                }
+                events.push(ResponseStreamEvent::OutputTextDelta {
+                    item_id: message.id.clone(),
+                    output_index: output_index as u32,
+                    content_index: 0,
+                    delta: text,
+                });
+            }
+        }
+
+        for tool_call in choice.delta.tool_calls.into_iter().flatten() {
+            let function = tool_call.function.unwrap_or(FunctionCallStream {
+                name: None,
+                arguments: None,
+            });
+            let output_index = match self.tool_calls.get(&tool_call.index) {
+                Some(output_index) => *output_index,
+                None => {
+                    let call_id = tool_call.id.unwrap_or_default();
+                    let item = ResponseOutputItem::FunctionCall(ResponseFunctionToolCall {
+                        id: Some(format!("fc_{call_id}")),
+                        call_id,
+                        name: function.name.unwrap_or_default(),
+                        arguments: String::new(),
+                        status: Some(ResponseItemStatus::InProgress),
+                    });
+                    let output_index = self.push_item(item, &mut events);
+                    self.tool_calls.insert(tool_call.index, output_index);
+                    output_index
+                }
+            };
+            let Some(arguments) = function.arguments.filter(|arguments| !arguments.is_empty())
+            else {
+                continue;
+            };
+            if let ResponseOutputItem::FunctionCall(call) = &mut self.output[output_index] {
+                call.arguments.push_str(&arguments);
+                events.push(ResponseStreamEvent::FunctionCallArgumentsDelta {
+                    item_id: call.id.clone().unwrap_or_default(),
+                    output_index: output_index as u32,
+                    delta: arguments,
+                });
+            }
+        }
+
+        if choice.finish_reason.is_some() {
+            self.finish_reason = choice.finish_reason;
+        }
+        events
+    }
+
+    /// The events closing the output items and the response, to emit once the chunks are over:
+    /// [ResponseStreamEvent::Incomplete] if the completion was cut by its length or the content filter,
+    /// [ResponseStreamEvent::Completed] otherwise.
+    pub fn finish(&mut self) -> Vec<ResponseStreamEvent> {
+        let incomplete_reason = match self.finish_reason {
+            Some(FinishReason::Length) => Some("max_output_tokens"),
+            Some(FinishReason::ContentFilter) => Some("content_filter"),
+            _ => None,
+        };
+        let item_status = match incomplete_reason {
+            Some(_) => ResponseItemStatus::Incomplete,
+            None => ResponseItemStatus::Completed,
+        };
+
+        let mut events = vec![];
+        for (output_index, item) in self.output.iter_mut().enumerate() {
+            let output_index = output_index as u32;
+            match item {
+                ResponseOutputItem::Message(message) => {
+                    message.status = item_status;
+                    for (content_index, part) in message.content.iter().enumerate() {
+                        if let ResponseOutputContent::OutputText { text, .. } = part {
+                            events.push(ResponseStreamEvent::OutputTextDone {
+                                item_id: message.id.clone(),
+                                output_index,
+                                content_index: content_index as u32,
+                                text: text.clone(),
+                            });
+                        }
+                        events.push(ResponseStreamEvent::ContentPartDone {
+                            item_id: message.id.clone(),
+                            output_index,
+                            content_index: content_index as u32,
+                            part: part.clone(),
+                        });
+                    }
+                }
+                ResponseOutputItem::FunctionCall(call) => {
+                    call.status = Some(item_status);
+                    events.push(ResponseStreamEvent::FunctionCallArgumentsDone {
+                        item_id: call.id.clone().unwrap_or_default(),
+                        output_index,
+                        arguments: call.arguments.clone(),
+                    });
+                }
+                _ => {}
+            }
+            events.push(ResponseStreamEvent::OutputItemDone {
+                output_index,
+                item: item.clone(),
+            });
+        }
+
+        let response = match incomplete_reason {
+            Some(reason) => ResponseStreamEvent::Incomplete {
+                response: Response {
+                    incomplete_details: Some(ResponseIncompleteDetails {
+                        reason: reason.into(),
+                    }),
+                    ..self.response(ResponseStatus::Incomplete)
+                },
+            },
+            None => ResponseStreamEvent::Completed {
+                response: self.response(ResponseStatus::Completed),
+            },
+        };
+        events.push(response);
+        events
+    }
+
+    /// Output index of the message item, added with its text part if it is not yet
+    fn message_index(&mut self, events: &mut Vec<ResponseStreamEvent>) -> usize {
+        if let Some(output_index) = self.message {
+            return output_index;
+        }
+        let item_id = format!("msg_{}", self.id);
+        let part = ResponseOutputContent::OutputText {
+            text: String::new(),
+            annotations: vec![],
+        };
+        let item = ResponseOutputItem::Message(ResponseOutputMessage {
+            id: item_id.clone(),
+            role: ResponseRole::Assistant,
+            status: ResponseItemStatus::InProgress,
+            content: vec![part.clone()],
+        });
+        let output_index = self.push_item(item, events);
+        events.push(ResponseStreamEvent::ContentPartAdded {
+            item_id,
+            output_index: output_index as u32,
+            content_index: 0,
+            part,
+        });
+        self.message = Some(output_index);
+        output_index
+    }
+
+    fn push_item(
+        &mut self,
+        item: ResponseOutputItem,
+        events: &mut Vec<ResponseStreamEvent>,
+    ) -> usize {
+        let output_index = self.output.len();
+        events.push(ResponseStreamEvent::OutputItemAdded {
+            output_index: output_index as u32,
+            item: item.clone(),
+        });
+        self.output.push(item);
+        output_index
+    }
+
+    fn response(&self, status: ResponseStatus) -> Response {
+        Response {
+            id: self.id.clone(),
+            object: "response".into(),
+            created_at: self.created_at,
+            status,
+            model: self.model.clone(),
+            output: self.output.clone(),
+            error: None,
+            incomplete_details: None,
+            instructions: None,
+            max_output_tokens: None,
+            parallel_tool_calls: None,
+            previous_response_id: None,
+            reasoning: None,
+            temperature: None,
+            top_p: None,
+            text: None,
+            tool_choice: None,
+            tools: vec![],
+            truncation: None,
+            usage: self.usage.clone().map(Into::into),
+            user: None,
+            metadata: None,
+        }
+    }
+}
+
+/// Streamed response, as chat completion chunks
+#[pin_project]
+pub struct ChatCompletionChunkStream<S> {
+    #[pin]
+    events: S,
+    translator: ResponsesToChat,
+}
+
+impl<S> Stream for ChatCompletionChunkStream<S>
+where
+    S: Stream<Item = Result<ResponseStreamEvent, OpenAIError>>,
+{
+    type Item = Result<CreateChatCompletionStreamResponse, OpenAIError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            let event = match this.events.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(event))) => event,
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            };
+            match this.translator.translate(event) {
+                Ok(Some(chunk)) => return Poll::Ready(Some(Ok(chunk))),
+                Ok(None) => continue,
+                Err(e) => return Poll::Ready(Some(Err(e))),
+            }
+        }
+    }
+}
+
+/// Streamed chat completion, as the events of a response
+#[pin_project]
+pub struct ResponseEventStream<S> {
+    #[pin]
+    chunks: S,
+    translator: ChatToResponses,
+    pending: VecDeque<ResponseStreamEvent>,
+    done: bool,
+}
+
+impl<S> Stream for ResponseEventStream<S>
+where
+    S: Stream<Item = Result<CreateChatCompletionStreamResponse, OpenAIError>>,
+{
+    type Item = Result<ResponseStreamEvent, OpenAIError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            if let Some(event) = this.pending.pop_front() {
+                return Poll::Ready(Some(Ok(event)));
+            }
+            if *this.done {
+                return Poll::Ready(None);
+            }
+            match this.chunks.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(chunk))) => {
+                    this.pending.extend(this.translator.translate(chunk))
+                }
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => {
+                    *this.done = true;
+                    this.pending.extend(this.translator.finish());
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// `events` of a streamed response as the chunks of a chat completion, see [ResponsesToChat]
+pub fn chat_stream<S>(events: S) -> ChatCompletionChunkStream<S>
+where
+    S: Stream<Item = Result<ResponseStreamEvent, OpenAIError>>,
+{
+    ChatCompletionChunkStream {
+        events,
+        translator: ResponsesToChat::new(),
+    }
+}
+
+/// `chunks` of a chat completion as the events of a streamed response, see [ChatToResponses]
+pub fn response_stream<S>(chunks: S) -> ResponseEventStream<S>
+where
+    S: Stream<Item = Result<CreateChatCompletionStreamResponse, OpenAIError>>,
+{
+    ResponseEventStream {
+        chunks,
+        translator: ChatToResponses::new(),
+        pending: VecDeque::new(),
+        done: false,
+    }
+}
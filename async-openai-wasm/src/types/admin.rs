@@ -0,0 +1,321 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use crate::error::OpenAIError;
+
+/// Role of a user in an organization
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OrganizationRole {
+    Owner,
+    #[default]
+    Reader,
+}
+
+/// Role of a user or a service account in a project
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectRole {
+    Owner,
+    Member,
+}
+
+/// Represents an individual `user` within an organization.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct User {
+    /// The object type, which is always `organization.user`
+    pub object: String,
+    /// The identifier, which can be referenced in API endpoints
+    pub id: String,
+    /// The name of the user
+    pub name: String,
+    /// The email address of the user
+    pub email: String,
+    /// `owner` or `reader`
+    pub role: OrganizationRole,
+    /// The Unix timestamp (in seconds) of when the user was added.
+    pub added_at: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UserListResponse {
+    pub object: String,
+    pub data: Vec<User>,
+    pub first_id: Option<String>,
+    pub last_id: Option<String>,
+    pub has_more: bool,
+}
+
+#[derive(Debug, Serialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "UserRoleUpdateRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option))]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct UserRoleUpdateRequest {
+    /// `owner` or `reader`
+    pub role: OrganizationRole,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UserDeleteResponse {
+    pub object: String,
+    pub id: String,
+    pub deleted: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum InviteStatus {
+    Accepted,
+    Expired,
+    Pending,
+}
+
+/// Project a user is added to once they accept their invite
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct InviteProject {
+    /// Project's public ID
+    pub id: String,
+    /// Project membership role
+    pub role: ProjectRole,
+}
+
+/// Represents an individual `invite` to the organization.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Invite {
+    /// The object type, which is always `organization.invite`
+    pub object: String,
+    /// The identifier, which can be referenced in API endpoints
+    pub id: String,
+    /// The email address of the individual to whom the invite was sent
+    pub email: String,
+    /// `owner` or `reader`
+    pub role: OrganizationRole,
+    /// `accepted`, `expired`, or `pending`
+    pub status: InviteStatus,
+    /// The Unix timestamp (in seconds) of when the invite was sent.
+    pub invited_at: u32,
+    /// The Unix timestamp (in seconds) of when the invite expires.
+    pub expires_at: u32,
+    /// The Unix timestamp (in seconds) of when the invite was accepted.
+    pub accepted_at: Option<u32>,
+    /// The projects that were granted membership upon acceptance of the invite.
+    #[serde(default)]
+    pub projects: Vec<InviteProject>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct InviteListResponse {
+    pub object: String,
+    pub data: Vec<Invite>,
+    pub first_id: Option<String>,
+    pub last_id: Option<String>,
+    pub has_more: bool,
+}
+
+#[derive(Debug, Serialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "InviteRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option))]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct InviteRequest {
+    /// Send an email to this address
+    pub email: String,
+    /// `owner` or `reader`
+    pub role: OrganizationRole,
+    /// Projects the invited user is added to once they accept the invite.
+    /// Without them the user is only added to the default project.
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub projects: Option<Vec<InviteProject>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct InviteDeleteResponse {
+    pub object: String,
+    pub id: String,
+    pub deleted: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectStatus {
+    Active,
+    Archived,
+}
+
+/// Represents an individual project.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Project {
+    /// The identifier, which can be referenced in API endpoints
+    pub id: String,
+    /// The object type, which is always `organization.project`
+    pub object: String,
+    /// The name of the project. This appears in reporting.
+    pub name: String,
+    /// The Unix timestamp (in seconds) of when the project was created.
+    pub created_at: u32,
+    /// The Unix timestamp (in seconds) of when the project was archived or `null`.
+    pub archived_at: Option<u32>,
+    /// `active` or `archived`
+    pub status: ProjectStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProjectListResponse {
+    pub object: String,
+    pub data: Vec<Project>,
+    pub first_id: Option<String>,
+    pub last_id: Option<String>,
+    pub has_more: bool,
+}
+
+#[derive(Debug, Serialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ProjectCreateRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option))]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ProjectCreateRequest {
+    /// The friendly name of the project, this name appears in reports.
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ProjectUpdateRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option))]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ProjectUpdateRequest {
+    /// The updated name of the project, this name appears in reports.
+    pub name: String,
+}
+
+/// Represents an individual user in a project.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProjectUser {
+    /// The object type, which is always `organization.project.user`
+    pub object: String,
+    /// The identifier, which can be referenced in API endpoints
+    pub id: String,
+    /// The name of the user
+    pub name: String,
+    /// The email address of the user
+    pub email: String,
+    /// `owner` or `member`
+    pub role: ProjectRole,
+    /// The Unix timestamp (in seconds) of when the project was added.
+    pub added_at: u32,
+}
+
+/// Represents an individual service account in a project.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProjectServiceAccount {
+    /// The object type, which is always `organization.project.service_account`
+    pub object: String,
+    /// The identifier, which can be referenced in API endpoints
+    pub id: String,
+    /// The name of the service account
+    pub name: String,
+    /// `owner` or `member`
+    pub role: ProjectRole,
+    /// The Unix timestamp (in seconds) of when the service account was created
+    pub created_at: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProjectServiceAccountListResponse {
+    pub object: String,
+    pub data: Vec<ProjectServiceAccount>,
+    pub first_id: Option<String>,
+    pub last_id: Option<String>,
+    pub has_more: bool,
+}
+
+#[derive(Debug, Serialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ProjectServiceAccountCreateRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option))]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ProjectServiceAccountCreateRequest {
+    /// The name of the service account being created.
+    pub name: String,
+}
+
+/// The API key of a service account, whose value is only returned when the account is created.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProjectServiceAccountApiKey {
+    /// The object type, which is always `organization.project.service_account.api_key`
+    pub object: String,
+    pub value: String,
+    pub name: String,
+    pub created_at: u32,
+    pub id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProjectServiceAccountCreateResponse {
+    /// The object type, which is always `organization.project.service_account`
+    pub object: String,
+    pub id: String,
+    pub name: String,
+    /// Service accounts can only have one role of type `member`
+    pub role: ProjectRole,
+    pub created_at: u32,
+    pub api_key: ProjectServiceAccountApiKey,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProjectServiceAccountDeleteResponse {
+    pub object: String,
+    pub id: String,
+    pub deleted: bool,
+}
+
+/// Owner of a project API key, a user or a service account
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProjectApiKeyOwner {
+    User {
+        user: ProjectUser,
+    },
+    ServiceAccount {
+        service_account: ProjectServiceAccount,
+    },
+}
+
+/// Represents an individual API key in a project.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProjectApiKey {
+    /// The object type, which is always `organization.project.api_key`
+    pub object: String,
+    /// The redacted value of the API key
+    pub redacted_value: String,
+    /// The name of the API key
+    pub name: String,
+    /// The Unix timestamp (in seconds) of when the API key was created
+    pub created_at: u32,
+    /// The identifier, which can be referenced in API endpoints
+    pub id: String,
+    pub owner: ProjectApiKeyOwner,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProjectApiKeyListResponse {
+    pub object: String,
+    pub data: Vec<ProjectApiKey>,
+    pub first_id: Option<String>,
+    pub last_id: Option<String>,
+    pub has_more: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProjectApiKeyDeleteResponse {
+    pub object: String,
+    pub id: String,
+    pub deleted: bool,
+}
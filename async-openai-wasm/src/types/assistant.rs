@@ -57,22 +57,55 @@ pub struct AssistantVectorStore {
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq, Default)]
 #[serde(tag = "type")]
+#[serde(rename_all = "lowercase")]
 pub enum AssistantVectorStoreChunkingStrategy {
     /// The default strategy. This strategy currently uses a `max_chunk_size_tokens` of `800` and `chunk_overlap_tokens` of `400`.
     #[default]
     Auto,
-    Static(StaticChunkingStrategy),
+    Static {
+        r#static: StaticChunkingStrategy,
+    },
 }
 
 /// Static Chunking Strategy
-#[derive(Clone, Serialize, Debug, Deserialize, PartialEq, Default)]
+#[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
 pub struct StaticChunkingStrategy {
     /// The maximum number of tokens in each chunk. The default value is `800`. The minimum value is `100` and the maximum value is `4096`.
-    max_chunk_size_tokens: u16,
+    pub max_chunk_size_tokens: u16,
     /// The number of tokens that overlap between chunks. The default value is `400`.
     ///
     /// Note that the overlap must not exceed half of `max_chunk_size_tokens`.
-    chunk_overlap_tokens: u16,
+    pub chunk_overlap_tokens: u16,
+}
+
+impl Default for StaticChunkingStrategy {
+    fn default() -> Self {
+        Self {
+            max_chunk_size_tokens: 800,
+            chunk_overlap_tokens: 400,
+        }
+    }
+}
+
+impl StaticChunkingStrategy {
+    /// Static chunking strategy, validated against the limits of the API.
+    pub fn new(max_chunk_size_tokens: u16, chunk_overlap_tokens: u16) -> Result<Self, OpenAIError> {
+        if !(100..=4096).contains(&max_chunk_size_tokens) {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "max_chunk_size_tokens must be between 100 and 4096, got {max_chunk_size_tokens}"
+            )));
+        }
+        if chunk_overlap_tokens > max_chunk_size_tokens / 2 {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "chunk_overlap_tokens must not exceed half of max_chunk_size_tokens ({}), got {chunk_overlap_tokens}",
+                max_chunk_size_tokens / 2
+            )));
+        }
+        Ok(Self {
+            max_chunk_size_tokens,
+            chunk_overlap_tokens,
+        })
+    }
 }
 
 /// Represents an `assistant` that can call the model and use tools.
@@ -63,3 +63,65 @@ impl From<AssistantToolFileSearchResources> for AssistantToolResources {
         }
     }
 }
+
+macro_rules! impl_tool_resources {
+    ($resources:ident, $file_search:ident) => {
+        impl $resources {
+            /// Resources of the `file_search` tool, searching the vector stores of `vector_store_ids`
+            pub fn file_search<I, S>(vector_store_ids: I) -> Self
+            where
+                I: IntoIterator<Item = S>,
+                S: Into<String>,
+            {
+                Self {
+                    code_interpreter: None,
+                    file_search: None,
+                }
+                .with_file_search(vector_store_ids)
+            }
+
+            /// Resources of the `code_interpreter` tool, the files of `file_ids`
+            pub fn code_interpreter<I, S>(file_ids: I) -> Self
+            where
+                I: IntoIterator<Item = S>,
+                S: Into<String>,
+            {
+                Self {
+                    code_interpreter: None,
+                    file_search: None,
+                }
+                .with_code_interpreter(file_ids)
+            }
+
+            /// These resources with the vector stores of `vector_store_ids` for the `file_search` tool
+            pub fn with_file_search<I, S>(mut self, vector_store_ids: I) -> Self
+            where
+                I: IntoIterator<Item = S>,
+                S: Into<String>,
+            {
+                let mut file_search = $file_search::default();
+                file_search.vector_store_ids = vector_store_ids.into_iter().map(Into::into).collect();
+                self.file_search = Some(file_search);
+                self
+            }
+
+            /// These resources with the files of `file_ids` for the `code_interpreter` tool
+            pub fn with_code_interpreter<I, S>(mut self, file_ids: I) -> Self
+            where
+                I: IntoIterator<Item = S>,
+                S: Into<String>,
+            {
+                self.code_interpreter = Some(AssistantToolCodeInterpreterResources {
+                    file_ids: file_ids.into_iter().map(Into::into).collect(),
+                });
+                self
+            }
+        }
+    };
+}
+
+impl_tool_resources!(AssistantToolResources, AssistantToolFileSearchResources);
+impl_tool_resources!(
+    CreateAssistantToolResources,
+    CreateAssistantToolFileSearchResources
+);
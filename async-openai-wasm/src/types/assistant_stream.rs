@@ -1,10 +1,12 @@
+use futures::{future, stream::FilterMap, Stream, StreamExt};
 use serde::Deserialize;
 
 use crate::client::OpenAIEventMappedStream;
-use crate::error::{ApiError, map_deserialization_error, OpenAIError};
+use crate::error::{ApiError, InvalidStreamEvent, map_deserialization_error, OpenAIError};
 
 use super::{
-    MessageDeltaObject, MessageObject, RunObject, RunStepDeltaObject, RunStepObject, ThreadObject,
+    MessageDeltaContent, MessageDeltaObject, MessageObject, RunObject, RunStepDeltaObject,
+    RunStepObject, ThreadObject,
 };
 
 /// Represents an event emitted when streaming a Run.
@@ -109,6 +111,108 @@ pub enum AssistantStreamEvent {
 
 pub type AssistantEventStream = OpenAIEventMappedStream<AssistantStreamEvent>;
 
+/// Stream of the `T` projected from the events of an [AssistantEventStream] by [AssistantEventStreamExt]
+pub type AssistantEventProjection<S, T> = FilterMap<
+    S,
+    future::Ready<Option<Result<T, OpenAIError>>>,
+    fn(Result<AssistantStreamEvent, OpenAIError>) -> future::Ready<Option<Result<T, OpenAIError>>>,
+>;
+
+/// Projections of [AssistantEventStream]s into streams of the events UIs usually handle.
+///
+/// Other events are skipped, while errors, including [AssistantStreamEvent::ErrorEvent]s
+/// as [OpenAIError::ApiError]s, are passed through.
+///
+/// ```no_run
+/// use async_openai_wasm::{types::{AssistantEventStreamExt, CreateRunRequest}, Client};
+/// use futures::StreamExt;
+///
+/// # async fn example(request: CreateRunRequest) -> Result<(), async_openai_wasm::error::OpenAIError> {
+/// let client = Client::new();
+/// let mut text = client
+///     .threads()
+///     .runs("thread_abc")
+///     .create_stream(request)
+///     .await?
+///     .text_deltas();
+/// while let Some(delta) = text.next().await {
+///     print!("{}", delta?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub trait AssistantEventStreamExt:
+    Stream<Item = Result<AssistantStreamEvent, OpenAIError>> + Sized
+{
+    /// Text streamed in message deltas, one item per delta with text
+    fn text_deltas(self) -> AssistantEventProjection<Self, String> {
+        self.filter_map(|event| {
+            future::ready(project(event, |event| match event {
+                AssistantStreamEvent::ThreadMessageDelta(delta) => {
+                    let text: String = delta
+                        .delta
+                        .content
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter_map(|content| match content {
+                            MessageDeltaContent::Text(text) => text.text?.value,
+                            _ => None,
+                        })
+                        .collect();
+                    (!text.is_empty()).then_some(text)
+                }
+                _ => None,
+            }))
+        })
+    }
+
+    /// Messages once completed
+    fn message_completions(self) -> AssistantEventProjection<Self, MessageObject> {
+        self.filter_map(|event| {
+            future::ready(project(event, |event| match event {
+                AssistantStreamEvent::ThreadMessageCompleted(message) => Some(message),
+                _ => None,
+            }))
+        })
+    }
+
+    /// Runs whenever they are created or change status, e.g. to `requires_action` or `completed`
+    fn run_status_changes(self) -> AssistantEventProjection<Self, RunObject> {
+        self.filter_map(|event| {
+            future::ready(project(event, |event| match event {
+                AssistantStreamEvent::ThreadRunCreated(run)
+                | AssistantStreamEvent::ThreadRunQueued(run)
+                | AssistantStreamEvent::ThreadRunInProgress(run)
+                | AssistantStreamEvent::ThreadRunRequiresAction(run)
+                | AssistantStreamEvent::ThreadRunCompleted(run)
+                | AssistantStreamEvent::ThreadRunIncomplete(run)
+                | AssistantStreamEvent::ThreadRunFailed(run)
+                | AssistantStreamEvent::ThreadRunCancelling(run)
+                | AssistantStreamEvent::ThreadRunCancelled(run)
+                | AssistantStreamEvent::ThreadRunExpired(run) => Some(run),
+                _ => None,
+            }))
+        })
+    }
+}
+
+impl<S> AssistantEventStreamExt for S where
+    S: Stream<Item = Result<AssistantStreamEvent, OpenAIError>> + Sized
+{
+}
+
+/// `projection` of `event`, passing errors through
+fn project<T>(
+    event: Result<AssistantStreamEvent, OpenAIError>,
+    projection: impl FnOnce(AssistantStreamEvent) -> Option<T>,
+) -> Option<Result<T, OpenAIError>> {
+    match event {
+        Ok(AssistantStreamEvent::ErrorEvent(error)) => Some(Err(OpenAIError::ApiError(error))),
+        Ok(event) => projection(event).map(Ok),
+        Err(e) => Some(Err(e)),
+    }
+}
+
 impl TryFrom<eventsource_stream::Event> for AssistantStreamEvent {
     type Error = OpenAIError;
     fn try_from(value: eventsource_stream::Event) -> Result<Self, Self::Error> {
@@ -203,9 +307,12 @@ impl TryFrom<eventsource_stream::Event> for AssistantStreamEvent {
                 .map(AssistantStreamEvent::ErrorEvent),
             "done" => Ok(AssistantStreamEvent::Done(value.data)),
 
-            _ => Err(OpenAIError::StreamError(
-                "Unrecognized event: {value:?#}".into(),
-            )),
+            _ => Err(InvalidStreamEvent {
+                event: value.event,
+                data: value.data,
+                reason: "unrecognized event".into(),
+            }
+            .into()),
         }
     }
 }
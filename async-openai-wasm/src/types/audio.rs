@@ -34,17 +34,22 @@ pub enum SpeechResponseFormat {
     Wav,
 }
 
-#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "lowercase")]
 #[non_exhaustive]
 pub enum Voice {
     #[default]
     Alloy,
+    Ash,
+    Ballad,
+    Coral,
     Echo,
     Fable,
     Onyx,
     Nova,
+    Sage,
     Shimmer,
+    Verse,
 }
 
 #[derive(Debug, Default, Serialize, Clone, PartialEq)]
@@ -26,6 +26,7 @@ pub struct BatchRequest {
     pub completion_window: BatchCompletionWindow,
 
     /// Optional custom metadata for the batch.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
 }
 
@@ -40,7 +41,7 @@ pub enum BatchEndpoint {
     V1Completions,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Default)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
 pub enum BatchCompletionWindow {
     #[default]
     #[serde(rename = "24h")]
@@ -77,7 +78,7 @@ pub struct Batch {
     pub completed_at: Option<u32>,
     /// The Unix timestamp (in seconds) for when the batch failed.
     pub failed_at: Option<u32>,
-    /// he Unix timestamp (in seconds) for when the batch expired.
+    /// The Unix timestamp (in seconds) for when the batch expired.
     pub expired_at: Option<u32>,
     /// The Unix timestamp (in seconds) for when the batch started cancelling.
     pub cancelling_at: Option<u32>,
@@ -186,3 +187,32 @@ pub struct BatchRequestOutput {
     ///  For requests that failed with a non-HTTP error, this will contain more information on the cause of the failure.
     pub error: Option<BatchRequestOutputError>,
 }
+
+impl BatchRequestInput {
+    /// Serialize requests into the JSONL format expected for the input file of a batch,
+    /// ready to be uploaded with purpose [super::FilePurpose::Batch].
+    pub fn to_jsonl(inputs: &[BatchRequestInput]) -> Result<String, OpenAIError> {
+        let mut jsonl = String::new();
+        for input in inputs {
+            let line = serde_json::to_string(input)
+                .map_err(|e| OpenAIError::InvalidArgument(e.to_string()))?;
+            jsonl.push_str(&line);
+            jsonl.push('\n');
+        }
+        Ok(jsonl)
+    }
+}
+
+impl BatchRequestOutput {
+    /// Parse the contents of the output or error file of a batch.
+    pub fn from_jsonl(jsonl: &str) -> Result<Vec<BatchRequestOutput>, OpenAIError> {
+        jsonl
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                serde_json::from_str(line)
+                    .map_err(|e| crate::error::map_deserialization_error(e, line.as_bytes()))
+            })
+            .collect()
+    }
+}
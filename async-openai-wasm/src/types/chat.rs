@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 use derive_builder::Builder;
@@ -5,6 +6,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::client::OpenAIEventStream;
 use crate::error::OpenAIError;
+use crate::validation::check_range;
+
+use super::{ReasoningEffort, Voice};
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(untagged)]
@@ -67,6 +71,7 @@ pub enum ChatCompletionFunctionCall {
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum Role {
+    Developer,
     System,
     #[default]
     User,
@@ -93,6 +98,34 @@ pub struct CompletionUsage {
     pub completion_tokens: u32,
     /// Total number of tokens used in the request (prompt + completion).
     pub total_tokens: u32,
+    /// Breakdown of tokens used in the prompt.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt_tokens_details: Option<PromptTokensDetails>,
+    /// Breakdown of tokens used in a completion.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completion_tokens_details: Option<CompletionTokensDetails>,
+}
+
+/// Breakdown of tokens used in the prompt.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq)]
+pub struct PromptTokensDetails {
+    /// Audio input tokens present in the prompt.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio_tokens: Option<u32>,
+    /// Cached tokens present in the prompt, billed at a discount. [Learn more](https://platform.openai.com/docs/guides/prompt-caching).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cached_tokens: Option<u32>,
+}
+
+/// Breakdown of tokens used in a completion.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq)]
+pub struct CompletionTokensDetails {
+    /// Audio input tokens generated by the model.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio_tokens: Option<u32>,
+    /// Tokens generated by the model for reasoning, billed as completion tokens but not part of the message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning_tokens: Option<u32>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
@@ -109,6 +142,20 @@ pub struct ChatCompletionRequestSystemMessage {
     pub name: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ChatCompletionRequestDeveloperMessageArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ChatCompletionRequestDeveloperMessage {
+    /// The contents of the developer message.
+    pub content: String,
+    /// An optional name for the participant. Provides the model information to differentiate between participants of the same role.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
 #[builder(name = "ChatCompletionRequestMessageContentPartTextArgs")]
 #[builder(pattern = "mutable")]
@@ -151,12 +198,75 @@ pub struct ChatCompletionRequestMessageContentPartImage {
     pub image_url: ImageUrl,
 }
 
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum InputAudioFormat {
+    #[default]
+    Wav,
+    Mp3,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "InputAudioArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct InputAudio {
+    /// Base64 encoded audio data.
+    pub data: String,
+    /// The format of the encoded audio data. Currently supports "wav" and "mp3".
+    pub format: InputAudioFormat,
+}
+
+/// Learn about [audio inputs](https://platform.openai.com/docs/guides/audio).
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ChatCompletionRequestMessageContentPartAudioArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ChatCompletionRequestMessageContentPartAudio {
+    pub input_audio: InputAudio,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "FileObjectArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct FileObject {
+    /// The base64 encoded file data, as a data URL e.g. `data:application/pdf;base64,...`, used when passing the file to the model as a string.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_data: Option<String>,
+    /// The ID of an uploaded file to use as input.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_id: Option<String>,
+    /// The name of the file, used when passing the file to the model as a string.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filename: Option<String>,
+}
+
+/// Learn about [file inputs](https://platform.openai.com/docs/guides/pdf-files) for text generation.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ChatCompletionRequestMessageContentPartFileArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ChatCompletionRequestMessageContentPartFile {
+    pub file: FileObject,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(tag = "type")]
 #[serde(rename_all = "snake_case")]
 pub enum ChatCompletionRequestMessageContentPart {
     Text(ChatCompletionRequestMessageContentPartText),
     ImageUrl(ChatCompletionRequestMessageContentPartImage),
+    InputAudio(ChatCompletionRequestMessageContentPartAudio),
+    File(ChatCompletionRequestMessageContentPartFile),
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -234,6 +344,9 @@ pub struct ChatCompletionRequestFunctionMessage {
 #[serde(tag = "role")]
 #[serde(rename_all = "lowercase")]
 pub enum ChatCompletionRequestMessage {
+    /// Instructions the model should follow regardless of the messages of the user, replacing system messages
+    /// with o1 and newer models.
+    Developer(ChatCompletionRequestDeveloperMessage),
     System(ChatCompletionRequestSystemMessage),
     User(ChatCompletionRequestUserMessage),
     Assistant(ChatCompletionRequestAssistantMessage),
@@ -267,6 +380,76 @@ pub struct ChatCompletionResponseMessage {
     /// The name and arguments of a function that should be called, as generated by the model.
     #[deprecated]
     pub function_call: Option<FunctionCall>,
+
+    /// If the audio output modality is requested, this object contains data about the audio response from the model. [Learn more](https://platform.openai.com/docs/guides/audio).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio: Option<ChatCompletionResponseMessageAudio>,
+
+    /// Annotations of the content, such as the URL citations of [web search](https://platform.openai.com/docs/guides/tools-web-search?api-mode=chat) models.
+    /// See [citations](crate::citations) to render them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<Vec<ChatCompletionResponseMessageAnnotation>>,
+}
+
+/// An annotation of the content of a [ChatCompletionResponseMessage]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChatCompletionResponseMessageAnnotation {
+    /// A citation of a web resource found by a web search model.
+    UrlCitation { url_citation: UrlCitation },
+}
+
+/// A citation of a web resource, spanning `start_index..end_index` of the content
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct UrlCitation {
+    /// The index of the first character of the citation in the content.
+    pub start_index: u32,
+    /// The index past the last character of the citation in the content.
+    pub end_index: u32,
+    /// The URL of the web resource.
+    pub url: String,
+    /// The title of the web resource.
+    pub title: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ChatCompletionResponseMessageAudio {
+    /// Unique identifier for this audio response.
+    pub id: String,
+    /// The Unix timestamp (in seconds) for when this audio response will no longer be accessible on the server for use in multi-turn conversations.
+    pub expires_at: u32,
+    /// Base64 encoded audio bytes generated by the model, in the format specified in the request.
+    pub data: String,
+    /// Transcript of the audio generated by the model.
+    pub transcript: String,
+}
+
+/// Output types the model may generate
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatCompletionModalities {
+    Text,
+    Audio,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatCompletionAudioFormat {
+    Wav,
+    #[default]
+    Mp3,
+    Flac,
+    Opus,
+    Pcm16,
+}
+
+/// Parameters for audio output. Required when audio output is requested with `modalities: ["audio"]`.
+#[derive(Debug, Deserialize, Serialize, Default, Clone, PartialEq)]
+pub struct ChatCompletionAudio {
+    /// The voice the model uses to respond.
+    pub voice: Voice,
+    /// Specifies the output audio format.
+    pub format: ChatCompletionAudioFormat,
 }
 
 #[derive(Clone, Serialize, Default, Debug, Deserialize, Builder, PartialEq)]
@@ -288,6 +471,22 @@ pub struct ChatCompletionFunctions {
     pub parameters: serde_json::Value,
 }
 
+#[allow(deprecated)]
+impl ChatCompletionFunctions {
+    /// The equivalent function tool
+    pub fn into_tool(self) -> ChatCompletionTool {
+        ChatCompletionTool {
+            r#type: ChatCompletionToolType::Function,
+            function: FunctionObject {
+                name: self.name,
+                description: self.description,
+                parameters: Some(self.parameters),
+                strict: None,
+            },
+        }
+    }
+}
+
 #[derive(Clone, Serialize, Default, Debug, Deserialize, Builder, PartialEq)]
 #[builder(name = "FunctionObjectArgs")]
 #[builder(pattern = "mutable")]
@@ -305,6 +504,10 @@ pub struct FunctionObject {
     /// Omitting `parameters` defines a function with an empty parameter list.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parameters: Option<serde_json::Value>,
+
+    /// Whether to enable strict schema adherence when generating the function call. If set to true, the model will follow the exact schema defined in the `parameters` field. Only a subset of JSON Schema is supported when `strict` is `true`. Learn more about Structured Outputs in the [function calling guide](https://platform.openai.com/docs/guides/function-calling).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strict: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
@@ -384,7 +587,7 @@ pub enum ChatCompletionToolChoiceOption {
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateChatCompletionRequest {
     /// A list of messages comprising the conversation so far. [Example Python code](https://cookbook.openai.com/examples/how_to_format_inputs_to_chatgpt_models).
     pub messages: Vec<ChatCompletionRequestMessage>, // min: 1
@@ -419,13 +622,43 @@ pub struct CreateChatCompletionRequest {
     /// The maximum number of [tokens](https://platform.openai.com/tokenizer) that can be generated in the chat completion.
     ///
     /// The total length of input tokens and generated tokens is limited by the model's context length. [Example Python code](https://cookbook.openai.com/examples/how_to_count_tokens_with_tiktoken) for counting tokens.
+    ///
+    /// Deprecated in favor of `max_completion_tokens`, and not compatible with [o-series models](https://platform.openai.com/docs/guides/reasoning),
+    /// for which [Chat::create](crate::Chat::create) moves it to `max_completion_tokens`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_tokens: Option<u16>,
 
+    /// An upper bound for the number of tokens that can be generated for a completion, including visible output tokens and [reasoning tokens](https://platform.openai.com/docs/guides/reasoning).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_completion_tokens: Option<u32>,
+
+    /// **o-series models only**
+    ///
+    /// Constrains effort on reasoning for [reasoning models](https://platform.openai.com/docs/guides/reasoning).
+    /// Reducing reasoning effort can result in faster responses and fewer tokens used on reasoning in a response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<ReasoningEffort>,
+
     /// How many chat completion choices to generate for each input message. Note that you will be charged based on the number of generated tokens across all of the choices. Keep `n` as `1` to minimize costs.
+    ///
+    /// The choices of a response can be looked up by index with [CreateChatCompletionResponse::choice]. When streamed,
+    /// the chunks of all choices are interleaved, and can be split with [ChatCompletionStreamExt::demultiplex]
+    /// or merged per choice with [ChatCompletionStreamExt::accumulate].
     #[serde(skip_serializing_if = "Option::is_none")]
     pub n: Option<u8>, // min:1, max: 128, default: 1
 
+    /// Output types that you would like the model to generate for this request.
+    /// Most models are capable of generating text, which is the default: `["text"]`.
+    ///
+    /// The `gpt-4o-audio-preview` model can also be used to [generate audio](https://platform.openai.com/docs/guides/audio).
+    /// To request that this model generate both text and audio responses, you can use: `["text", "audio"]`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modalities: Option<Vec<ChatCompletionModalities>>,
+
+    /// Parameters for audio output. Required when audio output is requested with `modalities: ["audio"]`. [Learn more](https://platform.openai.com/docs/guides/audio).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio: Option<ChatCompletionAudio>,
+
     /// Number between -2.0 and 2.0. Positive values penalize new tokens based on whether they appear in the text so far, increasing the model's likelihood to talk about new topics.
     ///
     /// [See more information about frequency and presence penalties.](https://platform.openai.com/docs/api-reference/parameter-details)
@@ -447,6 +680,26 @@ pub struct CreateChatCompletionRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub seed: Option<i64>,
 
+    /// Specifies the latency tier to use for processing the request, for customers subscribed to the scale tier service:
+    ///   - If set to `auto`, the system will utilize scale tier credits until they are exhausted.
+    ///   - If set to `default`, the request will be processed using the default service tier with a lower uptime SLA and no latency guarantee.
+    ///
+    /// When this parameter is set, the response body will include the `service_tier` utilized.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_tier: Option<ServiceTier>,
+
+    /// Whether or not to store the output of this chat completion request, for use in
+    /// [model distillation](https://platform.openai.com/docs/guides/distillation) or [evals](https://platform.openai.com/docs/guides/evals) products.
+    ///
+    /// Stored completions can be listed, retrieved, updated and deleted with [Chat](crate::Chat).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub store: Option<bool>, // default: false
+
+    /// Developer-defined tags and values used for filtering completions in the [dashboard](https://platform.openai.com/chat-completions).
+    /// Up to 16 pairs, with keys of at most 64 characters and values of at most 512 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+
     /// Up to 4 sequences where the API will stop generating further tokens.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stop: Option<Stop>,
@@ -457,6 +710,8 @@ pub struct CreateChatCompletionRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stream: Option<bool>,
 
+    /// Options for streaming response, such as `include_usage` to get the usage of the request in a last chunk.
+    /// Only set this when you set `stream: true`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stream_options: Option<ChatCompletionStreamOptions>,
 
@@ -511,6 +766,26 @@ pub struct CreateChatCompletionRequest {
     pub functions: Option<Vec<ChatCompletionFunctions>>,
 }
 
+/// The latency tier to process a request with.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ServiceTier {
+    Auto,
+    Default,
+    Flex,
+    Priority,
+}
+
+/// The service tier used for processing a request.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ServiceTierResponse {
+    Scale,
+    Default,
+    Flex,
+    Priority,
+}
+
 /// Options for streaming response. Only set this when you set `stream: true`.
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
 pub struct ChatCompletionStreamOptions {
@@ -550,10 +825,43 @@ pub struct ChatCompletionTokenLogprob {
     pub top_logprobs: Vec<TopLogprobs>,
 }
 
+impl TopLogprobs {
+    /// The probability of this token, between 0 and 1.
+    pub fn probability(&self) -> f64 {
+        (self.logprob as f64).exp()
+    }
+}
+
+impl ChatCompletionTokenLogprob {
+    /// The probability of this token, between 0 and 1.
+    pub fn probability(&self) -> f64 {
+        (self.logprob as f64).exp()
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct ChatChoiceLogprobs {
     /// A list of message content tokens with log probability information.
     pub content: Option<Vec<ChatCompletionTokenLogprob>>,
+    /// A list of message refusal tokens with log probability information.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refusal: Option<Vec<ChatCompletionTokenLogprob>>,
+}
+
+impl ChatChoiceLogprobs {
+    /// The log probability of the whole content, the sum of the log probabilities of its tokens.
+    /// `None` if there is no content token.
+    pub fn logprob(&self) -> Option<f64> {
+        let tokens = self.content.as_deref().filter(|tokens| !tokens.is_empty())?;
+        Some(tokens.iter().map(|token| token.logprob as f64).sum())
+    }
+
+    /// The perplexity of the content, `exp` of the mean negative log probability of its tokens,
+    /// from 1 for a content the model is certain of, upwards. `None` if there is no content token.
+    pub fn perplexity(&self) -> Option<f64> {
+        let tokens = self.content.as_deref().filter(|tokens| !tokens.is_empty())?;
+        Some((-self.logprob()? / tokens.len() as f64).exp())
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
@@ -585,10 +893,240 @@ pub struct CreateChatCompletionResponse {
     ///
     /// Can be used in conjunction with the `seed` request parameter to understand when backend changes have been made that might impact determinism.
     pub system_fingerprint: Option<String>,
+    /// The service tier used for processing the request, present when `service_tier` is set in the request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_tier: Option<ServiceTierResponse>,
 
     /// The object type, which is always `chat.completion`.
     pub object: String,
     pub usage: Option<CompletionUsage>,
+    /// The metadata of the request, returned for [stored](CreateChatCompletionRequest::store) chat completions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+/// A page of stored chat completions, see [Chat::list](crate::Chat::list).
+#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+pub struct ChatCompletionList {
+    pub object: String,
+    pub data: Vec<CreateChatCompletionResponse>,
+    pub first_id: Option<String>,
+    pub last_id: Option<String>,
+    pub has_more: bool,
+}
+
+/// A message of a stored chat completion, see [Chat::messages](crate::Chat::messages).
+#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+pub struct ChatCompletionStoreMessage {
+    /// The identifier of the message.
+    pub id: String,
+    #[serde(flatten)]
+    pub message: ChatCompletionResponseMessage,
+}
+
+/// A page of the messages of a stored chat completion, see [Chat::messages](crate::Chat::messages).
+#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+pub struct ChatCompletionMessageList {
+    pub object: String,
+    pub data: Vec<ChatCompletionStoreMessage>,
+    pub first_id: Option<String>,
+    pub last_id: Option<String>,
+    pub has_more: bool,
+}
+
+#[derive(Clone, Serialize, Default, Debug, Deserialize, Builder, PartialEq)]
+#[builder(name = "UpdateChatCompletionRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct UpdateChatCompletionRequest {
+    /// The new metadata of the stored chat completion, replacing its previous metadata; `None` clears it.
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+/// Response of [Chat::delete](crate::Chat::delete).
+#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+pub struct ChatCompletionDeleted {
+    pub object: String,
+    pub id: String,
+    pub deleted: bool,
+}
+
+impl CreateChatCompletionRequestArgs {
+    /// Check the sampling parameters are within the ranges the API accepts
+    fn validate(&self) -> Result<(), OpenAIError> {
+        check_range("temperature", self.temperature.flatten(), 0.0, 2.0)?;
+        check_range("top_p", self.top_p.flatten(), 0.0, 1.0)?;
+        check_range(
+            "frequency_penalty",
+            self.frequency_penalty.flatten(),
+            -2.0,
+            2.0,
+        )?;
+        check_range(
+            "presence_penalty",
+            self.presence_penalty.flatten(),
+            -2.0,
+            2.0,
+        )?;
+        check_range("n", self.n.flatten(), 1, 128)?;
+        check_range("top_logprobs", self.top_logprobs.flatten(), 0, 20)?;
+        for (token, bias) in self.logit_bias.iter().flatten().flatten() {
+            check_range(&format!("logit_bias.{token}"), bias.as_f64(), -100.0, 100.0)?;
+        }
+        Ok(())
+    }
+}
+
+#[allow(deprecated)]
+impl CreateChatCompletionRequest {
+    /// Whether the request uses the deprecated `functions` or `function_call`
+    pub fn uses_legacy_functions(&self) -> bool {
+        self.functions.is_some()
+            || self.function_call.is_some()
+            || self.messages.iter().any(|message| match message {
+                ChatCompletionRequestMessage::Assistant(assistant) => {
+                    assistant.function_call.is_some()
+                }
+                ChatCompletionRequestMessage::Function(_) => true,
+                _ => false,
+            })
+    }
+
+    /// Whether the model is an [o-series reasoning model](https://platform.openai.com/docs/guides/reasoning), like `o1` or `o3-mini`
+    pub fn is_reasoning_model(&self) -> bool {
+        let mut chars = self.model.chars();
+        chars.next() == Some('o') && chars.next().map_or(false, |c| c.is_ascii_digit())
+    }
+
+    /// Move `max_tokens` to `max_completion_tokens` if the model is a reasoning model, which rejects `max_tokens`,
+    /// returning whether it was moved.
+    pub fn migrate_max_tokens(&mut self) -> bool {
+        if !self.is_reasoning_model() {
+            return false;
+        }
+        match self.max_tokens.take() {
+            Some(max_tokens) => {
+                self.max_completion_tokens.get_or_insert(max_tokens.into());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Replace the deprecated `functions` and `function_call` with the equivalent `tools` and `tool_choice`,
+    /// including in the conversation history, for providers which removed them.
+    ///
+    /// Function calls of assistant messages get made up tool call ids, which the following function messages
+    /// of the same name, turned into tool messages, refer to.
+    pub fn migrate_functions_to_tools(&mut self) {
+        if let Some(functions) = self.functions.take() {
+            self.tools.get_or_insert_with(Vec::new).extend(
+                functions
+                    .into_iter()
+                    .map(ChatCompletionFunctions::into_tool),
+            );
+        }
+        if let Some(function_call) = self.function_call.take() {
+            self.tool_choice.get_or_insert(function_call.into());
+        }
+
+        let mut call_ids: HashMap<String, String> = HashMap::new();
+        for (index, message) in self.messages.iter_mut().enumerate() {
+            if let ChatCompletionRequestMessage::Assistant(assistant) = message {
+                if let Some(function_call) = assistant.function_call.take() {
+                    let id = format!("call_{index}");
+                    call_ids.insert(function_call.name.clone(), id.clone());
+                    assistant.tool_calls.get_or_insert_with(Vec::new).push(
+                        ChatCompletionMessageToolCall {
+                            id,
+                            r#type: ChatCompletionToolType::Function,
+                            function: function_call,
+                        },
+                    );
+                }
+            } else if let ChatCompletionRequestMessage::Function(function) = message {
+                let tool_message = ChatCompletionRequestToolMessage {
+                    content: function.content.take().unwrap_or_default(),
+                    tool_call_id: call_ids
+                        .get(&function.name)
+                        .cloned()
+                        .unwrap_or_else(|| format!("call_{index}")),
+                };
+                *message = ChatCompletionRequestMessage::Tool(tool_message);
+            }
+        }
+    }
+}
+
+impl CreateChatCompletionResponse {
+    /// Mirror the first tool call of each choice into the deprecated `function_call`,
+    /// for code written against the legacy functions which were migrated with
+    /// [CreateChatCompletionRequest::migrate_functions_to_tools].
+    #[allow(deprecated)]
+    pub fn restore_function_calls(&mut self) {
+        for choice in &mut self.choices {
+            let message = &mut choice.message;
+            if message.function_call.is_none() {
+                message.function_call = message
+                    .tool_calls
+                    .as_ref()
+                    .and_then(|tool_calls| tool_calls.first())
+                    .map(|tool_call| tool_call.function.clone());
+            }
+            if message.function_call.is_some()
+                && choice.finish_reason == Some(FinishReason::ToolCalls)
+            {
+                choice.finish_reason = Some(FinishReason::FunctionCall);
+            }
+        }
+    }
+
+    /// The content of the first choice, if any.
+    pub fn first_text(&self) -> Option<&str> {
+        self.choices.first()?.message.content.as_deref()
+    }
+
+    /// The choice generated with `index`, among the `n` of the request, if any
+    pub fn choice(&self, index: u32) -> Option<&ChatChoice> {
+        self.choices.iter().find(|choice| choice.index == index)
+    }
+
+    /// The content of each choice, in the order of their index, `None` for choices without content,
+    /// e.g. calling tools
+    pub fn choices_text(&self) -> Vec<Option<&str>> {
+        let mut choices: Vec<_> = self.choices.iter().collect();
+        choices.sort_by_key(|choice| choice.index);
+        choices
+            .iter()
+            .map(|choice| choice.message.content.as_deref())
+            .collect()
+    }
+
+    /// The content of all choices concatenated, empty if there is none.
+    ///
+    /// Only allocates when more than one choice has content.
+    pub fn text(&self) -> Cow<'_, str> {
+        concat(
+            self.choices
+                .iter()
+                .filter_map(|choice| choice.message.content.as_deref()),
+        )
+    }
+
+    /// The perplexity of the content of the first choice, if its `logprobs` were requested.
+    /// See [ChatChoiceLogprobs::perplexity].
+    pub fn perplexity(&self) -> Option<f64> {
+        self.choices.first()?.logprobs.as_ref()?.perplexity()
+    }
+
+    /// The tool calls of all choices.
+    pub fn tool_calls(&self) -> impl Iterator<Item = &ChatCompletionMessageToolCall> {
+        self.choices
+            .iter()
+            .flat_map(|choice| choice.message.tool_calls.iter().flatten())
+    }
 }
 
 /// Parsed server side events stream until an \[DONE\] is received from server.
@@ -654,6 +1192,9 @@ pub struct CreateChatCompletionStreamResponse {
     /// This fingerprint represents the backend configuration that the model runs with.
     /// Can be used in conjunction with the `seed` request parameter to understand when backend changes have been made that might impact determinism.
     pub system_fingerprint: Option<String>,
+    /// The service tier used for processing the request, present when `service_tier` is set in the request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_tier: Option<ServiceTierResponse>,
     /// The object type, which is always `chat.completion.chunk`.
     pub object: String,
 
@@ -661,3 +1202,41 @@ pub struct CreateChatCompletionStreamResponse {
     /// When present, it contains a null value except for the last chunk which contains the token usage statistics for the entire request.
     pub usage: Option<CompletionUsage>,
 }
+
+impl CreateChatCompletionStreamResponse {
+    /// The content delta of the first choice, if any.
+    pub fn first_text(&self) -> Option<&str> {
+        self.choices.first()?.delta.content.as_deref()
+    }
+
+    /// The content deltas of all choices concatenated, empty if there is none.
+    ///
+    /// Only allocates when more than one choice has content.
+    pub fn text(&self) -> Cow<'_, str> {
+        concat(
+            self.choices
+                .iter()
+                .filter_map(|choice| choice.delta.content.as_deref()),
+        )
+    }
+
+    /// The tool call chunks of all choices.
+    pub fn tool_calls(&self) -> impl Iterator<Item = &ChatCompletionMessageToolCallChunk> {
+        self.choices
+            .iter()
+            .flat_map(|choice| choice.delta.tool_calls.iter().flatten())
+    }
+}
+
+fn concat<'a>(mut texts: impl Iterator<Item = &'a str>) -> Cow<'a, str> {
+    match (texts.next(), texts.next()) {
+        (None, _) => Cow::Borrowed(""),
+        (Some(first), None) => Cow::Borrowed(first),
+        (Some(first), Some(second)) => {
+            let mut text = String::from(first);
+            text.push_str(second);
+            texts.for_each(|next| text.push_str(next));
+            Cow::Owned(text)
+        }
+    }
+}
@@ -0,0 +1,430 @@
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{future, stream::TryFold, Stream, TryStreamExt};
+use pin_project::pin_project;
+
+use crate::error::OpenAIError;
+
+use super::{
+    ChatChoice, ChatChoiceLogprobs, ChatChoiceStream, ChatCompletionMessageToolCall,
+    ChatCompletionResponseMessage, CompletionUsage, CreateChatCompletionResponse,
+    CreateChatCompletionStreamResponse, FunctionCall, Role, ServiceTierResponse,
+};
+
+/// Merges the chunks of a chat completion stream into the response the request would get unstreamed,
+/// with the content of each choice and its tool calls, whose names and arguments come in fragments, reassembled.
+///
+/// ```no_run
+/// use async_openai_wasm::{
+///     types::{ChatCompletionStreamAccumulator, CreateChatCompletionRequest},
+///     Client,
+/// };
+/// use futures::StreamExt;
+///
+/// # async fn example(request: CreateChatCompletionRequest) -> Result<(), async_openai_wasm::error::OpenAIError> {
+/// let client = Client::new();
+/// let mut stream = client.chat().create_stream(request).await?;
+/// let mut accumulator = ChatCompletionStreamAccumulator::new();
+/// while let Some(chunk) = stream.next().await {
+///     let chunk = chunk?;
+///     print!("{}", chunk.text());
+///     accumulator.push(&chunk);
+/// }
+/// for tool_call in accumulator.into_message().and_then(|message| message.tool_calls).unwrap_or_default() {
+///     println!("{}({})", tool_call.function.name, tool_call.function.arguments);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChatCompletionStreamAccumulator {
+    id: String,
+    created: u32,
+    model: String,
+    system_fingerprint: Option<String>,
+    service_tier: Option<ServiceTierResponse>,
+    usage: Option<CompletionUsage>,
+    /// Choices in order of index, with the stream indices of their tool calls
+    choices: Vec<(ChatChoice, Vec<i32>)>,
+}
+
+impl ChatCompletionStreamAccumulator {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Merge `chunk` into the choices accumulated so far
+    pub fn push(&mut self, chunk: &CreateChatCompletionStreamResponse) {
+        self.id.clone_from(&chunk.id);
+        self.created = chunk.created;
+        self.model.clone_from(&chunk.model);
+        if chunk.system_fingerprint.is_some() {
+            self.system_fingerprint
+                .clone_from(&chunk.system_fingerprint);
+        }
+        if chunk.service_tier.is_some() {
+            self.service_tier = chunk.service_tier;
+        }
+        if chunk.usage.is_some() {
+            self.usage.clone_from(&chunk.usage);
+        }
+        for choice in &chunk.choices {
+            self.push_choice(choice);
+        }
+    }
+
+    #[allow(deprecated)]
+    fn push_choice(&mut self, chunk: &ChatChoiceStream) {
+        let position = match self
+            .choices
+            .iter()
+            .position(|(choice, _)| choice.index == chunk.index)
+        {
+            Some(position) => position,
+            None => {
+                let choice = ChatChoice {
+                    index: chunk.index,
+                    message: ChatCompletionResponseMessage {
+                        content: None,
+                        tool_calls: None,
+                        role: Role::Assistant,
+                        function_call: None,
+                        audio: None,
+                        annotations: None,
+                    },
+                    finish_reason: None,
+                    logprobs: None,
+                };
+                let position = self
+                    .choices
+                    .partition_point(|(choice, _)| choice.index < chunk.index);
+                self.choices.insert(position, (choice, vec![]));
+                position
+            }
+        };
+        let (choice, tool_call_indices) = &mut self.choices[position];
+        let message = &mut choice.message;
+        let delta = &chunk.delta;
+
+        if let Some(role) = delta.role {
+            message.role = role;
+        }
+        if let Some(content) = &delta.content {
+            message
+                .content
+                .get_or_insert_with(String::new)
+                .push_str(content);
+        }
+        if let Some(function_call) = &delta.function_call {
+            let call = message.function_call.get_or_insert_with(|| FunctionCall {
+                name: String::new(),
+                arguments: String::new(),
+            });
+            call.name
+                .push_str(function_call.name.as_deref().unwrap_or_default());
+            call.arguments
+                .push_str(function_call.arguments.as_deref().unwrap_or_default());
+        }
+        for tool_call_chunk in delta.tool_calls.iter().flatten() {
+            let tool_calls = message.tool_calls.get_or_insert_with(Vec::new);
+            let tool_call = match tool_call_indices
+                .iter()
+                .position(|index| *index == tool_call_chunk.index)
+            {
+                Some(position) => &mut tool_calls[position],
+                None => {
+                    tool_call_indices.push(tool_call_chunk.index);
+                    tool_calls.push(ChatCompletionMessageToolCall {
+                        id: String::new(),
+                        r#type: Default::default(),
+                        function: FunctionCall {
+                            name: String::new(),
+                            arguments: String::new(),
+                        },
+                    });
+                    tool_calls.last_mut().unwrap()
+                }
+            };
+            if let Some(id) = &tool_call_chunk.id {
+                tool_call.id.push_str(id);
+            }
+            if let Some(r#type) = &tool_call_chunk.r#type {
+                tool_call.r#type = r#type.clone();
+            }
+            if let Some(function) = &tool_call_chunk.function {
+                let name = function.name.as_deref().unwrap_or_default();
+                tool_call.function.name.push_str(name);
+                let arguments = function.arguments.as_deref().unwrap_or_default();
+                tool_call.function.arguments.push_str(arguments);
+            }
+        }
+        if chunk.finish_reason.is_some() {
+            choice.finish_reason = chunk.finish_reason;
+        }
+        if let Some(logprobs) = &chunk.logprobs {
+            let merged = choice.logprobs.get_or_insert(ChatChoiceLogprobs {
+                content: None,
+                refusal: None,
+            });
+            for (merged, tokens) in [
+                (&mut merged.content, &logprobs.content),
+                (&mut merged.refusal, &logprobs.refusal),
+            ] {
+                if let Some(tokens) = tokens {
+                    merged
+                        .get_or_insert_with(Vec::new)
+                        .extend_from_slice(tokens);
+                }
+            }
+        }
+    }
+
+    /// The usage of the whole request, streamed in the last chunk when `stream_options.include_usage` is set
+    pub fn usage(&self) -> Option<&CompletionUsage> {
+        self.usage.as_ref()
+    }
+
+    /// The message of the first choice accumulated so far, if any
+    pub fn message(&self) -> Option<&ChatCompletionResponseMessage> {
+        self.choices.first().map(|(choice, _)| &choice.message)
+    }
+
+    /// The message of the first choice, if any
+    pub fn into_message(self) -> Option<ChatCompletionResponseMessage> {
+        self.choices
+            .into_iter()
+            .next()
+            .map(|(choice, _)| choice.message)
+    }
+
+    /// The response accumulated so far
+    pub fn response(&self) -> CreateChatCompletionResponse {
+        self.clone().into_response()
+    }
+
+    pub fn into_response(self) -> CreateChatCompletionResponse {
+        CreateChatCompletionResponse {
+            id: self.id,
+            choices: self.choices.into_iter().map(|(choice, _)| choice).collect(),
+            created: self.created,
+            model: self.model,
+            system_fingerprint: self.system_fingerprint,
+            service_tier: self.service_tier,
+            object: "chat.completion".into(),
+            usage: self.usage,
+            metadata: None,
+        }
+    }
+}
+
+/// Future of the [ChatCompletionStreamAccumulator] of a whole stream, see [ChatCompletionStreamExt::accumulate]
+pub type ChatCompletionStreamAccumulation<S> = TryFold<
+    S,
+    future::Ready<Result<ChatCompletionStreamAccumulator, OpenAIError>>,
+    ChatCompletionStreamAccumulator,
+    fn(
+        ChatCompletionStreamAccumulator,
+        CreateChatCompletionStreamResponse,
+    ) -> future::Ready<Result<ChatCompletionStreamAccumulator, OpenAIError>>,
+>;
+
+/// Accumulation of chat completion streams, such as [ChatCompletionResponseStream](super::ChatCompletionResponseStream)s.
+///
+/// ```no_run
+/// use async_openai_wasm::{types::{ChatCompletionStreamExt, CreateChatCompletionRequest}, Client};
+///
+/// # async fn example(request: CreateChatCompletionRequest) -> Result<(), async_openai_wasm::error::OpenAIError> {
+/// let client = Client::new();
+/// let response = client
+///     .chat()
+///     .create_stream(request)
+///     .await?
+///     .accumulate()
+///     .await?
+///     .into_response();
+/// # Ok(())
+/// # }
+/// ```
+pub trait ChatCompletionStreamExt:
+    Stream<Item = Result<CreateChatCompletionStreamResponse, OpenAIError>> + Sized
+{
+    /// The stream, keeping the usage of its last chunk, see [ChatCompletionUsageStream]
+    fn track_usage(self) -> ChatCompletionUsageStream<Self> {
+        ChatCompletionUsageStream {
+            stream: self,
+            usage: None,
+        }
+    }
+
+    /// The choices of the chunks of the stream, one by one.
+    ///
+    /// When the request sets `n`, the deltas of its choices are interleaved in the stream,
+    /// for their [ChatChoiceStream::index] to route them, e.g. to one output per choice.
+    fn demultiplex(self) -> ChatChoiceStreamDemultiplexer<Self> {
+        ChatChoiceStreamDemultiplexer {
+            stream: self,
+            buffered: VecDeque::new(),
+        }
+    }
+
+    /// All the chunks of the stream merged, failing on the first error of the stream
+    fn accumulate(self) -> ChatCompletionStreamAccumulation<Self> {
+        self.try_fold(
+            ChatCompletionStreamAccumulator::new(),
+            |mut accumulator, chunk| {
+                accumulator.push(&chunk);
+                future::ready(Ok(accumulator))
+            },
+        )
+    }
+}
+
+impl<S> ChatCompletionStreamExt for S where
+    S: Stream<Item = Result<CreateChatCompletionStreamResponse, OpenAIError>> + Sized
+{
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+type ChunkStream<'c> =
+    futures::stream::BoxStream<'c, Result<CreateChatCompletionStreamResponse, OpenAIError>>;
+#[cfg(target_arch = "wasm32")]
+type ChunkStream<'c> =
+    futures::stream::LocalBoxStream<'c, Result<CreateChatCompletionStreamResponse, OpenAIError>>;
+
+/// Chat completion stream resumed when it fails before its answer is complete,
+/// see [Chat::create_stream_resumable](crate::Chat::create_stream_resumable)
+pub struct ResumableChatCompletionStream<'c> {
+    stream: ChunkStream<'c>,
+}
+
+impl<'c> ResumableChatCompletionStream<'c> {
+    pub(crate) fn new(stream: ChunkStream<'c>) -> Self {
+        Self { stream }
+    }
+}
+
+impl Stream for ResumableChatCompletionStream<'_> {
+    type Item = Result<CreateChatCompletionStreamResponse, OpenAIError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.stream.as_mut().poll_next(cx)
+    }
+}
+
+/// Chat completion stream keeping the usage of the request, which is streamed in a last chunk without choices
+/// when `stream_options.include_usage` is set, see [Chat::create_stream_with_usage](crate::Chat::create_stream_with_usage).
+///
+/// ```no_run
+/// use async_openai_wasm::{types::CreateChatCompletionRequest, Client};
+/// use futures::StreamExt;
+///
+/// # async fn example(request: CreateChatCompletionRequest) -> Result<(), async_openai_wasm::error::OpenAIError> {
+/// let client = Client::new();
+/// let mut stream = client.chat().create_stream_with_usage(request).await?;
+/// while let Some(chunk) = stream.next().await {
+///     print!("{}", chunk?.text());
+/// }
+/// if let Some(usage) = stream.usage() {
+///     println!("\n{} tokens", usage.total_tokens);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[pin_project]
+#[derive(Debug)]
+pub struct ChatCompletionUsageStream<S> {
+    #[pin]
+    stream: S,
+    usage: Option<CompletionUsage>,
+}
+
+impl<S> ChatCompletionUsageStream<S> {
+    /// The usage of the request, once its chunk was streamed
+    pub fn usage(&self) -> Option<&CompletionUsage> {
+        self.usage.as_ref()
+    }
+
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S> Stream for ChatCompletionUsageStream<S>
+where
+    S: Stream<Item = Result<CreateChatCompletionStreamResponse, OpenAIError>>,
+{
+    type Item = Result<CreateChatCompletionStreamResponse, OpenAIError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let poll = this.stream.poll_next(cx);
+        if let Poll::Ready(Some(Ok(chunk))) = &poll {
+            if chunk.usage.is_some() {
+                this.usage.clone_from(&chunk.usage);
+            }
+        }
+        poll
+    }
+}
+
+/// The choices of the chunks of a chat completion stream, see [ChatCompletionStreamExt::demultiplex].
+///
+/// ```no_run
+/// use async_openai_wasm::{types::{ChatCompletionStreamExt, CreateChatCompletionRequest}, Client};
+/// use futures::StreamExt;
+///
+/// # async fn example(request: CreateChatCompletionRequest) -> Result<(), async_openai_wasm::error::OpenAIError> {
+/// let client = Client::new();
+/// // e.g. with `n` set to 3
+/// let mut drafts = vec![String::new(); 3];
+/// let mut choices = client.chat().create_stream(request).await?.demultiplex();
+/// while let Some(choice) = choices.next().await {
+///     let choice = choice?;
+///     if let Some(content) = &choice.delta.content {
+///         drafts[choice.index as usize].push_str(content);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[pin_project]
+#[derive(Debug)]
+pub struct ChatChoiceStreamDemultiplexer<S> {
+    #[pin]
+    stream: S,
+    /// Choices of the last chunk not yielded yet
+    buffered: VecDeque<ChatChoiceStream>,
+}
+
+impl<S> ChatChoiceStreamDemultiplexer<S> {
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S> Stream for ChatChoiceStreamDemultiplexer<S>
+where
+    S: Stream<Item = Result<CreateChatCompletionStreamResponse, OpenAIError>>,
+{
+    type Item = Result<ChatChoiceStream, OpenAIError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            if let Some(choice) = this.buffered.pop_front() {
+                return Poll::Ready(Some(Ok(choice)));
+            }
+            // chunks without choices, e.g. the last one with usage, are skipped
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(chunk))) => this.buffered.extend(chunk.choices),
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
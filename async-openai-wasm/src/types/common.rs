@@ -1,7 +1,20 @@
 use bytes::Bytes;
 
+use crate::{client::OpenAIEventStream, error::OpenAIError};
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum InputSource {
     Bytes { filename: String, bytes: Bytes },
     VecU8 { filename: String, vec: Vec<u8> },
 }
+
+/// Body of a response, streamed as it is received
+#[cfg(not(target_arch = "wasm32"))]
+pub type ByteStream = futures::stream::BoxStream<'static, Result<Bytes, OpenAIError>>;
+
+/// Body of a response, streamed as it is received
+#[cfg(target_arch = "wasm32")]
+pub type ByteStream = futures::stream::LocalBoxStream<'static, Result<Bytes, OpenAIError>>;
+
+/// Data of the server-sent events of a response, as JSON
+pub type JsonEventStream = OpenAIEventStream<serde_json::Value>;
@@ -4,7 +4,8 @@ use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
 use crate::client::OpenAIEventStream;
-use crate::error::OpenAIError;
+use crate::error::{FieldError, OpenAIError};
+use crate::validation::check_range;
 
 use super::{ChatCompletionStreamOptions, Choice, CompletionUsage, Prompt, Stop};
 
@@ -13,7 +14,7 @@ use super::{ChatCompletionStreamOptions, Choice, CompletionUsage, Prompt, Stop};
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateCompletionRequest {
     /// ID of the model to use. You can use the [List models](https://platform.openai.com/docs/api-reference/models/list) API to see all of your available models, or see our [Model overview](https://platform.openai.com/docs/models/overview) for descriptions of them.
     pub model: String,
@@ -115,6 +116,62 @@ pub struct CreateCompletionRequest {
     pub seed: Option<i64>,
 }
 
+impl CreateCompletionRequestArgs {
+    /// Check the sampling parameters are within the ranges the API accepts
+    fn validate(&self) -> Result<(), OpenAIError> {
+        check_range("temperature", self.temperature.flatten(), 0.0, 2.0)?;
+        check_range("top_p", self.top_p.flatten(), 0.0, 1.0)?;
+        check_range(
+            "frequency_penalty",
+            self.frequency_penalty.flatten(),
+            -2.0,
+            2.0,
+        )?;
+        check_range(
+            "presence_penalty",
+            self.presence_penalty.flatten(),
+            -2.0,
+            2.0,
+        )?;
+        check_range("n", self.n.flatten(), 1, 128)?;
+        check_range("logprobs", self.logprobs.flatten(), 0, 5)?;
+        check_range("best_of", self.best_of.flatten(), 0, 20)?;
+        if let Some(best_of) = self.best_of.flatten() {
+            if self.stream.flatten() == Some(true) {
+                return Err(FieldError::new("best_of", "cannot be used with `stream`")
+                    .with_suggestion(
+                        "unset `best_of`, or `stream` to get the best completions at once",
+                    )
+                    .into());
+            }
+            let n = self.n.flatten().unwrap_or(1);
+            if best_of < n {
+                return Err(FieldError::new(
+                    "best_of",
+                    format!("must be at least `n`, got {best_of} < {n}"),
+                )
+                .with_suggestion(format!("raise it to {n}"))
+                .into());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl CreateCompletionResponse {
+    /// The choice generated with `index`, among the `n` of the request, if any
+    pub fn choice(&self, index: u32) -> Option<&Choice> {
+        self.choices.iter().find(|choice| choice.index == index)
+    }
+
+    /// The text of each choice, in the order of their index
+    pub fn choices_text(&self) -> Vec<&str> {
+        let mut choices: Vec<_> = self.choices.iter().collect();
+        choices.sort_by_key(|choice| choice.index);
+        choices.iter().map(|choice| choice.text.as_str()).collect()
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
 pub struct CreateCompletionResponse {
     /// A unique identifier for the completion.
@@ -1,10 +1,12 @@
+use std::ops::Range;
+
 use base64::engine::{Engine, general_purpose};
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
 use crate::error::OpenAIError;
 
-#[derive(Debug, Serialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(untagged)]
 pub enum EmbeddingInput {
     String(String),
@@ -14,7 +16,7 @@ pub enum EmbeddingInput {
     ArrayOfIntegerArray(Vec<Vec<u32>>),
 }
 
-#[derive(Debug, Serialize, Default, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum EncodingFormat {
     #[default]
@@ -22,7 +24,7 @@ pub enum EncodingFormat {
     Base64,
 }
 
-#[derive(Debug, Serialize, Default, Clone, Builder, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
 #[builder(name = "CreateEmbeddingRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
@@ -65,18 +67,39 @@ pub struct Embedding {
     pub embedding: Vec<f32>,
 }
 
+/// Embedding vector as sent by the API when `encoding_format` is `base64`: the little endian bytes
+/// of its floats, in base64. Decoded only when [Base64EmbeddingVector::decode] is called.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct Base64EmbeddingVector(pub String);
 
-impl From<Base64EmbeddingVector> for Vec<f32> {
-    fn from(value: Base64EmbeddingVector) -> Self {
+impl Base64EmbeddingVector {
+    /// The floats of the vector
+    pub fn decode(&self) -> Result<Vec<f32>, OpenAIError> {
         let bytes = general_purpose::STANDARD
-            .decode(value.0)
-            .expect("openai base64 encoding to be valid");
-        let chunks = bytes.chunks_exact(4);
-        chunks
+            .decode(&self.0)
+            .map_err(|e| OpenAIError::InvalidArgument(format!("invalid base64 embedding: {e}")))?;
+        if bytes.len() % 4 != 0 {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "base64 embedding of {} bytes is not a vector of f32",
+                bytes.len()
+            )));
+        }
+        Ok(bytes
+            .chunks_exact(4)
             .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
-            .collect()
+            .collect())
+    }
+
+    /// Number of dimensions of the vector, without decoding it
+    pub fn dimensions(&self) -> usize {
+        let padding = self.0.bytes().rev().take_while(|&byte| byte == b'=').count();
+        (self.0.len() / 4 * 3).saturating_sub(padding) / 4
+    }
+}
+
+impl From<Base64EmbeddingVector> for Vec<f32> {
+    fn from(value: Base64EmbeddingVector) -> Self {
+        value.decode().expect("openai base64 encoding to be valid")
     }
 }
 
@@ -91,6 +114,17 @@ pub struct Base64Embedding {
     pub embedding: Base64EmbeddingVector,
 }
 
+impl Base64Embedding {
+    /// The embedding with its vector decoded
+    pub fn decode(&self) -> Result<Embedding, OpenAIError> {
+        Ok(Embedding {
+            index: self.index,
+            object: self.object.clone(),
+            embedding: self.embedding.decode()?,
+        })
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct EmbeddingUsage {
     /// The number of tokens used by the prompt.
@@ -99,6 +133,17 @@ pub struct EmbeddingUsage {
     pub total_tokens: u32,
 }
 
+/// Embeddings of consecutive inputs of a request, made by [Embeddings::create_chunked_with_sink](crate::Embeddings::create_chunked_with_sink)
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmbeddingChunk {
+    /// Indices of the inputs embedded, in the input of the request
+    pub range: Range<usize>,
+    /// The embeddings of the inputs, in their order and indexed like the inputs of the request
+    pub embeddings: Vec<Embedding>,
+    /// The usage of the request embedding them
+    pub usage: EmbeddingUsage,
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
 pub struct CreateEmbeddingResponse {
     pub object: String,
@@ -120,3 +165,24 @@ pub struct CreateBase64EmbeddingResponse {
     /// The usage information for the request.
     pub usage: EmbeddingUsage,
 }
+
+impl CreateBase64EmbeddingResponse {
+    /// The vectors of the embeddings, decoded one at a time as they are iterated
+    pub fn vectors(&self) -> impl Iterator<Item = Result<Vec<f32>, OpenAIError>> + '_ {
+        self.data.iter().map(|embedding| embedding.embedding.decode())
+    }
+
+    /// The response with all its vectors decoded, as if `encoding_format` was `float`
+    pub fn decode(&self) -> Result<CreateEmbeddingResponse, OpenAIError> {
+        Ok(CreateEmbeddingResponse {
+            object: self.object.clone(),
+            model: self.model.clone(),
+            data: self
+                .data
+                .iter()
+                .map(Base64Embedding::decode)
+                .collect::<Result<_, _>>()?,
+            usage: self.usage.clone(),
+        })
+    }
+}
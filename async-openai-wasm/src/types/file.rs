@@ -39,6 +39,12 @@ pub struct CreateFileRequest {
 pub struct ListFilesResponse {
     pub object: String,
     pub data: Vec<OpenAIFile>,
+    #[serde(default)]
+    pub first_id: Option<String>,
+    #[serde(default)]
+    pub last_id: Option<String>,
+    #[serde(default)]
+    pub has_more: bool,
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
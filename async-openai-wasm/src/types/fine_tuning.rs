@@ -8,14 +8,170 @@ use crate::error::OpenAIError;
 pub enum NEpochs {
     NEpochs(u8),
     #[default]
-    #[serde(rename = "auto")]
+    #[serde(
+        serialize_with = "auto::serialize",
+        deserialize_with = "auto::deserialize"
+    )]
     Auto,
 }
 
+/// Number of examples in each batch, larger batch sizes updating the model parameters less frequently but with lower variance.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(untagged)]
+pub enum BatchSize {
+    BatchSize(u16),
+    #[default]
+    #[serde(
+        serialize_with = "auto::serialize",
+        deserialize_with = "auto::deserialize"
+    )]
+    Auto,
+}
+
+/// Scaling factor for the learning rate, a smaller learning rate being useful to avoid overfitting.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(untagged)]
+pub enum LearningRateMultiplier {
+    LearningRateMultiplier(f32),
+    #[default]
+    #[serde(
+        serialize_with = "auto::serialize",
+        deserialize_with = "auto::deserialize"
+    )]
+    Auto,
+}
+
+/// Weight of the penalty between the policy and reference model of DPO, a higher beta being more conservative.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(untagged)]
+pub enum Beta {
+    Beta(f32),
+    #[default]
+    #[serde(
+        serialize_with = "auto::serialize",
+        deserialize_with = "auto::deserialize"
+    )]
+    Auto,
+}
+
+/// (De)serialization of the `auto` value of hyperparameters
+mod auto {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str("auto")
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<(), D::Error> {
+        match String::deserialize(deserializer)?.as_str() {
+            "auto" => Ok(()),
+            other => Err(de::Error::invalid_value(
+                de::Unexpected::Str(other),
+                &"auto",
+            )),
+        }
+    }
+}
+
+/// Hyperparameters of a fine-tuning job, `auto` when not set.
+///
+/// Deprecated in requests in favor of the hyperparameters of their [FineTuneMethod].
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
 pub struct Hyperparameters {
     /// The number of epochs to train the model for. An epoch refers to one full cycle through the training dataset.
     pub n_epochs: NEpochs,
+    /// Number of examples in each batch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch_size: Option<BatchSize>,
+    /// Scaling factor for the learning rate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub learning_rate_multiplier: Option<LearningRateMultiplier>,
+}
+
+/// The method used for fine-tuning.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum FineTuneMethod {
+    /// Supervised fine-tuning, on examples of the answers the model should give
+    Supervised {
+        supervised: FineTuneSupervisedMethod,
+    },
+    /// [Direct preference optimization](https://platform.openai.com/docs/guides/direct-preference-optimization),
+    /// on pairs of preferred and non-preferred answers
+    Dpo { dpo: FineTuneDpoMethod },
+}
+
+impl FineTuneMethod {
+    /// Supervised fine-tuning with `hyperparameters`
+    pub fn supervised(hyperparameters: FineTuneSupervisedHyperparameters) -> Self {
+        Self::Supervised {
+            supervised: FineTuneSupervisedMethod {
+                hyperparameters: Some(hyperparameters),
+            },
+        }
+    }
+
+    /// Direct preference optimization with `hyperparameters`
+    pub fn dpo(hyperparameters: FineTuneDpoHyperparameters) -> Self {
+        Self::Dpo {
+            dpo: FineTuneDpoMethod {
+                hyperparameters: Some(hyperparameters),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct FineTuneSupervisedMethod {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hyperparameters: Option<FineTuneSupervisedHyperparameters>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct FineTuneDpoMethod {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hyperparameters: Option<FineTuneDpoHyperparameters>,
+}
+
+/// Hyperparameters of supervised fine-tuning, `auto` when not set
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Builder, PartialEq)]
+#[builder(name = "FineTuneSupervisedHyperparametersArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct FineTuneSupervisedHyperparameters {
+    /// Number of examples in each batch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch_size: Option<BatchSize>,
+    /// Scaling factor for the learning rate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub learning_rate_multiplier: Option<LearningRateMultiplier>,
+    /// The number of epochs to train the model for. An epoch refers to one full cycle through the training dataset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n_epochs: Option<NEpochs>,
+}
+
+/// Hyperparameters of direct preference optimization, `auto` when not set
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Builder, PartialEq)]
+#[builder(name = "FineTuneDpoHyperparametersArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct FineTuneDpoHyperparameters {
+    /// Weight of the penalty between the policy and reference model.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beta: Option<Beta>,
+    /// Number of examples in each batch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch_size: Option<BatchSize>,
+    /// Scaling factor for the learning rate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub learning_rate_multiplier: Option<LearningRateMultiplier>,
+    /// The number of epochs to train the model for. An epoch refers to one full cycle through the training dataset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n_epochs: Option<NEpochs>,
 }
 
 #[derive(Debug, Serialize, Clone, Default, Builder, PartialEq)]
@@ -41,8 +197,16 @@ pub struct CreateFineTuningJobRequest {
     pub training_file: String,
 
     /// The hyperparameters used for the fine-tuning job.
+    ///
+    /// Deprecated in favor of the hyperparameters of `method`.
+    #[deprecated]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub hyperparameters: Option<Hyperparameters>,
 
+    /// The method used for fine-tuning, supervised by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<FineTuneMethod>,
+
     /// A string of up to 18 characters that will be added to your fine-tuned model name.
     ///
     /// For example, a `suffix` of "custom-model-name" would produce a model name
@@ -126,11 +290,30 @@ pub enum FineTuningJobStatus {
     ValidatingFiles,
     Queued,
     Running,
+    /// The job was paused with [crate::FineTuning::pause]
+    Paused,
     Succeeded,
     Failed,
     Cancelled,
 }
 
+impl FineTuningJobStatus {
+    /// Whether the job has stopped for good and its status won't change anymore.
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Succeeded | Self::Failed | Self::Cancelled)
+    }
+
+    /// Whether a job in this status can be paused.
+    pub fn can_pause(&self) -> bool {
+        matches!(self, Self::Running)
+    }
+
+    /// Whether a job in this status can be resumed.
+    pub fn can_resume(&self) -> bool {
+        matches!(self, Self::Paused)
+    }
+}
+
 /// The `fine_tuning.job` object represents a fine-tuning job that has been created through the API.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct FineTuningJob {
@@ -151,6 +334,10 @@ pub struct FineTuningJob {
     /// See the [fine-tuning guide](/docs/guides/fine-tuning) for more details.
     pub hyperparameters: Hyperparameters,
 
+    /// The method used for fine-tuning.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<FineTuneMethod>,
+
     ///  The base model that is being fine-tuned.
     pub model: String,
 
@@ -164,7 +351,7 @@ pub struct FineTuningJob {
     pub result_files: Vec<String>,
 
     /// The current status of the fine-tuning job, which can be either
-    /// `validating_files`, `queued`, `running`, `succeeded`, `failed`, or `cancelled`.
+    /// `validating_files`, `queued`, `running`, `paused`, `succeeded`, `failed`, or `cancelled`.
     pub status: FineTuningJobStatus,
 
     /// The total number of billable tokens processed by this fine-tuning job. The value will be null if the fine-tuning job is still running.
@@ -18,6 +18,13 @@ pub enum ImageSize {
     S1792x1024,
     #[serde(rename = "1024x1792")]
     S1024x1792,
+    #[serde(rename = "1536x1024")]
+    S1536x1024,
+    #[serde(rename = "1024x1536")]
+    S1024x1536,
+    /// Picked by `gpt-image-1` from the prompt
+    #[serde(rename = "auto")]
+    Auto,
 }
 
 #[derive(Default, Debug, Serialize, Clone, Copy, PartialEq)]
@@ -47,6 +54,8 @@ pub enum ImageModel {
     DallE2,
     #[serde(rename = "dall-e-3")]
     DallE3,
+    #[serde(rename = "gpt-image-1")]
+    GptImage1,
     #[serde(untagged)]
     Other(String),
 }
@@ -57,6 +66,45 @@ pub enum ImageQuality {
     #[default]
     Standard,
     HD,
+    /// `gpt-image-1` only
+    Low,
+    /// `gpt-image-1` only
+    Medium,
+    /// `gpt-image-1` only
+    High,
+    /// The best quality for the model, the default of `gpt-image-1`
+    Auto,
+}
+
+/// Background of images generated by `gpt-image-1`
+#[derive(Debug, Serialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageBackground {
+    /// Requires an `output_format` of `png` or `webp`
+    Transparent,
+    Opaque,
+    #[default]
+    Auto,
+}
+
+/// Format of images generated by `gpt-image-1`
+#[derive(Debug, Serialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageOutputFormat {
+    #[default]
+    Png,
+    Jpeg,
+    Webp,
+}
+
+/// Content moderation of images generated by `gpt-image-1`
+#[derive(Debug, Serialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageModeration {
+    #[default]
+    Auto,
+    /// Less restrictive filtering
+    Low,
 }
 
 #[derive(Debug, Serialize, Default, Clone, PartialEq)]
@@ -87,16 +135,19 @@ pub struct CreateImageRequest {
     pub n: Option<u8>, // min:1 max:10 default:1
 
     /// The quality of the image that will be generated. `hd` creates images with finer details and greater
-    /// consistency across the image. This param is only supported for `dall-e-3`.
+    /// consistency across the image. `standard` and `hd` are supported for `dall-e-3`,
+    /// `low`, `medium`, `high` and `auto` for `gpt-image-1`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub quality: Option<ImageQuality>,
 
     /// The format in which the generated images are returned. Must be one of `url` or `b64_json`. URLs are only valid for 60 minutes after the image has been generated.
+    /// Not supported for `gpt-image-1`, which always returns `b64_json`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub response_format: Option<ResponseFormat>,
 
     /// The size of the generated images. Must be one of `256x256`, `512x512`, or `1024x1024` for `dall-e-2`.
-    /// Must be one of `1024x1024`, `1792x1024`, or `1024x1792` for `dall-e-3` models.
+    /// Must be one of `1024x1024`, `1792x1024`, or `1024x1792` for `dall-e-3` models,
+    /// and one of `1024x1024`, `1536x1024`, `1024x1536` or `auto` for `gpt-image-1`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub size: Option<ImageSize>,
 
@@ -107,6 +158,23 @@ pub struct CreateImageRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub style: Option<ImageStyle>,
 
+    /// The background of the generated images. Only supported for `gpt-image-1`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<ImageBackground>,
+
+    /// The format of the generated images. Only supported for `gpt-image-1`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_format: Option<ImageOutputFormat>,
+
+    /// The compression level, from 0 to 100%, of images with an `output_format` of `jpeg` or `webp`.
+    /// Only supported for `gpt-image-1`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_compression: Option<u8>,
+
+    /// The content moderation level of the generated images. Only supported for `gpt-image-1`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub moderation: Option<ImageModeration>,
+
     /// A unique identifier representing your end-user, which will help OpenAI to monitor and detect abuse. [Learn more](https://platform.openai.com/docs/usage-policies/end-user-ids).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user: Option<String>,
@@ -131,6 +199,19 @@ pub enum Image {
 pub struct ImagesResponse {
     pub created: u32,
     pub data: Vec<std::sync::Arc<Image>>,
+    /// Token usage of the request, for `gpt-image-1` only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<ImagesUsage>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ImagesUsage {
+    /// The number of tokens, text and image, of the input.
+    pub input_tokens: u32,
+    /// The number of image tokens generated.
+    pub output_tokens: u32,
+    /// The total number of tokens used.
+    pub total_tokens: u32,
 }
 
 #[derive(Debug, Default, Clone, PartialEq)]
@@ -138,6 +219,20 @@ pub struct ImageInput {
     pub source: InputSource,
 }
 
+/// Image or images to edit
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImageEditInput {
+    Image(ImageInput),
+    /// Up to 16 images, for `gpt-image-1` only
+    Images(Vec<ImageInput>),
+}
+
+impl Default for ImageEditInput {
+    fn default() -> Self {
+        Self::Image(Default::default())
+    }
+}
+
 #[derive(Debug, Clone, Default, Builder, PartialEq)]
 #[builder(name = "CreateImageEditRequestArgs")]
 #[builder(pattern = "mutable")]
@@ -146,15 +241,17 @@ pub struct ImageInput {
 #[builder(build_fn(error = "OpenAIError"))]
 pub struct CreateImageEditRequest {
     /// The image to edit. Must be a valid PNG file, less than 4MB, and square. If mask is not provided, image must have transparency, which will be used as the mask.
-    pub image: ImageInput,
+    ///
+    /// For `gpt-image-1`, up to 16 PNG, WEBP or JPG images of less than 25MB each.
+    pub image: ImageEditInput,
 
-    /// A text description of the desired image(s). The maximum length is 1000 characters.
+    /// A text description of the desired image(s). The maximum length is 1000 characters, 32000 for `gpt-image-1`.
     pub prompt: String,
 
     /// An additional image whose fully transparent areas (e.g. where alpha is zero) indicate where `image` should be edited. Must be a valid PNG file, less than 4MB, and have the same dimensions as `image`.
     pub mask: Option<ImageInput>,
 
-    /// The model to use for image generation. Only `dall-e-2` is supported at this time.
+    /// The model to use for image generation. `dall-e-2` and `gpt-image-1` are supported.
     pub model: Option<ImageModel>,
 
     /// The number of images to generate. Must be between 1 and 10.
@@ -164,10 +261,24 @@ pub struct CreateImageEditRequest {
     pub size: Option<DallE2ImageSize>,
 
     /// The format in which the generated images are returned. Must be one of `url` or `b64_json`.
+    /// Not supported for `gpt-image-1`, which always returns `b64_json`.
     pub response_format: Option<ResponseFormat>,
 
     /// A unique identifier representing your end-user, which will help OpenAI to monitor and detect abuse. [Learn more](https://platform.openai.com/docs/usage-policies/end-user-ids).
     pub user: Option<String>,
+
+    /// The quality of the generated images: `low`, `medium`, `high` or `auto`. Only supported for `gpt-image-1`.
+    pub quality: Option<ImageQuality>,
+
+    /// The background of the generated images. Only supported for `gpt-image-1`.
+    pub background: Option<ImageBackground>,
+
+    /// The format of the generated images. Only supported for `gpt-image-1`.
+    pub output_format: Option<ImageOutputFormat>,
+
+    /// The compression level, from 0 to 100%, of images with an `output_format` of `jpeg` or `webp`.
+    /// Only supported for `gpt-image-1`.
+    pub output_compression: Option<u8>,
 }
 
 #[derive(Debug, Default, Clone, Builder, PartialEq)]
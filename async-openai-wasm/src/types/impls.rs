@@ -9,23 +9,28 @@ use crate::{
 };
 
 use super::{
-    AudioInput,
+    AddUploadPartRequest, AttributeValue, AudioInput,
     AudioResponseFormat,
     ChatCompletionFunctionCall, ChatCompletionFunctions,
     ChatCompletionNamedToolChoice, ChatCompletionRequestAssistantMessage,
     ChatCompletionRequestFunctionMessage, ChatCompletionRequestMessage,
     ChatCompletionRequestMessageContentPart, ChatCompletionRequestMessageContentPartImage,
-    ChatCompletionRequestMessageContentPartText, ChatCompletionRequestSystemMessage,
+    ChatCompletionRequestDeveloperMessage, ChatCompletionRequestMessageContentPartAudio,
+    ChatCompletionRequestMessageContentPartFile,
+    ChatCompletionRequestMessageContentPartText,
+    ChatCompletionRequestSystemMessage,
     ChatCompletionRequestToolMessage,
     ChatCompletionRequestUserMessage, ChatCompletionRequestUserMessageContent,
-    ChatCompletionToolChoiceOption,
+    ChatCompletionResponseMessage, ChatCompletionToolChoiceOption,
     CreateFileRequest,
     CreateImageEditRequest, CreateImageVariationRequest, CreateMessageRequestContent,
     CreateTranscriptionRequest, CreateTranslationRequest, DallE2ImageSize,
     EmbeddingInput, FileInput, FilePurpose,
-    FunctionName, ImageInput, ImageModel, ImageSize, ImageUrl, ModerationInput,
-    Prompt, ResponseFormat, Role, Stop,
-    TimestampGranularity,
+    FunctionName, ImageBackground, ImageEditInput, ImageInput, ImageModel, ImageOutputFormat,
+    ImageQuality, ImageSize, ImageUrl, ModerationContentPart, ModerationImageUrl, ModerationInput,
+    Prompt, ResponseFormat, ResponseFunctionCallOutput, ResponseInput, ResponseInputContent,
+    ResponseInputContentPart, ResponseInputItem, ResponseInputMessage, Role, Stop,
+    TimestampGranularity, VectorStoreSearchQuery,
 };
 
 /// for `impl_from!(T, Enum)`, implements
@@ -106,6 +111,31 @@ macro_rules! impl_default {
     };
 }
 
+impl From<Vec<ModerationContentPart>> for ModerationInput {
+    fn from(value: Vec<ModerationContentPart>) -> Self {
+        ModerationInput::MultiModal(value)
+    }
+}
+
+impl<const N: usize> From<[ModerationContentPart; N]> for ModerationInput {
+    fn from(value: [ModerationContentPart; N]) -> Self {
+        ModerationInput::MultiModal(value.to_vec())
+    }
+}
+
+impl ModerationContentPart {
+    pub fn text<S: Into<String>>(text: S) -> Self {
+        ModerationContentPart::Text { text: text.into() }
+    }
+
+    /// Image of `url`, either a URL of the image or the base64 encoded image data
+    pub fn image_url<S: Into<String>>(url: S) -> Self {
+        ModerationContentPart::ImageUrl {
+            image_url: ModerationImageUrl { url: url.into() },
+        }
+    }
+}
+
 impl_default!(Prompt);
 impl_default!(ModerationInput);
 impl_default!(EmbeddingInput);
@@ -138,9 +168,10 @@ macro_rules! impl_input {
                 }
             }
 
+            /// Takes ownership of `vec` without copying it, so that retries do not copy it either
             pub fn from_vec_u8(filename: String, vec: Vec<u8>) -> Self {
                 Self {
-                    source: InputSource::VecU8 { filename, vec },
+                    source: InputSource::Bytes { filename, bytes: vec.into() },
                 }
             }
         }
@@ -162,6 +193,9 @@ impl Display for ImageSize {
                 Self::S1024x1024 => "1024x1024",
                 Self::S1792x1024 => "1792x1024",
                 Self::S1024x1792 => "1024x1792",
+                Self::S1536x1024 => "1536x1024",
+                Self::S1024x1536 => "1024x1536",
+                Self::Auto => "auto",
             }
         )
     }
@@ -189,12 +223,76 @@ impl Display for ImageModel {
             match self {
                 Self::DallE2 => "dall-e-2",
                 Self::DallE3 => "dall-e-3",
+                Self::GptImage1 => "gpt-image-1",
                 Self::Other(other) => other,
             }
         )
     }
 }
 
+impl Display for ImageQuality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Standard => "standard",
+                Self::HD => "hd",
+                Self::Low => "low",
+                Self::Medium => "medium",
+                Self::High => "high",
+                Self::Auto => "auto",
+            }
+        )
+    }
+}
+
+impl Display for ImageBackground {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Transparent => "transparent",
+                Self::Opaque => "opaque",
+                Self::Auto => "auto",
+            }
+        )
+    }
+}
+
+impl Display for ImageOutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Png => "png",
+                Self::Jpeg => "jpeg",
+                Self::Webp => "webp",
+            }
+        )
+    }
+}
+
+impl From<ImageInput> for ImageEditInput {
+    fn from(image: ImageInput) -> Self {
+        Self::Image(image)
+    }
+}
+
+impl From<Vec<ImageInput>> for ImageEditInput {
+    fn from(images: Vec<ImageInput>) -> Self {
+        Self::Images(images)
+    }
+}
+
+impl<const N: usize> From<[ImageInput; N]> for ImageEditInput {
+    fn from(images: [ImageInput; N]) -> Self {
+        Self::Images(images.into())
+    }
+}
+
 impl Display for ResponseFormat {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -244,6 +342,7 @@ impl Display for Role {
             "{}",
             match self {
                 Role::User => "user",
+                Role::Developer => "developer",
                 Role::System => "system",
                 Role::Assistant => "assistant",
                 Role::Function => "function",
@@ -442,6 +541,7 @@ impl From<&str> for ChatCompletionToolChoiceOption {
         match value {
             "auto" => Self::Auto,
             "none" => Self::None,
+            "required" => Self::Required,
             _ => Self::Named(value.into()),
         }
     }
@@ -452,11 +552,22 @@ impl From<String> for ChatCompletionToolChoiceOption {
         match value.as_str() {
             "auto" => Self::Auto,
             "none" => Self::None,
+            "required" => Self::Required,
             _ => Self::Named(value.into()),
         }
     }
 }
 
+impl From<ChatCompletionFunctionCall> for ChatCompletionToolChoiceOption {
+    fn from(value: ChatCompletionFunctionCall) -> Self {
+        match value {
+            ChatCompletionFunctionCall::None => Self::None,
+            ChatCompletionFunctionCall::Auto => Self::Auto,
+            ChatCompletionFunctionCall::Function { name } => Self::Named(name.into()),
+        }
+    }
+}
+
 impl From<(String, serde_json::Value)> for ChatCompletionFunctions {
     fn from(value: (String, serde_json::Value)) -> Self {
         Self {
@@ -473,6 +584,12 @@ impl From<ChatCompletionRequestUserMessage> for ChatCompletionRequestMessage {
     }
 }
 
+impl From<ChatCompletionRequestDeveloperMessage> for ChatCompletionRequestMessage {
+    fn from(value: ChatCompletionRequestDeveloperMessage) -> Self {
+        Self::Developer(value)
+    }
+}
+
 impl From<ChatCompletionRequestSystemMessage> for ChatCompletionRequestMessage {
     fn from(value: ChatCompletionRequestSystemMessage) -> Self {
         Self::System(value)
@@ -497,6 +614,19 @@ impl From<ChatCompletionRequestToolMessage> for ChatCompletionRequestMessage {
     }
 }
 
+/// The assistant message to send back a response message in the conversation
+impl From<ChatCompletionResponseMessage> for ChatCompletionRequestAssistantMessage {
+    #[allow(deprecated)]
+    fn from(value: ChatCompletionResponseMessage) -> Self {
+        Self {
+            content: value.content,
+            name: None,
+            tool_calls: value.tool_calls,
+            function_call: value.function_call,
+        }
+    }
+}
+
 impl From<&str> for ChatCompletionRequestUserMessageContent {
     fn from(value: &str) -> Self {
         ChatCompletionRequestUserMessageContent::Text(value.into())
@@ -531,6 +661,22 @@ for ChatCompletionRequestMessageContentPart
     }
 }
 
+impl From<ChatCompletionRequestMessageContentPartAudio>
+for ChatCompletionRequestMessageContentPart
+{
+    fn from(value: ChatCompletionRequestMessageContentPartAudio) -> Self {
+        ChatCompletionRequestMessageContentPart::InputAudio(value)
+    }
+}
+
+impl From<ChatCompletionRequestMessageContentPartFile>
+for ChatCompletionRequestMessageContentPart
+{
+    fn from(value: ChatCompletionRequestMessageContentPartFile) -> Self {
+        ChatCompletionRequestMessageContentPart::File(value)
+    }
+}
+
 impl From<&str> for ChatCompletionRequestMessageContentPartText {
     fn from(value: &str) -> Self {
         ChatCompletionRequestMessageContentPartText { text: value.into() }
@@ -585,6 +731,110 @@ impl Default for CreateMessageRequestContent {
     }
 }
 
+impl From<&str> for AttributeValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.into())
+    }
+}
+
+impl From<String> for AttributeValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<bool> for AttributeValue {
+    fn from(value: bool) -> Self {
+        Self::Boolean(value)
+    }
+}
+
+macro_rules! impl_from_number_for_attribute_value {
+    ($($from_typ:ty),*) => {
+        $(
+            impl From<$from_typ> for AttributeValue {
+                fn from(value: $from_typ) -> Self {
+                    Self::Number(value.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_number_for_attribute_value!(f64, f32, i32, u32, i16, u16, i8, u8);
+
+impl From<&str> for VectorStoreSearchQuery {
+    fn from(value: &str) -> Self {
+        Self::Text(value.into())
+    }
+}
+
+impl From<String> for VectorStoreSearchQuery {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<Vec<String>> for VectorStoreSearchQuery {
+    fn from(value: Vec<String>) -> Self {
+        Self::Array(value)
+    }
+}
+
+impl Default for VectorStoreSearchQuery {
+    fn default() -> Self {
+        Self::Text("".into())
+    }
+}
+
+impl From<&str> for ResponseInput {
+    fn from(value: &str) -> Self {
+        ResponseInput::Text(value.into())
+    }
+}
+
+impl From<String> for ResponseInput {
+    fn from(value: String) -> Self {
+        ResponseInput::Text(value)
+    }
+}
+
+impl From<Vec<ResponseInputItem>> for ResponseInput {
+    fn from(value: Vec<ResponseInputItem>) -> Self {
+        ResponseInput::Items(value)
+    }
+}
+
+impl From<&str> for ResponseInputContent {
+    fn from(value: &str) -> Self {
+        ResponseInputContent::Text(value.into())
+    }
+}
+
+impl From<String> for ResponseInputContent {
+    fn from(value: String) -> Self {
+        ResponseInputContent::Text(value)
+    }
+}
+
+impl From<Vec<ResponseInputContentPart>> for ResponseInputContent {
+    fn from(value: Vec<ResponseInputContentPart>) -> Self {
+        ResponseInputContent::Parts(value)
+    }
+}
+
+impl From<ResponseInputMessage> for ResponseInputItem {
+    fn from(value: ResponseInputMessage) -> Self {
+        ResponseInputItem::Message(value)
+    }
+}
+
+impl From<ResponseFunctionCallOutput> for ResponseInputItem {
+    fn from(value: ResponseFunctionCallOutput) -> Self {
+        ResponseInputItem::FunctionCallOutput(value)
+    }
+}
+
 // start: types to multipart from
 
 #[async_convert::async_trait]
@@ -655,11 +905,18 @@ impl async_convert::TryFrom<CreateImageEditRequest> for reqwest::multipart::Form
     type Error = OpenAIError;
 
     async fn try_from(request: CreateImageEditRequest) -> Result<Self, Self::Error> {
-        let image_part = create_file_part(request.image.source).await?;
-
-        let mut form = reqwest::multipart::Form::new()
-            .part("image", image_part)
-            .text("prompt", request.prompt);
+        let mut form = reqwest::multipart::Form::new();
+        match request.image {
+            ImageEditInput::Image(image) => {
+                form = form.part("image", create_file_part(image.source).await?);
+            }
+            ImageEditInput::Images(images) => {
+                for image in images {
+                    form = form.part("image[]", create_file_part(image.source).await?);
+                }
+            }
+        }
+        form = form.text("prompt", request.prompt);
 
         if let Some(mask) = request.mask {
             let mask_part = create_file_part(mask.source).await?;
@@ -688,6 +945,22 @@ impl async_convert::TryFrom<CreateImageEditRequest> for reqwest::multipart::Form
         if request.user.is_some() {
             form = form.text("user", request.user.unwrap())
         }
+
+        if let Some(quality) = request.quality {
+            form = form.text("quality", quality.to_string())
+        }
+
+        if let Some(background) = request.background {
+            form = form.text("background", background.to_string())
+        }
+
+        if let Some(output_format) = request.output_format {
+            form = form.text("output_format", output_format.to_string())
+        }
+
+        if let Some(output_compression) = request.output_compression {
+            form = form.text("output_compression", output_compression.to_string())
+        }
         Ok(form)
     }
 }
@@ -740,4 +1013,15 @@ impl async_convert::TryFrom<CreateFileRequest> for reqwest::multipart::Form {
     }
 }
 
+#[async_convert::async_trait]
+impl async_convert::TryFrom<AddUploadPartRequest> for reqwest::multipart::Form {
+    type Error = OpenAIError;
+
+    async fn try_from(request: AddUploadPartRequest) -> Result<Self, Self::Error> {
+        let data_part = create_file_part(request.data).await?;
+        let form = reqwest::multipart::Form::new().part("data", data_part);
+        Ok(form)
+    }
+}
+
 // end: types to multipart form
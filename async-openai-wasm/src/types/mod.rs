@@ -2,12 +2,14 @@
 //! These types are created from component schemas in the [OpenAPI spec](https://github.com/openai/openai-openapi)
 use derive_builder::UninitializedFieldError;
 
+pub use admin::*;
 pub use assistant::*;
 pub use assistant_file::*;
 pub use assistant_stream::*;
 pub use audio::*;
 pub use batch::*;
 pub use chat::*;
+pub use chat_stream::*;
 pub use common::*;
 pub use completion::*;
 pub use embedding::*;
@@ -18,13 +20,21 @@ pub use message::*;
 pub use message_file::*;
 pub use model::*;
 pub use moderation::*;
+pub use responses::*;
 pub use run::*;
 pub use step::*;
 pub use thread::*;
+pub use typestate::*;
+pub use upload::*;
+pub use user_message::*;
 pub use vector_store::*;
 
-use crate::error::OpenAIError;
+#[cfg(feature = "tokens")]
+pub use crate::tokens::token_count;
 
+use crate::error::{FieldError, OpenAIError};
+
+mod admin;
 mod assistant;
 mod assistant_file;
 mod assistant_impls;
@@ -32,6 +42,7 @@ mod assistant_stream;
 mod audio;
 mod batch;
 mod chat;
+mod chat_stream;
 mod common;
 mod completion;
 mod embedding;
@@ -43,15 +54,21 @@ mod message;
 mod message_file;
 mod model;
 mod moderation;
+mod responses;
 mod run;
 mod step;
 mod thread;
+mod typestate;
+mod upload;
+mod user_message;
 mod vector_store;
 
 mod impls;
 
 impl From<UninitializedFieldError> for OpenAIError {
     fn from(value: UninitializedFieldError) -> Self {
-        OpenAIError::InvalidArgument(value.to_string())
+        FieldError::new(value.field_name(), "is required but not set")
+            .with_suggestion(format!("set it with the `{}` method of the builder", value.field_name()))
+            .into()
     }
 }
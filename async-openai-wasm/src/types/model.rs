@@ -13,6 +13,13 @@ pub struct Model {
     pub owned_by: String,
 }
 
+impl Model {
+    /// Whether this is a model created by a fine-tuning job, i.e. its id has the `ft:` prefix.
+    pub fn is_fine_tuned(&self) -> bool {
+        self.id.starts_with("ft:")
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
 pub struct ListModelResponse {
     pub object: String,
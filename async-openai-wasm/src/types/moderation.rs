@@ -8,6 +8,22 @@ use crate::error::OpenAIError;
 pub enum ModerationInput {
     String(String),
     StringArray(Vec<String>),
+    /// Text and images, classified together, for the `omni-moderation` models only
+    MultiModal(Vec<ModerationContentPart>),
+}
+
+/// Part of a [ModerationInput::MultiModal] input
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ModerationContentPart {
+    Text { text: String },
+    ImageUrl { image_url: ModerationImageUrl },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ModerationImageUrl {
+    /// Either a URL of the image or the base64 encoded image data.
+    pub url: String,
 }
 
 #[derive(Debug, Serialize, Default, Clone, Copy, PartialEq)]
@@ -17,6 +33,11 @@ pub enum TextModerationModel {
     Latest,
     #[serde(rename = "text-moderation-stable")]
     Stable,
+    /// Model classifying text and images, with more categories
+    #[serde(rename = "omni-moderation-latest")]
+    OmniLatest,
+    #[serde(rename = "omni-moderation-2024-09-26")]
+    Omni20240926,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Builder, PartialEq)]
@@ -26,11 +47,13 @@ pub enum TextModerationModel {
 #[builder(derive(Debug))]
 #[builder(build_fn(error = "OpenAIError"))]
 pub struct CreateModerationRequest {
-    /// The input text to classify
+    /// The input text to classify, or text and images with the `omni-moderation` models
     pub input: ModerationInput,
 
     /// Two content moderations models are available: `text-moderation-stable` and `text-moderation-latest`.
     ///
+    /// The `omni-moderation` models also classify images, in [ModerationInput::MultiModal] inputs.
+    ///
     /// The default is `text-moderation-latest` which will be automatically upgraded over time. This ensures you are always using our most accurate model. If you use `text-moderation-stable`, we will provide advanced notice before updating the model. Accuracy of `text-moderation-stable` may be slightly lower than for `text-moderation-latest`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub model: Option<TextModerationModel>,
@@ -72,6 +95,18 @@ pub struct Category {
     /// Content that depicts death, violence, or physical injury in graphic detail.
     #[serde(rename = "violence/graphic")]
     pub violence_graphic: bool,
+    /// Content that includes instructions or advice that facilitate the planning or execution of wrongdoing,
+    /// or that gives advice or instruction on how to commit illicit acts. Only classified by the `omni-moderation` models.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub illicit: Option<bool>,
+    /// Illicit content that also includes references to violence or procuring a weapon.
+    /// Only classified by the `omni-moderation` models.
+    #[serde(
+        rename = "illicit/violent",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub illicit_violent: Option<bool>,
 }
 
 /// A list of the categories along with their scores as predicted by model.
@@ -106,6 +141,51 @@ pub struct CategoryScore {
     /// The score for the category 'violence/graphic'.
     #[serde(rename = "violence/graphic")]
     pub violence_graphic: f32,
+    /// The score for the category 'illicit'.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub illicit: Option<f32>,
+    /// The score for the category 'illicit/violent'.
+    #[serde(
+        rename = "illicit/violent",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub illicit_violent: Option<f32>,
+}
+
+/// Type of input a category was classified on
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ModerationInputType {
+    Text,
+    Image,
+}
+
+/// The input types each category was classified on, e.g. only text for 'hate' but text and images for 'violence'.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct CategoryAppliedInputTypes {
+    pub hate: Vec<ModerationInputType>,
+    #[serde(rename = "hate/threatening")]
+    pub hate_threatening: Vec<ModerationInputType>,
+    pub harassment: Vec<ModerationInputType>,
+    #[serde(rename = "harassment/threatening")]
+    pub harassment_threatening: Vec<ModerationInputType>,
+    #[serde(rename = "self-harm")]
+    pub self_harm: Vec<ModerationInputType>,
+    #[serde(rename = "self-harm/intent")]
+    pub self_harm_intent: Vec<ModerationInputType>,
+    #[serde(rename = "self-harm/instructions")]
+    pub self_harm_instructions: Vec<ModerationInputType>,
+    pub sexual: Vec<ModerationInputType>,
+    #[serde(rename = "sexual/minors")]
+    pub sexual_minors: Vec<ModerationInputType>,
+    pub violence: Vec<ModerationInputType>,
+    #[serde(rename = "violence/graphic")]
+    pub violence_graphic: Vec<ModerationInputType>,
+    #[serde(default)]
+    pub illicit: Vec<ModerationInputType>,
+    #[serde(rename = "illicit/violent", default)]
+    pub illicit_violent: Vec<ModerationInputType>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
@@ -116,6 +196,9 @@ pub struct ContentModerationResult {
     pub categories: Category,
     /// A list of the categories along with their scores as predicted by model.
     pub category_scores: CategoryScore,
+    /// The input types each category was classified on, returned by the `omni-moderation` models.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category_applied_input_types: Option<CategoryAppliedInputTypes>,
 }
 
 /// Represents if a given text input is potentially harmful.
@@ -0,0 +1,719 @@
+use std::collections::HashMap;
+
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use crate::client::OpenAIEventStream;
+use crate::error::OpenAIError;
+
+use super::{Filter, FunctionObject, ImageDetail, VectorStoreSearchRankingOptions};
+
+#[derive(Clone, Serialize, Default, Debug, Deserialize, Builder, PartialEq)]
+#[builder(name = "CreateResponseRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct CreateResponseRequest {
+    /// ID of the model to use, e.g. `gpt-4o` or `o3`.
+    pub model: String,
+
+    /// Text, image, or file inputs to the model, used to generate a response.
+    pub input: ResponseInput,
+
+    /// Inserts a system (or developer) message as the first item in the model's context.
+    ///
+    /// When used along with `previous_response_id`, the instructions from a previous response will not be carried over to the next response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+
+    /// An array of tools the model may call while generating a response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<ResponseTool>>,
+
+    /// How the model should select which tool (or tools) to use when generating a response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<ResponseToolChoice>,
+
+    /// Whether to allow the model to run tool calls in parallel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parallel_tool_calls: Option<bool>,
+
+    /// The unique ID of the previous response to the model. Use this to create multi-turn conversations without resending the whole conversation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_response_id: Option<String>,
+
+    /// Configuration options for reasoning models (o-series models only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning: Option<ReasoningConfig>,
+
+    /// Configuration options for a text response from the model. Can be plain text or structured JSON data.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<ResponseTextConfig>,
+
+    /// An upper bound for the number of tokens that can be generated for a response, including visible output tokens and reasoning tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u32>,
+
+    /// What sampling temperature to use, between 0 and 2.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>, // min: 0, max: 2, default: 1
+
+    /// An alternative to sampling with temperature, called nucleus sampling.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>, // min: 0, max: 1, default: 1
+
+    /// The truncation strategy to use for the model response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncation: Option<ResponseTruncation>,
+
+    /// Additional output data to include in the model response, e.g. `file_search_call.results`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include: Option<Vec<String>>,
+
+    /// Whether to store the generated model response for later retrieval via API. Defaults to true.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub store: Option<bool>,
+
+    /// If set, model response data will be streamed to the client as it is generated using [server-sent events](https://developer.mozilla.org/en-US/docs/Web/API/Server-sent_events/Using_server-sent_events#Event_stream_format).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+
+    /// Set of 16 key-value pairs that can be attached to an object.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+
+    /// A unique identifier representing your end-user, which can help OpenAI to monitor and detect abuse.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum ResponseInput {
+    /// A text input to the model, equivalent to a text input with the `user` role.
+    Text(String),
+    /// A list of one or many input items to the model, containing different content types.
+    Items(Vec<ResponseInputItem>),
+}
+
+impl Default for ResponseInput {
+    fn default() -> Self {
+        ResponseInput::Text("".into())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseInputItem {
+    Message(ResponseInputMessage),
+    /// A function tool call of a previous response, passed back together with its output.
+    FunctionCall(ResponseFunctionToolCall),
+    /// The output of a function tool call.
+    FunctionCallOutput(ResponseFunctionCallOutput),
+    /// A reasoning item of a previous response.
+    Reasoning(ResponseReasoningItem),
+    /// An internal identifier for an item to reference.
+    ItemReference {
+        id: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ResponseInputMessageArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ResponseInputMessage {
+    pub role: ResponseRole,
+    pub content: ResponseInputContent,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ResponseRole {
+    #[default]
+    User,
+    Assistant,
+    System,
+    Developer,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum ResponseInputContent {
+    Text(String),
+    Parts(Vec<ResponseInputContentPart>),
+}
+
+impl Default for ResponseInputContent {
+    fn default() -> Self {
+        ResponseInputContent::Text("".into())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseInputContentPart {
+    InputText {
+        text: String,
+    },
+    InputImage {
+        /// The URL of the image, or the base64 encoded image data as a data url.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        image_url: Option<String>,
+        /// The ID of an uploaded file.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        file_id: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        detail: Option<ImageDetail>,
+    },
+    InputFile {
+        /// The ID of an uploaded file.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        file_id: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        filename: Option<String>,
+        /// The content of the file as a base64 data url.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        file_data: Option<String>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct ResponseFunctionCallOutput {
+    /// The ID of the function tool call generated by the model.
+    pub call_id: String,
+    /// A JSON string of the output of the function tool call.
+    pub output: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseTool {
+    Function(FunctionObject),
+    FileSearch(ResponseFileSearchTool),
+    WebSearchPreview(ResponseWebSearchTool),
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ResponseFileSearchToolArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ResponseFileSearchTool {
+    /// The IDs of the vector stores to search.
+    pub vector_store_ids: Vec<String>,
+    /// The maximum number of results to return. This number should be between 1 and 50 inclusive.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_num_results: Option<u8>,
+    /// A filter to apply based on file attributes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filters: Option<Filter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ranking_options: Option<VectorStoreSearchRankingOptions>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ResponseWebSearchToolArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ResponseWebSearchTool {
+    /// High level guidance for the amount of context window space to use for the search.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_context_size: Option<WebSearchContextSize>,
+    /// Approximate location of the user, used to refine search results.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_location: Option<WebSearchUserLocation>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum WebSearchContextSize {
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct WebSearchUserLocation {
+    /// The type of location approximation. Always `approximate`.
+    pub r#type: String,
+    /// Two-letter [ISO country code](https://en.wikipedia.org/wiki/ISO_3166-1) of the user, e.g. `US`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    /// The [IANA timezone](https://timeapi.io/documentation/iana-timezones) of the user, e.g. `America/Los_Angeles`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum ResponseToolChoice {
+    Mode(ResponseToolChoiceMode),
+    /// Force the model to call a specific tool.
+    Tool(ResponseNamedToolChoice),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ResponseToolChoiceMode {
+    /// The model will not call any tool and instead generates a message.
+    None,
+    /// The model can pick between generating a message or calling one or more tools.
+    Auto,
+    /// The model must call one or more tools.
+    Required,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseNamedToolChoice {
+    Function { name: String },
+    FileSearch,
+    WebSearchPreview,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct ReasoningConfig {
+    /// Constrains effort on reasoning for reasoning models.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effort: Option<ReasoningEffort>,
+    /// A summary of the reasoning performed by the model, useful for debugging and understanding the model's reasoning process.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<ReasoningSummary>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReasoningEffort {
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReasoningSummary {
+    Auto,
+    Concise,
+    Detailed,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct ResponseTextConfig {
+    pub format: ResponseTextFormat,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseTextFormat {
+    #[default]
+    Text,
+    JsonObject,
+    JsonSchema {
+        /// The name of the response format. Must be a-z, A-Z, 0-9, or contain underscores and dashes, with a maximum length of 64.
+        name: String,
+        /// The schema for the response format, described as a JSON Schema object.
+        schema: serde_json::Value,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        /// Whether to enable strict schema adherence when generating the output.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        strict: Option<bool>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ResponseTruncation {
+    /// If the context of this response and previous ones exceeds the model's context window size, the model will truncate the response to fit the context window by dropping input items in the middle of the conversation.
+    Auto,
+    /// If a model response will exceed the context window size for a model, the request will fail with a 400 error.
+    Disabled,
+}
+
+/// A model response, as returned by [Responses::create](crate::Responses::create).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Response {
+    /// Unique identifier for this Response.
+    pub id: String,
+    /// The object type of this resource, always set to `response`.
+    pub object: String,
+    /// Unix timestamp (in seconds) of when this Response was created.
+    pub created_at: u64,
+    pub status: ResponseStatus,
+    pub model: String,
+    /// An array of content items generated by the model.
+    ///
+    /// The order and length of the items depend on the model's response, use [Response::output_text] to get all the text output.
+    pub output: Vec<ResponseOutputItem>,
+    /// An error object returned when the model fails to generate a Response.
+    pub error: Option<ResponseError>,
+    /// Details about why the response is incomplete.
+    pub incomplete_details: Option<ResponseIncompleteDetails>,
+    pub instructions: Option<String>,
+    pub max_output_tokens: Option<u32>,
+    pub parallel_tool_calls: Option<bool>,
+    pub previous_response_id: Option<String>,
+    pub reasoning: Option<ReasoningConfig>,
+    pub temperature: Option<f32>,
+    pub top_p: Option<f32>,
+    pub text: Option<ResponseTextConfig>,
+    pub tool_choice: Option<ResponseToolChoice>,
+    #[serde(default)]
+    pub tools: Vec<ResponseTool>,
+    pub truncation: Option<ResponseTruncation>,
+    pub usage: Option<ResponseUsage>,
+    pub user: Option<String>,
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+impl Response {
+    /// Concatenation of all the `output_text` content of the output messages.
+    pub fn output_text(&self) -> String {
+        self.output
+            .iter()
+            .filter_map(|item| match item {
+                ResponseOutputItem::Message(message) => Some(message),
+                _ => None,
+            })
+            .flat_map(|message| message.content.iter())
+            .filter_map(|content| match content {
+                ResponseOutputContent::OutputText { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The function tool calls the model wants the caller to make.
+    pub fn function_calls(&self) -> impl Iterator<Item = &ResponseFunctionToolCall> {
+        self.output.iter().filter_map(|item| match item {
+            ResponseOutputItem::FunctionCall(call) => Some(call),
+            _ => None,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseStatus {
+    Completed,
+    Failed,
+    InProgress,
+    Incomplete,
+    Queued,
+    Cancelled,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseError {
+    pub code: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseIncompleteDetails {
+    /// The reason why the response is incomplete, e.g. `max_output_tokens` or `content_filter`.
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseOutputItem {
+    Message(ResponseOutputMessage),
+    FunctionCall(ResponseFunctionToolCall),
+    FileSearchCall(ResponseFileSearchToolCall),
+    WebSearchCall(ResponseWebSearchToolCall),
+    Reasoning(ResponseReasoningItem),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseOutputMessage {
+    pub id: String,
+    /// The role of the output message. Always `assistant`.
+    pub role: ResponseRole,
+    pub status: ResponseItemStatus,
+    pub content: Vec<ResponseOutputContent>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseItemStatus {
+    InProgress,
+    Completed,
+    Incomplete,
+    Searching,
+    Failed,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseOutputContent {
+    OutputText {
+        text: String,
+        #[serde(default)]
+        annotations: Vec<ResponseAnnotation>,
+    },
+    Refusal {
+        refusal: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseAnnotation {
+    /// A citation to a file found by the file search tool.
+    FileCitation { file_id: String, index: u32 },
+    /// A citation for a web resource found by the web search tool.
+    UrlCitation {
+        url: String,
+        title: String,
+        start_index: u32,
+        end_index: u32,
+    },
+    /// A path to a file.
+    FilePath { file_id: String, index: u32 },
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct ResponseFunctionToolCall {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// The unique ID of the function tool call generated by the model, to be referenced by [ResponseFunctionCallOutput::call_id].
+    pub call_id: String,
+    pub name: String,
+    /// A JSON string of the arguments to pass to the function.
+    pub arguments: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<ResponseItemStatus>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseFileSearchToolCall {
+    pub id: String,
+    pub status: ResponseItemStatus,
+    /// The queries used to search for files.
+    #[serde(default)]
+    pub queries: Vec<String>,
+    /// The results of the file search tool call, only present when `file_search_call.results` is included.
+    pub results: Option<Vec<ResponseFileSearchResult>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseFileSearchResult {
+    pub file_id: String,
+    pub filename: String,
+    pub score: f32,
+    pub text: String,
+    pub attributes: Option<HashMap<String, serde_json::Value>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseWebSearchToolCall {
+    pub id: String,
+    pub status: ResponseItemStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct ResponseReasoningItem {
+    pub id: String,
+    /// Reasoning text contents.
+    pub summary: Vec<ResponseReasoningSummaryPart>,
+    /// The encrypted content of the reasoning item, only present when `reasoning.encrypted_content` is included.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encrypted_content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<ResponseItemStatus>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseReasoningSummaryPart {
+    SummaryText { text: String },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseUsage {
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+    pub total_tokens: u32,
+    pub input_tokens_details: Option<ResponseInputTokensDetails>,
+    pub output_tokens_details: Option<ResponseOutputTokensDetails>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseInputTokensDetails {
+    /// The number of tokens that were retrieved from the cache.
+    pub cached_tokens: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseOutputTokensDetails {
+    pub reasoning_tokens: u32,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct DeleteResponseResponse {
+    pub id: String,
+    pub object: String,
+    pub deleted: bool,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ListResponseInputItemsResponse {
+    pub object: String,
+    pub data: Vec<ResponseInputItem>,
+    pub first_id: Option<String>,
+    pub last_id: Option<String>,
+    pub has_more: bool,
+}
+
+/// Events emitted by [Responses::create_stream](crate::Responses::create_stream).
+///
+/// The stream ends after one of [ResponseStreamEvent::Completed], [ResponseStreamEvent::Failed], [ResponseStreamEvent::Incomplete] or [ResponseStreamEvent::Error].
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum ResponseStreamEvent {
+    #[serde(rename = "response.created")]
+    Created { response: Response },
+    #[serde(rename = "response.in_progress")]
+    InProgress { response: Response },
+    #[serde(rename = "response.completed")]
+    Completed { response: Response },
+    #[serde(rename = "response.failed")]
+    Failed { response: Response },
+    #[serde(rename = "response.incomplete")]
+    Incomplete { response: Response },
+    #[serde(rename = "response.output_item.added")]
+    OutputItemAdded {
+        output_index: u32,
+        item: ResponseOutputItem,
+    },
+    #[serde(rename = "response.output_item.done")]
+    OutputItemDone {
+        output_index: u32,
+        item: ResponseOutputItem,
+    },
+    #[serde(rename = "response.content_part.added")]
+    ContentPartAdded {
+        item_id: String,
+        output_index: u32,
+        content_index: u32,
+        part: ResponseOutputContent,
+    },
+    #[serde(rename = "response.content_part.done")]
+    ContentPartDone {
+        item_id: String,
+        output_index: u32,
+        content_index: u32,
+        part: ResponseOutputContent,
+    },
+    #[serde(rename = "response.output_text.delta")]
+    OutputTextDelta {
+        item_id: String,
+        output_index: u32,
+        content_index: u32,
+        delta: String,
+    },
+    #[serde(rename = "response.output_text.annotation.added")]
+    OutputTextAnnotationAdded {
+        item_id: String,
+        output_index: u32,
+        content_index: u32,
+        annotation_index: u32,
+        annotation: ResponseAnnotation,
+    },
+    #[serde(rename = "response.output_text.done")]
+    OutputTextDone {
+        item_id: String,
+        output_index: u32,
+        content_index: u32,
+        text: String,
+    },
+    #[serde(rename = "response.refusal.delta")]
+    RefusalDelta {
+        item_id: String,
+        output_index: u32,
+        content_index: u32,
+        delta: String,
+    },
+    #[serde(rename = "response.refusal.done")]
+    RefusalDone {
+        item_id: String,
+        output_index: u32,
+        content_index: u32,
+        refusal: String,
+    },
+    #[serde(rename = "response.function_call_arguments.delta")]
+    FunctionCallArgumentsDelta {
+        item_id: String,
+        output_index: u32,
+        delta: String,
+    },
+    #[serde(rename = "response.function_call_arguments.done")]
+    FunctionCallArgumentsDone {
+        item_id: String,
+        output_index: u32,
+        arguments: String,
+    },
+    #[serde(rename = "response.file_search_call.in_progress")]
+    FileSearchCallInProgress { item_id: String, output_index: u32 },
+    #[serde(rename = "response.file_search_call.searching")]
+    FileSearchCallSearching { item_id: String, output_index: u32 },
+    #[serde(rename = "response.file_search_call.completed")]
+    FileSearchCallCompleted { item_id: String, output_index: u32 },
+    #[serde(rename = "response.web_search_call.in_progress")]
+    WebSearchCallInProgress { item_id: String, output_index: u32 },
+    #[serde(rename = "response.web_search_call.searching")]
+    WebSearchCallSearching { item_id: String, output_index: u32 },
+    #[serde(rename = "response.web_search_call.completed")]
+    WebSearchCallCompleted { item_id: String, output_index: u32 },
+    #[serde(rename = "response.reasoning_summary_text.delta")]
+    ReasoningSummaryTextDelta {
+        item_id: String,
+        output_index: u32,
+        summary_index: u32,
+        delta: String,
+    },
+    #[serde(rename = "response.reasoning_summary_text.done")]
+    ReasoningSummaryTextDone {
+        item_id: String,
+        output_index: u32,
+        summary_index: u32,
+        text: String,
+    },
+    #[serde(rename = "error")]
+    Error {
+        code: Option<String>,
+        message: String,
+        param: Option<String>,
+    },
+    /// An event type not known to this version of the library.
+    #[serde(other)]
+    Unknown,
+}
+
+impl ResponseStreamEvent {
+    /// Whether no more events follow this one.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            ResponseStreamEvent::Completed { .. }
+                | ResponseStreamEvent::Failed { .. }
+                | ResponseStreamEvent::Incomplete { .. }
+                | ResponseStreamEvent::Error { .. }
+        )
+    }
+}
+
+/// Parsed server side events stream until the response is completed.
+pub type ResponseStream = OpenAIEventStream<ResponseStreamEvent>;
@@ -152,6 +152,16 @@ pub struct RunToolCallObject {
     pub function: FunctionCall,
 }
 
+/// Tool calls a run requires outputs for, as persisted in a [SessionStore](crate::session::SessionStore)
+/// by [Runs::create_with_tools](crate::Runs::create_with_tools) until the outputs are submitted.
+#[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+pub struct PendingToolCalls {
+    pub run_id: String,
+    pub tool_calls: Vec<RunToolCallObject>,
+    /// Outputs of the tool calls, once all of them were made, to submit without calling the tools again
+    pub tool_outputs: Option<Vec<ToolsOutputs>>,
+}
+
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
 pub struct LastError {
     /// One of `server_error`, `rate_limit_exceeded`, or `invalid_prompt`.
@@ -168,7 +178,7 @@ pub enum LastErrorCode {
     InvalidPrompt,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+#[derive(Clone, Serialize, Debug, Deserialize, PartialEq, Default)]
 pub struct RunCompletionUsage {
     /// Number of completion tokens used over the course of the run.
     pub completion_tokens: u32,
@@ -178,6 +188,24 @@ pub struct RunCompletionUsage {
     pub total_tokens: u32,
 }
 
+impl std::ops::AddAssign<&RunCompletionUsage> for RunCompletionUsage {
+    fn add_assign(&mut self, other: &RunCompletionUsage) {
+        self.completion_tokens += other.completion_tokens;
+        self.prompt_tokens += other.prompt_tokens;
+        self.total_tokens += other.total_tokens;
+    }
+}
+
+impl<'a> std::iter::Sum<&'a RunCompletionUsage> for RunCompletionUsage {
+    fn sum<I: Iterator<Item = &'a RunCompletionUsage>>(usages: I) -> Self {
+        let mut total = Self::default();
+        for usage in usages {
+            total += usage;
+        }
+        total
+    }
+}
+
 #[derive(Clone, Serialize, Default, Debug, Deserialize, Builder, PartialEq)]
 #[builder(name = "CreateRunRequestArgs")]
 #[builder(pattern = "mutable")]
@@ -60,7 +60,7 @@ pub struct RunStepObject {
     pub usage: Option<RunStepCompletionUsage>,
 }
 
-#[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+#[derive(Clone, Serialize, Debug, Deserialize, PartialEq, Default)]
 pub struct RunStepCompletionUsage {
     /// Number of completion tokens used over the course of the run step.
     pub completion_tokens: u32,
@@ -0,0 +1,132 @@
+//! Builders checking at compile time that the required fields of a request are set,
+//! instead of failing at runtime with an uninitialized field error like the `*Args` builders.
+//!
+//! Optional fields are set on the underlying `*Args` builder with `with`.
+use std::marker::PhantomData;
+
+use super::{
+    ChatCompletionRequestMessage, CreateChatCompletionRequest, CreateChatCompletionRequestArgs,
+    CreateCompletionRequest, CreateCompletionRequestArgs, CreateEmbeddingRequest,
+    CreateEmbeddingRequestArgs, CreateResponseRequest, CreateResponseRequestArgs, EmbeddingInput,
+    Prompt, ResponseInput,
+};
+
+/// Marks a required field which is not set yet
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Unset;
+
+/// Marks a required field which is set
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IsSet;
+
+macro_rules! typed_builder {
+    ($(#[$doc:meta])* $builder:ident, $request:ident, $args:ident, $field:ident: $field_type:ty) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone)]
+        pub struct $builder<Model = Unset, Field = Unset> {
+            args: $args,
+            _state: PhantomData<(Model, Field)>,
+        }
+
+        impl $request {
+            /// Builder which only compiles `build` once the required fields are set
+            pub fn typed_builder() -> $builder {
+                $builder {
+                    args: Default::default(),
+                    _state: PhantomData,
+                }
+            }
+        }
+
+        impl<Field> $builder<Unset, Field> {
+            /// ID of the model to use.
+            pub fn model<S: Into<String>>(mut self, model: S) -> $builder<IsSet, Field> {
+                self.args.model(model);
+                $builder {
+                    args: self.args,
+                    _state: PhantomData,
+                }
+            }
+        }
+
+        impl<Model> $builder<Model, Unset> {
+            pub fn $field<V: Into<$field_type>>(mut self, value: V) -> $builder<Model, IsSet> {
+                self.args.$field(value.into());
+                $builder {
+                    args: self.args,
+                    _state: PhantomData,
+                }
+            }
+        }
+
+        impl $builder<IsSet, IsSet> {
+            pub fn build(self) -> $request {
+                self.args
+                    .build()
+                    .expect("all fields without a default are set")
+            }
+        }
+
+        impl<Model, Field> $builder<Model, Field> {
+            /// Set optional fields on the underlying `*Args` builder
+            pub fn with(mut self, f: impl FnOnce(&mut $args) -> &mut $args) -> Self {
+                f(&mut self.args);
+                self
+            }
+        }
+    };
+}
+
+typed_builder!(
+    /// Typestate builder of [CreateChatCompletionRequest], requiring `model` and `messages`.
+    ///
+    /// ```
+    /// use async_openai_wasm::types::{ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequest};
+    ///
+    /// let request = CreateChatCompletionRequest::typed_builder()
+    ///     .model("gpt-4o")
+    ///     .messages([ChatCompletionRequestUserMessageArgs::default()
+    ///         .content("Hello!")
+    ///         .build()
+    ///         .unwrap()
+    ///         .into()])
+    ///     .with(|args| args.temperature(0.5).max_tokens(256_u16))
+    ///     .build();
+    /// ```
+    ///
+    /// Forgetting a required field does not compile:
+    ///
+    /// ```compile_fail
+    /// use async_openai_wasm::types::CreateChatCompletionRequest;
+    ///
+    /// let request = CreateChatCompletionRequest::typed_builder().model("gpt-4o").build();
+    /// ```
+    ChatCompletionRequestBuilder,
+    CreateChatCompletionRequest,
+    CreateChatCompletionRequestArgs,
+    messages: Vec<ChatCompletionRequestMessage>
+);
+
+typed_builder!(
+    /// Typestate builder of [CreateCompletionRequest], requiring `model` and `prompt`.
+    CompletionRequestBuilder,
+    CreateCompletionRequest,
+    CreateCompletionRequestArgs,
+    prompt: Prompt
+);
+
+typed_builder!(
+    /// Typestate builder of [CreateEmbeddingRequest], requiring `model` and `input`.
+    EmbeddingRequestBuilder,
+    CreateEmbeddingRequest,
+    CreateEmbeddingRequestArgs,
+    input: EmbeddingInput
+);
+
+typed_builder!(
+    /// Typestate builder of [CreateResponseRequest], requiring `model` and `input`.
+    ResponseRequestBuilder,
+    CreateResponseRequest,
+    CreateResponseRequestArgs,
+    input: ResponseInput
+);
@@ -0,0 +1,115 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use crate::error::OpenAIError;
+
+use super::{InputSource, OpenAIFile};
+
+/// Largest part accepted by [AddUploadPartRequest], 64 MB
+pub const MAX_UPLOAD_PART_SIZE: usize = 64 * 1024 * 1024;
+
+#[derive(Debug, Serialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "CreateUploadRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct CreateUploadRequest {
+    /// The name of the file to upload.
+    pub filename: String,
+
+    /// The intended purpose of the uploaded file.
+    ///
+    /// See the [documentation on File purposes](https://platform.openai.com/docs/api-reference/files/create#files-create-purpose).
+    pub purpose: UploadPurpose,
+
+    /// The number of bytes in the file you are uploading.
+    pub bytes: u64,
+
+    /// The MIME type of the file.
+    ///
+    /// This must fall within the supported MIME types for your file purpose. See the supported MIME types for assistants and vision.
+    pub mime_type: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum UploadPurpose {
+    Assistants,
+    Batch,
+    #[default]
+    #[serde(rename = "fine-tune")]
+    FineTune,
+    Vision,
+}
+
+/// The Upload object can accept byte chunks in the form of Parts.
+#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+pub struct Upload {
+    /// The Upload unique identifier, which can be referenced in API endpoints
+    pub id: String,
+    /// The Unix timestamp (in seconds) for when the Upload was created
+    pub created_at: u32,
+    /// The name of the file to be uploaded
+    pub filename: String,
+    /// The intended number of bytes to be uploaded
+    pub bytes: u64,
+    /// The intended purpose of the file.
+    pub purpose: UploadPurpose,
+    /// The status of the Upload.
+    pub status: UploadStatus,
+    /// The Unix timestamp (in seconds) for when the Upload will expire
+    pub expires_at: u32,
+    /// The object type, which is always "upload"
+    pub object: String,
+    /// The ready File object after the Upload is completed.
+    pub file: Option<OpenAIFile>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UploadStatus {
+    Pending,
+    Completed,
+    Cancelled,
+    Expired,
+}
+
+#[derive(Debug, Default, Clone, Builder, PartialEq)]
+#[builder(name = "AddUploadPartRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct AddUploadPartRequest {
+    /// The chunk of bytes for this Part, of up to 64 MB.
+    pub data: InputSource,
+}
+
+/// The upload Part represents a chunk of bytes we can add to an Upload object.
+#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+pub struct UploadPart {
+    /// The upload Part unique identifier, which can be referenced in API endpoints
+    pub id: String,
+    /// The Unix timestamp (in seconds) for when the Part was created
+    pub created_at: u32,
+    /// The ID of the Upload object that this Part was added to
+    pub upload_id: String,
+    /// The object type, which is always `upload.part`
+    pub object: String,
+}
+
+#[derive(Debug, Serialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "CompleteUploadRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct CompleteUploadRequest {
+    /// The ordered list of Part IDs.
+    pub part_ids: Vec<String>,
+
+    /// The optional md5 checksum for the file contents to verify if the bytes uploaded matches what you expect.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub md5: Option<String>,
+}
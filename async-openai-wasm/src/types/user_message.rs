@@ -0,0 +1,117 @@
+use base64::engine::{general_purpose, Engine};
+
+use crate::image_info::ImageFormat;
+
+use super::{
+    ChatCompletionRequestMessage, ChatCompletionRequestMessageContentPart,
+    ChatCompletionRequestMessageContentPartImage, ChatCompletionRequestUserMessage,
+    ChatCompletionRequestUserMessageContent, ImageUrl,
+};
+
+/// Fluent builder of user messages mixing text and images, in the order their parts are added.
+///
+/// Messages without images are sent as plain text, their text parts joined by new lines.
+///
+/// ```
+/// use async_openai_wasm::types::{ChatCompletionRequestMessage, UserMessageBuilder};
+///
+/// let message: ChatCompletionRequestMessage = UserMessageBuilder::new()
+///     .text("What is the difference between these pictures?")
+///     .image_url("https://example.com/before.png")
+///     .image_bytes(b"\x89PNG\r\n\x1a\n...")
+///     .into();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UserMessageBuilder {
+    parts: Vec<ChatCompletionRequestMessageContentPart>,
+    name: Option<String>,
+}
+
+impl UserMessageBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a text part
+    pub fn text<S: Into<String>>(mut self, text: S) -> Self {
+        self.parts
+            .push(ChatCompletionRequestMessageContentPart::Text(
+                text.into().into(),
+            ));
+        self
+    }
+
+    /// Add an image, with its detail if set, e.g. one made by `vision::PreparedImage::image_url`
+    pub fn image<I: Into<ImageUrl>>(mut self, image: I) -> Self {
+        self.parts
+            .push(ChatCompletionRequestMessageContentPart::ImageUrl(
+                ChatCompletionRequestMessageContentPartImage {
+                    image_url: image.into(),
+                },
+            ));
+        self
+    }
+
+    /// Add an image by its URL, or its data URL
+    pub fn image_url<S: Into<String>>(self, url: S) -> Self {
+        self.image(ImageUrl {
+            url: url.into(),
+            detail: None,
+        })
+    }
+
+    /// Add an image from the bytes of a PNG, JPEG, GIF or WebP file, sent as a base64 data URL.
+    ///
+    /// The image is sent as is; see `vision::prepare_image`, behind the `vision` feature, to fit it within the limits of the provider first.
+    pub fn image_bytes<B: AsRef<[u8]>>(self, bytes: B) -> Self {
+        let bytes = bytes.as_ref();
+        let url = format!(
+            "data:{};base64,{}",
+            ImageFormat::detect(bytes).map_or("image/png", |format| format.mime_type()),
+            general_purpose::STANDARD.encode(bytes)
+        );
+        self.image_url(url)
+    }
+
+    /// Name of the participant, to differentiate between participants of the same role
+    pub fn name<S: Into<String>>(mut self, name: S) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn build(self) -> ChatCompletionRequestUserMessage {
+        let has_images = self
+            .parts
+            .iter()
+            .any(|part| !matches!(part, ChatCompletionRequestMessageContentPart::Text(_)));
+        let content = if has_images {
+            ChatCompletionRequestUserMessageContent::Array(self.parts)
+        } else {
+            let texts: Vec<String> = self
+                .parts
+                .into_iter()
+                .filter_map(|part| match part {
+                    ChatCompletionRequestMessageContentPart::Text(text) => Some(text.text),
+                    _ => None,
+                })
+                .collect();
+            ChatCompletionRequestUserMessageContent::Text(texts.join("\n"))
+        };
+        ChatCompletionRequestUserMessage {
+            content,
+            name: self.name,
+        }
+    }
+}
+
+impl From<UserMessageBuilder> for ChatCompletionRequestUserMessage {
+    fn from(value: UserMessageBuilder) -> Self {
+        value.build()
+    }
+}
+
+impl From<UserMessageBuilder> for ChatCompletionRequestMessage {
+    fn from(value: UserMessageBuilder) -> Self {
+        ChatCompletionRequestMessage::User(value.build())
+    }
+}
@@ -36,12 +36,25 @@ pub struct CreateVectorStoreRequest {
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
 #[serde(tag = "type")]
+#[serde(rename_all = "lowercase")]
 pub enum VectorStoreChunkingStrategy {
     /// The default strategy. This strategy currently uses a `max_chunk_size_tokens` of `800` and `chunk_overlap_tokens` of `400`.
     #[default]
     Auto,
-    ///
-    Static(StaticChunkingStrategy),
+    /// Chunk files with a fixed chunk size and overlap.
+    Static { r#static: StaticChunkingStrategy },
+}
+
+impl VectorStoreChunkingStrategy {
+    /// Static chunking strategy, see [StaticChunkingStrategy::new].
+    pub fn fixed(
+        max_chunk_size_tokens: u16,
+        chunk_overlap_tokens: u16,
+    ) -> Result<Self, OpenAIError> {
+        Ok(Self::Static {
+            r#static: StaticChunkingStrategy::new(max_chunk_size_tokens, chunk_overlap_tokens)?,
+        })
+    }
 }
 
 /// Vector store expiration policy
@@ -53,6 +66,21 @@ pub struct VectorStoreExpirationAfter {
     pub days: u16, // min: 1, max: 365
 }
 
+impl VectorStoreExpirationAfter {
+    /// Expire the vector store `days` after it was last active, validated against the limits of the API.
+    pub fn last_active(days: u16) -> Result<Self, OpenAIError> {
+        if !(1..=365).contains(&days) {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "expiration days must be between 1 and 365, got {days}"
+            )));
+        }
+        Ok(Self {
+            anchor: "last_active_at".into(),
+            days,
+        })
+    }
+}
+
 /// A vector store is a collection of processed files can be used by the `file_search` tool.
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
 pub struct VectorStoreObject {
@@ -79,6 +107,16 @@ pub struct VectorStoreObject {
     pub metadata: Option<HashMap<String, serde_json::Value>>,
 }
 
+impl VectorStoreObject {
+    /// Seconds left before the vector store expires, relative to `now` (a Unix timestamp in seconds).
+    ///
+    /// `None` if the vector store has no expiration policy.
+    pub fn expires_in(&self, now: u32) -> Option<i64> {
+        self.expires_at
+            .map(|expires_at| i64::from(expires_at) - i64::from(now))
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum VectorStoreStatus {
@@ -105,8 +143,8 @@ pub struct VectorStoreFileCounts {
 pub struct ListVectorStoresResponse {
     pub object: String,
     pub data: Vec<VectorStoreObject>,
-    pub first_id: String,
-    pub last_id: String,
+    pub first_id: Option<String>,
+    pub last_id: Option<String>,
     pub has_more: bool,
 }
 
@@ -136,8 +174,8 @@ pub struct UpdateVectorStoreRequest {
 pub struct ListVectorStoreFilesResponse {
     pub object: String,
     pub data: Vec<VectorStoreFileObject>,
-    pub first_id: String,
-    pub last_id: String,
+    pub first_id: Option<String>,
+    pub last_id: Option<String>,
     pub has_more: bool,
 }
 
@@ -159,6 +197,8 @@ pub struct VectorStoreFileObject {
     pub last_error: Option<VectorStoreFileError>,
     /// The strategy used to chunk the file.
     pub chunking_strategy: Option<VectorStoreFileObjectChunkingStrategy>,
+    /// Set of 16 key-value pairs attached to the file.
+    pub attributes: Option<HashMap<String, AttributeValue>>,
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
@@ -206,7 +246,12 @@ pub enum VectorStoreFileObjectChunkingStrategy {
 pub struct CreateVectorStoreFileRequest {
     /// A [File](https://platform.openai.com/docs/api-reference/files) ID that the vector store should use. Useful for tools like `file_search` that can access files.
     pub file_id: String,
+    /// The chunking strategy used to chunk the file. If not set, will use the `auto` strategy.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub chunking_strategy: Option<VectorStoreChunkingStrategy>,
+    /// Set of 16 key-value pairs that can be attached to the file, which can be filtered on with a [Filter].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attributes: Option<HashMap<String, AttributeValue>>,
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
@@ -225,6 +270,8 @@ pub struct DeleteVectorStoreFileResponse {
 pub struct CreateVectorStoreFileBatchRequest {
     /// A list of [File](https://platform.openai.com/docs/api-reference/files) IDs that the vector store should use. Useful for tools like `file_search` that can access files.
     pub file_ids: Vec<String>, // minItems: 1, maxItems: 500
+    /// The chunking strategy used to chunk the files. If not set, will use the `auto` strategy.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub chunking_strategy: Option<VectorStoreChunkingStrategy>,
 }
 
@@ -266,3 +313,304 @@ pub struct VectorStoreFileBatchObject {
     pub status: VectorStoreFileBatchStatus,
     pub file_counts: VectorStoreFileBatchCounts,
 }
+
+/// The value of a file attribute, or the value an attribute is compared against in a [Filter].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum AttributeValue {
+    String(String),
+    Number(f64),
+    Boolean(bool),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum ComparisonFilterValue {
+    Scalar(AttributeValue),
+    /// Only used with the `in` and `nin` operators
+    Array(Vec<AttributeValue>),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ComparisonType {
+    /// equals
+    Eq,
+    /// not equal
+    Ne,
+    /// greater than
+    Gt,
+    /// greater than or equal
+    Gte,
+    /// less than
+    Lt,
+    /// less than or equal
+    Lte,
+    /// in the given array
+    In,
+    /// not in the given array
+    Nin,
+}
+
+/// A filter used to compare a specified attribute key to a given value using a defined comparison operation.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ComparisonFilter {
+    /// Specifies the comparison operator.
+    pub r#type: ComparisonType,
+    /// The key to compare against the value.
+    pub key: String,
+    /// The value to compare against the attribute key.
+    pub value: ComparisonFilterValue,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CompoundType {
+    And,
+    Or,
+}
+
+/// Combine multiple filters using `and` or `or`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CompoundFilter {
+    /// Type of operation.
+    pub r#type: CompoundType,
+    /// Array of filters to combine.
+    pub filters: Vec<Filter>,
+}
+
+/// A filter to apply based on file attributes.
+///
+/// Use the constructors to build well-formed filters:
+/// ```
+/// use async_openai_wasm::types::Filter;
+///
+/// let filter = Filter::and([
+///     Filter::eq("author", "Jane Doe"),
+///     Filter::or([Filter::gte("year", 2020), Filter::is_in("genre", ["fiction", "poetry"])]),
+/// ]);
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum Filter {
+    Comparison(ComparisonFilter),
+    Compound(CompoundFilter),
+}
+
+impl Filter {
+    fn comparison<K: Into<String>>(
+        r#type: ComparisonType,
+        key: K,
+        value: ComparisonFilterValue,
+    ) -> Self {
+        Self::Comparison(ComparisonFilter {
+            r#type,
+            key: key.into(),
+            value,
+        })
+    }
+
+    fn compound<I: IntoIterator<Item = Filter>>(r#type: CompoundType, filters: I) -> Self {
+        Self::Compound(CompoundFilter {
+            r#type,
+            filters: filters.into_iter().collect(),
+        })
+    }
+
+    /// Attribute `key` equals `value`
+    pub fn eq<K: Into<String>, V: Into<AttributeValue>>(key: K, value: V) -> Self {
+        Self::comparison(
+            ComparisonType::Eq,
+            key,
+            ComparisonFilterValue::Scalar(value.into()),
+        )
+    }
+
+    /// Attribute `key` does not equal `value`
+    pub fn ne<K: Into<String>, V: Into<AttributeValue>>(key: K, value: V) -> Self {
+        Self::comparison(
+            ComparisonType::Ne,
+            key,
+            ComparisonFilterValue::Scalar(value.into()),
+        )
+    }
+
+    /// Attribute `key` is greater than `value`
+    pub fn gt<K: Into<String>, V: Into<f64>>(key: K, value: V) -> Self {
+        Self::comparison(
+            ComparisonType::Gt,
+            key,
+            ComparisonFilterValue::Scalar(AttributeValue::Number(value.into())),
+        )
+    }
+
+    /// Attribute `key` is greater than or equal to `value`
+    pub fn gte<K: Into<String>, V: Into<f64>>(key: K, value: V) -> Self {
+        Self::comparison(
+            ComparisonType::Gte,
+            key,
+            ComparisonFilterValue::Scalar(AttributeValue::Number(value.into())),
+        )
+    }
+
+    /// Attribute `key` is less than `value`
+    pub fn lt<K: Into<String>, V: Into<f64>>(key: K, value: V) -> Self {
+        Self::comparison(
+            ComparisonType::Lt,
+            key,
+            ComparisonFilterValue::Scalar(AttributeValue::Number(value.into())),
+        )
+    }
+
+    /// Attribute `key` is less than or equal to `value`
+    pub fn lte<K: Into<String>, V: Into<f64>>(key: K, value: V) -> Self {
+        Self::comparison(
+            ComparisonType::Lte,
+            key,
+            ComparisonFilterValue::Scalar(AttributeValue::Number(value.into())),
+        )
+    }
+
+    /// Attribute `key` is one of `values` (the `in` operator)
+    pub fn is_in<K, I, V>(key: K, values: I) -> Self
+    where
+        K: Into<String>,
+        I: IntoIterator<Item = V>,
+        V: Into<AttributeValue>,
+    {
+        Self::comparison(
+            ComparisonType::In,
+            key,
+            ComparisonFilterValue::Array(values.into_iter().map(Into::into).collect()),
+        )
+    }
+
+    /// Attribute `key` is none of `values` (the `nin` operator)
+    pub fn not_in<K, I, V>(key: K, values: I) -> Self
+    where
+        K: Into<String>,
+        I: IntoIterator<Item = V>,
+        V: Into<AttributeValue>,
+    {
+        Self::comparison(
+            ComparisonType::Nin,
+            key,
+            ComparisonFilterValue::Array(values.into_iter().map(Into::into).collect()),
+        )
+    }
+
+    /// All of `filters` match
+    pub fn and<I: IntoIterator<Item = Filter>>(filters: I) -> Self {
+        Self::compound(CompoundType::And, filters)
+    }
+
+    /// Any of `filters` matches
+    pub fn or<I: IntoIterator<Item = Filter>>(filters: I) -> Self {
+        Self::compound(CompoundType::Or, filters)
+    }
+
+    /// Checks the constraints the constructors can't express in types:
+    /// compound filters must not be empty, and attribute keys must not be empty.
+    pub fn validate(&self) -> Result<(), OpenAIError> {
+        match self {
+            Self::Comparison(comparison) => {
+                if comparison.key.is_empty() {
+                    return Err(OpenAIError::InvalidArgument(
+                        "filter attribute key must not be empty".into(),
+                    ));
+                }
+                let is_array_operator =
+                    matches!(comparison.r#type, ComparisonType::In | ComparisonType::Nin);
+                let is_array = matches!(comparison.value, ComparisonFilterValue::Array(_));
+                if is_array_operator != is_array {
+                    return Err(OpenAIError::InvalidArgument(format!(
+                        "filter on `{}`: only `in` and `nin` compare against an array",
+                        comparison.key
+                    )));
+                }
+                Ok(())
+            }
+            Self::Compound(compound) => {
+                if compound.filters.is_empty() {
+                    return Err(OpenAIError::InvalidArgument(
+                        "compound filter must contain at least one filter".into(),
+                    ));
+                }
+                compound.filters.iter().try_for_each(Filter::validate)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum VectorStoreSearchQuery {
+    Text(String),
+    Array(Vec<String>),
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct VectorStoreSearchRankingOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ranker: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score_threshold: Option<f32>,
+}
+
+#[derive(Debug, Serialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "VectorStoreSearchRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct VectorStoreSearchRequest {
+    /// A query string for a search.
+    pub query: VectorStoreSearchQuery,
+    /// Whether to rewrite the natural language query for vector search.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rewrite_query: Option<bool>,
+    /// The maximum number of results to return. This number should be between 1 and 50 inclusive.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_num_results: Option<u8>,
+    /// A filter to apply based on file attributes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filters: Option<Filter>,
+    /// Ranking options for search.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ranking_options: Option<VectorStoreSearchRankingOptions>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+pub struct VectorStoreSearchResultContent {
+    /// The type of content, which is always `text`.
+    pub r#type: String,
+    /// The text content returned from search.
+    pub text: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+pub struct VectorStoreSearchResultItem {
+    /// The ID of the vector store file.
+    pub file_id: String,
+    /// The name of the vector store file.
+    pub filename: String,
+    /// The similarity score for the result.
+    pub score: f32,
+    /// Set of 16 key-value pairs that can be attached to an object.
+    pub attributes: Option<HashMap<String, AttributeValue>>,
+    /// Content chunks from the file.
+    pub content: Vec<VectorStoreSearchResultContent>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+pub struct VectorStoreSearchResultsPage {
+    /// The object type, which is always `vector_store.search_results.page`
+    pub object: String,
+    pub search_query: Vec<String>,
+    /// The list of search result items.
+    pub data: Vec<VectorStoreSearchResultItem>,
+    /// Indicates if there are more results to fetch.
+    pub has_more: bool,
+    /// The token for the next page, if any.
+    pub next_page: Option<String>,
+}
@@ -0,0 +1,145 @@
+use bytes::Bytes;
+use futures::{StreamExt, TryStreamExt};
+
+use crate::{
+    checksum,
+    config::Config,
+    error::OpenAIError,
+    types::{
+        AddUploadPartRequest, CompleteUploadRequest, CreateUploadRequest, InputSource, Upload,
+        UploadPart, UploadPurpose, MAX_UPLOAD_PART_SIZE,
+    },
+    Client,
+};
+
+/// Number of parts [Uploads::upload_file_chunked] sends at once
+const UPLOAD_PART_CONCURRENCY: usize = 4;
+
+/// Allows you to upload large files in multiple parts.
+pub struct Uploads<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Uploads<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    /// Creates an intermediate [Upload] object that you can add [Parts](UploadPart) to. Currently, an Upload can accept at most 8 GB in total and expires after an hour after you create it.
+    ///
+    /// Once you complete the Upload, we will create a [File](crate::types::OpenAIFile) object that contains all the parts you uploaded. This File is usable in the rest of our platform as a regular File object.
+    pub async fn create(&self, request: CreateUploadRequest) -> Result<Upload, OpenAIError> {
+        self.client.post("/uploads", request).await
+    }
+
+    /// Adds a [Part](UploadPart) to an [Upload] object. A Part represents a chunk of bytes from the file you are trying to upload.
+    ///
+    /// Each Part can be at most 64 MB, and you can add Parts until you hit the Upload maximum of 8 GB.
+    ///
+    /// It is possible to add multiple Parts in parallel. You can decide the intended order of the Parts when you [complete the Upload](Uploads::complete).
+    pub async fn add_part(
+        &self,
+        upload_id: &str,
+        request: AddUploadPartRequest,
+    ) -> Result<UploadPart, OpenAIError> {
+        self.client
+            .post_form(&format!("/uploads/{upload_id}/parts"), request)
+            .await
+    }
+
+    /// Completes the [Upload].
+    ///
+    /// Within the returned Upload object, there is a nested [File](crate::types::OpenAIFile) object that is ready to use in the rest of the platform.
+    ///
+    /// You can specify the order of the Parts by passing in an ordered list of the Part IDs.
+    ///
+    /// The number of bytes uploaded upon completion must match the number of bytes initially specified when creating the Upload object. No Parts may be added after an Upload is completed.
+    pub async fn complete(
+        &self,
+        upload_id: &str,
+        request: CompleteUploadRequest,
+    ) -> Result<Upload, OpenAIError> {
+        self.client
+            .post(&format!("/uploads/{upload_id}/complete"), request)
+            .await
+    }
+
+    /// Cancels the Upload. No Parts may be added after an Upload is cancelled.
+    pub async fn cancel(&self, upload_id: &str) -> Result<Upload, OpenAIError> {
+        self.client
+            .post(
+                &format!("/uploads/{upload_id}/cancel"),
+                serde_json::json!({}),
+            )
+            .await
+    }
+
+    /// Upload a file larger than [Files::create](crate::Files::create) accepts, in parts of `part_size` bytes
+    /// sent a few at a time, and complete the upload with the MD5 checksum of the file.
+    ///
+    /// The upload is cancelled if a part fails to be added, or the [CancellationToken](crate::cancel::CancellationToken)
+    /// of the client is cancelled while parts are added.
+    pub async fn upload_file_chunked(
+        &self,
+        source: InputSource,
+        purpose: UploadPurpose,
+        mime_type: &str,
+        part_size: usize,
+    ) -> Result<Upload, OpenAIError> {
+        if part_size == 0 || part_size > MAX_UPLOAD_PART_SIZE {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "part_size must be between 1 and {MAX_UPLOAD_PART_SIZE} bytes"
+            )));
+        }
+        let (filename, bytes) = match source {
+            InputSource::Bytes { filename, bytes } => (filename, bytes),
+            InputSource::VecU8 { filename, vec } => (filename, Bytes::from(vec)),
+        };
+
+        let upload = self
+            .create(CreateUploadRequest {
+                filename: filename.clone(),
+                purpose,
+                bytes: bytes.len() as u64,
+                mime_type: mime_type.to_string(),
+            })
+            .await?;
+
+        let parts = (0..bytes.len()).step_by(part_size).map(|start| {
+            let data = bytes.slice(start..(start + part_size).min(bytes.len()));
+            self.add_part(
+                &upload.id,
+                AddUploadPartRequest {
+                    data: InputSource::Bytes {
+                        filename: filename.clone(),
+                        bytes: data,
+                    },
+                },
+            )
+        });
+        let parts: Vec<UploadPart> = match futures::stream::iter(parts)
+            .buffered(UPLOAD_PART_CONCURRENCY)
+            .try_collect()
+            .await
+        {
+            Ok(parts) => parts,
+            Err(e) => {
+                // the upload is cancelled even when the parts were, by the token of the client
+                let client = self.client.uncancellable();
+                if let Err(cancel_error) = Uploads::new(&client).cancel(&upload.id).await {
+                    tracing::warn!("failed to cancel upload {}: {cancel_error}", upload.id);
+                }
+                return Err(e);
+            }
+        };
+
+        self.complete(
+            &upload.id,
+            CompleteUploadRequest {
+                part_ids: parts.into_iter().map(|part| part.id).collect(),
+                md5: Some(checksum::md5_hex(&bytes)),
+            },
+        )
+        .await
+    }
+}
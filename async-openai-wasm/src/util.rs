@@ -1,22 +1,30 @@
+use bytes::Bytes;
 use reqwest::Body;
 
 use crate::error::OpenAIError;
 use crate::types::InputSource;
 
+/// Size of the chunks file parts are streamed in
+#[cfg(not(target_arch = "wasm32"))]
+const FILE_CHUNK_SIZE: usize = 64 * 1024;
+
 /// Creates the part for the given file for multipart upload.
+///
+/// The file is streamed in chunks of its bytes rather than copied into the request body,
+/// except on wasm32 where `fetch` needs the whole body.
 pub(crate) async fn create_file_part(
     source: InputSource,
 ) -> Result<reqwest::multipart::Part, OpenAIError> {
-    let (stream, file_name) = match source {
+    let (bytes, file_name) = match source {
         InputSource::Bytes { filename, bytes } => {
-            (Body::from(bytes), filename)
+            (bytes, filename)
         }
         InputSource::VecU8 { filename, vec } => {
-            (Body::from(vec), filename)
+            (Bytes::from(vec), filename)
         }
     };
 
-    let file_part = reqwest::multipart::Part::stream(stream)
+    let file_part = file_part(bytes)
         .file_name(file_name)
         .mime_str("application/octet-stream")
         .unwrap();
@@ -24,3 +32,20 @@ pub(crate) async fn create_file_part(
     Ok(file_part)
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+fn file_part(bytes: Bytes) -> reqwest::multipart::Part {
+    let length = bytes.len() as u64;
+    let chunks = (0..bytes.len())
+        .step_by(FILE_CHUNK_SIZE)
+        .map(move |start| {
+            let end = (start + FILE_CHUNK_SIZE).min(bytes.len());
+            Ok::<_, std::convert::Infallible>(bytes.slice(start..end))
+        });
+    let body = Body::wrap_stream(futures::stream::iter(chunks));
+    reqwest::multipart::Part::stream_with_length(body, length)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn file_part(bytes: Bytes) -> reqwest::multipart::Part {
+    reqwest::multipart::Part::stream(Body::from(bytes))
+}
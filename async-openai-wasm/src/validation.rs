@@ -0,0 +1,214 @@
+//! Client side validation of requests against provider limits.
+//!
+//! Some providers, Azure OpenAI Service in particular, reject requests exceeding their limits
+//! with vague error messages. [RequestLimits] catches those requests before they are sent,
+//! with an error pointing at what to change.
+//!
+//! A [Config](crate::config::Config) can provide the limits of its provider, like
+//! [AzureConfig](crate::config::AzureConfig) does for the images of its `api-version`; they can also be set on
+//! the client, or turned off with [Client::without_request_limits](crate::Client::without_request_limits):
+//!
+//! ```
+//! use async_openai_wasm::{Client, validation::RequestLimits};
+//!
+//! let client = Client::new().with_request_limits(RequestLimits::azure());
+//! ```
+use std::fmt::Display;
+
+use crate::error::{FieldError, OpenAIError};
+use crate::types::{
+    ChatCompletionRequestMessage, ChatCompletionRequestMessageContentPart,
+    ChatCompletionRequestUserMessageContent, CreateChatCompletionRequest, ImageUrl,
+};
+
+/// Limits a request must be within. `None` means unlimited.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RequestLimits {
+    /// Maximum number of messages in a chat completion request
+    pub max_messages: Option<usize>,
+    /// Maximum number of tools (and legacy functions) in a chat completion request
+    pub max_tools: Option<usize>,
+    /// Maximum number of images across all messages of a chat completion request
+    pub max_images: Option<usize>,
+    /// Maximum size in bytes of an image passed inline as a base64 data url
+    pub max_image_bytes: Option<usize>,
+    /// Whether every image must set [ImageUrl::detail]
+    pub require_image_detail: bool,
+}
+
+impl RequestLimits {
+    /// No limits
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Limits of [Azure OpenAI Service](https://learn.microsoft.com/en-us/azure/ai-services/openai/quotas-limits)
+    pub fn azure() -> Self {
+        Self {
+            max_messages: Some(2048),
+            max_tools: Some(128),
+            max_images: Some(10),
+            max_image_bytes: Some(20 * 1024 * 1024),
+            require_image_detail: false,
+        }
+    }
+
+    /// Limits of Azure OpenAI Service for the given `api-version`: the ones of [RequestLimits::azure],
+    /// with the images of [RequestLimits::azure_vision].
+    pub fn azure_api_version(api_version: &str) -> Self {
+        let vision = Self::azure_vision(api_version);
+        Self {
+            max_images: vision.max_images,
+            require_image_detail: vision.require_image_detail,
+            ..Self::azure()
+        }
+    }
+
+    /// Limits of the images of Azure OpenAI Service for the given `api-version`, the default ones of
+    /// [AzureConfig](crate::config::AzureConfig).
+    ///
+    /// GPT-4 Turbo with Vision previews require the `detail` of every image,
+    /// while API versions since `2024-10-21` accept up to 50 images per request.
+    pub fn azure_vision(api_version: &str) -> Self {
+        let limits = Self::new().with_max_images(10);
+        // api versions are dates, optionally suffixed with -preview, so they compare lexicographically
+        if api_version >= "2024-10-21" {
+            limits.with_max_images(50)
+        } else if api_version < "2024-05-01" {
+            limits.with_require_image_detail(true)
+        } else {
+            limits
+        }
+    }
+
+    pub fn with_max_messages(mut self, max_messages: usize) -> Self {
+        self.max_messages = Some(max_messages);
+        self
+    }
+
+    pub fn with_max_tools(mut self, max_tools: usize) -> Self {
+        self.max_tools = Some(max_tools);
+        self
+    }
+
+    pub fn with_max_images(mut self, max_images: usize) -> Self {
+        self.max_images = Some(max_images);
+        self
+    }
+
+    pub fn with_max_image_bytes(mut self, max_image_bytes: usize) -> Self {
+        self.max_image_bytes = Some(max_image_bytes);
+        self
+    }
+
+    pub fn with_require_image_detail(mut self, require_image_detail: bool) -> Self {
+        self.require_image_detail = require_image_detail;
+        self
+    }
+
+    /// Check a chat completion request against the limits.
+    pub fn validate_chat(&self, request: &CreateChatCompletionRequest) -> Result<(), OpenAIError> {
+        if let Some(max_messages) = self.max_messages {
+            if request.messages.len() > max_messages {
+                return Err(OpenAIError::InvalidArgument(format!(
+                    "request has {} messages but at most {max_messages} are allowed; truncate or summarize the conversation history",
+                    request.messages.len()
+                )));
+            }
+        }
+
+        if let Some(max_tools) = self.max_tools {
+            #[allow(deprecated)]
+            let functions = request.functions.as_ref().map_or(0, Vec::len);
+            let tools = request.tools.as_ref().map_or(0, Vec::len) + functions;
+            if tools > max_tools {
+                return Err(OpenAIError::InvalidArgument(format!(
+                    "request has {tools} tools but at most {max_tools} are allowed; only pass the tools relevant to the conversation"
+                )));
+            }
+        }
+
+        let images: Vec<&ImageUrl> = request.messages.iter().flat_map(images).collect();
+
+        if let Some(max_images) = self.max_images {
+            if images.len() > max_images {
+                return Err(OpenAIError::InvalidArgument(format!(
+                    "request has {} images but at most {max_images} are allowed; split the request or drop images from earlier messages",
+                    images.len()
+                )));
+            }
+        }
+
+        if self.require_image_detail {
+            if let Some(index) = images.iter().position(|image| image.detail.is_none()) {
+                return Err(OpenAIError::InvalidArgument(format!(
+                    "image #{index} has no detail but it is required; set it, e.g. to ImageDetail::Auto"
+                )));
+            }
+        }
+
+        if let Some(max_image_bytes) = self.max_image_bytes {
+            for (index, image) in images.iter().enumerate() {
+                if let Some(bytes) = data_url_size(&image.url) {
+                    if bytes > max_image_bytes {
+                        return Err(OpenAIError::InvalidArgument(format!(
+                            "image #{index} is {bytes} bytes but at most {max_image_bytes} bytes are allowed; downscale or recompress it"
+                        )));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Images attached to a message
+pub(crate) fn images(message: &ChatCompletionRequestMessage) -> Vec<&ImageUrl> {
+    match message {
+        ChatCompletionRequestMessage::User(user) => match &user.content {
+            ChatCompletionRequestUserMessageContent::Array(parts) => parts
+                .iter()
+                .filter_map(|part| match part {
+                    ChatCompletionRequestMessageContentPart::ImageUrl(image) => {
+                        Some(&image.image_url)
+                    }
+                    _ => None,
+                })
+                .collect(),
+            ChatCompletionRequestUserMessageContent::Text(_) => vec![],
+        },
+        _ => vec![],
+    }
+}
+
+/// Decoded size of the data in a base64 data url, `None` for other urls.
+fn data_url_size(url: &str) -> Option<usize> {
+    let data = url.strip_prefix("data:")?;
+    let (_, base64) = data.split_once(";base64,")?;
+    let padding = base64.bytes().rev().take_while(|b| *b == b'=').count();
+    Some((base64.len() / 4 * 3).saturating_sub(padding))
+}
+
+/// Check the `value` of `field`, if set, is within `min` and `max`, suggesting the bound it crosses otherwise
+pub(crate) fn check_range<T: PartialOrd + Display + Copy>(
+    field: &str,
+    value: Option<T>,
+    min: T,
+    max: T,
+) -> Result<(), FieldError> {
+    match value {
+        Some(value) if !(value >= min && value <= max) => {
+            let suggestion = if value < min {
+                format!("raise it to {min}")
+            } else {
+                format!("lower it to {max}")
+            };
+            Err(
+                FieldError::new(field, format!("must be within [{min}, {max}], got {value}"))
+                    .with_suggestion(suggestion),
+            )
+        }
+        _ => Ok(()),
+    }
+}
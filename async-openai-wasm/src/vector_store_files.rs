@@ -4,6 +4,7 @@ use crate::{
     Client,
     config::Config,
     error::OpenAIError,
+    pagination::Paginator,
     types::{
         CreateVectorStoreFileRequest, DeleteVectorStoreFileResponse, ListVectorStoreFilesResponse,
         VectorStoreFileObject,
@@ -74,6 +75,15 @@ impl<'c, C: Config> VectorStoreFiles<'c, C> {
             )
             .await
     }
+
+    /// Returns all the files of the vector store, fetching them page by page as the stream is consumed.
+    pub fn list_auto_paginated<Q>(&self, query: &Q) -> Paginator<'c, VectorStoreFileObject>
+    where
+        C: Sync,
+        Q: Serialize + ?Sized,
+    {
+        Paginator::new::<_, ListVectorStoreFilesResponse, _>(self.client, format!("/vector_stores/{}/files", &self.vector_store_id), query)
+    }
 }
 
 #[cfg(test)]
@@ -1,16 +1,24 @@
+use std::time::Duration;
+
 use serde::Serialize;
 
 use crate::{
     Client,
     config::Config,
     error::OpenAIError,
+    pagination::Paginator,
+    time::{sleep, Instant},
     types::{
         CreateVectorStoreRequest, DeleteVectorStoreResponse, ListVectorStoresResponse,
-        UpdateVectorStoreRequest, VectorStoreObject,
+        UpdateVectorStoreRequest, VectorStoreObject, VectorStoreSearchRequest,
+        VectorStoreSearchResultsPage, VectorStoreStatus,
     },
     vector_store_file_batches::VectorStoreFileBatches, VectorStoreFiles,
 };
 
+/// Vector stores are used to store files for use by the `file_search` tool.
+///
+/// Related guide: [File Search](https://platform.openai.com/docs/assistants/tools/file-search)
 pub struct VectorStores<'c, C: Config> {
     client: &'c Client<C>,
 }
@@ -38,6 +46,40 @@ impl<'c, C: Config> VectorStores<'c, C> {
         self.client.post("/vector_stores", request).await
     }
 
+    /// Create a vector store and poll it until its files are processed.
+    ///
+    /// The delay between two polls starts at `poll_interval` and doubles after each poll, up to 8 times `poll_interval`.
+    /// Once `timeout` elapses, if any, the vector store is returned as last retrieved, still in progress.
+    pub async fn create_and_poll(
+        &self,
+        request: CreateVectorStoreRequest,
+        poll_interval: Duration,
+        timeout: Option<Duration>,
+    ) -> Result<VectorStoreObject, OpenAIError> {
+        let _polling = self.client.active_poller();
+        let started = Instant::now();
+        let mut vector_store = self.create(request).await?;
+        let mut delay = poll_interval;
+        while vector_store.status == VectorStoreStatus::InProgress {
+            let delay_until_timeout = match timeout {
+                Some(timeout) => match timeout.checked_sub(started.elapsed()) {
+                    Some(remaining) if !remaining.is_zero() => delay.min(remaining),
+                    _ => break,
+                },
+                None => delay,
+            };
+            self.client
+                .cancellable(async {
+                    sleep(delay_until_timeout).await;
+                    Ok(())
+                })
+                .await?;
+            vector_store = self.retrieve(&vector_store.id).await?;
+            delay = (delay * 2).min(poll_interval * 8);
+        }
+        Ok(vector_store)
+    }
+
     /// Retrieves a vector store.
     pub async fn retrieve(&self, vector_store_id: &str) -> Result<VectorStoreObject, OpenAIError> {
         self.client
@@ -53,6 +95,15 @@ impl<'c, C: Config> VectorStores<'c, C> {
         self.client.get_with_query("/vector_stores", query).await
     }
 
+    /// Returns all the vector stores, fetching them page by page as the stream is consumed.
+    pub fn list_auto_paginated<Q>(&self, query: &Q) -> Paginator<'c, VectorStoreObject>
+    where
+        C: Sync,
+        Q: Serialize + ?Sized,
+    {
+        Paginator::new::<_, ListVectorStoresResponse, _>(self.client, "/vector_stores".into(), query)
+    }
+
     /// Delete a vector store.
     pub async fn delete(
         &self,
@@ -73,4 +124,42 @@ impl<'c, C: Config> VectorStores<'c, C> {
             .post(&format!("/vector_stores/{vector_store_id}"), request)
             .await
     }
+
+    /// Renews the expiration of a vector store which expires some days after it was last active.
+    ///
+    /// Call this when an application uses a vector store indirectly
+    /// (e.g. it is cached by id) to keep it alive without making it expire.
+    /// Returns the vector store unchanged if it has no expiration policy.
+    pub async fn renew_expiration(
+        &self,
+        vector_store_id: &str,
+    ) -> Result<VectorStoreObject, OpenAIError> {
+        let vector_store = self.retrieve(vector_store_id).await?;
+        let Some(expires_after) = vector_store.expires_after.clone() else {
+            return Ok(vector_store);
+        };
+        self.update(
+            vector_store_id,
+            UpdateVectorStoreRequest {
+                name: None,
+                expires_after: Some(expires_after),
+                metadata: None,
+            },
+        )
+        .await
+    }
+
+    /// Search a vector store for relevant chunks based on a query and file attributes filter.
+    pub async fn search(
+        &self,
+        vector_store_id: &str,
+        request: VectorStoreSearchRequest,
+    ) -> Result<VectorStoreSearchResultsPage, OpenAIError> {
+        if let Some(filters) = &request.filters {
+            filters.validate()?;
+        }
+        self.client
+            .post(&format!("/vector_stores/{vector_store_id}/search"), request)
+            .await
+    }
 }
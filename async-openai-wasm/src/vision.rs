@@ -0,0 +1,320 @@
+//! Preparation of images for vision inputs, to send them within the limits of the provider
+//! rather than getting a 400 for an oversized or unsupported image.
+//!
+//! The format, dimensions and EXIF orientation of an image are read from its header. Images exceeding
+//! [ImageLimits], in an unsupported format or rotated by their EXIF orientation are handed to an
+//! [ImageTranscoder], which decodes, rotates, resizes and encodes them again.
+//!
+//! The `vision` feature brings no image codec, as they weigh a lot on wasm32: implement the
+//! transcoder with the codec of your choice, or enable the `image` feature for [ImageCodec], built on
+//! the [image](https://crates.io/crates/image) crate. It encodes PNG, JPEG and GIF images but not WebP
+//! ones, which are then encoded in another format the provider accepts.
+//!
+//! ```
+//! # #[cfg(feature = "image")]
+//! # {
+//! use async_openai_wasm::{
+//!     types::ImageDetail,
+//!     vision::{prepare_image, ImageCodec, ImageLimits},
+//! };
+//!
+//! # let png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\x02\0\0\0\x01\0".to_vec();
+//! let image = prepare_image(png, &ImageLimits::openai(), &ImageCodec::default())?;
+//! assert_eq!((image.width, image.height), (512, 256));
+//! let image_url = image.image_url(ImageDetail::High);
+//! # }
+//! # Ok::<(), async_openai_wasm::error::OpenAIError>(())
+//! ```
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+pub use crate::image_info::{ImageFormat, ImageInfo};
+use crate::{
+    error::OpenAIError,
+    types::{ImageDetail, ImageInput, ImageUrl},
+};
+
+/// Dimensions, size and formats of images a provider accepts
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageLimits {
+    /// Maximum width in pixels, beyond which images are scaled down keeping their aspect ratio
+    pub max_width: u32,
+    /// Maximum height in pixels, beyond which images are scaled down keeping their aspect ratio
+    pub max_height: u32,
+    /// Maximum size in bytes of an image
+    pub max_bytes: usize,
+    /// Accepted formats, images in other formats being encoded in the first one
+    pub formats: Vec<ImageFormat>,
+}
+
+impl Default for ImageLimits {
+    fn default() -> Self {
+        Self::openai()
+    }
+}
+
+impl ImageLimits {
+    /// Limits of [OpenAI](https://platform.openai.com/docs/guides/images-vision), which scales
+    /// images down to fit in 2048 by 2048 pixels anyway
+    pub fn openai() -> Self {
+        Self {
+            max_width: 2048,
+            max_height: 2048,
+            max_bytes: 20 * 1024 * 1024,
+            formats: vec![
+                ImageFormat::Png,
+                ImageFormat::Jpeg,
+                ImageFormat::Webp,
+                ImageFormat::Gif,
+            ],
+        }
+    }
+
+    /// Limits of [Anthropic](https://docs.anthropic.com/en/docs/build-with-claude/vision), which
+    /// scales images down to 1568 pixels on their longest side anyway
+    pub fn anthropic() -> Self {
+        Self {
+            max_width: 1568,
+            max_height: 1568,
+            max_bytes: 5 * 1024 * 1024,
+            formats: vec![
+                ImageFormat::Jpeg,
+                ImageFormat::Png,
+                ImageFormat::Gif,
+                ImageFormat::Webp,
+            ],
+        }
+    }
+
+    pub fn with_max_dimensions(mut self, max_width: u32, max_height: u32) -> Self {
+        self.max_width = max_width;
+        self.max_height = max_height;
+        self
+    }
+
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    pub fn with_formats(mut self, formats: Vec<ImageFormat>) -> Self {
+        self.formats = formats;
+        self
+    }
+
+    /// Dimensions `width` by `height` are scaled down to, keeping the aspect ratio
+    pub fn fit(&self, width: u32, height: u32) -> (u32, u32) {
+        let scale = (self.max_width as f64 / width.max(1) as f64)
+            .min(self.max_height as f64 / height.max(1) as f64)
+            .min(1.0);
+        if scale == 1.0 {
+            return (width, height);
+        }
+        let scaled = |dimension: u32| ((dimension as f64 * scale).round() as u32).max(1);
+        (scaled(width), scaled(height))
+    }
+}
+
+/// Image codec [prepare_image] hands the images to change to
+pub trait ImageTranscoder {
+    /// Decode `image`, apply its EXIF `orientation`, resize it to exactly `width` by `height` pixels
+    /// and encode it in `format`, without EXIF orientation.
+    fn transcode(
+        &self,
+        image: &[u8],
+        orientation: u8,
+        width: u32,
+        height: u32,
+        format: ImageFormat,
+    ) -> Result<Vec<u8>, OpenAIError>;
+
+    /// Whether the transcoder encodes images in `format`, [prepare_image] picking another format of
+    /// the limits for those it does not
+    fn encodes(&self, format: ImageFormat) -> bool {
+        let _ = format;
+        true
+    }
+}
+
+/// Image within the limits of a provider, ready to be sent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreparedImage {
+    pub bytes: Vec<u8>,
+    pub format: ImageFormat,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl PreparedImage {
+    /// The image as a base64 data URL
+    pub fn data_url(&self) -> String {
+        format!(
+            "data:{};base64,{}",
+            self.format.mime_type(),
+            STANDARD.encode(&self.bytes)
+        )
+    }
+
+    /// The image as the [ImageUrl] of a chat completion message
+    pub fn image_url(&self, detail: ImageDetail) -> ImageUrl {
+        ImageUrl {
+            url: self.data_url(),
+            detail: Some(detail),
+        }
+    }
+
+    /// The image as the upload of an image edit or variation, named `name` with the extension of its format
+    pub fn image_input(&self, name: &str) -> ImageInput {
+        ImageInput::from_vec_u8(
+            format!("{name}.{}", self.format.extension()),
+            self.bytes.clone(),
+        )
+    }
+}
+
+/// Fit `image` in `limits`, handing it to `transcoder` when it is too large, in a format `limits`
+/// does not accept, or rotated by its EXIF orientation. Images already fine are kept as they are.
+pub fn prepare_image(
+    image: Vec<u8>,
+    limits: &ImageLimits,
+    transcoder: &dyn ImageTranscoder,
+) -> Result<PreparedImage, OpenAIError> {
+    let info = ImageInfo::read(&image).ok_or_else(|| {
+        OpenAIError::InvalidArgument(
+            "image is not a PNG, JPEG, GIF or WebP image, or its header is truncated".into(),
+        )
+    })?;
+    let (oriented_width, oriented_height) = info.oriented_dimensions();
+    let (width, height) = limits.fit(oriented_width, oriented_height);
+    let format = if limits.formats.contains(&info.format) {
+        info.format
+    } else {
+        *limits
+            .formats
+            .first()
+            .ok_or_else(|| OpenAIError::InvalidArgument("image limits accept no format".into()))?
+    };
+
+    let unchanged = info.orientation == 1
+        && format == info.format
+        && (width, height) == (info.width, info.height);
+    if unchanged && image.len() <= limits.max_bytes {
+        return Ok(PreparedImage {
+            bytes: image,
+            format,
+            width,
+            height,
+        });
+    }
+
+    let format = if transcoder.encodes(format) {
+        format
+    } else {
+        *limits
+            .formats
+            .iter()
+            .find(|format| transcoder.encodes(**format))
+            .ok_or_else(|| {
+                OpenAIError::InvalidArgument(
+                    "image needs transcoding but the transcoder encodes none of the accepted formats"
+                        .into(),
+                )
+            })?
+    };
+    let bytes = transcoder.transcode(&image, info.orientation, width, height, format)?;
+    if bytes.len() > limits.max_bytes {
+        return Err(OpenAIError::InvalidArgument(format!(
+            "image is {} bytes once transcoded but at most {} bytes are allowed; lower its maximum dimensions",
+            bytes.len(),
+            limits.max_bytes
+        )));
+    }
+    Ok(PreparedImage {
+        bytes,
+        format,
+        width,
+        height,
+    })
+}
+
+
+/// [ImageTranscoder] built on the [image](https://crates.io/crates/image) crate, encoding PNG, JPEG
+/// and GIF images
+#[cfg(feature = "image")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageCodec {
+    /// Quality of JPEG images, from 1 to 100
+    pub jpeg_quality: u8,
+}
+
+#[cfg(feature = "image")]
+impl Default for ImageCodec {
+    fn default() -> Self {
+        Self { jpeg_quality: 85 }
+    }
+}
+
+#[cfg(feature = "image")]
+impl ImageCodec {
+    pub fn with_jpeg_quality(mut self, jpeg_quality: u8) -> Self {
+        self.jpeg_quality = jpeg_quality;
+        self
+    }
+}
+
+#[cfg(feature = "image")]
+impl ImageTranscoder for ImageCodec {
+    fn transcode(
+        &self,
+        image: &[u8],
+        orientation: u8,
+        width: u32,
+        height: u32,
+        format: ImageFormat,
+    ) -> Result<Vec<u8>, OpenAIError> {
+        use ::image::{imageops::FilterType, DynamicImage, ImageOutputFormat};
+
+        let decoded = ::image::load_from_memory(image).map_err(|error| {
+            OpenAIError::InvalidArgument(format!("failed to decode image: {error}"))
+        })?;
+        let oriented = match orientation {
+            2 => decoded.fliph(),
+            3 => decoded.rotate180(),
+            4 => decoded.flipv(),
+            5 => decoded.rotate90().fliph(),
+            6 => decoded.rotate90(),
+            7 => decoded.rotate270().fliph(),
+            8 => decoded.rotate270(),
+            _ => decoded,
+        };
+        let resized = if (oriented.width(), oriented.height()) == (width, height) {
+            oriented
+        } else {
+            oriented.resize_exact(width, height, FilterType::Lanczos3)
+        };
+        let (resized, output_format) = match format {
+            ImageFormat::Png => (resized, ImageOutputFormat::Png),
+            // JPEG has no alpha channel
+            ImageFormat::Jpeg => (
+                DynamicImage::ImageRgb8(resized.to_rgb8()),
+                ImageOutputFormat::Jpeg(self.jpeg_quality),
+            ),
+            ImageFormat::Gif => (resized, ImageOutputFormat::Gif),
+            ImageFormat::Webp => {
+                return Err(OpenAIError::InvalidArgument(
+                    "ImageCodec does not encode WebP images".into(),
+                ))
+            }
+        };
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        resized
+            .write_to(&mut bytes, output_format)
+            .map_err(|error| {
+                OpenAIError::InvalidArgument(format!("failed to encode image: {error}"))
+            })?;
+        Ok(bytes.into_inner())
+    }
+
+    fn encodes(&self, format: ImageFormat) -> bool {
+        format != ImageFormat::Webp
+    }
+}
@@ -0,0 +1,181 @@
+#![cfg(feature = "testing")]
+
+use async_openai_wasm::{
+    config::OpenAIConfig,
+    testing::{MockResponse, MockServer},
+    types::{
+        InviteProject, InviteRequestArgs, OrganizationRole, ProjectApiKeyOwner, ProjectRole,
+        ProjectStatus, UserRoleUpdateRequestArgs,
+    },
+    Client,
+};
+use futures::StreamExt;
+use serde_json::json;
+
+fn admin_client(server: &MockServer) -> Client<OpenAIConfig> {
+    Client::with_config(server.config().with_api_key("sk-admin-test"))
+}
+
+#[tokio::test]
+async fn users_are_listed_page_by_page_and_modified() {
+    let server = MockServer::start();
+    let user = |id: &str, role: &str| {
+        json!({
+            "object": "organization.user", "id": id, "name": id, "email": format!("{id}@example.com"),
+            "role": role, "added_at": 1711471533,
+        })
+    };
+    server.mock(
+        "GET",
+        "/organization/users",
+        MockResponse::json(json!({
+            "object": "list", "data": [user("user_1", "owner")],
+            "first_id": "user_1", "last_id": "user_1", "has_more": true,
+        })),
+    );
+    server.mock(
+        "GET",
+        "/organization/users",
+        MockResponse::json(json!({
+            "object": "list", "data": [user("user_2", "reader")],
+            "first_id": "user_2", "last_id": "user_2", "has_more": false,
+        })),
+    );
+    server.mock(
+        "POST",
+        "/organization/users/user_2",
+        MockResponse::json(user("user_2", "owner")),
+    );
+
+    let client = admin_client(&server);
+    let users: Vec<_> = client
+        .admin()
+        .users()
+        .list_auto_paginated(&[("limit", "1")])
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    assert_eq!(users.len(), 2);
+    assert_eq!(users[1].role, OrganizationRole::Reader);
+    assert_eq!(
+        server.requests_to("/organization/users")[1].query,
+        "limit=1&after=user_1"
+    );
+
+    let user = client
+        .admin()
+        .users()
+        .modify(
+            "user_2",
+            UserRoleUpdateRequestArgs::default()
+                .role(OrganizationRole::Owner)
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(user.role, OrganizationRole::Owner);
+    assert_eq!(
+        server.requests_to("/organization/users/user_2")[0].json(),
+        json!({"role": "owner"})
+    );
+}
+
+#[tokio::test]
+async fn invites_are_sent_with_their_projects() {
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/organization/invites",
+        MockResponse::json(json!({
+            "object": "organization.invite", "id": "invite-abc", "email": "user@example.com",
+            "role": "reader", "status": "pending", "invited_at": 1711471533,
+            "expires_at": 1711471533, "accepted_at": null,
+            "projects": [{"id": "proj_1", "role": "member"}],
+        })),
+    );
+
+    let invite = admin_client(&server)
+        .admin()
+        .invites()
+        .create(
+            InviteRequestArgs::default()
+                .email("user@example.com")
+                .role(OrganizationRole::Reader)
+                .projects(vec![InviteProject {
+                    id: "proj_1".into(),
+                    role: ProjectRole::Member,
+                }])
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(invite.projects[0].role, ProjectRole::Member);
+    assert_eq!(
+        server.requests_to("/organization/invites")[0].json(),
+        json!({
+            "email": "user@example.com", "role": "reader",
+            "projects": [{"id": "proj_1", "role": "member"}],
+        })
+    );
+}
+
+#[tokio::test]
+async fn projects_keys_and_service_accounts_are_managed() {
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/organization/projects/proj_1/archive",
+        MockResponse::json(json!({
+            "id": "proj_1", "object": "organization.project", "name": "Staging",
+            "created_at": 1711471533, "archived_at": 1711471534, "status": "archived",
+        })),
+    );
+    server.mock(
+        "GET",
+        "/organization/projects/proj_1/api_keys/key_1",
+        MockResponse::json(json!({
+            "object": "organization.project.api_key", "redacted_value": "sk-abc...def",
+            "name": "deploy", "created_at": 1711471533, "id": "key_1",
+            "owner": {
+                "type": "service_account",
+                "service_account": {
+                    "object": "organization.project.service_account", "id": "svc_acct_1",
+                    "name": "deploy", "role": "member", "created_at": 1711471533,
+                },
+            },
+        })),
+    );
+    server.mock(
+        "DELETE",
+        "/organization/projects/proj_1/service_accounts/svc_acct_1",
+        MockResponse::json(json!({
+            "object": "organization.project.service_account.deleted",
+            "id": "svc_acct_1", "deleted": true,
+        })),
+    );
+
+    let client = admin_client(&server);
+    let project = client.admin().projects().archive("proj_1").await.unwrap();
+    assert_eq!(project.status, ProjectStatus::Archived);
+
+    let key = client
+        .admin()
+        .project_api_keys("proj_1")
+        .retrieve("key_1")
+        .await
+        .unwrap();
+    assert!(
+        matches!(key.owner, ProjectApiKeyOwner::ServiceAccount { service_account } if service_account.id == "svc_acct_1")
+    );
+
+    let deleted = client
+        .admin()
+        .project_service_accounts("proj_1")
+        .delete("svc_acct_1")
+        .await
+        .unwrap();
+    assert!(deleted.deleted);
+}
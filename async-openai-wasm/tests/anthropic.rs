@@ -0,0 +1,238 @@
+use async_openai_wasm::{
+    anthropic::{
+        ChatStreamTranslator, MessagesRequest, MessagesResponse, StreamEvent, DEFAULT_MAX_TOKENS,
+    },
+    types::{
+        ChatCompletionMessageToolCall, ChatCompletionRequestAssistantMessageArgs,
+        ChatCompletionRequestMessageContentPartFileArgs, ChatCompletionRequestSystemMessageArgs,
+        ChatCompletionRequestToolMessageArgs, ChatCompletionRequestUserMessageArgs,
+        ChatCompletionToolArgs, CreateChatCompletionRequestArgs, CreateChatCompletionResponse,
+        FileObjectArgs, FinishReason, FunctionCall, FunctionObjectArgs,
+    },
+};
+use serde_json::json;
+
+#[test]
+fn chat_request_to_messages_request() {
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("claude-3-5-sonnet-latest")
+        .messages([
+            ChatCompletionRequestSystemMessageArgs::default()
+                .content("Be brief.")
+                .build()
+                .unwrap()
+                .into(),
+            ChatCompletionRequestUserMessageArgs::default()
+                .content("Weather in Paris and Rome?")
+                .build()
+                .unwrap()
+                .into(),
+            ChatCompletionRequestAssistantMessageArgs::default()
+                .tool_calls(
+                    ["Paris", "Rome"]
+                        .map(|city| ChatCompletionMessageToolCall {
+                            id: format!("toolu_{city}"),
+                            r#type: Default::default(),
+                            function: FunctionCall {
+                                name: "get_weather".into(),
+                                arguments: json!({ "city": city }).to_string(),
+                            },
+                        })
+                        .to_vec(),
+                )
+                .build()
+                .unwrap()
+                .into(),
+            ChatCompletionRequestToolMessageArgs::default()
+                .tool_call_id("toolu_Paris")
+                .content("sunny")
+                .build()
+                .unwrap()
+                .into(),
+            ChatCompletionRequestToolMessageArgs::default()
+                .tool_call_id("toolu_Rome")
+                .content("rainy")
+                .build()
+                .unwrap()
+                .into(),
+        ])
+        .tools([ChatCompletionToolArgs::default()
+            .function(
+                FunctionObjectArgs::default()
+                    .name("get_weather")
+                    .parameters(json!({"type": "object"}))
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap()])
+        .parallel_tool_calls(false)
+        .stop("END")
+        .build()
+        .unwrap();
+
+    let request = MessagesRequest::try_from(request).unwrap();
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        json!({
+            "model": "claude-3-5-sonnet-latest",
+            "max_tokens": 4096,
+            "system": "Be brief.",
+            "stop_sequences": ["END"],
+            "messages": [
+                {"role": "user", "content": [{"type": "text", "text": "Weather in Paris and Rome?"}]},
+                {"role": "assistant", "content": [
+                    {"type": "tool_use", "id": "toolu_Paris", "name": "get_weather", "input": {"city": "Paris"}},
+                    {"type": "tool_use", "id": "toolu_Rome", "name": "get_weather", "input": {"city": "Rome"}},
+                ]},
+                {"role": "user", "content": [
+                    {"type": "tool_result", "tool_use_id": "toolu_Paris", "content": "sunny"},
+                    {"type": "tool_result", "tool_use_id": "toolu_Rome", "content": "rainy"},
+                ]},
+            ],
+            "tools": [{"name": "get_weather", "input_schema": {"type": "object"}}],
+            "tool_choice": {"type": "auto", "disable_parallel_tool_use": true},
+        })
+    );
+}
+
+#[test]
+fn max_completion_tokens_to_max_tokens() {
+    let request = |max_tokens: Option<u16>, max_completion_tokens: Option<u32>| {
+        let mut request = CreateChatCompletionRequestArgs::default();
+        request
+            .model("claude-3-5-sonnet-latest")
+            .messages([ChatCompletionRequestUserMessageArgs::default()
+                .content("Hi")
+                .build()
+                .unwrap()
+                .into()]);
+        if let Some(max_tokens) = max_tokens {
+            request.max_tokens(max_tokens);
+        }
+        if let Some(max_completion_tokens) = max_completion_tokens {
+            request.max_completion_tokens(max_completion_tokens);
+        }
+        MessagesRequest::try_from(request.build().unwrap())
+            .unwrap()
+            .max_tokens
+    };
+
+    assert_eq!(request(None, Some(1024)), 1024);
+    assert_eq!(request(Some(512), Some(1024)), 512);
+    assert_eq!(request(None, None), DEFAULT_MAX_TOKENS);
+}
+
+#[test]
+fn file_parts_to_documents() {
+    let message = |file| {
+        CreateChatCompletionRequestArgs::default()
+            .model("claude-3-5-sonnet-latest")
+            .messages([ChatCompletionRequestUserMessageArgs::default()
+                .content(vec![
+                    ChatCompletionRequestMessageContentPartFileArgs::default()
+                        .file(file)
+                        .build()
+                        .unwrap()
+                        .into(),
+                ])
+                .build()
+                .unwrap()
+                .into()])
+            .build()
+            .unwrap()
+    };
+
+    let request = MessagesRequest::try_from(message(
+        FileObjectArgs::default()
+            .file_data("data:application/pdf;base64,JVBERi0=")
+            .filename("report.pdf")
+            .build()
+            .unwrap(),
+    ))
+    .unwrap();
+    assert_eq!(
+        serde_json::to_value(&request).unwrap()["messages"][0]["content"],
+        json!([{
+            "type": "document",
+            "source": {"type": "base64", "media_type": "application/pdf", "data": "JVBERi0="},
+        }])
+    );
+
+    let uploaded = FileObjectArgs::default()
+        .file_id("file-abc")
+        .build()
+        .unwrap();
+    assert!(MessagesRequest::try_from(message(uploaded)).is_err());
+}
+
+#[test]
+fn messages_response_to_chat_response() {
+    let response: MessagesResponse = serde_json::from_value(json!({
+        "id": "msg_1",
+        "type": "message",
+        "role": "assistant",
+        "model": "claude-3-5-sonnet-latest",
+        "content": [
+            {"type": "text", "text": "Checking."},
+            {"type": "tool_use", "id": "toolu_1", "name": "get_weather", "input": {"city": "Paris"}},
+        ],
+        "stop_reason": "tool_use",
+        "stop_sequence": null,
+        "usage": {"input_tokens": 10, "output_tokens": 5, "cache_read_input_tokens": 2},
+    }))
+    .unwrap();
+
+    let response = CreateChatCompletionResponse::from(response);
+    assert_eq!(response.first_text(), Some("Checking."));
+    let tool_call = response.tool_calls().next().unwrap();
+    assert_eq!(tool_call.id, "toolu_1");
+    assert_eq!(tool_call.function.arguments, r#"{"city":"Paris"}"#);
+    assert_eq!(
+        response.choices[0].finish_reason,
+        Some(FinishReason::ToolCalls)
+    );
+    let usage = response.usage.unwrap();
+    assert_eq!((usage.prompt_tokens, usage.total_tokens), (12, 17));
+}
+
+#[test]
+fn stream_events_to_chat_chunks() {
+    let events = [
+        json!({"type": "message_start", "message": {"id": "msg_1", "type": "message", "role": "assistant", "model": "claude", "content": [], "stop_reason": null, "stop_sequence": null, "usage": {"input_tokens": 10, "output_tokens": 1}}}),
+        json!({"type": "content_block_start", "index": 0, "content_block": {"type": "text", "text": ""}}),
+        json!({"type": "ping"}),
+        json!({"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": "Hi"}}),
+        json!({"type": "content_block_stop", "index": 0}),
+        json!({"type": "content_block_start", "index": 1, "content_block": {"type": "tool_use", "id": "toolu_1", "name": "get_weather", "input": {}}}),
+        json!({"type": "content_block_delta", "index": 1, "delta": {"type": "input_json_delta", "partial_json": "{\"city\":"}}),
+        json!({"type": "message_delta", "delta": {"stop_reason": "tool_use", "stop_sequence": null}, "usage": {"output_tokens": 7}}),
+        json!({"type": "message_stop"}),
+    ];
+
+    let mut translator = ChatStreamTranslator::new();
+    let chunks: Vec<_> = events
+        .into_iter()
+        .map(|event| serde_json::from_value::<StreamEvent>(event).unwrap())
+        .filter_map(|event| translator.translate(event).unwrap())
+        .collect();
+
+    assert_eq!(chunks.len(), 5);
+    assert!(chunks.iter().all(|chunk| chunk.id == "msg_1"));
+    assert_eq!(chunks[1].first_text(), Some("Hi"));
+    let tool_call = &chunks[2].choices[0].delta.tool_calls.as_ref().unwrap()[0];
+    assert_eq!(tool_call.id.as_deref(), Some("toolu_1"));
+    let arguments = &chunks[3].choices[0].delta.tool_calls.as_ref().unwrap()[0];
+    assert_eq!(arguments.index, 0);
+    assert_eq!(
+        chunks[4].choices[0].finish_reason,
+        Some(FinishReason::ToolCalls)
+    );
+    assert_eq!(chunks[4].usage.as_ref().unwrap().completion_tokens, 7);
+
+    let error = serde_json::from_value::<StreamEvent>(
+        json!({"type": "error", "error": {"type": "overloaded_error", "message": "Overloaded"}}),
+    )
+    .unwrap();
+    assert!(translator.translate(error).is_err());
+}
@@ -0,0 +1,183 @@
+#![cfg(feature = "testing")]
+
+use async_openai_wasm::{
+    error::{ApiError, ApiErrorKind, OpenAIError},
+    testing::{MockResponse, MockServer},
+    types::{ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs},
+};
+use futures::StreamExt;
+use reqwest::StatusCode;
+use serde_json::json;
+
+async fn list_models_error(response: MockResponse) -> ApiError {
+    let server = MockServer::start();
+    server.mock("GET", "/models", response);
+    match server.client().models().list().await {
+        Err(OpenAIError::ApiError(error)) => error,
+        other => panic!("expected an API error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn errors_are_classified_by_status_type_and_code() {
+    let cases = [
+        (
+            429,
+            "tokens",
+            "rate_limit_exceeded",
+            ApiErrorKind::RateLimit,
+        ),
+        (
+            429,
+            "insufficient_quota",
+            "insufficient_quota",
+            ApiErrorKind::QuotaExceeded,
+        ),
+        (
+            401,
+            "invalid_request_error",
+            "invalid_api_key",
+            ApiErrorKind::Authentication,
+        ),
+        (
+            400,
+            "invalid_request_error",
+            "invalid_value",
+            ApiErrorKind::InvalidRequest,
+        ),
+        (
+            404,
+            "invalid_request_error",
+            "model_not_found",
+            ApiErrorKind::InvalidRequest,
+        ),
+        (500, "server_error", "", ApiErrorKind::Server),
+    ];
+    for (status, r#type, code, kind) in cases {
+        let error = list_models_error(MockResponse {
+            status,
+            ..MockResponse::json(json!({"error": {
+                "message": "failed", "type": r#type, "param": null, "code": code,
+            }}))
+        })
+        .await;
+        assert_eq!(error.status.map(|status| status.as_u16()), Some(status));
+        assert_eq!(error.kind(), kind, "{status} {type} {code}");
+    }
+}
+
+#[tokio::test]
+async fn azure_content_filter_errors_keep_their_results() {
+    let error = list_models_error(MockResponse {
+        status: 400,
+        ..MockResponse::json(json!({"error": {
+            "message": "The response was filtered due to the prompt triggering Azure OpenAI's content management policy.",
+            "type": null,
+            "param": "prompt",
+            "code": "content_filter",
+            "status": 400,
+            "innererror": {
+                "code": "ResponsibleAIPolicyViolation",
+                "content_filter_result": {
+                    "hate": {"filtered": false, "severity": "safe"},
+                    "jailbreak": {"filtered": true, "detected": true},
+                    "self_harm": {"filtered": false, "severity": "safe"},
+                    "sexual": {"filtered": false, "severity": "safe"},
+                    "violence": {"filtered": true, "severity": "medium"}
+                }
+            }
+        }}))
+    })
+    .await;
+
+    assert_eq!(error.kind(), ApiErrorKind::ContentFilter);
+    let inner = error.innererror.unwrap();
+    assert_eq!(inner.code.as_deref(), Some("ResponsibleAIPolicyViolation"));
+    let results = inner.content_filter_result.unwrap();
+    assert_eq!(results.filtered(), ["violence", "jailbreak"]);
+    assert_eq!(
+        results.violence.unwrap().severity.as_deref(),
+        Some("medium")
+    );
+}
+
+#[tokio::test]
+async fn numeric_codes_of_azure_are_read_as_strings() {
+    let error = list_models_error(MockResponse {
+        status: 429,
+        ..MockResponse::json(json!({"error": {
+            "code": 429,
+            "message": "Requests to the ChatCompletions_Create Operation have exceeded call rate limit.",
+        }}))
+    })
+    .await;
+
+    assert_eq!(error.code.as_deref(), Some("429"));
+    assert_eq!(error.kind(), ApiErrorKind::RateLimit);
+}
+
+#[tokio::test]
+async fn errors_carry_the_status_request_id_and_body_of_their_response() {
+    let server = MockServer::start();
+    server.mock(
+        "GET",
+        "/models",
+        MockResponse::error(503, "server_error", "overloaded")
+            .with_header("x-request-id", "req_42"),
+    );
+
+    let error = server.client().models().list().await.unwrap_err();
+
+    assert_eq!(error.status(), Some(StatusCode::SERVICE_UNAVAILABLE));
+    assert_eq!(error.request_id(), Some("req_42"));
+    let OpenAIError::ApiError(error) = error else {
+        panic!("expected an API error");
+    };
+    assert!(error.body().unwrap().contains("\"overloaded\""));
+}
+
+#[tokio::test]
+async fn responses_without_an_api_error_keep_their_status_request_id_and_body() {
+    let error = list_models_error(
+        MockResponse::bytes("<html><body>502 Bad Gateway</body></html>")
+            .with_status(502)
+            .with_header("x-request-id", "req_9"),
+    )
+    .await;
+
+    assert_eq!(error.status, Some(StatusCode::BAD_GATEWAY));
+    assert_eq!(error.message, "502 Bad Gateway");
+    assert_eq!(error.request_id(), Some("req_9"));
+    assert_eq!(
+        error.body(),
+        Some("<html><body>502 Bad Gateway</body></html>")
+    );
+    assert_eq!(error.r#type, None);
+}
+
+#[tokio::test]
+async fn streams_refused_with_an_error_return_it() {
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/chat/completions",
+        MockResponse::error(400, "invalid_request_error", "unknown model")
+            .with_header("x-request-id", "req_7"),
+    );
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-5-nano-turbo")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello!")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap();
+
+    let mut stream = server.client().chat().create_stream(request).await.unwrap();
+
+    let error = stream.next().await.unwrap().unwrap_err();
+    assert!(matches!(&error, OpenAIError::ApiError(e) if e.message == "unknown model"));
+    assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST));
+    assert_eq!(error.request_id(), Some("req_7"));
+}
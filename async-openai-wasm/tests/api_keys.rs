@@ -0,0 +1,105 @@
+use async_openai_wasm::{
+    config::{ApiKeyKind, Config, OpenAIConfig},
+    error::OpenAIError,
+    Client,
+};
+
+#[test]
+fn kinds_of_api_keys() {
+    assert_eq!(ApiKeyKind::of("sk-abc"), Some(ApiKeyKind::User));
+    assert_eq!(ApiKeyKind::of("sk-proj-abc"), Some(ApiKeyKind::Project));
+    assert_eq!(
+        ApiKeyKind::of("sk-svcacct-abc"),
+        Some(ApiKeyKind::ServiceAccount)
+    );
+    assert_eq!(ApiKeyKind::of("sk-admin-abc"), Some(ApiKeyKind::Admin));
+    assert_eq!(ApiKeyKind::of("azure-key"), None);
+    assert_eq!(ApiKeyKind::of(""), None);
+}
+
+#[test]
+fn admin_keys_only_call_the_administration_api() {
+    assert!(ApiKeyKind::Admin.check_path("/organization/users").is_ok());
+    assert!(matches!(
+        ApiKeyKind::Admin.check_path("/chat/completions"),
+        Err(OpenAIError::InvalidArgument(_))
+    ));
+    assert!(ApiKeyKind::Project.check_path("/chat/completions").is_ok());
+    assert!(ApiKeyKind::ServiceAccount
+        .check_path("/organizations")
+        .is_ok());
+    assert!(matches!(
+        ApiKeyKind::ServiceAccount.check_path("/organization/projects"),
+        Err(OpenAIError::InvalidArgument(_))
+    ));
+}
+
+#[test]
+fn admin_keys_send_no_project_nor_beta_header() {
+    let config = OpenAIConfig::new()
+        .with_api_key("sk-admin-abc")
+        .with_org_id("org-1")
+        .with_project_id("proj-1");
+    let headers = config.headers();
+    assert_eq!(config.api_key_kind(), Some(ApiKeyKind::Admin));
+    assert_eq!(headers["OpenAI-Organization"], "org-1");
+    assert!(!headers.contains_key("OpenAI-Project"));
+    assert!(!headers.contains_key("OpenAI-Beta"));
+
+    let headers = config.with_api_key("sk-proj-abc").headers();
+    assert_eq!(headers["OpenAI-Project"], "proj-1");
+    assert_eq!(headers["OpenAI-Beta"], "assistants=v2");
+}
+
+#[test]
+fn builder_rejects_malformed_api_keys() {
+    for api_key in ["sk-proj-", "sk-proj-abc\n", " sk-abc"] {
+        assert!(matches!(
+            Client::builder().api_key(api_key).build(),
+            Err(OpenAIError::InvalidArgument(_))
+        ));
+    }
+    assert!(Client::builder().api_key("sk-svcacct-abc").build().is_ok());
+}
+
+#[tokio::test]
+async fn admin_keys_fail_before_calling_other_endpoints() {
+    // nothing listens on this port, so a request actually sent would fail with another error
+    let client = Client::with_config(
+        OpenAIConfig::new()
+            .with_api_key("sk-admin-abc")
+            .with_api_base("http://127.0.0.1:1/v1"),
+    );
+    let error = client.models().list().await.unwrap_err();
+    assert!(matches!(error, OpenAIError::InvalidArgument(message) if message.contains("/models")));
+}
+
+#[tokio::test]
+async fn endpoints_are_checked_relative_to_the_api_base() {
+    let client = |api_key: &str, api_base: &str| {
+        Client::with_config(
+            OpenAIConfig::new()
+                .with_api_key(api_key)
+                .with_api_base(api_base),
+        )
+    };
+    let is_refused = |error: Option<OpenAIError>| matches!(error, Some(OpenAIError::InvalidArgument(message)) if message.contains("API key"));
+    // case of the origin and of the path, and trailing slashes, are not part of the endpoint
+    for api_base in [
+        "http://127.0.0.1:1/v1",
+        "HTTP://127.0.0.1:1/V1/",
+        "http://127.0.0.1:1/api/openai/v1//",
+    ] {
+        let admin = client("sk-admin-abc", api_base);
+        assert!(!is_refused(
+            admin.admin().users().retrieve("user_1").await.err()
+        ));
+        assert!(is_refused(admin.models().list().await.err()));
+
+        let project = client("sk-proj-abc", api_base);
+        assert!(is_refused(
+            project.admin().users().retrieve("user_1").await.err()
+        ));
+        assert!(!is_refused(project.models().list().await.err()));
+    }
+}
@@ -0,0 +1,128 @@
+#![cfg(feature = "testing")]
+
+use std::time::Duration;
+
+use async_openai_wasm::{
+    testing::{MockResponse, MockServer},
+    types::{
+        AssistantToolResources, CreateAssistantRequestArgs,
+        CreateAssistantToolResources, FileInput,
+    },
+};
+use serde_json::json;
+
+#[test]
+fn tool_resources_are_built_per_tool() {
+    let resources =
+        CreateAssistantToolResources::file_search(["vs_1"]).with_code_interpreter(["file-1"]);
+    assert_eq!(
+        serde_json::to_value(resources).unwrap(),
+        json!({
+            "code_interpreter": {"file_ids": ["file-1"]},
+            "file_search": {"vector_store_ids": ["vs_1"], "vector_stores": []},
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(AssistantToolResources::code_interpreter(vec![
+            "file-2".to_string()
+        ]))
+        .unwrap(),
+        json!({"code_interpreter": {"file_ids": ["file-2"]}})
+    );
+}
+
+#[tokio::test]
+async fn assistants_are_created_with_a_vector_store_of_their_files() {
+    let server = MockServer::start();
+    for id in ["file-1", "file-2"] {
+        server.mock(
+            "POST",
+            "/files",
+            MockResponse::json(json!({
+                "id": id, "object": "file", "bytes": 2, "created_at": 0,
+                "filename": "notes.txt", "purpose": "assistants",
+            })),
+        );
+    }
+    let vector_store = |status: &str, in_progress: u32, completed: u32| {
+        json!({
+            "id": "vs_1", "object": "vector_store", "created_at": 0, "name": "Helper",
+            "usage_bytes": 0, "status": status, "expires_after": null, "expires_at": null,
+            "last_active_at": null, "metadata": {},
+            "file_counts": {
+                "in_progress": in_progress, "completed": completed, "failed": 0,
+                "cancelled": 0, "total": 2,
+            },
+        })
+    };
+    server.mock(
+        "POST",
+        "/vector_stores",
+        MockResponse::json(vector_store("in_progress", 2, 0)),
+    );
+    server.mock(
+        "GET",
+        "/vector_stores/vs_1",
+        MockResponse::json(vector_store("completed", 0, 2)),
+    );
+    server.mock(
+        "POST",
+        "/assistants",
+        MockResponse::json(json!({
+            "id": "asst_1", "object": "assistant", "created_at": 0, "name": "Helper",
+            "description": null, "model": "gpt-4o", "instructions": null,
+            "tools": [{"type": "code_interpreter"}, {"type": "file_search"}],
+            "tool_resources": {"file_search": {"vector_store_ids": ["vs_1"]}},
+            "metadata": {}, "temperature": 1.0, "top_p": 1.0, "response_format": "auto",
+        })),
+    );
+    let request = CreateAssistantRequestArgs::default()
+        .model("gpt-4o")
+        .name("Helper")
+        .tools(vec![
+            async_openai_wasm::types::AssistantTools::CodeInterpreter,
+        ])
+        .tool_resources(CreateAssistantToolResources::code_interpreter(["file-0"]))
+        .build()
+        .unwrap();
+
+    let assistant = server
+        .client()
+        .assistants()
+        .create_with_file_search(
+            request,
+            vec![
+                FileInput::from_vec_u8("a.txt".into(), b"a".to_vec()),
+                FileInput::from_vec_u8("b.txt".into(), b"b".to_vec()),
+            ],
+            Duration::from_millis(1),
+            None,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(assistant.id, "asst_1");
+    // files are uploaded concurrently, in any order
+    let mut sent = server.requests_to("/vector_stores")[0].json();
+    sent["file_ids"]
+        .as_array_mut()
+        .unwrap()
+        .sort_by_key(|id| id.to_string());
+    assert_eq!(
+        sent,
+        json!({"file_ids": ["file-1", "file-2"], "name": "Helper"})
+    );
+    assert_eq!(server.requests_to("/vector_stores/vs_1").len(), 1);
+    let sent = server.requests_to("/assistants")[0].json();
+    assert_eq!(
+        sent["tools"],
+        json!([{"type": "code_interpreter"}, {"type": "file_search"}])
+    );
+    assert_eq!(
+        sent["tool_resources"],
+        json!({
+            "code_interpreter": {"file_ids": ["file-0"]},
+            "file_search": {"vector_store_ids": ["vs_1"], "vector_stores": []},
+        })
+    );
+}
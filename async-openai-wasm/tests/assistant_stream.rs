@@ -0,0 +1,112 @@
+use async_openai_wasm::{
+    error::OpenAIError,
+    types::{AssistantEventStreamExt, AssistantStreamEvent, RunStatus},
+};
+use futures::{stream, StreamExt};
+use serde_json::{json, Value};
+
+fn event(event: &str, data: Value) -> Result<AssistantStreamEvent, OpenAIError> {
+    Ok(serde_json::from_value(json!({"event": event, "data": data})).unwrap())
+}
+
+fn run(status: &str) -> Value {
+    json!({
+        "id": "run_1", "object": "thread.run", "created_at": 0, "thread_id": "thread_1",
+        "assistant_id": "asst_1", "status": status, "required_action": null,
+        "last_error": null, "expires_at": null, "started_at": null, "cancelled_at": null,
+        "failed_at": null, "completed_at": null, "incomplete_details": null, "model": "gpt-4o",
+        "instructions": "", "tools": [], "metadata": null, "usage": null, "temperature": null,
+        "top_p": null, "max_prompt_tokens": null, "max_completion_tokens": null,
+        "truncation_strategy": null, "tool_choice": null, "parallel_tool_calls": true,
+        "response_format": null,
+    })
+}
+
+fn delta(text: &str) -> Value {
+    json!({
+        "id": "msg_1", "object": "thread.message.delta",
+        "delta": {"content": [{"type": "text", "index": 0, "text": {"value": text}}]},
+    })
+}
+
+fn message() -> Value {
+    json!({
+        "id": "msg_1", "object": "thread.message", "created_at": 0, "thread_id": "thread_1",
+        "status": "completed", "role": "assistant", "assistant_id": "asst_1", "run_id": "run_1",
+        "content": [{"type": "text", "text": {"value": "Hello world", "annotations": []}}],
+        "attachments": [], "metadata": {},
+    })
+}
+
+fn events() -> Vec<Result<AssistantStreamEvent, OpenAIError>> {
+    vec![
+        event("thread.run.created", run("queued")),
+        event("thread.run.in_progress", run("in_progress")),
+        event("thread.message.delta", delta("Hello")),
+        event(
+            "thread.message.delta",
+            json!({"id": "msg_1", "object": "thread.message.delta", "delta": {}}),
+        ),
+        event("thread.message.delta", delta(" world")),
+        event("thread.message.completed", message()),
+        event("thread.run.completed", run("completed")),
+        Ok(AssistantStreamEvent::Done("[DONE]".into())),
+    ]
+}
+
+#[tokio::test]
+async fn text_deltas_project_the_streamed_text() {
+    let deltas: Vec<String> = stream::iter(events())
+        .text_deltas()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    assert_eq!(deltas, ["Hello", " world"]);
+}
+
+#[tokio::test]
+async fn message_completions_project_completed_messages() {
+    let messages: Vec<_> = stream::iter(events())
+        .message_completions()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].id, "msg_1");
+}
+
+#[tokio::test]
+async fn run_status_changes_project_runs() {
+    let statuses: Vec<_> = stream::iter(events())
+        .run_status_changes()
+        .map(|run| run.unwrap().status)
+        .collect()
+        .await;
+    assert_eq!(
+        statuses,
+        [
+            RunStatus::Queued,
+            RunStatus::InProgress,
+            RunStatus::Completed
+        ]
+    );
+}
+
+#[tokio::test]
+async fn projections_pass_errors_through() {
+    let events = vec![
+        event("thread.message.delta", delta("Hello")),
+        event(
+            "error",
+            json!({"message": "server error", "type": "server_error", "param": null, "code": null}),
+        ),
+        Err(OpenAIError::StreamError {
+            message: "closed".into(),
+            source: None,
+        }),
+    ];
+    let deltas: Vec<_> = stream::iter(events).text_deltas().collect().await;
+    assert_eq!(deltas.len(), 3);
+    assert!(matches!(&deltas[1], Err(OpenAIError::ApiError(e)) if e.message == "server error"));
+    assert!(matches!(&deltas[2], Err(OpenAIError::StreamError { .. })));
+}
@@ -0,0 +1,311 @@
+#![cfg(feature = "testing")]
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use async_openai_wasm::{
+    session::{MemorySessionStore, SessionStore},
+    testing::{MockResponse, MockServer},
+    tools::ToolRegistry,
+    types::{CreateRunRequest, PendingToolCalls, RunStatus, RunToolCallObject, ToolsOutputs},
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+const KEY: &str = "pending_tool_calls/thread_1";
+
+fn run(status: &str, tool_calls: Value) -> Value {
+    let required_action = if tool_calls.is_null() {
+        Value::Null
+    } else {
+        json!({"type": "submit_tool_outputs", "submit_tool_outputs": {"tool_calls": tool_calls}})
+    };
+    json!({
+        "id": "run_1", "object": "thread.run", "created_at": 0, "thread_id": "thread_1",
+        "assistant_id": "asst_1", "status": status, "required_action": required_action,
+        "last_error": null, "expires_at": null, "started_at": null, "cancelled_at": null,
+        "failed_at": null, "completed_at": null, "incomplete_details": null, "model": "gpt-4o",
+        "instructions": "", "tools": [], "metadata": null, "usage": null, "temperature": null,
+        "top_p": null, "max_prompt_tokens": null, "max_completion_tokens": null,
+        "truncation_strategy": null, "tool_choice": null, "parallel_tool_calls": true,
+        "response_format": null,
+    })
+}
+
+fn tool_calls() -> Value {
+    json!([{"id": "call_1", "type": "function", "function": {"name": "add", "arguments": "{\"a\": 1, \"b\": 2}"}}])
+}
+
+#[derive(Deserialize)]
+struct Add {
+    a: i64,
+    b: i64,
+}
+
+/// Registry of an `add` function, and the number of times it was called
+fn registry() -> (ToolRegistry, Rc<Cell<usize>>) {
+    let calls = Rc::new(Cell::new(0));
+    let mut registry = ToolRegistry::new();
+    let counter = calls.clone();
+    registry.register("add", "Add two integers", json!({}), move |Add { a, b }| {
+        counter.set(counter.get() + 1);
+        async move { Ok::<_, String>(json!({ "sum": a + b })) }
+    });
+    (registry, calls)
+}
+
+fn submitted_outputs(server: &MockServer) -> Vec<Value> {
+    server
+        .requests_to("/threads/thread_1/runs/run_1/submit_tool_outputs")
+        .iter()
+        .map(|request| request.json()["tool_outputs"].clone())
+        .collect()
+}
+
+#[tokio::test]
+async fn runs_call_tools_until_they_end() {
+    let server = MockServer::start();
+    server
+        .mock(
+            "POST",
+            "/threads/thread_1/runs",
+            MockResponse::json(run("queued", Value::Null)),
+        )
+        .mock(
+            "GET",
+            "/threads/thread_1/runs/run_1",
+            MockResponse::json(run("requires_action", tool_calls())),
+        )
+        .mock(
+            "POST",
+            "/threads/thread_1/runs/run_1/submit_tool_outputs",
+            MockResponse::json(run("completed", Value::Null)),
+        );
+    let (registry, calls) = registry();
+    let store = MemorySessionStore::new();
+
+    let run = server
+        .client()
+        .threads()
+        .runs("thread_1")
+        .create_with_tools(
+            CreateRunRequest {
+                assistant_id: "asst_1".into(),
+                ..Default::default()
+            },
+            &registry,
+            &store,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(run.status, RunStatus::Completed);
+    assert_eq!(calls.get(), 1);
+    assert_eq!(
+        submitted_outputs(&server),
+        vec![json!([{"tool_call_id": "call_1", "output": "{\"sum\":3}"}])]
+    );
+    assert_eq!(store.load(KEY).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn resumed_runs_submit_saved_outputs_without_calling_tools_again() {
+    let server = MockServer::start();
+    server
+        .mock(
+            "GET",
+            "/threads/thread_1/runs/run_1",
+            MockResponse::json(run("requires_action", tool_calls())),
+        )
+        .mock(
+            "POST",
+            "/threads/thread_1/runs/run_1/submit_tool_outputs",
+            MockResponse::json(run("completed", Value::Null)),
+        );
+    let (registry, calls) = registry();
+    let store = MemorySessionStore::new();
+    let pending = PendingToolCalls {
+        run_id: "run_1".into(),
+        tool_calls: serde_json::from_value::<Vec<RunToolCallObject>>(tool_calls()).unwrap(),
+        tool_outputs: Some(vec![ToolsOutputs {
+            tool_call_id: Some("call_1".into()),
+            output: Some("saved".into()),
+        }]),
+    };
+    store
+        .save(KEY, serde_json::to_string(&pending).unwrap())
+        .await
+        .unwrap();
+
+    let client = server.client();
+    let threads = client.threads();
+    let runs = threads.runs("thread_1");
+    let run = runs
+        .resume_with_tools(&registry, &store)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(run.status, RunStatus::Completed);
+    assert_eq!(calls.get(), 0);
+    assert_eq!(
+        submitted_outputs(&server),
+        vec![json!([{"tool_call_id": "call_1", "output": "saved"}])]
+    );
+    assert_eq!(store.load(KEY).await.unwrap(), None);
+    assert_eq!(
+        runs.resume_with_tools(&registry, &store).await.unwrap(),
+        None
+    );
+}
+
+#[tokio::test]
+async fn resumed_runs_call_tools_whose_outputs_were_not_saved() {
+    let server = MockServer::start();
+    server
+        .mock(
+            "GET",
+            "/threads/thread_1/runs/run_1",
+            MockResponse::json(run("requires_action", tool_calls())),
+        )
+        .mock(
+            "POST",
+            "/threads/thread_1/runs/run_1/submit_tool_outputs",
+            MockResponse::json(run("completed", Value::Null)),
+        );
+    let (registry, calls) = registry();
+    let store = MemorySessionStore::new();
+    let pending = PendingToolCalls {
+        run_id: "run_1".into(),
+        tool_calls: serde_json::from_value(tool_calls()).unwrap(),
+        tool_outputs: None,
+    };
+    store
+        .save(KEY, serde_json::to_string(&pending).unwrap())
+        .await
+        .unwrap();
+
+    let run = server
+        .client()
+        .threads()
+        .runs("thread_1")
+        .resume_with_tools(&registry, &store)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(run.status, RunStatus::Completed);
+    assert_eq!(calls.get(), 1);
+    assert_eq!(
+        submitted_outputs(&server),
+        vec![json!([{"tool_call_id": "call_1", "output": "{\"sum\":3}"}])]
+    );
+}
+
+#[tokio::test]
+async fn resumed_runs_forget_tool_calls_submitted_before_the_crash() {
+    let server = MockServer::start();
+    server.mock(
+        "GET",
+        "/threads/thread_1/runs/run_1",
+        MockResponse::json(run("completed", Value::Null)),
+    );
+    let (registry, calls) = registry();
+    let store = MemorySessionStore::new();
+    let pending = PendingToolCalls {
+        run_id: "run_1".into(),
+        tool_calls: serde_json::from_value(tool_calls()).unwrap(),
+        tool_outputs: None,
+    };
+    store
+        .save(KEY, serde_json::to_string(&pending).unwrap())
+        .await
+        .unwrap();
+
+    let run = server
+        .client()
+        .threads()
+        .runs("thread_1")
+        .resume_with_tools(&registry, &store)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(run.status, RunStatus::Completed);
+    assert_eq!(calls.get(), 0);
+    assert!(submitted_outputs(&server).is_empty());
+    assert_eq!(store.load(KEY).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn polled_runs_return_early_with_their_tool_calls() {
+    let server = MockServer::start();
+    server
+        .mock(
+            "POST",
+            "/threads/thread_1/runs",
+            MockResponse::json(run("queued", Value::Null)),
+        )
+        .mock(
+            "GET",
+            "/threads/thread_1/runs/run_1",
+            MockResponse::json(run("requires_action", tool_calls())),
+        );
+
+    let run = server
+        .client()
+        .threads()
+        .runs("thread_1")
+        .create_and_poll(
+            CreateRunRequest {
+                assistant_id: "asst_1".into(),
+                ..Default::default()
+            },
+            Duration::from_millis(1),
+            None,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(run.status, RunStatus::RequiresAction);
+    let tool_calls = run.required_action.unwrap().submit_tool_outputs.tool_calls;
+    assert_eq!(tool_calls[0].function.name, "add");
+    assert_eq!(server.requests_to("/threads/thread_1/runs/run_1").len(), 1);
+}
+
+#[tokio::test]
+async fn polled_runs_are_returned_in_progress_on_timeout() {
+    let server = MockServer::start();
+    server
+        .mock(
+            "POST",
+            "/threads/thread_1/runs",
+            MockResponse::json(run("queued", Value::Null)),
+        )
+        .mock(
+            "GET",
+            "/threads/thread_1/runs/run_1",
+            MockResponse::json(run("in_progress", Value::Null)),
+        );
+
+    let run = server
+        .client()
+        .threads()
+        .runs("thread_1")
+        .create_and_poll(
+            CreateRunRequest {
+                assistant_id: "asst_1".into(),
+                ..Default::default()
+            },
+            Duration::from_millis(10),
+            Some(Duration::from_millis(100)),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(run.status, RunStatus::InProgress);
+    assert!(!server
+        .requests_to("/threads/thread_1/runs/run_1")
+        .is_empty());
+}
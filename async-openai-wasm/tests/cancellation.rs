@@ -0,0 +1,204 @@
+#![cfg(feature = "testing")]
+
+use std::time::{Duration, Instant};
+
+use async_openai_wasm::{
+    cancel::CancellationToken,
+    error::OpenAIError,
+    session::{MemorySessionStore, SessionStore},
+    testing::{MockResponse, MockServer},
+    tools::ToolRegistry,
+    types::{
+        ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequest,
+        CreateChatCompletionRequestArgs, CreateRunRequest,
+    },
+};
+use futures::StreamExt;
+use serde_json::{json, Value};
+
+fn run(status: &str, tool_calls: Value) -> Value {
+    let required_action = if tool_calls.is_null() {
+        Value::Null
+    } else {
+        json!({"type": "submit_tool_outputs", "submit_tool_outputs": {"tool_calls": tool_calls}})
+    };
+    json!({
+        "id": "run_1", "object": "thread.run", "created_at": 0, "thread_id": "thread_1",
+        "assistant_id": "asst_1", "status": status, "required_action": required_action,
+        "last_error": null, "expires_at": null, "started_at": null, "cancelled_at": null,
+        "failed_at": null, "completed_at": null, "incomplete_details": null, "model": "gpt-4o",
+        "instructions": "", "tools": [], "metadata": null, "usage": null, "temperature": null,
+        "top_p": null, "max_prompt_tokens": null, "max_completion_tokens": null,
+        "truncation_strategy": null, "tool_choice": null, "parallel_tool_calls": true,
+        "response_format": null,
+    })
+}
+
+fn chat_request() -> CreateChatCompletionRequest {
+    CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn cancelled_clients_do_not_send_requests() {
+    let server = MockServer::start();
+    server.mock(
+        "GET",
+        "/models",
+        MockResponse::json(json!({"object": "list", "data": []})),
+    );
+    let token = CancellationToken::new();
+    let client = server.client().with_cancellation(token.child_token());
+
+    client.models().list().await.unwrap();
+    token.cancel();
+    let result = client.models().list().await;
+
+    assert!(matches!(result, Err(OpenAIError::Cancelled)));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn cancelled_streams_end() {
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/chat/completions",
+        MockResponse::sse([json!({
+            "id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 0, "model": "gpt-4o",
+            "choices": [{"index": 0, "delta": {"content": "Hi"}, "finish_reason": "stop"}],
+        })]),
+    );
+    let token = CancellationToken::new();
+    let client = server.client().with_cancellation(token.clone());
+
+    let mut stream = client.chat().create_stream(chat_request()).await.unwrap();
+    token.cancel();
+
+    assert!(matches!(
+        stream.next().await,
+        Some(Err(OpenAIError::Cancelled))
+    ));
+    assert!(stream.next().await.is_none());
+}
+
+#[tokio::test]
+async fn calls_are_aborted_by_their_own_token() {
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/chat/completions",
+        MockResponse::json(json!({})).with_delay(Duration::from_secs(5)),
+    );
+    let token = CancellationToken::new();
+    let stop = token.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        // e.g. the stop button of a UI
+        stop.cancel();
+    });
+
+    let started = Instant::now();
+    let result = server
+        .client()
+        .chat()
+        .create_with_cancel(chat_request(), &token)
+        .await;
+
+    assert!(matches!(result, Err(OpenAIError::Cancelled)));
+    assert!(started.elapsed() < Duration::from_secs(2));
+}
+
+#[tokio::test]
+async fn calls_with_their_own_token_are_cancelled_with_the_client() {
+    let server = MockServer::start();
+    let client_token = CancellationToken::new();
+    let client = server.client().with_cancellation(client_token.clone());
+    client_token.cancel();
+
+    let mut stream = client
+        .chat()
+        .create_stream_with_cancel(chat_request(), &CancellationToken::new())
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        stream.next().await,
+        Some(Err(OpenAIError::Cancelled))
+    ));
+    assert!(server.requests().is_empty());
+}
+
+#[tokio::test]
+async fn cancelled_runs_are_cancelled_on_the_server() {
+    let tool_calls = json!([{"id": "call_1", "type": "function", "function": {"name": "stop", "arguments": "{}"}}]);
+    let server = MockServer::start();
+    server
+        .mock(
+            "POST",
+            "/threads/thread_1/runs",
+            MockResponse::json(run("requires_action", tool_calls)),
+        )
+        .mock(
+            "POST",
+            "/threads/thread_1/runs/run_1/cancel",
+            MockResponse::json(run("cancelling", Value::Null)),
+        );
+    let token = CancellationToken::new();
+    let client = server.client().with_cancellation(token.clone());
+    let mut registry = ToolRegistry::new();
+    // e.g. the user stops the assistant while its tools run
+    registry.register("stop", "Stop", json!({}), move |_: Value| {
+        token.cancel();
+        async { Ok::<_, String>(json!({})) }
+    });
+    let store = MemorySessionStore::new();
+
+    let result = client
+        .threads()
+        .runs("thread_1")
+        .create_with_tools(
+            CreateRunRequest {
+                assistant_id: "asst_1".into(),
+                ..Default::default()
+            },
+            &registry,
+            &store,
+        )
+        .await;
+
+    assert!(matches!(result, Err(OpenAIError::Cancelled)));
+    assert_eq!(
+        server
+            .requests_to("/threads/thread_1/runs/run_1/cancel")
+            .len(),
+        1
+    );
+    assert!(server
+        .requests_to("/threads/thread_1/runs/run_1/submit_tool_outputs")
+        .is_empty());
+    assert_eq!(
+        store.load("pending_tool_calls/thread_1").await.unwrap(),
+        None
+    );
+}
+
+#[test]
+fn child_tokens_are_cancelled_with_their_parent() {
+    let parent = CancellationToken::new();
+    let first = parent.child_token();
+    let second = parent.child_token();
+
+    first.cancel();
+    assert!(!parent.is_cancelled() && !second.is_cancelled());
+    parent.cancel();
+    assert!(second.is_cancelled());
+    assert!(parent.child_token().is_cancelled());
+}
@@ -0,0 +1,225 @@
+#![cfg(feature = "testing")]
+
+use async_openai_wasm::{
+    testing::{MockResponse, MockServer},
+    types::{
+        ChatCompletionRequestUserMessageArgs, ChatCompletionStreamAccumulator,
+        ChatCompletionStreamExt, CreateChatCompletionRequestArgs,
+        CreateChatCompletionStreamResponse, FinishReason, Role,
+    },
+};
+use futures::StreamExt;
+use serde_json::{json, Value};
+
+fn chunk(choices: Value) -> Value {
+    json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion.chunk",
+        "created": 1,
+        "model": "gpt-4o",
+        "system_fingerprint": "fp_1",
+        "choices": choices,
+    })
+}
+
+fn delta(delta: Value, finish_reason: Value) -> Value {
+    chunk(json!([{"index": 0, "delta": delta, "finish_reason": finish_reason}]))
+}
+
+fn tool_call(index: i32, id: Option<&str>, name: Option<&str>, arguments: &str) -> Value {
+    let mut call = json!({"index": index, "function": {"arguments": arguments}});
+    if let Some(id) = id {
+        call["id"] = json!(id);
+        call["type"] = json!("function");
+    }
+    if let Some(name) = name {
+        call["function"]["name"] = json!(name);
+    }
+    delta(json!({"tool_calls": [call]}), Value::Null)
+}
+
+#[tokio::test]
+async fn tool_call_deltas_are_reassembled() {
+    let mut usage = chunk(json!([]));
+    usage["usage"] = json!({"prompt_tokens": 10, "completion_tokens": 20, "total_tokens": 30});
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/chat/completions",
+        MockResponse::sse([
+            delta(
+                json!({"role": "assistant", "content": "Let me "}),
+                Value::Null,
+            ),
+            delta(json!({"content": "check."}), Value::Null),
+            tool_call(0, Some("call_1"), Some("get_weather"), ""),
+            tool_call(0, None, None, "{\"city\": "),
+            tool_call(1, Some("call_2"), Some("get_time"), "{}"),
+            tool_call(0, None, None, "\"Paris\"}"),
+            delta(json!({}), json!("tool_calls")),
+            usage,
+        ]),
+    );
+
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Weather and time in Paris?")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap();
+    let response = server
+        .client()
+        .chat()
+        .create_stream(request)
+        .await
+        .unwrap()
+        .accumulate()
+        .await
+        .unwrap()
+        .into_response();
+
+    assert_eq!(response.object, "chat.completion");
+    assert_eq!(response.system_fingerprint.as_deref(), Some("fp_1"));
+    assert_eq!(response.usage.unwrap().total_tokens, 30);
+    let choice = &response.choices[0];
+    assert_eq!(choice.finish_reason, Some(FinishReason::ToolCalls));
+    assert_eq!(choice.message.role, Role::Assistant);
+    assert_eq!(choice.message.content.as_deref(), Some("Let me check."));
+    let calls: Vec<_> = choice
+        .message
+        .tool_calls
+        .iter()
+        .flatten()
+        .map(|call| {
+            (
+                call.id.as_str(),
+                call.function.name.as_str(),
+                call.function.arguments.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        calls,
+        [
+            ("call_1", "get_weather", "{\"city\": \"Paris\"}"),
+            ("call_2", "get_time", "{}")
+        ]
+    );
+}
+
+#[test]
+fn choices_are_accumulated_by_index() {
+    let mut accumulator = ChatCompletionStreamAccumulator::new();
+    for choices in [
+        json!([{"index": 1, "delta": {"content": "B"}, "finish_reason": null}]),
+        json!([
+            {"index": 0, "delta": {"content": "A"}, "finish_reason": null},
+            {"index": 1, "delta": {"content": "b"}, "finish_reason": "stop"},
+        ]),
+    ] {
+        let chunk: CreateChatCompletionStreamResponse =
+            serde_json::from_value(chunk(choices)).unwrap();
+        accumulator.push(&chunk);
+    }
+
+    assert_eq!(accumulator.message().unwrap().content.as_deref(), Some("A"));
+    let response = accumulator.response();
+    assert_eq!(response.choices[1].message.content.as_deref(), Some("Bb"));
+    assert_eq!(response.choices[1].finish_reason, Some(FinishReason::Stop));
+    assert_eq!(response.choices[0].finish_reason, None);
+}
+
+#[tokio::test]
+async fn choices_are_demultiplexed() {
+    let chunks = [
+        chunk(json!([
+            {"index": 0, "delta": {"content": "A"}, "finish_reason": null},
+            {"index": 1, "delta": {"content": "B"}, "finish_reason": null},
+        ])),
+        chunk(json!([])),
+        chunk(json!([{"index": 1, "delta": {"content": "b"}, "finish_reason": "stop"}])),
+    ]
+    .map(|chunk| Ok(serde_json::from_value::<CreateChatCompletionStreamResponse>(chunk).unwrap()));
+
+    let choices: Vec<_> = futures::stream::iter(chunks)
+        .demultiplex()
+        .map(|choice| {
+            let choice = choice.unwrap();
+            (choice.index, choice.delta.content.unwrap())
+        })
+        .collect()
+        .await;
+    assert_eq!(
+        choices,
+        [
+            (0, "A".to_string()),
+            (1, "B".to_string()),
+            (1, "b".to_string())
+        ]
+    );
+
+    let response = futures::stream::iter(
+        [json!([
+            {"index": 1, "delta": {"content": "B"}, "finish_reason": "stop"},
+            {"index": 0, "delta": {"tool_calls": []}, "finish_reason": "tool_calls"},
+        ])]
+        .map(|choices| serde_json::from_value(chunk(choices))),
+    )
+    .map(|chunk| chunk.map_err(async_openai_wasm::error::OpenAIError::JSONDeserialize))
+    .accumulate()
+    .await
+    .unwrap()
+    .into_response();
+    assert_eq!(response.choices_text(), [None, Some("B")]);
+    assert_eq!(
+        response.choice(1).unwrap().finish_reason,
+        Some(FinishReason::Stop)
+    );
+    assert!(response.choice(2).is_none());
+}
+
+#[tokio::test]
+async fn usage_is_kept_once_the_stream_is_done() {
+    let mut usage = chunk(json!([]));
+    usage["usage"] = json!({"prompt_tokens": 5, "completion_tokens": 2, "total_tokens": 7});
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/chat/completions",
+        MockResponse::sse([
+            delta(json!({"role": "assistant", "content": "Hi"}), json!("stop")),
+            usage,
+        ]),
+    );
+
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap();
+    let mut stream = server
+        .client()
+        .chat()
+        .create_stream_with_usage(request)
+        .await
+        .unwrap();
+    let first = stream.next().await.unwrap().unwrap();
+    assert_eq!(first.text(), "Hi");
+    assert!(stream.usage().is_none());
+    while let Some(chunk) = stream.next().await {
+        assert!(chunk.unwrap().choices.is_empty());
+    }
+
+    assert_eq!(stream.usage().unwrap().total_tokens, 7);
+    assert_eq!(
+        server.requests()[0].json()["stream_options"],
+        json!({"include_usage": true})
+    );
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "testing")]
+
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc,
+};
+
+use async_openai_wasm::{
+    error::OpenAIError,
+    testing::{MockResponse, MockServer},
+    tools::ToolRegistry,
+    types::{ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs},
+};
+use serde_json::{json, Value};
+
+fn tool_call_completion() -> Value {
+    json!({
+        "id": "chatcmpl-1", "object": "chat.completion", "created": 0, "model": "gpt-4o",
+        "choices": [{
+            "index": 0,
+            "message": {
+                "role": "assistant",
+                "content": null,
+                "tool_calls": [{"id": "call_1", "type": "function", "function": {"name": "launch", "arguments": "{}"}}],
+            },
+            "finish_reason": "tool_calls",
+        }],
+    })
+}
+
+#[tokio::test]
+async fn tools_are_not_called_once_rounds_are_spent() {
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/chat/completions",
+        MockResponse::json(tool_call_completion()),
+    );
+    let launches = Arc::new(AtomicU32::new(0));
+    let mut registry = ToolRegistry::new().with_max_rounds(2);
+    let counter = launches.clone();
+    registry.register("launch", "Launch", json!({}), move |_: Value| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        async { Ok::<_, String>(json!({})) }
+    });
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Launch")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap();
+
+    let error = server
+        .client()
+        .chat()
+        .create_with_tools(request, &registry)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, OpenAIError::InvalidArgument(message) if message.contains("2 rounds")));
+    assert_eq!(server.requests_to("/chat/completions").len(), 3);
+    assert_eq!(launches.load(Ordering::SeqCst), 2);
+}
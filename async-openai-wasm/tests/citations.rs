@@ -0,0 +1,105 @@
+use async_openai_wasm::{
+    citations::{self, Citation, CitationSource},
+    types::{ChatCompletionResponseMessage, ResponseOutputContent, TextData},
+};
+use serde_json::json;
+
+#[test]
+fn chat_url_citations_become_footnotes() {
+    let content = "Café prices rose ([lemonde.fr](https://lemonde.fr/cafe)) — again ([lemonde.fr](https://lemonde.fr/cafe)).";
+    let link = "([lemonde.fr](https://lemonde.fr/cafe))";
+    let start = content.find(link).unwrap();
+    let second = content.rfind(link).unwrap();
+    // indices count characters, `é` and `—` taking more than one byte
+    let chars = |byte: usize| content[..byte].chars().count();
+    let message: ChatCompletionResponseMessage = serde_json::from_value(json!({
+        "role": "assistant",
+        "content": content,
+        "annotations": [
+            {"type": "url_citation", "url_citation": {
+                "start_index": chars(second), "end_index": chars(second + link.len()),
+                "url": "https://lemonde.fr/cafe", "title": "Le Monde",
+            }},
+            {"type": "url_citation", "url_citation": {
+                "start_index": chars(start), "end_index": chars(start + link.len()),
+                "url": "https://lemonde.fr/cafe", "title": "Le Monde",
+            }},
+        ],
+    }))
+    .unwrap();
+
+    let rendered = citations::footnotes(content, &message.citations());
+
+    assert_eq!(
+        rendered,
+        "Café prices rose [^1] — again [^1].\n\n[^1]: [Le Monde](https://lemonde.fr/cafe)"
+    );
+}
+
+#[test]
+fn assistant_placeholders_are_replaced_by_markers() {
+    let text: TextData = serde_json::from_value(json!({
+        "value": "Ünïcode is covered【4:0†source】 and files too【4:1†source】.",
+        "annotations": [
+            {"type": "file_citation", "text": "【4:0†source】", "start_index": 18, "end_index": 30,
+             "file_citation": {"file_id": "file-a", "quote": "covered"}},
+            {"type": "file_citation", "text": "【4:1†source】", "start_index": 44, "end_index": 56,
+             "file_citation": {"file_id": "file-b"}},
+        ],
+    }))
+    .unwrap();
+
+    let rendered =
+        citations::splice(
+            &text.value,
+            &text.citations(),
+            |number, citation| match citation.source {
+                CitationSource::File { file_id, .. } => format!(" [{number}: {file_id}]"),
+                _ => unreachable!(),
+            },
+        );
+
+    assert_eq!(
+        rendered,
+        "Ünïcode is covered [1: file-a] and files too [2: file-b]."
+    );
+}
+
+#[test]
+fn positions_are_clamped_and_overlaps_dropped() {
+    let source = CitationSource::FilePath { file_id: "file-1" };
+    let content: ResponseOutputContent = serde_json::from_value(json!({
+        "type": "output_text", "text": "Done",
+        "annotations": [{"type": "file_path", "file_id": "file-1", "index": 99}],
+    }))
+    .unwrap();
+    assert_eq!(
+        content.citations(),
+        [Citation {
+            start: 99,
+            end: 99,
+            source
+        }]
+    );
+    assert_eq!(
+        citations::footnotes("Done", &content.citations()),
+        "Done[^1]\n\n[^1]: file-1"
+    );
+
+    let overlapping = [
+        Citation {
+            start: 0,
+            end: 3,
+            source,
+        },
+        Citation {
+            start: 2,
+            end: 4,
+            source,
+        },
+    ];
+    assert_eq!(
+        citations::splice("abcd", &overlapping, |_, _| "*".into()),
+        "*d"
+    );
+}
@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+use async_openai_wasm::{
+    config::{AzureConfig, Config},
+    error::OpenAIError,
+    retry::RetryPolicy,
+    Client,
+};
+
+#[test]
+fn builder_applies_config_and_options() {
+    let client = Client::builder()
+        .api_key("sk-test")
+        .api_base("http://localhost:8080/v1")
+        .org_id("org-1")
+        .user_agent("my-app/1.0")
+        .timeout(Duration::from_secs(30))
+        .connect_timeout(Duration::from_secs(5))
+        .retry_policy(RetryPolicy::default())
+        .default_user("user-1")
+        .build()
+        .unwrap();
+    assert_eq!(client.config().api_base(), "http://localhost:8080/v1");
+    assert_eq!(client.config().headers()["OpenAI-Organization"], "org-1");
+
+    let client = Client::builder()
+        .config(AzureConfig::new().with_api_base("https://example.openai.azure.com"))
+        .build()
+        .unwrap();
+    assert_eq!(
+        client.config().api_base(),
+        "https://example.openai.azure.com"
+    );
+}
+
+#[test]
+fn builder_rejects_invalid_settings() {
+    let invalid = |result: Result<_, OpenAIError>| {
+        matches!(
+            result.map(|_: Client<_>| ()),
+            Err(OpenAIError::InvalidArgument(_))
+        )
+    };
+
+    assert!(invalid(Client::builder().api_base("not a url").build()));
+    assert!(invalid(
+        Client::builder()
+            .retry_policy(RetryPolicy::default().with_max_attempts(0))
+            .build()
+    ));
+    assert!(invalid(
+        Client::builder()
+            .http_client(reqwest::Client::new())
+            .connect_timeout(Duration::from_secs(1))
+            .build()
+    ));
+}
@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+use async_openai_wasm::{
+    compat::{check_compat, Provider},
+    types::{
+        ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequest,
+        CreateChatCompletionRequestArgs, UserMessageBuilder,
+    },
+    validation::RequestLimits,
+};
+use serde_json::json;
+
+fn chat_request(model: &str) -> CreateChatCompletionRequestArgs {
+    let mut request = CreateChatCompletionRequestArgs::default();
+    request
+        .model(model)
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello!")
+            .build()
+            .unwrap()
+            .into()]);
+    request
+}
+
+fn fields(request: &CreateChatCompletionRequest, provider: &Provider) -> Vec<String> {
+    check_compat(request, provider)
+        .into_iter()
+        .map(|incompatibility| incompatibility.field)
+        .collect()
+}
+
+#[test]
+fn sampling_fields_are_incompatible_with_reasoning_models() {
+    let request = chat_request("o3-mini")
+        .temperature(0.2)
+        .frequency_penalty(0.5)
+        .seed(7)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        fields(&request, &Provider::openai()),
+        ["frequency_penalty", "temperature"]
+    );
+    assert_eq!(
+        check_compat(&request, &Provider::azure())[1].to_string(),
+        "`temperature`: is not supported by o3-mini, a reasoning model, on Azure OpenAI Service"
+    );
+
+    let request = chat_request("gpt-4o").temperature(0.2).build().unwrap();
+    assert!(check_compat(&request, &Provider::openai()).is_empty());
+}
+
+#[test]
+fn fields_outside_the_supported_ones_are_incompatible() {
+    let request = chat_request("llama3.2")
+        .temperature(0.2)
+        .user("user-1")
+        .n(2)
+        .build()
+        .unwrap();
+
+    assert_eq!(fields(&request, &Provider::ollama()), ["n", "user"]);
+    assert!(check_compat(&request, &Provider::new("vLLM")).is_empty());
+}
+
+#[test]
+fn vision_requests_and_limits_are_checked() {
+    let image = UserMessageBuilder::new()
+        .image_url("https://example.com/cat.png")
+        .build();
+    let request = chat_request("gpt-4o")
+        .messages([image.clone().into(), image.into()])
+        .logit_bias(HashMap::from([("50256".to_string(), json!(-100))]))
+        .build()
+        .unwrap();
+    let provider = Provider::azure().with_limits(RequestLimits::new().with_max_images(1));
+
+    let incompatibilities = check_compat(&request, &provider);
+
+    assert_eq!(incompatibilities.len(), 2);
+    assert_eq!(incompatibilities[0].field, "logit_bias");
+    assert!(incompatibilities[1].message.starts_with(
+        "exceeds the limits of Azure OpenAI Service: invalid args: request has 2 images"
+    ));
+    assert!(check_compat(&request, &Provider::openai()).is_empty());
+}
@@ -0,0 +1,73 @@
+#![cfg(all(feature = "testing", feature = "conformance"))]
+
+use async_openai_wasm::{
+    conformance::{self, Vector},
+    testing::{MockResponse, MockServer},
+};
+use serde_json::json;
+
+/// Response of the mock server to `vector`
+fn response(vector: &Vector) -> MockResponse {
+    match vector.events.is_empty() {
+        true => MockResponse::json(&vector.response),
+        false => MockResponse::sse(&vector.events),
+    }
+}
+
+#[tokio::test]
+async fn vectors_pass_against_their_responses() {
+    let vectors = conformance::vectors();
+    for vector in &vectors {
+        let server = MockServer::start();
+        server.mock(&vector.method, &vector.path, response(vector));
+
+        let report = conformance::run(&server.client(), std::slice::from_ref(vector)).await;
+        assert!(report.passed(), "{report}");
+
+        // the crate sends the request of the vector as is
+        let requests = server.requests_to(&vector.path);
+        assert_eq!(requests.len(), 1, "{}", vector.name);
+        assert_eq!(requests[0].method, vector.method, "{}", vector.name);
+        if let Some(request) = &vector.request {
+            assert_eq!(&requests[0].json(), request, "{}", vector.name);
+        }
+    }
+}
+
+#[tokio::test]
+async fn failures_are_reported_by_vector() {
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/embeddings",
+        MockResponse::json(json!({
+            "object": "list",
+            "model": "text-embedding-3-small",
+            "data": [{"object": "embedding", "index": 0, "embedding": [0.1]}],
+            "usage": {"prompt_tokens": 10, "total_tokens": 10},
+        })),
+    );
+    let vectors: Vec<_> = conformance::vectors()
+        .into_iter()
+        .filter(|vector| vector.name == "embeddings" || vector.name == "models.list")
+        .map(|vector| vector.with_model("my-embedder"))
+        .collect();
+
+    let report = conformance::run(&server.client(), &vectors).await;
+
+    assert!(!report.passed());
+    let failures: Vec<_> = report
+        .failures()
+        .map(|outcome| outcome.name.as_str())
+        .collect();
+    assert_eq!(failures, ["models.list", "embeddings"]);
+    assert_eq!(
+        report.outcomes[1].failure.as_deref(),
+        Some("1 embeddings for 2 inputs")
+    );
+    assert_eq!(
+        server.requests_to("/embeddings")[0].json()["model"],
+        "my-embedder"
+    );
+    assert!(report.to_string().ends_with("0 passed, 2 failed"));
+}
@@ -0,0 +1,159 @@
+#![cfg(feature = "testing")]
+
+use async_openai_wasm::{
+    content_filter::{FilterReason, Sanitizer},
+    testing::{MockResponse, MockServer},
+    types::{
+        ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequest,
+        CreateChatCompletionRequestArgs,
+    },
+};
+use serde_json::json;
+
+fn chat_request(content: &str) -> CreateChatCompletionRequest {
+    CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content(content)
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap()
+}
+
+fn completion(content: &str, finish_reason: &str) -> MockResponse {
+    MockResponse::json(json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion",
+        "created": 0,
+        "model": "gpt-4o",
+        "choices": [{
+            "index": 0,
+            "message": {"role": "assistant", "content": content},
+            "finish_reason": finish_reason,
+        }],
+    }))
+}
+
+fn prompt_filtered() -> MockResponse {
+    MockResponse::json(json!({
+        "error": {
+            "message": "The response was filtered due to the prompt triggering Azure OpenAI's content management policy.",
+            "type": null,
+            "param": "prompt",
+            "code": "content_filter",
+        }
+    }))
+    .with_status(400)
+}
+
+#[tokio::test]
+async fn filtered_prompts_are_rephrased() {
+    let server = MockServer::start();
+    server
+        .mock("POST", "/chat/completions", prompt_filtered())
+        .mock(
+            "POST",
+            "/chat/completions",
+            completion("How to kill a Python process?", "stop"),
+        )
+        .mock(
+            "POST",
+            "/chat/completions",
+            completion("Run `kill <pid>`.", "stop"),
+        );
+
+    let outcome = server
+        .client()
+        .chat()
+        .create_with_content_filter_fallback(
+            chat_request("How do I kill a python?"),
+            &Sanitizer::Rephrase {
+                model: "gpt-4o-mini".into(),
+            },
+            2,
+        )
+        .await
+        .unwrap();
+
+    assert!(!outcome.is_filtered());
+    assert_eq!(outcome.response.first_text(), Some("Run `kill <pid>`."));
+    assert_eq!(outcome.filtered.len(), 1);
+    assert!(
+        matches!(&outcome.filtered[0].reason, FilterReason::Prompt(message) if message.contains("content management"))
+    );
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    let rephrasing = requests[1].json();
+    assert_eq!(rephrasing["model"], "gpt-4o-mini");
+    assert!(rephrasing["messages"][0]["content"]
+        .as_str()
+        .unwrap()
+        .ends_with("How do I kill a python?"));
+    assert_eq!(requests[2].json()["model"], "gpt-4o");
+    assert_eq!(
+        requests[2].json()["messages"][0]["content"],
+        "How to kill a Python process?"
+    );
+}
+
+#[tokio::test]
+async fn filtered_completions_are_retried_until_retries_are_exhausted() {
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/chat/completions",
+        completion("", "content_filter"),
+    );
+
+    let outcome = server
+        .client()
+        .chat()
+        .create_with_content_filter_fallback(
+            chat_request("Tell me a story"),
+            &Sanitizer::transform(|mut request, retry| {
+                request.temperature = Some(retry as f32 / 10.0);
+                request
+            }),
+            2,
+        )
+        .await
+        .unwrap();
+
+    assert!(outcome.is_filtered());
+    assert_eq!(outcome.filtered.len(), 2);
+    assert!(outcome
+        .filtered
+        .iter()
+        .all(|attempt| attempt.reason == FilterReason::Completion));
+    assert_eq!(outcome.request.temperature, Some(0.2));
+
+    let temperatures: Vec<_> = server
+        .requests()
+        .iter()
+        .map(|request| request.json()["temperature"].as_f64())
+        .collect();
+    assert_eq!(temperatures.len(), 3);
+    assert_eq!(temperatures[0], None);
+}
+
+#[tokio::test]
+async fn filtered_prompts_fail_once_retries_are_exhausted() {
+    let server = MockServer::start();
+    server.mock("POST", "/chat/completions", prompt_filtered());
+
+    let error = server
+        .client()
+        .chat()
+        .create_with_content_filter_fallback(
+            chat_request("Hello"),
+            &Sanitizer::transform(|request, _| request),
+            1,
+        )
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("content management"));
+    assert_eq!(server.requests().len(), 2);
+}
@@ -0,0 +1,68 @@
+use std::sync::{Arc, Mutex};
+
+use async_openai_wasm::{
+    config::OpenAIConfig,
+    error::OpenAIError,
+    middleware::{async_trait, RequestInterceptor},
+    types::{
+        AudioInput, ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs,
+        CreateTranscriptionRequestArgs,
+    },
+    Client,
+};
+
+/// Captures requests instead of sending them
+#[derive(Default)]
+struct Capture(Arc<Mutex<Vec<reqwest::Request>>>);
+
+#[async_trait]
+impl RequestInterceptor for Capture {
+    async fn before_request(&self, request: &mut reqwest::Request) -> Result<(), OpenAIError> {
+        let mut captured = reqwest::Request::new(request.method().clone(), request.url().clone());
+        *captured.headers_mut() = request.headers().clone();
+        self.0.lock().unwrap().push(captured);
+        Err(OpenAIError::InvalidArgument("captured".into()))
+    }
+}
+
+#[tokio::test]
+async fn default_headers_and_query_are_sent_with_every_request() {
+    let config = OpenAIConfig::new()
+        .with_api_key("sk-test")
+        .with_header("Helicone-Auth", "Bearer helicone")
+        .with_header("x-portkey-trace-id", "trace")
+        .with_header("invalid header", "ignored")
+        .with_query_param("tenant", "acme");
+    assert_eq!(config.default_headers().len(), 2);
+
+    let captured = Arc::new(Mutex::new(vec![]));
+    let client = Client::with_config(config).with_interceptor(Capture(captured.clone()));
+
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello!")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap();
+    assert!(client.chat().create(request.clone()).await.is_err());
+    assert!(client.chat().create_stream(request).await.is_err());
+
+    let transcription = CreateTranscriptionRequestArgs::default()
+        .model("whisper-1")
+        .file(AudioInput::from_vec_u8("audio.mp3".into(), vec![0; 16]))
+        .build()
+        .unwrap();
+    assert!(client.audio().transcribe(transcription).await.is_err());
+
+    let captured = captured.lock().unwrap();
+    assert_eq!(captured.len(), 3);
+    for request in captured.iter() {
+        assert_eq!(request.headers()["helicone-auth"], "Bearer helicone");
+        assert_eq!(request.headers()["x-portkey-trace-id"], "trace");
+        assert_eq!(request.headers()["authorization"], "Bearer sk-test");
+        assert_eq!(request.url().query(), Some("tenant=acme"));
+    }
+}
@@ -0,0 +1,87 @@
+#![cfg(feature = "testing")]
+
+use async_openai_wasm::{
+    checksum::Algorithm,
+    error::OpenAIError,
+    progress::DownloadProgress,
+    testing::{MockResponse, MockServer},
+};
+
+/// First half of the content, after which the connection is closed
+fn interrupted() -> MockResponse {
+    MockResponse::bytes("0123456789")
+        .with_header("accept-ranges", "bytes")
+        .truncated(5)
+}
+
+async fn download(server: &MockServer) -> (String, Vec<DownloadProgress>) {
+    let mut progress = vec![];
+    let content = server
+        .client()
+        .files()
+        .retrieve_content_with_progress("file-1", |p| progress.push(p))
+        .await
+        .unwrap();
+    (String::from_utf8(content.to_vec()).unwrap(), progress)
+}
+
+#[tokio::test]
+async fn interrupted_download_is_resumed_with_range() {
+    let server = MockServer::start();
+    server
+        .mock("GET", "/files/file-1/content", interrupted())
+        .mock(
+            "GET",
+            "/files/file-1/content",
+            MockResponse::bytes("56789")
+                .with_status(206)
+                .with_header("content-range", "bytes 5-9/10"),
+        );
+    let (content, progress) = download(&server).await;
+
+    assert_eq!(content, "0123456789");
+    let requests = server.requests();
+    assert_eq!(requests[0].header("range"), None);
+    assert_eq!(requests[1].header("range"), Some("bytes=5-"));
+    assert_eq!(
+        progress.last(),
+        Some(&DownloadProgress {
+            received: 10,
+            total: Some(10)
+        })
+    );
+}
+
+#[tokio::test]
+async fn download_starts_over_without_range_support() {
+    let server = MockServer::start();
+    server
+        .mock("GET", "/files/file-1/content", interrupted())
+        .mock(
+            "GET",
+            "/files/file-1/content",
+            MockResponse::bytes("0123456789")
+                .with_header("content-md5", "eB5eJF1ptWaXm4bijSPyxw=="),
+        );
+    let (content, progress) = download(&server).await;
+
+    assert_eq!(content, "0123456789");
+    assert_eq!(progress.last().unwrap().received, 10);
+}
+
+#[tokio::test]
+async fn corrupted_download_fails_with_checksum_mismatch() {
+    let server = MockServer::start();
+    server.mock(
+        "GET",
+        "/files/file-1/content",
+        MockResponse::bytes("0123456789").with_header(
+            "repr-digest",
+            "sha-256=:0T8urdTtWwJ/p3OilSDMDWXON0Nl1kERLeeG+KApwv4=:",
+        ),
+    );
+    let error = server.client().files().content("file-1").await.unwrap_err();
+    assert!(
+        matches!(error, OpenAIError::ChecksumMismatch(mismatch) if mismatch.algorithm == Algorithm::Sha256)
+    );
+}
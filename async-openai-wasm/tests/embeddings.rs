@@ -44,3 +44,36 @@ fn create_embedding_input() {
     let _ = embedding_input(&input);
     let _ = embedding_input(input);
 }
+
+#[test]
+fn decode_base64_embeddings() {
+    use async_openai_wasm::types::{Base64EmbeddingVector, CreateBase64EmbeddingResponse};
+    use base64::engine::{general_purpose, Engine};
+
+    let vector = [0.5f32, -1.25, 3.0];
+    let bytes: Vec<u8> = vector
+        .iter()
+        .flat_map(|float| float.to_le_bytes())
+        .collect();
+    let response: CreateBase64EmbeddingResponse = serde_json::from_value(serde_json::json!({
+        "object": "list",
+        "model": "text-embedding-3-small",
+        "data": [{"index": 0, "object": "embedding", "embedding": general_purpose::STANDARD.encode(bytes)}],
+        "usage": {"prompt_tokens": 2, "total_tokens": 2}
+    }))
+    .unwrap();
+
+    assert_eq!(response.data[0].embedding.dimensions(), 3);
+    assert_eq!(
+        response.vectors().collect::<Result<Vec<_>, _>>().unwrap(),
+        [vector.to_vec()]
+    );
+    let decoded = response.decode().unwrap();
+    assert_eq!(decoded.data[0].embedding, vector);
+    assert_eq!(decoded.usage.total_tokens, 2);
+
+    assert!(Base64EmbeddingVector("not base64!".into())
+        .decode()
+        .is_err());
+    assert!(Base64EmbeddingVector("AAA=".into()).decode().is_err());
+}
@@ -0,0 +1,179 @@
+#![cfg(feature = "testing")]
+
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
+
+use async_openai_wasm::{
+    error::OpenAIError,
+    testing::{MockResponse, MockServer},
+    types::CreateEmbeddingRequestArgs,
+};
+use serde_json::{json, Value};
+
+/// Response embedding each input of `inputs` as the vector `[input]`, in reverse order
+fn response(inputs: &[f32]) -> MockResponse {
+    let data: Vec<Value> = inputs
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(index, input)| json!({"index": index, "object": "embedding", "embedding": [input]}))
+        .collect();
+    MockResponse::json(json!({
+        "object": "list",
+        "model": "text-embedding-3-small",
+        "data": data,
+        "usage": {"prompt_tokens": inputs.len(), "total_tokens": inputs.len()},
+    }))
+}
+
+#[tokio::test]
+async fn chunked_embeddings_keep_the_order_of_inputs() {
+    let server = MockServer::start();
+    server
+        .mock("POST", "/embeddings", response(&[0.0, 1.0]))
+        .mock("POST", "/embeddings", response(&[2.0, 3.0]))
+        .mock("POST", "/embeddings", response(&[4.0]));
+
+    let inputs: Vec<String> = (0..5).map(|i| format!("input {i}")).collect();
+    let request = CreateEmbeddingRequestArgs::default()
+        .model("text-embedding-3-small")
+        .input(inputs)
+        .dimensions(1u32)
+        .build()
+        .unwrap();
+    let response = server
+        .client()
+        .embeddings()
+        .create_chunked(request, 2, 1)
+        .await
+        .unwrap();
+
+    let embeddings: Vec<(u32, f32)> = response
+        .data
+        .iter()
+        .map(|embedding| (embedding.index, embedding.embedding[0]))
+        .collect();
+    assert_eq!(
+        embeddings,
+        [(0, 0.0), (1, 1.0), (2, 2.0), (3, 3.0), (4, 4.0)]
+    );
+    assert_eq!(response.usage.prompt_tokens, 5);
+    assert_eq!(response.usage.total_tokens, 5);
+
+    let requests = server.requests_to("/embeddings");
+    let inputs: Vec<Value> = requests
+        .iter()
+        .map(|request| request.json()["input"].clone())
+        .collect();
+    assert_eq!(
+        inputs,
+        [
+            json!(["input 0", "input 1"]),
+            json!(["input 2", "input 3"]),
+            json!(["input 4"])
+        ]
+    );
+    assert!(requests
+        .iter()
+        .all(|request| request.json()["dimensions"] == 1));
+}
+
+#[tokio::test]
+async fn chunked_embeddings_fail_on_the_first_failed_request() {
+    let server = MockServer::start();
+    server
+        .mock("POST", "/embeddings", response(&[0.0, 1.0]))
+        .mock(
+            "POST",
+            "/embeddings",
+            MockResponse::error(400, "invalid_request_error", "input too long"),
+        );
+
+    let request = CreateEmbeddingRequestArgs::default()
+        .model("text-embedding-3-small")
+        .input(["a", "b", "c", "d"])
+        .build()
+        .unwrap();
+    let error = server
+        .client()
+        .embeddings()
+        .create_chunked(request, 2, 2)
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("input too long"));
+}
+
+/// Ranges handed to a sink, with the index and value of their embeddings
+type Sunk = Vec<(Range<usize>, Vec<(u32, f32)>)>;
+
+#[tokio::test]
+async fn sunk_embeddings_are_handed_over_in_order_from_where_to_resume() {
+    let server = MockServer::start();
+    server
+        .mock("POST", "/embeddings", response(&[2.0, 3.0]))
+        .mock("POST", "/embeddings", response(&[4.0]));
+
+    let request = CreateEmbeddingRequestArgs::default()
+        .model("text-embedding-3-small")
+        .input(["a", "b", "c", "d", "e"])
+        .build()
+        .unwrap();
+    let sunk: Arc<Mutex<Sunk>> = Default::default();
+    let usage = server
+        .client()
+        .embeddings()
+        .create_chunked_with_sink(request, 2, 2, 2, |chunk| {
+            let sunk = sunk.clone();
+            async move {
+                let embeddings = chunk
+                    .embeddings
+                    .iter()
+                    .map(|embedding| (embedding.index, embedding.embedding[0]))
+                    .collect();
+                sunk.lock().unwrap().push((chunk.range, embeddings));
+                Ok(())
+            }
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(
+        *sunk.lock().unwrap(),
+        [
+            (2..4, vec![(2, 2.0), (3, 3.0)]),
+            (4..5, vec![(4, 4.0)])
+        ]
+    );
+    assert_eq!(usage.total_tokens, 3);
+    let inputs: Vec<Value> = server
+        .requests_to("/embeddings")
+        .iter()
+        .map(|request| request.json()["input"].clone())
+        .collect();
+    assert_eq!(inputs, [json!(["c", "d"]), json!(["e"])]);
+}
+
+#[tokio::test]
+async fn failed_sinks_stop_the_run() {
+    let server = MockServer::start();
+    server
+        .mock("POST", "/embeddings", response(&[0.0]))
+        .mock("POST", "/embeddings", response(&[1.0]));
+
+    let request = CreateEmbeddingRequestArgs::default()
+        .model("text-embedding-3-small")
+        .input(["a", "b"])
+        .build()
+        .unwrap();
+    let error = server
+        .client()
+        .embeddings()
+        .create_chunked_with_sink(request, 1, 1, 0, |_| async {
+            Err(OpenAIError::InvalidArgument("disk full".into()))
+        })
+        .await
+        .unwrap_err();
+
+    assert!(error.to_string().contains("disk full"));
+    assert_eq!(server.requests_to("/embeddings").len(), 1);
+}
@@ -0,0 +1,104 @@
+use std::error::Error;
+
+use async_openai_wasm::{
+    error::{FieldError, OpenAIError},
+    types::{CreateChatCompletionRequestArgs, CreateCompletionRequestArgs},
+};
+use serde_json::json;
+
+#[test]
+fn out_of_range_sampling_parameters_name_their_field() {
+    let error = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .temperature(2.5)
+        .build()
+        .unwrap_err();
+    let OpenAIError::InvalidField(field_error) = &error else {
+        panic!("expected an invalid field, got {error:?}");
+    };
+    assert_eq!(
+        field_error,
+        &FieldError::new("temperature", "must be within [0, 2], got 2.5")
+            .with_suggestion("lower it to 2")
+    );
+    assert_eq!(
+        error.to_string(),
+        "invalid args: `temperature` must be within [0, 2], got 2.5; lower it to 2"
+    );
+
+    let error = CreateChatCompletionRequestArgs::default()
+        .n(0)
+        .build()
+        .unwrap_err();
+    assert!(matches!(error, OpenAIError::InvalidField(FieldError { field, .. }) if field == "n"));
+
+    let error = CreateChatCompletionRequestArgs::default()
+        .logit_bias([("50256".to_string(), json!(-150))])
+        .build()
+        .unwrap_err();
+    assert!(
+        matches!(error, OpenAIError::InvalidField(FieldError { field, suggestion, .. })
+            if field == "logit_bias.50256" && suggestion.as_deref() == Some("raise it to -100"))
+    );
+
+    let error = CreateCompletionRequestArgs::default()
+        .logprobs(6)
+        .build()
+        .unwrap_err();
+    assert!(
+        matches!(error, OpenAIError::InvalidField(FieldError { field, .. }) if field == "logprobs")
+    );
+
+    assert!(CreateChatCompletionRequestArgs::default()
+        .temperature(0.0)
+        .top_p(1.0)
+        .presence_penalty(-2.0)
+        .build()
+        .is_ok());
+}
+
+#[test]
+fn wrapped_errors_are_sources() {
+    let json_error = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+    let error = OpenAIError::JSONDeserialize(json_error);
+    assert!(error
+        .source()
+        .unwrap()
+        .downcast_ref::<serde_json::Error>()
+        .is_some());
+
+    let error: OpenAIError = FieldError::new("model", "is required but not set").into();
+    assert!(error.source().is_none());
+    assert_eq!(
+        error.to_string(),
+        "invalid args: `model` is required but not set"
+    );
+}
+
+#[test]
+fn best_of_is_checked_against_n_and_stream() {
+    let error = CreateCompletionRequestArgs::default()
+        .n(3)
+        .best_of(2)
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "invalid args: `best_of` must be at least `n`, got 2 < 3; raise it to 3"
+    );
+
+    let error = CreateCompletionRequestArgs::default()
+        .best_of(2)
+        .stream(true)
+        .build()
+        .unwrap_err();
+    assert!(
+        matches!(error, OpenAIError::InvalidField(FieldError { field, .. }) if field == "best_of")
+    );
+
+    assert!(CreateCompletionRequestArgs::default()
+        .n(2)
+        .best_of(2)
+        .build()
+        .is_ok());
+}
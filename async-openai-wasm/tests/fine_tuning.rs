@@ -0,0 +1,45 @@
+#![cfg(feature = "testing")]
+
+use async_openai_wasm::{
+    testing::{MockResponse, MockServer},
+    types::FineTuningJobStatus,
+};
+use serde_json::{json, Value};
+
+fn job(status: &str) -> Value {
+    json!({
+        "id": "ftjob-1", "object": "fine_tuning.job", "created_at": 0, "model": "gpt-4o-mini",
+        "organization_id": "org-1", "status": status, "training_file": "file-abc123",
+        "result_files": [], "seed": 42, "hyperparameters": {"n_epochs": "auto"},
+    })
+}
+
+#[tokio::test]
+async fn jobs_are_paused_and_resumed() {
+    let server = MockServer::start();
+    server
+        .mock(
+            "POST",
+            "/fine_tuning/jobs/ftjob-1/pause",
+            MockResponse::json(job("paused")),
+        )
+        .mock(
+            "POST",
+            "/fine_tuning/jobs/ftjob-1/resume",
+            MockResponse::json(job("running")),
+        );
+    let client = server.client();
+
+    let job = client.fine_tuning().pause("ftjob-1").await.unwrap();
+    assert_eq!(job.status, FineTuningJobStatus::Paused);
+    assert!(job.status.can_resume());
+
+    let job = client.fine_tuning().resume("ftjob-1").await.unwrap();
+    assert_eq!(job.status, FineTuningJobStatus::Running);
+    assert!(job.status.can_pause());
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].path, "/fine_tuning/jobs/ftjob-1/pause");
+    assert_eq!(requests[1].path, "/fine_tuning/jobs/ftjob-1/resume");
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "testing")]
+
+use std::time::Duration;
+
+use async_openai_wasm::{
+    retry::RetryPolicy,
+    testing::{MockResponse, MockServer},
+    types::{ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs, ServiceTier},
+};
+use serde_json::json;
+
+fn resource_unavailable() -> MockResponse {
+    MockResponse {
+        status: 429,
+        ..MockResponse::json(json!({"error": {
+            "message": "Resource unavailable, please try again later", "type": "invalid_request_error",
+            "param": null, "code": "resource_unavailable",
+        }}))
+    }
+}
+
+async fn create_flex(policy: RetryPolicy, server: &MockServer) -> Vec<serde_json::Value> {
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("o3")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello!")
+            .build()
+            .unwrap()
+            .into()])
+        .service_tier(ServiceTier::Flex)
+        .build()
+        .unwrap();
+    let _ = server
+        .client()
+        .with_retry_policy(policy.with_initial_backoff(Duration::from_millis(1)))
+        .chat()
+        .create(request)
+        .await;
+    server
+        .requests_to("/chat/completions")
+        .iter()
+        .map(|request| request.json()["service_tier"].clone())
+        .collect()
+}
+
+#[tokio::test]
+async fn flex_requests_fall_back_to_the_default_tier() {
+    let server = MockServer::start();
+    server.mock("POST", "/chat/completions", resource_unavailable());
+    server.mock("POST", "/chat/completions", resource_unavailable());
+    server.mock(
+        "POST",
+        "/chat/completions",
+        MockResponse::json(json!({
+            "id": "chatcmpl-1", "object": "chat.completion", "created": 0, "model": "o3",
+            "service_tier": "default",
+            "choices": [{
+                "index": 0, "message": {"role": "assistant", "content": "Hi"},
+                "finish_reason": "stop", "logprobs": null,
+            }],
+        })),
+    );
+
+    let tiers = create_flex(RetryPolicy::default().with_flex_fallback_after(2), &server).await;
+
+    assert_eq!(tiers, [json!("flex"), json!("flex"), json!("default")]);
+}
+
+#[tokio::test]
+async fn flex_requests_are_retried_at_the_flex_tier_by_default() {
+    let server = MockServer::start();
+    for _ in 0..3 {
+        server.mock("POST", "/chat/completions", resource_unavailable());
+    }
+
+    let tiers = create_flex(RetryPolicy::default().with_max_attempts(3), &server).await;
+
+    assert_eq!(tiers, [json!("flex"), json!("flex"), json!("flex")]);
+}
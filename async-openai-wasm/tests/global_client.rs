@@ -0,0 +1,35 @@
+#![cfg(feature = "testing")]
+
+use async_openai_wasm::{
+    config::Config,
+    testing::{MockResponse, MockServer},
+    Client,
+};
+use serde_json::json;
+
+// the global client is shared by the whole test binary, hence a single test
+#[tokio::test]
+async fn global_client_is_shared_until_replaced() {
+    let default_base = Client::global().config().api_base().to_string();
+    assert_eq!(default_base, Client::new().config().api_base());
+
+    let server = MockServer::start();
+    server.mock(
+        "GET",
+        "/models",
+        MockResponse::json(json!({"object": "list", "data": []})),
+    );
+    let previous = Client::set_global(server.client());
+    assert_eq!(previous.unwrap().config().api_base(), default_base);
+
+    let models = Client::global().models().list().await.unwrap();
+    assert!(models.data.is_empty());
+    assert_eq!(server.requests().len(), 1);
+
+    let other = MockServer::start();
+    Client::set_global(other.client());
+    assert_eq!(
+        Client::global().config().api_base(),
+        other.client().config().api_base()
+    );
+}
@@ -0,0 +1,100 @@
+#![cfg(feature = "testing")]
+
+use async_openai_wasm::{
+    testing::{MockResponse, MockServer},
+    types::{
+        CreateImageEditRequestArgs, CreateImageRequestArgs, ImageBackground, ImageInput,
+        ImageModel, ImageOutputFormat, ImageQuality, ImageSize,
+    },
+};
+use serde_json::json;
+
+fn images_response() -> MockResponse {
+    MockResponse::json(json!({
+        "created": 0,
+        "data": [{"b64_json": "aW1hZ2U="}],
+        "usage": {"input_tokens": 50, "output_tokens": 272, "total_tokens": 322},
+    }))
+}
+
+#[tokio::test]
+async fn gpt_image_1_generations() {
+    let server = MockServer::start();
+    server.mock("POST", "/images/generations", images_response());
+
+    let request = CreateImageRequestArgs::default()
+        .model(ImageModel::GptImage1)
+        .prompt("A cat sticker")
+        .quality(ImageQuality::High)
+        .size(ImageSize::S1024x1536)
+        .background(ImageBackground::Transparent)
+        .output_format(ImageOutputFormat::Webp)
+        .output_compression(80)
+        .build()
+        .unwrap();
+    let response = server.client().images().create(request).await.unwrap();
+
+    assert_eq!(response.usage.unwrap().total_tokens, 322);
+    assert_eq!(
+        server.requests()[0].json(),
+        json!({
+            "model": "gpt-image-1",
+            "prompt": "A cat sticker",
+            "quality": "high",
+            "size": "1024x1536",
+            "background": "transparent",
+            "output_format": "webp",
+            "output_compression": 80,
+        })
+    );
+}
+
+#[tokio::test]
+async fn edits_of_several_images() {
+    let server = MockServer::start();
+    server.mock("POST", "/images/edits", images_response());
+
+    let request = CreateImageEditRequestArgs::default()
+        .model(ImageModel::GptImage1)
+        .image([
+            ImageInput::from_vec_u8("body-lotion.png".into(), b"lotion".to_vec()),
+            ImageInput::from_vec_u8("soap.png".into(), b"soap".to_vec()),
+        ])
+        .prompt("A gift basket of these items")
+        .quality(ImageQuality::Low)
+        .output_format(ImageOutputFormat::Jpeg)
+        .build()
+        .unwrap();
+    server.client().images().create_edit(request).await.unwrap();
+
+    let body = String::from_utf8_lossy(&server.requests()[0].body).into_owned();
+    assert_eq!(body.matches("name=\"image[]\"").count(), 2);
+    assert!(body.contains("filename=\"soap.png\""));
+    assert!(!body.contains("name=\"image\""));
+    for (name, value) in [
+        ("model", "gpt-image-1"),
+        ("quality", "low"),
+        ("output_format", "jpeg"),
+    ] {
+        assert!(body.contains(&format!("name=\"{name}\"\r\n\r\n{value}\r\n")));
+    }
+}
+
+#[tokio::test]
+async fn edits_of_one_image() {
+    let server = MockServer::start();
+    server.mock("POST", "/images/edits", images_response());
+
+    let request = CreateImageEditRequestArgs::default()
+        .image(ImageInput::from_vec_u8(
+            "otter.png".into(),
+            b"otter".to_vec(),
+        ))
+        .prompt("An otter wearing a beret")
+        .build()
+        .unwrap();
+    server.client().images().create_edit(request).await.unwrap();
+
+    let body = String::from_utf8_lossy(&server.requests()[0].body).into_owned();
+    assert_eq!(body.matches("name=\"image\"").count(), 1);
+}
@@ -0,0 +1,121 @@
+#![cfg(feature = "testing")]
+
+use async_openai_wasm::{
+    error::OpenAIError,
+    json_mode::{enforce_json_mode, JSON_INSTRUCTION},
+    testing::{MockResponse, MockServer},
+    types::{
+        ChatCompletionRequestSystemMessageArgs, ChatCompletionRequestUserMessageArgs,
+        CreateChatCompletionRequest, CreateChatCompletionRequestArgs, FinishReason,
+    },
+};
+use serde::Deserialize;
+use serde_json::json;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Capital {
+    country: String,
+    capital: String,
+}
+
+fn chat_request(content: &str) -> CreateChatCompletionRequest {
+    CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o-mini")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content(content)
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap()
+}
+
+fn completion(content: &str, finish_reason: &str) -> MockResponse {
+    MockResponse::json(json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion",
+        "created": 0,
+        "model": "gpt-4o-mini",
+        "choices": [{
+            "index": 0,
+            "message": {"role": "assistant", "content": content},
+            "finish_reason": finish_reason,
+        }],
+    }))
+}
+
+#[tokio::test]
+async fn json_answers_are_parsed() {
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/chat/completions",
+        completion(r#"{"country": "France", "capital": "Paris"}"#, "stop"),
+    );
+
+    let capital: Capital = server
+        .client()
+        .chat()
+        .create_json(chat_request("What is the capital of France?"))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        capital,
+        Capital {
+            country: "France".into(),
+            capital: "Paris".into()
+        }
+    );
+    let request = server.requests()[0].json();
+    assert_eq!(request["response_format"], json!({"type": "json_object"}));
+    assert_eq!(request["messages"][0]["role"], "system");
+    assert_eq!(request["messages"][0]["content"], JSON_INSTRUCTION);
+}
+
+#[tokio::test]
+async fn truncated_answers_fail_with_their_content() {
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/chat/completions",
+        completion(r#"{"country": "France", "capi"#, "length"),
+    );
+
+    let error = server
+        .client()
+        .chat()
+        .create_json::<Capital>(chat_request("Answer in json: capital of France?"))
+        .await
+        .unwrap_err();
+
+    let OpenAIError::InvalidJsonResponse(error) = error else {
+        panic!("unexpected error {error}");
+    };
+    assert_eq!(error.content, r#"{"country": "France", "capi"#);
+    assert_eq!(error.finish_reason, Some(FinishReason::Length));
+    let request = server.requests()[0].json();
+    assert_eq!(request["messages"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn instruction_is_appended_to_the_system_message() {
+    let mut request = chat_request("Capital of France?");
+    request.messages.insert(
+        0,
+        ChatCompletionRequestSystemMessageArgs::default()
+            .content("You are a geographer.")
+            .build()
+            .unwrap()
+            .into(),
+    );
+
+    assert!(enforce_json_mode(&mut request));
+    assert!(!enforce_json_mode(&mut request));
+    assert_eq!(request.messages.len(), 2);
+    let system = serde_json::to_value(&request.messages[0]).unwrap();
+    assert_eq!(
+        system["content"],
+        format!("You are a geographer.\n\n{JSON_INSTRUCTION}")
+    );
+}
@@ -0,0 +1,64 @@
+#![allow(deprecated)]
+use async_openai_wasm::types::{
+    ChatCompletionFunctionsArgs, ChatCompletionRequestAssistantMessageArgs,
+    ChatCompletionRequestFunctionMessageArgs, ChatCompletionRequestMessage,
+    ChatCompletionRequestUserMessageArgs, ChatCompletionToolChoiceOption,
+    CreateChatCompletionRequestArgs, FunctionCall,
+};
+use serde_json::json;
+
+#[test]
+fn functions_are_migrated_to_tools() {
+    let mut request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([
+            ChatCompletionRequestUserMessageArgs::default()
+                .content("What's the weather in Paris?")
+                .build()
+                .unwrap()
+                .into(),
+            ChatCompletionRequestAssistantMessageArgs::default()
+                .function_call(FunctionCall {
+                    name: "get_weather".into(),
+                    arguments: r#"{"city":"Paris"}"#.into(),
+                })
+                .build()
+                .unwrap()
+                .into(),
+            ChatCompletionRequestFunctionMessageArgs::default()
+                .name("get_weather")
+                .content(r#"{"forecast":"sunny"}"#)
+                .build()
+                .unwrap()
+                .into(),
+        ])
+        .functions([ChatCompletionFunctionsArgs::default()
+            .name("get_weather")
+            .parameters(json!({"type": "object", "properties": {"city": {"type": "string"}}}))
+            .build()
+            .unwrap()])
+        .function_call("get_weather")
+        .build()
+        .unwrap();
+
+    assert!(request.uses_legacy_functions());
+    request.migrate_functions_to_tools();
+    assert!(!request.uses_legacy_functions());
+
+    assert_eq!(
+        request.tools.as_ref().unwrap()[0].function.name,
+        "get_weather"
+    );
+    assert!(matches!(
+        &request.tool_choice,
+        Some(ChatCompletionToolChoiceOption::Named(choice)) if choice.function.name == "get_weather"
+    ));
+    let ChatCompletionRequestMessage::Assistant(assistant) = &request.messages[1] else {
+        panic!("expected an assistant message");
+    };
+    let call_id = &assistant.tool_calls.as_ref().unwrap()[0].id;
+    assert!(matches!(
+        &request.messages[2],
+        ChatCompletionRequestMessage::Tool(tool) if &tool.tool_call_id == call_id
+    ));
+}
@@ -0,0 +1,113 @@
+#![cfg(feature = "testing")]
+
+use async_openai_wasm::{
+    error::OpenAIError,
+    testing::{MockResponse, MockServer},
+    types::{
+        AssistantStreamEvent, ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequest,
+        CreateChatCompletionRequestArgs, CreateRunRequest,
+    },
+    Client,
+};
+use futures::StreamExt;
+use serde_json::json;
+
+fn chat_request() -> CreateChatCompletionRequest {
+    CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o-mini")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello!")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap()
+}
+
+fn chunk(content: &str) -> String {
+    let chunk = json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion.chunk",
+        "created": 0,
+        "model": "gpt-4o-mini",
+        "choices": [{"index": 0, "delta": {"content": content}, "finish_reason": null}],
+    });
+    format!("data: {chunk}\n\n")
+}
+
+/// Chat completion stream with an event injected by a proxy, and a truncated one
+fn chat_stream() -> MockResponse {
+    MockResponse::raw_sse(format!(
+        "{}event: keepalive\ndata: {{}}\n\ndata: {{\"id\": \n\n{}data: [DONE]\n\n",
+        chunk("Hel"),
+        chunk("lo")
+    ))
+}
+
+async fn stream_chat(
+    client: &Client<impl async_openai_wasm::config::Config>,
+) -> Vec<Result<String, OpenAIError>> {
+    client
+        .chat()
+        .create_stream(chat_request())
+        .await
+        .unwrap()
+        .map(|chunk| chunk.map(|chunk| chunk.text().into_owned()))
+        .collect()
+        .await
+}
+
+#[tokio::test]
+async fn invalid_events_end_streams_by_default() {
+    let server = MockServer::start();
+    server.mock("POST", "/chat/completions", chat_stream());
+
+    let chunks = stream_chat(&server.client()).await;
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks[0].as_deref().unwrap(), "Hel");
+    match &chunks[1] {
+        Err(OpenAIError::InvalidStreamEvent(invalid)) => {
+            assert_eq!(invalid.event, "keepalive");
+            assert_eq!(invalid.data, "{}");
+            assert!(invalid.reason.contains("missing field"));
+        }
+        other => panic!("expected an invalid stream event, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn lenient_streams_skip_invalid_events() {
+    let server = MockServer::start();
+    server.mock("POST", "/chat/completions", chat_stream());
+
+    let client = server.client().with_lenient_streaming(true);
+    let text: String = stream_chat(&client)
+        .await
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(text, "Hello");
+}
+
+#[tokio::test]
+async fn lenient_assistant_streams_skip_unknown_events() {
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/threads/thread_1/runs",
+        MockResponse::raw_sse(
+            "event: thread.future\ndata: {}\n\nevent: thread.run.created\ndata: {\"id\": 1}\n\nevent: done\ndata: [DONE]\n\n",
+        ),
+    );
+    let client = server.client().with_lenient_streaming(true);
+    let threads = client.threads();
+    let runs = threads.runs("thread_1");
+
+    let request = CreateRunRequest {
+        assistant_id: "asst_1".into(),
+        ..Default::default()
+    };
+    let events: Vec<_> = runs.create_stream(request).await.unwrap().collect().await;
+    assert_eq!(events.len(), 1);
+    assert!(matches!(events[0], Ok(AssistantStreamEvent::Done(_))));
+}
@@ -0,0 +1,106 @@
+#![cfg(feature = "request-lint")]
+use async_openai_wasm::{
+    lint::{check_round_trip, lint_request},
+    types::{
+        ChatCompletionRequestUserMessageArgs, ChatCompletionStreamOptions, ChatCompletionToolArgs,
+        CreateChatCompletionRequest, CreateChatCompletionRequestArgs, FunctionObjectArgs,
+    },
+};
+
+fn request() -> CreateChatCompletionRequestArgs {
+    let mut request = CreateChatCompletionRequestArgs::default();
+    request
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello!")
+            .build()
+            .unwrap()
+            .into()]);
+    request
+}
+
+#[test]
+fn well_formed_request_has_no_lints() {
+    let request: CreateChatCompletionRequest = request().build().unwrap();
+    assert_eq!(lint_request(&request), vec![]);
+    assert!(check_round_trip(&request).is_ok());
+}
+
+#[test]
+fn misconfigured_request_is_linted() {
+    let request = request()
+        .tools(vec![])
+        .stream_options(ChatCompletionStreamOptions {
+            include_usage: true,
+        })
+        .top_logprobs(3)
+        .build()
+        .unwrap();
+    let mut fields: Vec<_> = lint_request(&request)
+        .into_iter()
+        .map(|lint| lint.field)
+        .collect();
+    fields.sort();
+    assert_eq!(fields, ["stream_options", "tools", "top_logprobs"]);
+}
+
+#[test]
+fn parallel_calls_of_strict_functions_are_linted() {
+    let tool = ChatCompletionToolArgs::default()
+        .function(
+            FunctionObjectArgs::default()
+                .name("get_weather")
+                .strict(true)
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+    let parallel = request().tools([tool.clone()]).build().unwrap();
+    let fields: Vec<_> = lint_request(&parallel)
+        .into_iter()
+        .map(|lint| lint.field)
+        .collect();
+    assert_eq!(fields, ["parallel_tool_calls"]);
+
+    let sequential = request()
+        .tools([tool])
+        .parallel_tool_calls(false)
+        .build()
+        .unwrap();
+    assert_eq!(lint_request(&sequential), vec![]);
+}
+
+#[test]
+fn schemas_of_strict_functions_are_linted() {
+    let tool = ChatCompletionToolArgs::default()
+        .function(
+            FunctionObjectArgs::default()
+                .name("get_weather")
+                .parameters(serde_json::json!({
+                    "type": "object",
+                    "properties": {"city": {"type": "string", "format": "city"}},
+                    "required": ["city"],
+                    "additionalProperties": false,
+                }))
+                .strict(true)
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+    let request = request()
+        .tools([tool])
+        .parallel_tool_calls(false)
+        .build()
+        .unwrap();
+    let lints = lint_request(&request);
+    assert_eq!(lints.len(), 1);
+    assert_eq!(
+        lints[0].to_string(),
+        "`tools`: schema of strict function `get_weather`: `#/properties/city/format` \
+         is not one of the formats strict structured outputs support; use one of date-time, time, date, \
+         duration, email, hostname, ipv4, ipv6, uuid, or state the constraint in `description` \
+         and check it after parsing the output"
+    );
+}
@@ -0,0 +1,141 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+
+use async_openai_wasm::{
+    config::OpenAIConfig,
+    error::OpenAIError,
+    types::{ChatCompletionRequestUserMessage, CreateChatCompletionRequest},
+    Client,
+};
+use serde_json::{json, Value};
+
+/// Answer chat completions with the content of their user message without its `map: ` prefix,
+/// or the first letters of the answers to `reduce: aa+bb`, `ab`
+fn handle(stream: TcpStream) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    loop {
+        let mut request_line = String::new();
+        if reader.read_line(&mut request_line).unwrap_or(0) == 0 {
+            return;
+        }
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+        let request: Value = serde_json::from_slice(&body).unwrap();
+        let prompt = request["messages"][0]["content"].as_str().unwrap();
+        let content = match prompt.strip_prefix("reduce: ") {
+            Some(answers) => answers.split('+').map(|answer| &answer[..1]).collect(),
+            None => prompt.strip_prefix("map: ").unwrap().to_string(),
+        };
+
+        let response = json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "gpt-4o-mini",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": content},
+                "finish_reason": "stop",
+            }],
+        })
+        .to_string();
+        write!(
+            &stream,
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{response}",
+            response.len()
+        )
+        .unwrap();
+    }
+}
+
+fn client() -> Client<OpenAIConfig> {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let api_base = format!("http://{}/v1", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let stream = stream.unwrap();
+            std::thread::spawn(move || handle(stream));
+        }
+    });
+    Client::with_config(OpenAIConfig::new().with_api_base(api_base))
+}
+
+fn request(content: String) -> CreateChatCompletionRequest {
+    CreateChatCompletionRequest {
+        model: "gpt-4o-mini".into(),
+        messages: vec![ChatCompletionRequestUserMessage {
+            content: content.into(),
+            ..Default::default()
+        }
+        .into()],
+        ..Default::default()
+    }
+}
+
+fn map_prompt(item: &str) -> CreateChatCompletionRequest {
+    request(format!("map: {item}"))
+}
+
+fn reduce_prompt(answers: &[String]) -> CreateChatCompletionRequest {
+    request(format!("reduce: {}", answers.join("+")))
+}
+
+/// Prompt tokens of the reduce request of `answers`, counted like the crate does
+fn reduce_tokens(answers: &[&str]) -> usize {
+    let answers: Vec<String> = answers.iter().map(|answer| answer.to_string()).collect();
+    let request = reduce_prompt(&answers);
+    #[cfg(feature = "tokens")]
+    let tokens = async_openai_wasm::types::token_count(&request.model, &request.messages).get();
+    // estimate of the crate without the tokens feature: a token per 4 bytes of JSON, plus the tokens of the chat format
+    #[cfg(not(feature = "tokens"))]
+    let tokens = 3 + request
+        .messages
+        .iter()
+        .map(|message| 3 + (serde_json::to_string(message).unwrap().len() + 3) / 4)
+        .sum::<usize>();
+    tokens
+}
+
+#[tokio::test]
+async fn answers_are_reduced_in_one_request_within_budget() {
+    let response = client()
+        .chat()
+        .map_reduce(["a", "b", "c", "d"], map_prompt, reduce_prompt, 2)
+        .await
+        .unwrap();
+    assert_eq!(response.first_text(), Some("abcd"));
+}
+
+#[tokio::test]
+async fn answers_over_budget_are_reduced_in_rounds() {
+    let items = ["aaaaaaaa", "bbbbbbbb", "cccccccc", "dddddddd"];
+    // enough for two answers of the items at a time, but not for all of them
+    let budget = reduce_tokens(&items[..2]);
+    assert!(reduce_tokens(&items[..3]) > budget);
+    let response = client()
+        .chat()
+        .map_reduce_with_budget(items, map_prompt, reduce_prompt, 4, budget)
+        .await
+        .unwrap();
+    assert_eq!(response.first_text(), Some("ac"));
+
+    let error = client()
+        .chat()
+        .map_reduce_with_budget(items, map_prompt, reduce_prompt, 4, 1)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, OpenAIError::InvalidArgument(_)));
+}
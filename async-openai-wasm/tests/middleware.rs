@@ -0,0 +1,138 @@
+use std::sync::{Arc, Mutex};
+
+use async_openai_wasm::{
+    error::OpenAIError,
+    middleware::{async_trait, RequestInterceptor},
+    types::CreateChatCompletionRequestArgs,
+    Client,
+};
+
+struct Record {
+    name: &'static str,
+    seen: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl RequestInterceptor for Record {
+    async fn before_request(&self, request: &mut reqwest::Request) -> Result<(), OpenAIError> {
+        self.seen
+            .lock()
+            .unwrap()
+            .push(format!("{} {}", self.name, request.url().path()));
+        request
+            .headers_mut()
+            .insert("x-intercepted-by", self.name.parse().unwrap());
+        Ok(())
+    }
+}
+
+struct Block;
+
+#[async_trait]
+impl RequestInterceptor for Block {
+    async fn before_request(&self, request: &mut reqwest::Request) -> Result<(), OpenAIError> {
+        let intercepted_by = request.headers()["x-intercepted-by"].to_str().unwrap();
+        Err(OpenAIError::InvalidArgument(format!(
+            "blocked after {intercepted_by}"
+        )))
+    }
+}
+
+#[tokio::test]
+async fn interceptors_are_chained_in_order() {
+    let seen = Arc::new(Mutex::new(vec![]));
+    let client = Client::new()
+        .with_interceptor(Record {
+            name: "first",
+            seen: seen.clone(),
+        })
+        .with_interceptor(Record {
+            name: "second",
+            seen: seen.clone(),
+        })
+        .with_interceptor(Block);
+
+    let error = client.models().list().await.unwrap_err();
+    assert!(
+        matches!(error, OpenAIError::InvalidArgument(message) if message == "blocked after second")
+    );
+
+    // streams are intercepted too
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([])
+        .build()
+        .unwrap();
+    assert!(client.chat().create_stream(request).await.is_err());
+
+    assert_eq!(
+        *seen.lock().unwrap(),
+        [
+            "first /v1/models",
+            "second /v1/models",
+            "first /v1/chat/completions",
+            "second /v1/chat/completions"
+        ]
+    );
+}
+
+#[cfg(feature = "testing")]
+type Responses = Arc<Mutex<Vec<(u16, Option<String>)>>>;
+
+#[cfg(feature = "testing")]
+struct RecordResponses(Responses);
+
+#[cfg(feature = "testing")]
+#[async_trait]
+impl RequestInterceptor for RecordResponses {
+    async fn after_response(&self, response: &reqwest::Response) -> Result<(), OpenAIError> {
+        let request_id = response
+            .headers()
+            .get("x-request-id")
+            .map(|id| id.to_str().unwrap().to_string());
+        self.0
+            .lock()
+            .unwrap()
+            .push((response.status().as_u16(), request_id));
+        Ok(())
+    }
+}
+
+#[cfg(feature = "testing")]
+#[tokio::test]
+async fn responses_of_streams_are_intercepted() {
+    use async_openai_wasm::testing::{MockResponse, MockServer};
+    use futures::StreamExt;
+
+    let server = MockServer::start();
+    server
+        .mock(
+            "POST",
+            "/chat/completions",
+            MockResponse::raw_sse("data: [DONE]\n\n").with_header("x-request-id", "req_1"),
+        )
+        .mock(
+            "POST",
+            "/chat/completions",
+            MockResponse::error(400, "invalid_request_error", "bad request"),
+        );
+    let seen = Arc::new(Mutex::new(vec![]));
+    let client = server
+        .client()
+        .with_interceptor(RecordResponses(seen.clone()));
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([])
+        .build()
+        .unwrap();
+
+    let mut stream = client.chat().create_stream(request.clone()).await.unwrap();
+    assert!(stream.next().await.is_none());
+    let mut stream = client.chat().create_stream(request).await.unwrap();
+    assert!(stream.next().await.unwrap().is_err());
+
+    assert_eq!(
+        *seen.lock().unwrap(),
+        [(200, Some("req_1".to_string())), (400, None)]
+    );
+}
@@ -0,0 +1,167 @@
+#![cfg(feature = "testing")]
+
+use std::time::Duration;
+
+use async_openai_wasm::{
+    error::OpenAIError,
+    retry::RetryPolicy,
+    testing::{MockResponse, MockServer},
+    types::{
+        ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequest,
+        CreateChatCompletionRequestArgs, CreateFileRequestArgs, FileInput, FilePurpose,
+    },
+};
+use futures::StreamExt;
+use serde_json::json;
+
+fn chat_request() -> CreateChatCompletionRequest {
+    CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o-mini")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello!")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap()
+}
+
+fn completion(content: &str) -> serde_json::Value {
+    json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion",
+        "created": 0,
+        "model": "gpt-4o-mini",
+        "choices": [{
+            "index": 0,
+            "message": {"role": "assistant", "content": content},
+            "finish_reason": "stop",
+        }],
+    })
+}
+
+fn chunk(content: &str) -> serde_json::Value {
+    json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion.chunk",
+        "created": 0,
+        "model": "gpt-4o-mini",
+        "choices": [{"index": 0, "delta": {"content": content}, "finish_reason": null}],
+    })
+}
+
+#[tokio::test]
+async fn json_responses_and_captured_requests() {
+    let server = MockServer::start();
+    server.mock("POST", "/chat/completions", MockResponse::json(completion("Hi!")));
+
+    let response = server.client().chat().create(chat_request()).await.unwrap();
+    assert_eq!(response.first_text(), Some("Hi!"));
+
+    let requests = server.requests_to("/chat/completions");
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].header("authorization"), Some("Bearer sk-test"));
+    assert_eq!(requests[0].json()["messages"][0]["content"], "Hello!");
+}
+
+#[tokio::test]
+async fn sse_streams() {
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/chat/completions",
+        MockResponse::sse([chunk("Hel"), chunk("lo")]),
+    );
+
+    let stream = server.client().chat().create_stream(chat_request()).await.unwrap();
+    let chunks: Vec<_> = stream.collect().await;
+    let text: String = chunks
+        .into_iter()
+        .map(|chunk| chunk.unwrap().text().into_owned())
+        .collect();
+    assert_eq!(text, "Hello");
+    assert_eq!(server.requests()[0].json()["stream"], true);
+}
+
+#[tokio::test]
+async fn responses_are_sent_in_turn() {
+    let server = MockServer::start();
+    server
+        .mock(
+            "POST",
+            "/chat/completions",
+            MockResponse::error(429, "rate_limit_exceeded", "Slow down"),
+        )
+        .mock("POST", "/chat/completions", MockResponse::json(completion("Hi!")));
+
+    let client = server.client().with_retry_policy(
+        RetryPolicy::default()
+            .with_max_attempts(2)
+            .with_initial_backoff(Duration::ZERO),
+    );
+    let response = client.chat().create(chat_request()).await.unwrap();
+    assert_eq!(response.first_text(), Some("Hi!"));
+    assert_eq!(server.requests().len(), 2);
+
+    // the last response is repeated
+    client.chat().create(chat_request()).await.unwrap();
+}
+
+#[tokio::test]
+async fn gateway_errors_without_api_error_bodies_are_retried() {
+    let server = MockServer::start();
+    server
+        .mock(
+            "POST",
+            "/chat/completions",
+            MockResponse::bytes("503 Service Temporarily Unavailable").with_status(503),
+        )
+        .mock("POST", "/chat/completions", MockResponse::json(completion("Hi!")));
+
+    let client = server.client().with_retry_policy(
+        RetryPolicy::default()
+            .with_max_attempts(2)
+            .with_initial_backoff(Duration::ZERO),
+    );
+    let response = client.chat().create(chat_request()).await.unwrap();
+    assert_eq!(response.first_text(), Some("Hi!"));
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn streamed_uploads_are_captured() {
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/files",
+        MockResponse::json(json!({
+            "id": "file-1",
+            "object": "file",
+            "bytes": 5,
+            "created_at": 0,
+            "filename": "a.jsonl",
+            "purpose": "fine-tune",
+        })),
+    );
+
+    let request = CreateFileRequestArgs::default()
+        .file(FileInput::from_vec_u8("a.jsonl".into(), b"{}\n{}".to_vec()))
+        .purpose(FilePurpose::FineTune)
+        .build()
+        .unwrap();
+    let file = server.client().files().create(request).await.unwrap();
+    assert_eq!(file.id, "file-1");
+
+    let body = String::from_utf8_lossy(&server.requests()[0].body).into_owned();
+    assert!(body.contains("filename=\"a.jsonl\""));
+    assert!(body.contains("{}\n{}"));
+}
+
+#[tokio::test]
+async fn unmocked_endpoints_are_not_found() {
+    let server = MockServer::start();
+
+    let error = server.client().models().list().await.unwrap_err();
+    assert!(matches!(error, OpenAIError::ApiError(error) if error.message == "no mock for GET /models"));
+}
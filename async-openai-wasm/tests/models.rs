@@ -0,0 +1,62 @@
+#![cfg(feature = "testing")]
+
+use async_openai_wasm::testing::{MockResponse, MockServer};
+use serde_json::json;
+
+fn server() -> MockServer {
+    let model = |id: &str, owned_by: &str| {
+        json!({"id": id, "object": "model", "created": 0, "owned_by": owned_by})
+    };
+    let server = MockServer::start();
+    server.mock(
+        "GET",
+        "/models",
+        MockResponse::json(json!({
+            "object": "list",
+            "data": [
+                model("gpt-4o-mini", "system"),
+                model("ft:gpt-4o-mini:acme::abc123", "org-acme"),
+                model("ft:gpt-4o-mini:acme:ckpt-step-10:def456", "org-acme-research"),
+                model("my-embedding", "user-42"),
+            ],
+        })),
+    );
+    server
+}
+
+fn ids(models: Vec<async_openai_wasm::types::Model>) -> Vec<String> {
+    models.into_iter().map(|model| model.id).collect()
+}
+
+#[tokio::test]
+async fn fine_tuned_models_are_listed() {
+    let server = server();
+
+    let models = server.client().models().list_fine_tuned().await.unwrap();
+
+    assert_eq!(
+        ids(models),
+        [
+            "ft:gpt-4o-mini:acme::abc123",
+            "ft:gpt-4o-mini:acme:ckpt-step-10:def456"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn models_are_listed_by_owner() {
+    let server = server();
+    let client = server.client();
+    let models = client.models();
+
+    assert_eq!(
+        ids(models.list_owned_by("org-acme").await.unwrap()),
+        ["ft:gpt-4o-mini:acme::abc123"]
+    );
+    assert_eq!(
+        ids(models.list_owned_by("org-acme-research").await.unwrap()),
+        ["ft:gpt-4o-mini:acme:ckpt-step-10:def456"]
+    );
+    assert!(models.list_owned_by("org").await.unwrap().is_empty());
+    assert_eq!(server.requests_to("/models").len(), 3);
+}
@@ -0,0 +1,43 @@
+use async_openai_wasm::{
+    context::RequestContext,
+    observability::{Helicone, Portkey},
+};
+use serde_json::json;
+
+#[test]
+fn helicone_headers_carry_request_context() {
+    let helicone = Helicone::new("sk-helicone")
+        .with_user_id("user-1")
+        .with_property("Environment", "staging");
+    let context = RequestContext::new()
+        .with_trace_id("trace-1")
+        .with_tenant_id("acme");
+
+    let headers = helicone.headers(Some(&context));
+    assert_eq!(headers["helicone-auth"], "Bearer sk-helicone");
+    assert_eq!(headers["helicone-user-id"], "user-1");
+    assert_eq!(headers["helicone-property-environment"], "staging");
+    assert_eq!(headers["helicone-property-trace-id"], "trace-1");
+    assert_eq!(headers["helicone-property-tenant-id"], "acme");
+
+    let headers = helicone.headers(None);
+    assert!(!headers.contains_key("helicone-property-trace-id"));
+}
+
+#[test]
+fn portkey_headers_carry_request_context() {
+    let portkey = Portkey::new("pk")
+        .with_virtual_key("openai-vk")
+        .with_user("user-1");
+    let context = RequestContext::new()
+        .with_trace_id("trace-1")
+        .with_attribute("feature", "search");
+
+    let headers = portkey.headers(Some(&context));
+    assert_eq!(headers["x-portkey-api-key"], "pk");
+    assert_eq!(headers["x-portkey-virtual-key"], "openai-vk");
+    assert_eq!(headers["x-portkey-trace-id"], "trace-1");
+    let metadata: serde_json::Value =
+        serde_json::from_str(headers["x-portkey-metadata"].to_str().unwrap()).unwrap();
+    assert_eq!(metadata, json!({"_user": "user-1", "feature": "search"}));
+}
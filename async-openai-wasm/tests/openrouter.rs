@@ -0,0 +1,67 @@
+use std::sync::{Arc, Mutex};
+
+use async_openai_wasm::{
+    config::{Config, OpenRouterConfig, ProviderPreferences, ProviderSort},
+    error::OpenAIError,
+    middleware::{async_trait, RequestInterceptor},
+    types::{ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs},
+    Client,
+};
+use serde_json::{json, Value};
+
+/// Captures requests instead of sending them
+#[derive(Default)]
+struct Capture(Arc<Mutex<Option<(reqwest::header::HeaderMap, Value)>>>);
+
+#[async_trait]
+impl RequestInterceptor for Capture {
+    async fn before_request(&self, request: &mut reqwest::Request) -> Result<(), OpenAIError> {
+        let body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .map(|body| serde_json::from_slice(body).unwrap())
+            .unwrap_or_default();
+        *self.0.lock().unwrap() = Some((request.headers().clone(), body));
+        Err(OpenAIError::InvalidArgument("captured".into()))
+    }
+}
+
+#[tokio::test]
+async fn openrouter_headers_and_routing() {
+    let config = OpenRouterConfig::new()
+        .with_api_key("sk-or-test")
+        .with_site_url("https://example.com")
+        .with_app_name("Example")
+        .with_provider(ProviderPreferences {
+            order: Some(vec!["openai".into()]),
+            sort: Some(ProviderSort::Price),
+            ..Default::default()
+        })
+        .with_fallback_models(["anthropic/claude-3.5-sonnet"]);
+    assert_eq!(config.url("/models"), "https://openrouter.ai/api/v1/models");
+    assert!(config.extra_body("/embeddings").is_empty());
+
+    let captured = Arc::new(Mutex::new(None));
+    let client = Client::with_config(config).with_interceptor(Capture(captured.clone()));
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("openai/gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello!")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap();
+    assert!(client.chat().create(request).await.is_err());
+
+    let (headers, body) = captured.lock().unwrap().take().unwrap();
+    assert_eq!(headers["authorization"], "Bearer sk-or-test");
+    assert_eq!(headers["http-referer"], "https://example.com");
+    assert_eq!(headers["x-title"], "Example");
+    assert_eq!(body["model"], "openai/gpt-4o");
+    assert_eq!(
+        body["provider"],
+        json!({"order": ["openai"], "sort": "price"})
+    );
+    assert_eq!(body["models"], json!(["anthropic/claude-3.5-sonnet"]));
+}
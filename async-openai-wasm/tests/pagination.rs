@@ -0,0 +1,113 @@
+#![cfg(feature = "testing")]
+
+use async_openai_wasm::testing::{MockResponse, MockServer};
+use futures::{StreamExt, TryStreamExt};
+use serde_json::json;
+
+/// Request target of the requests received, path and query
+fn targets(server: &MockServer) -> Vec<String> {
+    server
+        .requests()
+        .into_iter()
+        .map(|request| format!("{}?{}", request.path, request.query))
+        .collect()
+}
+
+fn batch(id: &str) -> serde_json::Value {
+    json!({
+        "id": id,
+        "object": "batch",
+        "endpoint": "/v1/chat/completions",
+        "input_file_id": "file-1",
+        "completion_window": "24h",
+        "status": "completed",
+        "created_at": 0,
+    })
+}
+
+#[tokio::test]
+async fn pages_are_followed_with_the_last_id() {
+    let server = MockServer::start();
+    server
+        .mock(
+            "GET",
+            "/batches",
+            MockResponse::json(
+                json!({"object": "list", "data": [batch("batch_1"), batch("batch_2")], "first_id": "batch_1", "last_id": "batch_2", "has_more": true}),
+            ),
+        )
+        .mock(
+            "GET",
+            "/batches",
+            MockResponse::json(
+                json!({"object": "list", "data": [batch("batch_3")], "first_id": "batch_3", "last_id": "batch_3", "has_more": false}),
+            ),
+        );
+    let client = server.client();
+
+    let mut batches = client
+        .batches()
+        .list_auto_paginated(&[("limit", "2"), ("after", "batch_0")]);
+    assert_eq!(batches.next().await.unwrap().unwrap().id, "batch_1");
+    assert_eq!(
+        targets(&server),
+        ["/batches?limit=2&after=batch_0"],
+        "next page fetched too early"
+    );
+
+    let rest: Vec<_> = batches
+        .map_ok(|batch| batch.id)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(rest, ["batch_2", "batch_3"]);
+    assert_eq!(targets(&server)[1], "/batches?limit=2&after=batch_2");
+}
+
+#[tokio::test]
+async fn fine_tuning_jobs_are_followed_with_the_id_of_the_last_job() {
+    let job = |id: &str| {
+        json!({
+            "id": id,
+            "object": "fine_tuning.job",
+            "created_at": 0,
+            "model": "gpt-4o-mini",
+            "organization_id": "org-1",
+            "status": "succeeded",
+            "hyperparameters": {"n_epochs": 3},
+            "training_file": "file-1",
+            "result_files": [],
+            "seed": 0,
+        })
+    };
+    let server = MockServer::start();
+    server
+        .mock(
+            "GET",
+            "/fine_tuning/jobs",
+            MockResponse::json(
+                json!({"object": "list", "data": [job("ftjob-1")], "has_more": true}),
+            ),
+        )
+        .mock(
+            "GET",
+            "/fine_tuning/jobs",
+            MockResponse::json(json!({"object": "list", "data": [], "has_more": false})),
+        );
+
+    let jobs: Vec<_> = server
+        .client()
+        .fine_tuning()
+        .list_auto_paginated(&[("limit", 1)])
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(
+        targets(&server),
+        [
+            "/fine_tuning/jobs?limit=1",
+            "/fine_tuning/jobs?limit=1&after=ftjob-1"
+        ]
+    );
+}
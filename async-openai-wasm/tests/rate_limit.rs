@@ -0,0 +1,74 @@
+#![cfg(feature = "testing")]
+
+use std::time::{Duration, Instant};
+
+use async_openai_wasm::{
+    config::OpenAIConfig,
+    rate_limit::{RateLimit, RateLimiter},
+    testing::{MockResponse, MockServer},
+    Client,
+};
+use serde_json::json;
+
+fn models() -> MockResponse {
+    MockResponse::json(json!({"object": "list", "data": []}))
+}
+
+#[tokio::test]
+async fn rate_limit_of_the_last_response() {
+    let server = MockServer::start();
+    server.mock(
+        "GET",
+        "/models",
+        models()
+            .with_header("x-ratelimit-limit-requests", "60")
+            .with_header("x-ratelimit-limit-tokens", "150000")
+            .with_header("x-ratelimit-remaining-requests", "59")
+            .with_header("x-ratelimit-remaining-tokens", "149984")
+            .with_header("x-ratelimit-reset-requests", "1s")
+            .with_header("x-ratelimit-reset-tokens", "6m0s"),
+    );
+    let client = server.client();
+    assert_eq!(client.last_rate_limit(), None);
+
+    client.models().list().await.unwrap();
+
+    assert_eq!(
+        client.clone().last_rate_limit(),
+        Some(RateLimit {
+            limit_requests: Some(60),
+            limit_tokens: Some(150000),
+            remaining_requests: Some(59),
+            remaining_tokens: Some(149984),
+            reset_requests: Some(Duration::from_secs(1)),
+            reset_tokens: Some(Duration::from_secs(360)),
+        })
+    );
+}
+
+#[tokio::test]
+async fn responses_without_rate_limit_headers_are_ignored() {
+    let server = MockServer::start();
+    server.mock("GET", "/models", models());
+    let client = server.client();
+
+    client.models().list().await.unwrap();
+
+    assert_eq!(client.last_rate_limit(), None);
+}
+
+#[tokio::test]
+async fn requests_wait_for_the_rate_limiter() {
+    // 2 requests per second, after a burst of a minute of them
+    let rate_limiter = RateLimiter::new(120, 0);
+    for _ in 0..120 {
+        rate_limiter.acquire(0).await;
+    }
+    // nothing listens on port 1, so the request fails right after being delayed
+    let client = Client::with_config(OpenAIConfig::new().with_api_base("http://127.0.0.1:1/v1"))
+        .with_rate_limiter(rate_limiter);
+
+    let started = Instant::now();
+    client.models().list().await.unwrap_err();
+    assert!(started.elapsed() >= Duration::from_millis(300));
+}
@@ -0,0 +1,84 @@
+#![cfg(feature = "testing")]
+
+use async_openai_wasm::{
+    error::OpenAIError,
+    testing::{MockResponse, MockServer},
+};
+use futures::StreamExt;
+use serde_json::json;
+
+#[tokio::test]
+async fn untyped_requests_to_any_path() {
+    let server = MockServer::start();
+    server
+        .mock(
+            "POST",
+            "/new/endpoint",
+            MockResponse::json(json!({"id": "new_1"})),
+        )
+        .mock(
+            "GET",
+            "/new/endpoint/new_1",
+            MockResponse::json(json!({"id": "new_1", "status": "done"})),
+        )
+        .mock(
+            "GET",
+            "/new/endpoint/new_1/content",
+            MockResponse::bytes(b"content".to_vec()),
+        )
+        .mock(
+            "DELETE",
+            "/new/endpoint/new_1",
+            MockResponse::json(json!({"deleted": true})),
+        );
+    let client = server.client();
+    let raw = client.raw();
+
+    let created = raw
+        .post("/new/endpoint", json!({"input": "hi"}))
+        .await
+        .unwrap();
+    assert_eq!(created["id"], "new_1");
+    let retrieved = raw.get("/new/endpoint/new_1").await.unwrap();
+    assert_eq!(retrieved["status"], "done");
+    let content = raw.get_bytes("/new/endpoint/new_1/content").await.unwrap();
+    assert_eq!(content.as_ref(), b"content");
+    let deleted = raw.delete("/new/endpoint/new_1").await.unwrap();
+    assert_eq!(deleted["deleted"], true);
+
+    let requests = server.requests();
+    assert_eq!(requests[0].json(), json!({"input": "hi"}));
+    assert_eq!(requests[0].header("authorization"), Some("Bearer sk-test"));
+}
+
+#[tokio::test]
+async fn untyped_streams() {
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/new/stream",
+        MockResponse::sse([json!({"delta": "a"}), json!({"delta": "b"})]),
+    );
+    let client = server.client();
+
+    let stream = client
+        .raw()
+        .post_stream("/new/stream", json!({"input": "hi"}))
+        .await
+        .unwrap();
+    let events: Vec<_> = stream.map(Result::unwrap).collect().await;
+    assert_eq!(events, vec![json!({"delta": "a"}), json!({"delta": "b"})]);
+    assert_eq!(
+        server.requests()[0].json(),
+        json!({"input": "hi", "stream": true})
+    );
+}
+
+#[tokio::test]
+async fn relative_paths_are_rejected() {
+    let client = MockServer::start().client();
+    assert!(matches!(
+        client.raw().get("models").await,
+        Err(OpenAIError::InvalidArgument(_))
+    ));
+}
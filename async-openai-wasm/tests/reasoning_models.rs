@@ -0,0 +1,98 @@
+#![cfg(feature = "testing")]
+
+use async_openai_wasm::{
+    testing::{MockResponse, MockServer},
+    types::{
+        ChatCompletionRequestDeveloperMessageArgs, ChatCompletionRequestUserMessageArgs,
+        CreateChatCompletionRequest, CreateChatCompletionRequestArgs, ReasoningEffort,
+    },
+};
+use serde_json::{json, Value};
+
+fn request(model: &str) -> CreateChatCompletionRequest {
+    CreateChatCompletionRequestArgs::default()
+        .model(model)
+        .messages([
+            ChatCompletionRequestDeveloperMessageArgs::default()
+                .content("Answer in French.")
+                .build()
+                .unwrap()
+                .into(),
+            ChatCompletionRequestUserMessageArgs::default()
+                .content("What is 1 + 1?")
+                .build()
+                .unwrap()
+                .into(),
+        ])
+        .max_tokens(100_u16)
+        .reasoning_effort(ReasoningEffort::Low)
+        .build()
+        .unwrap()
+}
+
+fn completion() -> MockResponse {
+    MockResponse::json(json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion",
+        "created": 0,
+        "model": "o3-mini",
+        "choices": [{
+            "index": 0,
+            "message": {"role": "assistant", "content": "Deux."},
+            "finish_reason": "stop",
+        }],
+    }))
+}
+
+#[tokio::test]
+async fn max_tokens_is_moved_for_reasoning_models() {
+    let server = MockServer::start();
+    server.mock("POST", "/chat/completions", completion());
+
+    server
+        .client()
+        .chat()
+        .create(request("o3-mini"))
+        .await
+        .unwrap();
+
+    let sent = server.requests()[0].json();
+    assert_eq!(sent["max_tokens"], Value::Null);
+    assert_eq!(sent["max_completion_tokens"], 100);
+    assert_eq!(sent["reasoning_effort"], "low");
+    assert_eq!(
+        sent["messages"][0],
+        json!({"role": "developer", "content": "Answer in French."})
+    );
+}
+
+#[tokio::test]
+async fn max_tokens_is_kept_for_other_models() {
+    let server = MockServer::start();
+    server.mock("POST", "/chat/completions", completion());
+
+    server
+        .client()
+        .chat()
+        .create(request("gpt-4o"))
+        .await
+        .unwrap();
+
+    let sent = server.requests()[0].json();
+    assert_eq!(sent["max_tokens"], 100);
+    assert_eq!(sent["max_completion_tokens"], Value::Null);
+}
+
+#[test]
+fn reasoning_models_are_recognized() {
+    for (model, reasoning) in [
+        ("o1", true),
+        ("o1-mini-2024-09-12", true),
+        ("o3-mini", true),
+        ("o4-mini", true),
+        ("omni-moderation-latest", false),
+        ("gpt-4o", false),
+    ] {
+        assert_eq!(request(model).is_reasoning_model(), reasoning, "{model}");
+    }
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "testing")]
+
+use async_openai_wasm::{
+    testing::{MockResponse, MockServer},
+    types::{
+        ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequest,
+        CreateChatCompletionRequestArgs,
+    },
+    Client,
+};
+use futures::StreamExt;
+use serde_json::json;
+
+fn chat_request() -> CreateChatCompletionRequest {
+    CreateChatCompletionRequestArgs::default()
+        .model("o3-mini")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello!")
+            .build()
+            .unwrap()
+            .into()])
+        .max_tokens(16_u16)
+        .build()
+        .unwrap()
+}
+
+fn completion() -> MockResponse {
+    MockResponse::json(json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion",
+        "created": 1,
+        "model": "gpt-4o-mini",
+        "choices": [{
+            "index": 0,
+            "message": {"role": "assistant", "content": "Hi!"},
+            "logprobs": null,
+            "finish_reason": "stop",
+        }],
+    }))
+    .with_header("x-request-id", "req_123")
+}
+
+#[tokio::test]
+async fn requests_are_echoed_as_sent() {
+    let server = MockServer::start();
+    server.mock("POST", "/chat/completions", completion());
+    let client = Client::with_config(server.config().with_header("x-team", "search"))
+        .with_default_user("user-1")
+        .with_request_echo(true);
+
+    client.chat().create(chat_request()).await.unwrap();
+
+    let echo = client.last_request().unwrap();
+    assert_eq!(echo.method, "POST");
+    assert!(echo.url.ends_with("/v1/chat/completions"));
+    assert_eq!(echo.status, Some(200));
+    assert_eq!(echo.request_id.as_deref(), Some("req_123"));
+    assert_eq!(echo.header("x-team"), Some("search"));
+    assert_eq!(echo.header("authorization"), Some("[redacted]"));
+    // the defaults and migrations of the client are echoed
+    let body = echo.body.unwrap();
+    assert_eq!(body["user"], "user-1");
+    assert_eq!(body["max_completion_tokens"], 16);
+    assert!(body.get("max_tokens").is_none());
+    assert_eq!(body, server.requests_to("/chat/completions")[0].json());
+}
+
+#[tokio::test]
+async fn streams_are_echoed_without_status() {
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/chat/completions",
+        MockResponse::sse(Vec::<serde_json::Value>::new()),
+    );
+    let client = server.client().with_request_echo(true);
+
+    let stream = client.chat().create_stream(chat_request()).await.unwrap();
+    assert_eq!(stream.collect::<Vec<_>>().await.len(), 0);
+
+    let echo = client.last_request().unwrap();
+    assert_eq!(echo.body.unwrap()["stream"], true);
+    assert_eq!(echo.status, None);
+}
+
+#[tokio::test]
+async fn requests_are_not_echoed_by_default() {
+    let server = MockServer::start();
+    server.mock("POST", "/chat/completions", completion());
+    let client = server.client();
+
+    client.chat().create(chat_request()).await.unwrap();
+
+    assert_eq!(client.last_request(), None);
+}
@@ -0,0 +1,116 @@
+//! Requests exceeding [RequestLimits] must be rejected with an actionable error.
+use async_openai_wasm::{
+    error::OpenAIError,
+    types::{
+        ChatCompletionRequestMessageContentPartImageArgs, ChatCompletionRequestUserMessageArgs,
+        CreateChatCompletionRequestArgs, ImageUrlArgs,
+    },
+    validation::RequestLimits,
+};
+
+#[test]
+fn images_over_limits_are_rejected() {
+    let image = |url: &str| {
+        ChatCompletionRequestMessageContentPartImageArgs::default()
+            .image_url(ImageUrlArgs::default().url(url).build().unwrap())
+            .build()
+            .unwrap()
+            .into()
+    };
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content(vec![
+                image("https://example.com/cat.png"),
+                image("data:image/png;base64,AAAAAAAA"),
+            ])
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap();
+
+    assert!(RequestLimits::azure().validate_chat(&request).is_ok());
+
+    let error = RequestLimits::new()
+        .with_max_images(1)
+        .validate_chat(&request)
+        .unwrap_err();
+    assert!(matches!(error, OpenAIError::InvalidArgument(message) if message.contains("2 images")));
+
+    let error = RequestLimits::new()
+        .with_max_image_bytes(5)
+        .validate_chat(&request)
+        .unwrap_err();
+    assert!(matches!(error, OpenAIError::InvalidArgument(message) if message.contains("6 bytes")));
+}
+
+#[test]
+fn azure_vision_preview_requires_detail() {
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4-vision")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content(vec![
+                ChatCompletionRequestMessageContentPartImageArgs::default()
+                    .image_url("https://example.com/cat.png")
+                    .build()
+                    .unwrap()
+                    .into(),
+            ])
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap();
+
+    let error = RequestLimits::azure_api_version("2024-02-15-preview")
+        .validate_chat(&request)
+        .unwrap_err();
+    assert!(matches!(error, OpenAIError::InvalidArgument(message) if message.contains("detail")));
+    assert!(RequestLimits::azure_api_version("2024-10-21")
+        .validate_chat(&request)
+        .is_ok());
+}
+
+#[tokio::test]
+async fn azure_limits_only_cover_images_and_can_be_turned_off() {
+    use async_openai_wasm::{
+        config::{AzureConfig, Config},
+        Client,
+    };
+
+    let config = AzureConfig::new()
+        .with_api_version("2024-06-01")
+        // nothing listens on port 1, so requests within limits fail to connect
+        .with_api_base("http://127.0.0.1:1");
+    assert_eq!(
+        config.request_limits(),
+        Some(RequestLimits::new().with_max_images(10))
+    );
+
+    let image = ChatCompletionRequestMessageContentPartImageArgs::default()
+        .image_url("https://example.com/cat.png")
+        .build()
+        .unwrap();
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content(vec![image.into(); 11])
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap();
+
+    let client = Client::with_config(config);
+    let error = client.chat().create(request.clone()).await.unwrap_err();
+    assert!(matches!(error, OpenAIError::InvalidArgument(message) if message.contains("11 images")));
+
+    let error = client
+        .without_request_limits()
+        .chat()
+        .create(request)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, OpenAIError::Reqwest(_)));
+}
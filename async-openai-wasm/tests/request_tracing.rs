@@ -0,0 +1,160 @@
+#![cfg(all(feature = "tracing", feature = "testing"))]
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use async_openai_wasm::{
+    testing::{MockResponse, MockServer},
+    types::{ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs},
+};
+use futures::StreamExt;
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Metadata, Subscriber,
+};
+
+type Fields = Arc<Mutex<HashMap<String, String>>>;
+
+/// Collects the fields of `openai_request` spans
+struct RequestFields(Fields);
+
+impl Visit for RequestFields {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .lock()
+            .unwrap()
+            .insert(field.name().to_string(), format!("{value:?}"));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0
+            .lock()
+            .unwrap()
+            .insert(field.name().to_string(), value.to_string());
+    }
+}
+
+struct Collector {
+    fields: Fields,
+}
+
+impl Subscriber for Collector {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        if span.metadata().name() == "openai_request" {
+            span.record(&mut RequestFields(self.fields.clone()));
+            Id::from_u64(1)
+        } else {
+            Id::from_u64(2)
+        }
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        if *span == Id::from_u64(1) {
+            values.record(&mut RequestFields(self.fields.clone()));
+        }
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, _event: &Event<'_>) {}
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+/// Server answering chat completion requests with usage and a request id
+fn serve() -> MockServer {
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/chat/completions",
+        MockResponse::json(serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "gpt-4o-2024-08-06",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": "Hi!"},
+                "finish_reason": "stop",
+            }],
+            "usage": {"prompt_tokens": 9, "completion_tokens": 2, "total_tokens": 11},
+        }))
+        .with_header("x-request-id", "req_123")
+        .with_header("openai-processing-ms", "1"),
+    );
+    server
+}
+
+#[tokio::test]
+async fn request_span_records_the_call() {
+    let fields: Fields = Default::default();
+    let _guard = tracing::subscriber::set_default(Collector {
+        fields: fields.clone(),
+    });
+
+    let server = serve();
+    let client = server.client();
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello!")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap();
+    client.chat().create(request).await.unwrap();
+
+    let fields = fields.lock().unwrap();
+    assert_eq!(fields["http.request.method"], "POST");
+    assert_eq!(fields["url.path"], "/v1/chat/completions");
+    assert_eq!(fields["gen_ai.request.model"], "gpt-4o");
+    assert_eq!(fields["openai.stream"], "false");
+    assert_eq!(fields["openai.attempts"], "1");
+    assert_eq!(fields["http.response.status_code"], "200");
+    assert_eq!(fields["openai.request_id"], "req_123");
+    assert_eq!(fields["openai.processing_ms"], "1");
+    assert!(fields.contains_key("openai.overhead_ms"));
+    assert_eq!(fields["gen_ai.usage.input_tokens"], "9");
+    assert_eq!(fields["gen_ai.usage.output_tokens"], "2");
+    assert!(fields.contains_key("duration_ms"));
+}
+
+#[tokio::test]
+async fn request_span_records_the_response_of_streams() {
+    let fields: Fields = Default::default();
+    let _guard = tracing::subscriber::set_default(Collector {
+        fields: fields.clone(),
+    });
+
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/chat/completions",
+        MockResponse::raw_sse("data: [DONE]\n\n").with_header("x-request-id", "req_456"),
+    );
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello!")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap();
+    let mut stream = server.client().chat().create_stream(request).await.unwrap();
+    while stream.next().await.is_some() {}
+
+    let fields = fields.lock().unwrap();
+    assert_eq!(fields["openai.stream"], "true");
+    assert_eq!(fields["http.response.status_code"], "200");
+    assert_eq!(fields["openai.request_id"], "req_456");
+    assert!(fields.contains_key("duration_ms"));
+}
@@ -0,0 +1,61 @@
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc,
+};
+use std::time::Duration;
+
+use async_openai_wasm::{
+    config::OpenAIConfig,
+    error::OpenAIError,
+    middleware::{async_trait, RequestInterceptor},
+    retry::{RetryBudget, RetryPolicy},
+    Client,
+};
+
+struct CountAttempts(Arc<AtomicU32>);
+
+#[async_trait]
+impl RequestInterceptor for CountAttempts {
+    async fn before_request(&self, _request: &mut reqwest::Request) -> Result<(), OpenAIError> {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn transport_errors_are_retried() {
+    let attempts = Arc::new(AtomicU32::new(0));
+    // nothing listens on port 1, so connecting fails
+    let client = Client::with_config(OpenAIConfig::new().with_api_base("http://127.0.0.1:1"))
+        .with_interceptor(CountAttempts(attempts.clone()))
+        .with_retry_policy(
+            RetryPolicy::default()
+                .with_max_attempts(3)
+                .with_initial_backoff(Duration::from_millis(10)),
+        );
+
+    let error = client.models().list().await.unwrap_err();
+    assert!(matches!(error, OpenAIError::Reqwest(_)));
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn retry_budgets_are_shared_by_clones() {
+    let attempts = Arc::new(AtomicU32::new(0));
+    let budget = RetryBudget::new(1);
+    let client = Client::with_config(OpenAIConfig::new().with_api_base("http://127.0.0.1:1"))
+        .with_interceptor(CountAttempts(attempts.clone()))
+        .with_retry_policy(
+            RetryPolicy::default()
+                .with_max_attempts(3)
+                .with_initial_backoff(Duration::from_millis(10)),
+        )
+        .with_retry_budget(budget.clone());
+
+    // the only retry of the budget is spent by the first call, the clone fails at once
+    client.models().list().await.unwrap_err();
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    client.clone().models().list().await.unwrap_err();
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    assert_eq!(budget.available(), 0);
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "testing")]
+
+use async_openai_wasm::{
+    testing::{MockResponse, MockServer},
+    types::RunCompletionUsage,
+};
+use serde_json::{json, Value};
+
+fn run(id: &str, status: &str, usage: Value) -> Value {
+    json!({
+        "id": id, "object": "thread.run", "created_at": 0, "thread_id": "thread_1",
+        "assistant_id": "asst_1", "status": status, "required_action": null,
+        "last_error": null, "expires_at": null, "started_at": null, "cancelled_at": null,
+        "failed_at": null, "completed_at": null, "incomplete_details": null, "model": "gpt-4o",
+        "instructions": "", "tools": [], "metadata": null, "usage": usage, "temperature": null,
+        "top_p": null, "max_prompt_tokens": null, "max_completion_tokens": null,
+        "truncation_strategy": null, "tool_choice": null, "parallel_tool_calls": true,
+        "response_format": null,
+    })
+}
+
+fn usage(prompt_tokens: u32, completion_tokens: u32) -> Value {
+    json!({
+        "prompt_tokens": prompt_tokens,
+        "completion_tokens": completion_tokens,
+        "total_tokens": prompt_tokens + completion_tokens,
+    })
+}
+
+#[tokio::test]
+async fn thread_usage_sums_the_usage_of_its_runs() {
+    let server = MockServer::start();
+    server
+        .mock(
+            "GET",
+            "/threads/thread_1/runs",
+            MockResponse::json(json!({
+                "object": "list",
+                "data": [run("run_1", "completed", usage(100, 20)), run("run_2", "failed", usage(50, 5))],
+                "first_id": "run_1", "last_id": "run_2", "has_more": true,
+            })),
+        )
+        .mock(
+            "GET",
+            "/threads/thread_1/runs",
+            MockResponse::json(json!({
+                "object": "list",
+                "data": [run("run_3", "in_progress", Value::Null)],
+                "first_id": "run_3", "last_id": "run_3", "has_more": false,
+            })),
+        );
+
+    let usage = server
+        .client()
+        .threads()
+        .runs("thread_1")
+        .usage()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        usage,
+        RunCompletionUsage {
+            prompt_tokens: 150,
+            completion_tokens: 25,
+            total_tokens: 175,
+        }
+    );
+    let requests = server.requests_to("/threads/thread_1/runs");
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].query, "limit=100&after=run_2");
+}
@@ -0,0 +1,145 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use async_openai_wasm::{
+    config::OpenAIConfig,
+    sampling::Aggregator,
+    types::{ChatCompletionRequestUserMessage, CreateChatCompletionRequest},
+    Client,
+};
+use serde_json::{json, Value};
+
+const ANSWERS: [&str; 6] = ["Paris", "Lyon", " paris\n", "Lyon", "Paris", "Lyon"];
+
+/// Answer chat completions with at most 2 choices, taking turns through [ANSWERS],
+/// or `[2]` to requests of the `judge` model
+fn handle(stream: TcpStream, sampled: Arc<AtomicUsize>) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    loop {
+        let mut request_line = String::new();
+        if reader.read_line(&mut request_line).unwrap_or(0) == 0 {
+            return;
+        }
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+        let request: Value = serde_json::from_slice(&body).unwrap();
+
+        let contents: Vec<String> = if request["model"] == "judge" {
+            vec!["[2]".into()]
+        } else {
+            let n = request["n"].as_u64().unwrap_or(1).min(2) as usize;
+            (0..n)
+                .map(|_| {
+                    let sample = sampled.fetch_add(1, Ordering::SeqCst);
+                    ANSWERS[sample % ANSWERS.len()].to_string()
+                })
+                .collect()
+        };
+        let choices: Vec<Value> = contents
+            .iter()
+            .enumerate()
+            .map(|(index, content)| {
+                json!({
+                    "index": index,
+                    "message": {"role": "assistant", "content": content},
+                    "finish_reason": "stop",
+                })
+            })
+            .collect();
+        let response = json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "gpt-4o-mini",
+            "choices": choices,
+        })
+        .to_string();
+        write!(
+            &stream,
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{response}",
+            response.len()
+        )
+        .unwrap();
+    }
+}
+
+fn client(sampled: Arc<AtomicUsize>) -> Client<OpenAIConfig> {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let api_base = format!("http://{}/v1", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let stream = stream.unwrap();
+            let sampled = sampled.clone();
+            std::thread::spawn(move || handle(stream, sampled));
+        }
+    });
+    Client::with_config(OpenAIConfig::new().with_api_base(api_base))
+}
+
+fn request() -> CreateChatCompletionRequest {
+    CreateChatCompletionRequest {
+        model: "gpt-4o-mini".into(),
+        messages: vec![ChatCompletionRequestUserMessage {
+            content: "What is the capital of France?".into(),
+            ..Default::default()
+        }
+        .into()],
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn majority_vote_over_capped_n() {
+    let sampled = Arc::new(AtomicUsize::new(0));
+    let consensus = client(sampled.clone())
+        .chat()
+        .sample_n(request(), 5, &Aggregator::MajorityVote)
+        .await
+        .unwrap();
+
+    // 2 choices at a time, so 3 requests
+    assert_eq!(sampled.load(Ordering::SeqCst), 6);
+    assert_eq!(consensus.samples.len(), 5);
+    assert_eq!(consensus.answer, "Paris");
+    assert_eq!(consensus.votes, 3);
+    assert_eq!(consensus.agreement(), 0.6);
+    assert_eq!(
+        consensus.tally,
+        vec![("Paris".to_string(), 3), ("Lyon".to_string(), 2)]
+    );
+}
+
+#[tokio::test]
+async fn judge_picks_among_distinct_answers() {
+    let consensus = client(Default::default())
+        .chat()
+        .sample_n(
+            request(),
+            5,
+            &Aggregator::Judge {
+                model: "judge".into(),
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(consensus.answer, "Lyon");
+    assert_eq!(consensus.votes, 2);
+}
@@ -26,3 +26,690 @@ async fn chat_types_serde() {
     let deserialized: CreateChatCompletionRequest = serde_json::from_str(&serialized).unwrap();
     assert_eq!(request, deserialized);
 }
+
+#[test]
+fn fine_tuning_job_status_helpers() {
+    use async_openai_wasm::types::FineTuningJobStatus;
+
+    let status: FineTuningJobStatus = serde_json::from_value(serde_json::json!("paused")).unwrap();
+    assert_eq!(status, FineTuningJobStatus::Paused);
+
+    let statuses = [
+        FineTuningJobStatus::ValidatingFiles,
+        FineTuningJobStatus::Queued,
+        FineTuningJobStatus::Running,
+        FineTuningJobStatus::Paused,
+        FineTuningJobStatus::Succeeded,
+        FineTuningJobStatus::Failed,
+        FineTuningJobStatus::Cancelled,
+    ];
+    let pausable: Vec<_> = statuses.iter().filter(|status| status.can_pause()).collect();
+    assert_eq!(pausable, [&FineTuningJobStatus::Running]);
+    let resumable: Vec<_> = statuses.iter().filter(|status| status.can_resume()).collect();
+    assert_eq!(resumable, [&FineTuningJobStatus::Paused]);
+    assert!(!FineTuningJobStatus::Paused.is_terminal());
+}
+
+#[test]
+fn batch_jsonl_serde() {
+    use async_openai_wasm::{
+        error::OpenAIError,
+        types::{
+            BatchEndpoint, BatchRequestInput, BatchRequestInputMethod, BatchRequestOutput,
+            BatchRequestOutputError, BatchRequestOutputResponse,
+        },
+    };
+
+    let inputs = vec![
+        BatchRequestInput {
+            custom_id: "request-1".into(),
+            method: BatchRequestInputMethod::POST,
+            url: BatchEndpoint::V1ChatCompletions,
+            body: Some(serde_json::json!({
+                "model": "gpt-4o-mini",
+                "messages": [{"role": "user", "content": "Hello\nworld"}],
+            })),
+        },
+        BatchRequestInput {
+            custom_id: "request-2".into(),
+            method: BatchRequestInputMethod::POST,
+            url: BatchEndpoint::V1Embeddings,
+            body: Some(serde_json::json!({"model": "text-embedding-3-small", "input": "Hello"})),
+        },
+    ];
+    let jsonl = BatchRequestInput::to_jsonl(&inputs).unwrap();
+    assert_eq!(jsonl.lines().count(), 2);
+    assert!(jsonl.ends_with('\n'));
+    let parsed: Vec<BatchRequestInput> = jsonl
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(parsed, inputs);
+
+    let outputs = vec![
+        BatchRequestOutput {
+            id: "batch_req_1".into(),
+            custom_id: "request-1".into(),
+            response: Some(BatchRequestOutputResponse {
+                status_code: 200,
+                request_id: "req_1".into(),
+                body: serde_json::json!({"object": "chat.completion"}),
+            }),
+            error: None,
+        },
+        BatchRequestOutput {
+            id: "batch_req_2".into(),
+            custom_id: "request-2".into(),
+            response: None,
+            error: Some(BatchRequestOutputError {
+                code: "batch_expired".into(),
+                message: "expired".into(),
+            }),
+        },
+    ];
+    let jsonl: String = outputs
+        .iter()
+        .map(|output| format!("{}\n\n", serde_json::to_string(output).unwrap()))
+        .collect();
+    assert_eq!(BatchRequestOutput::from_jsonl(&jsonl).unwrap(), outputs);
+
+    let malformed = format!(
+        "{}\n{{\"id\": \"batch_req_3\"\n",
+        serde_json::to_string(&outputs[0]).unwrap()
+    );
+    assert!(matches!(
+        BatchRequestOutput::from_jsonl(&malformed),
+        Err(OpenAIError::JSONDeserialize(_))
+    ));
+}
+
+#[test]
+fn chunking_strategy_serde() {
+    use async_openai_wasm::types::{CreateVectorStoreFileRequestArgs, VectorStoreChunkingStrategy};
+
+    let request = CreateVectorStoreFileRequestArgs::default()
+        .file_id("file-abc123")
+        .chunking_strategy(VectorStoreChunkingStrategy::fixed(1000, 200).unwrap())
+        .build()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        serde_json::json!({
+            "file_id": "file-abc123",
+            "chunking_strategy": {
+                "type": "static",
+                "static": {"max_chunk_size_tokens": 1000, "chunk_overlap_tokens": 200}
+            }
+        })
+    );
+
+    assert_eq!(
+        serde_json::to_value(VectorStoreChunkingStrategy::Auto).unwrap(),
+        serde_json::json!({"type": "auto"})
+    );
+    assert!(VectorStoreChunkingStrategy::fixed(1000, 600).is_err());
+}
+
+#[test]
+fn vector_store_expiration_serde() {
+    use async_openai_wasm::types::{VectorStoreExpirationAfter, VectorStoreObject};
+
+    assert!(VectorStoreExpirationAfter::last_active(0).is_err());
+    assert!(VectorStoreExpirationAfter::last_active(366).is_err());
+    assert_eq!(VectorStoreExpirationAfter::last_active(1).unwrap().days, 1);
+    assert_eq!(
+        serde_json::to_value(VectorStoreExpirationAfter::last_active(365).unwrap()).unwrap(),
+        serde_json::json!({"anchor": "last_active_at", "days": 365})
+    );
+
+    let vector_store = |expires_at: Option<u32>| -> VectorStoreObject {
+        serde_json::from_value(serde_json::json!({
+            "id": "vs_1", "object": "vector_store", "created_at": 0, "name": "Helper",
+            "usage_bytes": 0, "status": "completed", "expires_at": expires_at,
+            "expires_after": expires_at.map(|_| serde_json::json!({"anchor": "last_active_at", "days": 7})),
+            "last_active_at": 1000, "metadata": {},
+            "file_counts": {"in_progress": 0, "completed": 0, "failed": 0, "cancelled": 0, "total": 0},
+        }))
+        .unwrap()
+    };
+    assert_eq!(vector_store(Some(1600)).expires_in(1000), Some(600));
+    assert_eq!(vector_store(Some(1600)).expires_in(2000), Some(-400));
+    assert_eq!(vector_store(None).expires_in(1000), None);
+}
+
+#[test]
+fn response_serde() {
+    use async_openai_wasm::types::{Response, ResponseOutputItem, ResponseStreamEvent};
+
+    let response: Response = serde_json::from_value(serde_json::json!({
+        "id": "resp_123",
+        "object": "response",
+        "created_at": 1741476542,
+        "status": "completed",
+        "error": null,
+        "incomplete_details": null,
+        "model": "gpt-4o-2024-08-06",
+        "output": [
+            {"type": "web_search_call", "id": "ws_123", "status": "completed"},
+            {
+                "type": "message",
+                "id": "msg_123",
+                "status": "completed",
+                "role": "assistant",
+                "content": [{
+                    "type": "output_text",
+                    "text": "It is sunny.",
+                    "annotations": [{
+                        "type": "url_citation",
+                        "url": "https://example.com/weather",
+                        "title": "Weather",
+                        "start_index": 0,
+                        "end_index": 12
+                    }]
+                }]
+            },
+            {
+                "type": "function_call",
+                "id": "fc_123",
+                "call_id": "call_123",
+                "name": "get_weather",
+                "arguments": "{\"city\":\"Paris\"}",
+                "status": "completed"
+            }
+        ],
+        "tools": [],
+        "usage": {"input_tokens": 36, "output_tokens": 87, "total_tokens": 123}
+    }))
+    .unwrap();
+    assert_eq!(response.output_text(), "It is sunny.");
+    assert_eq!(response.function_calls().count(), 1);
+    assert!(matches!(
+        response.output[0],
+        ResponseOutputItem::WebSearchCall(_)
+    ));
+
+    let event: ResponseStreamEvent = serde_json::from_str(
+        r#"{"type":"response.output_text.delta","item_id":"msg_123","output_index":1,"content_index":0,"delta":"It","sequence_number":4}"#,
+    )
+    .unwrap();
+    assert!(matches!(event, ResponseStreamEvent::OutputTextDelta { delta, .. } if delta == "It"));
+
+    let event: ResponseStreamEvent =
+        serde_json::from_str(r#"{"type":"response.some_future_event"}"#).unwrap();
+    assert_eq!(event, ResponseStreamEvent::Unknown);
+}
+
+#[test]
+fn chat_response_text() {
+    use async_openai_wasm::types::CreateChatCompletionResponse;
+
+    let response: CreateChatCompletionResponse = serde_json::from_value(serde_json::json!({
+        "id": "chatcmpl-123",
+        "object": "chat.completion",
+        "created": 1677652288,
+        "model": "gpt-4o",
+        "system_fingerprint": null,
+        "choices": [
+            {"index": 0, "message": {"role": "assistant", "content": "Hello"}, "finish_reason": "stop"},
+            {"index": 1, "message": {"role": "assistant", "content": " there"}, "finish_reason": "stop"},
+            {
+                "index": 2,
+                "message": {
+                    "role": "assistant",
+                    "content": null,
+                    "tool_calls": [{"id": "call_1", "type": "function", "function": {"name": "f", "arguments": "{}"}}]
+                },
+                "finish_reason": "tool_calls"
+            }
+        ],
+        "usage": null
+    }))
+    .unwrap();
+    assert_eq!(response.first_text(), Some("Hello"));
+    assert_eq!(response.text(), "Hello there");
+    assert_eq!(response.tool_calls().count(), 1);
+}
+
+#[test]
+fn chat_response_logprobs() {
+    use async_openai_wasm::types::CreateChatCompletionResponse;
+
+    let response: CreateChatCompletionResponse = serde_json::from_value(serde_json::json!({
+        "id": "chatcmpl-123",
+        "object": "chat.completion",
+        "created": 1677652288,
+        "model": "gpt-4o",
+        "system_fingerprint": null,
+        "choices": [{
+            "index": 0,
+            "message": {"role": "assistant", "content": "Yes."},
+            "finish_reason": "stop",
+            "logprobs": {
+                "content": [
+                    {
+                        "token": "Yes",
+                        "logprob": -0.25,
+                        "bytes": [89, 101, 115],
+                        "top_logprobs": [
+                            {"token": "Yes", "logprob": -0.25, "bytes": [89, 101, 115]},
+                            {"token": "No", "logprob": -1.5, "bytes": [78, 111]}
+                        ]
+                    },
+                    {"token": ".", "logprob": -0.75, "bytes": [46], "top_logprobs": []}
+                ],
+                "refusal": null
+            }
+        }],
+        "usage": null
+    }))
+    .unwrap();
+
+    let logprobs = response.choices[0].logprobs.as_ref().unwrap();
+    let content = logprobs.content.as_ref().unwrap();
+    assert_eq!(content[0].top_logprobs[1].token, "No");
+    assert!((content[0].top_logprobs[1].probability() - (-1.5f64).exp()).abs() < 1e-9);
+    assert_eq!(logprobs.logprob(), Some(-1.0));
+    assert!((response.perplexity().unwrap() - 0.5f64.exp()).abs() < 1e-9);
+
+    let json = serde_json::to_value(logprobs).unwrap();
+    assert!(json.get("refusal").is_none());
+}
+
+#[test]
+fn assistant_stream_event_mapping() {
+    use async_openai_wasm::{error::OpenAIError, types::AssistantStreamEvent};
+
+    let event = |event: &str, data: &str| eventsource_stream::Event {
+        event: event.into(),
+        data: data.into(),
+        id: String::new(),
+        retry: None,
+    };
+
+    let done = AssistantStreamEvent::try_from(event("done", "[DONE]")).unwrap();
+    assert!(matches!(done, AssistantStreamEvent::Done(data) if data == "[DONE]"));
+
+    let unknown = AssistantStreamEvent::try_from(event("thread.future", "{}")).unwrap_err();
+    assert!(
+        matches!(unknown, OpenAIError::InvalidStreamEvent(invalid) if invalid.event == "thread.future" && invalid.data == "{}")
+    );
+}
+
+#[test]
+fn multi_modal_moderation_serde() {
+    use async_openai_wasm::types::{
+        CreateModerationRequestArgs, CreateModerationResponse, ModerationContentPart,
+        ModerationInputType, TextModerationModel,
+    };
+
+    let request = CreateModerationRequestArgs::default()
+        .model(TextModerationModel::OmniLatest)
+        .input([
+            ModerationContentPart::text("...text to classify goes here..."),
+            ModerationContentPart::image_url("https://example.com/image.png"),
+        ])
+        .build()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        serde_json::json!({
+            "model": "omni-moderation-latest",
+            "input": [
+                {"type": "text", "text": "...text to classify goes here..."},
+                {"type": "image_url", "image_url": {"url": "https://example.com/image.png"}}
+            ]
+        })
+    );
+
+    let categories = |value: serde_json::Value| {
+        let names = [
+            "hate",
+            "hate/threatening",
+            "harassment",
+            "harassment/threatening",
+            "self-harm",
+            "self-harm/intent",
+            "self-harm/instructions",
+            "sexual",
+            "sexual/minors",
+            "violence",
+            "violence/graphic",
+            "illicit",
+            "illicit/violent",
+        ];
+        serde_json::Value::Object(
+            names
+                .iter()
+                .map(|name| (name.to_string(), value.clone()))
+                .collect(),
+        )
+    };
+    let mut applied_input_types = categories(serde_json::json!(["text"]));
+    applied_input_types["violence"] = serde_json::json!(["text", "image"]);
+    let response: CreateModerationResponse = serde_json::from_value(serde_json::json!({
+        "id": "modr-0d9740456c391e43c445bf0f010940c7",
+        "model": "omni-moderation-latest",
+        "results": [{
+            "flagged": false,
+            "categories": categories(serde_json::json!(false)),
+            "category_scores": categories(serde_json::json!(0.25)),
+            "category_applied_input_types": applied_input_types,
+        }]
+    }))
+    .unwrap();
+
+    let result = &response.results[0];
+    assert_eq!(result.categories.illicit_violent, Some(false));
+    assert_eq!(result.category_scores.illicit, Some(0.25));
+    let applied_input_types = result.category_applied_input_types.as_ref().unwrap();
+    assert_eq!(
+        applied_input_types.violence,
+        [ModerationInputType::Text, ModerationInputType::Image]
+    );
+
+    // text moderation models return neither the illicit categories nor the applied input types
+    let without_illicit = |mut value: serde_json::Value| {
+        let object = value.as_object_mut().unwrap();
+        object.remove("illicit");
+        object.remove("illicit/violent");
+        value
+    };
+    let text_categories = without_illicit(categories(serde_json::json!(false)));
+    let text_scores = without_illicit(categories(serde_json::json!(0.5)));
+    let response: CreateModerationResponse = serde_json::from_value(serde_json::json!({
+        "id": "modr-1",
+        "model": "text-moderation-007",
+        "results": [{"flagged": false, "categories": text_categories, "category_scores": text_scores}]
+    }))
+    .unwrap();
+    assert_eq!(response.results[0].categories.illicit, None);
+    assert_eq!(response.results[0].category_applied_input_types, None);
+}
+
+#[test]
+fn strict_function_tools_serde() {
+    use async_openai_wasm::types::{
+        ChatCompletionToolArgs, ChatCompletionToolChoiceOption, FunctionObjectArgs,
+    };
+
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([])
+        .tools([ChatCompletionToolArgs::default()
+            .function(
+                FunctionObjectArgs::default()
+                    .name("get_weather")
+                    .parameters(serde_json::json!({
+                        "type": "object",
+                        "properties": {"city": {"type": "string"}},
+                        "required": ["city"],
+                        "additionalProperties": false,
+                    }))
+                    .strict(true)
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap()])
+        .tool_choice("required")
+        .parallel_tool_calls(false)
+        .build()
+        .unwrap();
+    assert_eq!(
+        request.tool_choice,
+        Some(ChatCompletionToolChoiceOption::Required)
+    );
+
+    let value = serde_json::to_value(&request).unwrap();
+    assert_eq!(value["tools"][0]["function"]["strict"], true);
+    assert_eq!(value["tool_choice"], "required");
+    assert_eq!(value["parallel_tool_calls"], false);
+    let round_tripped: CreateChatCompletionRequest = serde_json::from_value(value).unwrap();
+    assert_eq!(round_tripped, request);
+}
+
+#[test]
+fn chat_audio_serde() {
+    use async_openai_wasm::types::{
+        ChatCompletionAudio, ChatCompletionAudioFormat, ChatCompletionModalities,
+        ChatCompletionRequestMessageContentPartAudioArgs, CreateChatCompletionResponse,
+        InputAudioArgs, InputAudioFormat, Voice,
+    };
+
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o-audio-preview")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content(vec![
+                ChatCompletionRequestMessageContentPartAudioArgs::default()
+                    .input_audio(
+                        InputAudioArgs::default()
+                            .data("UklGRg==")
+                            .format(InputAudioFormat::Wav)
+                            .build()
+                            .unwrap(),
+                    )
+                    .build()
+                    .unwrap()
+                    .into(),
+            ])
+            .build()
+            .unwrap()
+            .into()])
+        .modalities([
+            ChatCompletionModalities::Text,
+            ChatCompletionModalities::Audio,
+        ])
+        .audio(ChatCompletionAudio {
+            voice: Voice::Coral,
+            format: ChatCompletionAudioFormat::Pcm16,
+        })
+        .build()
+        .unwrap();
+    let value = serde_json::to_value(&request).unwrap();
+    assert_eq!(
+        value["messages"][0]["content"][0],
+        serde_json::json!({"type": "input_audio", "input_audio": {"data": "UklGRg==", "format": "wav"}})
+    );
+    assert_eq!(value["modalities"], serde_json::json!(["text", "audio"]));
+    assert_eq!(
+        value["audio"],
+        serde_json::json!({"voice": "coral", "format": "pcm16"})
+    );
+    let round_tripped: CreateChatCompletionRequest = serde_json::from_value(value).unwrap();
+    assert_eq!(round_tripped, request);
+
+    let response: CreateChatCompletionResponse = serde_json::from_value(serde_json::json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion",
+        "created": 0,
+        "model": "gpt-4o-audio-preview",
+        "choices": [{
+            "index": 0,
+            "message": {
+                "role": "assistant",
+                "content": null,
+                "audio": {
+                    "id": "audio_1",
+                    "expires_at": 1729018505,
+                    "data": "UklGRg==",
+                    "transcript": "Hello!"
+                }
+            },
+            "finish_reason": "stop"
+        }]
+    }))
+    .unwrap();
+    let audio = response.choices[0].message.audio.as_ref().unwrap();
+    assert_eq!(audio.id, "audio_1");
+    assert_eq!(audio.transcript, "Hello!");
+}
+
+#[test]
+fn chat_file_serde() {
+    use async_openai_wasm::types::{
+        ChatCompletionRequestMessageContentPartFileArgs, FileObjectArgs,
+    };
+
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content(vec![
+                ChatCompletionRequestMessageContentPartFileArgs::default()
+                    .file(
+                        FileObjectArgs::default()
+                            .file_id("file-abc")
+                            .build()
+                            .unwrap(),
+                    )
+                    .build()
+                    .unwrap()
+                    .into(),
+                ChatCompletionRequestMessageContentPartFileArgs::default()
+                    .file(
+                        FileObjectArgs::default()
+                            .file_data("data:application/pdf;base64,JVBERi0=")
+                            .filename("report.pdf")
+                            .build()
+                            .unwrap(),
+                    )
+                    .build()
+                    .unwrap()
+                    .into(),
+            ])
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap();
+    let value = serde_json::to_value(&request).unwrap();
+    assert_eq!(
+        value["messages"][0]["content"],
+        serde_json::json!([
+            {"type": "file", "file": {"file_id": "file-abc"}},
+            {"type": "file", "file": {"file_data": "data:application/pdf;base64,JVBERi0=", "filename": "report.pdf"}},
+        ])
+    );
+    let round_tripped: CreateChatCompletionRequest = serde_json::from_value(value).unwrap();
+    assert_eq!(round_tripped, request);
+}
+
+#[test]
+fn service_tier_and_usage_details_serde() {
+    use async_openai_wasm::types::{
+        CreateChatCompletionResponse, ServiceTier, ServiceTierResponse,
+    };
+
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("o3-mini")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("hello")
+            .build()
+            .unwrap()
+            .into()])
+        .service_tier(ServiceTier::Flex)
+        .build()
+        .unwrap();
+    let value = serde_json::to_value(&request).unwrap();
+    assert_eq!(value["service_tier"], "flex");
+
+    let response: CreateChatCompletionResponse = serde_json::from_value(serde_json::json!({
+        "id": "chatcmpl-123",
+        "object": "chat.completion",
+        "created": 1677652288,
+        "model": "o3-mini",
+        "service_tier": "default",
+        "system_fingerprint": null,
+        "choices": [],
+        "usage": {
+            "prompt_tokens": 2006,
+            "completion_tokens": 300,
+            "total_tokens": 2306,
+            "prompt_tokens_details": {"cached_tokens": 1920, "audio_tokens": 0},
+            "completion_tokens_details": {"reasoning_tokens": 256}
+        }
+    }))
+    .unwrap();
+    assert_eq!(response.service_tier, Some(ServiceTierResponse::Default));
+    let usage = response.usage.unwrap();
+    assert_eq!(
+        usage.prompt_tokens_details.unwrap().cached_tokens,
+        Some(1920)
+    );
+    assert_eq!(
+        usage.completion_tokens_details.unwrap().reasoning_tokens,
+        Some(256)
+    );
+}
+
+#[test]
+fn fine_tuning_method_serde() {
+    use async_openai_wasm::types::{
+        BatchSize, Beta, CreateFineTuningJobRequestArgs, FineTuneDpoHyperparametersArgs,
+        FineTuneMethod, FineTuneSupervisedHyperparameters, FineTuningJob, Hyperparameters, NEpochs,
+    };
+
+    let request = CreateFineTuningJobRequestArgs::default()
+        .model("gpt-4o-mini")
+        .training_file("file-abc123")
+        .method(FineTuneMethod::dpo(
+            FineTuneDpoHyperparametersArgs::default()
+                .beta(Beta::Beta(0.5))
+                .n_epochs(NEpochs::Auto)
+                .build()
+                .unwrap(),
+        ))
+        .seed(42u32)
+        .build()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        serde_json::json!({
+            "model": "gpt-4o-mini",
+            "training_file": "file-abc123",
+            "method": {"type": "dpo", "dpo": {"hyperparameters": {"beta": 0.5, "n_epochs": "auto"}}},
+            "seed": 42,
+        })
+    );
+
+    let job: FineTuningJob = serde_json::from_value(serde_json::json!({
+        "id": "ftjob-1", "object": "fine_tuning.job", "created_at": 0, "model": "gpt-4o-mini",
+        "organization_id": "org-1", "status": "running", "training_file": "file-abc123",
+        "result_files": [], "seed": 42,
+        "hyperparameters": {"n_epochs": "auto", "batch_size": 8, "learning_rate_multiplier": "auto"},
+        "method": {"type": "supervised", "supervised": {"hyperparameters": {"n_epochs": 3}}},
+    }))
+    .unwrap();
+    assert_eq!(job.hyperparameters.n_epochs, NEpochs::Auto);
+    assert_eq!(
+        job.hyperparameters.batch_size,
+        Some(BatchSize::BatchSize(8))
+    );
+    assert_eq!(
+        job.method,
+        Some(FineTuneMethod::supervised(
+            FineTuneSupervisedHyperparameters {
+                n_epochs: Some(NEpochs::NEpochs(3)),
+                ..Default::default()
+            }
+        ))
+    );
+    assert_eq!(
+        serde_json::to_value(Hyperparameters::default()).unwrap(),
+        serde_json::json!({"n_epochs": "auto"})
+    );
+}
+
+#[test]
+fn api_error_with_string_status_serde() {
+    use async_openai_wasm::error::ApiError;
+
+    // Google style errors of OpenAI compatible servers, e.g. Gemini
+    let error: ApiError = serde_json::from_value(serde_json::json!({
+        "code": 400, "message": "bad", "status": "INVALID_ARGUMENT",
+    }))
+    .unwrap();
+    assert_eq!(error.message, "bad");
+    assert_eq!(error.code.as_deref(), Some("400"));
+    // the status is the one of the HTTP response, not of the body
+    assert_eq!(error.status, None);
+}
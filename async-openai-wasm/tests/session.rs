@@ -0,0 +1,137 @@
+use std::sync::{Arc, Mutex};
+
+use async_openai_wasm::{
+    error::OpenAIError,
+    middleware::{async_trait, RequestInterceptor},
+    session::ChatSession,
+    types::{
+        ChatCompletionMessageToolCall, ChatCompletionRequestAssistantMessageArgs,
+        ChatCompletionRequestMessage, ChatCompletionRequestToolMessageArgs,
+        ChatCompletionRequestUserMessageArgs, ChatCompletionToolType, FunctionCall,
+    },
+    Client,
+};
+use serde_json::Value;
+
+/// Captures request bodies instead of sending requests
+#[derive(Default)]
+struct Capture(Arc<Mutex<Vec<Value>>>);
+
+#[async_trait]
+impl RequestInterceptor for Capture {
+    async fn before_request(&self, request: &mut reqwest::Request) -> Result<(), OpenAIError> {
+        let body = request.body().and_then(|body| body.as_bytes()).unwrap();
+        self.0
+            .lock()
+            .unwrap()
+            .push(serde_json::from_slice(body).unwrap());
+        Err(OpenAIError::InvalidArgument("captured".into()))
+    }
+}
+
+#[tokio::test]
+async fn failed_send_leaves_history_untouched() {
+    let captured = Arc::new(Mutex::new(vec![]));
+    let client = Client::new().with_interceptor(Capture(captured.clone()));
+    let mut session = ChatSession::new(client, "gpt-4o").with_system_prompt("Be brief.");
+    session.settings_mut().temperature = Some(0.5);
+    session.push(
+        ChatCompletionRequestAssistantMessageArgs::default()
+            .content("Hi, how can I help?")
+            .build()
+            .unwrap(),
+    );
+
+    assert!(session.send("Hello!").await.is_err());
+    assert_eq!(session.history().len(), 1);
+
+    let body = &captured.lock().unwrap()[0];
+    assert_eq!(body["model"], "gpt-4o");
+    assert_eq!(body["temperature"], 0.5);
+    let roles: Vec<_> = body["messages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|message| message["role"].as_str().unwrap())
+        .collect();
+    assert_eq!(roles, ["system", "assistant", "user"]);
+    assert_eq!(body["messages"][2]["content"], "Hello!");
+}
+
+#[test]
+fn history_is_truncated_to_token_budget() {
+    let mut session = ChatSession::new(Client::new(), "gpt-4o")
+        .with_system_prompt("Be brief.")
+        .with_max_history_tokens(60);
+    for i in 0..10 {
+        session.push(
+            ChatCompletionRequestAssistantMessageArgs::default()
+                .content(format!("message {i}"))
+                .build()
+                .unwrap(),
+        );
+    }
+
+    let messages = session.messages();
+    assert!(messages.len() > 1 && messages.len() < 11);
+    assert!(matches!(
+        messages[0],
+        ChatCompletionRequestMessage::System(_)
+    ));
+    assert_eq!(messages.last(), session.history().last());
+    assert_eq!(session.history().len(), 10);
+
+    // the latest message is kept whatever its size
+    let session = session.with_max_history_tokens(0);
+    assert_eq!(session.messages().len(), 2);
+}
+
+#[test]
+fn history_never_starts_with_tool_outputs() {
+    let tool_call = |id: &str| ChatCompletionMessageToolCall {
+        id: id.into(),
+        r#type: ChatCompletionToolType::Function,
+        function: FunctionCall {
+            name: "get_weather".into(),
+            arguments: "{}".into(),
+        },
+    };
+    let tool_output = |id: &str| {
+        ChatCompletionRequestToolMessageArgs::default()
+            .tool_call_id(id)
+            .content("sunny")
+            .build()
+            .unwrap()
+    };
+    let mut session = ChatSession::new(Client::new(), "gpt-4o").with_max_history_tokens(0);
+    session.push(
+        ChatCompletionRequestUserMessageArgs::default()
+            .content("Weather in Paris and Rome?")
+            .build()
+            .unwrap(),
+    );
+    session.push(
+        ChatCompletionRequestAssistantMessageArgs::default()
+            .tool_calls(vec![tool_call("call_1"), tool_call("call_2")])
+            .build()
+            .unwrap(),
+    );
+    session.push(tool_output("call_1"));
+    session.push(tool_output("call_2"));
+
+    // the latest message being a tool output, the tool calls it answers are kept with all their outputs
+    let messages = session.messages();
+    assert_eq!(messages, session.history()[1..]);
+    assert!(matches!(
+        messages[0],
+        ChatCompletionRequestMessage::Assistant(_)
+    ));
+
+    session.push(
+        ChatCompletionRequestAssistantMessageArgs::default()
+            .content("Sunny in both.")
+            .build()
+            .unwrap(),
+    );
+    assert_eq!(session.messages(), session.history()[4..]);
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "testing")]
+
+use async_openai_wasm::{
+    error::OpenAIError,
+    testing::{MockResponse, MockServer},
+    types::{CreateSpeechRequestArgs, SpeechModel, Voice},
+};
+use futures::StreamExt;
+
+fn request() -> async_openai_wasm::types::CreateSpeechRequest {
+    CreateSpeechRequestArgs::default()
+        .model(SpeechModel::Tts1)
+        .voice(Voice::Alloy)
+        .input("Hello!")
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn speech_is_streamed_in_chunks() {
+    let server = MockServer::start();
+    server.mock("POST", "/audio/speech", MockResponse::bytes("ID3audi"));
+    let mut stream = server
+        .client()
+        .audio()
+        .create_speech_byte_stream(request())
+        .await
+        .unwrap();
+
+    let mut audio = vec![];
+    while let Some(chunk) = stream.next().await {
+        audio.extend_from_slice(&chunk.unwrap());
+    }
+    assert_eq!(audio, b"ID3audi");
+}
+
+#[tokio::test]
+async fn speech_stream_fails_with_api_error() {
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/audio/speech",
+        MockResponse::error(400, "invalid_request_error", "bad voice"),
+    );
+    let error = server
+        .client()
+        .audio()
+        .create_speech_byte_stream(request())
+        .await
+        .err()
+        .unwrap();
+    assert!(matches!(error, OpenAIError::ApiError(e) if e.message == "bad voice"));
+}
@@ -0,0 +1,96 @@
+#![cfg(feature = "testing")]
+
+use std::time::Duration;
+
+use async_openai_wasm::{
+    retry::RetryPolicy,
+    stats::ClientStats,
+    testing::{MockResponse, MockServer},
+    types::{ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs},
+};
+use futures::StreamExt;
+use serde_json::json;
+
+fn models() -> MockResponse {
+    MockResponse::json(json!({"object": "list", "data": []}))
+}
+
+#[tokio::test]
+async fn requests_and_retries_are_counted_across_clones() {
+    let server = MockServer::start();
+    server
+        .mock(
+            "GET",
+            "/models",
+            MockResponse::error(429, "rate_limit_exceeded", "slow down"),
+        )
+        .mock("GET", "/models", models());
+    let client = server
+        .client()
+        .with_retry_policy(RetryPolicy::default().with_initial_backoff(Duration::from_millis(10)));
+
+    client.clone().models().list().await.unwrap();
+
+    assert_eq!(
+        client.stats(),
+        ClientStats {
+            requests_sent: 2,
+            retries: 1,
+            ..Default::default()
+        }
+    );
+}
+
+#[tokio::test]
+async fn calls_are_in_flight_until_answered() {
+    let server = MockServer::start();
+    server.mock(
+        "GET",
+        "/models",
+        models().with_delay(Duration::from_millis(300)),
+    );
+    let client = server.client();
+
+    let call = tokio::spawn({
+        let client = client.clone();
+        async move { client.models().list().await }
+    });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(client.stats().in_flight_requests, 1);
+
+    call.await.unwrap().unwrap();
+    assert_eq!(client.stats().in_flight_requests, 0);
+}
+
+#[tokio::test]
+async fn streams_are_active_until_ended_or_dropped() {
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/chat/completions",
+        MockResponse::sse([json!({
+            "id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 0, "model": "gpt-4o",
+            "choices": [{"index": 0, "delta": {"content": "Hi"}, "finish_reason": "stop"}],
+        })]),
+    );
+    let client = server.client();
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap();
+
+    let stream = client.chat().create_stream(request.clone()).await.unwrap();
+    let dropped = client.chat().create_stream(request).await.unwrap();
+    assert_eq!(client.stats().active_streams, 2);
+
+    assert_eq!(stream.collect::<Vec<_>>().await.len(), 1);
+    assert_eq!(client.stats().active_streams, 1);
+    drop(dropped);
+    assert_eq!(client.stats().active_streams, 0);
+    assert_eq!(client.stats().requests_sent, 2);
+}
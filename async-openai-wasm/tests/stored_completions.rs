@@ -0,0 +1,140 @@
+#![cfg(feature = "testing")]
+
+use std::collections::HashMap;
+
+use async_openai_wasm::{
+    testing::{MockResponse, MockServer},
+    types::{
+        ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs,
+        UpdateChatCompletionRequestArgs,
+    },
+};
+use futures::StreamExt;
+use serde_json::json;
+
+fn completion(id: &str, metadata: serde_json::Value) -> serde_json::Value {
+    json!({
+        "id": id, "object": "chat.completion", "created": 0, "model": "gpt-4o-mini",
+        "choices": [{
+            "index": 0, "message": {"role": "assistant", "content": "Hi"},
+            "finish_reason": "stop", "logprobs": null,
+        }],
+        "metadata": metadata,
+    })
+}
+
+#[tokio::test]
+async fn completions_are_stored_with_metadata() {
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/chat/completions",
+        MockResponse::json(completion("chatcmpl-1", json!({"topic": "greetings"}))),
+    );
+
+    let response = server
+        .client()
+        .chat()
+        .create(
+            CreateChatCompletionRequestArgs::default()
+                .model("gpt-4o-mini")
+                .messages([ChatCompletionRequestUserMessageArgs::default()
+                    .content("Hello!")
+                    .build()
+                    .unwrap()
+                    .into()])
+                .store(true)
+                .metadata([("topic".to_string(), "greetings".to_string())])
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let sent = server.requests_to("/chat/completions")[0].json();
+    assert_eq!(sent["store"], json!(true));
+    assert_eq!(sent["metadata"], json!({"topic": "greetings"}));
+    assert_eq!(response.metadata.unwrap()["topic"], "greetings");
+}
+
+#[tokio::test]
+async fn stored_completions_are_listed_and_managed() {
+    let server = MockServer::start();
+    server.mock(
+        "GET",
+        "/chat/completions",
+        MockResponse::json(json!({
+            "object": "list", "data": [completion("chatcmpl-1", json!({}))],
+            "first_id": "chatcmpl-1", "last_id": "chatcmpl-1", "has_more": true,
+        })),
+    );
+    server.mock(
+        "GET",
+        "/chat/completions",
+        MockResponse::json(json!({
+            "object": "list", "data": [completion("chatcmpl-2", json!({}))],
+            "first_id": "chatcmpl-2", "last_id": "chatcmpl-2", "has_more": false,
+        })),
+    );
+    server.mock(
+        "GET",
+        "/chat/completions/chatcmpl-2/messages",
+        MockResponse::json(json!({
+            "object": "list",
+            "data": [{"id": "chatcmpl-2-0", "role": "user", "content": "Hello!"}],
+            "first_id": "chatcmpl-2-0", "last_id": "chatcmpl-2-0", "has_more": false,
+        })),
+    );
+    server.mock(
+        "POST",
+        "/chat/completions/chatcmpl-2",
+        MockResponse::json(completion("chatcmpl-2", json!({"reviewed": "yes"}))),
+    );
+    server.mock(
+        "DELETE",
+        "/chat/completions/chatcmpl-2",
+        MockResponse::json(json!({
+            "object": "chat.completion.deleted", "id": "chatcmpl-2", "deleted": true,
+        })),
+    );
+    let client = server.client();
+
+    let completions: Vec<_> = client
+        .chat()
+        .list_auto_paginated(&[("limit", "1"), ("model", "gpt-4o-mini")])
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    assert_eq!(completions.len(), 2);
+    assert_eq!(
+        server.requests_to("/chat/completions")[1].query,
+        "limit=1&model=gpt-4o-mini&after=chatcmpl-1"
+    );
+
+    let messages = client
+        .chat()
+        .messages("chatcmpl-2", &[("order", "asc")])
+        .await
+        .unwrap();
+    assert_eq!(messages.data[0].id, "chatcmpl-2-0");
+    assert_eq!(messages.data[0].message.content.as_deref(), Some("Hello!"));
+
+    let updated = client
+        .chat()
+        .update(
+            "chatcmpl-2",
+            UpdateChatCompletionRequestArgs::default()
+                .metadata(HashMap::from([("reviewed".into(), "yes".into())]))
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(updated.metadata.unwrap()["reviewed"], "yes");
+    assert_eq!(
+        server.requests_to("/chat/completions/chatcmpl-2")[0].json(),
+        json!({"metadata": {"reviewed": "yes"}})
+    );
+
+    assert!(client.chat().delete("chatcmpl-2").await.unwrap().deleted);
+}
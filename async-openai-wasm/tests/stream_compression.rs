@@ -0,0 +1,190 @@
+#![cfg(feature = "testing")]
+
+use async_openai_wasm::{
+    compression::ContentDecoder,
+    error::OpenAIError,
+    testing::{MockResponse, MockServer},
+    types::{
+        ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequest,
+        CreateChatCompletionRequestArgs,
+    },
+    Client,
+};
+use futures::StreamExt;
+use serde_json::json;
+
+const KEY: u8 = 0x5a;
+
+/// Toy encoding XOR-ing bytes with [KEY], decoded a byte late to check that the end of the body is decoded too
+#[derive(Default)]
+struct Xor {
+    pending: Option<u8>,
+}
+
+impl ContentDecoder for Xor {
+    fn decode(&mut self, chunk: &[u8]) -> std::io::Result<Vec<u8>> {
+        let mut decoded: Vec<_> = self.pending.take().into_iter().collect();
+        decoded.extend(chunk.iter().map(|byte| byte ^ KEY));
+        self.pending = decoded.pop();
+        Ok(decoded)
+    }
+
+    fn finish(&mut self) -> std::io::Result<Vec<u8>> {
+        Ok(self.pending.take().into_iter().collect())
+    }
+}
+
+fn request() -> CreateChatCompletionRequest {
+    CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o-mini")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello!")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap()
+}
+
+fn encoded_stream(encoding: &str) -> MockResponse {
+    let chunk = |content: &str| {
+        json!({
+            "id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 0, "model": "gpt-4o-mini",
+            "choices": [{"index": 0, "delta": {"content": content}, "finish_reason": null}],
+        })
+    };
+    let mut response = MockResponse::sse([chunk("Hello"), chunk(" world")])
+        .with_header("content-encoding", encoding);
+    response.body = response.body.iter().map(|byte| byte ^ KEY).collect();
+    response
+}
+
+async fn contents(
+    client: &Client<impl async_openai_wasm::config::Config>,
+) -> Vec<Result<String, OpenAIError>> {
+    client
+        .chat()
+        .create_stream(request())
+        .await
+        .unwrap()
+        .map(|chunk| chunk.map(|chunk| chunk.choices[0].delta.content.clone().unwrap()))
+        .collect()
+        .await
+}
+
+#[tokio::test]
+async fn compressed_streams_are_decoded() {
+    let server = MockServer::start();
+    server.mock("POST", "/chat/completions", encoded_stream("x-xor"));
+    let client = server
+        .client()
+        .with_stream_decoder("x-xor", || Box::<Xor>::default());
+
+    let contents: Vec<_> = contents(&client)
+        .await
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+
+    assert_eq!(contents, ["Hello", " world"]);
+    let sent = &server.requests_to("/chat/completions")[0];
+    assert_eq!(sent.header("accept-encoding"), Some("x-xor"));
+}
+
+#[tokio::test]
+async fn streams_without_decoder_fail() {
+    let server = MockServer::start();
+    server.mock("POST", "/chat/completions", encoded_stream("x-xor"));
+
+    let contents = contents(&server.client()).await;
+
+    assert_eq!(contents.len(), 1);
+    assert!(
+        matches!(&contents[0], Err(OpenAIError::StreamError { message, .. }) if message.contains("x-xor"))
+    );
+}
+
+/// Decoder of data it cannot make sense of
+struct Corrupt;
+
+impl ContentDecoder for Corrupt {
+    fn decode(&mut self, _chunk: &[u8]) -> std::io::Result<Vec<u8>> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "corrupt data",
+        ))
+    }
+
+    fn finish(&mut self) -> std::io::Result<Vec<u8>> {
+        Ok(vec![])
+    }
+}
+
+#[tokio::test]
+async fn failures_to_decode_keep_their_cause() {
+    let server = MockServer::start();
+    server.mock("POST", "/chat/completions", encoded_stream("x-xor"));
+    let client = server
+        .client()
+        .with_stream_decoder("x-xor", || Box::new(Corrupt));
+
+    let contents = contents(&client).await;
+
+    let Err(error) = &contents[0] else {
+        panic!("expected a stream error, got {contents:?}");
+    };
+    assert_eq!(
+        error.to_string(),
+        "stream failed: failed to decode the x-xor stream"
+    );
+    let source = std::error::Error::source(error).unwrap();
+    assert_eq!(source.to_string(), "corrupt data");
+    assert!(source.downcast_ref::<std::io::Error>().is_some());
+}
+
+#[tokio::test]
+async fn identity_encoding_is_asked_for() {
+    let server = MockServer::start();
+    server.mock("POST", "/chat/completions", MockResponse::sse([json!({
+        "id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 0, "model": "gpt-4o-mini",
+        "choices": [{"index": 0, "delta": {"content": "Hello"}, "finish_reason": null}],
+    })]));
+    let client = server
+        .client()
+        .with_stream_decoder("x-xor", || Box::<Xor>::default())
+        .with_identity_stream_encoding(true);
+
+    let contents: Vec<_> = contents(&client)
+        .await
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+
+    assert_eq!(contents, ["Hello"]);
+    let sent = &server.requests_to("/chat/completions")[0];
+    assert_eq!(sent.header("accept-encoding"), Some("identity"));
+}
+
+#[cfg(feature = "gzip")]
+#[tokio::test]
+async fn gzip_streams_are_decoded_with_the_gzip_feature() {
+    use std::io::Write;
+
+    let server = MockServer::start();
+    let mut response = encoded_stream("gzip");
+    let sse: Vec<u8> = response.body.iter().map(|byte| byte ^ KEY).collect();
+    let mut encoder = flate2::write::GzEncoder::new(vec![], flate2::Compression::default());
+    encoder.write_all(&sse).unwrap();
+    response.body = encoder.finish().unwrap();
+    server.mock("POST", "/chat/completions", response);
+
+    let contents: Vec<_> = contents(&server.client())
+        .await
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+
+    assert_eq!(contents, ["Hello", " world"]);
+    let sent = &server.requests_to("/chat/completions")[0];
+    assert!(sent.header("accept-encoding").unwrap().contains("gzip"));
+}
@@ -0,0 +1,32 @@
+//! Unary calls must reject requests asking for a stream, and vice versa, before sending them.
+use async_openai_wasm::{
+    error::OpenAIError,
+    types::{CreateChatCompletionRequestArgs, CreateRunRequestArgs},
+    Client,
+};
+
+#[tokio::test]
+async fn stream_flag_mismatch_is_rejected() {
+    let client = Client::new();
+
+    let request = CreateRunRequestArgs::default()
+        .assistant_id("asst_abc123")
+        .stream(true)
+        .build()
+        .unwrap();
+    let error = client.threads().runs("thread_abc123").create(request).await;
+    assert!(
+        matches!(error, Err(OpenAIError::InvalidArgument(message)) if message.contains("create_stream"))
+    );
+
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([])
+        .stream(false)
+        .build()
+        .unwrap();
+    let error = client.chat().create_stream(request).await;
+    assert!(
+        matches!(error, Err(OpenAIError::InvalidArgument(message)) if message.contains("Chat::create"))
+    );
+}
@@ -0,0 +1,119 @@
+#![cfg(feature = "testing")]
+
+use async_openai_wasm::{
+    recorder::StreamRecorder,
+    testing::{MockResponse, MockServer},
+    types::{
+        ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequest,
+        CreateChatCompletionRequestArgs, CreateRunRequest,
+    },
+};
+use futures::StreamExt;
+use serde_json::json;
+
+fn chat_request() -> CreateChatCompletionRequest {
+    CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o-mini")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello!")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap()
+}
+
+fn chunk(content: &str) -> serde_json::Value {
+    json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion.chunk",
+        "created": 0,
+        "model": "gpt-4o-mini",
+        "choices": [{"index": 0, "delta": {"content": content}, "finish_reason": null}],
+    })
+}
+
+#[tokio::test]
+async fn streams_are_recorded_and_replayed() {
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/chat/completions",
+        MockResponse::sse([chunk("Hel"), chunk("lo")]),
+    );
+    let recorder = StreamRecorder::new();
+    let client = server.client().with_stream_recorder(recorder.clone());
+
+    let chunks: Vec<_> = client
+        .chat()
+        .create_stream(chat_request())
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    assert_eq!(chunks.len(), 2);
+
+    let events = recorder.events();
+    assert_eq!(events.len(), 3);
+    assert!(events.iter().all(|event| event.stream == 0
+        && event.path == "/v1/chat/completions"
+        && event.event == "message"
+        && event.timestamp_ms > 0));
+    assert_eq!(events[2].data, "[DONE]");
+
+    // the log of the stream drives another server
+    let log = StreamRecorder::from_jsonl(&recorder.to_jsonl()).unwrap();
+    assert_eq!(log.events(), events);
+    let replay = MockServer::start();
+    replay.mock(
+        "POST",
+        "/chat/completions",
+        MockResponse::replay(&log.stream_events(0)),
+    );
+    let text: String = replay
+        .client()
+        .chat()
+        .create_stream(chat_request())
+        .await
+        .unwrap()
+        .map(|chunk| chunk.unwrap().text().into_owned())
+        .collect()
+        .await;
+    assert_eq!(text, "Hello");
+}
+
+#[tokio::test]
+async fn named_events_are_recorded_per_stream() {
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/threads/thread_1/runs",
+        MockResponse::raw_sse("event: done\ndata: [DONE]\n\n"),
+    );
+    let recorder = StreamRecorder::new();
+    let client = server.client().with_stream_recorder(recorder.clone());
+    let threads = client.threads();
+    let runs = threads.runs("thread_1");
+
+    for _ in 0..2 {
+        let request = CreateRunRequest {
+            assistant_id: "asst_1".into(),
+            ..Default::default()
+        };
+        let events: Vec<_> = runs.create_stream(request).await.unwrap().collect().await;
+        assert_eq!(events.len(), 1);
+    }
+
+    let events = recorder.events();
+    assert_eq!(
+        events
+            .iter()
+            .map(|event| (event.stream, event.event.as_str()))
+            .collect::<Vec<_>>(),
+        [(0, "done"), (1, "done")]
+    );
+    assert_eq!(recorder.stream_events(1).len(), 1);
+
+    recorder.clear();
+    assert!(recorder.events().is_empty());
+}
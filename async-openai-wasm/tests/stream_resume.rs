@@ -0,0 +1,218 @@
+#![cfg(feature = "testing")]
+
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc,
+};
+use std::time::Duration;
+
+use async_openai_wasm::{
+    error::OpenAIError,
+    middleware::{async_trait, RequestInterceptor},
+    retry::StreamRetryPolicy,
+    testing::{MockResponse, MockServer},
+    types::{
+        ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequest,
+        CreateChatCompletionRequestArgs,
+    },
+};
+use futures::StreamExt;
+use serde_json::{json, Value};
+
+fn delta(delta: Value, finish_reason: Value) -> Value {
+    json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion.chunk",
+        "created": 1,
+        "model": "gpt-4o",
+        "choices": [{"index": 0, "delta": delta, "finish_reason": finish_reason}],
+    })
+}
+
+/// SSE stream of `events` cut before the end of the answer, without `data: [DONE]`
+fn cut(events: &[Value]) -> MockResponse {
+    let body: String = events
+        .iter()
+        .map(|event| format!("data: {event}\n\n"))
+        .collect();
+    MockResponse::raw_sse(body)
+}
+
+fn chat_request() -> CreateChatCompletionRequest {
+    CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Say hello")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap()
+}
+
+fn policy() -> StreamRetryPolicy {
+    StreamRetryPolicy::default().with_initial_backoff(Duration::from_millis(10))
+}
+
+#[tokio::test]
+async fn stream_is_resumed_with_the_content_received_so_far() {
+    let server = MockServer::start();
+    server
+        .mock(
+            "POST",
+            "/chat/completions",
+            cut(&[
+                delta(
+                    json!({"role": "assistant", "content": "Hello"}),
+                    Value::Null,
+                ),
+                delta(json!({"content": ", wor"}), Value::Null),
+            ]),
+        )
+        .mock(
+            "POST",
+            "/chat/completions",
+            MockResponse::sse([
+                delta(json!({"role": "assistant", "content": "ld!"}), Value::Null),
+                delta(json!({}), json!("stop")),
+            ]),
+        );
+
+    let client = server.client();
+    let chunks: Vec<_> = client
+        .chat()
+        .create_stream_resumable(chat_request(), policy())
+        .collect()
+        .await;
+    let content: String = chunks
+        .into_iter()
+        .map(|chunk| chunk.unwrap())
+        .filter_map(|chunk| chunk.choices[0].delta.content.clone())
+        .collect();
+    assert_eq!(content, "Hello, world!");
+
+    let requests = server.requests_to("/chat/completions");
+    assert_eq!(requests.len(), 2);
+    let messages = requests[1].json()["messages"].clone();
+    assert_eq!(messages.as_array().unwrap().len(), 2);
+    assert_eq!(messages[1]["role"], "assistant");
+    assert_eq!(messages[1]["content"], "Hello, wor");
+}
+
+/// Fails the second request to connect, as if the network was still down
+struct FailSecondConnect(Arc<AtomicU32>);
+
+#[async_trait]
+impl RequestInterceptor for FailSecondConnect {
+    async fn before_request(&self, _request: &mut reqwest::Request) -> Result<(), OpenAIError> {
+        match self.0.fetch_add(1, Ordering::SeqCst) {
+            1 => Err(OpenAIError::Timeout(Duration::from_secs(1))),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[tokio::test]
+async fn failures_to_reconnect_are_resumed() {
+    let server = MockServer::start();
+    server
+        .mock(
+            "POST",
+            "/chat/completions",
+            cut(&[delta(
+                json!({"role": "assistant", "content": "Hello"}),
+                Value::Null,
+            )]),
+        )
+        .mock(
+            "POST",
+            "/chat/completions",
+            MockResponse::sse([
+                delta(json!({"content": ", world!"}), Value::Null),
+                delta(json!({}), json!("stop")),
+            ]),
+        );
+
+    let connects = Arc::new(AtomicU32::new(0));
+    let client = server
+        .client()
+        .with_interceptor(FailSecondConnect(connects.clone()));
+    let content: String = client
+        .chat()
+        .create_stream_resumable(chat_request(), policy().with_max_resumes(2))
+        .map(|chunk| chunk.unwrap())
+        .filter_map(|chunk| async move { chunk.choices[0].delta.content.clone() })
+        .collect()
+        .await;
+    assert_eq!(content, "Hello, world!");
+    assert_eq!(connects.load(Ordering::SeqCst), 3);
+    assert_eq!(server.requests_to("/chat/completions").len(), 2);
+
+    // the failed reconnect counts as a resume
+    server.reset();
+    server.mock(
+        "POST",
+        "/chat/completions",
+        cut(&[delta(json!({"content": "Hello"}), Value::Null)]),
+    );
+    let connects = Arc::new(AtomicU32::new(0));
+    let client = server
+        .client()
+        .with_interceptor(FailSecondConnect(connects.clone()));
+    let chunks: Vec<_> = client
+        .chat()
+        .create_stream_resumable(chat_request(), policy().with_max_resumes(1))
+        .collect()
+        .await;
+    assert!(matches!(chunks.last(), Some(Err(OpenAIError::Timeout(_)))));
+    assert_eq!(connects.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn stream_fails_once_resumes_are_spent() {
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/chat/completions",
+        cut(&[delta(json!({"content": "Hel"}), Value::Null)]),
+    );
+
+    let client = server.client();
+    let chunks: Vec<_> = client
+        .chat()
+        .create_stream_resumable(chat_request(), policy().with_max_resumes(2))
+        .collect()
+        .await;
+    assert_eq!(chunks.len(), 4);
+    assert!(matches!(
+        chunks.last(),
+        Some(Err(OpenAIError::StreamError { .. }))
+    ));
+
+    let requests = server.requests_to("/chat/completions");
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[2].json()["messages"][1]["content"], "HelHel");
+}
+
+#[tokio::test]
+async fn tool_calls_are_not_resumed() {
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/chat/completions",
+        cut(&[delta(
+            json!({"tool_calls": [{"index": 0, "id": "call_1", "type": "function", "function": {"name": "get_weather", "arguments": "{\"ci"}}]}),
+            Value::Null,
+        )]),
+    );
+
+    let client = server.client();
+    let chunks: Vec<_> = client
+        .chat()
+        .create_stream_resumable(chat_request(), policy())
+        .collect()
+        .await;
+    assert_eq!(chunks.len(), 2);
+    assert!(chunks[1].is_err());
+    assert_eq!(server.requests_to("/chat/completions").len(), 1);
+}
@@ -0,0 +1,190 @@
+use async_openai_wasm::{
+    error::OpenAIError,
+    structured_output::{strict_schema, tagged_union},
+};
+use serde::Deserialize;
+use serde_json::json;
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Shape {
+    Circle { radius: f64 },
+    Empty,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Drawing {
+    shape: Shape,
+    label: Option<String>,
+}
+
+#[test]
+fn tagged_unions_are_made_strict() {
+    let shape = tagged_union(
+        "kind",
+        [
+            (
+                "circle",
+                json!({"type": "object", "properties": {"radius": {"type": "number"}}, "required": ["radius"]}),
+            ),
+            ("empty", json!({"type": "object"})),
+        ],
+    )
+    .unwrap();
+    let schema = strict_schema(json!({
+        "type": "object",
+        "properties": {"shape": shape, "label": {"type": "string"}},
+        "required": ["shape"],
+    }))
+    .unwrap();
+
+    assert_eq!(
+        schema,
+        json!({
+            "type": "object",
+            "properties": {
+                "shape": {"anyOf": [
+                    {
+                        "type": "object",
+                        "properties": {
+                            "kind": {"type": "string", "enum": ["circle"]},
+                            "radius": {"type": "number"},
+                        },
+                        "required": ["kind", "radius"],
+                        "additionalProperties": false,
+                    },
+                    {
+                        "type": "object",
+                        "properties": {"kind": {"type": "string", "enum": ["empty"]}},
+                        "required": ["kind"],
+                        "additionalProperties": false,
+                    },
+                ]},
+                "label": {"type": ["string", "null"]},
+            },
+            "required": ["label", "shape"],
+            "additionalProperties": false,
+        })
+    );
+
+    // what the model answers with the schema deserializes into the Rust types
+    let drawing: Drawing =
+        serde_json::from_value(json!({"shape": {"kind": "empty"}, "label": null})).unwrap();
+    assert_eq!(
+        drawing,
+        Drawing {
+            shape: Shape::Empty,
+            label: None
+        }
+    );
+}
+
+#[test]
+fn one_of_becomes_any_of_and_optional_references_nullable() {
+    let schema = strict_schema(json!({
+        "type": "object",
+        "properties": {
+            "point": {"$ref": "#/$defs/Point"},
+            "unit": {"type": "string", "enum": ["cm", "in"]},
+        },
+        "$defs": {"Point": {"oneOf": [{"type": "number"}, {"type": "string"}]}},
+    }))
+    .unwrap();
+
+    assert_eq!(
+        schema["properties"]["point"],
+        json!({"anyOf": [{"$ref": "#/$defs/Point"}, {"type": "null"}]})
+    );
+    assert_eq!(
+        schema["properties"]["unit"],
+        json!({"type": ["string", "null"], "enum": ["cm", "in", null]})
+    );
+    assert_eq!(
+        schema["$defs"]["Point"],
+        json!({"anyOf": [{"type": "number"}, {"type": "string"}]})
+    );
+}
+
+#[test]
+fn unsupported_constructs_are_pointed_at() {
+    let field = |result: Result<_, OpenAIError>| match result.unwrap_err() {
+        OpenAIError::InvalidField(error) => error.field,
+        error => panic!("unexpected error: {error}"),
+    };
+
+    let error = strict_schema(json!({
+        "type": "object",
+        "properties": {"sizes": {"type": "array", "items": {"type": "integer", "minimum": 0}}},
+    }))
+    .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "invalid args: `#/properties/sizes/items/minimum` is not supported by strict structured outputs; \
+         state the constraint in `description` and check it after parsing the output"
+    );
+
+    let map = json!({
+        "type": "object",
+        "properties": {"scores": {"type": "object", "additionalProperties": {"type": "number"}}},
+    });
+    assert_eq!(
+        field(strict_schema(map)),
+        "#/properties/scores/additionalProperties"
+    );
+
+    let union = tagged_union("kind", [("circle", json!({"type": "object"}))]).unwrap();
+    assert_eq!(field(strict_schema(union)), "#");
+
+    let conflicting = json!({"type": "object", "properties": {"kind": {"type": "string"}}});
+    assert_eq!(
+        field(tagged_union(
+            "kind",
+            [("a", json!({"type": "object"})), ("b", conflicting)]
+        )),
+        "#/anyOf/1/properties/kind"
+    );
+    assert_eq!(
+        field(tagged_union(
+            "kind",
+            [("newtype", json!({"type": "number"}))]
+        )),
+        "#/anyOf/0"
+    );
+}
+
+#[test]
+fn schemas_are_validated_without_changes() {
+    use async_openai_wasm::structured_output::validate_strict_schema;
+
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "name": {"type": "string"},
+            "born": {"type": "string", "format": "date"},
+            "tags": {"type": "array", "items": {"type": "string", "maxLength": 10}},
+            "extra": {"type": "object", "additionalProperties": true},
+        },
+        "required": ["name", "born", "tags"],
+    });
+    let pointers: Vec<_> = validate_strict_schema(&schema)
+        .into_iter()
+        .map(|issue| issue.pointer)
+        .collect();
+    assert_eq!(
+        pointers,
+        [
+            "#",
+            "#/properties/extra",
+            "#/properties/extra/additionalProperties",
+            "#/properties/tags/items/maxLength",
+        ]
+    );
+
+    let strict = strict_schema(json!({
+        "type": "object",
+        "properties": {"name": {"type": "string"}, "nickname": {"type": "string"}},
+        "required": ["name"],
+    }))
+    .unwrap();
+    assert_eq!(validate_strict_schema(&strict), vec![]);
+}
@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use async_openai_wasm::{
+    config::OpenAIConfig,
+    types::{
+        ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs,
+        CreateEmbeddingRequestArgs, EncodingFormat,
+    },
+    Client,
+};
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Metadata, Subscriber,
+};
+
+type Fields = Arc<Mutex<HashMap<String, String>>>;
+
+/// Collects the fields of `gen_ai` spans
+struct GenAiFields(Fields);
+
+impl Visit for GenAiFields {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .lock()
+            .unwrap()
+            .insert(field.name().to_string(), format!("{value:?}"));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0
+            .lock()
+            .unwrap()
+            .insert(field.name().to_string(), value.to_string());
+    }
+}
+
+struct Collector {
+    fields: Fields,
+    gen_ai: Mutex<Option<Id>>,
+}
+
+impl Subscriber for Collector {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        if span.metadata().name() == "gen_ai" {
+            span.record(&mut GenAiFields(self.fields.clone()));
+            *self.gen_ai.lock().unwrap() = Some(Id::from_u64(1));
+            Id::from_u64(1)
+        } else {
+            Id::from_u64(2)
+        }
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        if self.gen_ai.lock().unwrap().as_ref() == Some(span) {
+            values.record(&mut GenAiFields(self.fields.clone()));
+        }
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, _event: &Event<'_>) {}
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[tokio::test]
+async fn chat_span_follows_gen_ai_conventions() {
+    let fields: Fields = Default::default();
+    let _guard = tracing::subscriber::set_default(Collector {
+        fields: fields.clone(),
+        gen_ai: Mutex::new(None),
+    });
+
+    // nothing listens on port 1, so the call fails
+    let client = Client::with_config(OpenAIConfig::new().with_api_base("http://127.0.0.1:1"));
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .temperature(0.5)
+        .max_tokens(100_u16)
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello!")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap();
+    assert!(client.chat().create(request).await.is_err());
+
+    let fields = fields.lock().unwrap();
+    assert_eq!(fields["otel.name"], "chat gpt-4o");
+    assert_eq!(fields["gen_ai.system"], "openai");
+    assert_eq!(fields["gen_ai.operation.name"], "chat");
+    assert_eq!(fields["gen_ai.request.model"], "gpt-4o");
+    assert_eq!(fields["gen_ai.request.max_tokens"], "100");
+    assert_eq!(fields["gen_ai.request.temperature"], "0.5");
+    assert_eq!(fields["error.type"], "http_error");
+}
+
+#[tokio::test]
+async fn chat_span_records_max_completion_tokens() {
+    let fields: Fields = Default::default();
+    let _guard = tracing::subscriber::set_default(Collector {
+        fields: fields.clone(),
+        gen_ai: Mutex::new(None),
+    });
+
+    let client = Client::with_config(OpenAIConfig::new().with_api_base("http://127.0.0.1:1"));
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("o3-mini")
+        .max_completion_tokens(2000_u32)
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello!")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap();
+    assert!(client.chat().create(request).await.is_err());
+
+    assert_eq!(fields.lock().unwrap()["gen_ai.request.max_tokens"], "2000");
+}
+
+#[tokio::test]
+async fn base64_embeddings_span_follows_gen_ai_conventions() {
+    let fields: Fields = Default::default();
+    let _guard = tracing::subscriber::set_default(Collector {
+        fields: fields.clone(),
+        gen_ai: Mutex::new(None),
+    });
+
+    // nothing listens on port 1, so the call fails
+    let client = Client::with_config(OpenAIConfig::new().with_api_base("http://127.0.0.1:1"));
+    let request = CreateEmbeddingRequestArgs::default()
+        .model("text-embedding-3-small")
+        .input("Hello!")
+        .encoding_format(EncodingFormat::Base64)
+        .build()
+        .unwrap();
+    assert!(client.embeddings().create_base64(request).await.is_err());
+
+    let fields = fields.lock().unwrap();
+    assert_eq!(fields["otel.name"], "embeddings text-embedding-3-small");
+    assert_eq!(fields["gen_ai.operation.name"], "embeddings");
+    assert_eq!(fields["error.type"], "http_error");
+}
@@ -0,0 +1,101 @@
+#![cfg(feature = "testing")]
+
+use async_openai_wasm::{
+    testing::{MockResponse, MockServer},
+    types::{
+        AssistantStreamEvent, CreateMessageRequestArgs, CreateThreadAndRunRequestArgs,
+        CreateThreadRequestArgs, RunStatus,
+    },
+};
+use futures::StreamExt;
+use serde_json::{json, Value};
+
+fn run(status: &str) -> Value {
+    json!({
+        "id": "run_1", "object": "thread.run", "created_at": 0, "thread_id": "thread_1",
+        "assistant_id": "asst_1", "status": status, "required_action": null,
+        "last_error": null, "expires_at": null, "started_at": null, "cancelled_at": null,
+        "failed_at": null, "completed_at": null, "incomplete_details": null, "model": "gpt-4o",
+        "instructions": "", "tools": [], "metadata": null, "usage": null, "temperature": null,
+        "top_p": null, "max_prompt_tokens": null, "max_completion_tokens": null,
+        "truncation_strategy": null, "tool_choice": null, "parallel_tool_calls": true,
+        "response_format": null,
+    })
+}
+
+fn request() -> CreateThreadAndRunRequestArgs {
+    let mut request = CreateThreadAndRunRequestArgs::default();
+    request.assistant_id("asst_1").thread(
+        CreateThreadRequestArgs::default()
+            .messages([CreateMessageRequestArgs::default()
+                .content("Hello")
+                .build()
+                .unwrap()])
+            .build()
+            .unwrap(),
+    );
+    request
+}
+
+#[tokio::test]
+async fn threads_are_created_and_run_in_one_request() {
+    let server = MockServer::start();
+    server.mock("POST", "/threads/runs", MockResponse::json(run("queued")));
+
+    let run = server
+        .client()
+        .threads()
+        .create_and_run(request().build().unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(run.thread_id, "thread_1");
+    assert_eq!(run.status, RunStatus::Queued);
+    let sent = server.requests_to("/threads/runs")[0].json();
+    assert_eq!(sent["assistant_id"], "asst_1");
+    assert_eq!(sent["thread"]["messages"][0]["content"], "Hello");
+    assert!(sent.get("stream").is_none());
+
+    let error = server
+        .client()
+        .threads()
+        .create_and_run(request().stream(true).build().unwrap())
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("create_and_run_stream"));
+}
+
+#[tokio::test]
+async fn threads_are_created_and_run_streamed() {
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/threads/runs",
+        MockResponse::raw_sse(format!(
+            "event: thread.run.created\ndata: {}\n\nevent: thread.run.completed\ndata: {}\n\nevent: done\ndata: [DONE]\n\n",
+            run("queued"),
+            run("completed"),
+        )),
+    );
+
+    let events: Vec<_> = server
+        .client()
+        .threads()
+        .create_and_run_stream(request().build().unwrap())
+        .await
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    assert!(
+        matches!(&events[0], AssistantStreamEvent::ThreadRunCreated(run) if run.status == RunStatus::Queued)
+    );
+    assert!(
+        matches!(&events[1], AssistantStreamEvent::ThreadRunCompleted(run) if run.status == RunStatus::Completed)
+    );
+    assert_eq!(
+        server.requests_to("/threads/runs")[0].json()["stream"],
+        true
+    );
+}
@@ -0,0 +1,159 @@
+#![cfg(feature = "testing")]
+
+use std::time::Duration;
+
+use async_openai_wasm::{
+    error::OpenAIError,
+    testing::{MockResponse, MockServer},
+    types::{
+        ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequest,
+        CreateChatCompletionRequestArgs,
+    },
+    RequestOptions,
+};
+use futures::StreamExt;
+use reqwest::header::HeaderValue;
+use serde_json::json;
+
+fn models() -> MockResponse {
+    MockResponse::json(json!({"object": "list", "data": []}))
+}
+
+fn chat_request() -> CreateChatCompletionRequest {
+    CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o-mini")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello!")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn slow_calls_time_out() {
+    let server = MockServer::start();
+    server.mock(
+        "GET",
+        "/models",
+        models().with_delay(Duration::from_millis(500)),
+    );
+    let client = server.client().with_timeout(Duration::from_millis(50));
+
+    let result = client.models().list().await;
+
+    assert!(
+        matches!(result, Err(OpenAIError::Timeout(timeout)) if timeout == Duration::from_millis(50))
+    );
+}
+
+#[tokio::test]
+async fn request_options_override_the_timeout_and_add_headers() {
+    let server = MockServer::start();
+    server.mock(
+        "GET",
+        "/models",
+        models().with_delay(Duration::from_millis(100)),
+    );
+    let client = server.client().with_timeout(Duration::from_millis(20));
+    let options = RequestOptions::new()
+        .with_timeout(Duration::from_secs(5))
+        .with_header("x-request-source", HeaderValue::from_static("batch"));
+
+    client
+        .with_request_options(&options)
+        .models()
+        .list()
+        .await
+        .unwrap();
+
+    let request = &server.requests_to("/models")[0];
+    assert_eq!(request.header("x-request-source"), Some("batch"));
+    // the options are for one call only
+    assert!(matches!(
+        client.models().list().await,
+        Err(OpenAIError::Timeout(_))
+    ));
+}
+
+#[tokio::test]
+async fn hung_streams_time_out() {
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/chat/completions",
+        MockResponse::sse(Vec::<serde_json::Value>::new()).with_delay(Duration::from_millis(500)),
+    );
+    let options = RequestOptions::new().with_timeout(Duration::from_millis(50));
+
+    let mut stream = server
+        .client()
+        .chat()
+        .create_stream_with_options(chat_request(), &options)
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        stream.next().await,
+        Some(Err(OpenAIError::Timeout(_)))
+    ));
+    assert!(stream.next().await.is_none());
+}
+
+fn chunk() -> serde_json::Value {
+    json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion.chunk",
+        "created": 1,
+        "model": "gpt-4o-mini",
+        "choices": [{"index": 0, "delta": {"content": "Hello"}, "finish_reason": null}],
+    })
+}
+
+#[tokio::test]
+async fn stalled_streams_fail_with_stream_stalled() {
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/chat/completions",
+        MockResponse::sse([chunk()]).with_stall(Duration::from_millis(500)),
+    );
+    let client = server
+        .client()
+        .with_stream_watchdog(Duration::from_millis(50));
+
+    let mut stream = client.chat().create_stream(chat_request()).await.unwrap();
+
+    assert!(stream.next().await.unwrap().is_ok());
+    assert!(matches!(
+        stream.next().await,
+        Some(Err(OpenAIError::StreamStalled(after))) if after == Duration::from_millis(50)
+    ));
+    assert!(stream.next().await.is_none());
+    assert_eq!(client.stats().active_streams, 0);
+}
+
+#[tokio::test]
+async fn stream_watchdog_waits_for_the_first_event() {
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/chat/completions",
+        MockResponse::sse([chunk(), chunk()]).with_delay(Duration::from_millis(200)),
+    );
+    let client = server
+        .client()
+        .with_stream_watchdog(Duration::from_millis(50));
+
+    let chunks: Vec<_> = client
+        .chat()
+        .create_stream(chat_request())
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+    assert_eq!(chunks.len(), 2);
+    assert!(chunks.iter().all(Result::is_ok));
+}
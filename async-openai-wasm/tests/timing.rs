@@ -0,0 +1,81 @@
+#![cfg(feature = "testing")]
+
+use std::time::Duration;
+
+use async_openai_wasm::{
+    testing::{MockResponse, MockServer},
+    timing::ResponseTiming,
+    types::{ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs},
+};
+use futures::StreamExt;
+use reqwest::header::{HeaderMap, HeaderValue};
+use serde_json::json;
+
+#[test]
+fn timings_are_parsed_from_headers() {
+    let mut headers = HeaderMap::new();
+    headers.insert("openai-processing-ms", HeaderValue::from_static("250.5"));
+    headers.insert(
+        "x-envoy-upstream-service-time",
+        HeaderValue::from_static("260"),
+    );
+    let timing = ResponseTiming::from_headers(&headers, Duration::from_millis(400));
+
+    assert_eq!(timing.processing, Some(Duration::from_micros(250_500)));
+    assert_eq!(timing.upstream, Some(Duration::from_millis(260)));
+    assert_eq!(timing.overhead(), Some(Duration::from_micros(149_500)));
+
+    headers.insert("openai-processing-ms", HeaderValue::from_static("-1"));
+    let timing = ResponseTiming::from_headers(&headers, Duration::from_millis(400));
+    assert_eq!(timing.processing, None);
+    assert_eq!(timing.overhead(), None);
+}
+
+#[tokio::test]
+async fn timings_of_the_last_response_are_kept() {
+    let server = MockServer::start();
+    server.mock(
+        "GET",
+        "/models",
+        MockResponse::json(json!({"object": "list", "data": []}))
+            .with_header("openai-processing-ms", "12"),
+    );
+    server.mock(
+        "POST",
+        "/chat/completions",
+        MockResponse::sse([json!({
+            "id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 0, "model": "gpt-4o-mini",
+            "choices": [{"index": 0, "delta": {"content": "Hi"}, "finish_reason": null}],
+        })])
+        .with_header("openai-processing-ms", "34"),
+    );
+    let client = server.client();
+    assert_eq!(client.last_response_timing(), None);
+
+    client.models().list().await.unwrap();
+    let timing = client.last_response_timing().unwrap();
+    assert_eq!(timing.processing, Some(Duration::from_millis(12)));
+    assert!(timing.latency > Duration::ZERO);
+
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o-mini")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello!")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap();
+    let chunks: Vec<_> = client
+        .chat()
+        .create_stream(request)
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(
+        client.last_response_timing().unwrap().processing,
+        Some(Duration::from_millis(34))
+    );
+}
@@ -0,0 +1,202 @@
+#![cfg(feature = "tokens")]
+
+use async_openai_wasm::{
+    tokens::{self, Encoding, EncodingName, TokenCount},
+    types::{
+        token_count, ChatCompletionRequestMessageContentPartAudioArgs,
+        ChatCompletionRequestMessageContentPartImageArgs,
+        ChatCompletionRequestMessageContentPartTextArgs, ChatCompletionRequestSystemMessageArgs,
+        ChatCompletionRequestUserMessageArgs, ImageDetail, ImageUrlArgs, InputAudioArgs,
+        InputAudioFormat, Prompt,
+    },
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use std::time::Duration;
+
+/// All single bytes, then a few merges
+fn tiktoken_file() -> String {
+    let mut tokens: Vec<Vec<u8>> = (0..=255u8).map(|byte| vec![byte]).collect();
+    for merge in [
+        "he", "ll", "llo", "hello", " w", " wo", "rl", " worl", " world",
+    ] {
+        tokens.push(merge.as_bytes().to_vec());
+    }
+    tokens
+        .iter()
+        .enumerate()
+        .map(|(rank, token)| format!("{} {rank}", STANDARD.encode(token)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn counts_with_registered_encoding() {
+    let encoding = Encoding::from_tiktoken(EncodingName::Cl100kBase, &tiktoken_file()).unwrap();
+    assert_eq!(encoding.encode("hello world"), vec![259, 264]);
+    // "he" "y", "!" being its own piece
+    assert_eq!(encoding.encode("hey world!"), vec![256, 121, 264, 33]);
+    tokens::register_encoding(encoding);
+
+    assert_eq!(
+        tokens::text_token_count("gpt-4", "hello world"),
+        TokenCount::Exact(2)
+    );
+    assert_eq!(
+        Prompt::StringArray(vec!["hello".into(), "hello world".into()]).token_len("gpt-4"),
+        TokenCount::Exact(3)
+    );
+    assert_eq!(
+        Prompt::IntegerArray(vec![1, 2, 3]).token_len("gpt-4"),
+        TokenCount::Exact(3)
+    );
+
+    let messages = [
+        ChatCompletionRequestSystemMessageArgs::default()
+            .content("hello")
+            .build()
+            .unwrap()
+            .into(),
+        ChatCompletionRequestUserMessageArgs::default()
+            .content("hello world")
+            .name("hello")
+            .build()
+            .unwrap()
+            .into(),
+    ];
+    // 3 per message and 3 for the reply, plus 1 for the name
+    assert_eq!(
+        token_count("gpt-4", &messages),
+        TokenCount::Exact(3 + (3 + 1) + (3 + 2 + 1 + 1))
+    );
+}
+
+#[test]
+fn estimates_without_encoding() {
+    let count = tokens::text_token_count("some-model", "12345678");
+    assert_eq!(count, TokenCount::Estimate(2));
+    assert!(count.is_estimate());
+    assert_eq!(count.get(), 2);
+    // an estimate makes the whole count one
+    assert_eq!(
+        Prompt::StringArray(vec!["hello".into(), "12345678".into()]).token_len("some-model"),
+        TokenCount::Estimate(2 + 2)
+    );
+    assert_eq!(
+        TokenCount::Exact(3) + TokenCount::Estimate(2),
+        TokenCount::Estimate(5)
+    );
+    assert_eq!(
+        EncodingName::for_model("gpt-4o-mini"),
+        Some(EncodingName::O200kBase)
+    );
+    assert_eq!(
+        EncodingName::for_model("ft:gpt-3.5-turbo:org::id"),
+        Some(EncodingName::Cl100kBase)
+    );
+    assert!(Encoding::from_tiktoken(EncodingName::O200kBase, "not base64!").is_err());
+}
+
+#[test]
+fn counts_images_and_audio() {
+    assert_eq!(
+        tokens::image_token_count("gpt-4o", 1024, 1024, &ImageDetail::Low),
+        85
+    );
+    // 768 by 768 pixels once scaled, in 2 by 2 tiles
+    assert_eq!(
+        tokens::image_token_count("gpt-4o", 1024, 1024, &ImageDetail::High),
+        85 + 4 * 170
+    );
+    // 768 by 1536 pixels once scaled, in 2 by 3 tiles
+    assert_eq!(
+        tokens::image_token_count("gpt-4o", 2048, 4096, &ImageDetail::Auto),
+        85 + 6 * 170
+    );
+    assert_eq!(
+        tokens::image_token_count("gpt-4o-mini", 512, 512, &ImageDetail::High),
+        2833 + 5667
+    );
+    assert_eq!(tokens::audio_token_count(Duration::from_millis(2_550)), 26);
+
+    // a 16 by 16 pixels PNG
+    let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+    png.extend(16u32.to_be_bytes());
+    png.extend(16u32.to_be_bytes());
+    let image = |url: String| {
+        ChatCompletionRequestMessageContentPartImageArgs::default()
+            .image_url(ImageUrlArgs::default().url(url).build().unwrap())
+            .build()
+            .unwrap()
+            .into()
+    };
+    let messages = [ChatCompletionRequestUserMessageArgs::default()
+        .content(vec![
+            ChatCompletionRequestMessageContentPartTextArgs::default()
+                .text("12345678")
+                .build()
+                .unwrap()
+                .into(),
+            image(format!("data:image/png;base64,{}", STANDARD.encode(&png))),
+            image("https://example.com/cat.png".into()),
+        ])
+        .build()
+        .unwrap()
+        .into()];
+    // the image of unknown dimensions counts as the largest tiling, 2 by 4 tiles
+    assert_eq!(
+        token_count("some-model", &messages),
+        TokenCount::Estimate(3 + 3 + 2 + (85 + 170) + (85 + 8 * 170))
+    );
+}
+
+#[test]
+fn counts_input_audio() {
+    // 1.5 seconds of 16 kHz mono 16-bit PCM
+    let samples = vec![0u8; 48_000];
+    let mut wav = b"RIFF".to_vec();
+    wav.extend((36 + samples.len() as u32).to_le_bytes());
+    wav.extend(b"WAVEfmt ");
+    wav.extend(16u32.to_le_bytes());
+    wav.extend(1u16.to_le_bytes());
+    wav.extend(1u16.to_le_bytes());
+    wav.extend(16_000u32.to_le_bytes());
+    wav.extend(32_000u32.to_le_bytes());
+    wav.extend(2u16.to_le_bytes());
+    wav.extend(16u16.to_le_bytes());
+    wav.extend(b"data");
+    wav.extend((samples.len() as u32).to_le_bytes());
+    wav.extend(&samples);
+
+    let audio = |data: &[u8], format| {
+        InputAudioArgs::default()
+            .data(STANDARD.encode(data))
+            .format(format)
+            .build()
+            .unwrap()
+    };
+    assert_eq!(
+        tokens::input_audio_token_count(&audio(&wav, InputAudioFormat::Wav)),
+        15
+    );
+    // 2 seconds at 128 kbps
+    assert_eq!(
+        tokens::input_audio_token_count(&audio(&[0; 32_000], InputAudioFormat::Mp3)),
+        20
+    );
+
+    let messages = [ChatCompletionRequestUserMessageArgs::default()
+        .content(vec![
+            ChatCompletionRequestMessageContentPartAudioArgs::default()
+                .input_audio(audio(&wav, InputAudioFormat::Wav))
+                .build()
+                .unwrap()
+                .into(),
+        ])
+        .build()
+        .unwrap()
+        .into()];
+    assert_eq!(
+        token_count("gpt-4o-audio-preview", &messages),
+        TokenCount::Exact(3 + 3 + 15)
+    );
+}
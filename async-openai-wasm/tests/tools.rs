@@ -0,0 +1,53 @@
+use async_openai_wasm::tools::ToolRegistry;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+#[derive(Deserialize)]
+struct Add {
+    a: i64,
+    b: i64,
+}
+
+#[tokio::test]
+async fn tool_registry_calls() {
+    let mut registry = ToolRegistry::new();
+    registry.register(
+        "add",
+        "Add two integers",
+        json!({
+            "type": "object",
+            "properties": {"a": {"type": "integer"}, "b": {"type": "integer"}},
+            "required": ["a", "b"],
+        }),
+        |Add { a, b }| async move {
+            a.checked_add(b)
+                .map(|sum| json!({ "sum": sum }))
+                .ok_or("overflow")
+        },
+    );
+
+    let output = |output: String| serde_json::from_str::<Value>(&output).unwrap();
+
+    assert_eq!(
+        output(registry.call_function("add", r#"{"a": 1, "b": 2}"#).await),
+        json!({"sum": 3})
+    );
+    assert_eq!(
+        output(
+            registry
+                .call_function("add", &json!({"a": i64::MAX, "b": 1}).to_string())
+                .await
+        ),
+        json!({"error": "overflow"})
+    );
+    assert!(
+        output(registry.call_function("add", r#"{"a": 1}"#).await)["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("invalid arguments")
+    );
+    assert_eq!(
+        output(registry.call_function("sub", "{}").await),
+        json!({"error": "unknown function: sub"})
+    );
+}
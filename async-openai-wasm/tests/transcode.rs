@@ -0,0 +1,229 @@
+use async_openai_wasm::{
+    error::OpenAIError,
+    transcode,
+    types::{
+        ChatCompletionStreamExt, CreateChatCompletionStreamResponse, FinishReason,
+        ResponseOutputItem, ResponseStatus, ResponseStreamEvent,
+    },
+};
+use futures::{stream, StreamExt};
+use serde_json::{json, Value};
+
+fn response(status: &str, output: Value, usage: Value) -> Value {
+    json!({
+        "id": "resp_1",
+        "object": "response",
+        "created_at": 1700000000,
+        "status": status,
+        "model": "gpt-4o-mini",
+        "output": output,
+        "error": null,
+        "incomplete_details": null,
+        "instructions": null,
+        "max_output_tokens": null,
+        "parallel_tool_calls": true,
+        "previous_response_id": null,
+        "reasoning": null,
+        "temperature": 1.0,
+        "top_p": 1.0,
+        "text": null,
+        "tool_choice": "auto",
+        "tools": [],
+        "truncation": "disabled",
+        "usage": usage,
+        "user": null,
+        "metadata": {},
+    })
+}
+
+fn events(events: Vec<Value>) -> Vec<Result<ResponseStreamEvent, OpenAIError>> {
+    events
+        .into_iter()
+        .map(|event| Ok(serde_json::from_value(event).unwrap()))
+        .collect()
+}
+
+fn chunk(
+    delta: Value,
+    finish_reason: Value,
+) -> Result<CreateChatCompletionStreamResponse, OpenAIError> {
+    Ok(serde_json::from_value(json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion.chunk",
+        "created": 1700000000,
+        "model": "gpt-4o-mini",
+        "choices": [{"index": 0, "delta": delta, "finish_reason": finish_reason}],
+    }))
+    .unwrap())
+}
+
+#[tokio::test]
+async fn response_events_to_chat_chunks() {
+    let call = json!({"type": "function_call", "id": "fc_1", "call_id": "call_1", "name": "get_weather", "arguments": ""});
+    let message = json!({"type": "message", "id": "msg_1", "role": "assistant", "status": "in_progress", "content": []});
+    let events = events(vec![
+        json!({"type": "response.created", "response": response("in_progress", json!([]), Value::Null)}),
+        json!({"type": "response.output_item.added", "output_index": 0, "item": message}),
+        json!({"type": "response.output_text.delta", "item_id": "msg_1", "output_index": 0, "content_index": 0, "delta": "Let me "}),
+        json!({"type": "response.output_text.delta", "item_id": "msg_1", "output_index": 0, "content_index": 0, "delta": "check."}),
+        json!({"type": "response.output_item.added", "output_index": 1, "item": call}),
+        json!({"type": "response.function_call_arguments.delta", "item_id": "fc_1", "output_index": 1, "delta": "{\"city\":"}),
+        json!({"type": "response.function_call_arguments.delta", "item_id": "fc_1", "output_index": 1, "delta": "\"Paris\"}"}),
+        json!({"type": "response.completed", "response": response(
+            "completed",
+            json!([]),
+            json!({"input_tokens": 10, "output_tokens": 5, "total_tokens": 15}),
+        )}),
+    ]);
+
+    let response = transcode::chat_stream(stream::iter(events))
+        .accumulate()
+        .await
+        .unwrap()
+        .into_response();
+
+    assert_eq!(response.id, "resp_1");
+    assert_eq!(response.first_text(), Some("Let me check."));
+    let choice = &response.choices[0];
+    assert_eq!(choice.finish_reason, Some(FinishReason::ToolCalls));
+    let tool_call = &choice.message.tool_calls.as_ref().unwrap()[0];
+    assert_eq!(tool_call.id, "call_1");
+    assert_eq!(tool_call.function.name, "get_weather");
+    assert_eq!(tool_call.function.arguments, r#"{"city":"Paris"}"#);
+    assert_eq!(response.usage.unwrap().total_tokens, 15);
+}
+
+#[tokio::test]
+async fn failed_responses_are_errors() {
+    let mut failed = response("failed", json!([]), Value::Null);
+    failed["error"] = json!({"code": "server_error", "message": "boom"});
+    let events = events(vec![
+        json!({"type": "response.created", "response": response("in_progress", json!([]), Value::Null)}),
+        json!({"type": "response.failed", "response": failed}),
+    ]);
+
+    let chunks: Vec<_> = transcode::chat_stream(stream::iter(events)).collect().await;
+    assert!(chunks[0].is_ok());
+    assert!(matches!(&chunks[1], Err(OpenAIError::ApiError(error)) if error.message == "boom"));
+}
+
+#[tokio::test]
+async fn chat_chunks_to_response_events() {
+    let mut usage = chunk(json!({}), Value::Null).unwrap();
+    usage.choices.clear();
+    usage.usage = serde_json::from_value(
+        json!({"prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15}),
+    )
+    .unwrap();
+    let chunks = vec![
+        chunk(json!({"role": "assistant", "content": ""}), Value::Null),
+        chunk(json!({"content": "Let me "}), Value::Null),
+        chunk(json!({"content": "check."}), Value::Null),
+        chunk(
+            json!({"tool_calls": [{"index": 0, "id": "call_1", "type": "function", "function": {"name": "get_weather", "arguments": ""}}]}),
+            Value::Null,
+        ),
+        chunk(
+            json!({"tool_calls": [{"index": 0, "function": {"arguments": "{\"city\":\"Paris\"}"}}]}),
+            Value::Null,
+        ),
+        chunk(json!({}), json!("tool_calls")),
+        Ok(usage),
+    ];
+
+    let events: Vec<_> = transcode::response_stream(stream::iter(chunks))
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    let types: Vec<_> = events
+        .iter()
+        .map(|event| match event {
+            ResponseStreamEvent::Created { .. } => "created",
+            ResponseStreamEvent::OutputItemAdded { .. } => "output_item.added",
+            ResponseStreamEvent::ContentPartAdded { .. } => "content_part.added",
+            ResponseStreamEvent::OutputTextDelta { .. } => "output_text.delta",
+            ResponseStreamEvent::FunctionCallArgumentsDelta { .. } => "arguments.delta",
+            ResponseStreamEvent::OutputTextDone { .. } => "output_text.done",
+            ResponseStreamEvent::ContentPartDone { .. } => "content_part.done",
+            ResponseStreamEvent::FunctionCallArgumentsDone { .. } => "arguments.done",
+            ResponseStreamEvent::OutputItemDone { .. } => "output_item.done",
+            ResponseStreamEvent::Completed { .. } => "completed",
+            _ => "other",
+        })
+        .collect();
+    assert_eq!(
+        types,
+        [
+            "created",
+            "output_item.added",
+            "content_part.added",
+            "output_text.delta",
+            "output_text.delta",
+            "output_item.added",
+            "arguments.delta",
+            "output_text.done",
+            "content_part.done",
+            "output_item.done",
+            "arguments.done",
+            "output_item.done",
+            "completed",
+        ]
+    );
+
+    let Some(ResponseStreamEvent::Completed { response }) = events.last() else {
+        unreachable!()
+    };
+    assert_eq!(response.status, ResponseStatus::Completed);
+    assert_eq!(response.output_text(), "Let me check.");
+    let call = response.function_calls().next().unwrap();
+    assert_eq!(
+        (call.call_id.as_str(), call.arguments.as_str()),
+        ("call_1", r#"{"city":"Paris"}"#)
+    );
+    assert_eq!(response.usage.as_ref().unwrap().total_tokens, 15);
+}
+
+#[tokio::test]
+async fn truncated_chat_completions_are_incomplete_responses() {
+    let chunks = vec![
+        chunk(
+            json!({"role": "assistant", "content": "Once upon"}),
+            Value::Null,
+        ),
+        chunk(json!({}), json!("length")),
+    ];
+
+    let events: Vec<_> = transcode::response_stream(stream::iter(chunks))
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    let Some(ResponseStreamEvent::Incomplete { response }) = events.last() else {
+        panic!("expected an incomplete response, got {:?}", events.last())
+    };
+    assert_eq!(
+        response.incomplete_details.as_ref().unwrap().reason,
+        "max_output_tokens"
+    );
+    assert!(
+        matches!(&response.output[0], ResponseOutputItem::Message(message) if message.content.len() == 1)
+    );
+}
+
+#[tokio::test]
+async fn round_trip() {
+    let chunks = vec![
+        chunk(json!({"role": "assistant", "content": "Hi"}), Value::Null),
+        chunk(json!({"content": " there"}), json!("stop")),
+    ];
+
+    let response = transcode::chat_stream(transcode::response_stream(stream::iter(chunks)))
+        .accumulate()
+        .await
+        .unwrap()
+        .into_response();
+
+    assert_eq!(response.first_text(), Some("Hi there"));
+    assert_eq!(response.choices[0].finish_reason, Some(FinishReason::Stop));
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "testing")]
+
+use std::sync::{Arc, Mutex};
+
+use async_openai_wasm::{
+    progress::UploadProgress,
+    testing::{MockResponse, MockServer},
+    types::{CreateFileRequestArgs, FileInput, FilePurpose},
+};
+use serde_json::json;
+
+#[tokio::test]
+async fn upload_progress_is_reported_in_chunks() {
+    let server = MockServer::start();
+    server.mock(
+        "POST",
+        "/files",
+        MockResponse::json(json!({
+            "id": "file-1",
+            "object": "file",
+            "bytes": 300000,
+            "created_at": 0,
+            "filename": "train.jsonl",
+            "purpose": "fine-tune",
+        })),
+    );
+
+    let progress = Arc::new(Mutex::new(Vec::<UploadProgress>::new()));
+    let reported = progress.clone();
+    let client = server
+        .client()
+        .with_upload_progress(move |progress| reported.lock().unwrap().push(progress));
+
+    let request = CreateFileRequestArgs::default()
+        .file(FileInput::from_vec_u8(
+            "train.jsonl".into(),
+            vec![b'a'; 300_000],
+        ))
+        .purpose(FilePurpose::FineTune)
+        .build()
+        .unwrap();
+    let file = client.files().create(request).await.unwrap();
+    assert_eq!(file.id, "file-1");
+
+    let received = server.requests()[0].body.len() as u64;
+    let progress = progress.lock().unwrap();
+    assert!(progress.len() > 4);
+    assert!(progress.windows(2).all(|p| p[0].sent < p[1].sent));
+    assert_eq!(
+        progress.last(),
+        Some(&UploadProgress {
+            sent: received,
+            total: Some(received)
+        })
+    );
+}
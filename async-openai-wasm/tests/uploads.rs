@@ -0,0 +1,121 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+
+use async_openai_wasm::{
+    config::OpenAIConfig,
+    types::{InputSource, UploadPurpose, UploadStatus},
+    Client,
+};
+use serde_json::{json, Value};
+
+/// Path and body of a request received by the server
+type Received = Arc<Mutex<Vec<(String, Vec<u8>)>>>;
+
+fn upload(status: &str) -> Value {
+    json!({
+        "id": "upload_1",
+        "object": "upload",
+        "created_at": 0,
+        "expires_at": 3600,
+        "filename": "train.jsonl",
+        "bytes": 10,
+        "purpose": "fine-tune",
+        "status": status,
+    })
+}
+
+fn handle(stream: TcpStream, received: Received) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    loop {
+        let mut request_line = String::new();
+        if reader.read_line(&mut request_line).unwrap_or(0) == 0 {
+            return;
+        }
+        let path = request_line.split(' ').nth(1).unwrap().to_string();
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+
+        let response = if path.ends_with("/parts") {
+            let mut received = received.lock().unwrap();
+            let part = received
+                .iter()
+                .filter(|(p, _)| p.ends_with("/parts"))
+                .count();
+            received.push((path, body));
+            json!({"id": format!("part_{part}"), "object": "upload.part", "created_at": 0, "upload_id": "upload_1"})
+        } else {
+            let status = if path.ends_with("/complete") {
+                "completed"
+            } else {
+                "pending"
+            };
+            received.lock().unwrap().push((path, body));
+            upload(status)
+        };
+        let response = response.to_string();
+        write!(
+            &stream,
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{response}",
+            response.len()
+        )
+        .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn large_file_is_uploaded_in_parts() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let api_base = format!("http://{}/v1", listener.local_addr().unwrap());
+    let received = Received::default();
+    let server_received = received.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let received = server_received.clone();
+            std::thread::spawn(move || handle(stream.unwrap(), received));
+        }
+    });
+
+    let client = Client::with_config(OpenAIConfig::new().with_api_base(api_base));
+    let source = InputSource::Bytes {
+        filename: "train.jsonl".into(),
+        bytes: "0123456789".into(),
+    };
+    let upload = client
+        .uploads()
+        .upload_file_chunked(source, UploadPurpose::FineTune, "text/jsonl", 4)
+        .await
+        .unwrap();
+    assert_eq!(upload.status, UploadStatus::Completed);
+
+    let received = received.lock().unwrap();
+    let paths: Vec<_> = received.iter().map(|(path, _)| path.as_str()).collect();
+    assert_eq!(paths[0], "/v1/uploads");
+    assert_eq!(paths[1..4], ["/v1/uploads/upload_1/parts"; 3]);
+    assert_eq!(paths[4], "/v1/uploads/upload_1/complete");
+
+    let created: Value = serde_json::from_slice(&received[0].1).unwrap();
+    assert_eq!(created["bytes"], 10);
+    assert_eq!(created["purpose"], "fine-tune");
+    let completed: Value = serde_json::from_slice(&received[4].1).unwrap();
+    let mut part_ids: Vec<_> = completed["part_ids"].as_array().unwrap().clone();
+    part_ids.sort_by_key(|id| id.to_string());
+    assert_eq!(
+        part_ids,
+        [json!("part_0"), json!("part_1"), json!("part_2")]
+    );
+    assert_eq!(completed["md5"], "781e5e245d69b566979b86e28d23f2c7");
+}
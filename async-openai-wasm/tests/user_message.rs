@@ -0,0 +1,45 @@
+use async_openai_wasm::types::{
+    ChatCompletionRequestMessage, ChatCompletionRequestUserMessage,
+    ChatCompletionRequestUserMessageContent, ImageDetail, ImageUrl, UserMessageBuilder,
+};
+use serde_json::json;
+
+#[test]
+fn text_and_images_make_content_parts() {
+    let message: ChatCompletionRequestMessage = UserMessageBuilder::new()
+        .text("Compare these")
+        .image_url("https://example.com/a.png")
+        .image_bytes([0xFF, 0xD8, 0xFF, 0xE0])
+        .image(ImageUrl {
+            url: "https://example.com/b.png".into(),
+            detail: Some(ImageDetail::Low),
+        })
+        .name("alice")
+        .into();
+
+    assert_eq!(
+        serde_json::to_value(message).unwrap(),
+        json!({
+            "role": "user",
+            "name": "alice",
+            "content": [
+                {"type": "text", "text": "Compare these"},
+                {"type": "image_url", "image_url": {"url": "https://example.com/a.png", "detail": null}},
+                {"type": "image_url", "image_url": {"url": "data:image/jpeg;base64,/9j/4A==", "detail": null}},
+                {"type": "image_url", "image_url": {"url": "https://example.com/b.png", "detail": "low"}},
+            ],
+        })
+    );
+}
+
+#[test]
+fn messages_without_images_are_plain_text() {
+    let message: ChatCompletionRequestUserMessage =
+        UserMessageBuilder::new().text("Hello").text("World").into();
+
+    assert_eq!(
+        message.content,
+        ChatCompletionRequestUserMessageContent::Text("Hello\nWorld".into())
+    );
+    assert_eq!(message.name, None);
+}
@@ -0,0 +1,48 @@
+//! This test is primarily to make sure that filters serialize to the JSON shape of the API.
+use async_openai_wasm::types::Filter;
+use serde_json::json;
+
+#[test]
+fn filter_serialization() {
+    let filter = Filter::and([
+        Filter::eq("author", "Jane Doe"),
+        Filter::or([
+            Filter::gte("year", 2020),
+            Filter::is_in("genre", ["fiction", "poetry"]),
+        ]),
+        Filter::ne("draft", true),
+    ]);
+
+    assert!(filter.validate().is_ok());
+    assert_eq!(
+        serde_json::to_value(&filter).unwrap(),
+        json!({
+            "type": "and",
+            "filters": [
+                {"type": "eq", "key": "author", "value": "Jane Doe"},
+                {
+                    "type": "or",
+                    "filters": [
+                        {"type": "gte", "key": "year", "value": 2020.0},
+                        {"type": "in", "key": "genre", "value": ["fiction", "poetry"]},
+                    ]
+                },
+                {"type": "ne", "key": "draft", "value": true},
+            ]
+        })
+    );
+
+    // round trip
+    let deserialized: Filter =
+        serde_json::from_value(serde_json::to_value(&filter).unwrap()).unwrap();
+    assert_eq!(filter, deserialized);
+}
+
+#[test]
+fn malformed_filters_are_rejected() {
+    assert!(Filter::or([]).validate().is_err());
+    assert!(Filter::eq("", "value").validate().is_err());
+    assert!(Filter::and([Filter::eq("a", 1), Filter::and([])])
+        .validate()
+        .is_err());
+}
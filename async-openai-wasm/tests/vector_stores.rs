@@ -0,0 +1,66 @@
+#![cfg(feature = "testing")]
+
+use async_openai_wasm::testing::{MockResponse, MockServer};
+use serde_json::{json, Value};
+
+fn vector_store(expires_after: Value) -> Value {
+    json!({
+        "id": "vs_1", "object": "vector_store", "created_at": 0, "name": "Helper",
+        "usage_bytes": 0, "status": "completed", "expires_after": expires_after,
+        "expires_at": null, "last_active_at": 0, "metadata": {},
+        "file_counts": {"in_progress": 0, "completed": 0, "failed": 0, "cancelled": 0, "total": 0},
+    })
+}
+
+#[tokio::test]
+async fn expiration_is_renewed_with_the_policy_of_the_vector_store() {
+    let expires_after = json!({"anchor": "last_active_at", "days": 7});
+    let server = MockServer::start();
+    server
+        .mock(
+            "GET",
+            "/vector_stores/vs_1",
+            MockResponse::json(vector_store(expires_after.clone())),
+        )
+        .mock(
+            "POST",
+            "/vector_stores/vs_1",
+            MockResponse::json(vector_store(expires_after.clone())),
+        );
+
+    server
+        .client()
+        .vector_stores()
+        .renew_expiration("vs_1")
+        .await
+        .unwrap();
+
+    let updates = server
+        .requests()
+        .into_iter()
+        .filter(|request| request.method == "POST")
+        .collect::<Vec<_>>();
+    assert_eq!(updates.len(), 1);
+    assert_eq!(updates[0].path, "/vector_stores/vs_1");
+    assert_eq!(updates[0].json()["expires_after"], expires_after);
+}
+
+#[tokio::test]
+async fn vector_stores_without_expiration_are_not_updated() {
+    let server = MockServer::start();
+    server.mock(
+        "GET",
+        "/vector_stores/vs_1",
+        MockResponse::json(vector_store(Value::Null)),
+    );
+
+    let vector_store = server
+        .client()
+        .vector_stores()
+        .renew_expiration("vs_1")
+        .await
+        .unwrap();
+
+    assert_eq!(vector_store.expires_after, None);
+    assert_eq!(server.requests().len(), 1);
+}
@@ -0,0 +1,87 @@
+#![cfg(feature = "testing")]
+
+use async_openai_wasm::{
+    error::OpenAIError,
+    retry::RetryPolicy,
+    testing::{MockResponse, MockServer},
+    Client,
+};
+use serde_json::json;
+
+#[tokio::test]
+async fn accepted_credentials_are_reported_with_their_organization_and_project() {
+    let server = MockServer::start();
+    server.mock(
+        "GET",
+        "/models",
+        MockResponse::json(json!({"object": "list", "data": []}))
+            .with_header("openai-organization", "org-1")
+            .with_header("openai-project", "proj_1")
+            .with_header("x-request-id", "req_1"),
+    );
+
+    let report = server.client().verify().await.unwrap();
+
+    assert!(report.is_authenticated());
+    assert!(report.is_ok());
+    assert_eq!(report.organization.as_deref(), Some("org-1"));
+    assert_eq!(report.project.as_deref(), Some("proj_1"));
+    assert_eq!(report.request_id.as_deref(), Some("req_1"));
+    assert!(report
+        .to_string()
+        .starts_with("authenticated, organization org-1, project proj_1, in "));
+    assert_eq!(server.requests_to("/models").len(), 1);
+}
+
+#[tokio::test]
+async fn refused_credentials_are_reported_once() {
+    let server = MockServer::start();
+    let refused = MockResponse {
+        status: 401,
+        ..MockResponse::json(json!({"error": {
+            "message": "Incorrect API key provided", "type": "invalid_request_error",
+            "param": null, "code": "invalid_api_key",
+        }}))
+    };
+    server.mock("GET", "/models", refused.clone());
+    server.mock("GET", "/models", refused);
+    let client = server.client().with_retry_policy(RetryPolicy::default());
+
+    let report = client.verify().await.unwrap();
+
+    assert!(!report.is_authenticated());
+    assert_eq!(report.status, 401);
+    assert_eq!(
+        report.error.as_ref().unwrap().code.as_deref(),
+        Some("invalid_api_key")
+    );
+    assert!(report.to_string().starts_with(
+        "not authenticated, refused with 401: Incorrect API key provided (invalid_api_key)"
+    ));
+    assert!(matches!(
+        report.into_result(),
+        Err(OpenAIError::ApiError(_))
+    ));
+    assert_eq!(server.requests_to("/models").len(), 1);
+}
+
+#[tokio::test]
+async fn admin_keys_are_verified_on_the_administration_api() {
+    let server = MockServer::start();
+    server.mock(
+        "GET",
+        "/organization/projects",
+        MockResponse::json(json!({
+            "object": "list", "data": [], "first_id": null, "last_id": null, "has_more": false,
+        })),
+    );
+    let client = Client::with_config(server.config().with_api_key("sk-admin-test"));
+
+    let report = client.verify().await.unwrap().into_result().unwrap();
+
+    assert_eq!(report.status, 200);
+    assert_eq!(
+        server.requests_to("/organization/projects")[0].query,
+        "limit=1"
+    );
+}
@@ -0,0 +1,231 @@
+#![cfg(feature = "vision")]
+
+use std::cell::RefCell;
+
+use async_openai_wasm::{
+    error::OpenAIError,
+    types::{ImageDetail, ImageInput},
+    vision::{prepare_image, ImageFormat, ImageInfo, ImageLimits, ImageTranscoder},
+};
+
+/// Transcoder recording its calls, answering with `output` bytes
+#[derive(Default)]
+struct Recorder {
+    calls: RefCell<Vec<(u8, u32, u32, ImageFormat)>>,
+    output: Vec<u8>,
+}
+
+impl ImageTranscoder for Recorder {
+    fn transcode(
+        &self,
+        _image: &[u8],
+        orientation: u8,
+        width: u32,
+        height: u32,
+        format: ImageFormat,
+    ) -> Result<Vec<u8>, OpenAIError> {
+        self.calls
+            .borrow_mut()
+            .push((orientation, width, height, format));
+        Ok(self.output.clone())
+    }
+}
+
+fn png(width: u32, height: u32) -> Vec<u8> {
+    let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+    png.extend(width.to_be_bytes());
+    png.extend(height.to_be_bytes());
+    png
+}
+
+/// JPEG of `width` by `height` pixels with a big endian EXIF `orientation`
+fn jpeg(width: u16, height: u16, orientation: u16) -> Vec<u8> {
+    let mut exif = b"Exif\0\0MM\0\x2a\0\0\0\x08\0\x01\x01\x12\0\x03\0\0\0\x01".to_vec();
+    exif.extend(orientation.to_be_bytes());
+    exif.extend([0, 0, 0, 0, 0, 0]);
+    let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE1];
+    jpeg.extend((exif.len() as u16 + 2).to_be_bytes());
+    jpeg.extend(exif);
+    jpeg.extend([0xFF, 0xC0, 0x00, 0x11, 0x08]);
+    jpeg.extend(height.to_be_bytes());
+    jpeg.extend(width.to_be_bytes());
+    jpeg
+}
+
+#[test]
+fn reads_image_headers() {
+    let info = ImageInfo::read(&jpeg(4032, 3024, 6)).unwrap();
+    assert_eq!(
+        info,
+        ImageInfo {
+            format: ImageFormat::Jpeg,
+            width: 4032,
+            height: 3024,
+            orientation: 6,
+        }
+    );
+    assert_eq!(info.oriented_dimensions(), (3024, 4032));
+
+    let gif = ImageInfo::read(b"GIF89a\x20\x03\x58\x02").unwrap();
+    assert_eq!(
+        (gif.format, gif.width, gif.height),
+        (ImageFormat::Gif, 800, 600)
+    );
+
+    let mut webp = b"RIFF\0\0\0\0WEBPVP8X\x0a\0\0\0\0\0\0\0".to_vec();
+    webp.extend([0x7F, 0x02, 0x00, 0xDF, 0x01, 0x00]);
+    let webp = ImageInfo::read(&webp).unwrap();
+    assert_eq!((webp.width, webp.height), (640, 480));
+
+    assert_eq!(ImageInfo::read(b"not an image"), None);
+}
+
+#[test]
+fn images_within_limits_are_kept() {
+    let transcoder = Recorder::default();
+    let image = prepare_image(png(1024, 768), &ImageLimits::openai(), &transcoder).unwrap();
+
+    assert!(transcoder.calls.borrow().is_empty());
+    assert_eq!(image.bytes, png(1024, 768));
+    assert_eq!((image.width, image.height), (1024, 768));
+    let image_url = image.image_url(ImageDetail::Low);
+    assert!(image_url
+        .url
+        .starts_with("data:image/png;base64,iVBORw0KGgo"));
+    assert_eq!(
+        image.image_input("chart"),
+        ImageInput::from_vec_u8("chart.png".into(), png(1024, 768))
+    );
+}
+
+#[test]
+fn rotated_and_oversized_images_are_transcoded() {
+    let transcoder = Recorder {
+        output: b"resized".to_vec(),
+        ..Default::default()
+    };
+    let image = prepare_image(jpeg(4032, 3024, 6), &ImageLimits::anthropic(), &transcoder).unwrap();
+
+    // upright, 3024 by 4032 pixels scaled down to 1568 pixels high
+    assert_eq!(
+        *transcoder.calls.borrow(),
+        [(6, 1176, 1568, ImageFormat::Jpeg)]
+    );
+    assert_eq!(image.bytes, b"resized");
+    assert_eq!((image.width, image.height), (1176, 1568));
+
+    let gif = b"GIF89a\x20\x03\x58\x02".to_vec();
+    let limits = ImageLimits::openai().with_formats(vec![ImageFormat::Png]);
+    let image = prepare_image(gif, &limits, &transcoder).unwrap();
+    assert_eq!(image.format, ImageFormat::Png);
+    assert_eq!(
+        transcoder.calls.borrow()[1],
+        (1, 800, 600, ImageFormat::Png)
+    );
+}
+
+#[test]
+fn images_too_large_once_transcoded_fail() {
+    let transcoder = Recorder {
+        output: vec![0; 64],
+        ..Default::default()
+    };
+    let limits = ImageLimits::openai().with_max_bytes(32);
+    let error = prepare_image(png(4096, 4096), &limits, &transcoder).unwrap_err();
+    assert!(error.to_string().contains("64 bytes"));
+    assert_eq!(
+        *transcoder.calls.borrow(),
+        [(1, 2048, 2048, ImageFormat::Png)]
+    );
+
+    assert!(prepare_image(b"text".to_vec(), &limits, &transcoder).is_err());
+}
+
+#[test]
+fn exif_offsets_pointing_outside_the_segment_are_ignored() {
+    // the image file directory is at the largest offset, then at an offset whose entries run past it
+    for directory in [[0xFF, 0xFF, 0xFF, 0xFF], [0x00, 0x00, 0x00, 0x08]] {
+        let mut exif = b"Exif\0\0MM\0\x2a".to_vec();
+        exif.extend(directory);
+        exif.extend([0xFF, 0xFF, 0x00, 0x00]);
+        let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE1];
+        jpeg.extend((exif.len() as u16 + 2).to_be_bytes());
+        jpeg.extend(exif);
+        jpeg.extend([0xFF, 0xC0, 0x00, 0x11, 0x08, 0x00, 0x10, 0x00, 0x20]);
+
+        let info = ImageInfo::read(&jpeg).unwrap();
+        assert_eq!((info.width, info.height, info.orientation), (32, 16, 1));
+    }
+}
+
+/// Transcoder encoding no WebP images
+struct NoWebp(Recorder);
+
+impl ImageTranscoder for NoWebp {
+    fn transcode(
+        &self,
+        image: &[u8],
+        orientation: u8,
+        width: u32,
+        height: u32,
+        format: ImageFormat,
+    ) -> Result<Vec<u8>, OpenAIError> {
+        self.0.transcode(image, orientation, width, height, format)
+    }
+
+    fn encodes(&self, format: ImageFormat) -> bool {
+        format != ImageFormat::Webp
+    }
+}
+
+#[test]
+fn formats_the_transcoder_does_not_encode_are_replaced() {
+    let mut webp = b"RIFF\0\0\0\0WEBPVP8X\x0a\0\0\0\0\0\0\0".to_vec();
+    webp.extend([0xFF, 0x0F, 0x00, 0xFF, 0x0F, 0x00]);
+    let transcoder = NoWebp(Recorder::default());
+    let limits = ImageLimits::openai().with_formats(vec![ImageFormat::Webp, ImageFormat::Jpeg]);
+    let image = prepare_image(webp.clone(), &limits, &transcoder).unwrap();
+
+    assert_eq!(image.format, ImageFormat::Jpeg);
+    assert_eq!(
+        *transcoder.0.calls.borrow(),
+        [(1, 2048, 2048, ImageFormat::Jpeg)]
+    );
+
+    let limits = limits.with_formats(vec![ImageFormat::Webp]);
+    assert!(prepare_image(webp, &limits, &transcoder).is_err());
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn image_codec_rotates_resizes_and_encodes() {
+    use async_openai_wasm::vision::ImageCodec;
+    use image::{ImageOutputFormat, Rgb, RgbImage};
+
+    // 40 by 20 pixels, red in the top left corner
+    let mut pixels = RgbImage::new(40, 20);
+    pixels.put_pixel(0, 0, Rgb([255, 0, 0]));
+    let mut png = std::io::Cursor::new(Vec::new());
+    image::DynamicImage::ImageRgb8(pixels)
+        .write_to(&mut png, ImageOutputFormat::Png)
+        .unwrap();
+    let png = png.into_inner();
+
+    // rotated a quarter turn clockwise, the red pixel moves to the top right corner
+    let rotated = ImageCodec::default()
+        .transcode(&png, 6, 20, 40, ImageFormat::Png)
+        .unwrap();
+    let decoded = image::load_from_memory(&rotated).unwrap().to_rgb8();
+    assert_eq!(decoded.dimensions(), (20, 40));
+    assert_eq!(decoded.get_pixel(19, 0), &Rgb([255, 0, 0]));
+
+    let limits = ImageLimits::openai()
+        .with_max_dimensions(20, 20)
+        .with_formats(vec![ImageFormat::Jpeg]);
+    let image = prepare_image(png, &limits, &ImageCodec::default()).unwrap();
+    let info = ImageInfo::read(&image.bytes).unwrap();
+    assert_eq!(
+        (info.format, info.width, info.height),
+        (ImageFormat::Jpeg, 20, 10)
+    );
+}
@@ -0,0 +1,158 @@
+//! Just enough HTTP/1.1 for the gateway: one request per connection, answered with a whole body
+//! or a stream written as it comes, the connection being closed after each response.
+use futures::{stream::BoxStream, StreamExt};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub body: Vec<u8>,
+}
+
+pub enum Body {
+    Full(Vec<u8>),
+    /// Server-sent events, written as they are streamed
+    Stream(BoxStream<'static, Vec<u8>>),
+}
+
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: Body,
+}
+
+impl Response {
+    /// `status` with `body` of `content_type`
+    pub fn full(status: u16, content_type: &str, body: Vec<u8>) -> Self {
+        Self {
+            status,
+            headers: vec![("content-type", content_type.into())],
+            body: Body::Full(body),
+        }
+    }
+
+    /// `status` with `body` as JSON
+    pub fn json<T: serde::Serialize>(status: u16, body: &T) -> Self {
+        Self::full(
+            status,
+            "application/json",
+            serde_json::to_vec(body).expect("responses serialize to JSON"),
+        )
+    }
+
+    /// `200 OK` with `events` as a stream of server-sent events
+    pub fn event_stream(events: BoxStream<'static, Vec<u8>>) -> Self {
+        Self {
+            status: 200,
+            headers: vec![("content-type", "text/event-stream".into())],
+            body: Body::Stream(events),
+        }
+    }
+
+    pub fn with_header(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.headers.push((name, value.into()));
+        self
+    }
+}
+
+/// Why the request of a connection could not be read
+#[derive(Debug)]
+pub enum ReadError {
+    /// The request is malformed, or the connection closed early
+    Malformed,
+    /// The body of the request is of this many bytes, over the maximum
+    TooLarge(usize),
+}
+
+/// The request of a connection, whose body must be at most `max_body_bytes` long
+pub async fn read_request(
+    stream: &mut TcpStream,
+    max_body_bytes: usize,
+) -> Result<Request, ReadError> {
+    let mut reader = BufReader::new(stream);
+    let (method, path, content_length) =
+        read_head(&mut reader).await.ok_or(ReadError::Malformed)?;
+    // checked before allocating the body, whose length the client picks
+    if content_length > max_body_bytes {
+        return Err(ReadError::TooLarge(content_length));
+    }
+    let mut body = vec![0; content_length];
+    reader
+        .read_exact(&mut body)
+        .await
+        .map_err(|_| ReadError::Malformed)?;
+    Ok(Request { method, path, body })
+}
+
+/// Method, path and content length of a request
+async fn read_head(reader: &mut BufReader<&mut TcpStream>) -> Option<(String, String, usize)> {
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await.ok()?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next()?.to_string();
+    let path = parts.next()?.split('?').next()?.to_string();
+
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await.ok()? == 0 {
+            return None;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().ok()?;
+            }
+        }
+    }
+    Some((method, path, content_length))
+}
+
+pub async fn write_response(stream: &mut TcpStream, response: Response) -> std::io::Result<()> {
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\n",
+        response.status,
+        reason(response.status)
+    );
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{name}: {value}\r\n"));
+    }
+    head.push_str("connection: close\r\n");
+    match response.body {
+        Body::Full(body) => {
+            head.push_str(&format!("content-length: {}\r\n\r\n", body.len()));
+            stream.write_all(head.as_bytes()).await?;
+            stream.write_all(&body).await?;
+        }
+        Body::Stream(mut events) => {
+            // the end of the body is the end of the connection
+            head.push_str("\r\n");
+            stream.write_all(head.as_bytes()).await?;
+            while let Some(event) = events.next().await {
+                stream.write_all(&event).await?;
+                stream.flush().await?;
+            }
+        }
+    }
+    stream.shutdown().await
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        413 => "Payload Too Large",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        _ => "",
+    }
+}
@@ -0,0 +1,324 @@
+//! OpenAI compatible gateway: chat completion requests are parsed into the types of `async-openai-wasm`,
+//! checked against a token budget, answered from a cache when deterministic, and sent to the first
+//! upstream provider which does not fail, streamed or not.
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use async_openai_wasm::{
+    config::OpenAIConfig,
+    error::OpenAIError,
+    types::{
+        ChatCompletionStreamOptions, CreateChatCompletionRequest, CreateChatCompletionResponse,
+        CreateChatCompletionStreamResponse,
+    },
+    Client,
+};
+use futures::{future, stream, stream::BoxStream, StreamExt};
+use serde_json::{json, Value};
+use tokio::net::TcpListener;
+use tracing::Instrument;
+
+use crate::http::{ReadError, Request, Response};
+
+pub mod http;
+
+/// Header telling whether a response was served from the cache
+pub const CACHE_HEADER: &str = "x-gateway-cache";
+
+/// Default maximum size of request bodies, leaving room for a few images as base64 data URLs
+pub const DEFAULT_MAX_BODY_BYTES: usize = 32 * 1024 * 1024;
+
+/// Tokens left to spend on upstream requests, counted from the usage of their responses
+#[derive(Debug)]
+pub struct Budget {
+    remaining: Mutex<Option<u64>>,
+}
+
+impl Budget {
+    /// Budget of `tokens`, or an unlimited one
+    pub fn new(tokens: Option<u64>) -> Self {
+        Self {
+            remaining: Mutex::new(tokens),
+        }
+    }
+
+    /// Tokens left, `None` if unlimited
+    pub fn remaining(&self) -> Option<u64> {
+        *self.remaining.lock().unwrap()
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining() == Some(0)
+    }
+
+    pub fn charge(&self, tokens: u32) {
+        if let Some(remaining) = self.remaining.lock().unwrap().as_mut() {
+            *remaining = remaining.saturating_sub(tokens.into());
+        }
+    }
+}
+
+pub struct Gateway {
+    /// Providers tried in order until one answers
+    upstreams: Vec<Client<OpenAIConfig>>,
+    budget: Arc<Budget>,
+    /// Responses of requests with a temperature of 0, by their JSON serialization
+    cache: Mutex<HashMap<String, CreateChatCompletionResponse>>,
+    /// Requests with larger bodies are answered with `413 Payload Too Large`
+    max_body_bytes: usize,
+}
+
+impl Gateway {
+    pub fn new(upstreams: Vec<Client<OpenAIConfig>>, budget: Budget) -> Self {
+        Self {
+            upstreams,
+            budget: Arc::new(budget),
+            cache: Mutex::default(),
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+        }
+    }
+
+    pub fn with_max_body_bytes(mut self, max_body_bytes: usize) -> Self {
+        self.max_body_bytes = max_body_bytes;
+        self
+    }
+
+    pub fn budget(&self) -> &Budget {
+        &self.budget
+    }
+
+    /// Answer the connections of `listener` until it fails
+    pub async fn serve(self: Arc<Self>, listener: TcpListener) -> std::io::Result<()> {
+        loop {
+            let (mut stream, peer) = listener.accept().await?;
+            let gateway = self.clone();
+            tokio::spawn(async move {
+                let response = match http::read_request(&mut stream, gateway.max_body_bytes).await {
+                    Ok(request) => gateway.handle(request).await,
+                    Err(ReadError::TooLarge(length)) => {
+                        tracing::warn!(%peer, length, "request body too large");
+                        error(
+                            413,
+                            "invalid_request_error",
+                            &format!(
+                                "request body of {length} bytes exceeds the maximum of {} bytes",
+                                gateway.max_body_bytes
+                            ),
+                        )
+                    }
+                    Err(ReadError::Malformed) => {
+                        tracing::warn!(%peer, "malformed request");
+                        return;
+                    }
+                };
+                if let Err(e) = http::write_response(&mut stream, response).await {
+                    tracing::warn!(%peer, "connection failed: {e}");
+                }
+            });
+        }
+    }
+
+    pub async fn handle(&self, request: Request) -> Response {
+        let span = tracing::info_span!("gateway", method = request.method, path = request.path);
+        async move {
+            let response = match (request.method.as_str(), request.path.as_str()) {
+                ("POST", "/v1/chat/completions") => self.chat(&request.body).await,
+                ("GET", "/v1/models") => self.models().await,
+                _ => error(404, "invalid_request_error", "unknown endpoint"),
+            };
+            tracing::info!(status = response.status, "answered");
+            response
+        }
+        .instrument(span)
+        .await
+    }
+
+    async fn chat(&self, body: &[u8]) -> Response {
+        // requests are sent upstream re-serialized from their types, so that malformed ones fail here
+        let request: CreateChatCompletionRequest = match serde_json::from_slice(body) {
+            Ok(request) => request,
+            Err(e) => return error(400, "invalid_request_error", &e.to_string()),
+        };
+        if self.budget.is_exhausted() {
+            return error(
+                429,
+                "insufficient_quota",
+                "the token budget of the gateway is exhausted",
+            );
+        }
+        if request.stream == Some(true) {
+            self.chat_stream(request).await
+        } else {
+            self.chat_once(request).await
+        }
+    }
+
+    async fn chat_once(&self, request: CreateChatCompletionRequest) -> Response {
+        let key = (request.temperature == Some(0.0))
+            .then(|| serde_json::to_string(&request).ok())
+            .flatten();
+        let cached = key
+            .as_ref()
+            .and_then(|key| self.cache.lock().unwrap().get(key).cloned());
+        if let Some(response) = cached {
+            return Response::json(200, &response).with_header(CACHE_HEADER, "hit");
+        }
+
+        let mut last_error = None;
+        for (index, upstream) in self.upstreams.iter().enumerate() {
+            match upstream.chat().create(request.clone()).await {
+                Ok(response) => {
+                    if let Some(usage) = &response.usage {
+                        self.budget.charge(usage.total_tokens);
+                    }
+                    if let Some(key) = key {
+                        self.cache.lock().unwrap().insert(key, response.clone());
+                    }
+                    return Response::json(200, &response).with_header(CACHE_HEADER, "miss");
+                }
+                Err(e) if rejects_request(&e) => return upstream_error(&e),
+                Err(e) => {
+                    tracing::warn!(upstream = index, "upstream failed, falling back: {e}");
+                    last_error = Some(e);
+                }
+            }
+        }
+        no_upstream_answered(last_error)
+    }
+
+    async fn chat_stream(&self, mut request: CreateChatCompletionRequest) -> Response {
+        // usage is always requested to charge the budget, and only forwarded if the client asked for it
+        let forward_usage = request
+            .stream_options
+            .is_some_and(|options| options.include_usage);
+        request.stream_options = Some(ChatCompletionStreamOptions {
+            include_usage: true,
+        });
+
+        let mut last_error = None;
+        for (index, upstream) in self.upstreams.iter().enumerate() {
+            // streams fail on their first event, until which falling back is still possible
+            let first = match upstream.chat().create_stream(request.clone()).await {
+                Ok(mut chunks) => match chunks.next().await {
+                    Some(Ok(first)) => {
+                        Ok(stream::once(future::ready(Ok(first))).chain(chunks).boxed())
+                    }
+                    Some(Err(e)) => Err(e),
+                    None => Ok(chunks.boxed()),
+                },
+                Err(e) => Err(e),
+            };
+            match first {
+                Ok(chunks) => return self.event_stream(chunks, forward_usage),
+                Err(e) if rejects_request(&e) => return upstream_error(&e),
+                Err(e) => {
+                    tracing::warn!(
+                        upstream = index,
+                        "upstream stream failed, falling back: {e}"
+                    );
+                    last_error = Some(e);
+                }
+            }
+        }
+        no_upstream_answered(last_error)
+    }
+
+    /// `chunks` as server-sent events, charging the budget with their usage
+    fn event_stream(
+        &self,
+        chunks: BoxStream<'static, Result<CreateChatCompletionStreamResponse, OpenAIError>>,
+        forward_usage: bool,
+    ) -> Response {
+        let budget = self.budget.clone();
+        let events = chunks
+            .filter_map(move |chunk| {
+                let event = match chunk {
+                    Ok(chunk) => {
+                        if let Some(usage) = &chunk.usage {
+                            budget.charge(usage.total_tokens);
+                        }
+                        let usage_only = chunk.choices.is_empty() && chunk.usage.is_some();
+                        (forward_usage || !usage_only).then(|| event(&json!(chunk)))
+                    }
+                    Err(e) => Some(event(&error_body("server_error", &e.to_string()))),
+                };
+                future::ready(event)
+            })
+            .chain(stream::once(future::ready(b"data: [DONE]\n\n".to_vec())));
+        Response::event_stream(events.boxed())
+    }
+
+    async fn models(&self) -> Response {
+        let mut last_error = None;
+        for upstream in &self.upstreams {
+            match upstream.models().list().await {
+                Ok(models) => return Response::json(200, &models),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        no_upstream_answered(last_error)
+    }
+}
+
+/// Whether `error` rejects the request itself, which other upstreams would reject too
+fn rejects_request(error: &OpenAIError) -> bool {
+    match error {
+        OpenAIError::ApiError(error) => error.r#type.as_deref() == Some("invalid_request_error"),
+        OpenAIError::InvalidArgument(_) | OpenAIError::InvalidField(_) => true,
+        _ => false,
+    }
+}
+
+/// `error` of an upstream, forwarded with the status and body of its response when it has one, and
+/// as `502 Bad Gateway` when the upstream could not be reached or answered something else than an error
+fn upstream_error(error: &OpenAIError) -> Response {
+    match error {
+        OpenAIError::ApiError(api_error) => {
+            let status = api_error.status.map_or(502, |status| status.as_u16());
+            match api_error.body() {
+                Some(body) => {
+                    let content_type = if serde_json::from_str::<Value>(body).is_ok() {
+                        "application/json"
+                    } else {
+                        "text/plain"
+                    };
+                    Response::full(status, content_type, body.as_bytes().to_vec())
+                }
+                None => Response::json(
+                    status,
+                    &json!({
+                        "error": {
+                            "message": api_error.message,
+                            "type": api_error.r#type,
+                            "param": api_error.param,
+                            "code": api_error.code,
+                        }
+                    }),
+                ),
+            }
+        }
+        OpenAIError::InvalidArgument(_) | OpenAIError::InvalidField(_) => {
+            self::error(400, "invalid_request_error", &error.to_string())
+        }
+        _ => self::error(502, "server_error", &error.to_string()),
+    }
+}
+
+fn no_upstream_answered(last_error: Option<OpenAIError>) -> Response {
+    match last_error {
+        Some(e) => upstream_error(&e),
+        None => error(502, "server_error", "no upstream configured"),
+    }
+}
+
+fn error_body(r#type: &str, message: &str) -> Value {
+    json!({"error": {"message": message, "type": r#type, "param": null, "code": null}})
+}
+
+fn error(status: u16, r#type: &str, message: &str) -> Response {
+    Response::json(status, &error_body(r#type, message))
+}
+
+fn event(data: &Value) -> Vec<u8> {
+    format!("data: {data}\n\n").into_bytes()
+}
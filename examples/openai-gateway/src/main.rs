@@ -0,0 +1,39 @@
+//! Serves the gateway on `GATEWAY_ADDR`, `127.0.0.1:8080` by default, in front of the OpenAI API configured by
+//! `OPENAI_API_KEY`, and of an optional fallback OpenAI compatible provider configured by
+//! `GATEWAY_FALLBACK_API_BASE` and `GATEWAY_FALLBACK_API_KEY`. `GATEWAY_TOKEN_BUDGET` limits the tokens spent,
+//! and `GATEWAY_MAX_BODY_BYTES` the size of request bodies.
+use std::sync::Arc;
+
+use async_openai_wasm::{config::OpenAIConfig, Client};
+use openai_gateway::{Budget, Gateway};
+use tokio::net::TcpListener;
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let mut upstreams = vec![Client::new()];
+    if let Ok(api_base) = std::env::var("GATEWAY_FALLBACK_API_BASE") {
+        let api_key = std::env::var("GATEWAY_FALLBACK_API_KEY").unwrap_or_default();
+        upstreams.push(Client::with_config(
+            OpenAIConfig::new()
+                .with_api_base(api_base)
+                .with_api_key(api_key),
+        ));
+    }
+    let budget = std::env::var("GATEWAY_TOKEN_BUDGET")
+        .ok()
+        .and_then(|budget| budget.parse().ok());
+
+    let max_body_bytes = std::env::var("GATEWAY_MAX_BODY_BYTES")
+        .ok()
+        .and_then(|max_body_bytes| max_body_bytes.parse().ok())
+        .unwrap_or(openai_gateway::DEFAULT_MAX_BODY_BYTES);
+
+    let addr = std::env::var("GATEWAY_ADDR").unwrap_or_else(|_| "127.0.0.1:8080".into());
+    let listener = TcpListener::bind(&addr).await?;
+    tracing::info!("gateway listening on {addr}");
+    Arc::new(Gateway::new(upstreams, Budget::new(budget)).with_max_body_bytes(max_body_bytes))
+        .serve(listener)
+        .await
+}
@@ -0,0 +1,240 @@
+use std::sync::Arc;
+
+use async_openai_wasm::{
+    config::OpenAIConfig,
+    error::OpenAIError,
+    retry::RetryPolicy,
+    testing::{MockResponse, MockServer},
+    types::{
+        ChatCompletionRequestUserMessageArgs, ChatCompletionStreamExt, CreateChatCompletionRequest,
+        CreateChatCompletionRequestArgs,
+    },
+    Client,
+};
+use openai_gateway::{Budget, Gateway};
+use serde_json::json;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Client of a gateway in front of `upstreams`, with a budget of `budget` tokens
+async fn gateway(
+    upstreams: &[&MockServer],
+    budget: Option<u64>,
+) -> (Client<OpenAIConfig>, Arc<Gateway>) {
+    let upstreams = upstreams
+        .iter()
+        .map(|server| server.client().with_retry_policy(RetryPolicy::none()))
+        .collect();
+    let gateway = Arc::new(Gateway::new(upstreams, Budget::new(budget)));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let config =
+        OpenAIConfig::new().with_api_base(format!("http://{}/v1", listener.local_addr().unwrap()));
+    tokio::spawn(gateway.clone().serve(listener));
+    (
+        Client::with_config(config).with_retry_policy(RetryPolicy::none()),
+        gateway,
+    )
+}
+
+fn request() -> CreateChatCompletionRequest {
+    CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o-mini")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello")
+            .build()
+            .unwrap()
+            .into()])
+        .temperature(0.0)
+        .build()
+        .unwrap()
+}
+
+fn usage(total_tokens: u32) -> serde_json::Value {
+    json!({"prompt_tokens": total_tokens - 2, "completion_tokens": 2, "total_tokens": total_tokens})
+}
+
+fn completion(content: &str) -> MockResponse {
+    MockResponse::json(json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion",
+        "created": 0,
+        "model": "gpt-4o-mini",
+        "choices": [{
+            "index": 0,
+            "message": {"role": "assistant", "content": content},
+            "finish_reason": "stop",
+        }],
+        "usage": usage(10),
+    }))
+}
+
+#[tokio::test]
+async fn completions_fall_back_and_are_cached() {
+    let primary = MockServer::start();
+    primary.mock(
+        "POST",
+        "/chat/completions",
+        MockResponse::error(503, "server_error", "overloaded"),
+    );
+    let fallback = MockServer::start();
+    fallback.mock("POST", "/chat/completions", completion("Hi!"));
+    let (client, gateway) = gateway(&[&primary, &fallback], Some(100)).await;
+
+    for _ in 0..2 {
+        let response = client.chat().create(request()).await.unwrap();
+        assert_eq!(response.first_text(), Some("Hi!"));
+    }
+
+    assert_eq!(primary.requests().len(), 1);
+    // the second request was answered from the cache
+    assert_eq!(fallback.requests().len(), 1);
+    assert_eq!(gateway.budget().remaining(), Some(90));
+}
+
+#[tokio::test]
+async fn streams_are_forwarded_and_charged() {
+    let chunk = |choices: serde_json::Value| {
+        json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion.chunk",
+            "created": 0,
+            "model": "gpt-4o-mini",
+            "choices": choices,
+        })
+    };
+    let mut usage_chunk = chunk(json!([]));
+    usage_chunk["usage"] = usage(12);
+    let upstream = MockServer::start();
+    upstream.mock(
+        "POST",
+        "/chat/completions",
+        MockResponse::sse([
+            chunk(json!([{"index": 0, "delta": {"role": "assistant", "content": "Hi"}, "finish_reason": null}])),
+            chunk(json!([{"index": 0, "delta": {"content": " there"}, "finish_reason": "stop"}])),
+            usage_chunk,
+        ]),
+    );
+    let (client, gateway) = gateway(&[&upstream], Some(100)).await;
+
+    let response = client
+        .chat()
+        .create_stream(request())
+        .await
+        .unwrap()
+        .accumulate()
+        .await
+        .unwrap()
+        .into_response();
+
+    assert_eq!(response.first_text(), Some("Hi there"));
+    // usage was requested upstream to charge the budget, but not forwarded to the client which did not ask for it
+    assert_eq!(response.usage, None);
+    assert_eq!(
+        upstream.requests()[0].json()["stream_options"],
+        json!({"include_usage": true})
+    );
+    assert_eq!(gateway.budget().remaining(), Some(88));
+}
+
+#[tokio::test]
+async fn exhausted_budgets_and_malformed_requests_are_rejected() {
+    let upstream = MockServer::start();
+    upstream.mock("POST", "/chat/completions", completion("Hi!"));
+    let (client, _) = gateway(&[&upstream], Some(0)).await;
+
+    let error = client.chat().create(request()).await.unwrap_err();
+    assert!(
+        matches!(error, OpenAIError::ApiError(error) if error.r#type.as_deref() == Some("insufficient_quota"))
+    );
+
+    let error = client
+        .raw()
+        .post("/chat/completions", json!({"model": "gpt-4o-mini"}))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, OpenAIError::ApiError(error) if error.message.contains("messages")));
+    assert!(upstream.requests().is_empty());
+}
+
+#[tokio::test]
+async fn oversized_requests_are_rejected_before_reading_their_body() {
+    let upstream = MockServer::start();
+    let gateway = Gateway::new(vec![upstream.client()], Budget::new(None)).with_max_body_bytes(64);
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(Arc::new(gateway).serve(listener));
+
+    // the body announced is never sent
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    stream
+        .write_all(b"POST /v1/chat/completions HTTP/1.1\r\ncontent-length: 1000000000000\r\n\r\n")
+        .await
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+
+    assert!(response.starts_with("HTTP/1.1 413 Payload Too Large\r\n"));
+    assert!(response.contains("exceeds the maximum of 64 bytes"));
+    assert!(upstream.requests().is_empty());
+}
+
+#[tokio::test]
+async fn upstream_errors_keep_their_status_and_body() {
+    let upstream = MockServer::start();
+    upstream
+        .mock(
+            "POST",
+            "/chat/completions",
+            MockResponse::error(401, "invalid_request_error", "Incorrect API key provided"),
+        )
+        .mock(
+            "POST",
+            "/chat/completions",
+            MockResponse::bytes("upstream overloaded").with_status(503),
+        );
+    let (client, _) = gateway(&[&upstream], None).await;
+
+    let error = client.chat().create(request()).await.unwrap_err();
+    let OpenAIError::ApiError(error) = error else {
+        panic!("expected an API error, got {error:?}");
+    };
+    assert_eq!(error.status.map(|status| status.as_u16()), Some(401));
+    assert_eq!(error.message, "Incorrect API key provided");
+
+    let error = client.chat().create(request()).await.unwrap_err();
+    let OpenAIError::ApiError(error) = error else {
+        panic!("expected an API error, got {error:?}");
+    };
+    assert_eq!(error.status.map(|status| status.as_u16()), Some(503));
+    assert_eq!(error.body(), Some("upstream overloaded"));
+}
+
+#[tokio::test]
+async fn unreachable_upstreams_are_bad_gateways() {
+    // nothing listens on the port of a dropped listener
+    let addr = TcpListener::bind("127.0.0.1:0")
+        .await
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let upstream =
+        Client::with_config(OpenAIConfig::new().with_api_base(format!("http://{addr}/v1")))
+            .with_retry_policy(RetryPolicy::none());
+    let gateway = Gateway::new(vec![upstream], Budget::new(None));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let config =
+        OpenAIConfig::new().with_api_base(format!("http://{}/v1", listener.local_addr().unwrap()));
+    tokio::spawn(Arc::new(gateway).serve(listener));
+
+    let error = Client::with_config(config)
+        .with_retry_policy(RetryPolicy::none())
+        .chat()
+        .create(request())
+        .await
+        .unwrap_err();
+    let OpenAIError::ApiError(error) = error else {
+        panic!("expected an API error, got {error:?}");
+    };
+    assert_eq!(error.status.map(|status| status.as_u16()), Some(502));
+    assert_eq!(error.r#type.as_deref(), Some("server_error"));
+}
@@ -42,7 +42,7 @@ pub fn App() -> Element {
                 match chunk {
                     Ok(response) =>
                         response_string.with_mut(|string| {
-                            if let Some(content) = response.choices[0].delta.content.as_ref() {
+                            if let Some(content) = response.first_text() {
                                 info!("Response chunk: {:?}", content);
                                 string.push_str(content);
                             }